@@ -92,6 +92,10 @@ pub async fn launch_from_config<E: RethCliExt>(
             let builder = NodeBuilderWithDatabase { config, db, data_dir };
             builder.launch::<E>(ext, executor).await
         }
+        DatabaseInstance::InMemory { db, data_dir } => {
+            let builder = NodeBuilderWithDatabase { config, db, data_dir };
+            builder.launch::<E>(ext, executor).await
+        }
     }
 }
 
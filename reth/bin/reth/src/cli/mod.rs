@@ -7,7 +7,8 @@ use crate::{
     },
     cli::ext::RethCliExt,
     commands::{
-        config_cmd, db, debug_cmd, import, init_cmd, node, p2p, recover, stage, test_vectors,
+        config_cmd, db, debug_cmd, import, init_cmd, node, p2p, recover, snapshot, stage,
+        test_vectors,
     },
     runner::CliRunner,
     version::{LONG_VERSION, SHORT_VERSION},
@@ -82,6 +83,7 @@ impl<Ext: RethCliExt> Cli<Ext> {
             Commands::Init(command) => runner.run_blocking_until_ctrl_c(command.execute()),
             Commands::Import(command) => runner.run_blocking_until_ctrl_c(command.execute()),
             Commands::Db(command) => runner.run_blocking_until_ctrl_c(command.execute()),
+            Commands::Snapshot(command) => runner.run_blocking_until_ctrl_c(command.execute()),
             Commands::Stage(command) => runner.run_blocking_until_ctrl_c(command.execute()),
             Commands::P2P(command) => runner.run_until_ctrl_c(command.execute()),
             Commands::TestVectors(command) => runner.run_until_ctrl_c(command.execute()),
@@ -131,6 +133,9 @@ pub enum Commands<Ext: RethCliExt = ()> {
     /// Database debugging utilities
     #[command(name = "db")]
     Db(db::Command),
+    /// Export and import snapshot bundles
+    #[command(name = "snapshot")]
+    Snapshot(snapshot::Command),
     /// Manipulate individual stages.
     #[command(name = "stage")]
     Stage(stage::Command),
@@ -0,0 +1,46 @@
+//! `reth db compact` command.
+
+use clap::Parser;
+use human_bytes::human_bytes;
+use reth_db::{
+    mdbx::{CopyFlags, DATA_FILE_NAME},
+    version::create_db_version_file,
+    DatabaseEnv,
+};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+use tracing::info;
+
+/// The arguments for the `reth db compact` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// The directory to write the compacted database to. It must be empty or not exist.
+    #[arg(long, value_name = "PATH")]
+    to: PathBuf,
+}
+
+impl Command {
+    /// Execute `db compact` command
+    ///
+    /// The copy is made from a read transaction, so the node can keep running meanwhile.
+    pub fn execute(self, db: &DatabaseEnv, db_path: &Path) -> eyre::Result<()> {
+        if self.to.exists() && fs::read_dir(&self.to)?.next().is_some() {
+            eyre::bail!("output directory {} is not empty", self.to.display())
+        }
+        fs::create_dir_all(&self.to)?;
+
+        let size = fs::metadata(db_path.join(DATA_FILE_NAME))?.len();
+        info!(target: "reth::cli", path = ?self.to, size = human_bytes(size as f64), "Compacting database");
+
+        let data_path = self.to.join(DATA_FILE_NAME);
+        db.copy(&data_path, CopyFlags::COMPACT | CopyFlags::FORCE_DYNAMIC_SIZE)?;
+        create_db_version_file(&self.to)?;
+
+        let compacted_size = fs::metadata(&data_path)?.len();
+        info!(target: "reth::cli", path = ?self.to, size = human_bytes(compacted_size as f64), "Compacted database");
+
+        Ok(())
+    }
+}
@@ -1,42 +1,84 @@
 use crate::{
     args::DatabaseArgs,
     dirs::{DataDirPath, PlatformPath},
-    utils::DbTool,
 };
 use clap::Parser;
 use reth_db::{
-    cursor::DbCursorRO, database::Database, mdbx::DatabaseArguments, open_db_read_only,
-    table::Table, transaction::DbTx, AccountChangeSet, AccountHistory, AccountsTrie,
-    BlockBodyIndices, BlockOmmers, BlockWithdrawals, Bytecodes, CanonicalHeaders, DatabaseEnv,
-    HashedAccount, HashedStorage, HeaderNumbers, HeaderTD, Headers, PlainAccountState,
-    PlainStorageState, PruneCheckpoints, Receipts, StorageChangeSet, StorageHistory, StoragesTrie,
-    SyncStage, SyncStageProgress, Tables, TransactionBlock, Transactions, TxHashNumber, TxSenders,
+    cursor::DbCursorRO,
+    database::Database,
+    mdbx::DatabaseArguments,
+    models::BlockNumberAddress,
+    open_db_read_only,
+    snapshot::{iter_snapshots, SnapshotCursor},
+    table::{Decode, Decompress, DupSort, Encode, Key, Table},
+    tables,
+    transaction::DbTx,
+    DatabaseEnv, RawKey, RawTable, TableViewer, Tables,
 };
+use reth_nippy_jar::NippyJar;
+use reth_primitives::{snapshot::SegmentHeader, Address, BlockNumber, SnapshotSegment, TxNumber};
+use serde::Serialize;
 use std::{
+    cmp::Ordering,
     collections::HashMap,
-    fmt::Debug,
     fs::{self, File},
-    hash::Hash,
-    io::Write,
+    io::{BufWriter, Write},
+    marker::PhantomData,
+    ops::{Bound, RangeInclusive},
     path::{Path, PathBuf},
+    sync::Arc,
 };
 use tracing::info;
 
+/// Name of the JSON report in the output directory.
+const REPORT_FILE_NAME: &str = "report.json";
+
+/// The sorted block and transaction ranges of the snapshots of each segment.
+type SnapshotRanges =
+    HashMap<SnapshotSegment, Vec<(RangeInclusive<BlockNumber>, RangeInclusive<TxNumber>)>>;
+
 #[derive(Parser, Debug)]
 /// The arguments for the `reth db diff` command
 pub struct Command {
     /// The path to the data dir for all reth files and subdirectories.
-    #[arg(long, verbatim_doc_comment)]
-    secondary_datadir: PlatformPath<DataDirPath>,
+    #[arg(long, verbatim_doc_comment, required_unless_present = "snapshots")]
+    secondary_datadir: Option<PlatformPath<DataDirPath>>,
 
     /// Arguments for the second database
     #[clap(flatten)]
     second_db: DatabaseArgs,
 
+    /// Diffs the tables against the snapshots of the datadir instead of a second database.
+    ///
+    /// Only the tables that are snapshotted are diffed, over the ranges covered by their
+    /// snapshots.
+    #[arg(long, conflicts_with = "secondary_datadir")]
+    snapshots: bool,
+
     /// The table name to diff. If not specified, all tables are diffed.
     #[arg(long, verbatim_doc_comment)]
     table: Option<Tables>,
 
+    /// The first block to diff, for the tables keyed by block number
+    #[arg(long, value_name = "BLOCK")]
+    from_block: Option<BlockNumber>,
+
+    /// The last block to diff, for the tables keyed by block number
+    #[arg(long, value_name = "BLOCK")]
+    to_block: Option<BlockNumber>,
+
+    /// The first transaction to diff, for the tables keyed by transaction number.
+    ///
+    /// Defaults to the first transaction of `--from-block`.
+    #[arg(long, value_name = "TX_NUMBER")]
+    from_tx: Option<TxNumber>,
+
+    /// The last transaction to diff, for the tables keyed by transaction number.
+    ///
+    /// Defaults to the last transaction of `--to-block`.
+    #[arg(long, value_name = "TX_NUMBER")]
+    to_tx: Option<TxNumber>,
+
     /// The output directory for the diff report.
     #[arg(long, verbatim_doc_comment)]
     output: PlatformPath<PathBuf>,
@@ -46,366 +88,734 @@ impl Command {
     /// Execute the `db diff` command.
     ///
     /// This first opens the `db/` folder from the secondary datadir, where the second database is
-    /// opened read-only.
+    /// opened read-only. With `--snapshots`, the snapshots of the primary datadir are used
+    /// instead.
     ///
-    /// The tool will then iterate through all key-value pairs for the primary and secondary
-    /// databases. The value for each key will be compared with its corresponding value in the
-    /// other database. If the values are different, a discrepancy will be recorded in-memory. If
-    /// one key is present in one database but not the other, this will be recorded as an "extra
-    /// element" for that database.
+    /// The tool will then walk the entries of each table in both sources at the same time, in
+    /// key order. Entries with the same key but different values are recorded as discrepancies,
+    /// and entries that are only in one of the sources are recorded as such. Tables keyed by
+    /// block or transaction number can be limited to a range, and the other tables are skipped
+    /// if a range is set.
     ///
-    /// The discrepancies and extra elements, along with a brief summary of the diff results are
-    /// then written to a file in the output directory.
-    pub fn execute(self, tool: &DbTool<'_, DatabaseEnv>) -> eyre::Result<()> {
-        // open second db
-        let second_db_path: PathBuf = self.secondary_datadir.join("db").into();
-        let second_db = open_db_read_only(
-            &second_db_path,
-            DatabaseArguments::default().log_level(self.second_db.log_level),
-        )?;
+    /// The differences of each table are written to a file in the output directory, along with a
+    /// JSON report of the counts and the first differing key of every table.
+    pub fn execute(
+        self,
+        db: &DatabaseEnv,
+        db_path: &Path,
+        snapshots_path: &Path,
+    ) -> eyre::Result<()> {
+        let secondary = match &self.secondary_datadir {
+            Some(datadir) => {
+                // open second db
+                let path: PathBuf = datadir.join("db").into();
+                let db = open_db_read_only(
+                    &path,
+                    DatabaseArguments::default().log_level(self.second_db.log_level),
+                )?;
+                Secondary::Database { db, path }
+            }
+            None => Secondary::Snapshots {
+                ranges: iter_snapshots(snapshots_path)?,
+                path: snapshots_path.to_path_buf(),
+            },
+        };
+
+        let primary_tx = db.tx()?;
+        let (block_range, tx_range) = self.ranges(&primary_tx)?;
 
         let tables = match self.table {
             Some(table) => vec![table],
             None => Tables::ALL.to_vec(),
         };
 
+        fs::create_dir_all(&self.output)?;
+        let mut reports = Vec::new();
         for table in tables {
-            let primary_tx = tool.db.tx()?;
-            let secondary_tx = second_db.tx()?;
-
-            let output_dir = self.output.clone();
-            match table {
-                Tables::CanonicalHeaders => {
-                    find_diffs::<CanonicalHeaders>(primary_tx, secondary_tx, output_dir)?
-                }
-                Tables::HeaderTD => find_diffs::<HeaderTD>(primary_tx, secondary_tx, output_dir)?,
-                Tables::HeaderNumbers => {
-                    find_diffs::<HeaderNumbers>(primary_tx, secondary_tx, output_dir)?
-                }
-                Tables::Headers => find_diffs::<Headers>(primary_tx, secondary_tx, output_dir)?,
-                Tables::BlockBodyIndices => {
-                    find_diffs::<BlockBodyIndices>(primary_tx, secondary_tx, output_dir)?
+            let skip = |reason: &str| -> eyre::Result<()> {
+                if self.table.is_some() {
+                    eyre::bail!("cannot diff table {table}: {reason}")
                 }
-                Tables::BlockOmmers => {
-                    find_diffs::<BlockOmmers>(primary_tx, secondary_tx, output_dir)?
-                }
-                Tables::BlockWithdrawals => {
-                    find_diffs::<BlockWithdrawals>(primary_tx, secondary_tx, output_dir)?
-                }
-                Tables::TransactionBlock => {
-                    find_diffs::<TransactionBlock>(primary_tx, secondary_tx, output_dir)?
-                }
-                Tables::Transactions => {
-                    find_diffs::<Transactions>(primary_tx, secondary_tx, output_dir)?
-                }
-                Tables::TxHashNumber => {
-                    find_diffs::<TxHashNumber>(primary_tx, secondary_tx, output_dir)?
-                }
-                Tables::Receipts => find_diffs::<Receipts>(primary_tx, secondary_tx, output_dir)?,
-                Tables::PlainAccountState => {
-                    find_diffs::<PlainAccountState>(primary_tx, secondary_tx, output_dir)?
-                }
-                Tables::PlainStorageState => {
-                    find_diffs::<PlainStorageState>(primary_tx, secondary_tx, output_dir)?
-                }
-                Tables::Bytecodes => find_diffs::<Bytecodes>(primary_tx, secondary_tx, output_dir)?,
-                Tables::AccountHistory => {
-                    find_diffs::<AccountHistory>(primary_tx, secondary_tx, output_dir)?
-                }
-                Tables::StorageHistory => {
-                    find_diffs::<StorageHistory>(primary_tx, secondary_tx, output_dir)?
-                }
-                Tables::AccountChangeSet => {
-                    find_diffs::<AccountChangeSet>(primary_tx, secondary_tx, output_dir)?
-                }
-                Tables::StorageChangeSet => {
-                    find_diffs::<StorageChangeSet>(primary_tx, secondary_tx, output_dir)?
-                }
-                Tables::HashedAccount => {
-                    find_diffs::<HashedAccount>(primary_tx, secondary_tx, output_dir)?
-                }
-                Tables::HashedStorage => {
-                    find_diffs::<HashedStorage>(primary_tx, secondary_tx, output_dir)?
-                }
-                Tables::AccountsTrie => {
-                    find_diffs::<AccountsTrie>(primary_tx, secondary_tx, output_dir)?
-                }
-                Tables::StoragesTrie => {
-                    find_diffs::<StoragesTrie>(primary_tx, secondary_tx, output_dir)?
-                }
-                Tables::TxSenders => find_diffs::<TxSenders>(primary_tx, secondary_tx, output_dir)?,
-                Tables::SyncStage => find_diffs::<SyncStage>(primary_tx, secondary_tx, output_dir)?,
-                Tables::SyncStageProgress => {
-                    find_diffs::<SyncStageProgress>(primary_tx, secondary_tx, output_dir)?
-                }
-                Tables::PruneCheckpoints => {
-                    find_diffs::<PruneCheckpoints>(primary_tx, secondary_tx, output_dir)?
+                info!(target: "reth::cli", %table, "Skipping table, {reason}");
+                Ok(())
+            };
+
+            let mut range = match KeyKind::of(table) {
+                Some(KeyKind::Block) => block_range.clone(),
+                Some(KeyKind::Transaction) => tx_range.clone(),
+                None if block_range.is_some() || tx_range.is_some() => {
+                    skip("it is not keyed by block or transaction number")?;
+                    continue
                 }
+                None => None,
             };
+
+            if let Secondary::Snapshots { ranges, .. } = &secondary {
+                let Some(segment) = snapshot_segment(table) else {
+                    skip("it is not snapshotted")?;
+                    continue
+                };
+                let Some(covered) = covered_range(segment, ranges) else {
+                    skip("there are no snapshots of it")?;
+                    continue
+                };
+                let (start, end) = match range {
+                    Some(range) => {
+                        ((*range.start()).max(*covered.start()), (*range.end()).min(*covered.end()))
+                    }
+                    None => covered.into_inner(),
+                };
+                if start > end {
+                    skip("the range is not snapshotted")?;
+                    continue
+                }
+                range = Some(start..=end);
+            }
+
+            info!(target: "reth::cli", %table, ?range, "Analyzing table");
+            let report = table.view(&DiffViewer {
+                table,
+                primary_tx: &primary_tx,
+                secondary: &secondary,
+                range,
+                output: self.output.as_ref(),
+            })?;
+            info!(
+                target: "reth::cli",
+                %table,
+                discrepancies = report.discrepancies,
+                only_in_primary = report.only_in_primary,
+                only_in_secondary = report.only_in_secondary,
+                "Done analyzing table"
+            );
+            reports.push(report);
         }
 
+        let report = DiffReport {
+            primary: db_path.to_path_buf(),
+            secondary: secondary.path().to_path_buf(),
+            tables: reports,
+        };
+        let report_path = self.output.as_ref().join(REPORT_FILE_NAME);
+        serde_json::to_writer_pretty(BufWriter::new(File::create(&report_path)?), &report)?;
+        info!(target: "reth::cli", path = %report_path.display(), "Wrote diff report");
+
         Ok(())
     }
+
+    /// Returns the block and transaction ranges to diff, if limited.
+    ///
+    /// The transaction range is derived from the block range of the primary database if it's not
+    /// set explicitly.
+    fn ranges(
+        &self,
+        tx: &impl DbTx,
+    ) -> eyre::Result<(Option<RangeInclusive<BlockNumber>>, Option<RangeInclusive<TxNumber>>)> {
+        let block_range = (self.from_block.is_some() || self.to_block.is_some()).then(|| {
+            self.from_block.unwrap_or_default()..=self.to_block.unwrap_or(BlockNumber::MAX)
+        });
+
+        let body_indices = |block: BlockNumber| {
+            tx.get::<tables::BlockBodyIndices>(block)?
+                .ok_or_else(|| eyre::eyre!("block {block} has no body indices"))
+        };
+        let from_tx = match (self.from_tx, self.from_block) {
+            (Some(from_tx), _) => Some(from_tx),
+            (None, Some(from_block)) => Some(body_indices(from_block)?.first_tx_num),
+            (None, None) => None,
+        };
+        let to_tx = match (self.to_tx, self.to_block) {
+            (Some(to_tx), _) => Some(to_tx),
+            (None, Some(to_block)) => Some(body_indices(to_block)?.last_tx_num()),
+            (None, None) => None,
+        };
+        let tx_range = (from_tx.is_some() || to_tx.is_some())
+            .then(|| from_tx.unwrap_or_default()..=to_tx.unwrap_or(TxNumber::MAX));
+
+        Ok((block_range, tx_range))
+    }
 }
 
-/// Find diffs for a table, then analyzing the result
-fn find_diffs<T: Table>(
-    primary_tx: impl DbTx,
-    secondary_tx: impl DbTx,
-    output_dir: impl AsRef<Path>,
-) -> eyre::Result<()>
-where
-    T::Key: Hash,
-    T::Value: PartialEq,
-{
-    let table_name = T::NAME;
+/// The source the primary database is diffed against.
+enum Secondary {
+    /// A second database.
+    Database {
+        /// The opened database.
+        db: DatabaseEnv,
+        /// The path of the database.
+        path: PathBuf,
+    },
+    /// The snapshots of the primary database.
+    Snapshots {
+        /// The block and transaction ranges of the snapshots of each segment, sorted.
+        ranges: SnapshotRanges,
+        /// The snapshots directory.
+        path: PathBuf,
+    },
+}
 
-    info!("Analyzing table {table_name}...");
-    let result = find_diffs_advanced::<T>(&primary_tx, &secondary_tx)?;
-    info!("Done analyzing table {table_name}!");
+impl Secondary {
+    /// Returns the path of the source.
+    fn path(&self) -> &Path {
+        match self {
+            Self::Database { path, .. } | Self::Snapshots { path, .. } => path,
+        }
+    }
+}
 
-    // Pretty info summary header: newline then header
-    info!("");
-    info!("Diff results for {table_name}:");
+/// The kind of number the keys of a table start with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum KeyKind {
+    /// The keys start with a block number.
+    Block,
+    /// The keys are transaction numbers.
+    Transaction,
+}
 
-    // create directory and open file
-    fs::create_dir_all(output_dir.as_ref())?;
-    let file_name = format!("{table_name}.txt");
-    let mut file = File::create(output_dir.as_ref().join(file_name.clone()))?;
+impl KeyKind {
+    /// Returns the kind of number the keys of the table start with, if any.
+    fn of(table: Tables) -> Option<Self> {
+        match table {
+            Tables::CanonicalHeaders |
+            Tables::HeaderTD |
+            Tables::Headers |
+            Tables::BlockBodyIndices |
+            Tables::BlockOmmers |
+            Tables::BlockWithdrawals |
+            Tables::AccountChangeSet |
+            Tables::StorageChangeSet => Some(Self::Block),
+            Tables::TransactionBlock |
+            Tables::Transactions |
+            Tables::Receipts |
+            Tables::TxSenders => Some(Self::Transaction),
+            _ => None,
+        }
+    }
+}
 
-    // analyze the result and print some stats
-    let discrepancies = result.discrepancies.len();
-    let extra_elements = result.extra_elements.len();
+/// Returns the snapshot segment that holds the rows of the table, if any.
+fn snapshot_segment(table: Tables) -> Option<SnapshotSegment> {
+    match table {
+        Tables::CanonicalHeaders | Tables::HeaderTD | Tables::Headers => {
+            Some(SnapshotSegment::Headers)
+        }
+        Tables::Transactions => Some(SnapshotSegment::Transactions),
+        Tables::Receipts => Some(SnapshotSegment::Receipts),
+        Tables::TxSenders => Some(SnapshotSegment::TransactionSenders),
+        Tables::AccountChangeSet => Some(SnapshotSegment::AccountChangesets),
+        Tables::StorageChangeSet => Some(SnapshotSegment::StorageChangesets),
+        _ => None,
+    }
+}
 
-    // Make a pretty summary header for the table
-    writeln!(file, "Diff results for {table_name}")?;
+/// Returns the column of the table in the rows of its snapshot segment.
+fn snapshot_column(table: Tables) -> usize {
+    match table {
+        Tables::HeaderTD => 1,
+        Tables::CanonicalHeaders => 2,
+        _ => 0,
+    }
+}
 
-    if discrepancies > 0 {
-        // write to file
-        writeln!(file, "Found {discrepancies} discrepancies in table {table_name}")?;
+/// Returns whether the rows of the segment are numbered by transaction instead of block.
+fn is_tx_segment(segment: SnapshotSegment) -> bool {
+    matches!(
+        segment,
+        SnapshotSegment::Transactions |
+            SnapshotSegment::Receipts |
+            SnapshotSegment::TransactionSenders
+    )
+}
 
-        // also print to info
-        info!("Found {discrepancies} discrepancies in table {table_name}");
+/// Returns the block or transaction range of a snapshot of the segment.
+fn snapshot_range(
+    segment: SnapshotSegment,
+    (block_range, tx_range): &(RangeInclusive<BlockNumber>, RangeInclusive<TxNumber>),
+) -> &RangeInclusive<u64> {
+    if is_tx_segment(segment) {
+        tx_range
     } else {
-        // write to file
-        writeln!(file, "No discrepancies found in table {table_name}")?;
-
-        // also print to info
-        info!("No discrepancies found in table {table_name}");
+        block_range
     }
+}
 
-    if extra_elements > 0 {
-        // write to file
-        writeln!(file, "Found {extra_elements} extra elements in table {table_name}")?;
+/// Returns the range covered by the snapshots of the segment, if any.
+fn covered_range(segment: SnapshotSegment, ranges: &SnapshotRanges) -> Option<RangeInclusive<u64>> {
+    let ranges = ranges.get(&segment)?;
+    let first = snapshot_range(segment, ranges.first()?);
+    let last = snapshot_range(segment, ranges.last()?);
+    Some(*first.start()..=*last.end())
+}
 
-        // also print to info
-        info!("Found {extra_elements} extra elements in table {table_name}");
-    } else {
-        writeln!(file, "No extra elements found in table {table_name}")?;
+struct DiffViewer<'a, TX: DbTx> {
+    table: Tables,
+    primary_tx: &'a TX,
+    secondary: &'a Secondary,
+    range: Option<RangeInclusive<u64>>,
+    output: &'a Path,
+}
 
-        // also print to info
-        info!("No extra elements found in table {table_name}");
-    }
+impl<TX: DbTx> DiffViewer<'_, TX> {
+    /// Diffs the table, comparing the values of the same key for non-dupsort tables, and the
+    /// whole entries for dupsort tables.
+    fn diff<T: Table>(&self, dupsort: bool) -> eyre::Result<TableReport> {
+        let file_path = self.output.join(format!("{}.txt", T::NAME));
+        let mut file = BufWriter::new(File::create(&file_path)?);
+        writeln!(file, "Diff results for {}", T::NAME)?;
+
+        let mut primary_cursor = self.primary_tx.cursor_read::<RawTable<T>>()?;
+        let primary = walk_raw::<T, _>(&mut primary_cursor, &self.range)?;
+        let mut diff = TableDiff::<T, _, _>::new(primary, dupsort, &mut file)?;
+
+        match self.secondary {
+            Secondary::Database { db, .. } => {
+                let secondary_tx = db.tx()?;
+                let mut secondary_cursor = secondary_tx.cursor_read::<RawTable<T>>()?;
+                for row in walk_raw::<T, _>(&mut secondary_cursor, &self.range)? {
+                    diff.push_secondary(row?)?;
+                }
+            }
+            Secondary::Snapshots { ranges, path } => {
+                let range = self.range.as_ref().expect("snapshot diffs are limited to a range");
+                read_snapshot_rows(self.table, path, ranges, range, |row| {
+                    diff.push_secondary(row)
+                })?;
+            }
+        }
 
-    info!("Writing diff results for {table_name} to {file_name}...");
+        let mut report = diff.finish()?;
+        report.range = self.range.clone();
+        writeln!(
+            file,
+            "Found {} discrepancies, {} entries only in the primary database and {} entries only \
+             in the secondary source",
+            report.discrepancies, report.only_in_primary, report.only_in_secondary
+        )?;
+        file.flush()?;
 
-    if discrepancies > 0 {
-        writeln!(file, "Discrepancies:")?;
+        info!(target: "reth::cli", table = T::NAME, path = %file_path.display(), "Wrote diff results");
+        Ok(report)
     }
+}
 
-    for discrepancy in result.discrepancies.values() {
-        writeln!(file, "{discrepancy:?}")?;
-    }
+impl<TX: DbTx> TableViewer<TableReport> for DiffViewer<'_, TX> {
+    type Error = eyre::Report;
 
-    if extra_elements > 0 {
-        writeln!(file, "Extra elements:")?;
+    fn view<T: Table>(&self) -> Result<TableReport, Self::Error> {
+        self.diff::<T>(false)
     }
 
-    for extra_element in result.extra_elements.values() {
-        writeln!(file, "{extra_element:?}")?;
+    fn view_dupsort<T: DupSort>(&self) -> Result<TableReport, Self::Error> {
+        self.diff::<T>(true)
     }
+}
 
-    let full_file_name = output_dir.as_ref().join(file_name);
-    info!("Done writing diff results for {table_name} to {}", full_file_name.display());
-    Ok(())
+/// Raw key and value of an entry.
+type RawRow = (Vec<u8>, Vec<u8>);
+
+/// Walks the raw entries of the table whose keys start with a number in the range, if any.
+fn walk_raw<'a, T: Table, C: DbCursorRO<RawTable<T>>>(
+    cursor: &'a mut C,
+    range: &Option<RangeInclusive<u64>>,
+) -> eyre::Result<impl Iterator<Item = eyre::Result<RawRow>> + 'a> {
+    Ok(cursor.walk_range(key_range::<T::Key>(range))?.map(|row| {
+        let (key, value) = row?;
+        Ok((key.into_key(), value.into_value()))
+    }))
 }
 
-/// This diff algorithm is slightly different, it will walk _each_ table, cross-checking for the
-/// element in the other table.
-fn find_diffs_advanced<T: Table>(
-    primary_tx: &impl DbTx,
-    secondary_tx: &impl DbTx,
-) -> eyre::Result<TableDiffResult<T>>
-where
-    T::Value: PartialEq,
-    T::Key: Hash,
-{
-    // initialize the zipped walker
-    let mut primary_zip_cursor =
-        primary_tx.cursor_read::<T>().expect("Was not able to obtain a cursor.");
-    let primary_walker = primary_zip_cursor.walk(None)?;
-
-    let mut secondary_zip_cursor =
-        secondary_tx.cursor_read::<T>().expect("Was not able to obtain a cursor.");
-    let secondary_walker = secondary_zip_cursor.walk(None)?;
-    let zipped_cursor = primary_walker.zip(secondary_walker);
-
-    // initialize the cursors for seeking when we are cross checking elements
-    let mut primary_cursor =
-        primary_tx.cursor_read::<T>().expect("Was not able to obtain a cursor.");
-
-    let mut secondary_cursor =
-        secondary_tx.cursor_read::<T>().expect("Was not able to obtain a cursor.");
-
-    let mut result = TableDiffResult::<T>::default();
-
-    // this loop will walk both tables, cross-checking for the element in the other table.
-    // it basically just loops through both tables at the same time. if the keys are different, it
-    // will check each key in the other table. if the keys are the same, it will compare the
-    // values
-    for (primary_entry, secondary_entry) in zipped_cursor {
-        let (primary_key, primary_value) = primary_entry?;
-        let (secondary_key, secondary_value) = secondary_entry?;
-
-        if primary_key != secondary_key {
-            // if the keys are different, we need to check if the key is in the other table
-            let crossed_secondary =
-                secondary_cursor.seek_exact(primary_key.clone())?.map(|(_, value)| value);
-            result.try_push_discrepancy(
-                primary_key.clone(),
-                Some(primary_value),
-                crossed_secondary,
-            );
+/// Returns the bounds of the raw keys that start with a number in the range.
+fn key_range<K: Key>(range: &Option<RangeInclusive<u64>>) -> (Bound<RawKey<K>>, Bound<RawKey<K>>) {
+    let Some(range) = range else { return (Bound::Unbounded, Bound::Unbounded) };
+    let prefix =
+        |number: u64| RawKey::<K>::decode(number.to_be_bytes()).expect("raw keys are not decoded");
+    let end = match range.end().checked_add(1) {
+        Some(end) => Bound::Excluded(prefix(end)),
+        None => Bound::Unbounded,
+    };
+    (Bound::Included(prefix(*range.start())), end)
+}
 
-            // now do the same for the primary table
-            let crossed_primary =
-                primary_cursor.seek_exact(secondary_key.clone())?.map(|(_, value)| value);
-            result.try_push_discrepancy(
-                secondary_key.clone(),
-                crossed_primary,
-                Some(secondary_value),
-            );
-        } else {
-            // the keys are the same, so we need to compare the values
-            result.try_push_discrepancy(primary_key, Some(primary_value), Some(secondary_value));
+/// Reads the raw entries of the table in the range from the snapshots of its segment, in key
+/// order.
+fn read_snapshot_rows(
+    table: Tables,
+    snapshots_path: &Path,
+    ranges: &SnapshotRanges,
+    range: &RangeInclusive<u64>,
+    mut f: impl FnMut(RawRow) -> eyre::Result<()>,
+) -> eyre::Result<()> {
+    let segment = snapshot_segment(table).expect("table is snapshotted");
+    let column = snapshot_column(table);
+    let Some(segment_ranges) = ranges.get(&segment) else { return Ok(()) };
+
+    for ranges in segment_ranges {
+        let snapshot_range = snapshot_range(segment, ranges);
+        if snapshot_range.end() < range.start() || snapshot_range.start() > range.end() {
+            continue
+        }
+
+        let (block_range, tx_range) = ranges;
+        let jar = NippyJar::<SegmentHeader>::load(
+            &snapshots_path.join(segment.filename(block_range, tx_range)),
+        )?;
+        let mut cursor = SnapshotCursor::new(&jar, Arc::new(jar.open_data_reader()?))?;
+
+        match segment {
+            SnapshotSegment::AccountChangesets | SnapshotSegment::StorageChangesets => {
+                let mask = if segment == SnapshotSegment::AccountChangesets { 0b11 } else { 0b111 };
+                let rows = std::iter::from_fn(|| match cursor.next_row_with_cols(mask) {
+                    Ok(Some(row)) => Some(decode_changeset_row(segment, &row)),
+                    Ok(None) => None,
+                    Err(err) => Some(Err(err.into())),
+                })
+                .filter(|row| row.as_ref().map_or(true, |(block, _)| range.contains(block)));
+                push_changeset_rows(rows, &mut f)?;
+            }
+            // Other rows are numbered by block or transaction.
+            _ => {
+                let start = (*range.start()).max(*snapshot_range.start());
+                let end = (*range.end()).min(*snapshot_range.end());
+                for number in start..=end {
+                    let row_number = (number - snapshot_range.start()) as usize;
+                    let Some(row) = cursor.row_by_number_with_cols(row_number, 1 << column)? else {
+                        break
+                    };
+                    f((number.to_be_bytes().to_vec(), row[0].to_vec()))?;
+                }
+            }
         }
     }
 
-    Ok(result)
+    Ok(())
 }
 
-/// Includes a table element between two databases with the same key, but different values
-#[derive(Debug)]
-struct TableDiffElement<T: Table> {
-    /// The key for the element
-    key: T::Key,
+/// Decodes a changeset row of the snapshot into the block number and the raw entry of the table.
+///
+/// The block number is in the second column of the row, and the address of storage changesets in
+/// the third column.
+fn decode_changeset_row(
+    segment: SnapshotSegment,
+    row: &[&[u8]],
+) -> eyre::Result<(BlockNumber, RawRow)> {
+    let block_number = BlockNumber::decompress(row[1])?;
+    let key = match segment {
+        SnapshotSegment::StorageChangesets => {
+            BlockNumberAddress((block_number, Address::decompress(row[2])?)).encode().to_vec()
+        }
+        _ => block_number.encode().to_vec(),
+    };
+    Ok((block_number, (key, row[0].to_vec())))
+}
 
-    /// The element from the first table
-    #[allow(dead_code)]
-    first: T::Value,
+/// Pushes the changeset rows of a snapshot in key order.
+///
+/// The blocks of the rows have to be ascending, but the rows of a block are sorted before they
+/// are pushed, as their order within the block is not guaranteed by the snapshot.
+fn push_changeset_rows(
+    rows: impl Iterator<Item = eyre::Result<(BlockNumber, RawRow)>>,
+    mut f: impl FnMut(RawRow) -> eyre::Result<()>,
+) -> eyre::Result<()> {
+    let mut block = None;
+    let mut block_rows = Vec::new();
+    for row in rows {
+        let (block_number, row) = row?;
+        if block != Some(block_number) {
+            eyre::ensure!(
+                block < Some(block_number),
+                "changesets of block {block_number} are out of order in the snapshot"
+            );
+            block_rows.sort_unstable();
+            block_rows.drain(..).try_for_each(&mut f)?;
+            block = Some(block_number);
+        }
+        block_rows.push(row);
+    }
 
-    /// The element from the second table
-    #[allow(dead_code)]
-    second: T::Value,
+    block_rows.sort_unstable();
+    block_rows.drain(..).try_for_each(&mut f)
 }
 
-/// The diff result for an entire table. If the tables had the same number of elements, there will
-/// be no extra elements.
-struct TableDiffResult<T: Table>
-where
-    T::Key: Hash,
-{
-    /// All elements of the database that are different
-    discrepancies: HashMap<T::Key, TableDiffElement<T>>,
+/// The kind of a difference between the sources.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DifferenceKind {
+    /// The entry has different values in both sources.
+    Discrepancy,
+    /// The entry is only in the primary database.
+    OnlyInPrimary,
+    /// The entry is only in the secondary source.
+    OnlyInSecondary,
+}
 
-    /// Any extra elements, and the table they are in
-    extra_elements: HashMap<T::Key, ExtraTableElement<T>>,
+/// Diff of a table, which merges the sorted entries of the primary database with the entries of
+/// the secondary source as they are pushed.
+struct TableDiff<T: Table, I, W> {
+    /// The remaining entries of the primary database.
+    primary: I,
+    /// The next entry of the primary database, if any.
+    next_primary: Option<RawRow>,
+    /// Whether entries are compared as a whole, as a key can have multiple values.
+    dupsort: bool,
+    /// The writer of the differences.
+    writer: W,
+    /// The counts and the first difference.
+    report: TableReport,
+    _table: PhantomData<T>,
 }
 
-impl<T> Default for TableDiffResult<T>
+impl<T, I, W> TableDiff<T, I, W>
 where
     T: Table,
-    T::Key: Hash,
+    I: Iterator<Item = eyre::Result<RawRow>>,
+    W: Write,
 {
-    fn default() -> Self {
-        Self { discrepancies: HashMap::new(), extra_elements: HashMap::new() }
+    fn new(mut primary: I, dupsort: bool, writer: W) -> eyre::Result<Self> {
+        let next_primary = primary.next().transpose()?;
+        Ok(Self {
+            primary,
+            next_primary,
+            dupsort,
+            writer,
+            report: TableReport::new(T::NAME),
+            _table: PhantomData,
+        })
     }
-}
 
-impl<T: Table> TableDiffResult<T>
-where
-    T::Key: Hash,
-{
-    /// Push a diff result into the discrepancies set.
-    fn push_discrepancy(&mut self, discrepancy: TableDiffElement<T>) {
-        self.discrepancies.insert(discrepancy.key.clone(), discrepancy);
+    /// Takes the next entry of the primary database.
+    fn take_primary(&mut self) -> eyre::Result<Option<RawRow>> {
+        let next = self.primary.next().transpose()?;
+        Ok(std::mem::replace(&mut self.next_primary, next))
     }
 
-    /// Push an extra element into the extra elements set.
-    fn push_extra_element(&mut self, element: ExtraTableElement<T>) {
-        self.extra_elements.insert(element.key().clone(), element);
+    /// Compares the order of the entries of both sources.
+    fn cmp(&self, primary: &RawRow, secondary: &RawRow) -> Ordering {
+        let ordering = primary.0.cmp(&secondary.0);
+        if self.dupsort {
+            ordering.then_with(|| primary.1.cmp(&secondary.1))
+        } else {
+            ordering
+        }
     }
-}
 
-impl<T> TableDiffResult<T>
-where
-    T: Table,
-    T::Key: Hash,
-    T::Value: PartialEq,
-{
-    /// Try to push a diff result into the discrepancy set, only pushing if the given elements are
-    /// different, and the discrepancy does not exist anywhere already.
-    fn try_push_discrepancy(
-        &mut self,
-        key: T::Key,
-        first: Option<T::Value>,
-        second: Option<T::Value>,
-    ) {
-        // do not bother comparing if the key is already in the discrepancies map
-        if self.discrepancies.contains_key(&key) {
-            return
+    /// Diffs the next entry of the secondary source, which has to be pushed in order.
+    fn push_secondary(&mut self, secondary: RawRow) -> eyre::Result<()> {
+        while let Some(primary) = &self.next_primary {
+            match self.cmp(primary, &secondary) {
+                Ordering::Less => {
+                    let (key, value) = self.take_primary()?.expect("entry was peeked");
+                    self.record(DifferenceKind::OnlyInPrimary, &key, Some(&value), None)?;
+                }
+                Ordering::Equal => {
+                    let (key, value) = self.take_primary()?.expect("entry was peeked");
+                    if value == secondary.1 {
+                        self.report.matching += 1;
+                    } else {
+                        self.record(
+                            DifferenceKind::Discrepancy,
+                            &key,
+                            Some(&value),
+                            Some(&secondary.1),
+                        )?;
+                    }
+                    return Ok(())
+                }
+                Ordering::Greater => break,
+            }
+        }
+
+        self.record(DifferenceKind::OnlyInSecondary, &secondary.0, None, Some(&secondary.1))
+    }
+
+    /// Records the remaining entries of the primary database, and returns the report.
+    fn finish(mut self) -> eyre::Result<TableReport> {
+        while let Some((key, value)) = self.take_primary()? {
+            self.record(DifferenceKind::OnlyInPrimary, &key, Some(&value), None)?;
         }
+        Ok(self.report)
+    }
 
-        // do not bother comparing if the key is already in the extra elements map
-        if self.extra_elements.contains_key(&key) {
-            return
+    /// Counts and writes a difference.
+    fn record(
+        &mut self,
+        kind: DifferenceKind,
+        key: &[u8],
+        primary: Option<&[u8]>,
+        secondary: Option<&[u8]>,
+    ) -> eyre::Result<()> {
+        match kind {
+            DifferenceKind::Discrepancy => self.report.discrepancies += 1,
+            DifferenceKind::OnlyInPrimary => self.report.only_in_primary += 1,
+            DifferenceKind::OnlyInSecondary => self.report.only_in_secondary += 1,
         }
 
-        match (first, second) {
-            (Some(first), Some(second)) => {
-                if first != second {
-                    self.push_discrepancy(TableDiffElement { key, first, second });
-                }
-            }
-            (Some(first), None) => {
-                self.push_extra_element(ExtraTableElement::First { key, value: first });
-            }
-            (None, Some(second)) => {
-                self.push_extra_element(ExtraTableElement::Second { key, value: second });
-            }
-            (None, None) => {}
+        let key = T::Key::decode(key)?;
+        if self.report.first_difference.is_none() {
+            self.report.first_difference = Some(serde_json::to_value(&key)?);
         }
+        let primary = primary.map(T::Value::decompress).transpose()?;
+        let secondary = secondary.map(T::Value::decompress).transpose()?;
+        writeln!(self.writer, "{kind:?} {key:?}: primary {primary:?}, secondary {secondary:?}")?;
+
+        Ok(())
     }
 }
 
-/// A single extra element from a table
-#[derive(Debug)]
-enum ExtraTableElement<T: Table> {
-    /// The extra element that is in the first table
-    #[allow(dead_code)]
-    First { key: T::Key, value: T::Value },
+/// The JSON report of a diff.
+#[derive(Debug, Serialize)]
+struct DiffReport {
+    /// The path of the primary database.
+    primary: PathBuf,
+    /// The path of the secondary database or snapshots.
+    secondary: PathBuf,
+    /// The reports of the diffed tables.
+    tables: Vec<TableReport>,
+}
 
-    /// The extra element that is in the second table
-    #[allow(dead_code)]
-    Second { key: T::Key, value: T::Value },
+/// The JSON report of the diff of a table.
+#[derive(Debug, Serialize)]
+struct TableReport {
+    /// The table name.
+    table: &'static str,
+    /// The range of block or transaction numbers that was diffed, if limited.
+    range: Option<RangeInclusive<u64>>,
+    /// The number of entries that are the same in both sources.
+    matching: u64,
+    /// The number of keys with different values in both sources.
+    discrepancies: u64,
+    /// The number of entries that are only in the primary database.
+    only_in_primary: u64,
+    /// The number of entries that are only in the secondary source.
+    only_in_secondary: u64,
+    /// The first key that differs, if any.
+    first_difference: Option<serde_json::Value>,
 }
 
-impl<T: Table> ExtraTableElement<T> {
-    /// Return the key for the extra element
-    fn key(&self) -> &T::Key {
-        match self {
-            Self::First { key, .. } => key,
-            Self::Second { key, .. } => key,
+impl TableReport {
+    fn new(table: &'static str) -> Self {
+        Self {
+            table,
+            range: None,
+            matching: 0,
+            discrepancies: 0,
+            only_in_primary: 0,
+            only_in_secondary: 0,
+            first_difference: None,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_db::{init_db, models::AccountBeforeTx, transaction::DbTxMut};
+    use reth_primitives::B256;
+
+    fn diff_table<T: Table>(
+        primary: &DatabaseEnv,
+        secondary: &DatabaseEnv,
+        range: Option<RangeInclusive<u64>>,
+        dupsort: bool,
+    ) -> TableReport {
+        let (primary_tx, secondary_tx) = (primary.tx().unwrap(), secondary.tx().unwrap());
+        let mut primary_cursor = primary_tx.cursor_read::<RawTable<T>>().unwrap();
+        let mut secondary_cursor = secondary_tx.cursor_read::<RawTable<T>>().unwrap();
+
+        let primary = walk_raw::<T, _>(&mut primary_cursor, &range).unwrap();
+        let mut diff = TableDiff::<T, _, _>::new(primary, dupsort, Vec::new()).unwrap();
+        for row in walk_raw::<T, _>(&mut secondary_cursor, &range).unwrap() {
+            diff.push_secondary(row.unwrap()).unwrap();
+        }
+        diff.finish().unwrap()
+    }
+
+    #[test]
+    fn diff_tables() {
+        let primary =
+            init_db(tempfile::tempdir().unwrap().into_path(), DatabaseArguments::default())
+                .unwrap();
+        let secondary =
+            init_db(tempfile::tempdir().unwrap().into_path(), DatabaseArguments::default())
+                .unwrap();
+
+        primary
+            .update(|tx| {
+                for number in 0..5 {
+                    tx.put::<tables::CanonicalHeaders>(number, B256::with_last_byte(number as u8))
+                        .unwrap();
+                }
+                for address in [Address::with_last_byte(1), Address::with_last_byte(2)] {
+                    tx.put::<tables::AccountChangeSet>(3, AccountBeforeTx { address, info: None })
+                        .unwrap();
+                }
+            })
+            .unwrap();
+        secondary
+            .update(|tx| {
+                for number in 1..6 {
+                    let hash =
+                        if number == 3 { B256::ZERO } else { B256::with_last_byte(number as u8) };
+                    tx.put::<tables::CanonicalHeaders>(number, hash).unwrap();
+                }
+                let address = Address::with_last_byte(2);
+                tx.put::<tables::AccountChangeSet>(3, AccountBeforeTx { address, info: None })
+                    .unwrap();
+            })
+            .unwrap();
+
+        let report = diff_table::<tables::CanonicalHeaders>(&primary, &secondary, None, false);
+        assert_eq!(
+            (
+                report.matching,
+                report.discrepancies,
+                report.only_in_primary,
+                report.only_in_secondary
+            ),
+            (3, 1, 1, 1)
+        );
+        assert_eq!(report.first_difference, Some(serde_json::json!(0)));
+
+        let report =
+            diff_table::<tables::CanonicalHeaders>(&primary, &secondary, Some(2..=4), false);
+        assert_eq!(
+            (
+                report.matching,
+                report.discrepancies,
+                report.only_in_primary,
+                report.only_in_secondary
+            ),
+            (2, 1, 0, 0)
+        );
+        assert_eq!(report.first_difference, Some(serde_json::json!(3)));
+
+        let report = diff_table::<tables::AccountChangeSet>(&primary, &secondary, None, true);
+        assert_eq!(
+            (
+                report.matching,
+                report.discrepancies,
+                report.only_in_primary,
+                report.only_in_secondary
+            ),
+            (1, 0, 1, 0)
+        );
+    }
+
+    #[test]
+    fn changeset_rows_are_sorted_per_block() {
+        let row = |block: BlockNumber, address: u8| {
+            let key = block.encode().to_vec();
+            Ok((block, (key, Address::with_last_byte(address).to_vec())))
+        };
+
+        let mut pushed = Vec::new();
+        push_changeset_rows([row(1, 2), row(1, 1), row(2, 3), row(2, 0)].into_iter(), |row| {
+            pushed.push(row);
+            Ok(())
+        })
+        .unwrap();
+        let addresses = pushed.iter().map(|(_, value)| value[19]).collect::<Vec<_>>();
+        assert_eq!(addresses, vec![1, 2, 0, 3]);
+
+        // blocks have to be ascending
+        assert!(push_changeset_rows([row(2, 0), row(1, 0)].into_iter(), |_| Ok(())).is_err());
+    }
+}
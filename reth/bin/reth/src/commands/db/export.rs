@@ -0,0 +1,191 @@
+//! `reth db export` command.
+
+use super::get::maybe_json_value_parser;
+use clap::{Parser, ValueEnum};
+use reth_db::{
+    cursor::DbCursorRO, database::Database, table::Table, transaction::DbTx, RawKey, RawTable,
+    TableViewer, Tables,
+};
+use reth_primitives::{hex, Bytes};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    ops::Bound,
+    path::PathBuf,
+};
+
+/// The arguments for the `reth db export` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// The table name
+    table: Tables,
+    /// The file to write the rows to. If not set, the rows are written to stdout.
+    #[arg(long, short, value_name = "FILE")]
+    output: Option<PathBuf>,
+    /// The format of the rows
+    #[arg(long, value_enum, default_value_t = Format::Jsonl)]
+    format: Format,
+    /// The first key to export, inclusive
+    #[arg(long, value_parser = maybe_json_value_parser)]
+    from: Option<String>,
+    /// The last key to export, inclusive
+    #[arg(long, value_parser = maybe_json_value_parser)]
+    to: Option<String>,
+    /// Also export the raw encoded keys and values, which `reth db import` reads
+    #[arg(long)]
+    raw: bool,
+}
+
+impl Command {
+    /// Execute `db export` command
+    pub fn execute<DB: Database>(self, db: &DB) -> eyre::Result<()> {
+        self.table.view(&ExportViewer { db, args: &self })
+    }
+
+    /// Returns the writer of the rows.
+    fn writer(&self) -> eyre::Result<BufWriter<Box<dyn Write>>> {
+        let writer: Box<dyn Write> = match &self.output {
+            Some(path) => Box::new(File::create(path)?),
+            None => Box::new(io::stdout().lock()),
+        };
+        Ok(BufWriter::new(writer))
+    }
+}
+
+/// Format of exported and imported rows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// One JSON object per line, with the `key`, `value`, `raw_key` and `raw_value` fields.
+    Jsonl,
+    /// Comma-separated values with a header. Decoded keys and values are written as JSON.
+    Csv,
+}
+
+/// Row of a table, as exported.
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct Row {
+    /// Decoded key.
+    pub(crate) key: serde_json::Value,
+    /// Decoded value.
+    pub(crate) value: serde_json::Value,
+    /// Key as encoded in the database.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) raw_key: Option<Bytes>,
+    /// Value as compressed in the database.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) raw_value: Option<Bytes>,
+}
+
+/// Names of the CSV columns, in order.
+pub(crate) const CSV_COLUMNS: [&str; 4] = ["key", "value", "raw_key", "raw_value"];
+
+/// Writes the CSV header, with the raw columns if `raw` is set.
+fn write_csv_header(writer: &mut impl Write, raw: bool) -> io::Result<()> {
+    let columns = if raw { &CSV_COLUMNS[..] } else { &CSV_COLUMNS[..2] };
+    writeln!(writer, "{}", columns.join(","))
+}
+
+/// Writes a row in the given format.
+fn write_row(writer: &mut impl Write, format: Format, row: &Row) -> eyre::Result<()> {
+    match format {
+        Format::Jsonl => {
+            serde_json::to_writer(&mut *writer, row)?;
+            writeln!(writer)?;
+        }
+        Format::Csv => {
+            let mut fields = vec![
+                csv_field(&serde_json::to_string(&row.key)?),
+                csv_field(&serde_json::to_string(&row.value)?),
+            ];
+            if let (Some(raw_key), Some(raw_value)) = (&row.raw_key, &row.raw_value) {
+                fields.push(hex::encode_prefixed(raw_key));
+                fields.push(hex::encode_prefixed(raw_value));
+            }
+            writeln!(writer, "{}", fields.join(","))?;
+        }
+    }
+    Ok(())
+}
+
+/// Quotes a CSV field if it contains a separator, a quote or a line break.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+struct ExportViewer<'a, DB: Database> {
+    db: &'a DB,
+    args: &'a Command,
+}
+
+impl<DB: Database> TableViewer<()> for ExportViewer<'_, DB> {
+    type Error = eyre::Report;
+
+    fn view<T: Table>(&self) -> Result<(), Self::Error> {
+        let bound = |key: &Option<String>| -> eyre::Result<Bound<RawKey<T::Key>>> {
+            Ok(match key {
+                Some(key) => Bound::Included(RawKey::new(serde_json::from_str::<T::Key>(key)?)),
+                None => Bound::Unbounded,
+            })
+        };
+        let range = (bound(&self.args.from)?, bound(&self.args.to)?);
+
+        let mut writer = self.args.writer()?;
+        if self.args.format == Format::Csv {
+            write_csv_header(&mut writer, self.args.raw)?;
+        }
+
+        self.db.view(|tx| -> eyre::Result<()> {
+            let mut cursor = tx.cursor_read::<RawTable<T>>()?;
+            for entry in cursor.walk_range(range)? {
+                let (raw_key, raw_value) = entry?;
+                let row = Row {
+                    key: serde_json::to_value(raw_key.key()?)?,
+                    value: serde_json::to_value(raw_value.value()?)?,
+                    raw_key: self.args.raw.then(|| raw_key.into_key().into()),
+                    raw_value: self.args.raw.then(|| raw_value.into_value().into()),
+                };
+                write_row(&mut writer, self.args.format, &row)?;
+            }
+            Ok(())
+        })??;
+
+        writer.flush()?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_rows() {
+        let row = Row {
+            key: serde_json::json!(1),
+            value: serde_json::json!({ "a": "b,c" }),
+            raw_key: Some(Bytes::from_static(&[0x01])),
+            raw_value: Some(Bytes::from_static(&[0xab, 0xcd])),
+        };
+
+        let mut jsonl = Vec::new();
+        write_row(&mut jsonl, Format::Jsonl, &row).unwrap();
+        assert_eq!(
+            String::from_utf8(jsonl).unwrap(),
+            "{\"key\":1,\"value\":{\"a\":\"b,c\"},\"raw_key\":\"0x01\",\"raw_value\":\"0xabcd\"}\n"
+        );
+
+        let mut csv = Vec::new();
+        write_csv_header(&mut csv, true).unwrap();
+        write_row(&mut csv, Format::Csv, &row).unwrap();
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "key,value,raw_key,raw_value\n1,\"{\"\"a\"\":\"\"b,c\"\"}\",0x01,0xabcd\n"
+        );
+    }
+}
@@ -0,0 +1,243 @@
+//! `reth db import` command.
+
+use super::export::{Format, Row, CSV_COLUMNS};
+use clap::Parser;
+use reth_db::{
+    database::Database,
+    table::{Decode, Decompress, Table},
+    transaction::{DbTx, DbTxMut},
+    RawKey, RawTable, RawValue, TableViewer, Tables,
+};
+use reth_primitives::hex;
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
+    path::PathBuf,
+};
+use tracing::info;
+
+/// The arguments for the `reth db import` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// The table name
+    table: Tables,
+    /// The file to read the rows from, as written by `reth db export --raw`
+    #[arg(value_name = "FILE")]
+    input: PathBuf,
+    /// The format of the rows
+    #[arg(long, value_enum, default_value_t = Format::Jsonl)]
+    format: Format,
+}
+
+impl Command {
+    /// Execute `db import` command
+    pub fn execute<DB: Database>(self, db: &DB) -> eyre::Result<()> {
+        self.table.view(&ImportViewer { db, args: &self })
+    }
+}
+
+/// Reads the raw keys and values of the rows in the given format.
+fn read_rows(
+    reader: impl BufRead,
+    format: Format,
+) -> impl Iterator<Item = eyre::Result<(Vec<u8>, Vec<u8>)>> {
+    let mut raw_columns = None;
+    reader.lines().enumerate().filter_map(move |(index, line)| {
+        let line_number = index + 1;
+        let line = match line {
+            Ok(line) => line,
+            Err(err) => return Some(Err(err.into())),
+        };
+        if line.trim().is_empty() {
+            return None
+        }
+
+        let row = match format {
+            Format::Jsonl => serde_json::from_str::<Row>(&line)
+                .map_err(eyre::Report::from)
+                .and_then(|row| match (row.raw_key, row.raw_value) {
+                    (Some(raw_key), Some(raw_value)) => Ok((raw_key.to_vec(), raw_value.to_vec())),
+                    _ => Err(missing_raw_columns(line_number)),
+                }),
+            Format::Csv => {
+                let fields = csv_fields(&line);
+                let Some((raw_key, raw_value)) = raw_columns else {
+                    // The first line is the header.
+                    let position = |column: &str| fields.iter().position(|field| field == column);
+                    raw_columns = position(CSV_COLUMNS[2]).zip(position(CSV_COLUMNS[3]));
+                    return raw_columns.is_none().then(|| Err(missing_raw_columns(line_number)))
+                };
+
+                match (fields.get(raw_key), fields.get(raw_value)) {
+                    (Some(raw_key), Some(raw_value)) => hex::decode(raw_key)
+                        .and_then(|raw_key| {
+                            hex::decode(raw_value).map(|raw_value| (raw_key, raw_value))
+                        })
+                        .map_err(eyre::Report::from),
+                    _ => Err(missing_raw_columns(line_number)),
+                }
+            }
+        };
+
+        Some(row.map_err(|err| err.wrap_err(format!("invalid row on line {line_number}"))))
+    })
+}
+
+fn missing_raw_columns(line_number: usize) -> eyre::Report {
+    eyre::eyre!(
+        "line {line_number} has no raw key and value, the table must be exported with `--raw`"
+    )
+}
+
+/// Splits a CSV line into its fields, unquoting them.
+fn csv_fields(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(char) = chars.next() {
+        match char {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            char => field.push(char),
+        }
+    }
+    fields.push(field);
+    fields
+}
+
+struct ImportViewer<'a, DB: Database> {
+    db: &'a DB,
+    args: &'a Command,
+}
+
+impl<DB: Database> TableViewer<()> for ImportViewer<'_, DB> {
+    type Error = eyre::Report;
+
+    fn view<T: Table>(&self) -> Result<(), Self::Error> {
+        let reader = BufReader::new(File::open(&self.args.input)?);
+
+        // Rows are only committed if all of them are valid.
+        let tx = self.db.tx_mut()?;
+        let mut rows = 0;
+        for row in read_rows(reader, self.args.format) {
+            let (raw_key, raw_value) = row?;
+            tx.put::<RawTable<T>>(
+                RawKey::<T::Key>::decode(raw_key)?,
+                RawValue::<T::Value>::decompress_owned(raw_value)?,
+            )?;
+            rows += 1;
+        }
+        tx.commit()?;
+
+        info!(target: "reth::cli", table = T::NAME, rows, "Imported rows");
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::db::export;
+    use reth_db::{
+        cursor::DbCursorRO, init_db, mdbx::DatabaseArguments, models::AccountBeforeTx,
+        AccountChangeSet, Headers,
+    };
+    use reth_primitives::{Address, Header};
+
+    #[test]
+    fn parse_csv_fields() {
+        assert_eq!(csv_fields("a,\"b,\"\"c\"\"\",0x01"), vec!["a", "b,\"c\"", "0x01"]);
+        assert_eq!(csv_fields(""), vec![""]);
+    }
+
+    #[test]
+    fn export_import_roundtrip() {
+        let source =
+            init_db(tempfile::tempdir().unwrap().into_path(), DatabaseArguments::default())
+                .unwrap();
+        source
+            .update(|tx| {
+                for number in 0..3 {
+                    tx.put::<Headers>(number, Header { number, ..Default::default() }).unwrap();
+                }
+                // A dupsort table, whose values aren't deserializable.
+                for address in [Address::with_last_byte(1), Address::with_last_byte(2)] {
+                    tx.put::<AccountChangeSet>(1, AccountBeforeTx { address, info: None }).unwrap();
+                }
+            })
+            .unwrap();
+
+        for format in [Format::Jsonl, Format::Csv] {
+            let dir = tempfile::tempdir().unwrap();
+            let output = init_db(dir.path().join("db"), DatabaseArguments::default()).unwrap();
+
+            for table in [Tables::Headers, Tables::AccountChangeSet] {
+                let path = dir.path().join(table.name());
+                let format_arg = match format {
+                    Format::Jsonl => "jsonl",
+                    Format::Csv => "csv",
+                };
+                export::Command::try_parse_from([
+                    "reth",
+                    table.name(),
+                    "--output",
+                    path.to_str().unwrap(),
+                    "--format",
+                    format_arg,
+                    "--from",
+                    "1",
+                    "--raw",
+                ])
+                .unwrap()
+                .execute(&source)
+                .unwrap();
+
+                Command::try_parse_from([
+                    "reth",
+                    table.name(),
+                    path.to_str().unwrap(),
+                    "--format",
+                    format_arg,
+                ])
+                .unwrap()
+                .execute(&output)
+                .unwrap();
+            }
+
+            let tx = output.tx().unwrap();
+            let headers = tx
+                .cursor_read::<Headers>()
+                .unwrap()
+                .walk(None)
+                .unwrap()
+                .map(|row| row.unwrap().0)
+                .collect::<Vec<_>>();
+            assert_eq!(headers, vec![1, 2]);
+            let changes = tx
+                .cursor_read::<AccountChangeSet>()
+                .unwrap()
+                .walk(None)
+                .unwrap()
+                .map(|row| row.unwrap().1.address)
+                .collect::<Vec<_>>();
+            assert_eq!(changes, vec![Address::with_last_byte(1), Address::with_last_byte(2)]);
+        }
+    }
+
+    #[test]
+    fn import_requires_raw_columns() {
+        let rows = "{\"key\":1,\"value\":2}\n";
+        let mut rows = read_rows(rows.as_bytes(), Format::Jsonl);
+        assert!(rows.next().unwrap().is_err());
+
+        let rows = "key,value\n1,2\n";
+        let mut rows = read_rows(rows.as_bytes(), Format::Csv);
+        assert!(rows.next().unwrap().is_err());
+    }
+}
@@ -0,0 +1,146 @@
+//! `reth db migrate` command.
+
+use clap::Parser;
+use reth_db::{
+    migration::{migrations, Migration, MigrationReport, Migrator, DEFAULT_MIGRATION_BATCH_SIZE},
+    DatabaseEnv,
+};
+use std::path::Path;
+use tracing::info;
+
+/// The arguments for the `reth db migrate` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// Runs the first pending migration without committing its changes
+    #[arg(long)]
+    dry_run: bool,
+    /// The number of entries that are migrated in a single write transaction
+    #[arg(long, value_name = "SIZE", default_value_t = DEFAULT_MIGRATION_BATCH_SIZE)]
+    batch_size: usize,
+}
+
+impl Command {
+    /// Execute `db migrate` command
+    ///
+    /// Interrupted migrations resume from their last committed batch when the command is run
+    /// again.
+    pub fn execute(self, db: &DatabaseEnv, db_path: &Path) -> eyre::Result<()> {
+        self.run(db, db_path, migrations())?;
+        Ok(())
+    }
+
+    /// Runs the given migrations on the database, and returns their reports.
+    fn run(
+        &self,
+        db: &DatabaseEnv,
+        db_path: &Path,
+        migrations: Vec<Box<dyn Migration<DatabaseEnv>>>,
+    ) -> eyre::Result<Vec<MigrationReport>> {
+        let reports = Migrator::new(db, db_path, migrations)
+            .with_batch_size(self.batch_size)
+            .with_dry_run(self.dry_run)
+            .run()?;
+
+        if reports.is_empty() {
+            info!(target: "reth::cli", "Database is up to date");
+        }
+        for report in &reports {
+            if !report.finished {
+                info!(target: "reth::cli", version = report.version, description = report.description, "Skipped migration in dry run");
+            } else if self.dry_run {
+                info!(target: "reth::cli", version = report.version, description = report.description, entries = report.entries, "Dry run of migration succeeded, no changes were committed");
+            } else {
+                info!(target: "reth::cli", version = report.version, description = report.description, entries = report.entries, "Migrated database");
+            }
+        }
+
+        Ok(reports)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_db::{
+        cursor::DbCursorRO,
+        database::Database,
+        init_db,
+        mdbx::DatabaseArguments,
+        migration::MigrationBatch,
+        tables,
+        transaction::{DbTx, DbTxMut},
+        version::{get_db_version, write_db_version_file, DB_VERSION},
+        DatabaseError,
+    };
+    use reth_primitives::B256;
+
+    /// Sets the canonical hash of every block to its number.
+    #[derive(Debug)]
+    struct CanonicalHashesMigration;
+
+    impl Migration<DatabaseEnv> for CanonicalHashesMigration {
+        fn version(&self) -> u64 {
+            DB_VERSION
+        }
+
+        fn description(&self) -> &str {
+            "Set canonical hashes to block numbers"
+        }
+
+        fn migrate_batch(
+            &self,
+            tx: &<DatabaseEnv as Database>::TXMut,
+            _position: &[u8],
+            _batch_size: usize,
+        ) -> Result<MigrationBatch, DatabaseError> {
+            let numbers = tx
+                .cursor_read::<tables::CanonicalHeaders>()?
+                .walk(None)?
+                .map(|entry| entry.map(|(number, _)| number))
+                .collect::<Result<Vec<_>, _>>()?;
+            for &number in &numbers {
+                tx.put::<tables::CanonicalHeaders>(number, B256::with_last_byte(number as u8))?;
+            }
+            Ok(MigrationBatch { entries: numbers.len() as u64, next: None })
+        }
+    }
+
+    fn canonical_hash(db: &DatabaseEnv, number: u64) -> Option<B256> {
+        db.view(|tx| tx.get::<tables::CanonicalHeaders>(number)).unwrap().unwrap()
+    }
+
+    #[test]
+    fn migrate_database() {
+        let path = tempfile::tempdir().unwrap().into_path();
+        let db = init_db(&path, DatabaseArguments::default()).unwrap();
+        db.update(|tx| {
+            for number in 0..3 {
+                tx.put::<tables::CanonicalHeaders>(number, B256::ZERO).unwrap();
+            }
+        })
+        .unwrap();
+        write_db_version_file(&path, DB_VERSION - 1).unwrap();
+        let migrations =
+            || vec![Box::new(CanonicalHashesMigration) as Box<dyn Migration<DatabaseEnv>>];
+
+        // A dry run leaves the database untouched.
+        let command = Command::parse_from(["reth", "--dry-run"]);
+        let reports = command.run(&db, &path, migrations()).unwrap();
+        assert_eq!(reports.len(), 1);
+        assert_eq!((reports[0].entries, reports[0].finished), (3, true));
+        assert_eq!(get_db_version(&path).unwrap(), DB_VERSION - 1);
+        assert_eq!(canonical_hash(&db, 1), Some(B256::ZERO));
+
+        let command = Command::parse_from(["reth"]);
+        let reports = command.run(&db, &path, migrations()).unwrap();
+        assert_eq!(reports.len(), 1);
+        assert_eq!((reports[0].entries, reports[0].finished), (3, true));
+        assert_eq!(get_db_version(&path).unwrap(), DB_VERSION);
+        assert_eq!(canonical_hash(&db, 1), Some(B256::with_last_byte(1)));
+        drop(db);
+
+        // The migrated database passes the version check and is up to date.
+        let db = init_db(&path, DatabaseArguments::default()).unwrap();
+        assert!(command.run(&db, &path, migrations()).unwrap().is_empty());
+    }
+}
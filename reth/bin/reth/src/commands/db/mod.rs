@@ -9,16 +9,10 @@ use crate::{
     utils::DbTool,
 };
 use clap::{Parser, Subcommand};
-use comfy_table::{Cell, Row, Table as ComfyTable};
-use eyre::WrapErr;
-use human_bytes::human_bytes;
 use reth_db::{
-    database::Database,
-    mdbx,
-    mdbx::DatabaseArguments,
+    mdbx::{DatabaseArguments, MaxReadTransactionDuration},
     open_db, open_db_read_only,
     version::{get_db_version, DatabaseVersionError, DB_VERSION},
-    Tables,
 };
 use reth_primitives::ChainSpec;
 use std::{
@@ -27,10 +21,15 @@ use std::{
 };
 
 mod clear;
+mod compact;
 mod diff;
+mod export;
 mod get;
+mod import;
 mod list;
+mod migrate;
 mod snapshots;
+mod stats;
 /// DB List TUI
 mod tui;
 
@@ -71,13 +70,17 @@ pub struct Command {
 /// `reth db` subcommands
 pub enum Subcommands {
     /// Lists all the tables, their entry count and their size
-    Stats,
+    Stats(stats::Command),
     /// Lists the contents of a table
     List(list::Command),
     /// Create a diff between two database tables or two entire databases.
     Diff(diff::Command),
     /// Gets the content of a table for the given key
     Get(get::Command),
+    /// Exports the rows of a table as JSON Lines or CSV
+    Export(export::Command),
+    /// Imports the rows of a table written by `reth db export --raw`
+    Import(import::Command),
     /// Deletes all database entries
     Drop {
         /// Bypasses the interactive confirmation and drops the database directly
@@ -86,6 +89,10 @@ pub enum Subcommands {
     },
     /// Deletes all table entries
     Clear(clear::Command),
+    /// Writes a compacted copy of the database, leaving out its free pages
+    Compact(compact::Command),
+    /// Runs the pending database migrations
+    Migrate(migrate::Command),
     /// Snapshots tables from database
     Snapshot(snapshots::Command),
     /// Lists current and local database versions
@@ -102,93 +109,12 @@ impl Command {
         let db_path = data_dir.db_path();
 
         match self.command {
-            // TODO: We'll need to add this on the DB trait.
-            Subcommands::Stats { .. } => {
+            Subcommands::Stats(command) => {
                 let db = open_db_read_only(
                     &db_path,
                     DatabaseArguments::default().log_level(self.db.log_level),
                 )?;
-                let tool = DbTool::new(&db, self.chain.clone())?;
-                let mut stats_table = ComfyTable::new();
-                stats_table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
-                stats_table.set_header([
-                    "Table Name",
-                    "# Entries",
-                    "Branch Pages",
-                    "Leaf Pages",
-                    "Overflow Pages",
-                    "Total Size",
-                ]);
-
-                tool.db.view(|tx| {
-                    let mut tables =
-                        Tables::ALL.iter().map(|table| table.name()).collect::<Vec<_>>();
-                    tables.sort();
-                    let mut total_size = 0;
-                    for table in tables {
-                        let table_db =
-                            tx.inner.open_db(Some(table)).wrap_err("Could not open db.")?;
-
-                        let stats = tx
-                            .inner
-                            .db_stat(&table_db)
-                            .wrap_err(format!("Could not find table: {table}"))?;
-
-                        // Defaults to 16KB right now but we should
-                        // re-evaluate depending on the DB we end up using
-                        // (e.g. REDB does not have these options as configurable intentionally)
-                        let page_size = stats.page_size() as usize;
-                        let leaf_pages = stats.leaf_pages();
-                        let branch_pages = stats.branch_pages();
-                        let overflow_pages = stats.overflow_pages();
-                        let num_pages = leaf_pages + branch_pages + overflow_pages;
-                        let table_size = page_size * num_pages;
-
-                        total_size += table_size;
-                        let mut row = Row::new();
-                        row.add_cell(Cell::new(table))
-                            .add_cell(Cell::new(stats.entries()))
-                            .add_cell(Cell::new(branch_pages))
-                            .add_cell(Cell::new(leaf_pages))
-                            .add_cell(Cell::new(overflow_pages))
-                            .add_cell(Cell::new(human_bytes(table_size as f64)));
-                        stats_table.add_row(row);
-                    }
-
-                    let max_widths = stats_table.column_max_content_widths();
-
-                    let mut seperator = Row::new();
-                    for width in max_widths {
-                        seperator.add_cell(Cell::new("-".repeat(width as usize)));
-                    }
-                    stats_table.add_row(seperator);
-
-                    let mut row = Row::new();
-                    row.add_cell(Cell::new("Total DB size"))
-                        .add_cell(Cell::new(""))
-                        .add_cell(Cell::new(""))
-                        .add_cell(Cell::new(""))
-                        .add_cell(Cell::new(""))
-                        .add_cell(Cell::new(human_bytes(total_size as f64)));
-                    stats_table.add_row(row);
-
-                    let freelist = tx.inner.env().freelist()?;
-                    let freelist_size = freelist *
-                        tx.inner.db_stat(&mdbx::Database::freelist_db())?.page_size() as usize;
-
-                    let mut row = Row::new();
-                    row.add_cell(Cell::new("Freelist size"))
-                        .add_cell(Cell::new(freelist))
-                        .add_cell(Cell::new(""))
-                        .add_cell(Cell::new(""))
-                        .add_cell(Cell::new(""))
-                        .add_cell(Cell::new(human_bytes(freelist_size as f64)));
-                    stats_table.add_row(row);
-
-                    Ok::<(), eyre::Report>(())
-                })??;
-
-                println!("{stats_table}");
+                command.execute(&db, &data_dir.snapshots_path())?;
             }
             Subcommands::List(command) => {
                 let db = open_db_read_only(
@@ -203,8 +129,7 @@ impl Command {
                     &db_path,
                     DatabaseArguments::default().log_level(self.db.log_level),
                 )?;
-                let tool = DbTool::new(&db, self.chain.clone())?;
-                command.execute(&tool)?;
+                command.execute(&db, &db_path, &data_dir.snapshots_path())?;
             }
             Subcommands::Get(command) => {
                 let db = open_db_read_only(
@@ -214,6 +139,20 @@ impl Command {
                 let tool = DbTool::new(&db, self.chain.clone())?;
                 command.execute(&tool)?;
             }
+            Subcommands::Export(command) => {
+                let db = open_db_read_only(
+                    &db_path,
+                    DatabaseArguments::default()
+                        .log_level(self.db.log_level)
+                        .max_read_transaction_duration(Some(MaxReadTransactionDuration::Unbounded)),
+                )?;
+                command.execute(&db)?;
+            }
+            Subcommands::Import(command) => {
+                let db =
+                    open_db(&db_path, DatabaseArguments::default().log_level(self.db.log_level))?;
+                command.execute(&db)?;
+            }
             Subcommands::Drop { force } => {
                 if !force {
                     // Ask for confirmation
@@ -240,8 +179,26 @@ impl Command {
                     open_db(&db_path, DatabaseArguments::default().log_level(self.db.log_level))?;
                 command.execute(&db)?;
             }
+            Subcommands::Compact(command) => {
+                let db = open_db_read_only(
+                    &db_path,
+                    DatabaseArguments::default().log_level(self.db.log_level),
+                )?;
+                command.execute(&db, &db_path)?;
+            }
+            Subcommands::Migrate(command) => {
+                let db =
+                    open_db(&db_path, DatabaseArguments::default().log_level(self.db.log_level))?;
+                db.create_tables()?;
+                command.execute(&db, &db_path)?;
+            }
             Subcommands::Snapshot(command) => {
-                command.execute(&db_path, self.db.log_level, self.chain.clone())?;
+                command.execute(
+                    &db_path,
+                    &data_dir.snapshots_path(),
+                    self.db.log_level,
+                    self.chain.clone(),
+                )?;
             }
             Subcommands::Version => {
                 let local_db_version = match get_db_version(&db_path) {
@@ -278,4 +235,11 @@ mod tests {
         let cmd = Command::try_parse_from(["reth", "stats", "--datadir", &path]).unwrap();
         assert_eq!(cmd.datadir.as_ref(), Some(Path::new(&path)));
     }
+
+    #[test]
+    fn parse_stats_output_args() {
+        let cmd =
+            Command::try_parse_from(["reth", "stats", "--json", "--sample-size", "10"]).unwrap();
+        assert!(matches!(cmd.command, Subcommands::Stats(_)));
+    }
 }
@@ -1,4 +1,4 @@
-use clap::{builder::RangedU64ValueParser, Parser};
+use clap::{builder::RangedU64ValueParser, Parser, Subcommand};
 use human_bytes::human_bytes;
 use itertools::Itertools;
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
@@ -26,10 +26,15 @@ mod bench;
 mod headers;
 mod receipts;
 mod transactions;
+mod verify;
 
 #[derive(Parser, Debug)]
+#[command(args_conflicts_with_subcommands = true)]
 /// Arguments for the `reth db snapshot` command.
 pub struct Command {
+    #[command(subcommand)]
+    command: Option<Subcommands>,
+
     /// Snapshot segments to generate.
     segments: Vec<SnapshotSegment>,
 
@@ -75,14 +80,26 @@ pub struct Command {
     phf: Vec<PerfectHashingFunction>,
 }
 
+#[derive(Subcommand, Debug)]
+/// `reth db snapshot` subcommands
+pub enum Subcommands {
+    /// Verifies the snapshots against their checksums and the database
+    Verify(verify::Command),
+}
+
 impl Command {
     /// Execute `db snapshot` command
     pub fn execute(
         self,
         db_path: &Path,
+        snapshots_path: &Path,
         log_level: Option<LogLevel>,
         chain: Arc<ChainSpec>,
     ) -> eyre::Result<()> {
+        if let Some(Subcommands::Verify(command)) = &self.command {
+            return command.execute(db_path, snapshots_path, log_level, chain)
+        }
+
         let all_combinations =
             self.segments.iter().cartesian_product(self.compression.iter()).cartesian_product(
                 if self.phf.is_empty() {
@@ -121,6 +138,43 @@ impl Command {
                             factory.clone(),
                             snap_segments::Receipts::new(*compression, filters),
                         )?,
+                        SnapshotSegment::TransactionSenders => self
+                            .generate_snapshot::<DatabaseEnv>(
+                                factory.clone(),
+                                snap_segments::TransactionSenders::new(*compression, filters),
+                            )?,
+                        // Keyed segments are always created with filters and a PHF, since they
+                        // can't be queried otherwise.
+                        SnapshotSegment::AccountChangesets => self
+                            .generate_snapshot::<DatabaseEnv>(
+                                factory.clone(),
+                                snap_segments::AccountChangesets::new(
+                                    *compression,
+                                    phf.unwrap_or(PerfectHashingFunction::Fmph),
+                                ),
+                            )?,
+                        SnapshotSegment::StorageChangesets => self
+                            .generate_snapshot::<DatabaseEnv>(
+                                factory.clone(),
+                                snap_segments::StorageChangesets::new(
+                                    *compression,
+                                    phf.unwrap_or(PerfectHashingFunction::Fmph),
+                                ),
+                            )?,
+                        SnapshotSegment::AccountHistory => self.generate_snapshot::<DatabaseEnv>(
+                            factory.clone(),
+                            snap_segments::AccountHistory::new(
+                                *compression,
+                                phf.unwrap_or(PerfectHashingFunction::Fmph),
+                            ),
+                        )?,
+                        SnapshotSegment::StorageHistory => self.generate_snapshot::<DatabaseEnv>(
+                            factory.clone(),
+                            snap_segments::StorageHistory::new(
+                                *compression,
+                                phf.unwrap_or(PerfectHashingFunction::Fmph),
+                            ),
+                        )?,
                     }
                 }
             }
@@ -153,6 +207,13 @@ impl Command {
                         InclusionFilter::Cuckoo,
                         phf,
                     )?,
+                    SnapshotSegment::TransactionSenders |
+                    SnapshotSegment::AccountChangesets |
+                    SnapshotSegment::StorageChangesets |
+                    SnapshotSegment::AccountHistory |
+                    SnapshotSegment::StorageHistory => {
+                        eyre::bail!("benchmarks are not supported for the {mode} segment")
+                    }
                 }
             }
         }
@@ -0,0 +1,355 @@
+use clap::Parser;
+use reth_db::{
+    cursor::DbCursorRO,
+    database::Database,
+    mdbx::DatabaseArguments,
+    models::{AccountBeforeTx, BlockNumberAddress},
+    open_db_read_only,
+    snapshot::{
+        account_changeset_key, account_history_key, iter_snapshots, storage_changeset_key,
+        storage_history_key, AccountChangesetMask, AccountHistoryMask, HeaderMask, KeyOrNumber,
+        ReceiptMask, SnapshotCursor, StorageChangesetMask, StorageHistoryMask, TransactionMask,
+        TransactionSenderMask,
+    },
+    tables,
+    transaction::DbTx,
+    BlockNumberList,
+};
+use reth_interfaces::{db::LogLevel, provider::ProviderResult};
+use reth_nippy_jar::NippyJar;
+use reth_primitives::{
+    proofs::calculate_receipt_root_ref, snapshot::SegmentHeader, Address, BlockHash, BlockNumber,
+    ChainSpec, Header, Receipt, SnapshotSegment, StorageEntry, TransactionSignedNoHash, B256,
+};
+use reth_provider::{
+    AccountExtReader, BlockReader, DatabaseProviderRO, HeaderProvider, ProviderFactory,
+    StorageReader,
+};
+use std::{path::Path, sync::Arc};
+
+#[derive(Parser, Debug)]
+/// Arguments for the `reth db snapshot verify` command.
+pub struct Command {
+    /// Snapshot segments to verify. All segments are verified if none is given.
+    segments: Vec<SnapshotSegment>,
+}
+
+impl Command {
+    /// Execute `db snapshot verify` command
+    pub fn execute(
+        &self,
+        db_path: &Path,
+        snapshots_path: &Path,
+        log_level: Option<LogLevel>,
+        chain: Arc<ChainSpec>,
+    ) -> eyre::Result<()> {
+        let db = open_db_read_only(db_path, DatabaseArguments::default().log_level(log_level))?;
+        let factory = ProviderFactory::new(db, chain.clone());
+        let provider = factory.provider()?;
+
+        let mut total = 0;
+        let mut corrupted = 0;
+        for (segment, ranges) in iter_snapshots(snapshots_path)? {
+            if !self.segments.is_empty() && !self.segments.contains(&segment) {
+                continue
+            }
+
+            for (block_range, tx_range) in ranges {
+                let filename = segment.filename(&block_range, &tx_range);
+                total += 1;
+
+                match verify_snapshot(&provider, segment, &snapshots_path.join(&filename), &chain) {
+                    Ok(()) => println!("{filename}: OK"),
+                    Err(err) => {
+                        corrupted += 1;
+                        println!("{filename}: {err}");
+                    }
+                }
+            }
+        }
+
+        if corrupted > 0 {
+            eyre::bail!("{corrupted} of {total} snapshots failed verification")
+        }
+        println!("Verified {total} snapshots");
+
+        Ok(())
+    }
+}
+
+/// Verifies a snapshot against its checksums, and its contents against the hashes derived from
+/// the database.
+fn verify_snapshot<DB: Database>(
+    provider: &DatabaseProviderRO<DB>,
+    segment: SnapshotSegment,
+    path: &Path,
+    chain: &ChainSpec,
+) -> eyre::Result<()> {
+    let jar = NippyJar::<SegmentHeader>::load(path)?;
+    if jar.checksums().is_none() {
+        eyre::bail!("snapshot was created without checksums")
+    }
+    jar.verify_checksums()?;
+
+    // Keyed segments can only be queried with their filters and PHF loaded.
+    let jar = if segment.is_keyed() { jar.load_filters()? } else { jar };
+    let mut cursor = SnapshotCursor::new(&jar, Arc::new(jar.open_data_reader()?))?;
+    let segment_header = jar.user_header();
+
+    // Rows of segments queried by number map one to one to the numbers of the snapshot.
+    let numbers = match segment {
+        SnapshotSegment::Headers => Some(segment_header.block_range()),
+        SnapshotSegment::Transactions |
+        SnapshotSegment::Receipts |
+        SnapshotSegment::TransactionSenders => Some(segment_header.tx_range()),
+        _ => None,
+    };
+    if let Some(numbers) = numbers {
+        let expected = (numbers.end() + 1).saturating_sub(*numbers.start()) as usize;
+        if jar.rows() != expected {
+            eyre::bail!("snapshot has {} rows, but its range has {expected} numbers", jar.rows())
+        }
+    }
+
+    match segment {
+        SnapshotSegment::Headers => {
+            for number in segment_header.block_range().clone() {
+                let (snapshot_header, snapshot_hash) = cursor
+                    .get_two::<HeaderMask<Header, BlockHash>>(number.into())?
+                    .ok_or_else(|| eyre::eyre!("header {number} is missing"))?;
+                let expected = provider.tx_ref().get::<tables::CanonicalHeaders>(number)?;
+
+                if expected != Some(snapshot_header.hash_slow()) || expected != Some(snapshot_hash)
+                {
+                    eyre::bail!("header {number} does not match the canonical hash {expected:?}")
+                }
+            }
+        }
+        SnapshotSegment::Transactions => {
+            for tx_number in segment_header.tx_range().clone() {
+                let transaction = cursor
+                    .get_one::<TransactionMask<TransactionSignedNoHash>>(tx_number.into())?
+                    .ok_or_else(|| eyre::eyre!("transaction {tx_number} is missing"))?;
+                let hash = transaction.hash();
+
+                if provider.tx_ref().get::<tables::TxHashNumber>(hash)? != Some(tx_number) {
+                    eyre::bail!("transaction {tx_number} with hash {hash} is not in the database")
+                }
+            }
+        }
+        SnapshotSegment::TransactionSenders => {
+            for tx_number in segment_header.tx_range().clone() {
+                let sender = cursor
+                    .get_one::<TransactionSenderMask<Address>>(tx_number.into())?
+                    .ok_or_else(|| eyre::eyre!("sender of transaction {tx_number} is missing"))?;
+                // Senders of snapshotted transactions are removed from the database, so they are
+                // recovered from the transaction instead.
+                let expected = match provider.tx_ref().get::<tables::TxSenders>(tx_number)? {
+                    Some(sender) => Some(sender),
+                    None => provider
+                        .tx_ref()
+                        .get::<tables::Transactions>(tx_number)?
+                        .and_then(|transaction| transaction.recover_signer()),
+                };
+
+                if expected != Some(sender) {
+                    eyre::bail!("sender of transaction {tx_number} does not match {expected:?}")
+                }
+            }
+        }
+        SnapshotSegment::AccountChangesets => {
+            let mut changesets = provider.tx_ref().cursor_read::<tables::AccountChangeSet>()?;
+            verify_keyed_rows(
+                &jar,
+                &mut cursor,
+                |cursor, key_or_num| {
+                    cursor.get_two::<AccountChangesetMask<AccountBeforeTx, BlockNumber>>(key_or_num)
+                },
+                |(account_before, number)| {
+                    account_changeset_key(*number, account_before.address).to_vec()
+                },
+                |(_, number)| vec![*number],
+                changesets.walk_range(segment_header.block_range().clone())?.map(|entry| {
+                    Ok(entry.map(|(number, account_before)| (account_before, number))?)
+                }),
+            )?;
+        }
+        SnapshotSegment::StorageChangesets => {
+            let mut changesets = provider.tx_ref().cursor_read::<tables::StorageChangeSet>()?;
+            verify_keyed_rows(
+                &jar,
+                &mut cursor,
+                |cursor, key_or_num| {
+                    cursor.get_three::<StorageChangesetMask<StorageEntry, BlockNumber, Address>>(
+                        key_or_num,
+                    )
+                },
+                |(entry, number, address)| {
+                    storage_changeset_key(*number, *address, entry.key).to_vec()
+                },
+                |(_, number, _)| vec![*number],
+                changesets
+                    .walk_range(BlockNumberAddress::range(segment_header.block_range().clone()))?
+                    .map(|entry| {
+                        Ok(entry.map(|(BlockNumberAddress((number, address)), entry)| {
+                            (entry, number, address)
+                        })?)
+                    }),
+            )?;
+        }
+        SnapshotSegment::AccountHistory => {
+            // The history of the database is built from the changesets, same as when
+            // snapshotting.
+            let history = provider
+                .changed_accounts_and_blocks_with_range(segment_header.block_range().clone())?;
+            verify_keyed_rows(
+                &jar,
+                &mut cursor,
+                |cursor, key_or_num| {
+                    Ok(cursor
+                        .get_two::<AccountHistoryMask<BlockNumberList, Address>>(key_or_num)?
+                        .map(|(list, address)| (block_numbers(&list), address)))
+                },
+                |(_, address)| account_history_key(*address).to_vec(),
+                |(blocks, _)| blocks.clone(),
+                history.into_iter().map(|(address, blocks)| Ok((blocks, address))),
+            )?;
+        }
+        SnapshotSegment::StorageHistory => {
+            let history = provider
+                .changed_storages_and_blocks_with_range(segment_header.block_range().clone())?;
+            verify_keyed_rows(
+                &jar,
+                &mut cursor,
+                |cursor, key_or_num| {
+                    Ok(cursor
+                        .get_three::<StorageHistoryMask<BlockNumberList, Address, B256>>(
+                            key_or_num,
+                        )?
+                        .map(|(list, address, storage_key)| {
+                            (block_numbers(&list), address, storage_key)
+                        }))
+                },
+                |(_, address, storage_key)| storage_history_key(*address, *storage_key).to_vec(),
+                |(blocks, _, _)| blocks.clone(),
+                history
+                    .into_iter()
+                    .map(|((address, storage_key), blocks)| Ok((blocks, address, storage_key))),
+            )?;
+        }
+        SnapshotSegment::Receipts => {
+            for number in segment_header.block_range().clone() {
+                let indices = provider
+                    .block_body_indices(number)?
+                    .ok_or_else(|| eyre::eyre!("body indices of block {number} are missing"))?;
+                let expected = provider
+                    .header_by_number(number)?
+                    .ok_or_else(|| eyre::eyre!("header {number} is missing"))?;
+
+                let receipts = indices
+                    .tx_num_range()
+                    .map(|tx_number| {
+                        cursor
+                            .get_one::<ReceiptMask<Receipt>>(tx_number.into())?
+                            .ok_or_else(|| eyre::eyre!("receipt {tx_number} is missing"))
+                    })
+                    .collect::<eyre::Result<Vec<_>>>()?;
+
+                if receipts_root(&receipts, chain, &expected) != expected.receipts_root {
+                    eyre::bail!(
+                        "receipts of block {number} do not match the receipts root {}",
+                        expected.receipts_root
+                    )
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Reads the rows of a snapshot whose rows are queried by key one by one, and checks them against
+/// the jar itself: every row can be found by its key, the rows are strictly ordered by key, and the
+/// block numbers of every row are sorted and within the block range of the snapshot.
+///
+/// Each row is then compared with the next of the `database` rows in the block range of the
+/// snapshot, so neither side is held in memory. The comparison is skipped if there are no
+/// database rows, as they are removed from the database after snapshotting.
+fn verify_keyed_rows<T: PartialEq>(
+    jar: &NippyJar<SegmentHeader>,
+    cursor: &mut SnapshotCursor<'_>,
+    mut read: impl FnMut(&mut SnapshotCursor<'_>, KeyOrNumber<'_>) -> ProviderResult<Option<T>>,
+    key: impl Fn(&T) -> Vec<u8>,
+    blocks: impl Fn(&T) -> Vec<BlockNumber>,
+    database: impl Iterator<Item = eyre::Result<T>>,
+) -> eyre::Result<()> {
+    let segment_header = jar.user_header();
+    let mut database = database.peekable();
+    let compare = database.peek().is_some();
+    let mut previous_key = None;
+
+    for row_number in 0..jar.rows() as u64 {
+        let row = read(cursor, (segment_header.start() + row_number).into())?
+            .ok_or_else(|| eyre::eyre!("row {row_number} is missing"))?;
+
+        let row_key = key(&row);
+        if previous_key.as_ref().map_or(false, |previous| *previous >= row_key) {
+            eyre::bail!("row {row_number} is not ordered by key")
+        }
+
+        let row_blocks = blocks(&row);
+        if row_blocks.is_empty() || row_blocks.windows(2).any(|pair| pair[0] >= pair[1]) {
+            eyre::bail!("block numbers of row {row_number} are empty or not sorted")
+        }
+        if row_blocks.iter().any(|number| !segment_header.block_range().contains(number)) {
+            eyre::bail!("row {row_number} has a block number outside of the snapshot range")
+        }
+
+        if read(cursor, row_key.as_slice().into())?.as_ref() != Some(&row) {
+            eyre::bail!("row {row_number} can't be found by its key")
+        }
+
+        if compare {
+            match database.next().transpose()? {
+                Some(expected) if expected == row => {}
+                Some(_) => eyre::bail!("row {row_number} does not match the database"),
+                None => eyre::bail!(
+                    "snapshot has {} rows, but the database has {row_number} rows in its range",
+                    jar.rows()
+                ),
+            }
+        }
+
+        previous_key = Some(row_key);
+    }
+
+    if compare {
+        let remaining = database.count();
+        if remaining > 0 {
+            eyre::bail!(
+                "snapshot has {} rows, but the database has {} rows in its range",
+                jar.rows(),
+                jar.rows() + remaining
+            )
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the block numbers of a history list.
+fn block_numbers(list: &BlockNumberList) -> Vec<BlockNumber> {
+    list.iter(0).map(|i| i as BlockNumber).collect()
+}
+
+/// Returns the receipts root of the block with the given header.
+#[cfg(not(feature = "optimism"))]
+fn receipts_root(receipts: &[Receipt], _chain: &ChainSpec, _header: &Header) -> B256 {
+    calculate_receipt_root_ref(&receipts.iter().collect::<Vec<_>>())
+}
+
+/// Returns the receipts root of the block with the given header.
+#[cfg(feature = "optimism")]
+fn receipts_root(receipts: &[Receipt], chain: &ChainSpec, header: &Header) -> B256 {
+    calculate_receipt_root_ref(&receipts.iter().collect::<Vec<_>>(), chain, header.timestamp)
+}
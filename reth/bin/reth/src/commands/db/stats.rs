@@ -0,0 +1,225 @@
+//! `reth db stats` command.
+
+use clap::Parser;
+use comfy_table::{Cell, Row, Table as ComfyTable};
+use human_bytes::human_bytes;
+use reth_db::{database::Database, database_metrics::TableStats, mdbx, DatabaseEnv};
+use reth_primitives::SnapshotSegment;
+use serde::Serialize;
+use std::{collections::BTreeMap, fs, path::Path};
+
+/// The arguments for the `reth db stats` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// Prints the stats as JSON instead of a table.
+    #[arg(long)]
+    json: bool,
+
+    /// Number of entries sampled from the start of each table to estimate the average key and
+    /// value sizes and the compression savings.
+    #[arg(long, default_value_t = 1000)]
+    sample_size: usize,
+}
+
+/// Stats of the database and the snapshots.
+#[derive(Debug, Serialize)]
+struct Stats {
+    /// Stats of the tables, sorted by name.
+    tables: Vec<TableStats>,
+    /// Number of pages in the freelist.
+    freelist_pages: usize,
+    /// Size of the pages in the freelist in bytes.
+    freelist_size: usize,
+    /// Stats of the snapshot files, by segment.
+    snapshots: Vec<SegmentStats>,
+}
+
+/// Stats of the snapshot files of a segment.
+#[derive(Debug, Serialize)]
+struct SegmentStats {
+    /// Segment of the snapshots.
+    segment: SnapshotSegment,
+    /// Number of snapshots of the segment.
+    snapshots: usize,
+    /// Total size of the data, offsets, index and configuration files in bytes.
+    size: u64,
+}
+
+impl Command {
+    /// Execute `db stats` command
+    pub fn execute(self, db: &DatabaseEnv, snapshots_path: &Path) -> eyre::Result<()> {
+        let mut tables = db.table_stats(self.sample_size)?;
+        tables.sort_by_key(|stats| stats.table);
+
+        let freelist_pages = db.freelist()?;
+        let freelist_size = freelist_pages *
+            db.view(|tx| tx.inner.db_stat(&mdbx::Database::freelist_db()))??.page_size() as usize;
+
+        let stats = Stats {
+            tables,
+            freelist_pages,
+            freelist_size,
+            snapshots: segment_stats(snapshots_path)?,
+        };
+
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&stats)?);
+        } else {
+            println!("{}", tables_table(&stats));
+            if !stats.snapshots.is_empty() {
+                println!("\n{}", snapshots_table(&stats.snapshots));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the stats of the snapshot files in the directory, sorted by segment.
+fn segment_stats(snapshots_path: &Path) -> eyre::Result<Vec<SegmentStats>> {
+    if !snapshots_path.exists() {
+        return Ok(Vec::new())
+    }
+
+    let mut segments = BTreeMap::<SnapshotSegment, SegmentStats>::new();
+    for entry in fs::read_dir(snapshots_path)? {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        if !metadata.is_file() {
+            continue
+        }
+
+        // The offsets, index and configuration files share the name of the data file, with an
+        // extension.
+        let path = entry.path();
+        let Some((segment, _, _)) = path.file_stem().and_then(SnapshotSegment::parse_filename)
+        else {
+            continue
+        };
+
+        let stats = segments.entry(segment).or_insert_with(|| SegmentStats {
+            segment,
+            snapshots: 0,
+            size: 0,
+        });
+        if path.extension().is_none() {
+            stats.snapshots += 1;
+        }
+        stats.size += metadata.len();
+    }
+
+    Ok(segments.into_values().collect())
+}
+
+/// Returns the table of the database stats.
+fn tables_table(stats: &Stats) -> ComfyTable {
+    let mut table = ComfyTable::new();
+    table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+    table.set_header([
+        "Table Name",
+        "# Entries",
+        "Branch Pages",
+        "Leaf Pages",
+        "Overflow Pages",
+        "Avg Key Size",
+        "Avg Value Size",
+        "Est. Compression Savings",
+        "Total Size",
+    ]);
+
+    let mut total_size = 0;
+    for table_stats in &stats.tables {
+        total_size += table_stats.size();
+
+        let mut row = Row::new();
+        row.add_cell(Cell::new(table_stats.table))
+            .add_cell(Cell::new(table_stats.entries))
+            .add_cell(Cell::new(table_stats.branch_pages))
+            .add_cell(Cell::new(table_stats.leaf_pages))
+            .add_cell(Cell::new(table_stats.overflow_pages))
+            .add_cell(Cell::new(human_bytes(table_stats.sample.average_key_size())))
+            .add_cell(Cell::new(human_bytes(table_stats.sample.average_value_size())))
+            .add_cell(Cell::new(format!(
+                "{:.1}%",
+                table_stats.sample.compression_savings() * 100.0
+            )))
+            .add_cell(Cell::new(human_bytes(table_stats.size() as f64)));
+        table.add_row(row);
+    }
+
+    let max_widths = table.column_max_content_widths();
+
+    let mut separator = Row::new();
+    for width in max_widths {
+        separator.add_cell(Cell::new("-".repeat(width as usize)));
+    }
+    table.add_row(separator);
+
+    let mut row = Row::new();
+    row.add_cell(Cell::new("Total DB size"));
+    for _ in 0..7 {
+        row.add_cell(Cell::new(""));
+    }
+    row.add_cell(Cell::new(human_bytes(total_size as f64)));
+    table.add_row(row);
+
+    let mut row = Row::new();
+    row.add_cell(Cell::new("Freelist size")).add_cell(Cell::new(stats.freelist_pages));
+    for _ in 0..6 {
+        row.add_cell(Cell::new(""));
+    }
+    row.add_cell(Cell::new(human_bytes(stats.freelist_size as f64)));
+    table.add_row(row);
+
+    table
+}
+
+/// Returns the table of the snapshot stats.
+fn snapshots_table(segments: &[SegmentStats]) -> ComfyTable {
+    let mut table = ComfyTable::new();
+    table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+    table.set_header(["Snapshot Segment", "# Snapshots", "Total Size"]);
+
+    let mut total_size = 0;
+    for segment in segments {
+        total_size += segment.size;
+
+        let mut row = Row::new();
+        row.add_cell(Cell::new(segment.segment))
+            .add_cell(Cell::new(segment.snapshots))
+            .add_cell(Cell::new(human_bytes(segment.size as f64)));
+        table.add_row(row);
+    }
+
+    let mut row = Row::new();
+    row.add_cell(Cell::new("Total snapshots size"))
+        .add_cell(Cell::new(""))
+        .add_cell(Cell::new(human_bytes(total_size as f64)));
+    table.add_row(row);
+
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshot_segment_stats() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(segment_stats(&dir.path().join("missing")).unwrap().is_empty());
+
+        let name = SnapshotSegment::Headers.filename(&(0..=499), &(0..=999));
+        let data = dir.path().join(&name);
+        fs::write(&data, [0; 10]).unwrap();
+        fs::write(data.with_extension("off"), [0; 5]).unwrap();
+        fs::write(data.with_extension("conf"), [0; 1]).unwrap();
+        fs::write(dir.path().join("unrelated"), [0; 100]).unwrap();
+
+        let stats = segment_stats(dir.path()).unwrap();
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].segment, SnapshotSegment::Headers);
+        assert_eq!(stats[0].snapshots, 1);
+        assert_eq!(stats[0].size, 16);
+    }
+}
@@ -8,5 +8,6 @@ pub mod init_cmd;
 pub mod node;
 pub mod p2p;
 pub mod recover;
+pub mod snapshot;
 pub mod stage;
 pub mod test_vectors;
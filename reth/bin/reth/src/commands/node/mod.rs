@@ -199,8 +199,12 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
             ext,
         } = self;
 
-        // set up real database
-        let database = DatabaseBuilder::Real(datadir);
+        // set up real database, or keep it in memory for a dev node
+        let database = if dev.in_memory_db {
+            DatabaseBuilder::InMemory(datadir)
+        } else {
+            DatabaseBuilder::Real(datadir)
+        };
 
         // set up node config
         let mut node_config = NodeConfig {
@@ -0,0 +1,227 @@
+//! `reth snapshot export` command.
+
+use super::manifest::{Manifest, ManifestFile, ManifestSnapshot, DB_DIR_NAME, SNAPSHOTS_DIR_NAME};
+use crate::{
+    args::DatabaseArgs,
+    dirs::{ChainPath, DataDirPath},
+};
+use clap::Parser;
+use reth_db::{
+    cursor::{DbCursorRO, DbCursorRW},
+    database::Database,
+    init_db,
+    mdbx::{DatabaseArguments, MaxReadTransactionDuration, DATA_FILE_NAME},
+    open_db_read_only,
+    snapshot::iter_snapshots,
+    table::{DupSort, Table, TableImporter},
+    tables,
+    transaction::{DbTx, DbTxMut},
+    version::DB_VERSION_FILE_NAME,
+    DatabaseError, TableViewer, Tables,
+};
+use reth_nippy_jar::NippyJar;
+use reth_primitives::{
+    snapshot::SegmentHeader, stage::StageId, BlockNumber, ChainSpec, SnapshotSegment, TxNumber,
+};
+use reth_provider::{ProviderFactory, StageCheckpointReader};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tracing::info;
+
+/// `reth snapshot export` command
+///
+/// Writes the snapshots below the tip of a fully synced database, together with a dump of the
+/// database, into a bundle directory. Headers, transactions, receipts and senders that are already
+/// in the exported snapshots are left out of the dump.
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// The directory to write the bundle to. It must be empty or not exist.
+    #[arg(long, short, value_name = "OUTPUT_DIR")]
+    output: PathBuf,
+}
+
+impl Command {
+    /// Execute `snapshot export` command
+    pub fn execute(
+        self,
+        data_dir: &ChainPath<DataDirPath>,
+        db_args: DatabaseArgs,
+        chain: Arc<ChainSpec>,
+    ) -> eyre::Result<()> {
+        if self.output.exists() && fs::read_dir(&self.output)?.next().is_some() {
+            eyre::bail!("output directory {} is not empty", self.output.display())
+        }
+
+        let db = open_db_read_only(
+            &data_dir.db_path(),
+            DatabaseArguments::default()
+                .log_level(db_args.log_level)
+                .max_read_transaction_duration(Some(MaxReadTransactionDuration::Unbounded)),
+        )?;
+        let factory = ProviderFactory::new(db, chain.clone());
+        let provider = factory.provider()?;
+
+        // The dumped state is only consistent if all stages stopped at the same block.
+        let tip = provider.get_stage_checkpoint(StageId::Finish)?.unwrap_or_default().block_number;
+        for stage in StageId::ALL {
+            let checkpoint = provider.get_stage_checkpoint(stage)?.unwrap_or_default().block_number;
+            if checkpoint != tip {
+                eyre::bail!(
+                    "stage {stage} is at block {checkpoint} instead of {tip}, the node must be fully synced"
+                )
+            }
+        }
+
+        // Snapshots past the tip don't match the dumped state, so they are left out.
+        fs::create_dir_all(self.output.join(SNAPSHOTS_DIR_NAME))?;
+        let mut snapshots = Vec::new();
+        for (segment, ranges) in iter_snapshots(data_dir.snapshots_path())? {
+            for (block_range, tx_range) in ranges {
+                if *block_range.end() > tip {
+                    continue
+                }
+
+                info!(target: "reth::cli", %segment, ?block_range, "Exporting snapshot");
+                let files = self.export_snapshot(
+                    &data_dir.snapshots_path().join(segment.filename(&block_range, &tx_range)),
+                )?;
+                snapshots.push(ManifestSnapshot { segment, block_range, tx_range, files });
+            }
+        }
+
+        let db_dir = self.output.join(DB_DIR_NAME);
+        let output_db = init_db(&db_dir, DatabaseArguments::default())?;
+        let first_block = |segment| first_unsnapshotted(&snapshots, segment).0;
+        let first_tx = |segment| first_unsnapshotted(&snapshots, segment).1;
+        let tx = provider.tx_ref();
+        for table in Tables::ALL {
+            info!(target: "reth::cli", %table, "Exporting table");
+            output_db.update(|output_tx| -> eyre::Result<()> {
+                match table {
+                    Tables::Headers => copy_table_from::<tables::Headers>(
+                        tx,
+                        output_tx,
+                        first_block(SnapshotSegment::Headers),
+                    )?,
+                    Tables::HeaderTD => copy_table_from::<tables::HeaderTD>(
+                        tx,
+                        output_tx,
+                        first_block(SnapshotSegment::Headers),
+                    )?,
+                    Tables::Transactions => copy_table_from::<tables::Transactions>(
+                        tx,
+                        output_tx,
+                        first_tx(SnapshotSegment::Transactions),
+                    )?,
+                    Tables::Receipts => copy_table_from::<tables::Receipts>(
+                        tx,
+                        output_tx,
+                        first_tx(SnapshotSegment::Receipts),
+                    )?,
+                    Tables::TxSenders => copy_table_from::<tables::TxSenders>(
+                        tx,
+                        output_tx,
+                        first_tx(SnapshotSegment::TransactionSenders),
+                    )?,
+                    // All stages are at the tip, so there is no progress of unfinished stages.
+                    Tables::SyncStageProgress => {}
+                    // `CanonicalHeaders` is kept whole, since the genesis hash is checked against
+                    // it on startup. The history tables are sharded by key instead of by block,
+                    // and the changesets are kept since the stages only read them from the
+                    // database.
+                    _ => table.view(&CopyViewer { source: tx, output: output_tx })?,
+                }
+                Ok(())
+            })??;
+        }
+        drop(output_db);
+
+        let db_files = [DATA_FILE_NAME, DB_VERSION_FILE_NAME]
+            .into_iter()
+            .map(|name| ManifestFile::new(&self.output, Path::new(DB_DIR_NAME).join(name)))
+            .collect::<eyre::Result<Vec<_>>>()?;
+
+        let manifest = Manifest {
+            chain_id: chain.chain.id(),
+            genesis_hash: chain.genesis_hash(),
+            tip,
+            snapshots,
+            db_files,
+        };
+        manifest.write(&self.output)?;
+
+        info!(target: "reth::cli", path = ?self.output, tip, "Exported bundle");
+
+        Ok(())
+    }
+
+    /// Copies the files of the snapshot at `path` into the bundle.
+    fn export_snapshot(&self, path: &Path) -> eyre::Result<Vec<ManifestFile>> {
+        let jar = NippyJar::<SegmentHeader>::load(path)?;
+
+        let mut files = Vec::new();
+        for path in
+            [jar.data_path().to_path_buf(), jar.offsets_path(), jar.index_path(), jar.config_path()]
+        {
+            // The index file is only written for snapshots with filters or a PHF.
+            if !path.exists() {
+                continue
+            }
+
+            let relative_path = Path::new(SNAPSHOTS_DIR_NAME)
+                .join(path.file_name().expect("snapshot files have a file name"));
+            fs::copy(&path, self.output.join(&relative_path))?;
+            files.push(ManifestFile::new(&self.output, relative_path)?);
+        }
+
+        Ok(files)
+    }
+}
+
+/// Returns the first block and transaction that are not in the snapshots of the segment.
+fn first_unsnapshotted(
+    snapshots: &[ManifestSnapshot],
+    segment: SnapshotSegment,
+) -> (BlockNumber, TxNumber) {
+    snapshots
+        .iter()
+        .filter(|snapshot| snapshot.segment == segment)
+        .map(|snapshot| (snapshot.block_range.end() + 1, snapshot.tx_range.end() + 1))
+        .max()
+        .unwrap_or_default()
+}
+
+/// Copies the rows of a table from the key `from` onwards.
+fn copy_table_from<T: Table>(
+    source: &impl DbTx,
+    output: &impl DbTxMut,
+    from: T::Key,
+) -> Result<(), DatabaseError> {
+    let mut output_cursor = output.cursor_write::<T>()?;
+    for row in source.cursor_read::<T>()?.walk(Some(from))? {
+        let (key, value) = row?;
+        output_cursor.append(key, value)?;
+    }
+    Ok(())
+}
+
+/// Copies a whole table.
+struct CopyViewer<'a, R, W> {
+    source: &'a R,
+    output: &'a W,
+}
+
+impl<R: DbTx, W: TableImporter> TableViewer<()> for CopyViewer<'_, R, W> {
+    type Error = DatabaseError;
+
+    fn view<T: Table>(&self) -> Result<(), Self::Error> {
+        self.output.import_table::<T, _>(self.source)
+    }
+
+    fn view_dupsort<T: DupSort>(&self) -> Result<(), Self::Error> {
+        self.output.import_dupsort::<T, _>(self.source)
+    }
+}
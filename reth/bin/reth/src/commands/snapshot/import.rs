@@ -0,0 +1,86 @@
+//! `reth snapshot import` command.
+
+use super::manifest::Manifest;
+use crate::{
+    args::DatabaseArgs,
+    dirs::{ChainPath, DataDirPath},
+    init::init_genesis,
+};
+use clap::Parser;
+use reth_db::{init_db, is_database_empty, mdbx::DatabaseArguments};
+use reth_primitives::{
+    stage::{StageCheckpoint, StageId},
+    ChainSpec,
+};
+use reth_provider::{ProviderFactory, StageCheckpointWriter};
+use std::{fs, path::PathBuf, sync::Arc};
+use tracing::info;
+
+/// `reth snapshot import` command
+///
+/// Installs the snapshots and the database dump of a bundle into an empty data directory, and
+/// sets the stage checkpoints to the tip of the bundle so that the pipeline resumes from there.
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// The bundle directory created by `reth snapshot export`.
+    #[arg(value_name = "BUNDLE_DIR")]
+    bundle: PathBuf,
+}
+
+impl Command {
+    /// Execute `snapshot import` command
+    pub fn execute(
+        self,
+        data_dir: &ChainPath<DataDirPath>,
+        db_args: DatabaseArgs,
+        chain: Arc<ChainSpec>,
+    ) -> eyre::Result<()> {
+        let manifest = Manifest::read(&self.bundle)?;
+        manifest.validate_chain(&chain)?;
+
+        info!(target: "reth::cli", path = ?self.bundle, tip = manifest.tip, "Verifying bundle");
+        for file in manifest.files() {
+            file.verify(&self.bundle)?;
+        }
+
+        let db_path = data_dir.db_path();
+        if !is_database_empty(&db_path) {
+            eyre::bail!("database at {} is not empty", db_path.display())
+        }
+
+        let snapshots_path = data_dir.snapshots_path();
+        fs::create_dir_all(&snapshots_path)?;
+        for snapshot in &manifest.snapshots {
+            info!(target: "reth::cli", segment = %snapshot.segment, block_range = ?snapshot.block_range, "Importing snapshot");
+            for file in &snapshot.files {
+                let path = snapshots_path.join(file.name()?);
+                if path.exists() {
+                    eyre::bail!("snapshot file {} already exists", path.display())
+                }
+                fs::copy(self.bundle.join(&file.path), path)?;
+            }
+        }
+
+        info!(target: "reth::cli", path = ?db_path, "Importing database");
+        fs::create_dir_all(&db_path)?;
+        for file in &manifest.db_files {
+            fs::copy(self.bundle.join(&file.path), db_path.join(file.name()?))?;
+        }
+
+        let db =
+            Arc::new(init_db(&db_path, DatabaseArguments::default().log_level(db_args.log_level))?);
+        // Fails if the genesis of the database doesn't match the chain.
+        init_genesis(db.clone(), chain.clone())?;
+
+        let factory = ProviderFactory::new(db, chain);
+        let provider_rw = factory.provider_rw()?;
+        for stage in StageId::ALL {
+            provider_rw.save_stage_checkpoint(stage, StageCheckpoint::new(manifest.tip))?;
+        }
+        provider_rw.commit()?;
+
+        info!(target: "reth::cli", tip = manifest.tip, "Imported bundle");
+
+        Ok(())
+    }
+}
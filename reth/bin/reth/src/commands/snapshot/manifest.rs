@@ -0,0 +1,163 @@
+//! Manifest of a snapshot bundle.
+
+use reth_nippy_jar::checksum::file_checksum;
+use reth_primitives::{BlockNumber, ChainSpec, SnapshotSegment, TxNumber, B256};
+use serde::{Deserialize, Serialize};
+use std::{
+    ffi::OsStr,
+    fs::File,
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+};
+
+/// Name of the manifest file in a bundle.
+pub(crate) const MANIFEST_FILE_NAME: &str = "manifest.json";
+
+/// Directory of a bundle holding the snapshot files.
+pub(crate) const SNAPSHOTS_DIR_NAME: &str = "snapshots";
+
+/// Directory of a bundle holding the database dump.
+pub(crate) const DB_DIR_NAME: &str = "db";
+
+/// Describes the contents of a snapshot bundle: the snapshots, the database dump next to them and
+/// the chain they belong to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct Manifest {
+    /// Chain ID of the bundle.
+    pub(crate) chain_id: u64,
+    /// Genesis hash of the chain.
+    pub(crate) genesis_hash: B256,
+    /// Block at which the state of the database dump is. The pipeline resumes after it.
+    pub(crate) tip: BlockNumber,
+    /// Snapshots of the bundle.
+    pub(crate) snapshots: Vec<ManifestSnapshot>,
+    /// Files of the database dump.
+    pub(crate) db_files: Vec<ManifestFile>,
+}
+
+impl Manifest {
+    /// Returns an error if the bundle was not exported for the given chain.
+    pub(crate) fn validate_chain(&self, chain: &ChainSpec) -> eyre::Result<()> {
+        if self.chain_id != chain.chain.id() {
+            eyre::bail!(
+                "bundle is for chain {}, but the node is configured for chain {}",
+                self.chain_id,
+                chain.chain.id()
+            )
+        }
+        if self.genesis_hash != chain.genesis_hash() {
+            eyre::bail!(
+                "bundle has genesis hash {}, but the chain has genesis hash {}",
+                self.genesis_hash,
+                chain.genesis_hash()
+            )
+        }
+        Ok(())
+    }
+
+    /// Returns all files of the bundle, except the manifest.
+    pub(crate) fn files(&self) -> impl Iterator<Item = &ManifestFile> {
+        self.snapshots.iter().flat_map(|snapshot| snapshot.files.iter()).chain(&self.db_files)
+    }
+
+    /// Reads the manifest of the bundle at the given directory.
+    pub(crate) fn read(bundle: &Path) -> eyre::Result<Self> {
+        let file = File::open(bundle.join(MANIFEST_FILE_NAME))?;
+        Ok(serde_json::from_reader(file)?)
+    }
+
+    /// Writes the manifest to the bundle at the given directory.
+    pub(crate) fn write(&self, bundle: &Path) -> eyre::Result<()> {
+        let file = File::create(bundle.join(MANIFEST_FILE_NAME))?;
+        serde_json::to_writer_pretty(file, self)?;
+        Ok(())
+    }
+}
+
+/// Snapshot of a bundle.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct ManifestSnapshot {
+    /// Segment of the snapshot.
+    pub(crate) segment: SnapshotSegment,
+    /// Block range of the snapshot.
+    pub(crate) block_range: RangeInclusive<BlockNumber>,
+    /// Transaction range of the snapshot.
+    pub(crate) tx_range: RangeInclusive<TxNumber>,
+    /// Data, offsets, index and configuration files of the snapshot.
+    pub(crate) files: Vec<ManifestFile>,
+}
+
+/// File of a bundle.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct ManifestFile {
+    /// Path of the file, relative to the bundle directory.
+    pub(crate) path: PathBuf,
+    /// Size of the file in bytes.
+    pub(crate) size: u64,
+    /// CRC-32C checksum of the file.
+    pub(crate) checksum: u32,
+}
+
+impl ManifestFile {
+    /// Creates a [`ManifestFile`] for the file at `path` relative to the bundle directory.
+    pub(crate) fn new(bundle: &Path, path: PathBuf) -> eyre::Result<Self> {
+        let mut file = File::open(bundle.join(&path))?;
+        let size = file.metadata()?.len();
+        let checksum = file_checksum(&mut file)?;
+        Ok(Self { path, size, checksum })
+    }
+
+    /// Returns the file name of the file. Files are installed by name, so that a manifest can't
+    /// point outside of the directories they are installed into.
+    pub(crate) fn name(&self) -> eyre::Result<&OsStr> {
+        self.path
+            .file_name()
+            .ok_or_else(|| eyre::eyre!("file {} of the bundle has no name", self.path.display()))
+    }
+
+    /// Returns an error if the file in the bundle does not match its size and checksum.
+    pub(crate) fn verify(&self, bundle: &Path) -> eyre::Result<()> {
+        let actual = Self::new(bundle, self.path.clone())?;
+        if actual.size != self.size || actual.checksum != self.checksum {
+            eyre::bail!("file {} of the bundle is corrupted", self.path.display())
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_db::mdbx::DATA_FILE_NAME;
+    use reth_primitives::MAINNET;
+    use std::fs;
+
+    #[test]
+    fn manifest_roundtrip_and_validation() {
+        let bundle = tempfile::tempdir().unwrap();
+        let path = PathBuf::from(DB_DIR_NAME).join(DATA_FILE_NAME);
+        fs::create_dir(bundle.path().join(DB_DIR_NAME)).unwrap();
+        fs::write(bundle.path().join(&path), b"state").unwrap();
+
+        let manifest = Manifest {
+            chain_id: MAINNET.chain.id(),
+            genesis_hash: MAINNET.genesis_hash(),
+            tip: 10,
+            snapshots: Vec::new(),
+            db_files: vec![ManifestFile::new(bundle.path(), path.clone()).unwrap()],
+        };
+        manifest.write(bundle.path()).unwrap();
+
+        let read = Manifest::read(bundle.path()).unwrap();
+        assert_eq!(read, manifest);
+        assert!(read.validate_chain(&MAINNET).is_ok());
+        assert!(read.files().all(|file| file.verify(bundle.path()).is_ok()));
+
+        let mut other_chain = manifest.clone();
+        other_chain.chain_id += 1;
+        assert!(other_chain.validate_chain(&MAINNET).is_err());
+
+        fs::write(bundle.path().join(&path), b"stale").unwrap();
+        assert!(read.files().all(|file| file.verify(bundle.path()).is_err()));
+    }
+}
@@ -0,0 +1,71 @@
+//! `reth snapshot` command.
+
+use crate::{
+    args::{
+        utils::{chain_help, genesis_value_parser, SUPPORTED_CHAINS},
+        DatabaseArgs,
+    },
+    dirs::{DataDirPath, MaybePlatformPath},
+};
+use clap::{Parser, Subcommand};
+use reth_primitives::ChainSpec;
+use std::sync::Arc;
+
+mod export;
+mod import;
+mod manifest;
+
+/// `reth snapshot` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// The path to the data dir for all reth files and subdirectories.
+    ///
+    /// Defaults to the OS-specific data directory:
+    ///
+    /// - Linux: `$XDG_DATA_HOME/reth/` or `$HOME/.local/share/reth/`
+    /// - Windows: `{FOLDERID_RoamingAppData}/reth/`
+    /// - macOS: `$HOME/Library/Application Support/reth/`
+    #[arg(long, value_name = "DATA_DIR", verbatim_doc_comment, default_value_t, global = true)]
+    datadir: MaybePlatformPath<DataDirPath>,
+
+    /// The chain this node is running.
+    ///
+    /// Possible values are either a built-in chain or the path to a chain specification file.
+    #[arg(
+        long,
+        value_name = "CHAIN_OR_PATH",
+        long_help = chain_help(),
+        default_value = SUPPORTED_CHAINS[0],
+        value_parser = genesis_value_parser,
+        global = true,
+    )]
+    chain: Arc<ChainSpec>,
+
+    #[clap(flatten)]
+    db: DatabaseArgs,
+
+    #[clap(subcommand)]
+    command: Subcommands,
+}
+
+/// `reth snapshot` subcommands
+#[derive(Subcommand, Debug)]
+pub enum Subcommands {
+    /// Exports the snapshots and a dump of the database into a bundle.
+    Export(export::Command),
+    /// Bootstraps a node from a bundle created by `reth snapshot export`.
+    Import(import::Command),
+}
+
+impl Command {
+    /// Execute `snapshot` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        // add network name to data dir
+        let data_dir = self.datadir.unwrap_or_chain_default(self.chain.chain);
+
+        match self.command {
+            Subcommands::Export(command) => command.execute(&data_dir, self.db, self.chain),
+            Subcommands::Import(command) => command.execute(&data_dir, self.db, self.chain),
+        }
+    }
+}
@@ -28,6 +28,7 @@ use reth_provider::{
     ChainSpecProvider, DisplayBlocksChain, ExecutorFactory, HeaderProvider, ProviderError,
 };
 use reth_stages::{MetricEvent, MetricEventsSender};
+use reth_trie::ParallelStateRoot;
 use std::{
     collections::{BTreeMap, HashSet},
     sync::Arc,
@@ -1115,16 +1116,14 @@ impl<DB: Database, EF: ExecutorFactory> BlockchainTree<DB, EF> {
             }
             None => {
                 debug!(target: "blockchain_tree", blocks = ?block_hash_numbers, "Recomputing state root for insert");
-                let provider = self
-                    .externals
-                    .provider_factory
-                    .provider()?
-                    // State root calculation can take a while, and we're sure no write transaction
-                    // will be open in parallel. See https://github.com/paradigmxyz/reth/issues/6168.
-                    .disable_long_read_transaction_safety();
-                let (state_root, trie_updates) = hashed_state
-                    .state_root_with_updates(provider.tx_ref())
-                    .map_err(Into::<DatabaseError>::into)?;
+                // The storage roots are computed in parallel, each over its own read transaction.
+                // We're sure no write transaction will be open in parallel.
+                let (state_root, trie_updates) = ParallelStateRoot::new(
+                    self.externals.provider_factory.db_ref(),
+                    hashed_state.clone(),
+                )
+                .root_with_updates()
+                .map_err(Into::<DatabaseError>::into)?;
                 let tip = blocks.tip();
                 if state_root != tip.state_root {
                     return Err(RethError::Provider(ProviderError::StateRootMismatch(Box::new(
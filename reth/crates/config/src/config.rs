@@ -72,6 +72,10 @@ pub struct StageConfig {
     pub index_account_history: IndexHistoryConfig,
     /// Index Storage History stage configuration.
     pub index_storage_history: IndexHistoryConfig,
+    /// Index Address Transactions stage configuration.
+    pub index_address_transactions: IndexAddressTransactionsConfig,
+    /// Index Logs stage configuration.
+    pub index_logs: IndexLogsConfig,
 }
 
 /// Header stage configuration.
@@ -187,6 +191,9 @@ pub struct ExecutionConfig {
     pub max_cumulative_gas: Option<u64>,
     /// The maximum time spent on blocks processing before the execution stage commits.
     pub max_duration: Option<Duration>,
+    /// The number of threads prefetching the state of the upcoming transactions, disabled if
+    /// `None`.
+    pub prefetch_workers: Option<usize>,
 }
 
 impl Default for ExecutionConfig {
@@ -198,6 +205,7 @@ impl Default for ExecutionConfig {
             max_cumulative_gas: Some(30_000_000 * 50_000),
             // 10 minutes
             max_duration: Some(Duration::from_secs(10 * 60)),
+            prefetch_workers: None,
         }
     }
 }
@@ -262,6 +270,38 @@ impl Default for IndexHistoryConfig {
     }
 }
 
+/// Index Address Transactions stage configuration.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct IndexAddressTransactionsConfig {
+    /// Whether the stage is enabled. The stage is not part of the default pipeline.
+    pub enabled: bool,
+    /// The maximum number of blocks to process before committing progress to the database.
+    pub commit_threshold: u64,
+}
+
+impl Default for IndexAddressTransactionsConfig {
+    fn default() -> Self {
+        Self { enabled: false, commit_threshold: 100_000 }
+    }
+}
+
+/// Index Logs stage configuration.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct IndexLogsConfig {
+    /// Whether the stage is enabled. The stage is not part of the default pipeline.
+    pub enabled: bool,
+    /// The maximum number of blocks to process before committing progress to the database.
+    pub commit_threshold: u64,
+}
+
+impl Default for IndexLogsConfig {
+    fn default() -> Self {
+        Self { enabled: false, commit_threshold: 100_000 }
+    }
+}
+
 /// Pruning configuration.
 #[derive(Debug, Clone, Deserialize, PartialEq, Serialize)]
 #[serde(default)]
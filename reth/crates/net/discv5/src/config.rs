@@ -0,0 +1,186 @@
+//! Configuration of the discv5 service.
+
+use discv5::{
+    enr::{CombinedKey, Enr},
+    ListenConfig,
+};
+use reth_discv4::DEFAULT_DISCOVERY_PORT;
+use reth_primitives::ForkId;
+use std::{
+    fmt,
+    net::{Ipv4Addr, SocketAddr, SocketAddrV4},
+    time::Duration,
+};
+
+/// The default port for discv5. Distinct from the default discv5 port of consensus layer clients
+/// (9000), so both can run on the same host.
+pub const DEFAULT_DISCOVERY_V5_PORT: u16 = 9200;
+
+/// The default address for discv5: `0.0.0.0:9200`.
+pub const DEFAULT_DISCOVERY_V5_ADDR: SocketAddr =
+    SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, DEFAULT_DISCOVERY_V5_PORT));
+
+/// The default interval between two random lookups.
+pub const DEFAULT_LOOKUP_INTERVAL: Duration = Duration::from_secs(20);
+
+/// The ENR key under which the [`ForkId`] of the node is advertised, see
+/// <https://eips.ethereum.org/EIPS/eip-2124> and <https://eips.ethereum.org/EIPS/eip-868>.
+pub const ETH_FORK_ID_KEY: &str = "eth";
+
+/// Configuration of the discv5 service.
+#[derive(Clone)]
+pub struct Discv5Config {
+    /// Configuration of the underlying [`discv5::Discv5`] service. If not set, the default
+    /// configuration listening on the discovery address is used.
+    pub(crate) discv5_config: Option<discv5::Config>,
+    /// The address to listen on for discv5 traffic.
+    pub(crate) discovery_addr: SocketAddr,
+    /// The RLPx port advertised in the local ENR.
+    pub(crate) tcp_port: u16,
+    /// Nodes to boot from.
+    pub(crate) bootstrap_nodes: Vec<Enr<CombinedKey>>,
+    /// The ENR key of the [`ForkId`] entry. Discovered nodes without this entry are ignored.
+    pub(crate) fork_id_key: &'static str,
+    /// The [`ForkId`] advertised in the local ENR.
+    pub(crate) fork_id: Option<ForkId>,
+    /// The interval between two random lookups.
+    pub(crate) lookup_interval: Duration,
+}
+
+impl Discv5Config {
+    /// Returns a new [`Discv5ConfigBuilder`].
+    pub fn builder() -> Discv5ConfigBuilder {
+        Discv5ConfigBuilder::default()
+    }
+
+    /// Returns the address discv5 listens on.
+    pub fn discovery_addr(&self) -> SocketAddr {
+        self.discovery_addr
+    }
+
+    /// Sets the [`ForkId`] advertised in the local ENR.
+    pub fn set_fork_id(&mut self, fork_id: ForkId) {
+        self.fork_id = Some(fork_id);
+    }
+
+    /// Sets the RLPx port advertised in the local ENR.
+    pub fn set_tcp_port(&mut self, port: u16) {
+        self.tcp_port = port;
+    }
+
+    /// Returns the configuration of the underlying [`discv5::Discv5`] service.
+    pub(crate) fn take_discv5_config(&mut self) -> discv5::Config {
+        self.discv5_config.take().unwrap_or_else(|| {
+            discv5::ConfigBuilder::new(ListenConfig::from_ip(
+                self.discovery_addr.ip(),
+                self.discovery_addr.port(),
+            ))
+            .build()
+        })
+    }
+}
+
+impl Default for Discv5Config {
+    fn default() -> Self {
+        Discv5ConfigBuilder::default().build()
+    }
+}
+
+impl fmt::Debug for Discv5Config {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Discv5Config")
+            .field("discovery_addr", &self.discovery_addr)
+            .field("tcp_port", &self.tcp_port)
+            .field("bootstrap_nodes", &self.bootstrap_nodes)
+            .field("fork_id_key", &self.fork_id_key)
+            .field("fork_id", &self.fork_id)
+            .field("lookup_interval", &self.lookup_interval)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Builder for [`Discv5Config`].
+#[derive(Clone, Debug)]
+pub struct Discv5ConfigBuilder {
+    config: Discv5Config,
+}
+
+impl Discv5ConfigBuilder {
+    /// Sets the configuration of the underlying [`discv5::Discv5`] service.
+    ///
+    /// Note: this takes precedence over the [discovery address](Self::discovery_addr) as the
+    /// listen address of the service.
+    pub fn discv5_config(mut self, discv5_config: discv5::Config) -> Self {
+        self.config.discv5_config = Some(discv5_config);
+        self
+    }
+
+    /// Sets the address to listen on for discv5 traffic.
+    pub fn discovery_addr(mut self, discovery_addr: SocketAddr) -> Self {
+        self.config.discovery_addr = discovery_addr;
+        self
+    }
+
+    /// Sets the port to listen on for discv5 traffic.
+    pub fn discovery_port(mut self, port: u16) -> Self {
+        self.config.discovery_addr.set_port(port);
+        self
+    }
+
+    /// Sets the RLPx port advertised in the local ENR.
+    pub fn tcp_port(mut self, port: u16) -> Self {
+        self.config.tcp_port = port;
+        self
+    }
+
+    /// Adds a boot node.
+    pub fn add_boot_node(mut self, node: Enr<CombinedKey>) -> Self {
+        self.config.bootstrap_nodes.push(node);
+        self
+    }
+
+    /// Adds multiple boot nodes.
+    pub fn add_boot_nodes(mut self, nodes: impl IntoIterator<Item = Enr<CombinedKey>>) -> Self {
+        self.config.bootstrap_nodes.extend(nodes);
+        self
+    }
+
+    /// Sets the ENR key of the [`ForkId`] entry, `eth` by default.
+    pub fn fork_id_key(mut self, key: &'static str) -> Self {
+        self.config.fork_id_key = key;
+        self
+    }
+
+    /// Sets the [`ForkId`] advertised in the local ENR.
+    pub fn fork_id(mut self, fork_id: ForkId) -> Self {
+        self.config.fork_id = Some(fork_id);
+        self
+    }
+
+    /// Sets the interval between two random lookups.
+    pub fn lookup_interval(mut self, interval: Duration) -> Self {
+        self.config.lookup_interval = interval;
+        self
+    }
+
+    /// Returns the configured [`Discv5Config`].
+    pub fn build(self) -> Discv5Config {
+        self.config
+    }
+}
+
+impl Default for Discv5ConfigBuilder {
+    fn default() -> Self {
+        Self {
+            config: Discv5Config {
+                discv5_config: None,
+                discovery_addr: DEFAULT_DISCOVERY_V5_ADDR,
+                tcp_port: DEFAULT_DISCOVERY_PORT,
+                bootstrap_nodes: Vec::new(),
+                fork_id_key: ETH_FORK_ID_KEY,
+                fork_id: None,
+                lookup_interval: DEFAULT_LOOKUP_INTERVAL,
+            },
+        }
+    }
+}
@@ -0,0 +1,18 @@
+//! Error types that can occur in this crate.
+
+/// Errors that can occur when launching the discv5 service.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// The secret key could not be converted into a discv5 key.
+    #[error("failed to convert secret key into discv5 key")]
+    InvalidSecretKey,
+    /// Failed to build the local ENR.
+    #[error("failed to build local enr: {0:?}")]
+    Enr(discv5::enr::EnrError),
+    /// Failed to create the discv5 service.
+    #[error("failed to create discv5 service: {0}")]
+    Init(&'static str),
+    /// Failed to start the discv5 service.
+    #[error("failed to start discv5 service: {0:?}")]
+    Start(discv5::Error),
+}
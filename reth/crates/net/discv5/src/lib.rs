@@ -0,0 +1,327 @@
+//! Discovery v5 support: <https://github.com/ethereum/devp2p/blob/master/discv5/discv5.md>
+//!
+//! This wraps the [`discv5`] implementation of sigp, which manages the routing table and drives
+//! the UDP socket, and translates the discovered ENRs into [`NodeRecord`]s that can be dialed over
+//! RLPx.
+//!
+//! Discv5 is shared by the execution and consensus layer networks, so only nodes that advertise
+//! the `eth` [`ForkId`] entry in their ENR, see <https://eips.ethereum.org/EIPS/eip-868>, are
+//! reported. Whether the advertised [`ForkId`] is compatible with the local chain is left to the
+//! network, same as for the fork ids retrieved via discv4.
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+use alloy_rlp::Decodable;
+use discv5::{
+    enr::{CombinedKey, CombinedPublicKey, Enr, EnrPublicKey, NodeId},
+    Event,
+};
+use reth_discv4::EnrForkIdEntry;
+use reth_primitives::{keccak256, ForkId, NodeRecord, PeerId};
+use secp256k1::SecretKey;
+use std::{fmt, net::IpAddr, sync::Arc};
+use tokio::{sync::mpsc, task::JoinHandle};
+use tracing::{debug, trace, warn};
+
+pub mod config;
+mod error;
+
+pub use config::{Discv5Config, Discv5ConfigBuilder};
+pub use discv5;
+pub use error::Error;
+
+/// Handle to the running discv5 service.
+///
+/// The periodic random lookups are stopped once this is dropped.
+pub struct Discv5 {
+    /// The underlying discv5 service.
+    discv5: Arc<discv5::Discv5>,
+    /// The ENR key of the [`ForkId`] entry.
+    fork_id_key: &'static str,
+    /// The task performing the random lookups.
+    lookup_task: JoinHandle<()>,
+}
+
+impl Discv5 {
+    /// Starts the discv5 service with the given secret key and configuration.
+    ///
+    /// Returns the handle to the service and the stream of events emitted by it, which should be
+    /// passed to [`Discv5::on_discv5_update`].
+    pub async fn start(
+        sk: &SecretKey,
+        mut config: Discv5Config,
+    ) -> Result<(Self, mpsc::Receiver<Event>), Error> {
+        let discv5_config = config.take_discv5_config();
+        let Discv5Config {
+            discovery_addr,
+            tcp_port,
+            bootstrap_nodes,
+            fork_id_key,
+            fork_id,
+            lookup_interval,
+            ..
+        } = config;
+
+        let mut secret_key = sk.secret_bytes();
+        let enr_key = CombinedKey::secp256k1_from_bytes(&mut secret_key)
+            .map_err(|_| Error::InvalidSecretKey)?;
+
+        let local_enr = {
+            let mut builder = Enr::builder();
+            // the unspecified address is replaced by the external address voted by the peers
+            let ip = discovery_addr.ip();
+            if !ip.is_unspecified() {
+                builder.ip(ip);
+            }
+            match ip {
+                IpAddr::V4(_) => builder.udp4(discovery_addr.port()).tcp4(tcp_port),
+                IpAddr::V6(_) => builder.udp6(discovery_addr.port()).tcp6(tcp_port),
+            };
+            if let Some(fork_id) = fork_id {
+                // use forward-compatible forkid entry
+                builder.add_value(fork_id_key, &EnrForkIdEntry::from(fork_id));
+            }
+            builder.build(&enr_key).map_err(Error::Enr)?
+        };
+
+        let mut discv5 =
+            discv5::Discv5::new(local_enr, enr_key, discv5_config).map_err(Error::Init)?;
+        discv5.start().await.map_err(Error::Start)?;
+
+        for node in bootstrap_nodes {
+            let node_id = node.node_id();
+            if let Err(err) = discv5.add_enr(node) {
+                warn!(target: "net::discv5", %node_id, %err, "Failed to add boot node");
+            }
+        }
+
+        let events = discv5.event_stream().await.map_err(Error::Start)?;
+
+        let discv5 = Arc::new(discv5);
+        let lookup_task = tokio::spawn({
+            let discv5 = discv5.clone();
+            let mut interval = tokio::time::interval(lookup_interval);
+            async move {
+                loop {
+                    interval.tick().await;
+                    let target = NodeId::random();
+                    trace!(target: "net::discv5", %target, "Starting random lookup");
+                    match discv5.find_node(target).await {
+                        Ok(nodes) => {
+                            trace!(target: "net::discv5", %target, found = nodes.len(), "Random lookup finished")
+                        }
+                        Err(err) => {
+                            debug!(target: "net::discv5", %target, ?err, "Random lookup failed")
+                        }
+                    }
+                }
+            }
+        });
+
+        Ok((Self { discv5, fork_id_key, lookup_task }, events))
+    }
+
+    /// Returns the local ENR.
+    pub fn local_enr(&self) -> Enr<CombinedKey> {
+        self.discv5.local_enr()
+    }
+
+    /// Adds the node to the routing table.
+    pub fn add_node(&self, node: Enr<CombinedKey>) {
+        let node_id = node.node_id();
+        if let Err(err) = self.discv5.add_enr(node) {
+            debug!(target: "net::discv5", %node_id, %err, "Failed to add node");
+        }
+    }
+
+    /// Updates the [`ForkId`] advertised in the local ENR.
+    pub fn set_fork_id(&self, fork_id: ForkId) {
+        // use forward-compatible forkid entry
+        if let Err(err) = self.discv5.enr_insert(self.fork_id_key, &EnrForkIdEntry::from(fork_id)) {
+            warn!(target: "net::discv5", ?err, "Failed to update fork id in local enr");
+        }
+    }
+
+    /// Bans the [`IpAddr`] indefinitely.
+    pub fn ban_ip(&self, ip: IpAddr) {
+        self.discv5.ban_ip(ip, None)
+    }
+
+    /// Bans the [`PeerId`] and [`IpAddr`] indefinitely.
+    pub fn ban(&self, peer_id: PeerId, ip: IpAddr) {
+        // the node id is the hash of the uncompressed public key
+        self.discv5.ban_node(&NodeId::new(&keccak256(peer_id).0), None);
+        self.ban_ip(ip)
+    }
+
+    /// Processes an event emitted by the discv5 service.
+    ///
+    /// Returns the discovered peer, if the event discovered a node that supports the execution
+    /// layer network.
+    pub fn on_discv5_update(&self, event: Event) -> Option<DiscoveredPeer> {
+        match event {
+            Event::Discovered(enr) | Event::SessionEstablished(enr, _) => {
+                let peer = DiscoveredPeer::try_from_enr(&enr, self.fork_id_key);
+                if peer.is_none() {
+                    trace!(target: "net::discv5", node_id = %enr.node_id(), "Ignoring node without eth enr entry");
+                }
+                peer
+            }
+            _ => None,
+        }
+    }
+}
+
+impl Drop for Discv5 {
+    fn drop(&mut self) {
+        self.lookup_task.abort();
+    }
+}
+
+impl fmt::Debug for Discv5 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Discv5")
+            .field("local_enr", &self.discv5.local_enr())
+            .field("fork_id_key", &self.fork_id_key)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A node discovered via discv5.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiscoveredPeer {
+    /// The record of the node.
+    pub node_record: NodeRecord,
+    /// The [`ForkId`] advertised by the node.
+    pub fork_id: ForkId,
+}
+
+impl DiscoveredPeer {
+    /// Converts the ENR into a [`DiscoveredPeer`].
+    ///
+    /// Returns `None` if the ENR is not signed with a secp256k1 key, does not advertise an RLPx
+    /// address, or does not have a valid [`ForkId`] entry under the given key.
+    pub fn try_from_enr(enr: &Enr<CombinedKey>, fork_id_key: &str) -> Option<Self> {
+        let id = match enr.public_key() {
+            CombinedPublicKey::Secp256k1(pk) => PeerId::from_slice(&pk.encode_uncompressed()),
+            _ => return None,
+        };
+
+        let node_record = NodeRecord {
+            address: enr.ip4().map(IpAddr::from).or_else(|| enr.ip6().map(IpAddr::from))?,
+            tcp_port: enr.tcp4().or_else(|| enr.tcp6())?,
+            udp_port: enr.udp4().or_else(|| enr.udp6())?,
+            id,
+        }
+        .into_ipv4_mapped();
+
+        let mut fork_id_entry = enr.get_raw_rlp(fork_id_key)?;
+        let EnrForkIdEntry { fork_id } = EnrForkIdEntry::decode(&mut fork_id_entry).ok()?;
+
+        Some(Self { node_record, fork_id })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::thread_rng;
+    use reth_primitives::ForkHash;
+    use secp256k1::SECP256K1;
+    use std::net::{Ipv4Addr, SocketAddr};
+
+    const FORK_ID: ForkId = ForkId { hash: ForkHash([0xdc, 0xe9, 0x6c, 0x2d]), next: 0 };
+
+    fn enr_key(sk: &SecretKey) -> CombinedKey {
+        CombinedKey::secp256k1_from_bytes(&mut sk.secret_bytes()).unwrap()
+    }
+
+    #[test]
+    fn enr_to_discovered_peer() {
+        let (sk, _) = SECP256K1.generate_keypair(&mut thread_rng());
+        let enr = Enr::builder()
+            .ip4(Ipv4Addr::LOCALHOST)
+            .udp4(30303)
+            .tcp4(30304)
+            .add_value("eth", &EnrForkIdEntry::from(FORK_ID))
+            .build(&enr_key(&sk))
+            .unwrap();
+
+        let id =
+            NodeRecord::from_secret_key(SocketAddr::from((Ipv4Addr::LOCALHOST, 30303)), &sk).id;
+        assert_eq!(
+            DiscoveredPeer::try_from_enr(&enr, "eth"),
+            Some(DiscoveredPeer {
+                node_record: NodeRecord {
+                    address: Ipv4Addr::LOCALHOST.into(),
+                    tcp_port: 30304,
+                    udp_port: 30303,
+                    id,
+                },
+                fork_id: FORK_ID,
+            })
+        );
+    }
+
+    #[test]
+    fn ignores_enr_without_eth_entry() {
+        let (sk, _) = SECP256K1.generate_keypair(&mut thread_rng());
+        let enr = Enr::builder()
+            .ip4(Ipv4Addr::LOCALHOST)
+            .udp4(9000)
+            .tcp4(9000)
+            .build(&enr_key(&sk))
+            .unwrap();
+        assert_eq!(DiscoveredPeer::try_from_enr(&enr, "eth"), None);
+
+        // the fork id is advertised under a different key
+        let enr = Enr::builder()
+            .ip4(Ipv4Addr::LOCALHOST)
+            .udp4(9000)
+            .tcp4(9000)
+            .add_value("opstack", &EnrForkIdEntry::from(FORK_ID))
+            .build(&enr_key(&sk))
+            .unwrap();
+        assert_eq!(DiscoveredPeer::try_from_enr(&enr, "eth"), None);
+        assert!(DiscoveredPeer::try_from_enr(&enr, "opstack").is_some());
+    }
+
+    #[test]
+    fn ignores_enr_without_tcp_port() {
+        let (sk, _) = SECP256K1.generate_keypair(&mut thread_rng());
+        let enr = Enr::builder()
+            .ip4(Ipv4Addr::LOCALHOST)
+            .udp4(30303)
+            .add_value("eth", &EnrForkIdEntry::from(FORK_ID))
+            .build(&enr_key(&sk))
+            .unwrap();
+        assert_eq!(DiscoveredPeer::try_from_enr(&enr, "eth"), None);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn start_advertises_fork_id() {
+        let (sk, _) = SECP256K1.generate_keypair(&mut thread_rng());
+        let config = Discv5Config::builder()
+            .discovery_addr(SocketAddr::from((Ipv4Addr::LOCALHOST, 0)))
+            .tcp_port(30303)
+            .fork_id(FORK_ID)
+            .build();
+        let (discv5, _events) = Discv5::start(&sk, config).await.unwrap();
+
+        let local_enr = discv5.local_enr();
+        let mut entry = local_enr.get_raw_rlp("eth").unwrap();
+        assert_eq!(EnrForkIdEntry::decode(&mut entry).unwrap(), EnrForkIdEntry::from(FORK_ID));
+        assert_eq!(local_enr.tcp4(), Some(30303));
+
+        let fork_id = ForkId { hash: ForkHash([0xde, 0xad, 0xbe, 0xef]), next: 1 };
+        discv5.set_fork_id(fork_id);
+        let local_enr = discv5.local_enr();
+        let mut entry = local_enr.get_raw_rlp("eth").unwrap();
+        assert_eq!(EnrForkIdEntry::decode(&mut entry).unwrap(), EnrForkIdEntry::from(fork_id));
+    }
+}
@@ -61,10 +61,13 @@ where
             "sending eth status to peer"
         );
 
+        let version = EthVersion::try_from(status.version)?;
+
         // we need to encode and decode here on our own because we don't have an `EthStream` yet
         // The max length for a status with TTD is: <msg id = 1 byte> + <rlp(status) = 88 byte>
         let mut our_status_bytes = BytesMut::with_capacity(1 + 88);
-        ProtocolMessage::from(EthMessage::Status(status)).encode(&mut our_status_bytes);
+        ProtocolMessage::from(EthMessage::Status(status))
+            .encode_message(version, &mut our_status_bytes);
         let our_status_bytes = our_status_bytes.freeze();
         self.inner.send(our_status_bytes).await?;
 
@@ -83,7 +86,6 @@ where
             return Err(EthStreamError::MessageTooBig(their_msg.len()))
         }
 
-        let version = EthVersion::try_from(status.version)?;
         let msg = match ProtocolMessage::decode_message(version, &mut their_msg.as_ref()) {
             Ok(m) => m,
             Err(err) => {
@@ -297,11 +299,12 @@ where
             return Err(EthStreamError::EthHandshakeError(EthHandshakeError::StatusNotInHandshake))
         }
 
+        let this = self.project();
         let mut bytes = BytesMut::new();
-        ProtocolMessage::from(item).encode(&mut bytes);
+        ProtocolMessage::from(item).encode_message(*this.version, &mut bytes);
         let bytes = bytes.freeze();
 
-        self.project().inner.start_send(bytes)?;
+        this.inner.start_send(bytes)?;
 
         Ok(())
     }
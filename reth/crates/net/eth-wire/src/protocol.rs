@@ -1,6 +1,6 @@
 //! A Protocol defines a P2P subprotocol in a RLPx connection
 
-use crate::{capability::Capability, EthMessageID, EthVersion};
+use crate::{capability::Capability, EthMessageID, EthVersion, SnapMessageId};
 
 /// Type that represents a [Capability] and the number of messages it uses.
 ///
@@ -45,6 +45,16 @@ impl Protocol {
         Self::eth(EthVersion::Eth68)
     }
 
+    /// Returns the [EthVersion::Eth69] capability.
+    pub const fn eth_69() -> Self {
+        Self::eth(EthVersion::Eth69)
+    }
+
+    /// Returns the `snap/1` protocol.
+    pub const fn snap_1() -> Self {
+        Self::new(Capability::snap_1(), SnapMessageId::max() + 1)
+    }
+
     /// Consumes the type and returns a tuple of the [Capability] and number of messages.
     #[inline]
     pub(crate) fn split(self) -> (Capability, u8) {
@@ -147,7 +147,7 @@ impl NewPooledTransactionHashes {
                 matches!(version, EthVersion::Eth67 | EthVersion::Eth66)
             }
             NewPooledTransactionHashes::Eth68(_) => {
-                matches!(version, EthVersion::Eth68)
+                matches!(version, EthVersion::Eth68 | EthVersion::Eth69)
             }
         }
     }
@@ -1,4 +1,4 @@
-//! Implements Ethereum wire protocol for versions 66, 67, and 68.
+//! Implements Ethereum wire protocol for versions 66, 67, 68 and 69.
 //! Defines structs/enums for messages, request-response pairs, and broadcasts.
 //! Handles compatibility with [`EthVersion`].
 //!
@@ -9,7 +9,8 @@
 use super::{
     broadcast::NewBlockHashes, BlockBodies, BlockHeaders, GetBlockBodies, GetBlockHeaders,
     GetNodeData, GetPooledTransactions, GetReceipts, NewBlock, NewPooledTransactionHashes66,
-    NewPooledTransactionHashes68, NodeData, PooledTransactions, Receipts, Status, Transactions,
+    NewPooledTransactionHashes68, NodeData, PooledTransactions, Receipts, Receipts69, Status,
+    StatusEth69, Transactions,
 };
 use crate::{errors::EthStreamError, EthVersion, SharedTransactions};
 use alloy_rlp::{length_of_length, Decodable, Encodable, Header};
@@ -38,7 +39,13 @@ impl ProtocolMessage {
         let message_type = EthMessageID::decode(buf)?;
 
         let message = match message_type {
-            EthMessageID::Status => EthMessage::Status(Status::decode(buf)?),
+            EthMessageID::Status => {
+                if version >= EthVersion::Eth69 {
+                    EthMessage::Status(StatusEth69::decode(buf)?.into())
+                } else {
+                    EthMessage::Status(Status::decode(buf)?)
+                }
+            }
             EthMessageID::NewBlockHashes => {
                 EthMessage::NewBlockHashes(NewBlockHashes::decode(buf)?)
             }
@@ -104,12 +111,37 @@ impl ProtocolMessage {
                 EthMessage::GetReceipts(request_pair)
             }
             EthMessageID::Receipts => {
-                let request_pair = RequestPair::<Receipts>::decode(buf)?;
-                EthMessage::Receipts(request_pair)
+                if version >= EthVersion::Eth69 {
+                    let RequestPair { request_id, message } =
+                        RequestPair::<Receipts69>::decode(buf)?;
+                    EthMessage::Receipts(RequestPair { request_id, message: message.into() })
+                } else {
+                    let request_pair = RequestPair::<Receipts>::decode(buf)?;
+                    EthMessage::Receipts(request_pair)
+                }
             }
         };
         Ok(ProtocolMessage { message_type, message })
     }
+
+    /// Encodes the message for the given version.
+    ///
+    /// This is the same as the [Encodable] implementation, except for `eth/69`, whose [Status]
+    /// has no total difficulty and whose [Receipts] have no bloom.
+    pub fn encode_message(self, version: EthVersion, out: &mut dyn BufMut) {
+        if version < EthVersion::Eth69 {
+            return self.encode(out)
+        }
+
+        self.message_type.encode(out);
+        match self.message {
+            EthMessage::Status(status) => StatusEth69::from(status).encode(out),
+            EthMessage::Receipts(RequestPair { request_id, message }) => {
+                RequestPair { request_id, message: Receipts69::from(message) }.encode(out)
+            }
+            message => message.encode(out),
+        }
+    }
 }
 
 impl Encodable for ProtocolMessage {
@@ -158,7 +190,7 @@ impl From<EthBroadcastMessage> for ProtocolBroadcastMessage {
     }
 }
 
-/// Represents a message in the eth wire protocol, versions 66, 67 and 68.
+/// Represents a message in the eth wire protocol, versions 66, 67, 68 and 69.
 ///
 /// The ethereum wire protocol is a set of messages that are broadcast to the network in two
 /// styles:
@@ -175,6 +207,9 @@ impl From<EthBroadcastMessage> for ProtocolBroadcastMessage {
 /// The `eth/68` changes only NewPooledTransactionHashes to include `types` and `sized`. For
 /// it, NewPooledTransactionHashes is renamed as [`NewPooledTransactionHashes66`] and
 /// [`NewPooledTransactionHashes68`] is defined.
+///
+/// The `eth/69` removes the total difficulty from the [`Status`] and the bloom from the
+/// [`Receipts`], which only changes their encoding, see [`ProtocolMessage::encode_message`].
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum EthMessage {
@@ -477,10 +512,10 @@ where
 mod tests {
     use crate::{
         errors::EthStreamError, types::message::RequestPair, EthMessage, EthMessageID, GetNodeData,
-        NodeData, ProtocolMessage,
+        NodeData, ProtocolMessage, Receipts, Status,
     };
     use alloy_rlp::{Decodable, Encodable};
-    use reth_primitives::hex;
+    use reth_primitives::{hex, Receipt, U256};
 
     fn encode<T: Encodable>(value: T) -> Vec<u8> {
         let mut buf = vec![];
@@ -507,6 +542,27 @@ mod tests {
         assert!(matches!(msg, Err(EthStreamError::EthInvalidMessageError(..))));
     }
 
+    #[test]
+    fn eth69_messages_roundtrip() {
+        let version = crate::EthVersion::Eth69;
+        let status =
+            Status { version: version as u8, total_difficulty: U256::ZERO, ..Default::default() };
+        let receipts = Receipts(vec![vec![Receipt::default().with_bloom()]]);
+
+        for message in [
+            EthMessage::Status(status),
+            EthMessage::Receipts(RequestPair { request_id: 1337, message: receipts }),
+        ] {
+            let legacy = encode(ProtocolMessage::from(message.clone()));
+            let mut buf = vec![];
+            ProtocolMessage::from(message.clone()).encode_message(version, &mut buf);
+            assert!(buf.len() < legacy.len());
+
+            let decoded = ProtocolMessage::decode_message(version, &mut &buf[..]).unwrap();
+            assert_eq!(decoded.message, message);
+        }
+    }
+
     #[test]
     fn request_pair_encode() {
         let request_pair = RequestPair { request_id: 1337, message: vec![5u8] };
@@ -1,8 +1,10 @@
 //! Implements the `GetReceipts` and `Receipts` message types.
 
-use alloy_rlp::{RlpDecodableWrapper, RlpEncodableWrapper};
+use alloy_rlp::{
+    length_of_length, Decodable, Encodable, Header, RlpDecodableWrapper, RlpEncodableWrapper,
+};
 use reth_codecs::derive_arbitrary;
-use reth_primitives::{ReceiptWithBloom, B256};
+use reth_primitives::{bytes::BufMut, Receipt, ReceiptWithBloom, TxType, B256};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -32,11 +34,105 @@ pub struct Receipts(
     pub Vec<Vec<ReceiptWithBloom>>,
 );
 
+/// The response to [`GetReceipts`] in `eth/69`, which drops the bloom of the receipts as specified
+/// by [EIP-7642](https://eips.ethereum.org/EIPS/eip-7642).
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodableWrapper, RlpDecodableWrapper, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Receipts69(
+    /// Each receipt hash should correspond to a block hash in the request.
+    pub Vec<Vec<Receipt69>>,
+);
+
+impl From<Receipts> for Receipts69 {
+    fn from(receipts: Receipts) -> Self {
+        Self(
+            receipts
+                .0
+                .into_iter()
+                .map(|receipts| receipts.into_iter().map(|r| Receipt69(r.receipt)).collect())
+                .collect(),
+        )
+    }
+}
+
+impl From<Receipts69> for Receipts {
+    /// Converts the `eth/69` receipts into [`Receipts`] by computing their blooms.
+    fn from(receipts: Receipts69) -> Self {
+        Self(
+            receipts
+                .0
+                .into_iter()
+                .map(|receipts| receipts.into_iter().map(|r| r.0.with_bloom()).collect())
+                .collect(),
+        )
+    }
+}
+
+/// A receipt of [`Receipts69`].
+///
+/// Receipts of all transaction types are encoded as the list
+/// `[tx-type, post-state-or-status, cumulative-gas, logs]`.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Receipt69(pub Receipt);
+
+impl Receipt69 {
+    fn payload_length(&self) -> usize {
+        u8::from(self.0.tx_type).length() +
+            self.0.success.length() +
+            self.0.cumulative_gas_used.length() +
+            self.0.logs.length()
+    }
+}
+
+impl Encodable for Receipt69 {
+    fn encode(&self, out: &mut dyn BufMut) {
+        Header { list: true, payload_length: self.payload_length() }.encode(out);
+        u8::from(self.0.tx_type).encode(out);
+        self.0.success.encode(out);
+        self.0.cumulative_gas_used.encode(out);
+        self.0.logs.encode(out);
+    }
+
+    fn length(&self) -> usize {
+        let payload_length = self.payload_length();
+        payload_length + length_of_length(payload_length)
+    }
+}
+
+impl Decodable for Receipt69 {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let header = Header::decode(buf)?;
+        if !header.list {
+            return Err(alloy_rlp::Error::UnexpectedString)
+        }
+        let started_len = buf.len();
+        let tx_type = TxType::try_from(u8::decode(buf)?).map_err(alloy_rlp::Error::Custom)?;
+        let receipt = Receipt {
+            tx_type,
+            success: Decodable::decode(buf)?,
+            cumulative_gas_used: Decodable::decode(buf)?,
+            logs: Decodable::decode(buf)?,
+            #[cfg(feature = "optimism")]
+            deposit_nonce: None,
+            #[cfg(feature = "optimism")]
+            deposit_receipt_version: None,
+        };
+        if started_len - buf.len() != header.payload_length {
+            return Err(alloy_rlp::Error::ListLengthMismatch {
+                expected: header.payload_length,
+                got: started_len - buf.len(),
+            })
+        }
+        Ok(Self(receipt))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
         types::{message::RequestPair, GetReceipts},
-        Receipts,
+        Receipt69, Receipts, Receipts69,
     };
     use alloy_rlp::{Decodable, Encodable};
     use reth_primitives::{hex, Log, Receipt, ReceiptWithBloom, TxType};
@@ -173,4 +269,37 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn roundtrip_eth69_receipts() {
+        let receipt = Receipt {
+            tx_type: TxType::EIP1559,
+            success: true,
+            cumulative_gas_used: 21_000,
+            logs: vec![Log {
+                address: hex!("0000000000000000000000000000000000000011").into(),
+                topics: vec![hex!(
+                    "000000000000000000000000000000000000000000000000000000000000dead"
+                )
+                .into()],
+                data: hex!("0100ff")[..].into(),
+            }],
+            #[cfg(feature = "optimism")]
+            deposit_nonce: None,
+            #[cfg(feature = "optimism")]
+            deposit_receipt_version: None,
+        };
+        let receipts = Receipts(vec![vec![receipt.clone().with_bloom()], vec![]]);
+
+        let mut out = vec![];
+        Receipts69::from(receipts.clone()).encode(&mut out);
+        let decoded = Receipts69::decode(&mut &out[..]).unwrap();
+        assert_eq!(decoded, Receipts69(vec![vec![Receipt69(receipt)], vec![]]));
+        assert_eq!(Receipts::from(decoded), receipts);
+
+        // The bloom is omitted.
+        let mut legacy = vec![];
+        receipts.encode(&mut legacy);
+        assert!(out.len() + 256 < legacy.len());
+    }
 }
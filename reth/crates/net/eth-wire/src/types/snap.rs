@@ -0,0 +1,604 @@
+//! Implements the `snap/1` protocol messages.
+//!
+//! Reference: [Ethereum Snapshot Protocol](https://github.com/ethereum/devp2p/blob/master/caps/snap.md).
+//!
+//! Unlike the `eth` request-response messages, the request id is the first element of the message
+//! list itself.
+
+use alloy_rlp::{length_of_length, Decodable, Encodable, Header, RlpDecodable, RlpEncodable};
+use reth_codecs::derive_arbitrary;
+use reth_primitives::{
+    bytes::{Buf, BufMut},
+    constants::EMPTY_ROOT_HASH,
+    Account, Bytes, B256, KECCAK_EMPTY, U256,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Represents message IDs for `snap` protocol messages.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SnapMessageId {
+    /// Requests a range of accounts.
+    GetAccountRange = 0x00,
+    /// Represents a range of accounts.
+    AccountRange = 0x01,
+    /// Requests the storage slots of multiple accounts.
+    GetStorageRanges = 0x02,
+    /// Represents the storage slots of multiple accounts.
+    StorageRanges = 0x03,
+    /// Requests contract bytecodes.
+    GetByteCodes = 0x04,
+    /// Represents contract bytecodes.
+    ByteCodes = 0x05,
+    /// Requests trie nodes.
+    GetTrieNodes = 0x06,
+    /// Represents trie nodes.
+    TrieNodes = 0x07,
+}
+
+impl SnapMessageId {
+    /// Returns the max value.
+    pub const fn max() -> u8 {
+        Self::TrieNodes as u8
+    }
+}
+
+impl Encodable for SnapMessageId {
+    fn encode(&self, out: &mut dyn BufMut) {
+        out.put_u8(*self as u8);
+    }
+    fn length(&self) -> usize {
+        1
+    }
+}
+
+impl Decodable for SnapMessageId {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let id = match buf.first().ok_or(alloy_rlp::Error::InputTooShort)? {
+            0x00 => SnapMessageId::GetAccountRange,
+            0x01 => SnapMessageId::AccountRange,
+            0x02 => SnapMessageId::GetStorageRanges,
+            0x03 => SnapMessageId::StorageRanges,
+            0x04 => SnapMessageId::GetByteCodes,
+            0x05 => SnapMessageId::ByteCodes,
+            0x06 => SnapMessageId::GetTrieNodes,
+            0x07 => SnapMessageId::TrieNodes,
+            _ => return Err(alloy_rlp::Error::Custom("Invalid message ID")),
+        };
+        buf.advance(1);
+        Ok(id)
+    }
+}
+
+/// A `snap` protocol message, prefixed with its [SnapMessageId] when encoded.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SnapMessage {
+    /// Represents a [GetAccountRange] request.
+    GetAccountRange(GetAccountRange),
+    /// Represents an [AccountRange] response.
+    AccountRange(AccountRange),
+    /// Represents a [GetStorageRanges] request.
+    GetStorageRanges(GetStorageRanges),
+    /// Represents a [StorageRanges] response.
+    StorageRanges(StorageRanges),
+    /// Represents a [GetByteCodes] request.
+    GetByteCodes(GetByteCodes),
+    /// Represents a [ByteCodes] response.
+    ByteCodes(ByteCodes),
+    /// Represents a [GetTrieNodes] request.
+    GetTrieNodes(GetTrieNodes),
+    /// Represents a [TrieNodes] response.
+    TrieNodes(TrieNodes),
+}
+
+impl SnapMessage {
+    /// Returns the message's ID.
+    pub fn message_id(&self) -> SnapMessageId {
+        match self {
+            SnapMessage::GetAccountRange(_) => SnapMessageId::GetAccountRange,
+            SnapMessage::AccountRange(_) => SnapMessageId::AccountRange,
+            SnapMessage::GetStorageRanges(_) => SnapMessageId::GetStorageRanges,
+            SnapMessage::StorageRanges(_) => SnapMessageId::StorageRanges,
+            SnapMessage::GetByteCodes(_) => SnapMessageId::GetByteCodes,
+            SnapMessage::ByteCodes(_) => SnapMessageId::ByteCodes,
+            SnapMessage::GetTrieNodes(_) => SnapMessageId::GetTrieNodes,
+            SnapMessage::TrieNodes(_) => SnapMessageId::TrieNodes,
+        }
+    }
+
+    /// Returns the request id of the message.
+    pub fn request_id(&self) -> u64 {
+        match self {
+            SnapMessage::GetAccountRange(msg) => msg.request_id,
+            SnapMessage::AccountRange(msg) => msg.request_id,
+            SnapMessage::GetStorageRanges(msg) => msg.request_id,
+            SnapMessage::StorageRanges(msg) => msg.request_id,
+            SnapMessage::GetByteCodes(msg) => msg.request_id,
+            SnapMessage::ByteCodes(msg) => msg.request_id,
+            SnapMessage::GetTrieNodes(msg) => msg.request_id,
+            SnapMessage::TrieNodes(msg) => msg.request_id,
+        }
+    }
+
+    /// Decodes a message, prefixed with its [SnapMessageId], from the given buffer.
+    pub fn decode_message(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let message = match SnapMessageId::decode(buf)? {
+            SnapMessageId::GetAccountRange => {
+                SnapMessage::GetAccountRange(GetAccountRange::decode(buf)?)
+            }
+            SnapMessageId::AccountRange => SnapMessage::AccountRange(AccountRange::decode(buf)?),
+            SnapMessageId::GetStorageRanges => {
+                let request = GetStorageRanges::decode(buf)?;
+                if request.start().is_none() || request.limit().is_none() {
+                    return Err(alloy_rlp::Error::UnexpectedLength)
+                }
+                SnapMessage::GetStorageRanges(request)
+            }
+            SnapMessageId::StorageRanges => SnapMessage::StorageRanges(StorageRanges::decode(buf)?),
+            SnapMessageId::GetByteCodes => SnapMessage::GetByteCodes(GetByteCodes::decode(buf)?),
+            SnapMessageId::ByteCodes => SnapMessage::ByteCodes(ByteCodes::decode(buf)?),
+            SnapMessageId::GetTrieNodes => {
+                let request = GetTrieNodes::decode(buf)?;
+                if !request.has_valid_paths() {
+                    return Err(alloy_rlp::Error::UnexpectedLength)
+                }
+                SnapMessage::GetTrieNodes(request)
+            }
+            SnapMessageId::TrieNodes => SnapMessage::TrieNodes(TrieNodes::decode(buf)?),
+        };
+        Ok(message)
+    }
+}
+
+impl Encodable for SnapMessage {
+    /// Encodes the message, prefixed with its [SnapMessageId].
+    fn encode(&self, out: &mut dyn BufMut) {
+        self.message_id().encode(out);
+        match self {
+            SnapMessage::GetAccountRange(msg) => msg.encode(out),
+            SnapMessage::AccountRange(msg) => msg.encode(out),
+            SnapMessage::GetStorageRanges(msg) => msg.encode(out),
+            SnapMessage::StorageRanges(msg) => msg.encode(out),
+            SnapMessage::GetByteCodes(msg) => msg.encode(out),
+            SnapMessage::ByteCodes(msg) => msg.encode(out),
+            SnapMessage::GetTrieNodes(msg) => msg.encode(out),
+            SnapMessage::TrieNodes(msg) => msg.encode(out),
+        }
+    }
+    fn length(&self) -> usize {
+        let payload_length = match self {
+            SnapMessage::GetAccountRange(msg) => msg.length(),
+            SnapMessage::AccountRange(msg) => msg.length(),
+            SnapMessage::GetStorageRanges(msg) => msg.length(),
+            SnapMessage::StorageRanges(msg) => msg.length(),
+            SnapMessage::GetByteCodes(msg) => msg.length(),
+            SnapMessage::ByteCodes(msg) => msg.length(),
+            SnapMessage::GetTrieNodes(msg) => msg.length(),
+            SnapMessage::TrieNodes(msg) => msg.length(),
+        };
+        self.message_id().length() + payload_length
+    }
+}
+
+/// A request for the accounts of the state trie with the given root, starting at the
+/// `starting_hash` and ending at the `limit_hash`.
+#[derive_arbitrary(rlp)]
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GetAccountRange {
+    /// The request id.
+    pub request_id: u64,
+    /// The root of the account trie to serve.
+    pub root_hash: B256,
+    /// The hash of the first account to retrieve.
+    pub starting_hash: B256,
+    /// The hash after which to stop serving data.
+    pub limit_hash: B256,
+    /// The soft limit of the response size in bytes.
+    pub response_bytes: u64,
+}
+
+/// The response to [GetAccountRange], containing consecutive accounts and the merkle proofs of the
+/// first and the last account.
+#[derive_arbitrary(rlp)]
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AccountRange {
+    /// The request id.
+    pub request_id: u64,
+    /// The accounts, ordered by hash.
+    pub accounts: Vec<AccountData>,
+    /// The trie nodes proving the boundaries of the range.
+    pub proof: Vec<Bytes>,
+}
+
+/// An account of an [AccountRange] response.
+#[derive_arbitrary(rlp)]
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AccountData {
+    /// The hash of the account address.
+    pub hash: B256,
+    /// The account in the slim format.
+    pub body: SlimAccount,
+}
+
+/// An account in the slim format of the `snap` protocol.
+///
+/// This is the same as the account of the state trie, except that the empty storage root and the
+/// empty code hash are encoded as empty strings.
+#[derive_arbitrary(rlp)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SlimAccount {
+    /// Account nonce.
+    pub nonce: u64,
+    /// Account balance.
+    pub balance: U256,
+    /// Account's storage root.
+    pub storage_root: B256,
+    /// Hash of the account's bytecode.
+    pub code_hash: B256,
+}
+
+impl SlimAccount {
+    fn storage_root_slim(&self) -> &[u8] {
+        if self.storage_root == EMPTY_ROOT_HASH {
+            &[]
+        } else {
+            self.storage_root.as_slice()
+        }
+    }
+
+    fn code_hash_slim(&self) -> &[u8] {
+        if self.code_hash == KECCAK_EMPTY {
+            &[]
+        } else {
+            self.code_hash.as_slice()
+        }
+    }
+
+    fn payload_length(&self) -> usize {
+        self.nonce.length() +
+            self.balance.length() +
+            self.storage_root_slim().length() +
+            self.code_hash_slim().length()
+    }
+}
+
+impl From<(Account, B256)> for SlimAccount {
+    fn from((account, storage_root): (Account, B256)) -> Self {
+        Self {
+            nonce: account.nonce,
+            balance: account.balance,
+            storage_root,
+            code_hash: account.bytecode_hash.unwrap_or(KECCAK_EMPTY),
+        }
+    }
+}
+
+impl Encodable for SlimAccount {
+    fn encode(&self, out: &mut dyn BufMut) {
+        Header { list: true, payload_length: self.payload_length() }.encode(out);
+        self.nonce.encode(out);
+        self.balance.encode(out);
+        self.storage_root_slim().encode(out);
+        self.code_hash_slim().encode(out);
+    }
+
+    fn length(&self) -> usize {
+        let payload_length = self.payload_length();
+        payload_length + length_of_length(payload_length)
+    }
+}
+
+impl Decodable for SlimAccount {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        /// Decodes a hash that is encoded as an empty string if it equals the `empty` hash.
+        fn decode_slim_hash(buf: &mut &[u8], empty: B256) -> alloy_rlp::Result<B256> {
+            let bytes = Bytes::decode(buf)?;
+            match bytes.len() {
+                0 => Ok(empty),
+                32 => Ok(B256::from_slice(&bytes)),
+                _ => Err(alloy_rlp::Error::UnexpectedLength),
+            }
+        }
+
+        let header = Header::decode(buf)?;
+        if !header.list {
+            return Err(alloy_rlp::Error::UnexpectedString)
+        }
+        let started_len = buf.len();
+        let this = Self {
+            nonce: Decodable::decode(buf)?,
+            balance: Decodable::decode(buf)?,
+            storage_root: decode_slim_hash(buf, EMPTY_ROOT_HASH)?,
+            code_hash: decode_slim_hash(buf, KECCAK_EMPTY)?,
+        };
+        if started_len - buf.len() != header.payload_length {
+            return Err(alloy_rlp::Error::ListLengthMismatch {
+                expected: header.payload_length,
+                got: started_len - buf.len(),
+            })
+        }
+        Ok(this)
+    }
+}
+
+/// A request for the storage slots of the given accounts of the state trie with the given root.
+///
+/// The `starting_hash` and the `limit_hash` only apply to the first account, the storage of the
+/// following accounts is requested in full.
+#[derive_arbitrary(rlp)]
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GetStorageRanges {
+    /// The request id.
+    pub request_id: u64,
+    /// The root of the account trie to serve.
+    pub root_hash: B256,
+    /// The hashes of the account addresses whose storage to retrieve.
+    pub account_hashes: Vec<B256>,
+    /// The hash of the first storage slot to retrieve, empty for the first slot.
+    pub starting_hash: Bytes,
+    /// The hash after which to stop serving data, empty for the last slot.
+    pub limit_hash: Bytes,
+    /// The soft limit of the response size in bytes.
+    pub response_bytes: u64,
+}
+
+impl GetStorageRanges {
+    /// Returns the hash of the first storage slot to retrieve, or `None` if the `starting_hash` is
+    /// neither empty nor 32 bytes long.
+    pub fn start(&self) -> Option<B256> {
+        hash_or(&self.starting_hash, B256::ZERO)
+    }
+
+    /// Returns the hash after which to stop serving data, or `None` if the `limit_hash` is neither
+    /// empty nor 32 bytes long.
+    pub fn limit(&self) -> Option<B256> {
+        hash_or(&self.limit_hash, B256::repeat_byte(0xff))
+    }
+}
+
+/// Returns the given default if the hash is empty, and `None` if it is not 32 bytes long.
+fn hash_or(bytes: &[u8], default: B256) -> Option<B256> {
+    match bytes.len() {
+        0 => Some(default),
+        32 => Some(B256::from_slice(bytes)),
+        _ => None,
+    }
+}
+
+/// The response to [GetStorageRanges], containing consecutive storage slots of the requested
+/// accounts and the merkle proofs of the first and the last slot, if the storage of the last
+/// account is incomplete.
+#[derive_arbitrary(rlp)]
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct StorageRanges {
+    /// The request id.
+    pub request_id: u64,
+    /// The storage slots of each account, ordered by hash.
+    pub slots: Vec<Vec<StorageData>>,
+    /// The trie nodes proving the boundaries of the last range.
+    pub proof: Vec<Bytes>,
+}
+
+/// A storage slot of a [StorageRanges] response.
+#[derive_arbitrary(rlp)]
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct StorageData {
+    /// The hash of the storage slot.
+    pub hash: B256,
+    /// The RLP encoded value of the storage slot.
+    pub data: Bytes,
+}
+
+/// A request for the contract bytecodes with the given hashes.
+#[derive_arbitrary(rlp)]
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GetByteCodes {
+    /// The request id.
+    pub request_id: u64,
+    /// The code hashes of the bytecodes to retrieve.
+    pub hashes: Vec<B256>,
+    /// The soft limit of the response size in bytes.
+    pub response_bytes: u64,
+}
+
+/// The response to [GetByteCodes].
+///
+/// The bytecodes are in the order of the request, unavailable bytecodes are skipped.
+#[derive_arbitrary(rlp)]
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ByteCodes {
+    /// The request id.
+    pub request_id: u64,
+    /// The bytecodes.
+    pub codes: Vec<Bytes>,
+}
+
+/// A request for the trie nodes at the given paths of the state trie with the given root.
+///
+/// Each path set is either a single compact encoded path of the account trie, or the hash of an
+/// account address followed by compact encoded paths of its storage trie.
+#[derive_arbitrary(rlp)]
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GetTrieNodes {
+    /// The request id.
+    pub request_id: u64,
+    /// The root of the account trie to serve.
+    pub root_hash: B256,
+    /// The path sets of the trie nodes to retrieve.
+    pub paths: Vec<Vec<Bytes>>,
+    /// The soft limit of the response size in bytes.
+    pub response_bytes: u64,
+}
+
+impl GetTrieNodes {
+    /// The max length of a compact encoded path, which is a flag byte followed by 64 nibbles.
+    pub const MAX_PATH_LENGTH: usize = 33;
+
+    /// Returns `true` if the account hash of every storage path set is 32 bytes long, and all
+    /// paths are at most [Self::MAX_PATH_LENGTH] bytes long.
+    pub fn has_valid_paths(&self) -> bool {
+        self.paths.iter().all(|pathset| match pathset.as_slice() {
+            [account, storage_paths @ ..] if !storage_paths.is_empty() => {
+                account.len() == 32 &&
+                    storage_paths.iter().all(|path| path.len() <= Self::MAX_PATH_LENGTH)
+            }
+            paths => paths.iter().all(|path| path.len() <= Self::MAX_PATH_LENGTH),
+        })
+    }
+}
+
+/// The response to [GetTrieNodes].
+///
+/// The trie nodes are in the order of the request, missing trie nodes are empty.
+#[derive_arbitrary(rlp)]
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TrieNodes {
+    /// The request id.
+    pub request_id: u64,
+    /// The trie nodes.
+    pub nodes: Vec<Bytes>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::hex;
+
+    fn encode<T: Encodable>(value: T) -> Vec<u8> {
+        let mut buf = vec![];
+        value.encode(&mut buf);
+        assert_eq!(buf.len(), value.length());
+        buf
+    }
+
+    #[test]
+    fn slim_account_omits_empty_hashes() {
+        let account = SlimAccount {
+            nonce: 1,
+            balance: U256::from(2),
+            storage_root: EMPTY_ROOT_HASH,
+            code_hash: KECCAK_EMPTY,
+        };
+        let encoded = encode(account);
+        assert_eq!(encoded, hex!("c401028080"));
+        assert_eq!(SlimAccount::decode(&mut &encoded[..]).unwrap(), account);
+
+        let account = SlimAccount {
+            storage_root: B256::repeat_byte(0x11),
+            code_hash: B256::repeat_byte(0x22),
+            ..account
+        };
+        let encoded = encode(account);
+        assert_eq!(encoded.len(), 2 + 1 + 1 + 33 + 33);
+        assert_eq!(SlimAccount::decode(&mut &encoded[..]).unwrap(), account);
+    }
+
+    #[test]
+    fn snap_message_roundtrip() {
+        let messages = [
+            SnapMessage::GetAccountRange(GetAccountRange {
+                request_id: 1,
+                root_hash: B256::repeat_byte(0x01),
+                starting_hash: B256::ZERO,
+                limit_hash: B256::repeat_byte(0xff),
+                response_bytes: 512 * 1024,
+            }),
+            SnapMessage::AccountRange(AccountRange {
+                request_id: 1,
+                accounts: vec![AccountData {
+                    hash: B256::repeat_byte(0x02),
+                    body: SlimAccount { nonce: 1, ..Default::default() },
+                }],
+                proof: vec![Bytes::from_static(&[0xc0])],
+            }),
+            SnapMessage::GetStorageRanges(GetStorageRanges {
+                request_id: 2,
+                root_hash: B256::repeat_byte(0x01),
+                account_hashes: vec![B256::repeat_byte(0x02)],
+                starting_hash: Bytes::new(),
+                limit_hash: Bytes::new(),
+                response_bytes: 512 * 1024,
+            }),
+            SnapMessage::StorageRanges(StorageRanges {
+                request_id: 2,
+                slots: vec![vec![StorageData {
+                    hash: B256::repeat_byte(0x03),
+                    data: Bytes::from_static(&[0x01]),
+                }]],
+                proof: vec![],
+            }),
+            SnapMessage::GetByteCodes(GetByteCodes {
+                request_id: 3,
+                hashes: vec![B256::repeat_byte(0x04)],
+                response_bytes: 512 * 1024,
+            }),
+            SnapMessage::ByteCodes(ByteCodes {
+                request_id: 3,
+                codes: vec![Bytes::from_static(&[0x60, 0x00])],
+            }),
+            SnapMessage::GetTrieNodes(GetTrieNodes {
+                request_id: 4,
+                root_hash: B256::repeat_byte(0x01),
+                paths: vec![vec![Bytes::from_static(&[0x00])]],
+                response_bytes: 512 * 1024,
+            }),
+            SnapMessage::TrieNodes(TrieNodes { request_id: 4, nodes: vec![Bytes::new()] }),
+        ];
+
+        for (id, message) in messages.into_iter().enumerate() {
+            let encoded = encode(message.clone());
+            assert_eq!(encoded[0], id as u8);
+            assert_eq!(message.request_id(), (id / 2 + 1) as u64);
+            assert_eq!(SnapMessage::decode_message(&mut &encoded[..]).unwrap(), message);
+        }
+    }
+
+    #[test]
+    fn decode_invalid_hash_lengths() {
+        let request = GetStorageRanges {
+            request_id: 1,
+            starting_hash: Bytes::from_static(&[0x01]),
+            ..Default::default()
+        };
+        assert_eq!(request.start(), None);
+        assert_eq!(request.limit(), Some(B256::repeat_byte(0xff)));
+        let encoded = encode(SnapMessage::GetStorageRanges(request));
+        assert!(SnapMessage::decode_message(&mut &encoded[..]).is_err());
+
+        let request = GetTrieNodes {
+            request_id: 1,
+            paths: vec![vec![Bytes::from_static(&[0x01]), Bytes::from_static(&[0x00])]],
+            ..Default::default()
+        };
+        assert!(!request.has_valid_paths());
+        let encoded = encode(SnapMessage::GetTrieNodes(request));
+        assert!(SnapMessage::decode_message(&mut &encoded[..]).is_err());
+
+        let request = GetTrieNodes {
+            request_id: 1,
+            paths: vec![vec![Bytes::from(vec![0; 32]), Bytes::from_static(&[0x00])]],
+            ..Default::default()
+        };
+        assert!(request.has_valid_paths());
+    }
+
+    #[test]
+    fn decode_invalid_message_id() {
+        let encoded = [SnapMessageId::max() + 1, 0xc0];
+        assert!(SnapMessage::decode_message(&mut &encoded[..]).is_err());
+    }
+}
@@ -130,6 +130,56 @@ impl Debug for Status {
     }
 }
 
+/// The status message of `eth/69`, which is the [Status] without the total difficulty, as
+/// specified by [EIP-7642](https://eips.ethereum.org/EIPS/eip-7642).
+#[derive_arbitrary(rlp)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct StatusEth69 {
+    /// The current protocol version.
+    pub version: u8,
+
+    /// The chain id, as introduced in
+    /// [EIP155](https://eips.ethereum.org/EIPS/eip-155#list-of-chain-ids).
+    pub chain: Chain,
+
+    /// The hash of the latest block of the peer.
+    pub blockhash: B256,
+
+    /// The genesis hash of the peer's chain.
+    pub genesis: B256,
+
+    /// The fork identifier as defined by
+    /// [EIP-2124](https://github.com/ethereum/EIPs/blob/master/EIPS/eip-2124.md).
+    pub forkid: ForkId,
+}
+
+impl From<Status> for StatusEth69 {
+    fn from(status: Status) -> Self {
+        Self {
+            version: status.version,
+            chain: status.chain,
+            blockhash: status.blockhash,
+            genesis: status.genesis,
+            forkid: status.forkid,
+        }
+    }
+}
+
+impl From<StatusEth69> for Status {
+    /// Converts the `eth/69` status into a [Status] with a zero total difficulty.
+    fn from(status: StatusEth69) -> Self {
+        Self {
+            version: status.version,
+            chain: status.chain,
+            total_difficulty: U256::ZERO,
+            blockhash: status.blockhash,
+            genesis: status.genesis,
+            forkid: status.forkid,
+        }
+    }
+}
+
 // <https://etherscan.io/block/0>
 impl Default for Status {
     fn default() -> Self {
@@ -149,7 +199,7 @@ impl Default for Status {
 
 #[cfg(test)]
 mod tests {
-    use crate::types::{EthVersion, Status};
+    use crate::types::{EthVersion, Status, StatusEth69};
     use alloy_chains::{Chain, NamedChain};
     use alloy_rlp::{Decodable, Encodable};
     use rand::Rng;
@@ -270,6 +320,25 @@ mod tests {
         assert_eq!(status, expected);
     }
 
+    #[test]
+    fn eth69_status_omits_total_difficulty() {
+        let status = Status {
+            version: EthVersion::Eth69 as u8,
+            total_difficulty: U256::from(17_179_869_184u64),
+            ..Default::default()
+        };
+        let eth69 = StatusEth69::from(status);
+
+        let mut rlp_status = vec![];
+        eth69.encode(&mut rlp_status);
+        let mut rlp_legacy = vec![];
+        status.encode(&mut rlp_legacy);
+        assert_eq!(rlp_status.len() + status.total_difficulty.length(), rlp_legacy.len());
+
+        let decoded = StatusEth69::decode(&mut &rlp_status[..]).unwrap();
+        assert_eq!(Status::from(decoded), Status { total_difficulty: U256::ZERO, ..status });
+    }
+
     #[test]
     fn init_custom_status_fields() {
         let mut rng = rand::thread_rng();
@@ -51,10 +51,11 @@ pub mod fuzz_rlp {
     use alloy_rlp::{RlpDecodableWrapper, RlpEncodableWrapper};
     use reth_codecs::derive_arbitrary;
     use reth_eth_wire::{
-        BlockBodies, BlockHeaders, DisconnectReason, GetBlockBodies, GetBlockHeaders, GetNodeData,
-        GetPooledTransactions, GetReceipts, HelloMessage, NewBlock, NewBlockHashes,
+        AccountRange, BlockBodies, BlockHeaders, ByteCodes, DisconnectReason, GetAccountRange,
+        GetBlockBodies, GetBlockHeaders, GetByteCodes, GetNodeData, GetPooledTransactions,
+        GetReceipts, GetStorageRanges, GetTrieNodes, HelloMessage, NewBlock, NewBlockHashes,
         NewPooledTransactionHashes66, NewPooledTransactionHashes68, NodeData, P2PMessage,
-        PooledTransactions, Receipts, Status, Transactions,
+        PooledTransactions, Receipts, Status, StatusEth69, StorageRanges, Transactions, TrieNodes,
     };
     use reth_primitives::{BlockHashOrNumber, TransactionSigned};
     use serde::{Deserialize, Serialize};
@@ -108,6 +109,7 @@ pub mod fuzz_rlp {
 
     // eth subprotocol messages
     fuzz_type_and_name!(Status, fuzz_Status);
+    fuzz_type_and_name!(StatusEth69, fuzz_StatusEth69);
     fuzz_type_and_name!(NewBlockHashes, fuzz_NewBlockHashes);
     fuzz_type_and_name!(Transactions, fuzz_Transactions);
 
@@ -159,4 +161,12 @@ pub mod fuzz_rlp {
     fuzz_type_and_name!(GetReceipts, fuzz_GetReceipts);
     fuzz_type_and_name!(Receipts, fuzz_Receipts);
     fuzz_type_and_name!(TransactionSigned, fuzz_TransactionSigned);
+    fuzz_type_and_name!(GetAccountRange, fuzz_GetAccountRange);
+    fuzz_type_and_name!(AccountRange, fuzz_AccountRange);
+    fuzz_type_and_name!(GetStorageRanges, fuzz_GetStorageRanges);
+    fuzz_type_and_name!(StorageRanges, fuzz_StorageRanges);
+    fuzz_type_and_name!(GetByteCodes, fuzz_GetByteCodes);
+    fuzz_type_and_name!(ByteCodes, fuzz_ByteCodes);
+    fuzz_type_and_name!(GetTrieNodes, fuzz_GetTrieNodes);
+    fuzz_type_and_name!(TrieNodes, fuzz_TrieNodes);
 }
@@ -8,13 +8,14 @@ use crate::{
     NetworkHandle, NetworkManager,
 };
 use reth_discv4::{Discv4Config, Discv4ConfigBuilder, DEFAULT_DISCOVERY_ADDRESS};
+use reth_discv5::{Discv5Config, Discv5ConfigBuilder};
 use reth_dns_discovery::DnsDiscoveryConfig;
 use reth_ecies::util::pk2id;
-use reth_eth_wire::{HelloMessage, HelloMessageWithProtocols, Status};
+use reth_eth_wire::{protocol::Protocol, HelloMessage, HelloMessageWithProtocols, Status};
 use reth_primitives::{
     mainnet_nodes, sepolia_nodes, ChainSpec, ForkFilter, Head, NodeRecord, PeerId, MAINNET,
 };
-use reth_provider::{BlockReader, HeaderProvider};
+use reth_provider::{BlockReader, HashedStateRangeReader, HeaderProvider};
 use reth_tasks::{TaskSpawner, TokioTaskExecutor};
 use secp256k1::SECP256K1;
 use std::{collections::HashSet, net::SocketAddr, sync::Arc};
@@ -43,6 +44,8 @@ pub struct NetworkConfig<C> {
     pub dns_discovery_config: Option<DnsDiscoveryConfig>,
     /// How to set up discovery.
     pub discovery_v4_config: Option<Discv4Config>,
+    /// How to set up discovery over discv5, which runs alongside discv4.
+    pub discovery_v5_config: Option<Discv5Config>,
     /// Address to use for discovery
     pub discovery_addr: SocketAddr,
     /// Address to listen for incoming connections
@@ -74,6 +77,8 @@ pub struct NetworkConfig<C> {
     pub extra_protocols: RlpxSubProtocols,
     /// Whether to disable transaction gossip
     pub tx_gossip_disabled: bool,
+    /// Whether to serve the `snap` protocol to peers.
+    pub serve_snap: bool,
     /// Optimism Network Config
     #[cfg(feature = "optimism")]
     pub optimism_network_config: OptimismNetworkConfig,
@@ -117,7 +122,7 @@ impl<C> NetworkConfig<C> {
 
 impl<C> NetworkConfig<C>
 where
-    C: BlockReader + HeaderProvider + Clone + Unpin + 'static,
+    C: BlockReader + HeaderProvider + HashedStateRangeReader + Clone + Unpin + 'static,
 {
     /// Starts the networking stack given a [NetworkConfig] and returns a handle to the network.
     pub async fn start_network(self) -> Result<NetworkHandle, NetworkError> {
@@ -142,6 +147,9 @@ pub struct NetworkConfigBuilder {
     dns_discovery_config: Option<DnsDiscoveryConfig>,
     /// How to set up discovery.
     discovery_v4_builder: Option<Discv4ConfigBuilder>,
+    /// How to set up discovery over discv5.
+    #[serde(skip)]
+    discovery_v5_builder: Option<Discv5ConfigBuilder>,
     /// All boot nodes to start network discovery with.
     boot_nodes: HashSet<NodeRecord>,
     /// Address to use for discovery
@@ -168,6 +176,10 @@ pub struct NetworkConfigBuilder {
     head: Option<Head>,
     /// Whether tx gossip is disabled
     tx_gossip_disabled: bool,
+    /// Whether the `snap` protocol is served
+    serve_snap: bool,
+    /// Whether `eth/69` is advertised
+    eth69: bool,
     /// The block importer type
     #[serde(skip)]
     block_import: Option<Box<dyn BlockImport>>,
@@ -194,6 +206,7 @@ impl NetworkConfigBuilder {
             secret_key,
             dns_discovery_config: Some(Default::default()),
             discovery_v4_builder: Some(Default::default()),
+            discovery_v5_builder: None,
             boot_nodes: Default::default(),
             discovery_addr: None,
             listener_addr: None,
@@ -206,6 +219,8 @@ impl NetworkConfigBuilder {
             extra_protocols: Default::default(),
             head: None,
             tx_gossip_disabled: false,
+            serve_snap: false,
+            eth69: false,
             block_import: None,
             #[cfg(feature = "optimism")]
             optimism_network_config: OptimismNetworkConfigBuilder::default(),
@@ -321,6 +336,12 @@ impl NetworkConfigBuilder {
         self
     }
 
+    /// Sets the discv5 config to use. Discv5 runs alongside discv4 and is disabled by default.
+    pub fn discovery_v5(mut self, builder: Discv5ConfigBuilder) -> Self {
+        self.discovery_v5_builder = Some(builder);
+        self
+    }
+
     /// Sets the dns discovery config to use.
     pub fn dns_discovery(mut self, config: DnsDiscoveryConfig) -> Self {
         self.dns_discovery_config = Some(config);
@@ -351,7 +372,7 @@ impl NetworkConfigBuilder {
 
     /// Disables all discovery.
     pub fn disable_discovery(self) -> Self {
-        self.disable_discv4_discovery().disable_dns_discovery()
+        self.disable_discv4_discovery().disable_discv5_discovery().disable_dns_discovery()
     }
 
     /// Disables all discovery if the given condition is true.
@@ -387,6 +408,21 @@ impl NetworkConfigBuilder {
         }
     }
 
+    /// Disable the Discv5 discovery.
+    pub fn disable_discv5_discovery(mut self) -> Self {
+        self.discovery_v5_builder = None;
+        self
+    }
+
+    /// Disable the Discv5 discovery if the given condition is true.
+    pub fn disable_discv5_discovery_if(self, disable: bool) -> Self {
+        if disable {
+            self.disable_discv5_discovery()
+        } else {
+            self
+        }
+    }
+
     /// Adds a new additional protocol to the RLPx sub-protocol list.
     pub fn add_rlpx_sub_protocol(mut self, protocol: impl IntoRlpxSubProtocol) -> Self {
         self.extra_protocols.push(protocol);
@@ -399,6 +435,24 @@ impl NetworkConfigBuilder {
         self
     }
 
+    /// Sets whether the `snap` protocol is served to peers.
+    ///
+    /// The requests of the protocol are answered by the
+    /// [EthRequestHandler](crate::eth_requests::EthRequestHandler) from the hashed state.
+    pub fn serve_snap(mut self, serve_snap: bool) -> Self {
+        self.serve_snap = serve_snap;
+        self
+    }
+
+    /// Sets whether `eth/69` is advertised to peers, in addition to the default `eth` versions.
+    ///
+    /// The `eth/69` status does not contain the total difficulty, so the total difficulty of
+    /// peers that negotiate it is unknown and treated as zero.
+    pub fn eth69(mut self, eth69: bool) -> Self {
+        self.eth69 = eth69;
+        self
+    }
+
     /// Sets the block import type.
     pub fn block_import(mut self, block_import: Box<dyn BlockImport>) -> Self {
         self.block_import = Some(block_import);
@@ -432,6 +486,7 @@ impl NetworkConfigBuilder {
             secret_key,
             mut dns_discovery_config,
             discovery_v4_builder,
+            discovery_v5_builder,
             boot_nodes,
             discovery_addr,
             listener_addr,
@@ -444,6 +499,8 @@ impl NetworkConfigBuilder {
             extra_protocols,
             head,
             tx_gossip_disabled,
+            serve_snap,
+            eth69,
             block_import,
             #[cfg(feature = "optimism")]
                 optimism_network_config: OptimismNetworkConfigBuilder { sequencer_endpoint },
@@ -454,6 +511,10 @@ impl NetworkConfigBuilder {
         let mut hello_message =
             hello_message.unwrap_or_else(|| HelloMessage::builder(peer_id).build());
         hello_message.port = listener_addr.port();
+        if eth69 && !hello_message.contains_protocol(&Protocol::eth_69()) {
+            // the highest shared version is negotiated, so it doesn't matter where it is inserted
+            hello_message.protocols.insert(0, Protocol::eth_69());
+        }
 
         let head = head.unwrap_or(Head {
             hash: chain_spec.genesis_hash(),
@@ -486,6 +547,9 @@ impl NetworkConfigBuilder {
             boot_nodes,
             dns_discovery_config,
             discovery_v4_config: discovery_v4_builder.map(|builder| builder.build()),
+            // advertise the port of the listener in the ENR
+            discovery_v5_config: discovery_v5_builder
+                .map(|builder| builder.tcp_port(listener_addr.port()).build()),
             discovery_addr: discovery_addr.unwrap_or(DEFAULT_DISCOVERY_ADDRESS),
             listener_addr,
             peers_config: peers_config.unwrap_or_default(),
@@ -499,6 +563,7 @@ impl NetworkConfigBuilder {
             extra_protocols,
             fork_filter,
             tx_gossip_disabled,
+            serve_snap,
             #[cfg(feature = "optimism")]
             optimism_network_config: OptimismNetworkConfig { sequencer_endpoint },
         }
@@ -555,6 +620,15 @@ mod tests {
         assert_eq!(bootstrap_nodes.len(), 1);
     }
 
+    #[test]
+    fn test_network_eth69_opt_in() {
+        let config = builder().build(NoopProvider::default());
+        assert!(!config.hello_message.contains_protocol(&Protocol::eth_69()));
+
+        let config = builder().eth69(true).build(NoopProvider::default());
+        assert_eq!(config.hello_message.protocols[0], Protocol::eth_69());
+    }
+
     #[test]
     fn test_network_fork_filter_default() {
         let mut chain_spec = Arc::clone(&MAINNET);
@@ -6,6 +6,7 @@ use crate::{
 };
 use futures::StreamExt;
 use reth_discv4::{DiscoveryUpdate, Discv4, Discv4Config, EnrForkIdEntry};
+use reth_discv5::{discv5::Event as Discv5Event, Discv5, Discv5Config};
 use reth_dns_discovery::{
     DnsDiscoveryConfig, DnsDiscoveryHandle, DnsDiscoveryService, DnsNodeRecordUpdate, DnsResolver,
 };
@@ -39,6 +40,10 @@ pub struct Discovery {
     discv4_updates: Option<ReceiverStream<DiscoveryUpdate>>,
     /// The handle to the spawned discv4 service
     _discv4_service: Option<JoinHandle<()>>,
+    /// Handler to interact with the Discovery v5 service
+    discv5: Option<Discv5>,
+    /// All events from the discv5 service.
+    discv5_updates: Option<ReceiverStream<Discv5Event>>,
     /// Handler to interact with the DNS discovery service
     _dns_discovery: Option<DnsDiscoveryHandle>,
     /// Updates from the DNS discovery service.
@@ -55,11 +60,13 @@ impl Discovery {
     /// Spawns the discovery service.
     ///
     /// This will spawn the [`reth_discv4::Discv4Service`] onto a new task and establish a listener
-    /// channel to receive all discovered nodes.
+    /// channel to receive all discovered nodes. If configured, the discv5 service is started
+    /// alongside it.
     pub async fn new(
         discovery_addr: SocketAddr,
         sk: SecretKey,
         discv4_config: Option<Discv4Config>,
+        discv5_config: Option<Discv5Config>,
         dns_discovery_config: Option<DnsDiscoveryConfig>,
     ) -> Result<Self, NetworkError> {
         // setup discv4
@@ -77,6 +84,14 @@ impl Discovery {
             (None, None, None)
         };
 
+        // setup discv5
+        let (discv5, discv5_updates) = if let Some(discv5_config) = discv5_config {
+            let (discv5, discv5_updates) = Discv5::start(&sk, discv5_config).await?;
+            (Some(discv5), Some(ReceiverStream::new(discv5_updates)))
+        } else {
+            (None, None)
+        };
+
         // setup DNS discovery
         let (_dns_discovery, dns_discovery_updates, _dns_disc_service) =
             if let Some(dns_config) = dns_discovery_config {
@@ -97,6 +112,8 @@ impl Discovery {
             discv4,
             discv4_updates,
             _discv4_service,
+            discv5,
+            discv5_updates,
             discovered_nodes: Default::default(),
             queued_events: Default::default(),
             _dns_disc_service,
@@ -116,26 +133,35 @@ impl Discovery {
         self.discovery_listeners.retain_mut(|listener| listener.send(event.clone()).is_ok());
     }
 
-    /// Updates the `eth:ForkId` field in discv4.
+    /// Updates the `eth:ForkId` field in discv4 and discv5.
     pub(crate) fn update_fork_id(&self, fork_id: ForkId) {
         if let Some(discv4) = &self.discv4 {
             // use forward-compatible forkid entry
             discv4.set_eip868_rlp("eth".as_bytes().to_vec(), EnrForkIdEntry::from(fork_id))
         }
+        if let Some(discv5) = &self.discv5 {
+            discv5.set_fork_id(fork_id)
+        }
     }
 
-    /// Bans the [`IpAddr`] in the discovery service.
+    /// Bans the [`IpAddr`] in the discovery services.
     pub(crate) fn ban_ip(&self, ip: IpAddr) {
         if let Some(discv4) = &self.discv4 {
             discv4.ban_ip(ip)
         }
+        if let Some(discv5) = &self.discv5 {
+            discv5.ban_ip(ip)
+        }
     }
 
-    /// Bans the [`PeerId`] and [`IpAddr`] in the discovery service.
+    /// Bans the [`PeerId`] and [`IpAddr`] in the discovery services.
     pub(crate) fn ban(&self, peer_id: PeerId, ip: IpAddr) {
         if let Some(discv4) = &self.discv4 {
             discv4.ban(peer_id, ip)
         }
+        if let Some(discv5) = &self.discv5 {
+            discv5.ban(peer_id, ip)
+        }
     }
 
     /// Returns the id with which the local identifies itself in the network
@@ -202,6 +228,18 @@ impl Discovery {
                 self.on_discv4_update(update)
             }
 
+            while let Some(Poll::Ready(Some(event))) =
+                self.discv5_updates.as_mut().map(|updates| updates.poll_next_unpin(cx))
+            {
+                // only nodes that advertise a fork id are reported, which is then validated by
+                // the swarm
+                if let Some(peer) =
+                    self.discv5.as_ref().and_then(|discv5| discv5.on_discv5_update(event))
+                {
+                    self.on_node_record_update(peer.node_record, Some(peer.fork_id));
+                }
+            }
+
             while let Some(Poll::Ready(Some(update))) =
                 self.dns_discovery_updates.as_mut().map(|updates| updates.poll_next_unpin(cx))
             {
@@ -245,6 +283,8 @@ impl Discovery {
             discv4_updates: Default::default(),
             queued_events: Default::default(),
             _discv4_service: Default::default(),
+            discv5: None,
+            discv5_updates: None,
             _dns_discovery: None,
             dns_discovery_updates: None,
             _dns_disc_service: None,
@@ -274,9 +314,35 @@ mod tests {
         let mut rng = thread_rng();
         let (secret_key, _) = SECP256K1.generate_keypair(&mut rng);
         let discovery_addr = SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, 0));
-        let _discovery =
-            Discovery::new(discovery_addr, secret_key, Default::default(), Default::default())
-                .await
-                .unwrap();
+        let _discovery = Discovery::new(
+            discovery_addr,
+            secret_key,
+            Default::default(),
+            None,
+            Default::default(),
+        )
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_discovery_setup_with_discv5() {
+        let mut rng = thread_rng();
+        let (secret_key, _) = SECP256K1.generate_keypair(&mut rng);
+        let discovery_addr = SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, 0));
+        let discv5_config = Discv5Config::builder()
+            .discovery_addr(SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::LOCALHOST, 0)))
+            .build();
+        let discovery = Discovery::new(
+            discovery_addr,
+            secret_key,
+            Default::default(),
+            Some(discv5_config),
+            Default::default(),
+        )
+        .await
+        .unwrap();
+        assert!(discovery.discv4.is_some());
+        assert!(discovery.discv5.is_some());
     }
 }
@@ -3,20 +3,24 @@
 use crate::{metrics::EthRequestHandlerMetrics, peers::PeersHandle};
 use futures::StreamExt;
 use reth_eth_wire::{
-    BlockBodies, BlockHeaders, GetBlockBodies, GetBlockHeaders, GetNodeData, GetReceipts, NodeData,
-    Receipts,
+    AccountData, AccountRange, BlockBodies, BlockHeaders, ByteCodes, GetAccountRange,
+    GetBlockBodies, GetBlockHeaders, GetByteCodes, GetNodeData, GetReceipts, GetStorageRanges,
+    GetTrieNodes, NodeData, Receipts, StorageData, StorageRanges, TrieNodes,
 };
 use reth_interfaces::p2p::error::RequestResult;
-use reth_primitives::{BlockBody, BlockHashOrNumber, Header, HeadersDirection, PeerId};
-use reth_provider::{BlockReader, HeaderProvider, ReceiptProvider};
+use reth_primitives::{
+    trie::Nibbles, BlockBody, BlockHashOrNumber, Bytes, Header, HeadersDirection, PeerId, B256,
+};
+use reth_provider::{BlockReader, HashedStateRangeReader, HeaderProvider, ReceiptProvider};
 use std::{
     borrow::Borrow,
     future::Future,
     hash::Hash,
     pin::Pin,
+    sync::Arc,
     task::{Context, Poll},
 };
-use tokio::sync::{mpsc::Receiver, oneshot};
+use tokio::sync::{mpsc::Receiver, oneshot, Semaphore};
 use tokio_stream::wrappers::ReceiverStream;
 
 // Limits: <https://github.com/ethereum/go-ethereum/blob/b0d44338bbcefee044f1f635a84487cbbd8f0538/eth/protocols/eth/handler.go#L34-L56>
@@ -50,6 +54,21 @@ const SOFT_RESPONSE_LIMIT: usize = 2 * 1024 * 1024;
 /// Estimated size in bytes of an RLP encoded header.
 const APPROX_HEADER_SIZE: usize = 500;
 
+/// Maximum number of bytecodes to serve.
+///
+/// Used to limit lookups.
+const MAX_BYTECODES_SERVE: usize = 1024;
+
+/// Maximum number of trie nodes to serve.
+///
+/// Used to limit lookups.
+const MAX_TRIE_NODES_SERVE: usize = 1024;
+
+/// Maximum number of `snap` responses that are built concurrently on blocking threads.
+///
+/// Further requests are answered with an empty response until one of them is done.
+const MAX_CONCURRENT_SNAP_RESPONSES: usize = 32;
+
 /// Manages eth related requests on top of the p2p network.
 ///
 /// This can be spawned to another task and is supposed to be run as background service.
@@ -66,6 +85,8 @@ pub struct EthRequestHandler<C> {
     incoming_requests: ReceiverStream<IncomingEthRequest>,
     /// Metrics for the eth request handler.
     metrics: EthRequestHandlerMetrics,
+    /// Permits for building `snap` responses on blocking threads.
+    snap_response_permits: Arc<Semaphore>,
 }
 
 // === impl EthRequestHandler ===
@@ -73,13 +94,19 @@ impl<C> EthRequestHandler<C> {
     /// Create a new instance
     pub fn new(client: C, peers: PeersHandle, incoming: Receiver<IncomingEthRequest>) -> Self {
         let metrics = Default::default();
-        Self { client, peers, incoming_requests: ReceiverStream::new(incoming), metrics }
+        Self {
+            client,
+            peers,
+            incoming_requests: ReceiverStream::new(incoming),
+            metrics,
+            snap_response_permits: Arc::new(Semaphore::new(MAX_CONCURRENT_SNAP_RESPONSES)),
+        }
     }
 }
 
 impl<C> EthRequestHandler<C>
 where
-    C: BlockReader + HeaderProvider + ReceiptProvider,
+    C: BlockReader + HeaderProvider + ReceiptProvider + HashedStateRangeReader + Clone + 'static,
 {
     /// Returns the list of requested headers
     fn get_headers_response(&self, request: GetBlockHeaders) -> Vec<Header> {
@@ -226,6 +253,231 @@ where
 
         let _ = response.send(Ok(Receipts(receipts)));
     }
+
+    /// Builds the response of a `snap` request on a blocking thread and sends it through the
+    /// channel.
+    ///
+    /// Reading the state and building its proofs can take a while, which would otherwise stall
+    /// the other requests. If [`MAX_CONCURRENT_SNAP_RESPONSES`] are already being built, the
+    /// `unavailable` response is sent instead, which tells the peer that the data is not served.
+    fn spawn_snap_response<T, F>(
+        &self,
+        response: oneshot::Sender<RequestResult<T>>,
+        unavailable: T,
+        f: F,
+    ) where
+        T: Send + 'static,
+        F: FnOnce(C) -> T + Send + 'static,
+    {
+        let Ok(permit) = self.snap_response_permits.clone().try_acquire_owned() else {
+            let _ = response.send(Ok(unavailable));
+            return
+        };
+
+        let client = self.client.clone();
+        tokio::task::spawn_blocking(move || {
+            let _ = response.send(Ok(f(client)));
+            drop(permit);
+        });
+    }
+
+    fn on_account_range_request(
+        &mut self,
+        _peer_id: PeerId,
+        request: GetAccountRange,
+        response: oneshot::Sender<RequestResult<AccountRange>>,
+    ) {
+        self.metrics.received_snap_requests.increment(1);
+        let unavailable = AccountRange { request_id: request.request_id, ..Default::default() };
+        self.spawn_snap_response(response, unavailable, move |client| {
+            account_range_response(&client, request)
+        });
+    }
+
+    fn on_storage_ranges_request(
+        &mut self,
+        _peer_id: PeerId,
+        request: GetStorageRanges,
+        response: oneshot::Sender<RequestResult<StorageRanges>>,
+    ) {
+        self.metrics.received_snap_requests.increment(1);
+        let unavailable = StorageRanges { request_id: request.request_id, ..Default::default() };
+        self.spawn_snap_response(response, unavailable, move |client| {
+            storage_ranges_response(&client, request)
+        });
+    }
+
+    fn on_bytecodes_request(
+        &mut self,
+        _peer_id: PeerId,
+        request: GetByteCodes,
+        response: oneshot::Sender<RequestResult<ByteCodes>>,
+    ) {
+        self.metrics.received_snap_requests.increment(1);
+        let unavailable = ByteCodes { request_id: request.request_id, ..Default::default() };
+        self.spawn_snap_response(response, unavailable, move |client| {
+            bytecodes_response(&client, request)
+        });
+    }
+
+    fn on_trie_nodes_request(
+        &mut self,
+        _peer_id: PeerId,
+        request: GetTrieNodes,
+        response: oneshot::Sender<RequestResult<TrieNodes>>,
+    ) {
+        self.metrics.received_snap_requests.increment(1);
+        let unavailable = TrieNodes { request_id: request.request_id, ..Default::default() };
+        self.spawn_snap_response(response, unavailable, move |client| {
+            trie_nodes_response(&client, request)
+        });
+    }
+}
+
+/// Returns the [AccountRange] response to the request.
+fn account_range_response<C: HashedStateRangeReader>(
+    client: &C,
+    request: GetAccountRange,
+) -> AccountRange {
+    let GetAccountRange { request_id, root_hash, starting_hash, limit_hash, response_bytes } =
+        request;
+    let max_bytes = (response_bytes as usize).min(SOFT_RESPONSE_LIMIT);
+
+    // an unavailable state is answered with an empty range
+    let range = client
+        .hashed_account_range(root_hash, starting_hash, limit_hash, max_bytes)
+        .unwrap_or_default()
+        .unwrap_or_default();
+    let accounts = range
+        .accounts
+        .into_iter()
+        .map(|(hash, account, storage_root)| AccountData {
+            hash,
+            body: (account, storage_root).into(),
+        })
+        .collect();
+
+    AccountRange { request_id, accounts, proof: range.proof }
+}
+
+/// Returns the [StorageRanges] response to the request.
+fn storage_ranges_response<C: HashedStateRangeReader>(
+    client: &C,
+    request: GetStorageRanges,
+) -> StorageRanges {
+    let request_id = request.request_id;
+    let max_bytes = (request.response_bytes as usize).min(SOFT_RESPONSE_LIMIT);
+
+    // an invalid range or an unavailable state is answered with an empty range
+    let ranges = request
+        .start()
+        .zip(request.limit())
+        .and_then(|(start, limit)| {
+            client
+                .hashed_storage_ranges(
+                    request.root_hash,
+                    &request.account_hashes,
+                    start,
+                    limit,
+                    max_bytes,
+                )
+                .unwrap_or_default()
+        })
+        .unwrap_or_default();
+    let slots = ranges
+        .slots
+        .into_iter()
+        .map(|slots| {
+            slots
+                .into_iter()
+                .map(|slot| StorageData {
+                    hash: slot.key,
+                    data: alloy_rlp::encode(slot.value).into(),
+                })
+                .collect()
+        })
+        .collect();
+
+    StorageRanges { request_id, slots, proof: ranges.proof }
+}
+
+/// Returns the [ByteCodes] response to the request.
+fn bytecodes_response<C: HashedStateRangeReader>(client: &C, request: GetByteCodes) -> ByteCodes {
+    let GetByteCodes { request_id, mut hashes, response_bytes } = request;
+    hashes.truncate(MAX_BYTECODES_SERVE);
+    let max_bytes = (response_bytes as usize).min(SOFT_RESPONSE_LIMIT);
+
+    let codes = client.bytecodes(&hashes, max_bytes).unwrap_or_default();
+
+    ByteCodes { request_id, codes }
+}
+
+/// Returns the [TrieNodes] response to the request.
+fn trie_nodes_response<C: HashedStateRangeReader>(client: &C, request: GetTrieNodes) -> TrieNodes {
+    let GetTrieNodes { request_id, root_hash, paths, response_bytes } = request;
+    let max_bytes = (response_bytes as usize).min(SOFT_RESPONSE_LIMIT);
+
+    let mut nodes = Vec::new();
+    let mut total_bytes = 0;
+
+    // Each path set is either a single path of the account trie, or the hash of an account
+    // followed by paths of its storage trie.
+    for pathset in paths {
+        let nodes_of_pathset = match pathset.as_slice() {
+            [] => continue,
+            [path] => {
+                let Some(path) = decode_compact_path(path) else { break };
+                client.account_trie_nodes(root_hash, &[path])
+            }
+            [account, storage_paths @ ..] => {
+                let Ok(account) = B256::try_from(account.as_ref()) else { break };
+                let Some(paths) =
+                    storage_paths.iter().map(decode_compact_path).collect::<Option<Vec<_>>>()
+                else {
+                    break
+                };
+                client.storage_trie_nodes(root_hash, account, &paths)
+            }
+        };
+
+        // stop at the first unavailable path set
+        let Some(nodes_of_pathset) = nodes_of_pathset.unwrap_or_default() else { break };
+        for node in nodes_of_pathset {
+            total_bytes += node.len();
+            nodes.push(node);
+        }
+        if nodes.len() >= MAX_TRIE_NODES_SERVE || total_bytes > max_bytes {
+            break
+        }
+    }
+
+    TrieNodes { request_id, nodes }
+}
+
+/// Decodes a hex-prefix encoded trie path.
+///
+/// The high nibble of the first byte is the flag of the encoding, with the lowest bit set if the
+/// path has an odd number of nibbles, in which case the low nibble of the first byte is the first
+/// nibble of the path. Paths longer than [GetTrieNodes::MAX_PATH_LENGTH] are rejected.
+fn decode_compact_path(bytes: &Bytes) -> Option<Nibbles> {
+    if bytes.len() > GetTrieNodes::MAX_PATH_LENGTH {
+        return None
+    }
+    let (first, rest) = bytes.split_first()?;
+    let flag = first >> 4;
+    if flag > 3 {
+        return None
+    }
+
+    let mut nibbles = Vec::with_capacity(rest.len() * 2 + 1);
+    if flag & 1 == 1 {
+        nibbles.push(first & 0x0f);
+    }
+    for byte in rest {
+        nibbles.push(byte >> 4);
+        nibbles.push(byte & 0x0f);
+    }
+    Some(Nibbles::from_nibbles_unchecked(nibbles))
 }
 
 /// An endless future.
@@ -233,7 +485,7 @@ where
 /// This should be spawned or used as part of `tokio::select!`.
 impl<C> Future for EthRequestHandler<C>
 where
-    C: BlockReader + HeaderProvider + Unpin,
+    C: BlockReader + HeaderProvider + HashedStateRangeReader + Clone + Unpin + 'static,
 {
     type Output = ();
 
@@ -255,6 +507,18 @@ where
                     IncomingEthRequest::GetReceipts { peer_id, request, response } => {
                         this.on_receipts_request(peer_id, request, response)
                     }
+                    IncomingEthRequest::GetAccountRange { peer_id, request, response } => {
+                        this.on_account_range_request(peer_id, request, response)
+                    }
+                    IncomingEthRequest::GetStorageRanges { peer_id, request, response } => {
+                        this.on_storage_ranges_request(peer_id, request, response)
+                    }
+                    IncomingEthRequest::GetByteCodes { peer_id, request, response } => {
+                        this.on_bytecodes_request(peer_id, request, response)
+                    }
+                    IncomingEthRequest::GetTrieNodes { peer_id, request, response } => {
+                        this.on_trie_nodes_request(peer_id, request, response)
+                    }
                 },
             }
         }
@@ -323,4 +587,73 @@ pub enum IncomingEthRequest {
         /// The channel sender for the response containing receipts.
         response: oneshot::Sender<RequestResult<Receipts>>,
     },
+    /// Request a range of accounts from the peer over the `snap` protocol.
+    ///
+    /// The response should be sent through the channel.
+    GetAccountRange {
+        /// The ID of the peer to request the accounts from.
+        peer_id: PeerId,
+        /// The specific account range requested.
+        request: GetAccountRange,
+        /// The channel sender for the response containing the accounts.
+        response: oneshot::Sender<RequestResult<AccountRange>>,
+    },
+    /// Request storage ranges from the peer over the `snap` protocol.
+    ///
+    /// The response should be sent through the channel.
+    GetStorageRanges {
+        /// The ID of the peer to request the storage slots from.
+        peer_id: PeerId,
+        /// The specific storage ranges requested.
+        request: GetStorageRanges,
+        /// The channel sender for the response containing the storage slots.
+        response: oneshot::Sender<RequestResult<StorageRanges>>,
+    },
+    /// Request bytecodes from the peer over the `snap` protocol.
+    ///
+    /// The response should be sent through the channel.
+    GetByteCodes {
+        /// The ID of the peer to request the bytecodes from.
+        peer_id: PeerId,
+        /// The specific bytecodes requested.
+        request: GetByteCodes,
+        /// The channel sender for the response containing the bytecodes.
+        response: oneshot::Sender<RequestResult<ByteCodes>>,
+    },
+    /// Request trie nodes from the peer over the `snap` protocol.
+    ///
+    /// The response should be sent through the channel.
+    GetTrieNodes {
+        /// The ID of the peer to request the trie nodes from.
+        peer_id: PeerId,
+        /// The specific trie nodes requested.
+        request: GetTrieNodes,
+        /// The channel sender for the response containing the trie nodes.
+        response: oneshot::Sender<RequestResult<TrieNodes>>,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::hex;
+
+    #[test]
+    fn decode_hex_prefix_paths() {
+        let even = Bytes::from(hex!("00abcd").to_vec());
+        assert_eq!(
+            decode_compact_path(&even),
+            Some(Nibbles::from_nibbles_unchecked([0xa, 0xb, 0xc, 0xd]))
+        );
+
+        let odd = Bytes::from(hex!("1abc").to_vec());
+        assert_eq!(
+            decode_compact_path(&odd),
+            Some(Nibbles::from_nibbles_unchecked([0xa, 0xb, 0xc]))
+        );
+
+        assert_eq!(decode_compact_path(&Bytes::new()), None);
+        assert_eq!(decode_compact_path(&Bytes::from(hex!("40").to_vec())), None);
+        assert_eq!(decode_compact_path(&Bytes::from(vec![0; 34])), None);
+    }
 }
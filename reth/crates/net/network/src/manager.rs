@@ -28,6 +28,7 @@ use crate::{
     peers::{PeersHandle, PeersManager},
     protocol::IntoRlpxSubProtocol,
     session::SessionManager,
+    snap::SnapProtocolHandler,
     state::NetworkState,
     swarm::{Swarm, SwarmEvent},
     transactions::NetworkTransactionEvent,
@@ -99,6 +100,9 @@ pub struct NetworkManager<C> {
     /// requests. This channel size is set at
     /// [`ETH_REQUEST_CHANNEL_CAPACITY`](crate::builder::ETH_REQUEST_CHANNEL_CAPACITY)
     to_eth_request_handler: Option<mpsc::Sender<IncomingEthRequest>>,
+    /// Whether the `snap` protocol is served by the
+    /// [`EthRequestHandler`](crate::eth_requests::EthRequestHandler) once it is configured.
+    serve_snap: bool,
     /// Tracks the number of active session (connected peers).
     ///
     /// This is updated via internal events and shared via `Arc` with the [`NetworkHandle`]
@@ -121,7 +125,12 @@ impl<C> NetworkManager<C> {
 
     /// Sets the dedicated channel for events indented for the
     /// [`EthRequestHandler`](crate::eth_requests::EthRequestHandler).
+    ///
+    /// If the `snap` protocol is served, its requests are delegated to the same channel.
     pub fn set_eth_request_handler(&mut self, tx: mpsc::Sender<IncomingEthRequest>) {
+        if self.serve_snap {
+            self.add_rlpx_sub_protocol(SnapProtocolHandler::new(tx.clone()));
+        }
         self.to_eth_request_handler = Some(tx);
     }
 
@@ -162,6 +171,7 @@ where
             client,
             secret_key,
             mut discovery_v4_config,
+            mut discovery_v5_config,
             discovery_addr,
             listener_addr,
             peers_config,
@@ -177,6 +187,7 @@ where
             dns_discovery_config,
             extra_protocols,
             tx_gossip_disabled,
+            serve_snap,
             #[cfg(feature = "optimism")]
                 optimism_network_config: crate::config::OptimismNetworkConfig { sequencer_endpoint },
         } = config;
@@ -196,9 +207,18 @@ where
             disc_config
         });
 
-        let discovery =
-            Discovery::new(discovery_addr, secret_key, discovery_v4_config, dns_discovery_config)
-                .await?;
+        if let Some(disc_config) = discovery_v5_config.as_mut() {
+            disc_config.set_fork_id(status.forkid);
+        }
+
+        let discovery = Discovery::new(
+            discovery_addr,
+            secret_key,
+            discovery_v4_config,
+            discovery_v5_config,
+            dns_discovery_config,
+        )
+        .await?;
         // need to retrieve the addr here since provided port could be `0`
         let local_peer_id = discovery.local_id();
 
@@ -251,6 +271,7 @@ where
             event_listeners: Default::default(),
             to_transactions_manager: None,
             to_eth_request_handler: None,
+            serve_snap,
             num_active_peers,
             metrics: Default::default(),
             disconnect_metrics: Default::default(),
@@ -151,4 +151,7 @@ pub struct EthRequestHandlerMetrics {
 
     /// Number of received bodies requests
     pub(crate) received_bodies_requests: Counter,
+
+    /// Number of received snap requests
+    pub(crate) received_snap_requests: Counter,
 }
@@ -0,0 +1,244 @@
+//! Serves the `snap` protocol to peers.
+//!
+//! See also <https://github.com/ethereum/devp2p/blob/master/caps/snap.md>
+
+use crate::{
+    eth_requests::IncomingEthRequest,
+    protocol::{ConnectionHandler, OnNotSupported, ProtocolHandler},
+};
+use alloy_rlp::Encodable;
+use futures::{stream::FuturesUnordered, Stream, StreamExt};
+use reth_eth_wire::{
+    capability::SharedCapabilities, multiplex::ProtocolConnection, protocol::Protocol,
+    AccountRange, ByteCodes, SnapMessage, StorageRanges, TrieNodes,
+};
+use reth_interfaces::p2p::error::RequestResult;
+use reth_network_api::Direction;
+use reth_primitives::{BytesMut, PeerId};
+use std::{
+    fmt,
+    future::Future,
+    net::SocketAddr,
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+use tokio::sync::{mpsc, oneshot};
+use tracing::trace;
+
+/// The maximum number of requests of a peer that are served concurrently.
+///
+/// Further requests are read from the connection once a response has been sent.
+const MAX_CONCURRENT_SNAP_REQUESTS: usize = 16;
+
+/// A [ProtocolHandler] that serves the `snap/1` protocol.
+///
+/// The requests of peers are delegated to the
+/// [EthRequestHandler](crate::eth_requests::EthRequestHandler). Only the serving side of the
+/// protocol is implemented, so responses of peers are treated as a protocol breach.
+#[derive(Debug, Clone)]
+pub struct SnapProtocolHandler {
+    /// Sender half of the channel to the request handler.
+    to_eth_request_handler: mpsc::Sender<IncomingEthRequest>,
+}
+
+impl SnapProtocolHandler {
+    /// Creates a new handler that delegates requests to the given channel of the request handler.
+    pub fn new(to_eth_request_handler: mpsc::Sender<IncomingEthRequest>) -> Self {
+        Self { to_eth_request_handler }
+    }
+}
+
+impl ProtocolHandler for SnapProtocolHandler {
+    type ConnectionHandler = SnapConnectionHandler;
+
+    fn on_incoming(&self, _socket_addr: SocketAddr) -> Option<Self::ConnectionHandler> {
+        Some(SnapConnectionHandler { to_eth_request_handler: self.to_eth_request_handler.clone() })
+    }
+
+    fn on_outgoing(
+        &self,
+        _socket_addr: SocketAddr,
+        _peer_id: PeerId,
+    ) -> Option<Self::ConnectionHandler> {
+        Some(SnapConnectionHandler { to_eth_request_handler: self.to_eth_request_handler.clone() })
+    }
+}
+
+/// The [ConnectionHandler] of the `snap/1` protocol.
+#[derive(Debug)]
+pub struct SnapConnectionHandler {
+    to_eth_request_handler: mpsc::Sender<IncomingEthRequest>,
+}
+
+impl ConnectionHandler for SnapConnectionHandler {
+    type Connection = SnapConnection;
+
+    fn protocol(&self) -> Protocol {
+        Protocol::snap_1()
+    }
+
+    fn on_unsupported_by_peer(
+        self,
+        _supported: &SharedCapabilities,
+        _direction: Direction,
+        _peer_id: PeerId,
+    ) -> OnNotSupported {
+        OnNotSupported::KeepAlive
+    }
+
+    fn into_connection(
+        self,
+        _direction: Direction,
+        peer_id: PeerId,
+        conn: ProtocolConnection,
+    ) -> Self::Connection {
+        SnapConnection {
+            peer_id,
+            conn,
+            to_eth_request_handler: self.to_eth_request_handler,
+            pending_responses: Default::default(),
+        }
+    }
+}
+
+/// A response that is being served, resolves to the encoded message or `None` if the request was
+/// dropped.
+type PendingSnapResponse = Pin<Box<dyn Future<Output = Option<BytesMut>> + Send>>;
+
+/// A `snap/1` connection with a peer that answers its requests.
+#[must_use = "Streams do nothing unless polled."]
+pub struct SnapConnection {
+    /// The peer of the connection.
+    peer_id: PeerId,
+    /// The connection to the peer.
+    conn: ProtocolConnection,
+    /// Sender half of the channel to the request handler.
+    to_eth_request_handler: mpsc::Sender<IncomingEthRequest>,
+    /// The responses that are being served.
+    pending_responses: FuturesUnordered<PendingSnapResponse>,
+}
+
+impl SnapConnection {
+    /// Delegates the request to the request handler.
+    ///
+    /// Returns `false` if the message is not a request.
+    fn on_message(&mut self, msg: SnapMessage) -> bool {
+        let peer_id = self.peer_id;
+        let request = match msg {
+            SnapMessage::GetAccountRange(request) => {
+                let (response, rx) = oneshot::channel();
+                self.pending_responses.push(encode_response(rx, SnapMessage::AccountRange));
+                IncomingEthRequest::GetAccountRange { peer_id, request, response }
+            }
+            SnapMessage::GetStorageRanges(request) => {
+                let (response, rx) = oneshot::channel();
+                self.pending_responses.push(encode_response(rx, SnapMessage::StorageRanges));
+                IncomingEthRequest::GetStorageRanges { peer_id, request, response }
+            }
+            SnapMessage::GetByteCodes(request) => {
+                let (response, rx) = oneshot::channel();
+                self.pending_responses.push(encode_response(rx, SnapMessage::ByteCodes));
+                IncomingEthRequest::GetByteCodes { peer_id, request, response }
+            }
+            SnapMessage::GetTrieNodes(request) => {
+                let (response, rx) = oneshot::channel();
+                self.pending_responses.push(encode_response(rx, SnapMessage::TrieNodes));
+                IncomingEthRequest::GetTrieNodes { peer_id, request, response }
+            }
+            SnapMessage::AccountRange(_) |
+            SnapMessage::StorageRanges(_) |
+            SnapMessage::ByteCodes(_) |
+            SnapMessage::TrieNodes(_) => return false,
+        };
+
+        // if the handler is busy, the request is answered right away with an empty response, so
+        // that the peer does not wait for it to time out
+        if let Err(err) = self.to_eth_request_handler.try_send(request) {
+            respond_unavailable(err.into_inner());
+        }
+        true
+    }
+}
+
+impl Stream for SnapConnection {
+    type Item = BytesMut;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            if let Poll::Ready(Some(response)) = this.pending_responses.poll_next_unpin(cx) {
+                match response {
+                    Some(msg) => return Poll::Ready(Some(msg)),
+                    None => continue,
+                }
+            }
+
+            if this.pending_responses.len() >= MAX_CONCURRENT_SNAP_REQUESTS {
+                return Poll::Pending
+            }
+
+            let Some(msg) = ready!(this.conn.poll_next_unpin(cx)) else { return Poll::Ready(None) };
+
+            let msg = match SnapMessage::decode_message(&mut &msg[..]) {
+                Ok(msg) => msg,
+                Err(err) => {
+                    trace!(target: "net::snap", %err, peer_id=?this.peer_id, "Failed to decode snap message");
+                    return Poll::Ready(None)
+                }
+            };
+
+            if !this.on_message(msg) {
+                trace!(target: "net::snap", peer_id=?this.peer_id, "Received unsolicited snap response");
+                return Poll::Ready(None)
+            }
+        }
+    }
+}
+
+impl fmt::Debug for SnapConnection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SnapConnection")
+            .field("peer_id", &self.peer_id)
+            .field("pending_responses", &self.pending_responses.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Returns the future that encodes the response of the request handler as a message.
+fn encode_response<T: Send + 'static>(
+    rx: oneshot::Receiver<RequestResult<T>>,
+    into_message: fn(T) -> SnapMessage,
+) -> PendingSnapResponse {
+    Box::pin(async move {
+        let response = rx.await.ok()?.ok()?;
+        let mut buf = BytesMut::new();
+        into_message(response).encode(&mut buf);
+        Some(buf)
+    })
+}
+
+/// Answers a `snap` request with an empty response, which tells the peer that the requested data
+/// is not available.
+fn respond_unavailable(request: IncomingEthRequest) {
+    match request {
+        IncomingEthRequest::GetAccountRange { request, response, .. } => {
+            let _ = response
+                .send(Ok(AccountRange { request_id: request.request_id, ..Default::default() }));
+        }
+        IncomingEthRequest::GetStorageRanges { request, response, .. } => {
+            let _ = response
+                .send(Ok(StorageRanges { request_id: request.request_id, ..Default::default() }));
+        }
+        IncomingEthRequest::GetByteCodes { request, response, .. } => {
+            let _ = response
+                .send(Ok(ByteCodes { request_id: request.request_id, ..Default::default() }));
+        }
+        IncomingEthRequest::GetTrieNodes { request, response, .. } => {
+            let _ = response
+                .send(Ok(TrieNodes { request_id: request.request_id, ..Default::default() }));
+        }
+        // only `snap` requests are sent by the connection
+        _ => {}
+    }
+}
@@ -15,7 +15,8 @@ use reth_eth_wire::{protocol::Protocol, DisconnectReason, HelloMessageWithProtoc
 use reth_network_api::{NetworkInfo, Peers};
 use reth_primitives::{PeerId, MAINNET};
 use reth_provider::{
-    test_utils::NoopProvider, BlockReader, BlockReaderIdExt, HeaderProvider, StateProviderFactory,
+    test_utils::NoopProvider, BlockReader, BlockReaderIdExt, HashedStateRangeReader,
+    HeaderProvider, StateProviderFactory,
 };
 use reth_tasks::TokioTaskExecutor;
 use reth_transaction_pool::{
@@ -192,7 +193,7 @@ where
 
 impl<C, Pool> Testnet<C, Pool>
 where
-    C: BlockReader + HeaderProvider + Clone + Unpin + 'static,
+    C: BlockReader + HeaderProvider + HashedStateRangeReader + Clone + Unpin + 'static,
     Pool: TransactionPool + Unpin + 'static,
 {
     /// Spawns the testnet to a separate task
@@ -251,7 +252,7 @@ impl<C, Pool> fmt::Debug for Testnet<C, Pool> {
 
 impl<C, Pool> Future for Testnet<C, Pool>
 where
-    C: BlockReader + HeaderProvider + Unpin,
+    C: BlockReader + HeaderProvider + HashedStateRangeReader + Clone + Unpin + 'static,
     Pool: TransactionPool + Unpin + 'static,
 {
     type Output = ();
@@ -431,7 +432,7 @@ where
 
 impl<C, Pool> Future for Peer<C, Pool>
 where
-    C: BlockReader + HeaderProvider + Unpin,
+    C: BlockReader + HeaderProvider + HashedStateRangeReader + Clone + Unpin + 'static,
     Pool: TransactionPool + Unpin + 'static,
 {
     type Output = ();
@@ -1302,7 +1302,9 @@ impl PooledTransactionsHashesBuilder {
             EthVersion::Eth66 | EthVersion::Eth67 => {
                 PooledTransactionsHashesBuilder::Eth66(Default::default())
             }
-            EthVersion::Eth68 => PooledTransactionsHashesBuilder::Eth68(Default::default()),
+            EthVersion::Eth68 | EthVersion::Eth69 => {
+                PooledTransactionsHashesBuilder::Eth68(Default::default())
+            }
         }
     }
 
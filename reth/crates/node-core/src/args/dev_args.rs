@@ -39,6 +39,12 @@ pub struct DevArgs {
         verbatim_doc_comment
     )]
     pub block_time: Option<Duration>,
+
+    /// Keep the database in memory instead of on disk.
+    ///
+    /// The chain is lost when the node stops.
+    #[arg(long = "dev.in-memory-db", help_heading = "Dev testnet", requires = "dev")]
+    pub in_memory_db: bool,
 }
 
 #[cfg(test)]
@@ -56,13 +62,37 @@ mod tests {
     #[test]
     fn test_parse_dev_args() {
         let args = CommandParser::<DevArgs>::parse_from(["reth"]).args;
-        assert_eq!(args, DevArgs { dev: false, block_max_transactions: None, block_time: None });
+        assert_eq!(
+            args,
+            DevArgs {
+                dev: false,
+                block_max_transactions: None,
+                block_time: None,
+                in_memory_db: false
+            }
+        );
 
         let args = CommandParser::<DevArgs>::parse_from(["reth", "--dev"]).args;
-        assert_eq!(args, DevArgs { dev: true, block_max_transactions: None, block_time: None });
+        assert_eq!(
+            args,
+            DevArgs {
+                dev: true,
+                block_max_transactions: None,
+                block_time: None,
+                in_memory_db: false
+            }
+        );
 
         let args = CommandParser::<DevArgs>::parse_from(["reth", "--auto-mine"]).args;
-        assert_eq!(args, DevArgs { dev: true, block_max_transactions: None, block_time: None });
+        assert_eq!(
+            args,
+            DevArgs {
+                dev: true,
+                block_max_transactions: None,
+                block_time: None,
+                in_memory_db: false
+            }
+        );
 
         let args = CommandParser::<DevArgs>::parse_from([
             "reth",
@@ -71,7 +101,15 @@ mod tests {
             "2",
         ])
         .args;
-        assert_eq!(args, DevArgs { dev: true, block_max_transactions: Some(2), block_time: None });
+        assert_eq!(
+            args,
+            DevArgs {
+                dev: true,
+                block_max_transactions: Some(2),
+                block_time: None,
+                in_memory_db: false
+            }
+        );
 
         let args =
             CommandParser::<DevArgs>::parse_from(["reth", "--dev", "--dev.block-time", "1s"]).args;
@@ -80,11 +118,22 @@ mod tests {
             DevArgs {
                 dev: true,
                 block_max_transactions: None,
-                block_time: Some(std::time::Duration::from_secs(1))
+                block_time: Some(std::time::Duration::from_secs(1)),
+                in_memory_db: false
             }
         );
     }
 
+    #[test]
+    fn test_parse_dev_args_in_memory_db() {
+        let args =
+            CommandParser::<DevArgs>::parse_from(["reth", "--dev", "--dev.in-memory-db"]).args;
+        assert!(args.in_memory_db);
+
+        let args = CommandParser::<DevArgs>::try_parse_from(["reth", "--dev.in-memory-db"]);
+        assert!(args.is_err());
+    }
+
     #[test]
     fn test_parse_dev_args_conflicts() {
         let args = CommandParser::<DevArgs>::try_parse_from([
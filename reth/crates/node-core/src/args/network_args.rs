@@ -4,6 +4,11 @@ use crate::version::P2P_CLIENT_VERSION;
 use clap::Args;
 use reth_config::Config;
 use reth_discv4::{DEFAULT_DISCOVERY_ADDR, DEFAULT_DISCOVERY_PORT};
+use reth_discv5::{
+    config::DEFAULT_DISCOVERY_V5_PORT,
+    discv5::enr::{CombinedKey, Enr},
+    Discv5Config,
+};
 use reth_net_nat::NatResolver;
 use reth_network::{HelloMessageWithProtocols, NetworkConfigBuilder};
 use reth_primitives::{mainnet_nodes, ChainSpec, NodeRecord};
@@ -73,6 +78,16 @@ pub struct NetworkArgs {
     /// Maximum number of inbound requests. default: 30
     #[arg(long)]
     pub max_inbound_peers: Option<usize>,
+
+    /// Serve the snap protocol, so that peers can snap sync from this node.
+    #[arg(long)]
+    pub serve_snap: bool,
+
+    /// Advertise the eth/69 protocol to peers.
+    ///
+    /// Peers that negotiate eth/69 don't send their total difficulty.
+    #[arg(long)]
+    pub eth69: bool,
 }
 
 impl NetworkArgs {
@@ -103,7 +118,9 @@ impl NetworkArgs {
             .network_config(self.nat, self.persistent_peers_file(peers_file), secret_key)
             .peer_config(peer_config)
             .boot_nodes(self.bootnodes.clone().unwrap_or(chain_bootnodes))
-            .chain_spec(chain_spec);
+            .chain_spec(chain_spec)
+            .serve_snap(self.serve_snap)
+            .eth69(self.eth69);
 
         // Configure node identity
         let peer_id = network_config_builder.get_peer_id();
@@ -155,6 +172,8 @@ impl Default for NetworkArgs {
             port: DEFAULT_DISCOVERY_PORT,
             max_outbound_peers: None,
             max_inbound_peers: None,
+            serve_snap: false,
+            eth69: false,
         }
     }
 }
@@ -174,6 +193,10 @@ pub struct DiscoveryArgs {
     #[arg(long, conflicts_with = "disable_discovery")]
     pub disable_discv4_discovery: bool,
 
+    /// Enable Discv5 discovery, which runs alongside Discv4.
+    #[arg(long, conflicts_with = "disable_discovery")]
+    pub enable_discv5_discovery: bool,
+
     /// The UDP address to use for P2P discovery/networking
     #[arg(long = "discovery.addr", name = "discovery.addr", value_name = "DISCOVERY_ADDR", default_value_t = DEFAULT_DISCOVERY_ADDR)]
     pub addr: Ipv4Addr,
@@ -181,6 +204,19 @@ pub struct DiscoveryArgs {
     /// The UDP port to use for P2P discovery/networking
     #[arg(long = "discovery.port", name = "discovery.port", value_name = "DISCOVERY_PORT", default_value_t = DEFAULT_DISCOVERY_PORT)]
     pub port: u16,
+
+    /// The UDP port to use for Discv5 discovery, on the same address as Discv4
+    #[arg(long = "discovery.v5.port", name = "discovery.v5.port", value_name = "DISCOVERY_V5_PORT", default_value_t = DEFAULT_DISCOVERY_V5_PORT)]
+    pub discv5_port: u16,
+
+    /// Comma separated ENRs for Discv5 discovery bootstrap
+    #[arg(
+        long = "discovery.v5.bootnodes",
+        name = "discovery.v5.bootnodes",
+        value_name = "ENRS",
+        value_delimiter = ','
+    )]
+    pub discv5_bootnodes: Vec<Enr<CombinedKey>>,
 }
 
 impl DiscoveryArgs {
@@ -196,6 +232,14 @@ impl DiscoveryArgs {
         if self.disable_discovery || self.disable_discv4_discovery {
             network_config_builder = network_config_builder.disable_discv4_discovery();
         }
+
+        if self.enable_discv5_discovery && !self.disable_discovery {
+            network_config_builder = network_config_builder.discovery_v5(
+                Discv5Config::builder()
+                    .discovery_addr((self.addr, self.discv5_port).into())
+                    .add_boot_nodes(self.discv5_bootnodes.clone()),
+            );
+        }
         network_config_builder
     }
 
@@ -203,6 +247,7 @@ impl DiscoveryArgs {
     /// discovery binds to the socket.
     pub fn with_unused_discovery_port(mut self) -> Self {
         self.port = 0;
+        self.discv5_port = 0;
         self
     }
 }
@@ -213,8 +258,11 @@ impl Default for DiscoveryArgs {
             disable_discovery: false,
             disable_dns_discovery: false,
             disable_discv4_discovery: false,
+            enable_discv5_discovery: false,
             addr: DEFAULT_DISCOVERY_ADDR,
             port: DEFAULT_DISCOVERY_PORT,
+            discv5_port: DEFAULT_DISCOVERY_V5_PORT,
+            discv5_bootnodes: Vec::new(),
         }
     }
 }
@@ -278,6 +326,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_discv5_args() {
+        let args = CommandParser::<NetworkArgs>::parse_from(["reth"]).args;
+        assert!(!args.discovery.enable_discv5_discovery);
+
+        let args = CommandParser::<NetworkArgs>::parse_from([
+            "reth",
+            "--enable-discv5-discovery",
+            "--discovery.v5.port",
+            "9001",
+        ])
+        .args;
+        assert!(args.discovery.enable_discv5_discovery);
+        assert_eq!(args.discovery.discv5_port, 9001);
+
+        let res = CommandParser::<NetworkArgs>::try_parse_from([
+            "reth",
+            "--enable-discv5-discovery",
+            "--disable-discovery",
+        ]);
+        assert!(res.is_err());
+    }
+
     #[test]
     fn network_args_default_sanity_test() {
         let default_args = NetworkArgs::default();
@@ -21,8 +21,9 @@ use rand::Rng;
 use reth_network_api::{NetworkInfo, Peers};
 use reth_node_api::{ConfigureEvmEnv, EngineTypes};
 use reth_provider::{
-    AccountReader, BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader,
-    EvmEnvProvider, HeaderProvider, StateProviderFactory,
+    AccountReader, AddressTransactionsReader, BlockReaderIdExt, CanonStateSubscriptions,
+    ChainSpecProvider, ChangeSetReader, EvmEnvProvider, HeaderProvider, LogIndexReader,
+    StateProviderFactory, StateRangeReader,
 };
 use reth_rpc::{
     eth::{cache::EthStateCacheConfig, gas_oracle::GasPriceOracleConfig, RPC_DEFAULT_GAS_CAP},
@@ -164,6 +165,11 @@ pub struct RpcServerArgs {
     #[arg(long, value_name = "COUNT", default_value_t = ZeroAsNoneU64::new(constants::DEFAULT_MAX_LOGS_PER_RESPONSE as u64))]
     pub rpc_max_logs_per_response: ZeroAsNoneU64,
 
+    /// Maximum number of blocks that could be scanned per `trace_filter` request. (0 = entire
+    /// chain)
+    #[arg(long, value_name = "COUNT", default_value_t = ZeroAsNoneU64::new(constants::DEFAULT_MAX_TRACE_FILTER_BLOCKS))]
+    pub rpc_max_trace_filter_blocks: ZeroAsNoneU64,
+
     /// Maximum gas limit for `eth_call` and call tracing RPC methods.
     #[arg(
         long,
@@ -174,6 +180,27 @@ pub struct RpcServerArgs {
     )]
     pub rpc_gas_cap: u64,
 
+    /// The maximum number of blocks behind the tip for which `eth_getProof` is served.
+    #[arg(
+        long,
+        alias = "rpc.eth-proof-window",
+        value_name = "COUNT",
+        value_parser = RangedU64ValueParser::<u64>::new().range(..=constants::MAX_ETH_PROOF_WINDOW),
+        default_value_t = constants::DEFAULT_ETH_PROOF_WINDOW
+    )]
+    pub rpc_eth_proof_window: u64,
+
+    /// The maximum number of blocks behind the tip whose state is served by `debug_accountRange`,
+    /// `debug_dumpBlock` and `debug_storageRangeAt`.
+    #[arg(
+        long,
+        alias = "rpc.debug-state-window",
+        value_name = "COUNT",
+        value_parser = RangedU64ValueParser::<u64>::new().range(..=constants::MAX_DEBUG_STATE_WINDOW),
+        default_value_t = constants::DEFAULT_DEBUG_STATE_WINDOW
+    )]
+    pub rpc_debug_state_window: u64,
+
     /// State cache configuration.
     #[clap(flatten)]
     pub rpc_state_cache: RpcStateCacheArgs,
@@ -361,6 +388,9 @@ impl RpcServerArgs {
             + EvmEnvProvider
             + ChainSpecProvider
             + ChangeSetReader
+            + AddressTransactionsReader
+            + StateRangeReader
+            + LogIndexReader
             + Clone
             + Unpin
             + 'static,
@@ -401,6 +431,7 @@ impl RpcServerArgs {
             + EvmEnvProvider
             + HeaderProvider
             + StateProviderFactory
+            + LogIndexReader
             + Clone
             + Unpin
             + 'static,
@@ -441,7 +472,10 @@ impl RethRpcConfig for RpcServerArgs {
             .max_tracing_requests(self.rpc_max_tracing_requests)
             .max_blocks_per_filter(self.rpc_max_blocks_per_filter.unwrap_or_max())
             .max_logs_per_response(self.rpc_max_logs_per_response.unwrap_or_max() as usize)
+            .max_trace_filter_blocks(self.rpc_max_trace_filter_blocks.unwrap_or_max())
             .rpc_gas_cap(self.rpc_gas_cap)
+            .eth_proof_window(self.rpc_eth_proof_window)
+            .debug_state_window(self.rpc_debug_state_window)
             .state_cache(self.state_cache_config())
             .gpo_config(self.gas_price_oracle_config())
     }
@@ -582,7 +616,10 @@ impl Default for RpcServerArgs {
             rpc_max_tracing_requests: constants::DEFAULT_MAX_TRACING_REQUESTS,
             rpc_max_blocks_per_filter: constants::DEFAULT_MAX_BLOCKS_PER_FILTER.into(),
             rpc_max_logs_per_response: (constants::DEFAULT_MAX_LOGS_PER_RESPONSE as u64).into(),
+            rpc_max_trace_filter_blocks: constants::DEFAULT_MAX_TRACE_FILTER_BLOCKS.into(),
             rpc_gas_cap: RPC_DEFAULT_GAS_CAP.into(),
+            rpc_eth_proof_window: constants::DEFAULT_ETH_PROOF_WINDOW,
+            rpc_debug_state_window: constants::DEFAULT_DEBUG_STATE_WINDOW,
             gas_price_oracle: GasPriceOracleArgs::default(),
             rpc_state_cache: RpcStateCacheArgs::default(),
         }
@@ -650,6 +687,48 @@ mod tests {
         assert!(args.is_err());
     }
 
+    #[test]
+    fn test_rpc_eth_proof_window() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        let config = args.eth_config();
+        assert_eq!(config.eth_proof_window, constants::DEFAULT_ETH_PROOF_WINDOW);
+
+        let args =
+            CommandParser::<RpcServerArgs>::parse_from(["reth", "--rpc-eth-proof-window", "128"])
+                .args;
+        let config = args.eth_config();
+        assert_eq!(config.eth_proof_window, 128);
+
+        let max = (constants::MAX_ETH_PROOF_WINDOW + 1).to_string();
+        let args = CommandParser::<RpcServerArgs>::try_parse_from([
+            "reth",
+            "--rpc-eth-proof-window",
+            max.as_str(),
+        ]);
+        assert!(args.is_err());
+    }
+
+    #[test]
+    fn test_rpc_debug_state_window() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        let config = args.eth_config();
+        assert_eq!(config.debug_state_window, constants::DEFAULT_DEBUG_STATE_WINDOW);
+
+        let args =
+            CommandParser::<RpcServerArgs>::parse_from(["reth", "--rpc-debug-state-window", "0"])
+                .args;
+        let config = args.eth_config();
+        assert_eq!(config.debug_state_window, 0);
+
+        let max = (constants::MAX_DEBUG_STATE_WINDOW + 1).to_string();
+        let args = CommandParser::<RpcServerArgs>::try_parse_from([
+            "reth",
+            "--rpc-debug-state-window",
+            max.as_str(),
+        ]);
+        assert!(args.is_err());
+    }
+
     #[test]
     fn test_rpc_server_args_parser() {
         let args =
@@ -805,6 +884,23 @@ mod tests {
         assert_eq!(config.max_logs_per_response, Some(200));
     }
 
+    #[test]
+    fn test_trace_filter_limit() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        assert_eq!(
+            args.eth_config().max_trace_filter_blocks,
+            constants::DEFAULT_MAX_TRACE_FILTER_BLOCKS
+        );
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc-max-trace-filter-blocks",
+            "0",
+        ])
+        .args;
+        assert_eq!(args.eth_config().max_trace_filter_blocks, u64::MAX);
+    }
+
     #[test]
     fn rpc_server_args_default_sanity_test() {
         let default_args = RpcServerArgs::default();
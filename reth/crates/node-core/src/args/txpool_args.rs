@@ -1,13 +1,19 @@
 //! Transaction pool arguments
 
-use crate::cli::config::RethTransactionPoolConfig;
+use crate::{
+    args::utils::{admission_policy_config_value_parser, parse_duration_from_secs},
+    cli::config::RethTransactionPoolConfig,
+};
 use clap::Args;
 use reth_primitives::Address;
 use reth_transaction_pool::{
-    LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, DEFAULT_PRICE_BUMP,
-    REPLACE_BLOB_PRICE_BUMP, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
-    TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT, TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
+    maintain::DEFAULT_POOL_JOURNAL_INTERVAL, validate::AdmissionPolicyConfig,
+    LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, TransactionOrderingKind,
+    DEFAULT_MAX_QUEUED_LIFETIME, DEFAULT_PRICE_BUMP, REPLACE_BLOB_PRICE_BUMP,
+    TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER, TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT,
+    TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
 };
+use std::time::Duration;
 /// Parameters for debugging purposes
 #[derive(Debug, Args, PartialEq)]
 #[clap(next_help_heading = "TxPool")]
@@ -53,6 +59,21 @@ pub struct TxPoolArgs {
     /// Flag to toggle local transaction propagation.
     #[arg(long = "txpool.no-local-transactions-propagation")]
     pub no_local_transactions_propagation: bool,
+    /// Flag to journal the entire transaction pool, instead of only local transactions.
+    #[arg(long = "txpool.journal")]
+    pub journal: bool,
+    /// Interval in seconds at which the transaction pool is journaled.
+    #[arg(long = "txpool.journal_interval", value_parser = parse_duration_from_secs, default_value = "60", value_name = "SECONDS")]
+    pub journal_interval: Duration,
+    /// Maximum time in seconds non-local transactions are queued before they're evicted.
+    #[arg(long = "txpool.lifetime", value_parser = parse_duration_from_secs, default_value = "10800", value_name = "SECONDS")]
+    pub lifetime: Duration,
+    /// Path to a JSON file with the admission policies that transactions have to satisfy.
+    #[arg(long = "txpool.admission-policies", value_name = "FILE", value_parser = admission_policy_config_value_parser)]
+    pub admission_policies: Option<AdmissionPolicyConfig>,
+    /// How pending transactions are ordered when building blocks.
+    #[arg(long = "txpool.ordering", value_enum, default_value_t = TransactionOrderingKind::CoinbaseTip)]
+    pub ordering: TransactionOrderingKind,
 }
 
 impl Default for TxPoolArgs {
@@ -70,6 +91,11 @@ impl Default for TxPoolArgs {
             no_locals: false,
             locals: Default::default(),
             no_local_transactions_propagation: false,
+            journal: false,
+            journal_interval: DEFAULT_POOL_JOURNAL_INTERVAL,
+            lifetime: DEFAULT_MAX_QUEUED_LIFETIME,
+            admission_policies: None,
+            ordering: TransactionOrderingKind::CoinbaseTip,
         }
     }
 }
@@ -104,6 +130,7 @@ impl RethTransactionPoolConfig for TxPoolArgs {
                 default_price_bump: self.price_bump,
                 replace_blob_tx_price_bump: self.blob_transaction_price_bump,
             },
+            max_queued_lifetime: self.lifetime,
         }
     }
 }
@@ -1,6 +1,7 @@
 //! Clap parser utilities
 
 use reth_primitives::{fs, AllGenesisFormats, BlockHashOrNumber, ChainSpec, B256};
+use reth_transaction_pool::validate::AdmissionPolicyConfig;
 use std::{
     net::{IpAddr, Ipv4Addr, SocketAddr, ToSocketAddrs},
     path::PathBuf,
@@ -55,6 +56,15 @@ pub fn chain_spec_value_parser(s: &str) -> eyre::Result<Arc<ChainSpec>, eyre::Er
     })
 }
 
+/// Clap value parser for an [AdmissionPolicyConfig] that's loaded from the JSON file at the given
+/// path.
+pub fn admission_policy_config_value_parser(
+    s: &str,
+) -> eyre::Result<AdmissionPolicyConfig, eyre::Error> {
+    let raw = fs::read_to_string(PathBuf::from(shellexpand::full(s)?.into_owned()))?;
+    Ok(serde_json::from_str(&raw)?)
+}
+
 /// The help info for the --chain flag
 pub fn chain_help() -> String {
     format!("The chain this node is running.\nPossible values are either a built-in chain or the path to a chain specification file.\n\nBuilt-in chains:\n    {}", SUPPORTED_CHAINS.join(", "))
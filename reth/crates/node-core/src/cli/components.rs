@@ -6,8 +6,9 @@ use reth_network_api::{NetworkInfo, Peers};
 use reth_node_api::ConfigureEvmEnv;
 use reth_primitives::ChainSpec;
 use reth_provider::{
-    AccountReader, BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader,
-    DatabaseProviderFactory, EvmEnvProvider, StateProviderFactory,
+    AccountReader, AddressTransactionsReader, BlockReaderIdExt, CanonStateSubscriptions,
+    ChainSpecProvider, ChangeSetReader, DatabaseProviderFactory, EvmEnvProvider, LogIndexReader,
+    StateProviderFactory, StateRangeReader,
 };
 use reth_rpc_builder::{
     auth::{AuthRpcModule, AuthServerHandle},
@@ -26,6 +27,9 @@ pub trait FullProvider<DB: Database>:
     + EvmEnvProvider
     + ChainSpecProvider
     + ChangeSetReader
+    + AddressTransactionsReader
+    + StateRangeReader
+    + LogIndexReader
     + Clone
     + Unpin
     + 'static
@@ -40,6 +44,9 @@ impl<T, DB: Database> FullProvider<DB> for T where
         + EvmEnvProvider
         + ChainSpecProvider
         + ChangeSetReader
+        + AddressTransactionsReader
+        + StateRangeReader
+        + LogIndexReader
         + Clone
         + Unpin
         + 'static
@@ -1,24 +1,27 @@
-//! A real or test database type
+//! A real, test or in-memory database type
 
 use crate::dirs::{ChainPath, DataDirPath, MaybePlatformPath};
 use alloy_chains::Chain;
 use reth_db::{
     init_db,
     mdbx::DatabaseArguments,
+    memory::MemoryDatabase,
     test_utils::{create_test_rw_db, TempDatabase},
     DatabaseEnv,
 };
 use reth_interfaces::db::LogLevel;
 use std::{str::FromStr, sync::Arc};
 
-/// A type that represents either a _real_ (represented by a path), or _test_ database, which will
-/// use a [TempDatabase].
+/// A type that represents either a _real_ (represented by a path), _test_ database, which will
+/// use a [TempDatabase], or _in-memory_ database, which will use a [MemoryDatabase].
 #[derive(Debug)]
 pub enum DatabaseBuilder {
     /// The real database type, with a specified data dir
     Real(MaybePlatformPath<DataDirPath>),
     /// The test database type
     Test,
+    /// The in-memory database type, with a specified data dir for everything but the database
+    InMemory(MaybePlatformPath<DataDirPath>),
 }
 
 impl DatabaseBuilder {
@@ -35,6 +38,9 @@ impl DatabaseBuilder {
     /// If the [DatabaseBuilder] is real, then the db will be initialized using the given log level
     /// and the [ChainPath] will be derived from the given path and chain. This database path is
     /// then passed into [init_db].
+    ///
+    /// If the [DatabaseBuilder] is in-memory, then an empty [MemoryDatabase] is created, which is
+    /// lost on shutdown, and the [ChainPath] will be derived from the given path and chain.
     pub fn init_db(
         self,
         log_level: Option<LogLevel>,
@@ -61,6 +67,13 @@ impl DatabaseBuilder {
                 );
                 Ok(DatabaseInstance::Real { db, data_dir })
             }
+            DatabaseBuilder::InMemory(path) => {
+                let data_dir = path.unwrap_or_chain_default(chain);
+
+                tracing::info!(target: "reth::cli", "Opening in-memory database");
+                let db = Arc::new(MemoryDatabase::new());
+                Ok(DatabaseInstance::InMemory { db, data_dir })
+            }
         }
     }
 }
@@ -90,6 +103,13 @@ pub enum DatabaseInstance {
         /// The data dir
         data_dir: ChainPath<DataDirPath>,
     },
+    /// The in-memory database
+    InMemory {
+        /// The database
+        db: Arc<MemoryDatabase>,
+        /// The data dir
+        data_dir: ChainPath<DataDirPath>,
+    },
 }
 
 impl DatabaseInstance {
@@ -98,6 +118,7 @@ impl DatabaseInstance {
         match self {
             Self::Test { data_dir, .. } => data_dir,
             Self::Real { data_dir, .. } => data_dir,
+            Self::InMemory { data_dir, .. } => data_dir,
         }
     }
 }
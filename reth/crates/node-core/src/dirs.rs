@@ -316,6 +316,13 @@ impl<D> ChainPath<D> {
         self.0.join("txpool-transactions-backup.rlp").into()
     }
 
+    /// Returns the path to the journal file of all transactions of the transaction pool
+    ///
+    /// `<DIR>/<CHAIN_ID>/txpool-journal`
+    pub fn txpool_journal_path(&self) -> PathBuf {
+        self.0.join("txpool-journal").into()
+    }
+
     /// Returns the path to the config file for this chain.
     ///
     /// `<DIR>/<CHAIN_ID>/reth.toml`
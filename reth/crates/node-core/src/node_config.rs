@@ -45,22 +45,22 @@ use reth_primitives::{
 };
 use reth_provider::{
     providers::BlockchainProvider, BlockHashReader, BlockReader,
-    BlockchainTreePendingStateProvider, CanonStateSubscriptions, HeaderProvider, HeaderSyncMode,
-    ProviderFactory, StageCheckpointReader,
+    BlockchainTreePendingStateProvider, CanonStateSubscriptions, HashedStateRangeReader,
+    HeaderProvider, HeaderSyncMode, ProviderFactory, StageCheckpointReader,
 };
-use reth_revm::EvmProcessorFactory;
+use reth_revm::{prefetch::PrefetchProviders, EvmProcessorFactory};
 use reth_stages::{
     prelude::*,
     stages::{
         AccountHashingStage, ExecutionStage, ExecutionStageThresholds, IndexAccountHistoryStage,
-        IndexStorageHistoryStage, MerkleStage, SenderRecoveryStage, StorageHashingStage,
-        TotalDifficultyStage, TransactionLookupStage,
+        IndexAddressTransactionsStage, IndexLogsStage, IndexStorageHistoryStage, MerkleStage,
+        SenderRecoveryStage, StorageHashingStage, TotalDifficultyStage, TransactionLookupStage,
     },
     MetricEvent,
 };
 use reth_tasks::TaskExecutor;
 use reth_transaction_pool::{
-    blobstore::DiskFileBlobStore, EthTransactionPool, TransactionPool,
+    blobstore::DiskFileBlobStore, ConfiguredEthTransactionPool, TransactionPool,
     TransactionValidationTaskExecutor,
 };
 use revm_inspectors::stack::Hook;
@@ -449,7 +449,7 @@ impl NodeConfig {
         head: Head,
         executor: &TaskExecutor,
         data_dir: &ChainPath<DataDirPath>,
-    ) -> eyre::Result<EthTransactionPool<BlockchainProvider<DB, Tree>, DiskFileBlobStore>>
+    ) -> eyre::Result<ConfiguredEthTransactionPool<BlockchainProvider<DB, Tree>, DiskFileBlobStore>>
     where
         DB: Database + Unpin + Clone + 'static,
         Tree: BlockchainTreeEngine
@@ -459,15 +459,23 @@ impl NodeConfig {
             + 'static,
     {
         let blob_store = DiskFileBlobStore::open(data_dir.blobstore_path(), Default::default())?;
-        let validator = TransactionValidationTaskExecutor::eth_builder(Arc::clone(&self.chain))
+        let mut validator = TransactionValidationTaskExecutor::eth_builder(Arc::clone(&self.chain))
             .with_head_timestamp(head.timestamp)
             .kzg_settings(self.kzg_settings()?)
             // use an additional validation task so we can validate transactions in parallel
-            .with_additional_tasks(1)
-            .build_with_tasks(blockchain_db.clone(), executor.clone(), blob_store.clone());
-
-        let transaction_pool =
-            reth_transaction_pool::Pool::eth_pool(validator, blob_store, self.txpool.pool_config());
+            .with_additional_tasks(1);
+        if let Some(admission_policies) = &self.txpool.admission_policies {
+            validator = validator.with_admission_policy_config(admission_policies);
+        }
+        let validator =
+            validator.build_with_tasks(blockchain_db.clone(), executor.clone(), blob_store.clone());
+
+        let transaction_pool = reth_transaction_pool::Pool::eth_pool_with_ordering(
+            validator,
+            self.txpool.ordering,
+            blob_store,
+            self.txpool.pool_config(),
+        );
         info!(target: "reth::cli", "Transaction pool initialized");
         let transactions_path = data_dir.txpool_transactions_path();
 
@@ -476,19 +484,48 @@ impl NodeConfig {
             let pool = transaction_pool.clone();
             let chain_events = blockchain_db.canonical_state_stream();
             let client = blockchain_db.clone();
-            let transactions_backup_config =
-                reth_transaction_pool::maintain::LocalTransactionBackupConfig::with_local_txs_backup(transactions_path);
-
-            executor.spawn_critical_with_graceful_shutdown_signal(
-                "local transactions backup task",
-                |shutdown| {
-                    reth_transaction_pool::maintain::backup_local_transactions_task(
-                        shutdown,
-                        pool.clone(),
-                        transactions_backup_config,
-                    )
-                },
-            );
+            // the maintenance task deletes unreferenced blobs once the persisted transactions
+            // were reinserted
+            let transactions_reloaded =
+                reth_transaction_pool::maintain::TransactionsReloaded::default();
+
+            if self.txpool.journal {
+                // the journal includes local transactions, so they don't need a separate backup,
+                // but a backup left by a previous run without the journal is still reinserted
+                let journal_config = reth_transaction_pool::maintain::PoolJournalConfig::new(
+                    data_dir.txpool_journal_path(),
+                )
+                .with_interval(self.txpool.journal_interval)
+                .with_local_txs_backup(transactions_path)
+                .with_transactions_reloaded(transactions_reloaded.clone());
+
+                executor.spawn_critical_with_graceful_shutdown_signal(
+                    "transaction pool journal task",
+                    |shutdown| {
+                        reth_transaction_pool::maintain::journal_pool_transactions_task(
+                            shutdown,
+                            pool.clone(),
+                            executor.clone(),
+                            journal_config,
+                        )
+                    },
+                );
+            } else {
+                let transactions_backup_config =
+                    reth_transaction_pool::maintain::LocalTransactionBackupConfig::with_local_txs_backup(transactions_path)
+                        .with_transactions_reloaded(transactions_reloaded.clone());
+
+                executor.spawn_critical_with_graceful_shutdown_signal(
+                    "local transactions backup task",
+                    |shutdown| {
+                        reth_transaction_pool::maintain::backup_local_transactions_task(
+                            shutdown,
+                            pool.clone(),
+                            transactions_backup_config,
+                        )
+                    },
+                );
+            }
 
             // spawn the maintenance task
             executor.spawn_critical(
@@ -498,7 +535,10 @@ impl NodeConfig {
                     pool,
                     chain_events,
                     executor.clone(),
-                    Default::default(),
+                    reth_transaction_pool::maintain::MaintainPoolConfig {
+                        transactions_reloaded: Some(transactions_reloaded),
+                        ..Default::default()
+                    },
                 ),
             );
             debug!(target: "reth::cli", "Spawned txpool maintenance task");
@@ -616,7 +656,7 @@ impl NodeConfig {
         data_dir: &ChainPath<DataDirPath>,
     ) -> NetworkHandle
     where
-        C: BlockReader + HeaderProvider + Clone + Unpin + 'static,
+        C: BlockReader + HeaderProvider + HashedStateRangeReader + Clone + Unpin + 'static,
         Pool: TransactionPool + Unpin + 'static,
     {
         let (handle, network, txpool, eth) =
@@ -800,7 +840,14 @@ impl NodeConfig {
             },
         };
 
-        let factory = factory.with_stack_config(stack_config);
+        let mut factory = factory.with_stack_config(stack_config);
+        if let Some(workers) = stage_config.execution.prefetch_workers {
+            // The execution stage executes on the latest state, and only writes it at the end of
+            // every batch.
+            let provider_factory = provider_factory.clone();
+            factory = factory
+                .with_prefetch(workers, PrefetchProviders::new(move || provider_factory.latest()));
+        }
 
         let prune_modes = prune_config.map(|prune| prune.segments).unwrap_or_default();
 
@@ -863,7 +910,24 @@ impl NodeConfig {
                 .set(IndexStorageHistoryStage::new(
                     stage_config.index_storage_history.commit_threshold,
                     prune_modes.storage_history,
-                )),
+                ))
+                .add_before(
+                    IndexAddressTransactionsStage::new(
+                        stage_config.index_address_transactions.commit_threshold,
+                    ),
+                    StageId::Finish,
+                )
+                .disable_if(StageId::IndexAddressTransactions, || {
+                    !stage_config.index_address_transactions.enabled
+                })
+                .add_before(
+                    IndexLogsStage::new(
+                        stage_config.index_logs.commit_threshold,
+                        prune_modes.log_index,
+                    ),
+                    StageId::Finish,
+                )
+                .disable_if(StageId::IndexLogs, || !stage_config.index_logs.enabled),
             )
             .build(provider_factory);
 
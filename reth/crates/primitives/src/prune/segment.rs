@@ -23,15 +23,19 @@ pub enum PruneSegment {
     Headers,
     /// Prune segment responsible for the `Transactions` table.
     Transactions,
+    /// Prune segment responsible for the `LogAddressIndex` and `LogTopicIndex` tables.
+    LogIndex,
 }
 
 impl PruneSegment {
     /// Returns minimum number of blocks to left in the database for this segment.
     pub fn min_blocks(&self) -> u64 {
         match self {
-            Self::SenderRecovery | Self::TransactionLookup | Self::Headers | Self::Transactions => {
-                0
-            }
+            Self::SenderRecovery |
+            Self::TransactionLookup |
+            Self::Headers |
+            Self::Transactions |
+            Self::LogIndex => 0,
             Self::Receipts | Self::ContractLogs | Self::AccountHistory | Self::StorageHistory => {
                 MINIMUM_PRUNING_DISTANCE
             }
@@ -37,6 +37,9 @@ pub struct PruneModes {
         deserialize_with = "deserialize_opt_prune_mode_with_min_blocks::<MINIMUM_PRUNING_DISTANCE, _>"
     )]
     pub storage_history: Option<PruneMode>,
+    /// Log Index pruning configuration.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub log_index: Option<PruneMode>,
     /// Receipts pruning configuration by retaining only those receipts that contain logs emitted
     /// by the specified addresses, discarding others. This setting is overridden by `receipts`.
     ///
@@ -59,6 +62,7 @@ impl PruneModes {
             receipts: Some(PruneMode::Full),
             account_history: Some(PruneMode::Full),
             storage_history: Some(PruneMode::Full),
+            log_index: Some(PruneMode::Full),
             receipts_log_filter: Default::default(),
         }
     }
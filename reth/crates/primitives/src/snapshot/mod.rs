@@ -24,6 +24,21 @@ pub struct HighestSnapshots {
     /// Highest snapshotted block of transactions, inclusive.
     /// If [`None`], no snapshot is available.
     pub transactions: Option<BlockNumber>,
+    /// Highest snapshotted block of transaction senders, inclusive.
+    /// If [`None`], no snapshot is available.
+    pub transaction_senders: Option<BlockNumber>,
+    /// Highest snapshotted block of account changesets, inclusive.
+    /// If [`None`], no snapshot is available.
+    pub account_changesets: Option<BlockNumber>,
+    /// Highest snapshotted block of storage changesets, inclusive.
+    /// If [`None`], no snapshot is available.
+    pub storage_changesets: Option<BlockNumber>,
+    /// Highest snapshotted block of account history, inclusive.
+    /// If [`None`], no snapshot is available.
+    pub account_history: Option<BlockNumber>,
+    /// Highest snapshotted block of storage history, inclusive.
+    /// If [`None`], no snapshot is available.
+    pub storage_history: Option<BlockNumber>,
 }
 
 impl HighestSnapshots {
@@ -33,6 +48,11 @@ impl HighestSnapshots {
             SnapshotSegment::Headers => self.headers,
             SnapshotSegment::Transactions => self.transactions,
             SnapshotSegment::Receipts => self.receipts,
+            SnapshotSegment::TransactionSenders => self.transaction_senders,
+            SnapshotSegment::AccountChangesets => self.account_changesets,
+            SnapshotSegment::StorageChangesets => self.storage_changesets,
+            SnapshotSegment::AccountHistory => self.account_history,
+            SnapshotSegment::StorageHistory => self.storage_history,
         }
     }
 
@@ -42,6 +62,11 @@ impl HighestSnapshots {
             SnapshotSegment::Headers => &mut self.headers,
             SnapshotSegment::Transactions => &mut self.transactions,
             SnapshotSegment::Receipts => &mut self.receipts,
+            SnapshotSegment::TransactionSenders => &mut self.transaction_senders,
+            SnapshotSegment::AccountChangesets => &mut self.account_changesets,
+            SnapshotSegment::StorageChangesets => &mut self.storage_changesets,
+            SnapshotSegment::AccountHistory => &mut self.account_history,
+            SnapshotSegment::StorageHistory => &mut self.storage_history,
         }
     }
 }
@@ -35,6 +35,21 @@ pub enum SnapshotSegment {
     #[strum(serialize = "receipts")]
     /// Snapshot segment responsible for the `Receipts` table.
     Receipts,
+    #[strum(serialize = "transaction-senders")]
+    /// Snapshot segment responsible for the `TxSenders` table.
+    TransactionSenders,
+    #[strum(serialize = "account-changesets")]
+    /// Snapshot segment responsible for the `AccountChangeSet` table.
+    AccountChangesets,
+    #[strum(serialize = "storage-changesets")]
+    /// Snapshot segment responsible for the `StorageChangeSet` table.
+    StorageChangesets,
+    #[strum(serialize = "account-history")]
+    /// Snapshot segment responsible for the `AccountHistory` table.
+    AccountHistory,
+    #[strum(serialize = "storage-history")]
+    /// Snapshot segment responsible for the `StorageHistory` table.
+    StorageHistory,
 }
 
 impl SnapshotSegment {
@@ -52,9 +67,29 @@ impl SnapshotSegment {
             SnapshotSegment::Headers => default_config,
             SnapshotSegment::Transactions => default_config,
             SnapshotSegment::Receipts => default_config,
+            // Senders are only queried by transaction number.
+            SnapshotSegment::TransactionSenders => {
+                SegmentConfig { filters: Filters::WithoutFilters, compression: Compression::Lz4 }
+            }
+            SnapshotSegment::AccountChangesets |
+            SnapshotSegment::StorageChangesets |
+            SnapshotSegment::AccountHistory |
+            SnapshotSegment::StorageHistory => default_config,
         }
     }
 
+    /// Returns `true` if the rows of the segment can only be queried by key, and not by block or
+    /// transaction number. These segments always require filters and a perfect hashing function.
+    pub const fn is_keyed(&self) -> bool {
+        matches!(
+            self,
+            SnapshotSegment::AccountChangesets |
+                SnapshotSegment::StorageChangesets |
+                SnapshotSegment::AccountHistory |
+                SnapshotSegment::StorageHistory
+        )
+    }
+
     /// Returns the default file name for the provided segment and range.
     pub fn filename(
         &self,
@@ -182,8 +217,14 @@ impl SegmentHeader {
     /// Returns the row offset which depends on whether the segment is block or transaction based.
     pub fn start(&self) -> u64 {
         match self.segment {
-            SnapshotSegment::Headers => self.block_start(),
-            SnapshotSegment::Transactions | SnapshotSegment::Receipts => self.tx_start(),
+            SnapshotSegment::Headers |
+            SnapshotSegment::AccountChangesets |
+            SnapshotSegment::StorageChangesets |
+            SnapshotSegment::AccountHistory |
+            SnapshotSegment::StorageHistory => self.block_start(),
+            SnapshotSegment::Transactions |
+            SnapshotSegment::Receipts |
+            SnapshotSegment::TransactionSenders => self.tx_start(),
         }
     }
 }
@@ -219,6 +260,13 @@ mod tests {
                 "snapshot_transactions_1123233_11223233_1123233_2123233",
                 None,
             ),
+            (
+                SnapshotSegment::StorageChangesets,
+                500_000..=999_999,
+                1_000..=2_000,
+                "snapshot_storage-changesets_500000_999999_1000_2000",
+                None,
+            ),
             (
                 SnapshotSegment::Headers,
                 2..=30,
@@ -27,6 +27,14 @@ pub enum StageId {
     IndexStorageHistory,
     /// Index account history stage in the process.
     IndexAccountHistory,
+    /// Index address transactions stage in the process.
+    ///
+    /// This stage is optional and therefore not part of [`StageId::ALL`].
+    IndexAddressTransactions,
+    /// Index logs stage in the process.
+    ///
+    /// This stage is optional and therefore not part of [`StageId::ALL`].
+    IndexLogs,
     /// Finish stage in the process.
     Finish,
     /// Other custom stage with a provided string identifier.
@@ -51,6 +59,9 @@ impl StageId {
         StageId::Finish,
     ];
 
+    /// Stages that are not part of the default pipeline and only run if enabled.
+    pub const OPTIONAL: [StageId; 2] = [StageId::IndexAddressTransactions, StageId::IndexLogs];
+
     /// Return stage id formatted as string.
     pub fn as_str(&self) -> &str {
         match self {
@@ -66,6 +77,8 @@ impl StageId {
             StageId::TransactionLookup => "TransactionLookup",
             StageId::IndexAccountHistory => "IndexAccountHistory",
             StageId::IndexStorageHistory => "IndexStorageHistory",
+            StageId::IndexAddressTransactions => "IndexAddressTransactions",
+            StageId::IndexLogs => "IndexLogs",
             StageId::Finish => "Finish",
             StageId::Other(s) => s,
         }
@@ -105,6 +118,8 @@ mod tests {
         assert_eq!(StageId::MerkleExecute.to_string(), "MerkleExecute");
         assert_eq!(StageId::IndexAccountHistory.to_string(), "IndexAccountHistory");
         assert_eq!(StageId::IndexStorageHistory.to_string(), "IndexStorageHistory");
+        assert_eq!(StageId::IndexAddressTransactions.to_string(), "IndexAddressTransactions");
+        assert_eq!(StageId::IndexLogs.to_string(), "IndexLogs");
         assert_eq!(StageId::TransactionLookup.to_string(), "TransactionLookup");
         assert_eq!(StageId::Finish.to_string(), "Finish");
 
@@ -6,14 +6,17 @@ use crate::{
     Metrics, PrunerError, PrunerEvent,
 };
 use reth_db::database::Database;
-use reth_primitives::{BlockNumber, PruneMode, PruneProgress, PruneSegment};
-use reth_provider::{ProviderFactory, PruneCheckpointReader};
+use reth_primitives::{BlockNumber, PruneMode, PruneProgress};
+use reth_provider::ProviderFactory;
 use reth_snapshot::HighestSnapshotsTracker;
 use reth_tokio_util::EventListeners;
 use std::{collections::BTreeMap, sync::Arc, time::Instant};
 use tokio_stream::wrappers::UnboundedReceiverStream;
 use tracing::{debug, trace};
 
+/// Creates the segment that removes the data of the snapshotted blocks from the database.
+type SnapshottedSegment<DB> = fn(PruneMode) -> Box<dyn Segment<DB>>;
+
 /// Result of [Pruner::run] execution.
 pub type PrunerResult = Result<PruneProgress, PrunerError>;
 
@@ -88,7 +91,8 @@ impl<DB: Database> Pruner<DB> {
         let mut done = true;
         let mut stats = BTreeMap::new();
 
-        // TODO(alexey): prune snapshotted segments of data (headers, transactions)
+        // The data of the snapshotted blocks is removed from the database after the configured
+        // segments are pruned.
         let highest_snapshots = *self.highest_snapshots_tracker.borrow();
 
         // Multiply `self.delete_limit` (number of rows to delete per block) by number of blocks
@@ -128,7 +132,7 @@ impl<DB: Database> Pruner<DB> {
                 );
 
                 let segment_start = Instant::now();
-                let previous_checkpoint = provider.get_prune_checkpoint(segment.segment())?;
+                let previous_checkpoint = segment.checkpoint(&provider)?;
                 let output = segment
                     .prune(&provider, PruneInput { previous_checkpoint, to_block, delete_limit })?;
                 if let Some(checkpoint) = output.checkpoint {
@@ -152,51 +156,43 @@ impl<DB: Database> Pruner<DB> {
         }
 
         if let Some(snapshots) = highest_snapshots {
-            if let (Some(to_block), true) = (snapshots.headers, delete_limit > 0) {
-                let prune_mode = PruneMode::Before(to_block + 1);
-                trace!(
-                    target: "pruner",
-                    prune_segment = ?PruneSegment::Headers,
-                    %to_block,
-                    ?prune_mode,
-                    "Got target block to prune"
-                );
-
-                let segment_start = Instant::now();
-                let segment = segments::Headers::new(prune_mode);
-                let previous_checkpoint = provider.get_prune_checkpoint(PruneSegment::Headers)?;
-                let output = segment
-                    .prune(&provider, PruneInput { previous_checkpoint, to_block, delete_limit })?;
-                if let Some(checkpoint) = output.checkpoint {
-                    segment
-                        .save_checkpoint(&provider, checkpoint.as_prune_checkpoint(prune_mode))?;
+            // The changesets are only removed once both their snapshots and the history snapshots
+            // built from them cover the block.
+            let history = |changesets: Option<BlockNumber>, history: Option<BlockNumber>| {
+                changesets.zip(history).map(|(changesets, history)| changesets.min(history))
+            };
+            let snapshotted_segments: [(Option<BlockNumber>, SnapshottedSegment<DB>); 5] = [
+                (snapshots.headers, |mode| Box::new(segments::Headers::new(mode))),
+                (snapshots.transactions, |mode| Box::new(segments::Transactions::new(mode))),
+                (snapshots.transaction_senders, |mode| {
+                    Box::new(segments::SenderRecovery::new(mode))
+                }),
+                (history(snapshots.account_changesets, snapshots.account_history), |mode| {
+                    Box::new(segments::SnapshotAccountHistory::new(mode))
+                }),
+                (history(snapshots.storage_changesets, snapshots.storage_history), |mode| {
+                    Box::new(segments::SnapshotStorageHistory::new(mode))
+                }),
+            ];
+
+            for (to_block, segment) in snapshotted_segments {
+                let Some(to_block) = to_block else { continue };
+                if delete_limit == 0 {
+                    break
                 }
-                self.metrics
-                    .get_prune_segment_metrics(PruneSegment::Headers)
-                    .duration_seconds
-                    .record(segment_start.elapsed());
 
-                done = done && output.done;
-                delete_limit = delete_limit.saturating_sub(output.pruned);
-                stats.insert(
-                    PruneSegment::Headers,
-                    (PruneProgress::from_done(output.done), output.pruned),
-                );
-            }
-
-            if let (Some(to_block), true) = (snapshots.transactions, delete_limit > 0) {
                 let prune_mode = PruneMode::Before(to_block + 1);
+                let segment = segment(prune_mode);
                 trace!(
                     target: "pruner",
-                    prune_segment = ?PruneSegment::Transactions,
+                    prune_segment = ?segment.segment(),
                     %to_block,
                     ?prune_mode,
                     "Got target block to prune"
                 );
 
                 let segment_start = Instant::now();
-                let segment = segments::Transactions::new(prune_mode);
-                let previous_checkpoint = provider.get_prune_checkpoint(PruneSegment::Headers)?;
+                let previous_checkpoint = segment.checkpoint(&provider)?;
                 let output = segment
                     .prune(&provider, PruneInput { previous_checkpoint, to_block, delete_limit })?;
                 if let Some(checkpoint) = output.checkpoint {
@@ -204,14 +200,14 @@ impl<DB: Database> Pruner<DB> {
                         .save_checkpoint(&provider, checkpoint.as_prune_checkpoint(prune_mode))?;
                 }
                 self.metrics
-                    .get_prune_segment_metrics(PruneSegment::Transactions)
+                    .get_prune_segment_metrics(segment.segment())
                     .duration_seconds
                     .record(segment_start.elapsed());
 
                 done = done && output.done;
                 delete_limit = delete_limit.saturating_sub(output.pruned);
                 stats.insert(
-                    PruneSegment::Transactions,
+                    segment.segment(),
                     (PruneProgress::from_done(output.done), output.pruned),
                 );
             }
@@ -264,9 +260,17 @@ impl<DB: Database> Pruner<DB> {
 #[cfg(test)]
 mod tests {
     use crate::Pruner;
-    use reth_db::test_utils::create_test_rw_db;
-    use reth_primitives::MAINNET;
-    use reth_provider::ProviderFactory;
+    use reth_db::{tables, test_utils::create_test_rw_db};
+    use reth_interfaces::test_utils::{
+        generators,
+        generators::{
+            random_block_range, random_changeset_range, random_eoa_account_range, random_receipt,
+        },
+    };
+    use reth_primitives::{PruneProgress, PruneSegment, B256, MAINNET};
+    use reth_provider::{AccountExtReader, ProviderFactory, PruneCheckpointReader, StorageReader};
+    use reth_snapshot::Snapshotter;
+    use reth_stages::test_utils::TestStageDB;
     use tokio::sync::watch;
 
     #[test]
@@ -289,4 +293,108 @@ mod tests {
         let third_block_number = second_block_number;
         assert!(!pruner.is_pruning_needed(third_block_number));
     }
+
+    #[test]
+    fn prune_snapshotted_data() {
+        let db = TestStageDB::default();
+        let snapshots_dir = tempfile::TempDir::new().unwrap();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(&mut rng, 0..=3, B256::ZERO, 2..3);
+        db.insert_headers_with_td(blocks.iter().map(|block| &block.header))
+            .expect("insert headers");
+        db.insert_blocks(blocks.iter(), None).expect("insert blocks");
+
+        let transactions = blocks.iter().flat_map(|block| &block.body).enumerate();
+        let (receipts, senders): (Vec<_>, Vec<_>) = transactions
+            .map(|(tx_number, transaction)| {
+                (
+                    (tx_number as u64, random_receipt(&mut rng, transaction, Some(0))),
+                    (tx_number as u64, transaction.recover_signer().unwrap()),
+                )
+            })
+            .unzip();
+        db.insert_receipts(receipts).expect("insert receipts");
+        db.insert_transaction_senders(senders).expect("insert senders");
+
+        let accounts = random_eoa_account_range(&mut rng, 0..2)
+            .into_iter()
+            .map(|(address, account)| (address, (account, Vec::new())));
+        let (changesets, _) = random_changeset_range(&mut rng, blocks.iter(), accounts, 1..2, 0..4);
+        db.insert_changesets(changesets.clone(), None).expect("insert changesets");
+        db.insert_history(changesets, None).expect("insert history");
+
+        let provider = db.factory.provider().unwrap();
+        let changed_accounts = provider.changed_accounts_and_blocks_with_range(0..=3).unwrap();
+        let changed_storages = provider.changed_storages_and_blocks_with_range(0..=3).unwrap();
+        drop(provider);
+
+        let mut snapshotter =
+            Snapshotter::new(db.factory.clone(), snapshots_dir.path(), 2).unwrap();
+        let targets = snapshotter.get_snapshot_targets(1).expect("get snapshot targets");
+        snapshotter.run(targets).expect("run snapshotter");
+
+        // Snapshotting leaves the database untouched.
+        assert_eq!(db.table::<tables::TxSenders>().unwrap().len(), 8);
+
+        // The snapshotted data is removed over multiple runs, each within the delete limit.
+        let mut pruner = Pruner::new(
+            db.factory.clone(),
+            vec![],
+            1,
+            3,
+            1,
+            snapshotter.highest_snapshot_receiver(),
+        );
+        let mut tip_block_number = 3;
+        let mut runs = 0;
+        while pruner.run(tip_block_number).unwrap() != PruneProgress::Finished {
+            tip_block_number += 1;
+            runs += 1;
+            assert!(runs < 100);
+        }
+        assert!(runs > 1);
+
+        // Only the data of the blocks after the snapshots is left in the database.
+        let senders = db.table::<tables::TxSenders>().unwrap();
+        assert!(!senders.is_empty());
+        assert!(senders.iter().all(|(tx_number, _)| *tx_number > 3));
+        let account_changesets = db.table::<tables::AccountChangeSet>().unwrap();
+        assert!(!account_changesets.is_empty());
+        assert!(account_changesets.iter().all(|(block_number, _)| *block_number > 1));
+        let storage_changesets = db.table::<tables::StorageChangeSet>().unwrap();
+        assert!(!storage_changesets.is_empty());
+        assert!(storage_changesets.iter().all(|(key, _)| key.block_number() > 1));
+        for blocks in db
+            .table::<tables::AccountHistory>()
+            .unwrap()
+            .into_iter()
+            .map(|(_, blocks)| blocks)
+            .chain(
+                db.table::<tables::StorageHistory>().unwrap().into_iter().map(|(_, blocks)| blocks),
+            )
+        {
+            assert!(blocks.iter(0).all(|block| block > 1));
+        }
+
+        // Readers that know about the snapshots still see the changesets of all blocks, and the
+        // state of the snapshotted blocks is not reported as pruned.
+        let factory = db
+            .factory
+            .clone()
+            .with_snapshots(snapshots_dir.path().into(), snapshotter.highest_snapshot_receiver())
+            .unwrap();
+        let provider = factory.provider().unwrap();
+        assert_eq!(
+            provider.changed_accounts_and_blocks_with_range(0..=3).unwrap(),
+            changed_accounts
+        );
+        assert_eq!(
+            provider.changed_storages_and_blocks_with_range(0..=3).unwrap(),
+            changed_storages
+        );
+        for segment in [PruneSegment::AccountHistory, PruneSegment::StorageHistory] {
+            assert_eq!(provider.get_prune_checkpoint(segment).unwrap(), None);
+        }
+    }
 }
@@ -0,0 +1,233 @@
+use crate::{
+    segments::{PruneInput, PruneOutput, PruneOutputCheckpoint, Segment},
+    PrunerError,
+};
+use reth_db::{database::Database, models::ShardedKey, tables};
+use reth_primitives::{PruneMode, PruneSegment};
+use reth_provider::DatabaseProviderRW;
+use std::collections::BTreeSet;
+use tracing::{instrument, trace};
+
+/// Prunes the log index.
+///
+/// The shards to prune are found from the addresses and topics of the logs in the receipts of the
+/// pruned blocks, so the segment runs before the receipts are pruned.
+#[derive(Debug)]
+pub struct LogIndex {
+    mode: PruneMode,
+}
+
+impl LogIndex {
+    pub fn new(mode: PruneMode) -> Self {
+        Self { mode }
+    }
+}
+
+impl<DB: Database> Segment<DB> for LogIndex {
+    fn segment(&self) -> PruneSegment {
+        PruneSegment::LogIndex
+    }
+
+    fn mode(&self) -> Option<PruneMode> {
+        Some(self.mode)
+    }
+
+    #[instrument(level = "trace", target = "pruner", skip(self, provider), ret)]
+    fn prune(
+        &self,
+        provider: &DatabaseProviderRW<DB>,
+        input: PruneInput,
+    ) -> Result<PruneOutput, PrunerError> {
+        let range = match input.get_next_block_range() {
+            Some(range) => range,
+            None => {
+                trace!(target: "pruner", "No log index to prune");
+                return Ok(PruneOutput::done())
+            }
+        };
+        let range_start = *range.start();
+
+        // Collect the addresses and topics of the pruned blocks until there are at least as many
+        // keys as the delete limit, every key having at least one shard to prune.
+        let mut addresses = BTreeSet::new();
+        let mut topics = BTreeSet::new();
+        let mut last_block = range_start;
+        for block_number in range {
+            let (block_addresses, block_topics) =
+                provider.log_addresses_and_topics_with_range(block_number..=block_number)?;
+            addresses.extend(block_addresses.into_keys());
+            topics.extend(block_topics.into_keys());
+            last_block = block_number;
+
+            if addresses.len() + topics.len() >= input.delete_limit {
+                break
+            }
+        }
+
+        let address_keys = addresses.into_iter().map(|address| ShardedKey::new(address, 0));
+        let (processed, pruned_addresses, done_addresses) = provider
+            .prune_history_shards::<tables::LogAddressIndex, _>(
+                address_keys,
+                last_block,
+                input.delete_limit,
+                |a, b| a.key == b.key,
+            )?;
+        trace!(target: "pruner", %processed, pruned = %pruned_addresses, done = %done_addresses, "Pruned log index (addresses)");
+
+        let topic_keys = topics.into_iter().map(|topic| ShardedKey::new(topic, 0));
+        let (processed, pruned_topics, done_topics) = provider
+            .prune_history_shards::<tables::LogTopicIndex, _>(
+                topic_keys,
+                last_block,
+                input.delete_limit.saturating_sub(pruned_addresses),
+                |a, b| a.key == b.key,
+            )?;
+        trace!(target: "pruner", %processed, pruned = %pruned_topics, done = %done_topics, "Pruned log index (topics)");
+
+        // If not all keys were pruned up to the last block, keep the previous checkpoint. The keys
+        // that were already pruned have nothing left to prune on the next run.
+        let (done, last_pruned_block) = if done_addresses && done_topics {
+            (last_block == input.to_block, Some(last_block))
+        } else {
+            (false, range_start.checked_sub(1))
+        };
+
+        Ok(PruneOutput {
+            done,
+            pruned: pruned_addresses + pruned_topics,
+            checkpoint: Some(PruneOutputCheckpoint {
+                block_number: last_pruned_block,
+                tx_number: None,
+            }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::segments::{LogIndex, PruneInput, PruneOutput, Segment};
+    use assert_matches::assert_matches;
+    use reth_db::{models::ShardedKey, tables, BlockNumberList};
+    use reth_interfaces::test_utils::{
+        generators,
+        generators::{random_block_range, random_receipt},
+    };
+    use reth_primitives::{
+        Address, BlockNumber, Log, PruneCheckpoint, PruneMode, PruneSegment, B256,
+    };
+    use reth_provider::{HistoryWriter, PruneCheckpointReader};
+    use reth_stages::test_utils::TestStageDB;
+
+    /// Returns the shards left after pruning all blocks up to and including `to_block`.
+    fn expected_shards<K: Clone>(
+        shards: &[(ShardedKey<K>, BlockNumberList)],
+        to_block: BlockNumber,
+    ) -> Vec<(ShardedKey<K>, BlockNumberList)> {
+        shards
+            .iter()
+            .filter(|(key, _)| key.highest_block_number > to_block)
+            .map(|(key, blocks)| {
+                let new_blocks = blocks
+                    .iter(0)
+                    .skip_while(|block| *block <= to_block as usize)
+                    .collect::<Vec<_>>();
+                (key.clone(), BlockNumberList::new_pre_sorted(new_blocks))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn prune() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        // Every block has logs of both addresses and topics, so each of them is spread over
+        // multiple shards.
+        let blocks = random_block_range(&mut rng, 1..=5000, B256::ZERO, 1..2);
+        db.insert_blocks(blocks.iter(), None).expect("insert blocks");
+
+        let mut receipts = Vec::new();
+        for block in &blocks {
+            for transaction in &block.body {
+                let mut receipt = random_receipt(&mut rng, transaction, Some(0));
+                receipt.success = true;
+                receipt.logs = (1..=2)
+                    .map(|i| Log {
+                        address: Address::with_last_byte(i),
+                        topics: vec![B256::with_last_byte(i)],
+                        data: Default::default(),
+                    })
+                    .collect();
+                receipts.push((receipts.len() as u64, receipt));
+            }
+        }
+        db.insert_receipts(receipts).expect("insert receipts");
+
+        let provider = db.factory.provider_rw().unwrap();
+        let (addresses, topics) = provider.log_addresses_and_topics_with_range(0..=5000).unwrap();
+        provider.insert_log_index(addresses, topics).unwrap();
+        provider.commit().unwrap();
+
+        let original_address_shards = db.table::<tables::LogAddressIndex>().unwrap();
+        let original_topic_shards = db.table::<tables::LogTopicIndex>().unwrap();
+
+        let test_prune = |to_block: BlockNumber, delete_limit: usize| {
+            let prune_mode = PruneMode::Before(to_block);
+            let segment = LogIndex::new(prune_mode);
+
+            // Run the segment until it's done, every run staying within the delete limit.
+            loop {
+                let input = PruneInput {
+                    previous_checkpoint: db
+                        .factory
+                        .provider()
+                        .unwrap()
+                        .get_prune_checkpoint(PruneSegment::LogIndex)
+                        .unwrap(),
+                    to_block,
+                    delete_limit,
+                };
+
+                let provider = db.factory.provider_rw().unwrap();
+                let result = segment.prune(&provider, input).unwrap();
+                assert_matches!(result, PruneOutput { checkpoint: Some(_), .. });
+                assert!(result.pruned <= delete_limit);
+                segment
+                    .save_checkpoint(
+                        &provider,
+                        result.checkpoint.unwrap().as_prune_checkpoint(prune_mode),
+                    )
+                    .unwrap();
+                provider.commit().expect("commit");
+
+                if result.done {
+                    break
+                }
+            }
+
+            assert_eq!(
+                db.table::<tables::LogAddressIndex>().unwrap(),
+                expected_shards(&original_address_shards, to_block)
+            );
+            assert_eq!(
+                db.table::<tables::LogTopicIndex>().unwrap(),
+                expected_shards(&original_topic_shards, to_block)
+            );
+
+            assert_eq!(
+                db.factory
+                    .provider()
+                    .unwrap()
+                    .get_prune_checkpoint(PruneSegment::LogIndex)
+                    .unwrap(),
+                Some(PruneCheckpoint { block_number: Some(to_block), tx_number: None, prune_mode })
+            );
+        };
+
+        test_prune(1998, 2000);
+        test_prune(3000, 2000);
+        // Every block has more keys than the limit, so the blocks are pruned over multiple runs.
+        test_prune(3100, 3);
+        test_prune(4500, 2000);
+    }
+}
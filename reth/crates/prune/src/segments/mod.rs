@@ -1,20 +1,24 @@
 mod account_history;
 mod headers;
 mod history;
+mod log_index;
 mod receipts;
 mod receipts_by_logs;
 mod sender_recovery;
 mod set;
+mod snapshot_history;
 mod storage_history;
 mod transaction_lookup;
 mod transactions;
 
 pub use account_history::AccountHistory;
 pub use headers::Headers;
+pub use log_index::LogIndex;
 pub use receipts::Receipts;
 pub use receipts_by_logs::ReceiptsByLogs;
 pub use sender_recovery::SenderRecovery;
 pub use set::SegmentSet;
+pub use snapshot_history::{SnapshotAccountHistory, SnapshotStorageHistory};
 use std::fmt::Debug;
 pub use storage_history::StorageHistory;
 pub use transaction_lookup::TransactionLookup;
@@ -24,7 +28,9 @@ use crate::PrunerError;
 use reth_db::database::Database;
 use reth_interfaces::{provider::ProviderResult, RethResult};
 use reth_primitives::{BlockNumber, PruneCheckpoint, PruneMode, PruneSegment, TxNumber};
-use reth_provider::{BlockReader, DatabaseProviderRW, PruneCheckpointWriter};
+use reth_provider::{
+    BlockReader, DatabaseProviderRW, PruneCheckpointReader, PruneCheckpointWriter,
+};
 use std::ops::RangeInclusive;
 use tracing::error;
 
@@ -49,6 +55,14 @@ pub trait Segment<DB: Database>: Debug + Send + Sync {
         input: PruneInput,
     ) -> Result<PruneOutput, PrunerError>;
 
+    /// Fetch the checkpoint for [Self::segment] from the database.
+    fn checkpoint(
+        &self,
+        provider: &DatabaseProviderRW<DB>,
+    ) -> ProviderResult<Option<PruneCheckpoint>> {
+        provider.get_prune_checkpoint(self.segment())
+    }
+
     /// Save checkpoint for [Self::segment] to the database.
     fn save_checkpoint(
         &self,
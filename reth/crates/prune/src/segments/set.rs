@@ -1,5 +1,5 @@
 use crate::segments::{
-    AccountHistory, Receipts, ReceiptsByLogs, Segment, SenderRecovery, StorageHistory,
+    AccountHistory, LogIndex, Receipts, ReceiptsByLogs, Segment, SenderRecovery, StorageHistory,
     TransactionLookup,
 };
 use reth_db::database::Database;
@@ -45,10 +45,13 @@ impl<DB: Database> SegmentSet<DB> {
             receipts,
             account_history,
             storage_history,
+            log_index,
             receipts_log_filter,
         } = prune_modes;
 
         SegmentSet::default()
+            // Log index, before the receipts its keys are read from
+            .segment_opt(log_index.map(LogIndex::new))
             // Receipts
             .segment_opt(receipts.map(Receipts::new))
             // Receipts by logs
@@ -0,0 +1,352 @@
+use crate::{
+    segments::{PruneInput, PruneOutput, PruneOutputCheckpoint, Segment},
+    PrunerError,
+};
+use reth_db::{
+    cursor::DbCursorRO,
+    database::Database,
+    models::{storage_sharded_key::StorageShardedKey, BlockNumberAddress, ShardedKey},
+    tables,
+    transaction::{DbTx, DbTxMut},
+};
+use reth_interfaces::provider::ProviderResult;
+use reth_primitives::{BlockNumber, PruneCheckpoint, PruneMode, PruneSegment, SnapshotSegment};
+use reth_provider::DatabaseProviderRW;
+use std::collections::BTreeSet;
+use tracing::{instrument, trace};
+
+/// Removes the account changesets and history of the snapshotted blocks from the database.
+///
+/// The shards to prune are found from the addresses of the changesets of the removed blocks, so
+/// the changesets are only removed once the history of their blocks is pruned. Unlike
+/// [AccountHistory](crate::segments::AccountHistory), the checkpoint is kept apart from the prune
+/// checkpoints, as the state of the removed blocks is still available from the snapshots.
+#[derive(Debug)]
+pub struct SnapshotAccountHistory {
+    mode: PruneMode,
+}
+
+impl SnapshotAccountHistory {
+    pub fn new(mode: PruneMode) -> Self {
+        Self { mode }
+    }
+}
+
+impl<DB: Database> Segment<DB> for SnapshotAccountHistory {
+    fn segment(&self) -> PruneSegment {
+        PruneSegment::AccountHistory
+    }
+
+    fn mode(&self) -> Option<PruneMode> {
+        Some(self.mode)
+    }
+
+    #[instrument(level = "trace", target = "pruner", skip(self, provider), ret)]
+    fn prune(
+        &self,
+        provider: &DatabaseProviderRW<DB>,
+        input: PruneInput,
+    ) -> Result<PruneOutput, PrunerError> {
+        let range = match input.get_next_block_range() {
+            Some(range) => range,
+            None => {
+                trace!(target: "pruner", "No snapshotted account history to remove");
+                return Ok(PruneOutput::done())
+            }
+        };
+        let range_start = *range.start();
+
+        // Collect the addresses of whole blocks, until their changesets reach half of the limit.
+        let mut addresses = BTreeSet::new();
+        let mut changesets = 0;
+        let mut last_block = input.to_block;
+        let mut current_block = None;
+        let mut cursor = provider.tx_ref().cursor_read::<tables::AccountChangeSet>()?;
+        for entry in cursor.walk_range(range)? {
+            let (block_number, changeset) = entry?;
+            if current_block != Some(block_number) {
+                if current_block.is_some() && changesets >= input.delete_limit / 2 {
+                    last_block = block_number - 1;
+                    break
+                }
+                current_block = Some(block_number);
+            }
+            addresses.insert(changeset.address);
+            changesets += 1;
+        }
+
+        let keys = addresses.into_iter().map(|address| ShardedKey::new(address, 0));
+        let (processed, pruned_indices, done_indices) = provider
+            .prune_history_shards::<tables::AccountHistory, _>(
+                keys,
+                last_block,
+                input.delete_limit - input.delete_limit / 2,
+                |a, b| a.key == b.key,
+            )?;
+        trace!(target: "pruner", %processed, pruned = %pruned_indices, done = %done_indices, "Removed snapshotted account history (history)");
+
+        // If not all keys were pruned up to the last block, keep the changesets and the previous
+        // checkpoint. The keys that were already pruned have nothing left to prune on the next run.
+        if !done_indices {
+            return Ok(PruneOutput {
+                done: false,
+                pruned: pruned_indices,
+                checkpoint: Some(PruneOutputCheckpoint {
+                    block_number: range_start.checked_sub(1),
+                    tx_number: None,
+                }),
+            })
+        }
+
+        let (pruned_changesets, _) = provider.prune_table_with_range::<tables::AccountChangeSet>(
+            range_start..=last_block,
+            changesets,
+            |_| false,
+            |_| {},
+        )?;
+        trace!(target: "pruner", pruned = %pruned_changesets, "Removed snapshotted account history (changesets)");
+
+        Ok(PruneOutput {
+            done: last_block == input.to_block,
+            pruned: pruned_indices + pruned_changesets,
+            checkpoint: Some(PruneOutputCheckpoint {
+                block_number: Some(last_block),
+                tx_number: None,
+            }),
+        })
+    }
+
+    fn checkpoint(
+        &self,
+        provider: &DatabaseProviderRW<DB>,
+    ) -> ProviderResult<Option<PruneCheckpoint>> {
+        snapshot_prune_checkpoint(provider, SnapshotSegment::AccountHistory)
+    }
+
+    fn save_checkpoint(
+        &self,
+        provider: &DatabaseProviderRW<DB>,
+        checkpoint: PruneCheckpoint,
+    ) -> ProviderResult<()> {
+        save_snapshot_prune_checkpoint(provider, SnapshotSegment::AccountHistory, checkpoint)
+    }
+}
+
+/// Removes the storage changesets and history of the snapshotted blocks from the database.
+///
+/// See [SnapshotAccountHistory] for the order in which the data is removed.
+#[derive(Debug)]
+pub struct SnapshotStorageHistory {
+    mode: PruneMode,
+}
+
+impl SnapshotStorageHistory {
+    pub fn new(mode: PruneMode) -> Self {
+        Self { mode }
+    }
+}
+
+impl<DB: Database> Segment<DB> for SnapshotStorageHistory {
+    fn segment(&self) -> PruneSegment {
+        PruneSegment::StorageHistory
+    }
+
+    fn mode(&self) -> Option<PruneMode> {
+        Some(self.mode)
+    }
+
+    #[instrument(level = "trace", target = "pruner", skip(self, provider), ret)]
+    fn prune(
+        &self,
+        provider: &DatabaseProviderRW<DB>,
+        input: PruneInput,
+    ) -> Result<PruneOutput, PrunerError> {
+        let range = match input.get_next_block_range() {
+            Some(range) => range,
+            None => {
+                trace!(target: "pruner", "No snapshotted storage history to remove");
+                return Ok(PruneOutput::done())
+            }
+        };
+        let range_start = *range.start();
+
+        // Collect the storage keys of whole blocks, until their changesets reach half of the limit.
+        let mut keys = BTreeSet::new();
+        let mut changesets = 0;
+        let mut last_block = input.to_block;
+        let mut current_block = None;
+        let mut cursor = provider.tx_ref().cursor_read::<tables::StorageChangeSet>()?;
+        for entry in cursor.walk_range(BlockNumberAddress::range(range))? {
+            let (block_address, entry) = entry?;
+            let block_number = block_address.block_number();
+            if current_block != Some(block_number) {
+                if current_block.is_some() && changesets >= input.delete_limit / 2 {
+                    last_block = block_number - 1;
+                    break
+                }
+                current_block = Some(block_number);
+            }
+            keys.insert((block_address.address(), entry.key));
+            changesets += 1;
+        }
+
+        let keys = keys
+            .into_iter()
+            .map(|(address, storage_key)| StorageShardedKey::new(address, storage_key, 0));
+        let (processed, pruned_indices, done_indices) = provider
+            .prune_history_shards::<tables::StorageHistory, _>(
+                keys,
+                last_block,
+                input.delete_limit - input.delete_limit / 2,
+                |a, b| a.address == b.address && a.sharded_key.key == b.sharded_key.key,
+            )?;
+        trace!(target: "pruner", %processed, pruned = %pruned_indices, done = %done_indices, "Removed snapshotted storage history (history)");
+
+        // If not all keys were pruned up to the last block, keep the changesets and the previous
+        // checkpoint. The keys that were already pruned have nothing left to prune on the next run.
+        if !done_indices {
+            return Ok(PruneOutput {
+                done: false,
+                pruned: pruned_indices,
+                checkpoint: Some(PruneOutputCheckpoint {
+                    block_number: range_start.checked_sub(1),
+                    tx_number: None,
+                }),
+            })
+        }
+
+        let (pruned_changesets, _) = provider.prune_table_with_range::<tables::StorageChangeSet>(
+            BlockNumberAddress::range(range_start..=last_block),
+            changesets,
+            |_| false,
+            |_| {},
+        )?;
+        trace!(target: "pruner", pruned = %pruned_changesets, "Removed snapshotted storage history (changesets)");
+
+        Ok(PruneOutput {
+            done: last_block == input.to_block,
+            pruned: pruned_indices + pruned_changesets,
+            checkpoint: Some(PruneOutputCheckpoint {
+                block_number: Some(last_block),
+                tx_number: None,
+            }),
+        })
+    }
+
+    fn checkpoint(
+        &self,
+        provider: &DatabaseProviderRW<DB>,
+    ) -> ProviderResult<Option<PruneCheckpoint>> {
+        snapshot_prune_checkpoint(provider, SnapshotSegment::StorageHistory)
+    }
+
+    fn save_checkpoint(
+        &self,
+        provider: &DatabaseProviderRW<DB>,
+        checkpoint: PruneCheckpoint,
+    ) -> ProviderResult<()> {
+        save_snapshot_prune_checkpoint(provider, SnapshotSegment::StorageHistory, checkpoint)
+    }
+}
+
+fn snapshot_prune_checkpoint<DB: Database>(
+    provider: &DatabaseProviderRW<DB>,
+    segment: SnapshotSegment,
+) -> ProviderResult<Option<PruneCheckpoint>> {
+    Ok(provider.tx_ref().get::<tables::SnapshotPruneCheckpoints>(segment)?)
+}
+
+fn save_snapshot_prune_checkpoint<DB: Database>(
+    provider: &DatabaseProviderRW<DB>,
+    segment: SnapshotSegment,
+    checkpoint: PruneCheckpoint,
+) -> ProviderResult<()> {
+    Ok(provider.tx_ref().put::<tables::SnapshotPruneCheckpoints>(segment, checkpoint)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::segments::{
+        PruneInput, PruneOutput, Segment, SnapshotAccountHistory, SnapshotStorageHistory,
+    };
+    use reth_db::{tables, test_utils::TempDatabase, BlockNumberList, DatabaseEnv};
+    use reth_interfaces::test_utils::{
+        generators,
+        generators::{random_block_range, random_changeset_range, random_eoa_account_range},
+    };
+    use reth_primitives::{BlockNumber, PruneMode, B256};
+    use reth_provider::PruneCheckpointReader;
+    use reth_stages::test_utils::TestStageDB;
+    use std::sync::Arc;
+
+    #[test]
+    fn prune() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(&mut rng, 0..=20, B256::ZERO, 0..1);
+        db.insert_blocks(blocks.iter(), None).expect("insert blocks");
+
+        let accounts = random_eoa_account_range(&mut rng, 0..4)
+            .into_iter()
+            .map(|(address, account)| (address, (account, Vec::new())));
+        let (changesets, _) = random_changeset_range(&mut rng, blocks.iter(), accounts, 1..3, 1..3);
+        db.insert_changesets(changesets.clone(), None).expect("insert changesets");
+        db.insert_history(changesets, None).expect("insert history");
+
+        let to_block: BlockNumber = 10;
+        let mode = PruneMode::Before(to_block + 1);
+        let test_prune = |segment: &dyn Segment<Arc<TempDatabase<DatabaseEnv>>>| {
+            // Run the segment until it's done, removing the blocks over multiple runs.
+            let mut previous_checkpoint = None;
+            let mut runs = 0;
+            loop {
+                let provider = db.factory.provider_rw().unwrap();
+                let input = PruneInput { previous_checkpoint, to_block, delete_limit: 10 };
+                let output = segment.prune(&provider, input).unwrap();
+                runs += 1;
+                let checkpoint = output.checkpoint.unwrap().as_prune_checkpoint(mode);
+                segment.save_checkpoint(&provider, checkpoint).unwrap();
+                assert_eq!(segment.checkpoint(&provider).unwrap(), Some(checkpoint));
+                provider.commit().unwrap();
+
+                previous_checkpoint = Some(checkpoint);
+                if output.done {
+                    assert_eq!(checkpoint.block_number, Some(to_block));
+                    break
+                }
+            }
+            assert!(runs > 1);
+
+            // Nothing is left to remove up to the target block.
+            let provider = db.factory.provider_rw().unwrap();
+            let input = PruneInput { previous_checkpoint, to_block, delete_limit: 10 };
+            assert_eq!(segment.prune(&provider, input).unwrap(), PruneOutput::done());
+            // The prune checkpoint of the segment is left untouched.
+            assert_eq!(provider.get_prune_checkpoint(segment.segment()).unwrap(), None);
+        };
+
+        test_prune(&SnapshotAccountHistory::new(mode));
+        test_prune(&SnapshotStorageHistory::new(mode));
+
+        let account_changesets = db.table::<tables::AccountChangeSet>().unwrap();
+        assert!(!account_changesets.is_empty());
+        assert!(account_changesets.iter().all(|(block_number, _)| *block_number > to_block));
+        let storage_changesets = db.table::<tables::StorageChangeSet>().unwrap();
+        assert!(!storage_changesets.is_empty());
+        assert!(storage_changesets.iter().all(|(key, _)| key.block_number() > to_block));
+        let shards = db
+            .table::<tables::AccountHistory>()
+            .unwrap()
+            .into_iter()
+            .map(|(_, blocks)| blocks)
+            .chain(
+                db.table::<tables::StorageHistory>().unwrap().into_iter().map(|(_, blocks)| blocks),
+            )
+            .collect::<Vec<BlockNumberList>>();
+        assert!(!shards.is_empty());
+        for blocks in shards {
+            assert!(blocks.iter(0).all(|block| block as BlockNumber > to_block));
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use crate::{
     database::StateProviderDatabase,
+    prefetch::{PrefetchProviders, StatePrefetcher},
     processor::EVMProcessor,
     stack::{InspectorStack, InspectorStackConfig},
 };
@@ -13,6 +14,9 @@ use std::sync::Arc;
 pub struct EvmProcessorFactory<EvmConfig> {
     chain_spec: Arc<ChainSpec>,
     stack: Option<InspectorStack>,
+    /// Number of threads prefetching the state of the upcoming transactions and the providers
+    /// they read through, if enabled.
+    prefetch: Option<(usize, PrefetchProviders)>,
     /// Type that defines how the produced EVM should be configured.
     evm_config: EvmConfig,
 }
@@ -20,7 +24,7 @@ pub struct EvmProcessorFactory<EvmConfig> {
 impl<EvmConfig> EvmProcessorFactory<EvmConfig> {
     /// Create new factory
     pub fn new(chain_spec: Arc<ChainSpec>, evm_config: EvmConfig) -> Self {
-        Self { chain_spec, stack: None, evm_config }
+        Self { chain_spec, stack: None, prefetch: None, evm_config }
     }
 
     /// Sets the inspector stack for all generated executors.
@@ -34,6 +38,13 @@ impl<EvmConfig> EvmProcessorFactory<EvmConfig> {
         self.stack = Some(InspectorStack::new(config));
         self
     }
+
+    /// Enables the [StatePrefetcher] with the given number of threads for all generated
+    /// executors, each thread reading through its own provider.
+    pub fn with_prefetch(mut self, workers: usize, providers: PrefetchProviders) -> Self {
+        self.prefetch = Some((workers, providers));
+        self
+    }
 }
 
 impl<EvmConfig> ExecutorFactory for EvmProcessorFactory<EvmConfig>
@@ -44,12 +55,19 @@ where
         &'a self,
         sp: SP,
     ) -> Box<dyn PrunableBlockExecutor + 'a> {
-        let database_state = StateProviderDatabase::new(sp);
-        let mut evm = Box::new(EVMProcessor::new_with_db(
-            self.chain_spec.clone(),
-            database_state,
-            self.evm_config.clone(),
-        ));
+        let mut evm = Box::new(match &self.prefetch {
+            Some((workers, providers)) => EVMProcessor::new_with_prefetcher(
+                self.chain_spec.clone(),
+                sp,
+                StatePrefetcher::new(providers.clone(), *workers),
+                self.evm_config.clone(),
+            ),
+            None => EVMProcessor::new_with_db(
+                self.chain_spec.clone(),
+                StateProviderDatabase::new(sp),
+                self.evm_config.clone(),
+            ),
+        });
         if let Some(ref stack) = self.stack {
             evm.set_stack(stack.clone());
         }
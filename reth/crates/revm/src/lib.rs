@@ -16,6 +16,9 @@ mod factory;
 /// new revm account state executor
 pub mod processor;
 
+/// State prefetching for the block executor.
+pub mod prefetch;
+
 /// State changes that are not related to transactions.
 pub mod state_change;
 
@@ -0,0 +1,373 @@
+use crate::database::StateProviderDatabase;
+use parking_lot::RwLock;
+use reth_interfaces::provider::ProviderResult;
+use reth_primitives::{Address, BlockWithSenders, TransactionSigned, B256, U256};
+use reth_provider::{ProviderError, StateProvider, StateProviderBox};
+use revm::{
+    primitives::{AccountInfo, Bytecode},
+    Database, DatabaseRef,
+};
+use std::{
+    collections::HashMap,
+    fmt,
+    ops::Range,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        mpsc, Arc,
+    },
+    thread::JoinHandle,
+};
+use tracing::trace;
+
+/// Opens the state providers of the prefetch workers, each over its own read transaction.
+///
+/// The providers must read the same state the blocks are executed on, i.e. the latest state of
+/// the database for the execution stage.
+#[derive(Clone)]
+pub struct PrefetchProviders(Arc<dyn Fn() -> ProviderResult<StateProviderBox> + Send + Sync>);
+
+impl PrefetchProviders {
+    /// Creates a new source of the worker state providers from the given function.
+    pub fn new(
+        open: impl Fn() -> ProviderResult<StateProviderBox> + Send + Sync + 'static,
+    ) -> Self {
+        Self(Arc::new(open))
+    }
+
+    fn open(&self) -> ProviderResult<StateProviderBox> {
+        (self.0)()
+    }
+}
+
+impl fmt::Debug for PrefetchProviders {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PrefetchProviders").finish_non_exhaustive()
+    }
+}
+
+/// State prefetcher that reads ahead the accounts and storage slots touched by the upcoming
+/// transactions of a block on a pool of background threads.
+///
+/// Every worker reads through its own state provider, opened once when the pool is spawned. The
+/// prefetched values are served by the [PrefetchDatabase] that backs the revm `State`, so the
+/// state cache is warmed from memory instead of blocking the execution on database reads. The
+/// prefetched values are dropped after every block, since every value that was used is cached by
+/// the revm `State` itself.
+///
+/// The workers also recover the senders of the blocks that come without them in bulk, see
+/// [StatePrefetcher::recover_senders].
+pub struct StatePrefetcher {
+    /// The prefetched values shared with the workers and the database.
+    cache: Arc<PrefetchCache>,
+    /// The job queues of the workers.
+    workers: Vec<mpsc::Sender<PrefetchJob>>,
+    /// The worker threads, joined when the prefetcher is dropped.
+    handles: Vec<JoinHandle<()>>,
+}
+
+impl StatePrefetcher {
+    /// Spawns a pool with the given number of workers reading through the given providers.
+    pub fn new(providers: PrefetchProviders, workers: usize) -> Self {
+        let cache = Arc::new(PrefetchCache::default());
+        let (workers, handles) = (0..workers.max(1))
+            .map(|worker| {
+                let (jobs_tx, jobs_rx) = mpsc::channel();
+                let providers = providers.clone();
+                let cache = cache.clone();
+                let handle = std::thread::Builder::new()
+                    .name(format!("prefetch-{worker}"))
+                    .spawn(move || run_worker(providers, cache, jobs_rx))
+                    .expect("failed to spawn prefetch worker");
+                (jobs_tx, handle)
+            })
+            .unzip();
+        Self { cache, workers, handles }
+    }
+
+    /// Returns the revm database serving the prefetched values, and reading the rest through the
+    /// given state provider.
+    pub fn database<DB: StateProvider>(&self, provider: DB) -> PrefetchDatabase<DB> {
+        PrefetchDatabase {
+            database: StateProviderDatabase::new(provider),
+            cache: self.cache.clone(),
+        }
+    }
+
+    /// Starts prefetching the state of the block transactions.
+    ///
+    /// The transactions are distributed between the workers in a round-robin fashion, so that all
+    /// of them read ahead in the order of execution. The workers stop once the returned
+    /// [PrefetchWorkers] is dropped.
+    pub(crate) fn prefetch(&self, block: &BlockWithSenders) -> PrefetchWorkers {
+        let targets = Arc::new(
+            block
+                .transactions_with_sender()
+                .map(|(sender, transaction)| PrefetchTarget::new(*sender, transaction))
+                .collect::<Vec<_>>(),
+        );
+        let stop = Arc::new(AtomicBool::new(false));
+        let (done_tx, done_rx) = mpsc::channel();
+        for (offset, worker) in self.workers.iter().enumerate() {
+            let _ = worker.send(PrefetchJob::Transactions {
+                targets: targets.clone(),
+                offset,
+                step: self.workers.len(),
+                stop: stop.clone(),
+                done: done_tx.clone(),
+            });
+        }
+        PrefetchWorkers { stop, done: done_rx }
+    }
+
+    /// Recovers the senders of the transactions in bulk, splitting them into one chunk per worker.
+    ///
+    /// This does not ensure that the `s` value of the signatures is low, like the recovery of the
+    /// senders of stored blocks. Returns `None` if a signature is invalid.
+    pub fn recover_senders(&self, transactions: &[TransactionSigned]) -> Option<Vec<Address>> {
+        let transactions = Arc::new(transactions.to_vec());
+        let chunk_size = transactions.len().div_ceil(self.workers.len()).max(1);
+
+        let (result_tx, result_rx) = mpsc::channel();
+        let mut chunks = 0;
+        let starts = (0..transactions.len()).step_by(chunk_size);
+        for (worker, start) in self.workers.iter().zip(starts) {
+            let range = start..transactions.len().min(start + chunk_size);
+            worker
+                .send(PrefetchJob::RecoverSenders {
+                    transactions: transactions.clone(),
+                    range,
+                    result: result_tx.clone(),
+                })
+                .ok()?;
+            chunks += 1;
+        }
+
+        let mut senders = vec![Address::ZERO; transactions.len()];
+        for _ in 0..chunks {
+            let (start, chunk) = result_rx.recv().ok()?;
+            let chunk = chunk?;
+            senders[start..start + chunk.len()].copy_from_slice(&chunk);
+        }
+        Some(senders)
+    }
+
+    /// Drops the prefetched values and returns the number of reads served by the prefetcher and
+    /// the number of reads that missed it since the last call.
+    pub(crate) fn finish_block(&self) -> (u64, u64) {
+        self.cache.accounts.write().clear();
+        self.cache.storage.write().clear();
+        (self.cache.hits.swap(0, Ordering::Relaxed), self.cache.misses.swap(0, Ordering::Relaxed))
+    }
+}
+
+impl fmt::Debug for StatePrefetcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StatePrefetcher")
+            .field("workers", &self.workers.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Drop for StatePrefetcher {
+    fn drop(&mut self) {
+        // Closing the job queues stops the workers, joining them closes their read transactions.
+        self.workers.clear();
+        for handle in self.handles.drain(..) {
+            let _ = handle.join();
+        }
+    }
+}
+
+/// Guard of the prefetching of a block. Stops the workers when dropped.
+#[derive(Debug)]
+pub(crate) struct PrefetchWorkers {
+    stop: Arc<AtomicBool>,
+    /// Disconnected once every worker is done with the block.
+    done: mpsc::Receiver<()>,
+}
+
+impl PrefetchWorkers {
+    /// Waits for the workers to prefetch the state of the whole block.
+    #[cfg(test)]
+    pub(crate) fn wait(&self) {
+        let _ = self.done.recv();
+    }
+}
+
+impl Drop for PrefetchWorkers {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        // Wait for the workers to stop, so that nothing is prefetched after the block is finished.
+        let _ = self.done.recv();
+    }
+}
+
+/// A job of a prefetch worker.
+enum PrefetchJob {
+    /// Prefetch the state of every `step`-th transaction, starting with the one at `offset`.
+    Transactions {
+        targets: Arc<Vec<PrefetchTarget>>,
+        offset: usize,
+        step: usize,
+        stop: Arc<AtomicBool>,
+        done: mpsc::Sender<()>,
+    },
+    /// Recover the senders of the transactions in the range.
+    RecoverSenders {
+        transactions: Arc<Vec<TransactionSigned>>,
+        range: Range<usize>,
+        result: mpsc::Sender<(usize, Option<Vec<Address>>)>,
+    },
+}
+
+/// Runs the jobs of a prefetch worker until its queue is closed.
+fn run_worker(
+    providers: PrefetchProviders,
+    cache: Arc<PrefetchCache>,
+    jobs: mpsc::Receiver<PrefetchJob>,
+) {
+    let database = providers.open().map(StateProviderDatabase::new);
+    if let Err(err) = &database {
+        trace!(target: "evm::prefetch", %err, "Failed to open the prefetch state provider");
+    }
+
+    for job in jobs {
+        match job {
+            PrefetchJob::Transactions { targets, offset, step, stop, done: _done } => {
+                // Without a provider the values are read by the execution itself.
+                let Ok(database) = &database else { continue };
+                for target in targets.iter().skip(offset).step_by(step) {
+                    if stop.load(Ordering::Relaxed) {
+                        break
+                    }
+
+                    if let Err(err) = cache.prefetch(database, target) {
+                        trace!(target: "evm::prefetch", %err, "Failed to prefetch transaction state");
+                        break
+                    }
+                }
+            }
+            PrefetchJob::RecoverSenders { transactions, range, result } => {
+                let senders = transactions[range.clone()]
+                    .iter()
+                    .map(|transaction| transaction.recover_signer_unchecked())
+                    .collect();
+                let _ = result.send((range.start, senders));
+            }
+        }
+    }
+}
+
+/// The accounts and storage slots a transaction is known to touch ahead of its execution: the
+/// sender and recipient accounts, and the access list entries.
+struct PrefetchTarget {
+    accounts: Vec<Address>,
+    storage: Vec<(Address, U256)>,
+}
+
+impl PrefetchTarget {
+    fn new(sender: Address, transaction: &TransactionSigned) -> Self {
+        let mut accounts = vec![sender];
+        accounts.extend(transaction.to());
+
+        let mut storage = Vec::new();
+        if let Some(access_list) = transaction.access_list() {
+            for item in &access_list.0 {
+                accounts.push(item.address);
+                storage.extend(
+                    item.storage_keys.iter().map(|key| (item.address, U256::from_be_bytes(key.0))),
+                );
+            }
+        }
+
+        Self { accounts, storage }
+    }
+}
+
+/// The prefetched values shared between the prefetcher, its workers and the database.
+#[derive(Default)]
+struct PrefetchCache {
+    /// Prefetched accounts.
+    accounts: RwLock<HashMap<Address, Option<AccountInfo>>>,
+    /// Prefetched storage slots.
+    storage: RwLock<HashMap<(Address, U256), U256>>,
+    /// The number of reads served from the prefetched values.
+    hits: AtomicU64,
+    /// The number of reads that missed the prefetched values.
+    misses: AtomicU64,
+}
+
+impl PrefetchCache {
+    /// Prefetches the accounts and storage slots of the target that weren't prefetched yet.
+    fn prefetch<DB: StateProvider>(
+        &self,
+        database: &StateProviderDatabase<DB>,
+        target: &PrefetchTarget,
+    ) -> Result<(), ProviderError> {
+        for address in &target.accounts {
+            if self.accounts.read().contains_key(address) {
+                continue
+            }
+            let account = database.basic_ref(*address)?;
+            self.accounts.write().insert(*address, account);
+        }
+
+        for (address, index) in &target.storage {
+            if self.storage.read().contains_key(&(*address, *index)) {
+                continue
+            }
+            let value = database.storage_ref(*address, *index)?;
+            self.storage.write().insert((*address, *index), value);
+        }
+
+        Ok(())
+    }
+
+    /// Records whether the read was served from the prefetched values.
+    fn record<T>(&self, value: Option<T>) -> Option<T> {
+        let counter = if value.is_some() { &self.hits } else { &self.misses };
+        counter.fetch_add(1, Ordering::Relaxed);
+        value
+    }
+}
+
+/// The revm [Database] that serves the values read ahead by the [StatePrefetcher], and falls back
+/// to the state provider otherwise.
+pub struct PrefetchDatabase<DB: StateProvider> {
+    database: StateProviderDatabase<DB>,
+    cache: Arc<PrefetchCache>,
+}
+
+impl<DB: StateProvider> fmt::Debug for PrefetchDatabase<DB> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PrefetchDatabase").finish_non_exhaustive()
+    }
+}
+
+impl<DB: StateProvider> Database for PrefetchDatabase<DB> {
+    type Error = ProviderError;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        let prefetched = self.cache.accounts.read().get(&address).cloned();
+        match self.cache.record(prefetched) {
+            Some(account) => Ok(account),
+            None => self.database.basic_ref(address),
+        }
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        self.database.code_by_hash_ref(code_hash)
+    }
+
+    fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        let prefetched = self.cache.storage.read().get(&(address, index)).copied();
+        match self.cache.record(prefetched) {
+            Some(value) => Ok(value),
+            None => self.database.storage_ref(address, index),
+        }
+    }
+
+    fn block_hash(&mut self, number: U256) -> Result<B256, Self::Error> {
+        self.database.block_hash_ref(number)
+    }
+}
@@ -1,6 +1,7 @@
 use crate::{
     database::StateProviderDatabase,
     eth_dao_fork::{DAO_HARDFORK_BENEFICIARY, DAO_HARDKFORK_ACCOUNTS},
+    prefetch::StatePrefetcher,
     stack::{InspectorStack, InspectorStackConfig},
     state_change::{apply_beacon_root_contract_call, post_block_balance_increments},
 };
@@ -77,6 +78,8 @@ pub struct EVMProcessor<'a, EvmConfig> {
     pruning_address_filter: Option<(u64, Vec<Address>)>,
     /// Execution stats
     pub(crate) stats: BlockExecutorStats,
+    /// Optional prefetcher reading ahead the state of the upcoming transactions.
+    prefetcher: Option<StatePrefetcher>,
     /// The type that is able to configure the EVM environment.
     _evm_config: EvmConfig,
 }
@@ -113,6 +116,7 @@ where
             prune_modes: PruneModes::none(),
             pruning_address_filter: None,
             stats: BlockExecutorStats::default(),
+            prefetcher: None,
             _evm_config: evm_config,
         }
     }
@@ -131,6 +135,24 @@ where
         EVMProcessor::new_with_state(chain_spec, state, evm_config)
     }
 
+    /// Creates a new executor from the given chain spec and database, with the given
+    /// [StatePrefetcher] reading ahead the state of the upcoming transactions.
+    pub fn new_with_prefetcher<DB: StateProvider + 'a>(
+        chain_spec: Arc<ChainSpec>,
+        db: DB,
+        prefetcher: StatePrefetcher,
+        evm_config: EvmConfig,
+    ) -> Self {
+        let state = State::builder()
+            .with_database_boxed(Box::new(prefetcher.database(db)))
+            .with_bundle_update()
+            .without_state_clear()
+            .build();
+        let mut executor = EVMProcessor::new_with_state(chain_spec, state, evm_config);
+        executor.prefetcher = Some(prefetcher);
+        executor
+    }
+
     /// Create a new EVM processor with the given revm state.
     pub fn new_with_state(
         chain_spec: Arc<ChainSpec>,
@@ -150,6 +172,7 @@ where
             prune_modes: PruneModes::none(),
             pruning_address_filter: None,
             stats: BlockExecutorStats::default(),
+            prefetcher: None,
             _evm_config: evm_config,
         }
     }
@@ -296,7 +319,8 @@ where
     ) -> Result<Vec<Receipt>, BlockExecutionError> {
         self.init_env(&block.header, total_difficulty);
         self.apply_beacon_root_contract_call(block)?;
-        let (receipts, cumulative_gas_used) = self.execute_transactions(block, total_difficulty)?;
+        let (receipts, cumulative_gas_used) =
+            self.execute_transactions_with_prefetch(block, total_difficulty)?;
 
         // Check if gas used matches the value set in header.
         if block.gas_used != cumulative_gas_used {
@@ -336,6 +360,45 @@ where
         Ok(receipts)
     }
 
+    /// Execute the block transactions while the prefetcher, if any, reads ahead their state.
+    ///
+    /// The senders of a block that comes without them are recovered in bulk first, on the
+    /// prefetch workers if enabled.
+    fn execute_transactions_with_prefetch(
+        &mut self,
+        block: &BlockWithSenders,
+        total_difficulty: U256,
+    ) -> Result<(Vec<Receipt>, u64), BlockExecutionError> {
+        let recovered;
+        let block = if block.senders.len() == block.body.len() {
+            block
+        } else {
+            let senders = match &self.prefetcher {
+                Some(prefetcher) => prefetcher.recover_senders(&block.body),
+                None => TransactionSigned::recover_signers_unchecked(&block.body, block.body.len()),
+            }
+            .ok_or(BlockValidationError::SenderRecoveryError)?;
+            recovered = BlockWithSenders { block: block.block.clone(), senders };
+            &recovered
+        };
+
+        let Some(prefetcher) = &self.prefetcher else {
+            return self.execute_transactions(block, total_difficulty)
+        };
+
+        let workers = prefetcher.prefetch(block);
+        let output = self.execute_transactions(block, total_difficulty);
+        drop(workers);
+
+        if let Some(prefetcher) = &self.prefetcher {
+            let (hits, misses) = prefetcher.finish_block();
+            self.stats.prefetch_hits += hits;
+            self.stats.prefetch_misses += misses;
+        }
+
+        output
+    }
+
     /// Save receipts to the executor.
     pub fn save_receipts(&mut self, receipts: Vec<Receipt>) -> Result<(), BlockExecutionError> {
         let mut receipts = receipts.into_iter().map(Option::Some).collect();
@@ -569,14 +632,16 @@ pub fn verify_receipt<'a>(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::prefetch::PrefetchProviders;
     use reth_interfaces::provider::ProviderResult;
     use reth_node_ethereum::EthEvmConfig;
     use reth_primitives::{
         bytes,
         constants::{BEACON_ROOTS_ADDRESS, SYSTEM_ADDRESS},
-        keccak256,
+        keccak256, sign_message,
         trie::AccountProof,
-        Account, Bytecode, Bytes, ChainSpecBuilder, ForkCondition, StorageKey, MAINNET,
+        AccessList, AccessListItem, Account, Bytecode, Bytes, ChainSpecBuilder, ForkCondition,
+        Signature, StorageKey, Transaction, TransactionKind, TxEip2930, MAINNET,
     };
     use reth_provider::{
         AccountReader, BlockHashReader, BundleStateWithReceipts, StateRootProvider,
@@ -1061,4 +1126,86 @@ mod tests {
             .unwrap();
         assert_eq!(parent_beacon_block_root_storage, U256::from(0x69));
     }
+
+    #[test]
+    fn prefetcher_serves_block_state() {
+        let sender = Address::with_last_byte(1);
+        let recipient = Address::with_last_byte(2);
+        let slot = B256::with_last_byte(3);
+
+        let mut db = StateProviderTest::default();
+        db.insert_account(
+            sender,
+            Account { nonce: 1, balance: U256::from(10), bytecode_hash: None },
+            None,
+            HashMap::new(),
+        );
+        db.insert_account(
+            recipient,
+            Account { nonce: 0, balance: U256::ZERO, bytecode_hash: None },
+            None,
+            HashMap::from([(slot, U256::from(4))]),
+        );
+
+        let transaction = TransactionSigned::from_transaction_and_signature(
+            Transaction::Eip2930(TxEip2930 {
+                to: TransactionKind::Call(recipient),
+                access_list: AccessList(vec![AccessListItem {
+                    address: recipient,
+                    storage_keys: vec![slot],
+                }]),
+                ..Default::default()
+            }),
+            Signature::default(),
+        );
+        let block = BlockWithSenders {
+            block: Block { body: vec![transaction], ..Default::default() },
+            senders: vec![sender],
+        };
+
+        let providers = {
+            let db = db.clone();
+            PrefetchProviders::new(move || Ok(Box::new(db.clone())))
+        };
+        let prefetcher = StatePrefetcher::new(providers, 2);
+        let mut database = prefetcher.database(db);
+        prefetcher.prefetch(&block).wait();
+
+        assert_eq!(database.basic(sender).unwrap().map(|account| account.nonce), Some(1));
+        assert_eq!(database.basic(recipient).unwrap().map(|account| account.nonce), Some(0));
+        assert_eq!(
+            database.storage(recipient, U256::from_be_bytes(slot.0)).unwrap(),
+            U256::from(4)
+        );
+        assert_eq!(database.basic(Address::with_last_byte(5)).unwrap(), None);
+        assert_eq!(prefetcher.finish_block(), (3, 1));
+
+        // The prefetched state is dropped once the block is finished.
+        assert_eq!(database.basic(sender).unwrap().map(|account| account.nonce), Some(1));
+        assert_eq!(prefetcher.finish_block(), (0, 1));
+    }
+
+    #[test]
+    fn prefetcher_recovers_senders() {
+        let secrets = (1..=5).map(B256::with_last_byte).collect::<Vec<_>>();
+        let transactions = secrets
+            .iter()
+            .map(|secret| {
+                let transaction = Transaction::Eip2930(TxEip2930::default());
+                let signature =
+                    sign_message(*secret, transaction.signature_hash()).expect("valid secret");
+                TransactionSigned::from_transaction_and_signature(transaction, signature)
+            })
+            .collect::<Vec<_>>();
+        let expected = transactions
+            .iter()
+            .map(|transaction| transaction.recover_signer().expect("valid signature"))
+            .collect::<Vec<_>>();
+
+        // The transactions are split into uneven chunks between the workers.
+        let providers = PrefetchProviders::new(|| Ok(Box::<StateProviderTest>::default()));
+        let prefetcher = StatePrefetcher::new(providers, 2);
+        assert_eq!(prefetcher.recover_senders(&transactions), Some(expected));
+        assert_eq!(prefetcher.recover_senders(&[]), Some(Vec::new()));
+    }
 }
@@ -5,7 +5,8 @@ use reth_rpc_types::{
         BlockTraceResult, GethDebugTracingCallOptions, GethDebugTracingOptions, GethTrace,
         TraceResult,
     },
-    Bundle, CallRequest, RichBlock, StateContext,
+    AccountRangeResult, Bundle, CallRequest, RichBlock, StateContext, StateDump,
+    StorageRangeResult,
 };
 
 /// Debug rpc interface.
@@ -137,10 +138,12 @@ pub trait DebugApi {
     async fn debug_backtrace_at(&self, location: &str) -> RpcResult<()>;
 
     /// Enumerates all accounts at a given block with paging capability. `maxResults` are returned
-    /// in the page and the items have keys that come after the `start` key (hashed address).
+    /// in the page and the items have addresses that come after the `start` address.
     ///
-    /// If incompletes is false, then accounts for which the key preimage (i.e: the address) doesn't
-    /// exist in db are skipped. NB: geth by default does not store preimages.
+    /// Accounts are ordered by hashed address, `start` is a prefix of the hashed address to start
+    /// at and the `next` field of the result is the hashed address to continue from. Reth doesn't
+    /// store the preimages of hashed addresses, so the accounts are keyed by hashed address and
+    /// always included regardless of `incompletes`.
     #[method(name = "accountRange")]
     async fn debug_account_range(
         &self,
@@ -150,7 +153,7 @@ pub trait DebugApi {
         nocode: bool,
         nostorage: bool,
         incompletes: bool,
-    ) -> RpcResult<()>;
+    ) -> RpcResult<AccountRangeResult>;
 
     /// Turns on block profiling for the given duration and writes profile data to disk. It uses a
     /// profile rate of 1 for most accurate information. If a different rate is desired, set the
@@ -187,8 +190,11 @@ pub trait DebugApi {
 
     /// Retrieves the state that corresponds to the block number and returns a list of accounts
     /// (including storage and code).
+    ///
+    /// This fails for states with too many accounts or accounts with too many storage slots, use
+    /// `debug_accountRange` and `debug_storageRangeAt` to page through them instead.
     #[method(name = "dumpBlock")]
-    async fn debug_dump_block(&self, number: BlockId) -> RpcResult<()>;
+    async fn debug_dump_block(&self, number: BlockId) -> RpcResult<StateDump>;
 
     /// Forces garbage collection.
     #[method(name = "freeOSMemory")]
@@ -212,7 +218,7 @@ pub trait DebugApi {
         &self,
         from: BlockNumberOrTag,
         to: BlockNumberOrTag,
-    ) -> RpcResult<()>;
+    ) -> RpcResult<u64>;
 
     /// Returns all accounts that have changed between the two blocks specified. A change is defined
     /// as a difference in nonce, balance, code hash, or storage hash. With one parameter, returns
@@ -221,17 +227,18 @@ pub trait DebugApi {
     async fn debug_get_modified_accounts_by_hash(
         &self,
         start_hash: B256,
-        end_hash: B256,
-    ) -> RpcResult<()>;
+        end_hash: Option<B256>,
+    ) -> RpcResult<Vec<Address>>;
 
     /// Returns all accounts that have changed between the two blocks specified. A change is defined
-    /// as a difference in nonce, balance, code hash or storage hash.
+    /// as a difference in nonce, balance, code hash or storage hash. With one parameter, returns
+    /// the list of accounts modified in the specified block.
     #[method(name = "getModifiedAccountsByNumber")]
     async fn debug_get_modified_accounts_by_number(
         &self,
         start_number: u64,
-        end_number: u64,
-    ) -> RpcResult<()>;
+        end_number: Option<u64>,
+    ) -> RpcResult<Vec<Address>>;
 
     /// Turns on Go runtime tracing for the given duration and writes trace data to disk.
     #[method(name = "goTrace")]
@@ -244,7 +251,7 @@ pub trait DebugApi {
         &self,
         block_hash: B256,
         opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()>;
+    ) -> RpcResult<Vec<B256>>;
 
     /// Returns detailed runtime memory statistics.
     #[method(name = "memStats")]
@@ -258,11 +265,11 @@ pub trait DebugApi {
 
     /// Returns the preimage for a sha3 hash, if known.
     #[method(name = "preimage")]
-    async fn debug_preimage(&self, hash: B256) -> RpcResult<()>;
+    async fn debug_preimage(&self, hash: B256) -> RpcResult<Bytes>;
 
     /// Retrieves a block and returns its pretty printed form.
     #[method(name = "printBlock")]
-    async fn debug_print_block(&self, number: u64) -> RpcResult<()>;
+    async fn debug_print_block(&self, number: u64) -> RpcResult<String>;
 
     /// Fetches and retrieves the seed hash of the block by number.
     #[method(name = "seedHash")]
@@ -303,7 +310,7 @@ pub trait DebugApi {
         &self,
         block: BlockNumberOrTag,
         opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()>;
+    ) -> RpcResult<Vec<String>>;
 
     /// This method is similar to `debug_standardTraceBlockToFile`, but can be used to obtain info
     /// about a block which has been rejected as invalid (for some reason).
@@ -312,7 +319,7 @@ pub trait DebugApi {
         &self,
         block: BlockNumberOrTag,
         opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()>;
+    ) -> RpcResult<Vec<String>>;
 
     /// Turns on CPU profiling indefinitely, writing to the given file.
     #[method(name = "startCPUProfile")]
@@ -332,7 +339,11 @@ pub trait DebugApi {
 
     /// Returns the storage at the given block height and transaction index. The result can be
     /// paged by providing a `maxResult` to cap the number of storage slots returned as well as
-    /// specifying the offset via `keyStart` (hash of storage key).
+    /// specifying the offset via `keyStart`.
+    ///
+    /// Slots are ordered by hashed storage key, `keyStart` and the returned `nextKey` are hashed
+    /// storage keys. The preimage of a hashed key is only returned if the slot was touched by the
+    /// replayed transactions.
     #[method(name = "storageRangeAt")]
     async fn debug_storage_range_at(
         &self,
@@ -341,7 +352,7 @@ pub trait DebugApi {
         contract_address: Address,
         key_start: B256,
         max_result: u64,
-    ) -> RpcResult<()>;
+    ) -> RpcResult<StorageRangeResult>;
 
     /// Returns the structured logs created during the execution of EVM against a block pulled
     /// from the pool of bad ones and returns them as a JSON object. For the second parameter see
@@ -351,7 +362,7 @@ pub trait DebugApi {
         &self,
         block_hash: B256,
         opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()>;
+    ) -> RpcResult<Vec<TraceResult>>;
 
     /// Sets the logging verbosity ceiling. Log messages with level up to and including the given
     /// level will be printed.
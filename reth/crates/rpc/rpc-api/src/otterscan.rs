@@ -1,5 +1,5 @@
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
-use reth_primitives::{Address, BlockId, BlockNumberOrTag, TxHash, B256};
+use reth_primitives::{Address, BlockId, BlockNumberOrTag, Bytes, TxHash, B256};
 use reth_rpc_types::{
     BlockDetails, ContractCreator, InternalOperation, OtsBlockTransactions, TraceEntry,
     Transaction, TransactionsWithReceipts,
@@ -21,16 +21,19 @@ pub trait Otterscan {
 
     /// Return the internal ETH transfers inside a transaction.
     #[method(name = "getInternalOperations")]
-    async fn get_internal_operations(&self, tx_hash: TxHash) -> RpcResult<Vec<InternalOperation>>;
+    async fn get_internal_operations(
+        &self,
+        tx_hash: TxHash,
+    ) -> RpcResult<Option<Vec<InternalOperation>>>;
 
     /// Given a transaction hash, returns its raw revert reason.
     #[method(name = "getTransactionError")]
-    async fn get_transaction_error(&self, tx_hash: TxHash) -> RpcResult<String>;
+    async fn get_transaction_error(&self, tx_hash: TxHash) -> RpcResult<Option<Bytes>>;
 
     /// Extract all variations of calls, contract creation and self-destructs and returns a call
     /// tree.
     #[method(name = "traceTransaction")]
-    async fn trace_transaction(&self, tx_hash: TxHash) -> RpcResult<TraceEntry>;
+    async fn trace_transaction(&self, tx_hash: TxHash) -> RpcResult<Option<Vec<TraceEntry>>>;
 
     /// Tailor-made and expanded version of eth_getBlockByNumber for block details page in
     /// Otterscan.
@@ -14,8 +14,8 @@ use jsonrpsee::{
 use reth_network_api::{NetworkInfo, Peers};
 use reth_node_api::{ConfigureEvmEnv, EngineTypes};
 use reth_provider::{
-    BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, HeaderProvider, ReceiptProviderIdExt,
-    StateProviderFactory,
+    BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, HeaderProvider, LogIndexReader,
+    ReceiptProviderIdExt, StateProviderFactory,
 };
 use reth_rpc::{
     eth::{
@@ -52,6 +52,7 @@ where
         + HeaderProvider
         + ReceiptProviderIdExt
         + StateProviderFactory
+        + LogIndexReader
         + Clone
         + Unpin
         + 'static,
@@ -81,6 +82,7 @@ where
         eth_cache.clone(),
         gas_oracle,
         EthConfig::default().rpc_gas_cap,
+        EthConfig::default().eth_proof_window,
         Box::new(executor.clone()),
         BlockingTaskPool::build().expect("failed to build tracing pool"),
         fee_history_cache,
@@ -108,6 +110,7 @@ where
         + EvmEnvProvider
         + HeaderProvider
         + StateProviderFactory
+        + LogIndexReader
         + Clone
         + Unpin
         + 'static,
@@ -4,6 +4,9 @@ pub use reth_rpc::eth::gas_oracle::{
     DEFAULT_MAX_GAS_PRICE,
 };
 
+/// `eth_getProof` window reexports
+pub use reth_rpc::eth::{DEFAULT_ETH_PROOF_WINDOW, MAX_ETH_PROOF_WINDOW};
+
 /// The default port for the http server
 pub const DEFAULT_HTTP_RPC_PORT: u16 = 8545;
 
@@ -19,6 +22,17 @@ pub const DEFAULT_MAX_BLOCKS_PER_FILTER: u64 = 100_000;
 /// The default maximum of logs in a single response.
 pub const DEFAULT_MAX_LOGS_PER_RESPONSE: usize = 20_000;
 
+/// The default maximum block range allowed to scan in a single `trace_filter` request
+pub const DEFAULT_MAX_TRACE_FILTER_BLOCKS: u64 = 10_000;
+
+/// The default maximum number of blocks behind the tip whose state is served by
+/// `debug_accountRange`, `debug_dumpBlock` and `debug_storageRangeAt`
+pub const DEFAULT_DEBUG_STATE_WINDOW: u64 = 128;
+
+/// The upper bound for the configurable debug state window, since the state of older blocks is
+/// reverted in memory like for `eth_getProof`
+pub const MAX_DEBUG_STATE_WINDOW: u64 = MAX_ETH_PROOF_WINDOW;
+
 /// The default maximum number of concurrently executed tracing calls
 pub const DEFAULT_MAX_TRACING_REQUESTS: u32 = 25;
 
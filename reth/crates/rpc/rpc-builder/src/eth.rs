@@ -1,5 +1,6 @@
 use crate::constants::{
-    DEFAULT_MAX_BLOCKS_PER_FILTER, DEFAULT_MAX_LOGS_PER_RESPONSE, DEFAULT_MAX_TRACING_REQUESTS,
+    DEFAULT_DEBUG_STATE_WINDOW, DEFAULT_ETH_PROOF_WINDOW, DEFAULT_MAX_BLOCKS_PER_FILTER,
+    DEFAULT_MAX_LOGS_PER_RESPONSE, DEFAULT_MAX_TRACE_FILTER_BLOCKS, DEFAULT_MAX_TRACING_REQUESTS,
 };
 use reth_rpc::{
     eth::{
@@ -39,10 +40,17 @@ pub struct EthConfig {
     pub max_blocks_per_filter: u64,
     /// Maximum number of logs that can be returned in a single response in `eth_getLogs` calls.
     pub max_logs_per_response: usize,
+    /// Maximum number of blocks that could be scanned per `trace_filter` request.
+    pub max_trace_filter_blocks: u64,
     /// Gas limit for `eth_call` and call tracing RPC methods.
     ///
     /// Defaults to [RPC_DEFAULT_GAS_CAP]
     pub rpc_gas_cap: u64,
+    /// The maximum number of blocks behind the tip for which `eth_getProof` is served.
+    pub eth_proof_window: u64,
+    /// The maximum number of blocks behind the tip whose state is served by `debug_accountRange`,
+    /// `debug_dumpBlock` and `debug_storageRangeAt`.
+    pub debug_state_window: u64,
     ///
     /// Sets TTL for stale filters
     pub stale_filter_ttl: std::time::Duration,
@@ -71,7 +79,10 @@ impl Default for EthConfig {
             max_tracing_requests: DEFAULT_MAX_TRACING_REQUESTS,
             max_blocks_per_filter: DEFAULT_MAX_BLOCKS_PER_FILTER,
             max_logs_per_response: DEFAULT_MAX_LOGS_PER_RESPONSE,
+            max_trace_filter_blocks: DEFAULT_MAX_TRACE_FILTER_BLOCKS,
             rpc_gas_cap: RPC_DEFAULT_GAS_CAP.into(),
+            eth_proof_window: DEFAULT_ETH_PROOF_WINDOW,
+            debug_state_window: DEFAULT_DEBUG_STATE_WINDOW,
             stale_filter_ttl: DEFAULT_STALE_FILTER_TTL,
             fee_history_cache: FeeHistoryCacheConfig::default(),
         }
@@ -109,9 +120,28 @@ impl EthConfig {
         self
     }
 
+    /// Configures the maximum block length to scan per `trace_filter` request
+    pub fn max_trace_filter_blocks(mut self, max_blocks: u64) -> Self {
+        self.max_trace_filter_blocks = max_blocks;
+        self
+    }
+
     /// Configures the maximum gas limit for `eth_call` and call tracing RPC methods
     pub fn rpc_gas_cap(mut self, rpc_gas_cap: u64) -> Self {
         self.rpc_gas_cap = rpc_gas_cap;
         self
     }
+
+    /// Configures the maximum number of blocks behind the tip for which `eth_getProof` is served
+    pub fn eth_proof_window(mut self, window: u64) -> Self {
+        self.eth_proof_window = window;
+        self
+    }
+
+    /// Configures the maximum number of blocks behind the tip whose state is served by the
+    /// `debug` state inspection methods
+    pub fn debug_state_window(mut self, window: u64) -> Self {
+        self.debug_state_window = window;
+        self
+    }
 }
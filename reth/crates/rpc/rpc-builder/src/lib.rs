@@ -20,8 +20,9 @@
 //! use reth_network_api::{NetworkInfo, Peers};
 //! use reth_node_api::ConfigureEvmEnv;
 //! use reth_provider::{
-//!     AccountReader, BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider,
-//!     ChangeSetReader, EvmEnvProvider, StateProviderFactory,
+//!     AccountReader, AddressTransactionsReader, BlockReaderIdExt, CanonStateSubscriptions,
+//!     ChainSpecProvider, ChangeSetReader, EvmEnvProvider, LogIndexReader, StateProviderFactory,
+//!     StateRangeReader,
 //! };
 //! use reth_rpc_builder::{
 //!     RethRpcModule, RpcModuleBuilder, RpcServerConfig, ServerBuilder, TransportRpcModuleConfig,
@@ -39,6 +40,9 @@
 //!         + BlockReaderIdExt
 //!         + ChainSpecProvider
 //!         + ChangeSetReader
+//!         + AddressTransactionsReader
+//!         + StateRangeReader
+//!         + LogIndexReader
 //!         + StateProviderFactory
 //!         + EvmEnvProvider
 //!         + Clone
@@ -80,8 +84,9 @@
 //! use reth_network_api::{NetworkInfo, Peers};
 //! use reth_node_api::{ConfigureEvmEnv, EngineTypes};
 //! use reth_provider::{
-//!     AccountReader, BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider,
-//!     ChangeSetReader, EvmEnvProvider, StateProviderFactory,
+//!     AccountReader, AddressTransactionsReader, BlockReaderIdExt, CanonStateSubscriptions,
+//!     ChainSpecProvider, ChangeSetReader, EvmEnvProvider, LogIndexReader, StateProviderFactory,
+//!     StateRangeReader,
 //! };
 //! use reth_rpc::JwtSecret;
 //! use reth_rpc_api::EngineApiServer;
@@ -104,6 +109,9 @@
 //!         + BlockReaderIdExt
 //!         + ChainSpecProvider
 //!         + ChangeSetReader
+//!         + AddressTransactionsReader
+//!         + StateRangeReader
+//!         + LogIndexReader
 //!         + StateProviderFactory
 //!         + EvmEnvProvider
 //!         + Clone
@@ -184,8 +192,9 @@ use reth_ipc::server::IpcServer;
 pub use reth_ipc::server::{Builder as IpcServerBuilder, Endpoint};
 use reth_network_api::{noop::NoopNetwork, NetworkInfo, Peers};
 use reth_provider::{
-    AccountReader, BlockReader, BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider,
-    ChangeSetReader, EvmEnvProvider, StateProviderFactory,
+    AccountReader, AddressTransactionsReader, BlockReader, BlockReaderIdExt,
+    CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader, EvmEnvProvider, LogIndexReader,
+    StateProviderFactory, StateRangeReader,
 };
 use reth_rpc::{
     eth::{
@@ -196,7 +205,7 @@ use reth_rpc::{
     },
     AdminApi, AuthLayer, BlockingTaskGuard, BlockingTaskPool, Claims, DebugApi, EngineEthApi,
     EthApi, EthFilter, EthPubSub, EthSubscriptionIdProvider, JwtAuthValidator, JwtSecret, NetApi,
-    OtterscanApi, RPCApi, RethApi, TraceApi, TxPoolApi, Web3Api,
+    OtterscanApi, RPCApi, RethApi, TraceApi, TxPoolApi, ValidationApi, Web3Api,
 };
 use reth_rpc_api::{servers::*, EngineApiServer};
 use reth_tasks::{TaskSpawner, TokioTaskExecutor};
@@ -241,6 +250,9 @@ where
         + EvmEnvProvider
         + ChainSpecProvider
         + ChangeSetReader
+        + AddressTransactionsReader
+        + StateRangeReader
+        + LogIndexReader
         + Clone
         + Unpin
         + 'static,
@@ -432,6 +444,9 @@ where
         + EvmEnvProvider
         + ChainSpecProvider
         + ChangeSetReader
+        + AddressTransactionsReader
+        + StateRangeReader
+        + LogIndexReader
         + Clone
         + Unpin
         + 'static,
@@ -728,6 +743,9 @@ impl RpcModuleSelection {
             + EvmEnvProvider
             + ChainSpecProvider
             + ChangeSetReader
+            + AddressTransactionsReader
+            + StateRangeReader
+            + LogIndexReader
             + Clone
             + Unpin
             + 'static,
@@ -856,6 +874,10 @@ pub enum RethRpcModule {
     /// This is separate from [RethRpcModule::Eth] because it is a non standardized call that
     /// should be opt-in.
     EthCallBundle,
+    /// `mev_` module
+    Mev,
+    /// `flashbots_` module
+    Flashbots,
 }
 
 // === impl RethRpcModule ===
@@ -900,6 +922,8 @@ impl FromStr for RethRpcModule {
             "reth" => RethRpcModule::Reth,
             "ots" => RethRpcModule::Ots,
             "eth-call-bundle" | "eth_callBundle" => RethRpcModule::EthCallBundle,
+            "mev" => RethRpcModule::Mev,
+            "flashbots" => RethRpcModule::Flashbots,
             _ => return Err(ParseError::VariantNotFound),
         })
     }
@@ -1050,6 +1074,9 @@ where
         + EvmEnvProvider
         + ChainSpecProvider
         + ChangeSetReader
+        + AddressTransactionsReader
+        + StateRangeReader
+        + LogIndexReader
         + Clone
         + Unpin
         + 'static,
@@ -1205,6 +1232,7 @@ where
                             self.provider.clone(),
                             eth_api.clone(),
                             self.blocking_pool_guard.clone(),
+                            self.config.eth.debug_state_window,
                         )
                         .into_rpc()
                         .into(),
@@ -1223,6 +1251,7 @@ where
                             self.provider.clone(),
                             eth_api.clone(),
                             self.blocking_pool_guard.clone(),
+                            self.config.eth.max_trace_filter_blocks,
                         )
                         .into_rpc()
                         .into(),
@@ -1238,17 +1267,32 @@ where
                         )
                         .into_rpc()
                         .into(),
-                        RethRpcModule::Ots => OtterscanApi::new(eth_api.clone()).into_rpc().into(),
-                        RethRpcModule::Reth => {
-                            RethApi::new(self.provider.clone(), Box::new(self.executor.clone()))
+                        RethRpcModule::Ots => {
+                            OtterscanApi::new(self.provider.clone(), eth_api.clone())
                                 .into_rpc()
                                 .into()
                         }
-                        RethRpcModule::EthCallBundle => {
-                            EthBundle::new(eth_api.clone(), self.blocking_pool_guard.clone())
+                        RethRpcModule::Reth => {
+                            RethApi::new(self.provider.clone(), Box::new(self.executor.clone()))
                                 .into_rpc()
                                 .into()
                         }
+                        RethRpcModule::EthCallBundle => EthCallBundleApiServer::into_rpc(
+                            EthBundle::new(eth_api.clone(), self.blocking_pool_guard.clone()),
+                        )
+                        .into(),
+                        RethRpcModule::Mev => MevApiServer::into_rpc(EthBundle::new(
+                            eth_api.clone(),
+                            self.blocking_pool_guard.clone(),
+                        ))
+                        .into(),
+                        RethRpcModule::Flashbots => ValidationApi::new(
+                            self.provider.clone(),
+                            self.evm_config.clone(),
+                            Box::new(self.executor.clone()),
+                        )
+                        .into_rpc()
+                        .into(),
                     })
                     .clone()
             })
@@ -1317,6 +1361,7 @@ where
                 cache.clone(),
                 gas_oracle,
                 self.config.eth.rpc_gas_cap,
+                self.config.eth.eth_proof_window,
                 executor.clone(),
                 blocking_task_pool.clone(),
                 fee_history_cache,
@@ -1371,10 +1416,15 @@ where
     /// If called outside of the tokio runtime. See also [Self::eth_api]
     pub fn trace_api(&mut self) -> TraceApi<Provider, EthApi<Provider, Pool, Network, EvmConfig>> {
         let eth = self.eth_handlers();
-        TraceApi::new(self.provider.clone(), eth.api, self.blocking_pool_guard.clone())
+        TraceApi::new(
+            self.provider.clone(),
+            eth.api,
+            self.blocking_pool_guard.clone(),
+            self.config.eth.max_trace_filter_blocks,
+        )
     }
 
-    /// Instantiates [EthBundle] Api
+    /// Instantiates [EthBundle] Api, which serves both the `eth_callBundle` and the `mev` api
     ///
     /// # Panics
     ///
@@ -1384,14 +1434,25 @@ where
         EthBundle::new(eth_api, self.blocking_pool_guard.clone())
     }
 
+    /// Instantiates ValidationApi
+    pub fn validation_api(&mut self) -> ValidationApi<Provider, EvmConfig> {
+        ValidationApi::new(
+            self.provider.clone(),
+            self.evm_config.clone(),
+            Box::new(self.executor.clone()),
+        )
+    }
+
     /// Instantiates OtterscanApi
     ///
     /// # Panics
     ///
     /// If called outside of the tokio runtime. See also [Self::eth_api]
-    pub fn otterscan_api(&mut self) -> OtterscanApi<EthApi<Provider, Pool, Network, EvmConfig>> {
+    pub fn otterscan_api(
+        &mut self,
+    ) -> OtterscanApi<Provider, EthApi<Provider, Pool, Network, EvmConfig>> {
         let eth_api = self.eth_api();
-        OtterscanApi::new(eth_api)
+        OtterscanApi::new(self.provider.clone(), eth_api)
     }
 
     /// Instantiates DebugApi
@@ -1401,7 +1462,12 @@ where
     /// If called outside of the tokio runtime. See also [Self::eth_api]
     pub fn debug_api(&mut self) -> DebugApi<Provider, EthApi<Provider, Pool, Network, EvmConfig>> {
         let eth_api = self.eth_api();
-        DebugApi::new(self.provider.clone(), eth_api, self.blocking_pool_guard.clone())
+        DebugApi::new(
+            self.provider.clone(),
+            eth_api,
+            self.blocking_pool_guard.clone(),
+            self.config.eth.debug_state_window,
+        )
     }
 
     /// Instantiates NetApi
@@ -2379,6 +2445,8 @@ mod tests {
                 "rpc" => RethRpcModule::Rpc,
                 "ots" => RethRpcModule::Ots,
                 "reth" => RethRpcModule::Reth,
+                "mev" => RethRpcModule::Mev,
+                "flashbots" => RethRpcModule::Flashbots,
             );
     }
 
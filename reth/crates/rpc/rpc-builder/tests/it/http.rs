@@ -16,13 +16,13 @@ use reth_primitives::{
 };
 use reth_rpc_api::{
     clients::{AdminApiClient, EthApiClient},
-    DebugApiClient, EthFilterApiClient, NetApiClient, OtterscanClient, TraceApiClient,
-    Web3ApiClient,
+    DebugApiClient, EthFilterApiClient, MevApiClient, NetApiClient, OtterscanClient,
+    TraceApiClient, Web3ApiClient,
 };
 use reth_rpc_builder::RethRpcModule;
 use reth_rpc_types::{
     trace::filter::TraceFilter, CallRequest, Filter, Index, Log, PendingTransactionFilterKind,
-    TransactionRequest,
+    SendBundleRequest, SimBundleOverrides, TransactionRequest,
 };
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -38,6 +38,13 @@ fn is_unimplemented(err: Error) -> bool {
     }
 }
 
+fn is_method_not_found(err: Error) -> bool {
+    match err {
+        Error::Call(error_obj) => error_obj.code() == ErrorCode::MethodNotFound.code(),
+        _ => false,
+    }
+}
+
 /// Represents a builder for creating JSON-RPC requests.
 #[derive(Clone, Serialize, Deserialize)]
 pub struct RawRpcParamsBuilder {
@@ -203,6 +210,27 @@ where
     DebugApiClient::raw_transaction(client, B256::default()).await.unwrap();
     DebugApiClient::raw_receipts(client, block_id).await.unwrap();
     assert!(is_unimplemented(DebugApiClient::bad_blocks(client).await.err().unwrap()));
+    assert!(DebugApiClient::debug_get_modified_accounts_by_number(client, 0, None)
+        .await
+        .unwrap()
+        .is_empty());
+    assert!(is_method_not_found(DebugApiClient::debug_set_head(client, 0).await.err().unwrap()));
+}
+
+async fn test_basic_mev_calls<C>(client: &C)
+where
+    C: ClientT + SubscriptionClientT + Sync,
+{
+    assert!(MevApiClient::send_bundle(client, SendBundleRequest::default()).await.is_err());
+    let err = MevApiClient::sim_bundle(
+        client,
+        SendBundleRequest::default(),
+        SimBundleOverrides::default(),
+    )
+    .await
+    .err()
+    .unwrap();
+    assert!(matches!(err, Error::Call(err) if err.code() == ErrorCode::InvalidParams.code()));
 }
 
 async fn test_basic_net_calls<C>(client: &C)
@@ -270,47 +298,40 @@ where
 
     OtterscanClient::get_api_level(client).await.unwrap();
 
-    assert!(is_unimplemented(
-        OtterscanClient::get_internal_operations(client, tx_hash).await.err().unwrap()
-    ));
-    assert!(is_unimplemented(
-        OtterscanClient::get_transaction_error(client, tx_hash).await.err().unwrap()
-    ));
-    assert!(is_unimplemented(
-        OtterscanClient::trace_transaction(client, tx_hash).await.err().unwrap()
-    ));
+    assert!(OtterscanClient::get_internal_operations(client, tx_hash).await.unwrap().is_none());
+    assert!(OtterscanClient::get_transaction_error(client, tx_hash).await.unwrap().is_none());
+    assert!(OtterscanClient::trace_transaction(client, tx_hash).await.unwrap().is_none());
 
     OtterscanClient::get_block_details(client, block_number).await.unwrap();
 
     OtterscanClient::get_block_details_by_hash(client, block_hash).await.unwrap();
 
-    assert!(is_unimplemented(
-        OtterscanClient::get_block_transactions(client, block_number, page_number, page_size,)
-            .await
-            .err()
-            .unwrap()
-    ));
-    assert!(is_unimplemented(
-        OtterscanClient::search_transactions_before(client, address, block_number, page_size,)
-            .await
-            .err()
-            .unwrap()
-    ));
-    assert!(is_unimplemented(
-        OtterscanClient::search_transactions_after(client, address, block_number, page_size,)
-            .await
-            .err()
-            .unwrap()
-    ));
-    assert!(is_unimplemented(
-        OtterscanClient::get_transaction_by_sender_and_nonce(client, sender, nonce,)
+    OtterscanClient::get_block_transactions(client, block_number, page_number, page_size)
+        .await
+        .err()
+        .unwrap();
+
+    let search_block_number = BlockNumberOrTag::Number(0);
+    let txs = OtterscanClient::search_transactions_before(
+        client,
+        address,
+        search_block_number,
+        page_size,
+    )
+    .await
+    .unwrap();
+    assert!(txs.txs.is_empty() && txs.first_page && txs.last_page);
+    let txs =
+        OtterscanClient::search_transactions_after(client, address, search_block_number, page_size)
             .await
-            .err()
-            .unwrap()
-    ));
-    assert!(is_unimplemented(
-        OtterscanClient::get_contract_creator(client, address).await.err().unwrap()
-    ));
+            .unwrap();
+    assert!(txs.txs.is_empty() && txs.first_page && txs.last_page);
+
+    assert!(OtterscanClient::get_transaction_by_sender_and_nonce(client, sender, nonce)
+        .await
+        .unwrap()
+        .is_none());
+    assert!(OtterscanClient::get_contract_creator(client, address).await.unwrap().is_none());
 }
 
 #[tokio::test(flavor = "multi_thread")]
@@ -403,6 +424,15 @@ async fn test_call_debug_functions_http_and_ws() {
     test_basic_debug_calls(&client).await;
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_call_mev_functions_http() {
+    reth_tracing::init_test_tracing();
+
+    let handle = launch_http(vec![RethRpcModule::Mev]).await;
+    let client = handle.http_client().unwrap();
+    test_basic_mev_calls(&client).await;
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_call_net_functions_http() {
     reth_tracing::init_test_tracing();
@@ -0,0 +1,92 @@
+//! Types for the `debug` state inspection endpoints.
+
+use alloy_primitives::{Address, Bytes, B256, U256};
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, DisplayFromStr};
+use std::collections::BTreeMap;
+
+/// An account of a state dump, see `debug_accountRange` and `debug_dumpBlock`.
+#[serde_as]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DumpAccount {
+    /// The balance of the account, serialized as a decimal string.
+    #[serde_as(as = "DisplayFromStr")]
+    pub balance: U256,
+    /// The nonce of the account.
+    pub nonce: u64,
+    /// The storage root of the account.
+    pub root: B256,
+    /// The hash of the account's code.
+    pub code_hash: B256,
+    /// The code of the account, omitted if code was not requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<Bytes>,
+    /// The storage of the account, omitted if storage was not requested.
+    ///
+    /// The storage is keyed by slot if the address of the account is known, and by hashed slot
+    /// otherwise.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage: Option<BTreeMap<B256, B256>>,
+    /// The address of the account, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<Address>,
+    /// The hashed address of the account.
+    #[serde(default, rename = "key", skip_serializing_if = "Option::is_none")]
+    pub address_hash: Option<B256>,
+}
+
+/// A dump of the state at a block, returned by `debug_dumpBlock`.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct StateDump {
+    /// The state root of the block.
+    pub root: B256,
+    /// The accounts of the state, keyed by address.
+    pub accounts: BTreeMap<Address, DumpAccount>,
+}
+
+/// A page of accounts, returned by `debug_accountRange`.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct AccountRangeResult {
+    /// The state root of the block.
+    pub root: B256,
+    /// The accounts of the page, keyed by hashed address.
+    pub accounts: BTreeMap<B256, DumpAccount>,
+    /// The hashed address to start the next page at, `None` if this is the last page.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next: Option<B256>,
+}
+
+/// A storage slot returned by `debug_storageRangeAt`.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct StorageRangeEntry {
+    /// The storage slot, the preimage of the hashed key the entry is stored under, if known.
+    pub key: Option<B256>,
+    /// The value of the storage slot.
+    pub value: B256,
+}
+
+/// A page of storage slots, returned by `debug_storageRangeAt`.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageRangeResult {
+    /// The storage slots of the page, keyed by the hash of the slot.
+    pub storage: BTreeMap<B256, StorageRangeEntry>,
+    /// The hashed slot to start the next page at, `None` if this is the last page.
+    pub next_key: Option<B256>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_dump_account() {
+        let account =
+            DumpAccount { balance: U256::from(1_000_000_000u64), nonce: 1, ..Default::default() };
+        let json = serde_json::to_value(&account).unwrap();
+        assert_eq!(json["balance"], "1000000000");
+        assert!(json.get("code").is_none());
+        assert_eq!(serde_json::from_value::<DumpAccount>(json).unwrap(), account);
+    }
+}
@@ -11,6 +11,7 @@
 
 mod admin;
 pub mod beacon;
+mod debug;
 mod eth;
 mod mev;
 mod net;
@@ -38,6 +39,7 @@ pub use eth::{
 };
 
 pub use admin::*;
+pub use debug::*;
 pub use mev::*;
 pub use net::*;
 pub use otterscan::*;
@@ -346,11 +346,11 @@ pub struct SimBundleResponse {
     /// The block number of the simulated block.
     pub state_block: U64,
     /// The gas price of the simulated block.
-    pub mev_gas_price: U64,
+    pub mev_gas_price: U256,
     /// The profit of the simulated block.
-    pub profit: U64,
+    pub profit: U256,
     /// The refundable value of the simulated block.
-    pub refundable_value: U64,
+    pub refundable_value: U256,
     /// The gas used by the simulated block.
     pub gas_used: U64,
     /// Logs returned by mev_simBundle.
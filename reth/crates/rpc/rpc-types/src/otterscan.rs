@@ -1,9 +1,11 @@
 use crate::{Block, BlockTransactions, Rich, Transaction, TransactionReceipt};
 use alloy_primitives::{Address, Bytes, U256};
-use serde::{Deserialize, Serialize};
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
 
 /// Operation type enum for `InternalOperation` struct
-#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+///
+/// Serialized as its numeric value, as expected by Otterscan.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum OperationType {
     /// Operation Transfer
     OpTransfer = 0,
@@ -15,67 +17,107 @@ pub enum OperationType {
     OpCreate2 = 3,
 }
 
+impl Serialize for OperationType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(*self as u8)
+    }
+}
+
+impl<'de> Deserialize<'de> for OperationType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match u8::deserialize(deserializer)? {
+            0 => Ok(OperationType::OpTransfer),
+            1 => Ok(OperationType::OpSelfDestruct),
+            2 => Ok(OperationType::OpCreate),
+            3 => Ok(OperationType::OpCreate2),
+            other => Err(D::Error::custom(format!("invalid operation type: {other}"))),
+        }
+    }
+}
+
 /// Custom struct for otterscan `getInternalOperations` RPC response
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct InternalOperation {
-    r#type: OperationType,
-    from: Address,
-    to: Address,
-    value: u128,
+    /// The type of the operation.
+    pub r#type: OperationType,
+    /// The address the value is sent from.
+    pub from: Address,
+    /// The address the value is sent to.
+    pub to: Address,
+    /// The value sent.
+    pub value: U256,
 }
 
 /// Custom struct for otterscan `traceTransaction` RPC response
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct TraceEntry {
-    r#type: String,
-    depth: u32,
-    from: Address,
-    to: Address,
-    value: u128,
-    input: Bytes,
+    /// The type of the call, e.g. `CALL` or `CREATE2`.
+    pub r#type: String,
+    /// The depth of the call in the call tree.
+    pub depth: u32,
+    /// The caller.
+    pub from: Address,
+    /// The callee, or the created contract.
+    pub to: Address,
+    /// The value sent with the call.
+    pub value: U256,
+    /// The input of the call.
+    pub input: Bytes,
 }
 
 /// Internal issuance struct for `BlockDetails` struct
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct InternalIssuance {
-    block_reward: U256,
-    uncle_reward: U256,
-    issuance: U256,
+    /// The block reward.
+    pub block_reward: U256,
+    /// The uncle reward.
+    pub uncle_reward: U256,
+    /// The total issuance.
+    pub issuance: U256,
 }
 
 /// Custom `Block` struct that includes transaction count for Otterscan responses
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct OtsBlock {
+    /// The block.
     #[serde(flatten)]
-    block: Block,
-    transaction_count: usize,
+    pub block: Block,
+    /// The number of transactions in the block.
+    pub transaction_count: usize,
 }
 
 /// Custom struct for otterscan `getBlockDetails` RPC response
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct BlockDetails {
-    block: OtsBlock,
-    issuance: InternalIssuance,
-    total_fees: U256,
+    /// The block.
+    pub block: OtsBlock,
+    /// The issuance of the block.
+    pub issuance: InternalIssuance,
+    /// The total fees paid by the transactions of the block.
+    pub total_fees: U256,
 }
 
 /// Custom transaction receipt struct for otterscan `OtsBlockTransactions` struct
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct OtsTransactionReceipt {
+    /// The transaction receipt.
     #[serde(flatten)]
-    receipt: TransactionReceipt,
-    timestamp: u64,
+    pub receipt: TransactionReceipt,
+    /// The timestamp of the block the transaction is included in.
+    pub timestamp: u64,
 }
 
 /// Custom struct for otterscan `getBlockTransactions` RPC response
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct OtsBlockTransactions {
-    fullblock: OtsBlock,
-    receipts: Vec<OtsTransactionReceipt>,
+    /// The block with the transactions of the requested page.
+    pub fullblock: OtsBlock,
+    /// The receipts of the transactions of the requested page.
+    pub receipts: Vec<OtsTransactionReceipt>,
 }
 
 /// Custom struct for otterscan `searchTransactionsAfter`and `searchTransactionsBefore` RPC
@@ -83,17 +125,23 @@ pub struct OtsBlockTransactions {
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TransactionsWithReceipts {
-    txs: Vec<Transaction>,
-    receipts: Vec<OtsTransactionReceipt>,
-    first_page: bool,
-    last_page: bool,
+    /// The transactions, newest first.
+    pub txs: Vec<Transaction>,
+    /// The receipts of the transactions.
+    pub receipts: Vec<OtsTransactionReceipt>,
+    /// Whether this page contains the newest transactions.
+    pub first_page: bool,
+    /// Whether this page contains the oldest transactions.
+    pub last_page: bool,
 }
 
 /// Custom struct for otterscan `getContractCreator` RPC responses
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct ContractCreator {
-    tx: Transaction,
-    creator: Address,
+    /// The transaction that created the contract.
+    pub tx: Transaction,
+    /// The address that created the contract.
+    pub creator: Address,
 }
 
 impl From<Block> for OtsBlock {
@@ -21,20 +21,20 @@ pub enum ValidateBuilderSubmissionEqualityError {
         /// The actual block hash
         actual: B256,
     },
-    /// Thrown if block hash mismatches
+    /// Thrown if gas limit mismatches
     #[error("incorrect GasLimit {actual}, expected {expected}")]
     IncorrectGasLimit {
         /// The expected gas limit
         expected: u64,
         /// The actual gas limit
-        actual: B256,
+        actual: u64,
     },
-    /// Thrown if block hash mismatches
+    /// Thrown if gas used mismatches
     #[error("incorrect GasUsed {actual}, expected {expected}")]
     IncorrectGasUsed {
         /// The expected gas used
         expected: u64,
         /// The actual gas used
-        actual: B256,
+        actual: u64,
     },
 }
@@ -1,24 +1,30 @@
 use crate::{
     eth::{
         error::{EthApiError, EthResult},
+        pre_block_beacon_root_contract_call,
         revm_utils::{
             inspect, inspect_and_return_db, prepare_call_env, replay_transactions_until, transact,
             EvmOverrides,
         },
         EthTransactions, TransactionSource,
     },
-    result::{internal_rpc_err, ToRpcResult},
+    result::{internal_rpc_err, rpc_error_with_code, ToRpcResult},
     BlockingTaskGuard, EthApiSpec,
 };
 use alloy_rlp::{Decodable, Encodable};
 use async_trait::async_trait;
-use jsonrpsee::core::RpcResult;
+use jsonrpsee::{
+    core::RpcResult,
+    types::{error::METHOD_NOT_FOUND_CODE, ErrorObject},
+};
+use reth_interfaces::provider::ProviderError;
 use reth_primitives::{
-    revm::env::tx_env_with_recovered, Address, Block, BlockId, BlockNumberOrTag, Bytes,
-    TransactionSignedEcRecovered, Withdrawals, B256,
+    keccak256, revm::env::tx_env_with_recovered, Account, Address, Block, BlockId, BlockNumber,
+    BlockNumberOrTag, Bytes, Receipts, TransactionSignedEcRecovered, Withdrawals, B256, U256,
 };
 use reth_provider::{
-    BlockReaderIdExt, ChainSpecProvider, HeaderProvider, StateProviderBox, TransactionVariant,
+    BlockReaderIdExt, BundleStateWithReceipts, ChainSpecProvider, HeaderProvider, StateProvider,
+    StateProviderBox, StateRangeProvider, StateRangeReader, StateRootProvider, TransactionVariant,
 };
 use reth_revm::database::{StateProviderDatabase, SubState};
 use reth_rpc_api::DebugApiServer;
@@ -27,19 +33,31 @@ use reth_rpc_types::{
         BlockTraceResult, FourByteFrame, GethDebugBuiltInTracerType, GethDebugTracerType,
         GethDebugTracingCallOptions, GethDebugTracingOptions, GethTrace, NoopFrame, TraceResult,
     },
-    BlockError, Bundle, CallRequest, RichBlock, StateContext,
+    AccountRangeResult, BlockError, Bundle, CallRequest, DumpAccount, RichBlock, StateContext,
+    StateDump, StorageRangeEntry, StorageRangeResult,
 };
 use revm::{
-    db::CacheDB,
+    db::{states::bundle_state::BundleRetention, AccountState, CacheDB},
     primitives::{db::DatabaseCommit, BlockEnv, CfgEnvWithHandlerCfg, Env, EnvWithHandlerCfg},
+    State,
 };
 use revm_inspectors::tracing::{
     js::{JsInspector, TransactionContext},
     FourByteInspector, TracingInspector, TracingInspectorConfig,
 };
-use std::sync::Arc;
+use std::{collections::BTreeMap, sync::Arc};
 use tokio::sync::{AcquireError, OwnedSemaphorePermit};
 
+/// The maximum number of accounts returned by a single `debug_accountRange` call.
+const ACCOUNT_RANGE_MAX_RESULTS: usize = 256;
+
+/// The maximum number of accounts of the state dumped by `debug_dumpBlock`.
+const DUMP_BLOCK_MAX_ACCOUNTS: usize = 10_000;
+
+/// The maximum number of storage slots of an account dumped by `debug_accountRange` and
+/// `debug_dumpBlock`.
+const DUMP_MAX_STORAGE_SLOTS: usize = 10_000;
+
 /// `debug` API implementation.
 ///
 /// This type provides the functionality for handling `debug` related requests.
@@ -51,8 +69,14 @@ pub struct DebugApi<Provider, Eth> {
 
 impl<Provider, Eth> DebugApi<Provider, Eth> {
     /// Create a new instance of the [DebugApi]
-    pub fn new(provider: Provider, eth: Eth, blocking_task_guard: BlockingTaskGuard) -> Self {
-        let inner = Arc::new(DebugApiInner { provider, eth_api: eth, blocking_task_guard });
+    pub fn new(
+        provider: Provider,
+        eth: Eth,
+        blocking_task_guard: BlockingTaskGuard,
+        state_window: u64,
+    ) -> Self {
+        let inner =
+            Arc::new(DebugApiInner { provider, eth_api: eth, blocking_task_guard, state_window });
         Self { inner }
     }
 }
@@ -61,7 +85,7 @@ impl<Provider, Eth> DebugApi<Provider, Eth> {
 
 impl<Provider, Eth> DebugApi<Provider, Eth>
 where
-    Provider: BlockReaderIdExt + HeaderProvider + ChainSpecProvider + 'static,
+    Provider: BlockReaderIdExt + HeaderProvider + ChainSpecProvider + StateRangeReader + 'static,
     Eth: EthTransactions + 'static,
 {
     /// Acquires a permit to execute a tracing call.
@@ -483,6 +507,382 @@ where
             .await
     }
 
+    /// Returns a page of the accounts in the state after the given block, ordered by hashed
+    /// address and starting at the hashed address `start`.
+    ///
+    /// At most [ACCOUNT_RANGE_MAX_RESULTS] accounts are returned, a `max_results` of zero also
+    /// selects that limit.
+    pub async fn debug_account_range(
+        &self,
+        block_id: BlockId,
+        start: B256,
+        max_results: usize,
+        nocode: bool,
+        nostorage: bool,
+    ) -> EthResult<AccountRangeResult> {
+        let header = self
+            .inner
+            .provider
+            .sealed_header_by_id(block_id)?
+            .ok_or_else(|| EthApiError::UnknownBlockNumber)?;
+        self.ensure_within_state_window(header.number)?;
+        let limit = match max_results {
+            0 => ACCOUNT_RANGE_MAX_RESULTS,
+            max_results => max_results.min(ACCOUNT_RANGE_MAX_RESULTS),
+        };
+
+        let this = self.clone();
+        self.inner
+            .eth_api
+            .spawn_with_state_at_block(header.hash().into(), move |state| {
+                let state_range = this.inner.provider.state_range_at(header.number)?;
+
+                // fetch one more account to determine where the next page starts
+                let mut accounts = state_range.hashed_account_range(start, limit + 1)?;
+                let next = if accounts.len() > limit {
+                    accounts.pop().map(|(hashed_address, _)| hashed_address)
+                } else {
+                    None
+                };
+
+                // the addresses of the hashed accounts are not known
+                let hashed_addresses =
+                    accounts.iter().map(|(hashed_address, _)| *hashed_address).collect::<Vec<_>>();
+                let accounts = accounts
+                    .into_iter()
+                    .map(|(hashed_address, account)| (None, hashed_address, account))
+                    .collect();
+                let accounts = hashed_addresses
+                    .into_iter()
+                    .zip(Self::dump_accounts(&state, &*state_range, accounts, nocode, nostorage)?)
+                    .collect();
+                Ok(AccountRangeResult { root: header.state_root, accounts, next })
+            })
+            .await
+    }
+
+    /// Returns all accounts, including their code and storage, in the state after the given
+    /// block.
+    ///
+    /// This fails if the state has more than [DUMP_BLOCK_MAX_ACCOUNTS] accounts.
+    pub async fn debug_dump_block(&self, block_id: BlockId) -> EthResult<StateDump> {
+        let header = self
+            .inner
+            .provider
+            .sealed_header_by_id(block_id)?
+            .ok_or_else(|| EthApiError::UnknownBlockNumber)?;
+        self.ensure_within_state_window(header.number)?;
+
+        let this = self.clone();
+        self.inner
+            .eth_api
+            .spawn_with_state_at_block(header.hash().into(), move |state| {
+                let state_range = this.inner.provider.state_range_at(header.number)?;
+
+                // fetch one more account to determine whether the state exceeds the limit
+                let accounts =
+                    state_range.account_range(Address::ZERO, DUMP_BLOCK_MAX_ACCOUNTS + 1)?;
+                if accounts.len() > DUMP_BLOCK_MAX_ACCOUNTS {
+                    return Err(EthApiError::InvalidParams(format!(
+                        "state has more than {DUMP_BLOCK_MAX_ACCOUNTS} accounts, use debug_accountRange"
+                    )))
+                }
+
+                let addresses = accounts.iter().map(|(address, _)| *address).collect::<Vec<_>>();
+                let accounts = accounts
+                    .into_iter()
+                    .map(|(address, account)| (Some(address), keccak256(address), account))
+                    .collect();
+                let accounts = addresses
+                    .into_iter()
+                    .zip(Self::dump_accounts(&state, &*state_range, accounts, false, false)?)
+                    .collect();
+                Ok(StateDump { root: header.state_root, accounts })
+            })
+            .await
+    }
+
+    /// Returns a page of the storage of the given account, ordered by hashed slot and starting at
+    /// the hashed slot `start`, in the state after executing the first `tx_index` transactions of
+    /// the given block.
+    ///
+    /// The slot of an entry is only known if it was touched by the replayed transactions, since
+    /// reth doesn't store the preimages of hashed slots.
+    pub async fn debug_storage_range_at(
+        &self,
+        block_hash: B256,
+        tx_index: usize,
+        address: Address,
+        start: B256,
+        max_results: usize,
+    ) -> EthResult<StorageRangeResult> {
+        let block_id = BlockId::Hash(block_hash.into());
+        let ((cfg, block_env, _), block) = futures::try_join!(
+            self.inner.eth_api.evm_env_at(block_id),
+            self.inner.eth_api.block_by_id_with_senders(block_id),
+        )?;
+        let block = block.ok_or_else(|| EthApiError::UnknownBlockNumber)?;
+        if tx_index > block.body.len() {
+            return Err(EthApiError::InvalidParams(format!(
+                "transaction index {tx_index} out of range for block {block_hash}"
+            )))
+        }
+
+        // the transactions are replayed on top of the state at the parent block
+        let parent = block.number.saturating_sub(1);
+        self.ensure_within_state_window(parent)?;
+        let chain_spec = self.inner.provider.chain_spec();
+
+        let this = self.clone();
+        self.inner
+            .eth_api
+            .spawn_with_state_at_block(parent.into(), move |state| {
+                let mut db = CacheDB::new(StateProviderDatabase::new(state));
+                pre_block_beacon_root_contract_call(
+                    &mut db,
+                    &chain_spec,
+                    block.number,
+                    &cfg,
+                    &block_env,
+                    block.parent_beacon_block_root,
+                )?;
+                for tx in block.into_transactions_ecrecovered().take(tx_index) {
+                    let env = EnvWithHandlerCfg::new(
+                        Env::boxed(
+                            cfg.cfg_env.clone(),
+                            block_env.clone(),
+                            tx_env_with_recovered(&tx),
+                        ),
+                        cfg.handler_cfg.spec_id,
+                    );
+                    let (res, _) = transact(&mut db, env)?;
+                    db.commit(res.state);
+                }
+
+                // the slots touched by the replayed transactions and whether the storage of the
+                // account was wiped, in which case the persisted slots no longer apply
+                let (changed, wiped) = db
+                    .accounts
+                    .get(&address)
+                    .map(|account| {
+                        let wiped = matches!(
+                            account.account_state,
+                            AccountState::StorageCleared | AccountState::NotExisting
+                        );
+                        (account.storage.clone(), wiped)
+                    })
+                    .unwrap_or_default();
+
+                // fetch enough persisted slots to fill the page even if all changed slots were
+                // cleared, plus one to determine where the next page starts
+                let fetch_limit = max_results.saturating_add(1).saturating_add(changed.len());
+                let persisted = if wiped {
+                    Vec::new()
+                } else {
+                    this.inner.provider.state_range_at(parent)?.hashed_storage_range(
+                        keccak256(address),
+                        start,
+                        fetch_limit,
+                    )?
+                };
+
+                // if the persisted slots were truncated, changed slots past the last fetched one
+                // can't be ordered against the slots that were not fetched
+                let last_key = (persisted.len() == fetch_limit)
+                    .then(|| persisted.last().map(|entry| entry.key))
+                    .flatten();
+                let mut slots = persisted
+                    .into_iter()
+                    .map(|entry| (entry.key, (None, entry.value)))
+                    .collect::<BTreeMap<_, _>>();
+                for (key, value) in changed {
+                    let key = B256::new(key.to_be_bytes());
+                    let hashed_key = keccak256(key);
+                    if hashed_key < start ||
+                        last_key.map_or(false, |last_key| hashed_key > last_key)
+                    {
+                        continue
+                    }
+                    if value == U256::ZERO {
+                        slots.remove(&hashed_key);
+                    } else {
+                        slots.insert(hashed_key, (Some(key), value));
+                    }
+                }
+
+                let mut slots = slots.into_iter();
+                let storage = slots
+                    .by_ref()
+                    .take(max_results)
+                    .map(|(hashed_key, (key, value))| {
+                        (
+                            hashed_key,
+                            StorageRangeEntry { key, value: B256::new(value.to_be_bytes()) },
+                        )
+                    })
+                    .collect();
+                let next_key = slots.next().map(|(hashed_key, _)| hashed_key);
+                Ok(StorageRangeResult { storage, next_key })
+            })
+            .await
+    }
+
+    /// Executes the given block and returns the state root after each transaction.
+    pub async fn debug_intermediate_roots(&self, block_hash: B256) -> EthResult<Vec<B256>> {
+        let block_id = BlockId::Hash(block_hash.into());
+        let ((cfg, block_env, _), block) = futures::try_join!(
+            self.inner.eth_api.evm_env_at(block_id),
+            self.inner.eth_api.block_by_id_with_senders(block_id),
+        )?;
+        let block = block.ok_or_else(|| EthApiError::UnknownBlockNumber)?;
+
+        // the transactions are executed on top of the state at the parent block
+        let parent = block.number.saturating_sub(1);
+        let chain_spec = self.inner.provider.chain_spec();
+
+        self.inner
+            .eth_api
+            .spawn_with_state_at_block(parent.into(), move |state| {
+                let mut db = State::builder()
+                    .with_database(Box::new(StateProviderDatabase::new(&state)))
+                    .with_bundle_update()
+                    .build();
+                pre_block_beacon_root_contract_call(
+                    &mut db,
+                    &chain_spec,
+                    block.number,
+                    &cfg,
+                    &block_env,
+                    block.parent_beacon_block_root,
+                )?;
+
+                let block_number = block.number;
+                let mut roots = Vec::with_capacity(block.body.len());
+                for tx in block.into_transactions_ecrecovered() {
+                    let env = EnvWithHandlerCfg::new(
+                        Env::boxed(
+                            cfg.cfg_env.clone(),
+                            block_env.clone(),
+                            tx_env_with_recovered(&tx),
+                        ),
+                        cfg.handler_cfg.spec_id,
+                    );
+                    let (res, _) = transact(&mut db, env)?;
+                    db.commit(res.state);
+                    db.merge_transitions(BundleRetention::PlainState);
+
+                    let bundle = BundleStateWithReceipts::new(
+                        db.bundle_state.clone(),
+                        Receipts::default(),
+                        block_number,
+                    );
+                    roots.push(state.state_root(&bundle)?);
+                }
+                Ok(roots)
+            })
+            .await
+    }
+
+    /// Returns an error if the block is further behind the tip than the configured state window.
+    ///
+    /// The state inspection methods revert the state between the tip and the block in memory.
+    fn ensure_within_state_window(&self, block: BlockNumber) -> EthResult<()> {
+        let best = self.inner.provider.best_block_number()?;
+        if best.saturating_sub(block) > self.inner.state_window {
+            return Err(EthApiError::ExceedsMaxStateWindow)
+        }
+        Ok(())
+    }
+
+    /// Returns the accounts modified in the given block or, if `end` is set, in the blocks after
+    /// `start` up to and including `end`.
+    fn modified_accounts(
+        &self,
+        start: BlockNumber,
+        end: Option<BlockNumber>,
+    ) -> EthResult<Vec<Address>> {
+        let range = match end {
+            Some(end) if start >= end => {
+                return Err(EthApiError::InvalidParams(format!(
+                    "start block ({start}) must be less than end block ({end})"
+                )))
+            }
+            Some(end) => start + 1..=end,
+            None => start..=start,
+        };
+
+        let best = self.inner.provider.best_block_number()?;
+        if *range.end() > best {
+            return Err(EthApiError::UnknownBlockNumber)
+        }
+        let lowest = self.inner.provider.lowest_available_state_block()?;
+        if lowest > 0 && *range.start() <= lowest {
+            return Err(ProviderError::StateAtBlockPruned(*range.start()).into())
+        }
+
+        Ok(self.inner.provider.changed_addresses_with_range(range)?.into_iter().collect())
+    }
+
+    /// Converts the given accounts of the state after a block to [DumpAccount]s, in the same order.
+    ///
+    /// The storage of an account is keyed by slot if its address is known, and by hashed slot
+    /// otherwise. This fails if an account has more than [DUMP_MAX_STORAGE_SLOTS] storage slots.
+    ///
+    /// Caution: this is blocking and should be performed on a blocking task.
+    fn dump_accounts(
+        state: &StateProviderBox,
+        state_range: &dyn StateRangeProvider,
+        accounts: Vec<(Option<Address>, B256, Account)>,
+        nocode: bool,
+        nostorage: bool,
+    ) -> EthResult<Vec<DumpAccount>> {
+        let hashed_addresses =
+            accounts.iter().map(|(_, hashed_address, _)| *hashed_address).collect::<Vec<_>>();
+        let roots = state_range.storage_roots(&hashed_addresses)?;
+
+        let mut dump = Vec::with_capacity(accounts.len());
+        for ((address, hashed_address, account), root) in accounts.into_iter().zip(roots) {
+            let code = match account.bytecode_hash {
+                Some(code_hash) if !nocode => {
+                    state.bytecode_by_hash(code_hash)?.map(|code| code.original_bytes())
+                }
+                _ => None,
+            };
+            let storage = if nostorage {
+                None
+            } else {
+                // fetch one more slot to determine whether the storage exceeds the limit
+                let limit = DUMP_MAX_STORAGE_SLOTS + 1;
+                let storage = match address {
+                    Some(address) => state_range.storage_range(address, B256::ZERO, limit)?,
+                    None => state_range.hashed_storage_range(hashed_address, B256::ZERO, limit)?,
+                };
+                if storage.len() > DUMP_MAX_STORAGE_SLOTS {
+                    return Err(EthApiError::InvalidParams(format!(
+                        "account {hashed_address} has more than {DUMP_MAX_STORAGE_SLOTS} storage slots, use debug_storageRangeAt"
+                    )))
+                }
+                let storage = storage
+                    .into_iter()
+                    .map(|entry| (entry.key, B256::new(entry.value.to_be_bytes())))
+                    .collect();
+                Some(storage)
+            };
+
+            dump.push(DumpAccount {
+                balance: account.balance,
+                nonce: account.nonce,
+                root,
+                code_hash: account.get_bytecode_hash(),
+                code,
+                storage,
+                address,
+                address_hash: Some(hashed_address),
+            });
+        }
+        Ok(dump)
+    }
+
     /// Executes the configured transaction with the environment on the given database.
     ///
     /// Returns the trace frame and the state that got updated after executing the transaction.
@@ -583,7 +983,7 @@ where
 #[async_trait]
 impl<Provider, Eth> DebugApiServer for DebugApi<Provider, Eth>
 where
-    Provider: BlockReaderIdExt + HeaderProvider + ChainSpecProvider + 'static,
+    Provider: BlockReaderIdExt + HeaderProvider + ChainSpecProvider + StateRangeReader + 'static,
     Eth: EthApiSpec + 'static,
 {
     /// Handler for `debug_getRawHeader`
@@ -741,217 +1141,303 @@ where
     }
 
     async fn debug_backtrace_at(&self, _location: &str) -> RpcResult<()> {
-        Ok(())
+        Err(method_not_supported("backtraceAt"))
     }
 
+    /// Handler for `debug_accountRange`
     async fn debug_account_range(
         &self,
-        _block_number: BlockNumberOrTag,
-        _start: Bytes,
-        _max_results: u64,
-        _nocode: bool,
-        _nostorage: bool,
+        block_number: BlockNumberOrTag,
+        start: Bytes,
+        max_results: u64,
+        nocode: bool,
+        nostorage: bool,
         _incompletes: bool,
-    ) -> RpcResult<()> {
-        Ok(())
+    ) -> RpcResult<AccountRangeResult> {
+        if start.len() > 32 {
+            return Err(EthApiError::InvalidParams(
+                "start must be a hashed address prefix of at most 32 bytes".to_string(),
+            )
+            .into())
+        }
+        let mut start_key = B256::ZERO;
+        start_key[..start.len()].copy_from_slice(&start);
+
+        let max_results = usize::try_from(max_results).unwrap_or(usize::MAX);
+        Ok(DebugApi::debug_account_range(
+            self,
+            block_number.into(),
+            start_key,
+            max_results,
+            nocode,
+            nostorage,
+        )
+        .await?)
     }
 
     async fn debug_block_profile(&self, _file: String, _seconds: u64) -> RpcResult<()> {
-        Ok(())
+        Err(method_not_supported("blockProfile"))
     }
 
     async fn debug_chaindb_compact(&self) -> RpcResult<()> {
-        Ok(())
+        Err(method_not_supported("chaindbCompact"))
     }
 
     async fn debug_chaindb_property(&self, _property: String) -> RpcResult<()> {
-        Ok(())
+        Err(method_not_supported("chaindbProperty"))
     }
 
     async fn debug_cpu_profile(&self, _file: String, _seconds: u64) -> RpcResult<()> {
-        Ok(())
+        Err(method_not_supported("cpuProfile"))
     }
 
     async fn debug_db_ancient(&self, _kind: String, _number: u64) -> RpcResult<()> {
-        Ok(())
+        Err(method_not_supported("dbAncient"))
     }
 
     async fn debug_db_ancients(&self) -> RpcResult<()> {
-        Ok(())
+        Err(method_not_supported("dbAncients"))
     }
 
     async fn debug_db_get(&self, _key: String) -> RpcResult<()> {
-        Ok(())
+        Err(method_not_supported("dbGet"))
     }
 
-    async fn debug_dump_block(&self, _number: BlockId) -> RpcResult<()> {
-        Ok(())
+    /// Handler for `debug_dumpBlock`
+    async fn debug_dump_block(&self, number: BlockId) -> RpcResult<StateDump> {
+        let _permit = self.acquire_trace_permit().await;
+        Ok(DebugApi::debug_dump_block(self, number).await?)
     }
 
     async fn debug_free_os_memory(&self) -> RpcResult<()> {
-        Ok(())
+        Err(method_not_supported("freeOSMemory"))
     }
 
     async fn debug_freeze_client(&self, _node: String) -> RpcResult<()> {
-        Ok(())
+        Err(method_not_supported("freezeClient"))
     }
 
     async fn debug_gc_stats(&self) -> RpcResult<()> {
-        Ok(())
+        Err(method_not_supported("gcStats"))
     }
 
+    /// Handler for `debug_getAccessibleState`
     async fn debug_get_accessible_state(
         &self,
-        _from: BlockNumberOrTag,
-        _to: BlockNumberOrTag,
-    ) -> RpcResult<()> {
-        Ok(())
+        from: BlockNumberOrTag,
+        to: BlockNumberOrTag,
+    ) -> RpcResult<u64> {
+        let provider = &self.inner.provider;
+        let from = provider
+            .convert_block_number(from)
+            .to_rpc_result()?
+            .ok_or_else(|| EthApiError::UnknownBlockNumber)?;
+        let to = provider
+            .convert_block_number(to)
+            .to_rpc_result()?
+            .ok_or_else(|| EthApiError::UnknownBlockNumber)?;
+        let best = provider.best_block_number().to_rpc_result()?;
+        let lowest = provider.lowest_available_state_block().to_rpc_result()?;
+
+        // the state is available for all blocks from the lowest available block up to the tip,
+        // so the first block with state in the searched direction is either bound of that range
+        let accessible = if from <= to {
+            Some(from.max(lowest)).filter(|block| *block <= to.min(best))
+        } else {
+            Some(from.min(best)).filter(|block| *block >= to.max(lowest))
+        };
+        accessible.ok_or_else(|| internal_rpc_err("no state found"))
     }
 
+    /// Handler for `debug_getModifiedAccountsByHash`
     async fn debug_get_modified_accounts_by_hash(
         &self,
-        _start_hash: B256,
-        _end_hash: B256,
-    ) -> RpcResult<()> {
-        Ok(())
+        start_hash: B256,
+        end_hash: Option<B256>,
+    ) -> RpcResult<Vec<Address>> {
+        let provider = &self.inner.provider;
+        let start = provider
+            .block_number(start_hash)
+            .to_rpc_result()?
+            .ok_or_else(|| EthApiError::UnknownBlockNumber)?;
+        let end = match end_hash {
+            Some(end_hash) => Some(
+                provider
+                    .block_number(end_hash)
+                    .to_rpc_result()?
+                    .ok_or_else(|| EthApiError::UnknownBlockNumber)?,
+            ),
+            None => None,
+        };
+        Ok(self.modified_accounts(start, end)?)
     }
 
+    /// Handler for `debug_getModifiedAccountsByNumber`
     async fn debug_get_modified_accounts_by_number(
         &self,
-        _start_number: u64,
-        _end_number: u64,
-    ) -> RpcResult<()> {
-        Ok(())
+        start_number: u64,
+        end_number: Option<u64>,
+    ) -> RpcResult<Vec<Address>> {
+        Ok(self.modified_accounts(start_number, end_number)?)
     }
 
     async fn debug_go_trace(&self, _file: String, _seconds: u64) -> RpcResult<()> {
-        Ok(())
+        Err(method_not_supported("goTrace"))
     }
 
+    /// Handler for `debug_intermediateRoots`
     async fn debug_intermediate_roots(
         &self,
-        _block_hash: B256,
+        block_hash: B256,
         _opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()> {
-        Ok(())
+    ) -> RpcResult<Vec<B256>> {
+        let _permit = self.acquire_trace_permit().await;
+        Ok(DebugApi::debug_intermediate_roots(self, block_hash).await?)
     }
 
     async fn debug_mem_stats(&self) -> RpcResult<()> {
-        Ok(())
+        Err(method_not_supported("memStats"))
     }
 
     async fn debug_mutex_profile(&self, _file: String, _nsec: u64) -> RpcResult<()> {
-        Ok(())
+        Err(method_not_supported("mutexProfile"))
     }
 
-    async fn debug_preimage(&self, _hash: B256) -> RpcResult<()> {
-        Ok(())
+    async fn debug_preimage(&self, _hash: B256) -> RpcResult<Bytes> {
+        Err(method_not_supported("preimage"))
     }
 
-    async fn debug_print_block(&self, _number: u64) -> RpcResult<()> {
-        Ok(())
+    /// Handler for `debug_printBlock`
+    async fn debug_print_block(&self, number: u64) -> RpcResult<String> {
+        let block = self
+            .inner
+            .provider
+            .block_by_number(number)
+            .to_rpc_result()?
+            .ok_or_else(|| EthApiError::UnknownBlockNumber)?;
+        Ok(format!("{block:#?}"))
     }
 
     async fn debug_seed_hash(&self, _number: u64) -> RpcResult<B256> {
-        Ok(Default::default())
+        Err(method_not_supported("seedHash"))
     }
 
     async fn debug_set_block_profile_rate(&self, _rate: u64) -> RpcResult<()> {
-        Ok(())
+        Err(method_not_supported("setBlockProfileRate"))
     }
 
     async fn debug_set_gc_percent(&self, _v: i32) -> RpcResult<()> {
-        Ok(())
+        Err(method_not_supported("setGCPercent"))
     }
 
     async fn debug_set_head(&self, _number: u64) -> RpcResult<()> {
-        Ok(())
+        Err(method_not_supported("setHead"))
     }
 
     async fn debug_set_mutex_profile_fraction(&self, _rate: i32) -> RpcResult<()> {
-        Ok(())
+        Err(method_not_supported("setMutexProfileFraction"))
     }
 
     async fn debug_set_trie_flush_interval(&self, _interval: String) -> RpcResult<()> {
-        Ok(())
+        Err(method_not_supported("setTrieFlushInterval"))
     }
 
     async fn debug_stacks(&self) -> RpcResult<()> {
-        Ok(())
+        Err(method_not_supported("stacks"))
     }
 
     async fn debug_standard_trace_bad_block_to_file(
         &self,
         _block: BlockNumberOrTag,
         _opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()> {
-        Ok(())
+    ) -> RpcResult<Vec<String>> {
+        Err(method_not_supported("standardTraceBadBlockToFile"))
     }
 
     async fn debug_standard_trace_block_to_file(
         &self,
         _block: BlockNumberOrTag,
         _opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()> {
-        Ok(())
+    ) -> RpcResult<Vec<String>> {
+        Err(method_not_supported("standardTraceBlockToFile"))
     }
 
     async fn debug_start_cpu_profile(&self, _file: String) -> RpcResult<()> {
-        Ok(())
+        Err(method_not_supported("startCPUProfile"))
     }
 
     async fn debug_start_go_trace(&self, _file: String) -> RpcResult<()> {
-        Ok(())
+        Err(method_not_supported("startGoTrace"))
     }
 
     async fn debug_stop_cpu_profile(&self) -> RpcResult<()> {
-        Ok(())
+        Err(method_not_supported("stopCPUProfile"))
     }
 
     async fn debug_stop_go_trace(&self) -> RpcResult<()> {
-        Ok(())
+        Err(method_not_supported("stopGoTrace"))
     }
 
+    /// Handler for `debug_storageRangeAt`
     async fn debug_storage_range_at(
         &self,
-        _block_hash: B256,
-        _tx_idx: usize,
-        _contract_address: Address,
-        _key_start: B256,
-        _max_result: u64,
-    ) -> RpcResult<()> {
-        Ok(())
+        block_hash: B256,
+        tx_idx: usize,
+        contract_address: Address,
+        key_start: B256,
+        max_result: u64,
+    ) -> RpcResult<StorageRangeResult> {
+        let _permit = self.acquire_trace_permit().await;
+        let max_result = usize::try_from(max_result).unwrap_or(usize::MAX);
+        Ok(DebugApi::debug_storage_range_at(
+            self,
+            block_hash,
+            tx_idx,
+            contract_address,
+            key_start,
+            max_result,
+        )
+        .await?)
     }
 
     async fn debug_trace_bad_block(
         &self,
         _block_hash: B256,
         _opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()> {
-        Ok(())
+    ) -> RpcResult<Vec<TraceResult>> {
+        Err(method_not_supported("traceBadBlock"))
     }
 
     async fn debug_verbosity(&self, _level: usize) -> RpcResult<()> {
-        Ok(())
+        Err(method_not_supported("verbosity"))
     }
 
     async fn debug_vmodule(&self, _pattern: String) -> RpcResult<()> {
-        Ok(())
+        Err(method_not_supported("vmodule"))
     }
 
     async fn debug_write_block_profile(&self, _file: String) -> RpcResult<()> {
-        Ok(())
+        Err(method_not_supported("writeBlockProfile"))
     }
 
     async fn debug_write_mem_profile(&self, _file: String) -> RpcResult<()> {
-        Ok(())
+        Err(method_not_supported("writeMemProfile"))
     }
 
     async fn debug_write_mutex_profile(&self, _file: String) -> RpcResult<()> {
-        Ok(())
+        Err(method_not_supported("writeMutexProfile"))
     }
 }
 
+/// Returns the error for `debug` methods that depend on geth's runtime or database layout and
+/// are therefore not supported by reth.
+fn method_not_supported(method: &str) -> ErrorObject<'static> {
+    rpc_error_with_code(
+        METHOD_NOT_FOUND_CODE,
+        format!("the method debug_{method} does not exist/is not available"),
+    )
+}
+
 impl<Provider, Eth> std::fmt::Debug for DebugApi<Provider, Eth> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("DebugApi").finish_non_exhaustive()
@@ -971,4 +1457,7 @@ struct DebugApiInner<Provider, Eth> {
     eth_api: Eth,
     // restrict the number of concurrent calls to blocking calls
     blocking_task_guard: BlockingTaskGuard,
+    /// The maximum number of blocks behind the tip whose state is served by the state inspection
+    /// methods.
+    state_window: u64,
 }
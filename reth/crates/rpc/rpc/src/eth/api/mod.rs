@@ -44,7 +44,7 @@ pub(crate) mod fee_history;
 mod fees;
 #[cfg(feature = "optimism")]
 mod optimism;
-mod pending_block;
+pub(crate) mod pending_block;
 mod server;
 mod sign;
 mod state;
@@ -103,6 +103,7 @@ where
         eth_cache: EthStateCache,
         gas_oracle: GasPriceOracle<Provider>,
         gas_cap: impl Into<GasCap>,
+        eth_proof_window: u64,
         blocking_task_pool: BlockingTaskPool,
         fee_history_cache: FeeHistoryCache,
         evm_config: EvmConfig,
@@ -114,6 +115,7 @@ where
             eth_cache,
             gas_oracle,
             gas_cap.into().into(),
+            eth_proof_window,
             Box::<TokioTaskExecutor>::default(),
             blocking_task_pool,
             fee_history_cache,
@@ -130,6 +132,7 @@ where
         eth_cache: EthStateCache,
         gas_oracle: GasPriceOracle<Provider>,
         gas_cap: u64,
+        eth_proof_window: u64,
         task_spawner: Box<dyn TaskSpawner>,
         blocking_task_pool: BlockingTaskPool,
         fee_history_cache: FeeHistoryCache,
@@ -151,6 +154,7 @@ where
             eth_cache,
             gas_oracle,
             gas_cap,
+            eth_proof_window,
             starting_block: U256::from(latest_block),
             task_spawner,
             pending_block: Default::default(),
@@ -199,6 +203,11 @@ where
         self.inner.gas_cap
     }
 
+    /// Returns the maximum number of blocks behind the tip for which `eth_getProof` is served.
+    pub fn eth_proof_window(&self) -> u64 {
+        self.inner.eth_proof_window
+    }
+
     /// Returns the inner `Provider`
     pub fn provider(&self) -> &Provider {
         &self.inner.provider
@@ -443,6 +452,16 @@ where
 /// more complex calls.
 pub const RPC_DEFAULT_GAS_CAP: GasCap = GasCap(50_000_000);
 
+/// The default maximum number of blocks into the past for which `eth_getProof` is served.
+///
+/// By default only proofs for the latest block are generated.
+pub const DEFAULT_ETH_PROOF_WINDOW: u64 = 0;
+
+/// The upper bound for the configurable `eth_getProof` window, roughly 28 days of mainnet blocks.
+///
+/// Proofs for older blocks require reverting the state of the entire window in memory.
+pub const MAX_ETH_PROOF_WINDOW: u64 = 28 * 24 * 60 * 60 / 12;
+
 /// The wrapper type for gas limit
 #[derive(Debug, Clone, Copy)]
 pub struct GasCap(u64);
@@ -481,6 +500,8 @@ struct EthApiInner<Provider, Pool, Network, EvmConfig> {
     gas_oracle: GasPriceOracle<Provider>,
     /// Maximum gas limit for `eth_call` and call tracing RPC methods.
     gas_cap: u64,
+    /// The maximum number of blocks into the past for generating state proofs.
+    eth_proof_window: u64,
     /// The block number at which the node started
     starting_block: U256,
     /// The type that can spawn tasks which would otherwise block.
@@ -397,7 +397,7 @@ mod tests {
     use crate::{
         eth::{
             cache::EthStateCache, gas_oracle::GasPriceOracle, FeeHistoryCache,
-            FeeHistoryCacheConfig,
+            FeeHistoryCacheConfig, DEFAULT_ETH_PROOF_WINDOW,
         },
         BlockingTaskPool, EthApi,
     };
@@ -441,6 +441,7 @@ mod tests {
             cache.clone(),
             GasPriceOracle::new(provider.clone(), Default::default(), cache.clone()),
             ETHEREUM_BLOCK_GAS_LIMIT,
+            DEFAULT_ETH_PROOF_WINDOW,
             BlockingTaskPool::build().expect("failed to build tracing pool"),
             fee_history_cache,
             evm_config,
@@ -9,7 +9,8 @@ use reth_primitives::{
     serde_helper::JsonStorageKey, Address, BlockId, BlockNumberOrTag, Bytes, B256, U256,
 };
 use reth_provider::{
-    BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, StateProvider, StateProviderFactory,
+    BlockIdReader, BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, StateProvider,
+    StateProviderFactory,
 };
 use reth_rpc_types::EIP1186AccountProofResponse;
 use reth_rpc_types_compat::proof::from_primitive_account_proof;
@@ -94,21 +95,22 @@ where
         let chain_info = self.provider().chain_info()?;
         let block_id = block_id.unwrap_or(BlockId::Number(BlockNumberOrTag::Latest));
 
-        // if we are trying to create a proof for the latest block, but have a BlockId as input
-        // that is not BlockNumberOrTag::Latest, then we need to figure out whether or not the
-        // BlockId corresponds to the latest block
-        let is_latest_block = match block_id {
-            BlockId::Number(BlockNumberOrTag::Number(num)) => num == chain_info.best_number,
-            BlockId::Hash(hash) => hash == chain_info.best_hash.into(),
-            BlockId::Number(BlockNumberOrTag::Latest) => true,
-            _ => false,
-        };
-
-        // TODO: remove when HistoricalStateProviderRef::proof is implemented
-        if !is_latest_block {
+        // the pending block has no persisted state that could be reverted to generate a proof
+        if matches!(block_id, BlockId::Number(BlockNumberOrTag::Pending)) {
             return Err(EthApiError::InvalidBlockRange)
         }
 
+        // historical proofs are generated by reverting the state between the tip and the target
+        // block in memory, so this is only permitted within the configured window
+        let block_number = self
+            .provider()
+            .block_number_for_id(block_id)?
+            .ok_or(EthApiError::UnknownBlockNumber)?;
+        let max_window = self.eth_proof_window();
+        if chain_info.best_number.saturating_sub(block_number) > max_window {
+            return Err(EthApiError::ExceedsMaxProofWindow)
+        }
+
         let this = self.clone();
         self.inner
             .blocking_task_pool
@@ -129,7 +131,7 @@ mod tests {
     use crate::{
         eth::{
             cache::EthStateCache, gas_oracle::GasPriceOracle, FeeHistoryCache,
-            FeeHistoryCacheConfig,
+            FeeHistoryCacheConfig, DEFAULT_ETH_PROOF_WINDOW,
         },
         BlockingTaskPool,
     };
@@ -153,6 +155,7 @@ mod tests {
             cache.clone(),
             GasPriceOracle::new(NoopProvider::default(), Default::default(), cache.clone()),
             ETHEREUM_BLOCK_GAS_LIMIT,
+            DEFAULT_ETH_PROOF_WINDOW,
             BlockingTaskPool::build().expect("failed to build tracing pool"),
             FeeHistoryCache::new(cache.clone(), FeeHistoryCacheConfig::default()),
             evm_config,
@@ -177,6 +180,7 @@ mod tests {
             cache.clone(),
             GasPriceOracle::new(mock_provider.clone(), Default::default(), cache.clone()),
             ETHEREUM_BLOCK_GAS_LIMIT,
+            DEFAULT_ETH_PROOF_WINDOW,
             BlockingTaskPool::build().expect("failed to build tracing pool"),
             FeeHistoryCache::new(cache.clone(), FeeHistoryCacheConfig::default()),
             evm_config,
@@ -18,8 +18,8 @@ use reth_node_api::ConfigureEvmEnv;
 use reth_primitives::{
     eip4844::calc_blob_gasprice,
     revm::env::{fill_block_env_with_coinbase, tx_env_with_recovered},
-    Address, BlockId, BlockNumberOrTag, Bytes, FromRecoveredPooledTransaction, Header,
-    IntoRecoveredTransaction, Receipt, SealedBlock, SealedBlockWithSenders,
+    Address, BaseFeeParams, BlockId, BlockNumberOrTag, Bytes, FromRecoveredPooledTransaction,
+    Header, IntoRecoveredTransaction, Receipt, SealedBlock, SealedBlockWithSenders,
     TransactionKind::{Call, Create},
     TransactionMeta, TransactionSigned, TransactionSignedEcRecovered, B256, U128, U256, U64,
 };
@@ -87,6 +87,9 @@ pub trait EthTransactions: Send + Sync {
     /// Returns default gas limit to use for `eth_call` and tracing RPC methods.
     fn call_gas_limit(&self) -> u64;
 
+    /// Returns the base fee parameters of the chain that apply at the given timestamp.
+    fn base_fee_params(&self, timestamp: u64) -> BaseFeeParams;
+
     /// Returns the state at the given [BlockId]
     fn state_at(&self, at: BlockId) -> EthResult<StateProviderBox>;
 
@@ -347,6 +350,10 @@ where
         self.inner.gas_cap
     }
 
+    fn base_fee_params(&self, timestamp: u64) -> BaseFeeParams {
+        self.provider().chain_spec().base_fee_params(timestamp)
+    }
+
     fn state_at(&self, at: BlockId) -> EthResult<StateProviderBox> {
         self.state_at_block_id(at)
     }
@@ -1305,7 +1312,7 @@ mod tests {
     use crate::{
         eth::{
             cache::EthStateCache, gas_oracle::GasPriceOracle, FeeHistoryCache,
-            FeeHistoryCacheConfig,
+            FeeHistoryCacheConfig, DEFAULT_ETH_PROOF_WINDOW,
         },
         BlockingTaskPool, EthApi,
     };
@@ -1333,6 +1340,7 @@ mod tests {
             cache.clone(),
             GasPriceOracle::new(noop_provider, Default::default(), cache.clone()),
             ETHEREUM_BLOCK_GAS_LIMIT,
+            DEFAULT_ETH_PROOF_WINDOW,
             BlockingTaskPool::build().expect("failed to build tracing pool"),
             fee_history_cache,
             evm_config,
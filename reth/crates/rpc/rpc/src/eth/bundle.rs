@@ -36,6 +36,16 @@ impl<Eth> EthBundle<Eth> {
     pub fn new(eth_api: Eth, blocking_task_guard: BlockingTaskGuard) -> Self {
         Self { inner: Arc::new(EthBundleInner { eth_api, blocking_task_guard }) }
     }
+
+    /// Returns the `eth` API the bundles are simulated with.
+    pub(crate) fn eth_api(&self) -> &Eth {
+        &self.inner.eth_api
+    }
+
+    /// Returns the guard that restricts the number of concurrent simulations.
+    pub(crate) fn blocking_task_guard(&self) -> &BlockingTaskGuard {
+        &self.inner.blocking_task_guard
+    }
 }
 
 impl<Eth> EthBundle<Eth>
@@ -193,8 +203,7 @@ where
 struct EthBundleInner<Eth> {
     /// Access to commonly used code of the `eth` namespace
     eth_api: Eth,
-    // restrict the number of concurrent tracing calls.
-    #[allow(dead_code)]
+    // restrict the number of concurrent simulations.
     blocking_task_guard: BlockingTaskGuard,
 }
 
@@ -219,4 +228,13 @@ pub enum EthBundleError {
     /// Thrown if the bundle does not contain a block number, or block number is 0.
     #[error("bundle missing blockNumber")]
     BundleMissingBlockNumber,
+    /// Thrown if the bundle references transactions by hash, which can't be simulated.
+    #[error("bundle is not fully matched")]
+    BundleNotFullyMatched,
+    /// Thrown if a refund of the bundle refers to a transaction that is not part of the bundle.
+    #[error("invalid refund body index {0}")]
+    InvalidRefundIndex(usize),
+    /// Thrown if the bundle is not valid for the simulated block.
+    #[error("bundle is not valid for block {0}")]
+    BlockOutsideInclusionRange(u64),
 }
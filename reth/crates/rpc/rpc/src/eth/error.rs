@@ -11,8 +11,8 @@ use reth_primitives::{revm_primitives::InvalidHeader, Address, Bytes, U256};
 use reth_revm::tracing::js::JsInspectorError;
 use reth_rpc_types::{error::EthRpcErrorCode, BlockError, CallInputError};
 use reth_transaction_pool::error::{
-    Eip4844PoolTransactionError, InvalidPoolTransactionError, PoolError, PoolErrorKind,
-    PoolTransactionError,
+    AdmissionPolicyError, Eip4844PoolTransactionError, InvalidPoolTransactionError, PoolError,
+    PoolErrorKind, PoolTransactionError,
 };
 use revm::primitives::{EVMError, ExecutionResult, HaltReason, OutOfGasError};
 use std::time::Duration;
@@ -48,6 +48,13 @@ pub enum EthApiError {
     /// When an invalid block range is provided
     #[error("invalid block range")]
     InvalidBlockRange,
+    /// Thrown when the target block for proof computation exceeds the maximum configured window.
+    #[error("distance to target block exceeds maximum proof window")]
+    ExceedsMaxProofWindow,
+    /// Thrown when the target block of a state inspection method exceeds the maximum configured
+    /// window.
+    #[error("distance to target block exceeds maximum state window")]
+    ExceedsMaxStateWindow,
     /// An internal error where prevrandao is not set in the evm's environment
     #[error("prevrandao not in the EVM's environment after merge")]
     PrevrandaoNotSet,
@@ -143,6 +150,8 @@ impl From<EthApiError> for ErrorObject<'static> {
             EthApiError::InvalidTransactionSignature |
             EthApiError::EmptyRawTransactionData |
             EthApiError::InvalidBlockRange |
+            EthApiError::ExceedsMaxProofWindow |
+            EthApiError::ExceedsMaxStateWindow |
             EthApiError::ConflictingFeeFieldsInRequest |
             EthApiError::Signing(_) |
             EthApiError::BothStateAndStateDiffInOverride(_) |
@@ -611,6 +620,9 @@ pub enum RpcPoolError {
     /// Eip-4844 related error
     #[error(transparent)]
     Eip4844(#[from] Eip4844PoolTransactionError),
+    /// Thrown if the transaction was rejected by an admission policy of the pool
+    #[error(transparent)]
+    AdmissionPolicy(#[from] AdmissionPolicyError),
     /// Thrown if a conflicting transaction type is already in the pool
     ///
     /// In other words, thrown if a transaction with the same sender that violates the exclusivity
@@ -663,6 +675,7 @@ impl From<InvalidPoolTransactionError> for RpcPoolError {
             InvalidPoolTransactionError::Underpriced => RpcPoolError::Underpriced,
             InvalidPoolTransactionError::Other(err) => RpcPoolError::PoolTransactionError(err),
             InvalidPoolTransactionError::Eip4844(err) => RpcPoolError::Eip4844(err),
+            InvalidPoolTransactionError::AdmissionPolicy(err) => RpcPoolError::AdmissionPolicy(err),
             InvalidPoolTransactionError::Overdraft => {
                 RpcPoolError::Invalid(RpcInvalidTransactionError::InsufficientFunds)
             }
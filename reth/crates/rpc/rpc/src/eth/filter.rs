@@ -12,7 +12,7 @@ use core::fmt;
 use async_trait::async_trait;
 use jsonrpsee::{core::RpcResult, server::IdProvider};
 use reth_primitives::{IntoRecoveredTransaction, TxHash};
-use reth_provider::{BlockIdReader, BlockReader, EvmEnvProvider, ProviderError};
+use reth_provider::{BlockIdReader, BlockReader, EvmEnvProvider, LogIndexReader, ProviderError};
 use reth_rpc_api::EthFilterApiServer;
 use reth_rpc_types::{
     BlockNumHash, Filter, FilterBlockOption, FilterChanges, FilterId, FilteredParams, Log,
@@ -124,7 +124,7 @@ where
 
 impl<Provider, Pool> EthFilter<Provider, Pool>
 where
-    Provider: BlockReader + BlockIdReader + EvmEnvProvider + 'static,
+    Provider: BlockReader + BlockIdReader + EvmEnvProvider + LogIndexReader + 'static,
     Pool: TransactionPool + 'static,
     <Pool as TransactionPool>::Transaction: 'static,
 {
@@ -223,7 +223,7 @@ where
 #[async_trait]
 impl<Provider, Pool> EthFilterApiServer for EthFilter<Provider, Pool>
 where
-    Provider: BlockReader + BlockIdReader + EvmEnvProvider + 'static,
+    Provider: BlockReader + BlockIdReader + EvmEnvProvider + LogIndexReader + 'static,
     Pool: TransactionPool + 'static,
 {
     /// Handler for `eth_newFilter`
@@ -342,7 +342,7 @@ struct EthFilterInner<Provider, Pool> {
 
 impl<Provider, Pool> EthFilterInner<Provider, Pool>
 where
-    Provider: BlockReader + BlockIdReader + EvmEnvProvider + 'static,
+    Provider: BlockReader + BlockIdReader + EvmEnvProvider + LogIndexReader + 'static,
     Pool: TransactionPool + 'static,
 {
     /// Returns logs matching given filter object.
@@ -405,6 +405,10 @@ where
 
     /// Returns all logs in the given _inclusive_ range that match the filter
     ///
+    /// If the log index is enabled, the blocks of the range covered by the index are looked up in
+    /// it, and only the remaining blocks are scanned using the headers' logs bloom. The
+    /// `max_blocks_per_filter` limit only applies to the scanned blocks.
+    ///
     /// Returns an error if:
     ///  - underlying database error
     ///  - amount of matches exceeds configured limit
@@ -416,22 +420,99 @@ where
     ) -> Result<Vec<Log>, FilterError> {
         trace!(target: "rpc::eth::filter", from=from_block, to=to_block, ?filter, "finding logs in range");
 
-        if to_block - from_block > self.max_blocks_per_filter {
+        // the part of the range covered by the log index, if the filter can be looked up in it
+        let indexed_range = if logs_utils::has_indexed_params(filter) {
+            self.provider
+                .log_index_range()?
+                .map(|index| (*index.start()).max(from_block)..=(*index.end()).min(to_block))
+                .filter(|range| !range.is_empty())
+        } else {
+            None
+        };
+
+        let indexed_blocks =
+            indexed_range.as_ref().map_or(0, |range| range.end() - range.start() + 1);
+        if (to_block - from_block).saturating_sub(indexed_blocks) > self.max_blocks_per_filter {
             return Err(FilterError::QueryExceedsMaxBlocks(self.max_blocks_per_filter))
         }
 
         let mut all_logs = Vec::new();
         let filter_params = FilteredParams::new(Some(filter.clone()));
+        // size check but only if range is multiple blocks, so we always return all logs of a
+        // single block
+        let is_multi_block_range = from_block != to_block;
+
+        let Some(indexed_range) = indexed_range else {
+            self.append_logs_in_block_range(
+                &mut all_logs,
+                filter,
+                &filter_params,
+                from_block..=to_block,
+                is_multi_block_range,
+            )
+            .await?;
+            return Ok(all_logs)
+        };
 
+        if from_block < *indexed_range.start() {
+            self.append_logs_in_block_range(
+                &mut all_logs,
+                filter,
+                &filter_params,
+                from_block..=indexed_range.start() - 1,
+                is_multi_block_range,
+            )
+            .await?;
+        }
+
+        let end = *indexed_range.end();
+        for block_number in
+            logs_utils::indexed_filter_blocks(&self.provider, filter, indexed_range)?
+        {
+            let block_hash = self
+                .provider
+                .block_hash(block_number)?
+                .ok_or(ProviderError::BlockNotFound(block_number.into()))?;
+            self.append_block_logs(
+                &mut all_logs,
+                &filter_params,
+                BlockNumHash::new(block_number, block_hash),
+                is_multi_block_range,
+            )
+            .await?;
+        }
+
+        if end < to_block {
+            self.append_logs_in_block_range(
+                &mut all_logs,
+                filter,
+                &filter_params,
+                end + 1..=to_block,
+                is_multi_block_range,
+            )
+            .await?;
+        }
+
+        Ok(all_logs)
+    }
+
+    /// Appends all logs in the given _inclusive_ range that match the filter, checking the logs
+    /// bloom of every block in the range.
+    async fn append_logs_in_block_range(
+        &self,
+        all_logs: &mut Vec<Log>,
+        filter: &Filter,
+        filter_params: &FilteredParams,
+        range: RangeInclusive<u64>,
+        is_multi_block_range: bool,
+    ) -> Result<(), FilterError> {
         // derive bloom filters from filter input
         let address_filter = FilteredParams::address_filter(&filter.address);
         let topics_filter = FilteredParams::topics_filter(&filter.topics);
 
         // loop over the range of new blocks and check logs if the filter matches the log's bloom
         // filter
-        for (from, to) in
-            BlockRangeInclusiveIter::new(from_block..=to_block, self.max_headers_range)
-        {
+        for (from, to) in BlockRangeInclusiveIter::new(range, self.max_headers_range) {
             let headers = self.provider.headers_range(from..=to)?;
 
             for (idx, header) in headers.iter().enumerate() {
@@ -449,30 +530,46 @@ where
                             .ok_or(ProviderError::BlockNotFound(header.number.into()))?,
                     };
 
-                    if let Some(receipts) = self.eth_cache.get_receipts(block_hash).await? {
-                        append_matching_block_logs(
-                            &mut all_logs,
-                            &self.provider,
-                            &filter_params,
-                            BlockNumHash::new(header.number, block_hash),
-                            &receipts,
-                            false,
-                        )?;
-
-                        // size check but only if range is multiple blocks, so we always return all
-                        // logs of a single block
-                        let is_multi_block_range = from_block != to_block;
-                        if is_multi_block_range && all_logs.len() > self.max_logs_per_response {
-                            return Err(FilterError::QueryExceedsMaxResults(
-                                self.max_logs_per_response,
-                            ))
-                        }
-                    }
+                    self.append_block_logs(
+                        all_logs,
+                        filter_params,
+                        BlockNumHash::new(header.number, block_hash),
+                        is_multi_block_range,
+                    )
+                    .await?;
                 }
             }
         }
 
-        Ok(all_logs)
+        Ok(())
+    }
+
+    /// Appends all logs of the given block that match the filter.
+    ///
+    /// Returns an error if the amount of matches exceeds the configured limit, unless only a
+    /// single block is queried.
+    async fn append_block_logs(
+        &self,
+        all_logs: &mut Vec<Log>,
+        filter_params: &FilteredParams,
+        block_num_hash: BlockNumHash,
+        is_multi_block_range: bool,
+    ) -> Result<(), FilterError> {
+        if let Some(receipts) = self.eth_cache.get_receipts(block_num_hash.hash).await? {
+            append_matching_block_logs(
+                all_logs,
+                &self.provider,
+                filter_params,
+                block_num_hash,
+                &receipts,
+                false,
+            )?;
+
+            if is_multi_block_range && all_logs.len() > self.max_logs_per_response {
+                return Err(FilterError::QueryExceedsMaxResults(self.max_logs_per_response))
+            }
+        }
+        Ok(())
     }
 }
 
@@ -1,9 +1,10 @@
 use super::filter::FilterError;
 use alloy_primitives::TxHash;
 use reth_primitives::{BlockNumHash, ChainInfo, Receipt, U256};
-use reth_provider::{BlockReader, ProviderError};
-use reth_rpc_types::{FilteredParams, Log};
+use reth_provider::{BlockReader, LogIndexReader, ProviderError};
+use reth_rpc_types::{Filter, FilteredParams, Log};
 use reth_rpc_types_compat::log::from_primitive_log;
+use std::{collections::BTreeSet, ops::RangeInclusive};
 
 /// Returns all matching of a block's receipts when the transaction hashes are known.
 pub(crate) fn matching_block_logs_with_tx_hashes<'a, I>(
@@ -104,6 +105,51 @@ pub(crate) fn append_matching_block_logs(
     Ok(())
 }
 
+/// Returns true if the filter restricts the addresses or topics of the logs, which means that the
+/// matching blocks can be looked up in the log index.
+pub(crate) fn has_indexed_params(filter: &Filter) -> bool {
+    !filter.address.is_empty() || filter.topics.iter().any(|topics| !topics.is_empty())
+}
+
+/// Returns the blocks within the given range that can contain logs matching the filter, according
+/// to the log index, in ascending order.
+///
+/// The log index doesn't record the position of the topics, so the logs of the returned blocks
+/// still need to be matched against the filter.
+pub(crate) fn indexed_filter_blocks(
+    provider: impl LogIndexReader,
+    filter: &Filter,
+    range: RangeInclusive<u64>,
+) -> Result<BTreeSet<u64>, FilterError> {
+    let mut blocks: Option<BTreeSet<u64>> = None;
+    let mut intersect = |matching: BTreeSet<u64>| {
+        blocks = Some(match blocks.take() {
+            Some(blocks) => blocks.intersection(&matching).copied().collect(),
+            None => matching,
+        });
+    };
+
+    // addresses are OR'ed
+    if !filter.address.is_empty() {
+        let mut matching = BTreeSet::new();
+        for address in filter.address.iter() {
+            matching.extend(provider.log_address_blocks(*address, range.clone())?);
+        }
+        intersect(matching);
+    }
+
+    // topics are OR'ed within a position and AND'ed across positions
+    for topics in filter.topics.iter().filter(|topics| !topics.is_empty()) {
+        let mut matching = BTreeSet::new();
+        for topic in topics.iter() {
+            matching.extend(provider.log_topic_blocks(*topic, range.clone())?);
+        }
+        intersect(matching);
+    }
+
+    Ok(blocks.unwrap_or_default())
+}
+
 /// Returns true if the log matches the filter and should be included
 pub(crate) fn log_matches_filter(
     block: BlockNumHash,
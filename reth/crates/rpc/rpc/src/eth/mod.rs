@@ -11,11 +11,14 @@ mod logs_utils;
 mod pubsub;
 pub mod revm_utils;
 mod signer;
+mod sim_bundle;
 pub(crate) mod utils;
 
+pub(crate) use api::pending_block::pre_block_beacon_root_contract_call;
 pub use api::{
     fee_history::{fee_history_cache_new_blocks_task, FeeHistoryCache, FeeHistoryCacheConfig},
-    EthApi, EthApiSpec, EthTransactions, TransactionSource, RPC_DEFAULT_GAS_CAP,
+    EthApi, EthApiSpec, EthTransactions, TransactionSource, DEFAULT_ETH_PROOF_WINDOW,
+    MAX_ETH_PROOF_WINDOW, RPC_DEFAULT_GAS_CAP,
 };
 
 pub use bundle::EthBundle;
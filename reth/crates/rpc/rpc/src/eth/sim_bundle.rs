@@ -0,0 +1,267 @@
+//! `Eth` MEV-Share bundle simulation implementation and helpers.
+
+use crate::eth::{
+    bundle::{EthBundle, EthBundleError},
+    error::{EthApiError, EthResult},
+    revm_utils::FillableTransaction,
+    utils::recover_raw_transaction,
+    EthTransactions,
+};
+use jsonrpsee::core::RpcResult;
+use reth_primitives::{
+    revm_primitives::db::{DatabaseCommit, DatabaseRef},
+    BlockId, BlockNumberOrTag, U256, U64,
+};
+use reth_revm::database::StateProviderDatabase;
+use reth_rpc_api::MevApiServer;
+use reth_rpc_types::{
+    BundleItem, SendBundleRequest, SendBundleResponse, SimBundleLogs, SimBundleOverrides,
+    SimBundleResponse,
+};
+use reth_rpc_types_compat::log::from_primitive_log;
+use revm::{
+    db::CacheDB,
+    primitives::{EVMError, ResultAndState, TxEnv},
+};
+use revm_primitives::EnvWithHandlerCfg;
+use std::{
+    collections::HashSet,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+/// The default timeout of a `mev_simBundle` call.
+const DEFAULT_SIM_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The maximum timeout of a `mev_simBundle` call.
+const MAX_SIM_TIMEOUT: Duration = Duration::from_secs(30);
+
+impl<Eth> EthBundle<Eth>
+where
+    Eth: EthTransactions + 'static,
+{
+    /// Simulates a fully matched MEV-Share bundle on top of the state of the parent block.
+    ///
+    /// The simulated block is derived from the parent block, see [SimBundleOverrides]. A bundle
+    /// that fails to execute, or that contains a reverting transaction that is not allowed to
+    /// revert, is reported as an unsuccessful simulation rather than an error.
+    pub async fn sim_bundle(
+        &self,
+        request: SendBundleRequest,
+        overrides: SimBundleOverrides,
+    ) -> EthResult<SimBundleResponse> {
+        let SendBundleRequest { inclusion, bundle_body, validity, .. } = request;
+        if bundle_body.is_empty() {
+            return Err(EthApiError::InvalidParams(
+                EthBundleError::EmptyBundleTransactions.to_string(),
+            ))
+        }
+
+        let mut transactions = Vec::with_capacity(bundle_body.len());
+        for item in bundle_body {
+            match item {
+                BundleItem::Tx { tx, can_revert } => {
+                    let tx = recover_raw_transaction(tx)?.into_ecrecovered_transaction();
+                    transactions.push((tx, can_revert));
+                }
+                BundleItem::Hash { .. } => {
+                    return Err(EthApiError::InvalidParams(
+                        EthBundleError::BundleNotFullyMatched.to_string(),
+                    ))
+                }
+            }
+        }
+
+        // the profit of refunded transactions is not refundable again
+        let refund_indices = validity
+            .and_then(|validity| validity.refund)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|refund| refund.body_idx as usize)
+            .collect::<HashSet<_>>();
+        if let Some(idx) = refund_indices.iter().find(|idx| **idx >= transactions.len()) {
+            return Err(EthApiError::InvalidParams(
+                EthBundleError::InvalidRefundIndex(*idx).to_string(),
+            ))
+        }
+
+        let SimBundleOverrides {
+            parent_block,
+            block_number,
+            coinbase,
+            timestamp,
+            gas_limit,
+            base_fee,
+            timeout,
+        } = overrides;
+        let parent_block = parent_block.unwrap_or(BlockId::Number(BlockNumberOrTag::Latest));
+        let (cfg, mut block_env, at) = self.eth_api().evm_env_at(parent_block).await?;
+
+        // derive the simulated block from the parent block
+        let state_block = block_env.number.to::<u64>();
+        block_env.number =
+            U256::from(block_number.map(|n| n.to::<u64>()).unwrap_or(state_block + 1));
+        if let Some(coinbase) = coinbase {
+            block_env.coinbase = coinbase;
+        }
+        if let Some(timestamp) = timestamp {
+            block_env.timestamp = U256::from(timestamp.to::<u64>());
+        } else {
+            block_env.timestamp += U256::from(12);
+        }
+        if let Some(gas_limit) = gas_limit {
+            block_env.gas_limit = U256::from(gas_limit.to::<u64>());
+        }
+        if let Some(base_fee) = base_fee {
+            block_env.basefee = U256::from(base_fee.to::<u64>());
+        } else if let Some(parent) = self.eth_api().block_by_id(parent_block).await? {
+            // the simulated block is a child of the parent block
+            let base_fee_params = self.eth_api().base_fee_params(block_env.timestamp.to());
+            if let Some(base_fee) = parent.header.next_block_base_fee(base_fee_params) {
+                block_env.basefee = U256::from(base_fee);
+            }
+        }
+
+        let sim_block = block_env.number.to::<u64>();
+        let max_block = inclusion.max_block_number().unwrap_or_else(|| inclusion.block_number());
+        if sim_block < inclusion.block_number() || sim_block > max_block {
+            return Err(EthApiError::InvalidParams(
+                EthBundleError::BlockOutsideInclusionRange(sim_block).to_string(),
+            ))
+        }
+
+        let timeout = timeout
+            .map(|timeout| Duration::from_secs(timeout.to()))
+            .unwrap_or(DEFAULT_SIM_TIMEOUT)
+            .min(MAX_SIM_TIMEOUT);
+
+        // the simulation runs on a blocking task that can't be aborted, so it checks this flag
+        // before each transaction and stops once the timeout elapsed
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let sim_cancelled = Arc::clone(&cancelled);
+
+        let sim = self.eth_api().spawn_with_state_at_block(at, move |state| {
+            let coinbase = block_env.coinbase;
+            let block_number = block_env.number;
+            let env = EnvWithHandlerCfg::new_with_cfg_env(cfg, block_env, TxEnv::default());
+            let db = CacheDB::new(StateProviderDatabase::new(state));
+            let mut evm = revm::Evm::builder().with_db(db).with_env_with_handler_cfg(env).build();
+
+            let mut gas_used = 0u64;
+            let mut profit = U256::ZERO;
+            let mut refundable_value = U256::ZERO;
+            let mut logs = Vec::with_capacity(transactions.len());
+            let mut log_index = 0usize;
+            let mut error = None;
+
+            for (idx, (tx, can_revert)) in transactions.into_iter().enumerate() {
+                if sim_cancelled.load(Ordering::Relaxed) {
+                    return Err(EthApiError::ExecutionTimedOut(timeout))
+                }
+
+                let coinbase_balance_before =
+                    DatabaseRef::basic_ref(&evm.context.evm.db, coinbase)?
+                        .map(|acc| acc.balance)
+                        .unwrap_or_default();
+
+                tx.try_fill_tx_env(evm.tx_mut())?;
+                let ResultAndState { result, state } = match evm.transact() {
+                    Ok(res) => res,
+                    Err(EVMError::Database(err)) => return Err(err.into()),
+                    Err(err) => {
+                        error = Some(format!("transaction {} is invalid: {err}", tx.hash()));
+                        break
+                    }
+                };
+                if !result.is_success() && !can_revert {
+                    error = Some(format!("transaction {} reverted", tx.hash()));
+                    break
+                }
+                gas_used += result.gas_used();
+
+                let coinbase_balance_after = state
+                    .get(&coinbase)
+                    .map(|acc| acc.info.balance)
+                    .unwrap_or(coinbase_balance_before);
+                let coinbase_diff = coinbase_balance_after.saturating_sub(coinbase_balance_before);
+                profit += coinbase_diff;
+                if !refund_indices.contains(&idx) {
+                    refundable_value += coinbase_diff;
+                }
+
+                let tx_logs = result
+                    .into_logs()
+                    .into_iter()
+                    .map(|log| {
+                        let mut log = from_primitive_log(log.into());
+                        log.block_number = Some(block_number);
+                        log.transaction_hash = Some(tx.hash());
+                        log.transaction_index = Some(U256::from(idx));
+                        log.log_index = Some(U256::from(log_index));
+                        log_index += 1;
+                        log
+                    })
+                    .collect();
+                logs.push(SimBundleLogs { tx_logs: Some(tx_logs), bundle_logs: None });
+
+                // need to apply the state changes of this transaction before executing the next
+                evm.context.evm.db.commit(state);
+            }
+
+            if let Some(error) = error {
+                return Ok(SimBundleResponse {
+                    success: false,
+                    error: Some(error),
+                    state_block: U64::from(state_block),
+                    mev_gas_price: U256::ZERO,
+                    profit: U256::ZERO,
+                    refundable_value: U256::ZERO,
+                    gas_used: U64::ZERO,
+                    logs: None,
+                })
+            }
+
+            let mev_gas_price = profit.checked_div(U256::from(gas_used)).unwrap_or_default();
+            Ok(SimBundleResponse {
+                success: true,
+                error: None,
+                state_block: U64::from(state_block),
+                mev_gas_price,
+                profit,
+                refundable_value,
+                gas_used: U64::from(gas_used),
+                logs: Some(logs),
+            })
+        });
+
+        match tokio::time::timeout(timeout, sim).await {
+            Ok(res) => res,
+            Err(_) => {
+                cancelled.store(true, Ordering::Relaxed);
+                Err(EthApiError::ExecutionTimedOut(timeout))
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<Eth> MevApiServer for EthBundle<Eth>
+where
+    Eth: EthTransactions + 'static,
+{
+    async fn send_bundle(&self, _request: SendBundleRequest) -> RpcResult<SendBundleResponse> {
+        Err(EthApiError::Unsupported("bundles must be sent to a matchmaker").into())
+    }
+
+    async fn sim_bundle(
+        &self,
+        request: SendBundleRequest,
+        overrides: SimBundleOverrides,
+    ) -> RpcResult<SimBundleResponse> {
+        let _permit = self.blocking_task_guard().clone().acquire_owned().await;
+        Ok(EthBundle::sim_bundle(self, request, overrides).await?)
+    }
+}
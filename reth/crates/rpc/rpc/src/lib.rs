@@ -35,6 +35,7 @@ mod reth;
 mod rpc;
 mod trace;
 mod txpool;
+mod validation;
 mod web3;
 pub use admin::AdminApi;
 pub use blocking_pool::{BlockingTaskGuard, BlockingTaskPool};
@@ -48,6 +49,7 @@ pub use reth::RethApi;
 pub use rpc::RPCApi;
 pub use trace::TraceApi;
 pub use txpool::TxPoolApi;
+pub use validation::{ValidationApi, ValidationApiError};
 pub use web3::Web3Api;
 pub mod blocking_pool;
 pub mod result;
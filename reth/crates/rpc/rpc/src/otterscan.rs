@@ -1,32 +1,388 @@
-use crate::result::internal_rpc_err;
+use crate::{
+    eth::{error::EthApiError, EthTransactions},
+    result::internal_rpc_err,
+};
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
-use reth_primitives::{Address, BlockId, BlockNumberOrTag, TxHash, B256};
+use reth_primitives::{
+    Address, BlockId, BlockNumber, BlockNumberOrTag, Bloom, Bytes, TxHash, B256, U256,
+};
+use reth_provider::{AddressTransactionsReader, BlockNumReader};
+use reth_revm::tracing::{types::CallKind, TracingInspectorConfig};
 use reth_rpc_api::{EthApiServer, OtterscanServer};
 use reth_rpc_types::{
-    BlockDetails, ContractCreator, InternalOperation, OtsBlockTransactions, TraceEntry,
-    Transaction, TransactionsWithReceipts,
+    BlockDetails, BlockTransactions, ContractCreator, InternalOperation, OperationType, OtsBlock,
+    OtsBlockTransactions, OtsTransactionReceipt, TraceEntry, Transaction, TransactionsWithReceipts,
 };
+use revm::primitives::ExecutionResult;
+use std::future::Future;
 
 const API_LEVEL: u64 = 8;
 
+/// The maximum number of blocks without any transaction of the searched address that are scanned
+/// by `ots_searchTransactionsBefore`, `ots_searchTransactionsAfter` and `ots_getContractCreator`
+/// before the search stops.
+///
+/// This only matters if the address transactions index is not enabled, otherwise only blocks
+/// touching the address are visited.
+const MAX_SEARCH_UNMATCHED_BLOCKS: usize = 10_000;
+
 /// Otterscan API.
 #[derive(Debug)]
-pub struct OtterscanApi<Eth> {
+pub struct OtterscanApi<Provider, Eth> {
+    provider: Provider,
     eth: Eth,
 }
 
-impl<Eth> OtterscanApi<Eth> {
+impl<Provider, Eth> OtterscanApi<Provider, Eth> {
     /// Creates a new instance of `Otterscan`.
-    pub fn new(eth: Eth) -> Self {
-        Self { eth }
+    pub fn new(provider: Provider, eth: Eth) -> Self {
+        Self { provider, eth }
+    }
+}
+
+impl<Provider, Eth> OtterscanApi<Provider, Eth>
+where
+    Provider: BlockNumReader + AddressTransactionsReader + 'static,
+    Eth: EthApiServer + EthTransactions + 'static,
+{
+    /// Returns the highest block covered by the address transactions index, or `None` if the
+    /// index is not enabled.
+    fn index_tip(&self) -> RpcResult<Option<BlockNumber>> {
+        Ok(self.provider.address_transactions_index_tip().map_err(EthApiError::from)?)
+    }
+
+    /// Returns up to `limit` blocks lower than `block` that can contain transactions touching the
+    /// given address, in descending order.
+    ///
+    /// If the address transactions index is enabled, only blocks touching the address and blocks
+    /// that are not indexed yet are returned. Otherwise, all blocks are candidates.
+    fn candidate_blocks_before(
+        &self,
+        address: Address,
+        block: BlockNumber,
+        limit: usize,
+    ) -> RpcResult<Vec<BlockNumber>> {
+        let Some(tip) = self.index_tip()? else {
+            return Ok((0..block).rev().take(limit).collect())
+        };
+
+        let mut blocks = (tip + 1..block).rev().take(limit).collect::<Vec<_>>();
+        if blocks.len() < limit {
+            let indexed = self
+                .provider
+                .address_blocks_before(address, block.min(tip + 1), limit - blocks.len())
+                .map_err(EthApiError::from)?;
+            blocks.extend(indexed);
+        }
+        Ok(blocks)
+    }
+
+    /// Returns up to `limit` blocks higher than `block` that can contain transactions touching the
+    /// given address, in ascending order.
+    ///
+    /// See also [Self::candidate_blocks_before].
+    fn candidate_blocks_after(
+        &self,
+        address: Address,
+        block: BlockNumber,
+        limit: usize,
+    ) -> RpcResult<Vec<BlockNumber>> {
+        let best = self.provider.best_block_number().map_err(EthApiError::from)?;
+        let Some(tip) = self.index_tip()? else {
+            return Ok((block.saturating_add(1)..=best).take(limit).collect())
+        };
+
+        let mut blocks =
+            self.provider.address_blocks_after(address, block, limit).map_err(EthApiError::from)?;
+        if blocks.len() < limit {
+            blocks.extend((block.max(tip).saturating_add(1)..=best).take(limit - blocks.len()));
+        }
+        Ok(blocks)
+    }
+
+    /// Returns the transactions of the given block that touch the given address, together with
+    /// their receipts.
+    ///
+    /// A transaction touches an address if the address is its sender, its recipient or the contract
+    /// created by it. If `trace_calls` is set, the transactions are traced and also touch the
+    /// addresses of their internal calls.
+    async fn address_transactions_in_block(
+        &self,
+        address: Address,
+        block: BlockNumber,
+        trace_calls: bool,
+    ) -> RpcResult<Vec<(Transaction, OtsTransactionReceipt)>> {
+        let block_number = BlockNumberOrTag::Number(block);
+        let (block, receipts) = futures::try_join!(
+            EthApiServer::block_by_number(&self.eth, block_number, true),
+            EthApiServer::block_receipts(&self.eth, BlockId::Number(block_number)),
+        )?;
+        let (Some(block), Some(receipts)) = (block, receipts) else { return Ok(Vec::new()) };
+
+        let BlockTransactions::Full(transactions) = block.inner.transactions else {
+            return Ok(Vec::new())
+        };
+        let timestamp = block.inner.header.timestamp.saturating_to::<u64>();
+
+        let called = if trace_calls {
+            self.eth
+                .trace_block_with(
+                    BlockId::Number(block_number),
+                    TracingInspectorConfig::default_parity(),
+                    move |_tx_info, inspector, _, _, _| {
+                        Ok(inspector.into_traces().into_nodes().into_iter().any(|node| {
+                            node.trace.caller == address || node.trace.address == address
+                        }))
+                    },
+                )
+                .await?
+                .unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+
+        Ok(transactions
+            .into_iter()
+            .zip(receipts)
+            .enumerate()
+            .filter(|(idx, (tx, receipt))| {
+                tx.from == address ||
+                    tx.to == Some(address) ||
+                    receipt.contract_address == Some(address) ||
+                    called.get(*idx).copied().unwrap_or_default()
+            })
+            .map(|(_, (tx, receipt))| (tx, OtsTransactionReceipt { receipt, timestamp }))
+            .collect())
+    }
+
+    /// Handler for `ots_searchTransactionsBefore`
+    async fn search_transactions_before(
+        &self,
+        address: Address,
+        block: BlockNumber,
+        page_size: usize,
+    ) -> RpcResult<TransactionsWithReceipts> {
+        // Block 0 means the search starts at the tip of the chain.
+        let first_page = block == 0;
+        let mut cursor = if first_page {
+            self.provider.best_block_number().map_err(EthApiError::from)? + 1
+        } else {
+            block
+        };
+
+        // Internal calls are only searched in indexed blocks, which are known to touch the address.
+        let tip = self.index_tip()?;
+        let mut txs = Vec::new();
+        let mut receipts = Vec::new();
+        let mut unmatched_blocks = 0;
+        'search: loop {
+            let blocks = self.candidate_blocks_before(address, cursor, page_size)?;
+            if blocks.is_empty() {
+                break
+            }
+
+            for number in blocks {
+                cursor = number;
+                let indexed = tip.map_or(false, |tip| number <= tip);
+                let matched = self.address_transactions_in_block(address, number, indexed).await?;
+                if matched.is_empty() {
+                    unmatched_blocks += 1;
+                }
+                for (tx, receipt) in matched.into_iter().rev() {
+                    txs.push(tx);
+                    receipts.push(receipt);
+                }
+                if txs.len() >= page_size || unmatched_blocks >= MAX_SEARCH_UNMATCHED_BLOCKS {
+                    break 'search
+                }
+            }
+        }
+
+        if txs.is_empty() && unmatched_blocks >= MAX_SEARCH_UNMATCHED_BLOCKS {
+            return Err(EthApiError::InvalidParams(format!(
+                "no transactions found within {MAX_SEARCH_UNMATCHED_BLOCKS} blocks, enable the \
+                 address transactions index to search the full history"
+            ))
+            .into())
+        }
+
+        let last_page = self.candidate_blocks_before(address, cursor, 1)?.is_empty();
+        Ok(TransactionsWithReceipts { txs, receipts, first_page, last_page })
+    }
+
+    /// Handler for `ots_searchTransactionsAfter`
+    async fn search_transactions_after(
+        &self,
+        address: Address,
+        block: BlockNumber,
+        page_size: usize,
+    ) -> RpcResult<TransactionsWithReceipts> {
+        // Block 0 means the search starts at genesis, which is the last page.
+        let last_page = block == 0;
+        let mut cursor = block;
+
+        // Internal calls are only searched in indexed blocks, which are known to touch the address.
+        let tip = self.index_tip()?;
+        let mut txs = Vec::new();
+        let mut receipts = Vec::new();
+        let mut unmatched_blocks = 0;
+        'search: loop {
+            let blocks = self.candidate_blocks_after(address, cursor, page_size)?;
+            if blocks.is_empty() {
+                break
+            }
+
+            for number in blocks {
+                cursor = number;
+                let indexed = tip.map_or(false, |tip| number <= tip);
+                let matched = self.address_transactions_in_block(address, number, indexed).await?;
+                if matched.is_empty() {
+                    unmatched_blocks += 1;
+                }
+                for (tx, receipt) in matched {
+                    txs.push(tx);
+                    receipts.push(receipt);
+                }
+                if txs.len() >= page_size || unmatched_blocks >= MAX_SEARCH_UNMATCHED_BLOCKS {
+                    break 'search
+                }
+            }
+        }
+
+        if txs.is_empty() && unmatched_blocks >= MAX_SEARCH_UNMATCHED_BLOCKS {
+            return Err(EthApiError::InvalidParams(format!(
+                "no transactions found within {MAX_SEARCH_UNMATCHED_BLOCKS} blocks, enable the \
+                 address transactions index to search the full history"
+            ))
+            .into())
+        }
+
+        // Results are always returned newest first.
+        txs.reverse();
+        receipts.reverse();
+
+        let first_page = self.candidate_blocks_after(address, cursor, 1)?.is_empty();
+        Ok(TransactionsWithReceipts { txs, receipts, first_page, last_page })
+    }
+
+    /// Returns the first block at the end of which the given predicate holds, assuming it holds
+    /// for all following blocks as well.
+    ///
+    /// Returns `None` if the predicate does not hold at the tip of the chain.
+    async fn find_first_block<F, Fut>(&self, predicate: F) -> RpcResult<Option<BlockNumber>>
+    where
+        F: Fn(BlockNumber) -> Fut,
+        Fut: Future<Output = RpcResult<bool>>,
+    {
+        let mut high = self.provider.best_block_number().map_err(EthApiError::from)?;
+        if !predicate(high).await? {
+            return Ok(None)
+        }
+
+        let mut low = 0;
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if predicate(mid).await? {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+        Ok(Some(low))
+    }
+
+    /// Handler for `ots_getTransactionBySenderAndNonce`
+    async fn get_transaction_by_sender_and_nonce(
+        &self,
+        sender: Address,
+        nonce: u64,
+    ) -> RpcResult<Option<Transaction>> {
+        // The transaction is in the first block after which the nonce of the sender is higher.
+        let Some(block) = self
+            .find_first_block(|block| async move {
+                let at = Some(BlockId::Number(block.into()));
+                let count = EthApiServer::transaction_count(&self.eth, sender, at).await?;
+                Ok(count > U256::from(nonce))
+            })
+            .await?
+        else {
+            return Ok(None)
+        };
+
+        let block = EthApiServer::block_by_number(&self.eth, block.into(), true).await?;
+        let Some(BlockTransactions::Full(transactions)) =
+            block.map(|block| block.inner.transactions)
+        else {
+            return Ok(None)
+        };
+
+        Ok(transactions.into_iter().find(|tx| tx.from == sender && tx.nonce.to::<u64>() == nonce))
+    }
+
+    /// Returns the hash of the first transaction of the given block that created the given
+    /// address, together with the creator of the contract.
+    async fn contract_creation_in_block(
+        &self,
+        address: Address,
+        block: BlockNumber,
+    ) -> RpcResult<Option<(B256, Address)>> {
+        let creations = self
+            .eth
+            .trace_block_with(
+                BlockId::Number(block.into()),
+                TracingInspectorConfig::default_parity(),
+                move |tx_info, inspector, _, _, _| {
+                    let creator =
+                        inspector.into_traces().into_nodes().into_iter().find_map(|node| {
+                            (matches!(node.trace.kind, CallKind::Create | CallKind::Create2) &&
+                                node.trace.address == address)
+                                .then_some(node.trace.caller)
+                        });
+                    Ok(creator.map(|creator| (tx_info.hash, creator)))
+                },
+            )
+            .await?
+            .unwrap_or_default();
+
+        Ok(creations
+            .into_iter()
+            .flatten()
+            .find_map(|(tx_hash, creator)| tx_hash.map(|tx_hash| (tx_hash, creator))))
+    }
+
+    /// Handler for `ots_getContractCreator`
+    async fn get_contract_creator(&self, address: Address) -> RpcResult<Option<ContractCreator>> {
+        // The contract was created in one of the blocks touching it, which are traced in ascending
+        // order until the creation is found. Contracts existing at genesis have no creator.
+        let blocks = self.candidate_blocks_after(address, 0, MAX_SEARCH_UNMATCHED_BLOCKS)?;
+        let searched_all = blocks.len() < MAX_SEARCH_UNMATCHED_BLOCKS;
+
+        for block in blocks {
+            let Some((tx_hash, creator)) = self.contract_creation_in_block(address, block).await?
+            else {
+                continue
+            };
+            let tx = EthApiServer::transaction_by_hash(&self.eth, tx_hash).await?;
+            return Ok(tx.map(|tx| ContractCreator { tx, creator }))
+        }
+
+        if !searched_all {
+            return Err(EthApiError::InvalidParams(format!(
+                "no contract creation found within {MAX_SEARCH_UNMATCHED_BLOCKS} blocks, enable \
+                 the address transactions index to search the full history"
+            ))
+            .into())
+        }
+        Ok(None)
     }
 }
 
 #[async_trait]
-impl<Eth> OtterscanServer for OtterscanApi<Eth>
+impl<Provider, Eth> OtterscanServer for OtterscanApi<Provider, Eth>
 where
-    Eth: EthApiServer,
+    Provider: BlockNumReader + AddressTransactionsReader + 'static,
+    Eth: EthApiServer + EthTransactions + 'static,
 {
     /// Handler for `ots_hasCode`
     async fn has_code(&self, address: Address, block_number: Option<BlockId>) -> RpcResult<bool> {
@@ -39,18 +395,93 @@ where
     }
 
     /// Handler for `ots_getInternalOperations`
-    async fn get_internal_operations(&self, _tx_hash: TxHash) -> RpcResult<Vec<InternalOperation>> {
-        Err(internal_rpc_err("unimplemented"))
+    async fn get_internal_operations(
+        &self,
+        tx_hash: TxHash,
+    ) -> RpcResult<Option<Vec<InternalOperation>>> {
+        let operations = self
+            .eth
+            .spawn_trace_transaction_in_block(
+                tx_hash,
+                TracingInspectorConfig::default_parity(),
+                move |_tx_info, inspector, _, _| {
+                    let operations = inspector
+                        .into_traces()
+                        .into_nodes()
+                        .into_iter()
+                        .filter_map(|node| {
+                            let r#type = match node.trace.kind {
+                                _ if node.is_selfdestruct() => OperationType::OpSelfDestruct,
+                                // the value of the top-level call is part of the transaction
+                                _ if node.trace.depth == 0 => return None,
+                                CallKind::Call if !node.trace.value.is_zero() => {
+                                    OperationType::OpTransfer
+                                }
+                                CallKind::Create => OperationType::OpCreate,
+                                CallKind::Create2 => OperationType::OpCreate2,
+                                _ => return None,
+                            };
+                            Some(InternalOperation {
+                                r#type,
+                                from: node.trace.caller,
+                                to: node.trace.address,
+                                value: node.trace.value,
+                            })
+                        })
+                        .collect::<Vec<_>>();
+                    Ok(operations)
+                },
+            )
+            .await?;
+        Ok(operations)
     }
 
     /// Handler for `ots_getTransactionError`
-    async fn get_transaction_error(&self, _tx_hash: TxHash) -> RpcResult<String> {
-        Err(internal_rpc_err("unimplemented"))
+    async fn get_transaction_error(&self, tx_hash: TxHash) -> RpcResult<Option<Bytes>> {
+        let output = self
+            .eth
+            .spawn_trace_transaction_in_block(
+                tx_hash,
+                TracingInspectorConfig::none(),
+                move |_tx_info, _inspector, res, _| match res.result {
+                    ExecutionResult::Revert { output, .. } => Ok(output),
+                    _ => Ok(Bytes::default()),
+                },
+            )
+            .await?;
+        Ok(output)
     }
 
     /// Handler for `ots_traceTransaction`
-    async fn trace_transaction(&self, _tx_hash: TxHash) -> RpcResult<TraceEntry> {
-        Err(internal_rpc_err("unimplemented"))
+    async fn trace_transaction(&self, tx_hash: TxHash) -> RpcResult<Option<Vec<TraceEntry>>> {
+        let traces = self
+            .eth
+            .spawn_trace_transaction_in_block(
+                tx_hash,
+                TracingInspectorConfig::default_parity(),
+                move |_tx_info, inspector, _, _| {
+                    let traces = inspector
+                        .into_traces()
+                        .into_nodes()
+                        .into_iter()
+                        .map(|node| TraceEntry {
+                            r#type: if node.is_selfdestruct() {
+                                "SELFDESTRUCT".to_string()
+                            } else {
+                                node.trace.kind.to_string()
+                            },
+                            depth: node.trace.depth as u32,
+                            from: node.trace.caller,
+                            to: node.trace.address,
+                            value: node.trace.value,
+                            input: node.trace.data,
+                        })
+                        .collect::<Vec<_>>();
+                    Ok(traces)
+                },
+            )
+            .await?;
+        Ok(traces)
     }
 
     /// Handler for `ots_getBlockDetails`
@@ -71,44 +502,81 @@ where
     /// Handler for `getBlockTransactions`
     async fn get_block_transactions(
         &self,
-        _block_number: BlockNumberOrTag,
-        _page_number: usize,
-        _page_size: usize,
+        block_number: BlockNumberOrTag,
+        page_number: usize,
+        page_size: usize,
     ) -> RpcResult<OtsBlockTransactions> {
-        Err(internal_rpc_err("unimplemented"))
+        let (block, receipts) = futures::try_join!(
+            EthApiServer::block_by_number(&self.eth, block_number, true),
+            EthApiServer::block_receipts(&self.eth, BlockId::Number(block_number)),
+        )?;
+        let mut block = block.ok_or(EthApiError::UnknownBlockNumber)?.inner;
+        let mut receipts = receipts.ok_or(EthApiError::UnknownBlockNumber)?;
+
+        let BlockTransactions::Full(transactions) = &mut block.transactions else {
+            return Err(internal_rpc_err("block transactions are not available"))
+        };
+        let transaction_count = transactions.len();
+
+        // pages are counted from the end of the block
+        let page_end = transaction_count.saturating_sub(page_number.saturating_mul(page_size));
+        let page_start = page_end.saturating_sub(page_size);
+
+        // only the method selector of the input is returned
+        *transactions = transactions.drain(page_start..page_end).collect();
+        for tx in transactions.iter_mut() {
+            if tx.input.len() > 4 {
+                tx.input = tx.input.slice(..4);
+            }
+        }
+
+        // logs are not returned
+        let timestamp = block.header.timestamp.saturating_to::<u64>();
+        let receipts = receipts
+            .drain(page_start.min(receipts.len())..page_end.min(receipts.len()))
+            .map(|mut receipt| {
+                receipt.logs = Vec::new();
+                receipt.logs_bloom = Bloom::default();
+                OtsTransactionReceipt { receipt, timestamp }
+            })
+            .collect();
+
+        Ok(OtsBlockTransactions { fullblock: OtsBlock { block, transaction_count }, receipts })
     }
 
     /// Handler for `searchTransactionsBefore`
     async fn search_transactions_before(
         &self,
-        _address: Address,
-        _block_number: BlockNumberOrTag,
-        _page_size: usize,
+        address: Address,
+        block_number: BlockNumberOrTag,
+        page_size: usize,
     ) -> RpcResult<TransactionsWithReceipts> {
-        Err(internal_rpc_err("unimplemented"))
+        let block = block_number.as_number().ok_or(EthApiError::UnknownBlockNumber)?;
+        OtterscanApi::search_transactions_before(self, address, block, page_size).await
     }
 
     /// Handler for `searchTransactionsAfter`
     async fn search_transactions_after(
         &self,
-        _address: Address,
-        _block_number: BlockNumberOrTag,
-        _page_size: usize,
+        address: Address,
+        block_number: BlockNumberOrTag,
+        page_size: usize,
     ) -> RpcResult<TransactionsWithReceipts> {
-        Err(internal_rpc_err("unimplemented"))
+        let block = block_number.as_number().ok_or(EthApiError::UnknownBlockNumber)?;
+        OtterscanApi::search_transactions_after(self, address, block, page_size).await
     }
 
     /// Handler for `getTransactionBySenderAndNonce`
     async fn get_transaction_by_sender_and_nonce(
         &self,
-        _sender: Address,
-        _nonce: u64,
+        sender: Address,
+        nonce: u64,
     ) -> RpcResult<Option<Transaction>> {
-        Err(internal_rpc_err("unimplemented"))
+        OtterscanApi::get_transaction_by_sender_and_nonce(self, sender, nonce).await
     }
 
     /// Handler for `getContractCreator`
-    async fn get_contract_creator(&self, _address: Address) -> RpcResult<Option<ContractCreator>> {
-        Err(internal_rpc_err("unimplemented"))
+    async fn get_contract_creator(&self, address: Address) -> RpcResult<Option<ContractCreator>> {
+        OtterscanApi::get_contract_creator(self, address).await
     }
 }
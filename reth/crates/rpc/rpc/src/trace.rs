@@ -11,7 +11,8 @@ use async_trait::async_trait;
 use jsonrpsee::core::RpcResult as Result;
 use reth_consensus_common::calc::{base_block_reward, block_reward};
 use reth_primitives::{
-    revm::env::tx_env_with_recovered, BlockId, BlockNumberOrTag, Bytes, SealedHeader, B256, U256,
+    revm::env::tx_env_with_recovered, Address, BlockId, BlockNumberOrTag, Bytes, SealedHeader,
+    B256, U256,
 };
 use reth_provider::{BlockReader, ChainSpecProvider, EvmEnvProvider, StateProviderFactory};
 use reth_revm::{
@@ -21,8 +22,12 @@ use reth_revm::{
 use reth_rpc_api::TraceApiServer;
 use reth_rpc_types::{
     state::StateOverride,
-    trace::{filter::TraceFilter, parity::*, tracerequest::TraceCallRequest},
-    BlockError, BlockOverrides, CallRequest, Index,
+    trace::{
+        filter::{TraceFilter, TraceFilterMatcher},
+        parity::*,
+        tracerequest::TraceCallRequest,
+    },
+    BlockOverrides, CallRequest, Index,
 };
 use revm::{
     db::{CacheDB, DatabaseCommit},
@@ -47,8 +52,18 @@ impl<Provider, Eth> TraceApi<Provider, Eth> {
     }
 
     /// Create a new instance of the [TraceApi]
-    pub fn new(provider: Provider, eth_api: Eth, blocking_task_guard: BlockingTaskGuard) -> Self {
-        let inner = Arc::new(TraceApiInner { provider, eth_api, blocking_task_guard });
+    pub fn new(
+        provider: Provider,
+        eth_api: Eth,
+        blocking_task_guard: BlockingTaskGuard,
+        max_trace_filter_blocks: u64,
+    ) -> Self {
+        let inner = Arc::new(TraceApiInner {
+            provider,
+            eth_api,
+            blocking_task_guard,
+            max_trace_filter_blocks,
+        });
         Self { inner }
     }
 
@@ -227,83 +242,78 @@ where
         Ok(self.trace_transaction(hash).await?.and_then(|traces| traces.into_iter().nth(index)))
     }
 
-    /// Returns all transaction traces that match the given filter.
+    /// Returns all traces that match the given filter.
+    ///
+    /// Every call frame of a transaction is matched against the filter's `fromAddress` and
+    /// `toAddress`, not only the top-level call, and block rewards are matched against their
+    /// beneficiary. Matching traces are returned in block order, skipping the first `after`
+    /// matches and returning at most `count` traces.
     ///
-    /// This is similar to [Self::trace_block] but only returns traces for transactions that match
-    /// the filter.
+    /// The blocks of the range are traced in chunks, so that tracing stops as soon as `count`
+    /// traces have been found.
     pub async fn trace_filter(
         &self,
         filter: TraceFilter,
     ) -> EthResult<Vec<LocalizedTransactionTrace>> {
         let matcher = filter.matcher();
-        let TraceFilter { from_block, to_block, after: _after, count: _count, .. } = filter;
+        let TraceFilter { from_block, to_block, after, count, .. } = filter;
+        let best_block = self.provider().best_block_number()?;
         let start = from_block.unwrap_or(0);
-        let end = if let Some(to_block) = to_block {
-            to_block
-        } else {
-            self.provider().best_block_number()?
-        };
+        let end = to_block.unwrap_or(best_block);
 
-        // ensure that the range is not too large, since we need to fetch all blocks in the range
-        let distance = end.saturating_sub(start);
-        if distance > 100 {
+        if start > end {
             return Err(EthApiError::InvalidParams(
-                "Block range too large; currently limited to 100 blocks".to_string(),
+                "invalid parameters: fromBlock cannot be greater than toBlock".to_string(),
             ))
         }
+        if end > best_block {
+            return Err(EthApiError::InvalidParams(format!(
+                "invalid parameters: toBlock {end} is greater than latest block {best_block}"
+            )))
+        }
 
-        // fetch all blocks in that range
-        let blocks = self.provider().block_range(start..=end)?;
-
-        // find relevant blocks to trace
-        let mut target_blocks = Vec::new();
-        for block in blocks {
-            let mut transaction_indices = HashSet::new();
-            let mut highest_matching_index = 0;
-            for (tx_idx, tx) in block.body.iter().enumerate() {
-                let from = tx.recover_signer().ok_or(BlockError::InvalidSignature)?;
-                let to = tx.to();
-                if matcher.matches(from, to) {
-                    let idx = tx_idx as u64;
-                    transaction_indices.insert(idx);
-                    highest_matching_index = idx;
-                }
-            }
-            if !transaction_indices.is_empty() {
-                target_blocks.push((block.number, transaction_indices, highest_matching_index));
-            }
+        // ensure that the range is not too large, since we need to trace all blocks in the range
+        let distance = end - start;
+        if distance > self.inner.max_trace_filter_blocks {
+            return Err(EthApiError::InvalidParams(format!(
+                "Block range too large; currently limited to {} blocks",
+                self.inner.max_trace_filter_blocks
+            )))
         }
 
-        // trace all relevant blocks
-        let mut block_traces = Vec::with_capacity(target_blocks.len());
-        for (num, indices, highest_idx) in target_blocks {
-            let traces = self.inner.eth_api.trace_block_until(
-                num.into(),
-                Some(highest_idx),
-                TracingInspectorConfig::default_parity(),
-                move |tx_info, inspector, res, _, _| {
-                    if let Some(idx) = tx_info.index {
-                        if !indices.contains(&idx) {
-                            // only record traces for relevant transactions
-                            return Ok(None)
-                        }
-                    }
-                    let traces = inspector
-                        .with_transaction_gas_used(res.gas_used())
-                        .into_parity_builder()
-                        .into_localized_transaction_traces(tx_info);
-                    Ok(Some(traces))
-                },
-            );
-            block_traces.push(traces);
+        let mut skip = after.unwrap_or(0) as usize;
+        let count = count.map(|count| count as usize).unwrap_or(usize::MAX);
+        let mut all_traces = Vec::new();
+        if count == 0 {
+            return Ok(all_traces)
         }
 
-        let block_traces = futures::future::try_join_all(block_traces).await?;
-        let all_traces = block_traces
-            .into_iter()
-            .flatten()
-            .flat_map(|traces| traces.into_iter().flatten().flat_map(|traces| traces.into_iter()))
-            .collect();
+        let mut chunk_start = start;
+        while chunk_start <= end {
+            let chunk_end = chunk_start.saturating_add(TRACE_FILTER_CHUNK_SIZE - 1).min(end);
+
+            // trace all blocks of this chunk concurrently
+            let block_traces = futures::future::try_join_all(
+                (chunk_start..=chunk_end).map(|num| self.trace_block(num.into())),
+            )
+            .await?;
+
+            for trace in block_traces.into_iter().flatten().flatten() {
+                if !trace_matches(&matcher, &trace.trace) {
+                    continue
+                }
+                if skip > 0 {
+                    skip -= 1;
+                    continue
+                }
+                all_traces.push(trace);
+                if all_traces.len() == count {
+                    return Ok(all_traces)
+                }
+            }
+
+            chunk_start = chunk_end + 1;
+        }
 
         Ok(all_traces)
     }
@@ -499,8 +509,10 @@ where
     /// This is similar to `eth_getLogs` but for traces.
     ///
     /// # Limitations
-    /// This currently requires block filter fields, since reth does not have address indices yet.
+    /// This traces every block of the requested range, which is limited to the configured
+    /// maximum number of blocks.
     async fn trace_filter(&self, filter: TraceFilter) -> Result<Vec<LocalizedTransactionTrace>> {
+        let _permit = self.acquire_trace_permit().await;
         Ok(TraceApi::trace_filter(self, filter).await?)
     }
 
@@ -543,8 +555,34 @@ struct TraceApiInner<Provider, Eth> {
     eth_api: Eth,
     // restrict the number of concurrent calls to `trace_*`
     blocking_task_guard: BlockingTaskGuard,
+    /// Maximum number of blocks that could be scanned per `trace_filter` request.
+    max_trace_filter_blocks: u64,
+}
+
+/// Returns true if the given trace matches the filter.
+///
+/// Calls are matched by caller and callee, creations by creator and created contract,
+/// selfdestructs by the destroyed contract and refund address, and rewards by their beneficiary.
+fn trace_matches(matcher: &TraceFilterMatcher, trace: &TransactionTrace) -> bool {
+    match &trace.action {
+        Action::Call(call) => matcher.matches(call.from, Some(call.to)),
+        Action::Create(create) => {
+            let created = match &trace.result {
+                Some(TraceOutput::Create(output)) => Some(output.address),
+                _ => None,
+            };
+            matcher.matches(create.from, created)
+        }
+        Action::Selfdestruct(selfdestruct) => {
+            matcher.matches(selfdestruct.address, Some(selfdestruct.refund_address))
+        }
+        Action::Reward(reward) => matcher.matches(Address::ZERO, Some(reward.author)),
+    }
 }
 
+/// Number of blocks that are traced concurrently by `trace_filter`.
+const TRACE_FILTER_CHUNK_SIZE: u64 = 100;
+
 /// Helper to construct a [`LocalizedTransactionTrace`] that describes a reward to the block
 /// beneficiary.
 fn reward_trace(header: &SealedHeader, reward: RewardAction) -> LocalizedTransactionTrace {
@@ -562,3 +600,50 @@ fn reward_trace(header: &SealedHeader, reward: RewardAction) -> LocalizedTransac
         },
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call_trace(from: Address, to: Address) -> TransactionTrace {
+        TransactionTrace {
+            trace_address: vec![0],
+            subtraces: 0,
+            action: Action::Call(CallAction {
+                from,
+                call_type: CallType::Call,
+                gas: Default::default(),
+                input: Default::default(),
+                to,
+                value: Default::default(),
+            }),
+            error: None,
+            result: None,
+        }
+    }
+
+    #[test]
+    fn test_trace_matches() {
+        let (a, b, c) =
+            (Address::with_last_byte(1), Address::with_last_byte(2), Address::with_last_byte(3));
+        let filter = TraceFilter {
+            from_block: None,
+            to_block: None,
+            from_address: vec![],
+            to_address: vec![b],
+            mode: Default::default(),
+            after: None,
+            count: None,
+        };
+        let matcher = filter.matcher();
+
+        assert!(trace_matches(&matcher, &call_trace(a, b)));
+        assert!(!trace_matches(&matcher, &call_trace(b, c)));
+
+        let reward = reward_trace(
+            &SealedHeader::default(),
+            RewardAction { author: b, reward_type: RewardType::Block, value: U256::ZERO },
+        );
+        assert!(trace_matches(&matcher, &reward.trace));
+    }
+}
@@ -0,0 +1,525 @@
+use crate::{
+    eth::error::EthApiError,
+    result::{internal_rpc_err, invalid_params_rpc_err},
+};
+use async_trait::async_trait;
+use jsonrpsee::{core::RpcResult, types::ErrorObject};
+use reth_consensus_common::validation::{validate_block_standalone, validate_header_standalone};
+use reth_interfaces::{
+    consensus::ConsensusError, executor::BlockExecutionError, provider::ProviderError,
+};
+use reth_node_api::ConfigureEvmEnv;
+use reth_primitives::{
+    constants::MINIMUM_GAS_LIMIT, Address, GotExpected, Receipt, SealedBlock, B256, U256,
+};
+use reth_provider::{
+    BlockExecutor, ChainSpecProvider, ExecutorFactory, HeaderProvider, StateProvider,
+    StateProviderFactory, StateRootProvider,
+};
+use reth_revm::EvmProcessorFactory;
+use reth_rpc_api::BlockSubmissionValidationApiServer;
+use reth_rpc_types::{
+    relay::{
+        error::ValidateBuilderSubmissionEqualityError, BidTrace, BuilderBlockValidationRequest,
+        BuilderBlockValidationRequestV2, SubmitBlockRequest,
+    },
+    PayloadError,
+};
+use reth_rpc_types_compat::engine::payload::try_into_sealed_block;
+use reth_tasks::TaskSpawner;
+use std::sync::Arc;
+use tokio::sync::oneshot;
+
+/// The bound divisor of the gas limit, used to limit how much the gas limit can change between
+/// blocks.
+const GAS_LIMIT_BOUND_DIVISOR: u64 = 1024;
+
+/// `flashbots` API implementation.
+///
+/// This type provides the functionality for validating builder block submissions on behalf of a
+/// relay.
+pub struct ValidationApi<Provider, EvmConfig> {
+    inner: Arc<ValidationApiInner<Provider, EvmConfig>>,
+}
+
+// === impl ValidationApi ===
+
+impl<Provider, EvmConfig> ValidationApi<Provider, EvmConfig>
+where
+    Provider: ChainSpecProvider,
+{
+    /// Create a new instance of the [ValidationApi]
+    pub fn new(
+        provider: Provider,
+        evm_config: EvmConfig,
+        task_spawner: Box<dyn TaskSpawner>,
+    ) -> Self {
+        let executor_factory = EvmProcessorFactory::new(provider.chain_spec(), evm_config);
+        let inner = Arc::new(ValidationApiInner { provider, executor_factory, task_spawner });
+        Self { inner }
+    }
+}
+
+impl<Provider, EvmConfig> ValidationApi<Provider, EvmConfig>
+where
+    Provider: HeaderProvider + ChainSpecProvider + StateProviderFactory + 'static,
+    EvmConfig: ConfigureEvmEnv + 'static,
+{
+    /// Executes the closure on a new blocking task.
+    async fn on_blocking_task<F, R>(&self, f: F) -> RpcResult<R>
+    where
+        F: FnOnce(Self) -> Result<R, ValidationApiError> + Send + 'static,
+        R: Send + 'static,
+    {
+        let (tx, rx) = oneshot::channel();
+        let this = self.clone();
+        self.inner.task_spawner.spawn_blocking(Box::pin(async move {
+            let _ = tx.send(f(this));
+        }));
+        Ok(rx.await.map_err(|_| EthApiError::InternalEthError)??)
+    }
+
+    /// Validates the block of a builder submission.
+    ///
+    /// The submitted block must match the bid, build on a known parent and use the gas limit
+    /// derived from the proposer's registered gas limit. It is executed on top of the parent's
+    /// state and must pay at least the bid value to the proposer's fee recipient, either through
+    /// the fee recipient's balance change or through a payment transaction at the end of the
+    /// block.
+    ///
+    /// Caution: this is blocking and should be performed on a blocking task.
+    pub fn validate_builder_submission(
+        &self,
+        request: SubmitBlockRequest,
+        registered_gas_limit: u64,
+        withdrawals_root: Option<B256>,
+    ) -> Result<(), ValidationApiError> {
+        let SubmitBlockRequest { message, execution_payload, .. } = request;
+        let block = try_into_sealed_block(execution_payload, None)?;
+        compare_message_and_block(&message, &block)?;
+
+        if let Some(expected) = withdrawals_root {
+            if block.withdrawals_root != Some(expected) {
+                return Err(ValidationApiError::WithdrawalsRootMismatch {
+                    got: block.withdrawals_root,
+                    expected,
+                })
+            }
+        }
+
+        let provider = &self.inner.provider;
+        let chain_spec = provider.chain_spec();
+        let parent = provider
+            .header(&block.parent_hash)?
+            .ok_or(ValidationApiError::MissingParentBlock(block.parent_hash))?
+            .seal(block.parent_hash);
+
+        validate_header_standalone(&block.header, &chain_spec)?;
+        block.header.validate_against_parent(&parent, &chain_spec).map_err(ConsensusError::from)?;
+        validate_block_standalone(&block, &chain_spec)?;
+
+        let expected_gas_limit = calc_gas_limit(parent.gas_limit, registered_gas_limit);
+        if block.gas_limit != expected_gas_limit {
+            return Err(ValidationApiError::GasLimitMismatch(GotExpected {
+                got: block.gas_limit,
+                expected: expected_gas_limit,
+            }))
+        }
+
+        let total_difficulty = provider
+            .header_td(&block.parent_hash)?
+            .ok_or(ValidationApiError::MissingParentBlock(block.parent_hash))? +
+            block.difficulty;
+        let block_with_senders = block
+            .clone()
+            .unseal()
+            .with_recovered_senders()
+            .ok_or(ValidationApiError::InvalidTransactionSignature)?;
+
+        let state = provider.history_by_block_hash(block.parent_hash)?;
+        let mut executor = self.inner.executor_factory.with_state(&state);
+        executor.execute_and_verify_receipt(&block_with_senders, total_difficulty)?;
+        let output = executor.take_output_state();
+
+        let state_root = state.state_root(&output)?;
+        if state_root != block.state_root {
+            return Err(ValidationApiError::StateRootMismatch(GotExpected {
+                got: state_root,
+                expected: block.state_root,
+            }))
+        }
+
+        let fee_recipient = message.proposer_fee_recipient;
+        let balance_before = state.account_balance(fee_recipient)?.unwrap_or_default();
+        let balance_after = match output.account(&fee_recipient) {
+            Some(account) => account.map(|account| account.balance).unwrap_or_default(),
+            None => balance_before,
+        };
+        let payment_receipt =
+            output.receipts_by_block(block.number).last().and_then(Option::as_ref);
+
+        verify_proposer_payment(
+            &block,
+            payment_receipt,
+            fee_recipient,
+            message.value,
+            balance_before,
+            balance_after,
+        )
+    }
+}
+
+#[async_trait]
+impl<Provider, EvmConfig> BlockSubmissionValidationApiServer for ValidationApi<Provider, EvmConfig>
+where
+    Provider: HeaderProvider + ChainSpecProvider + StateProviderFactory + 'static,
+    EvmConfig: ConfigureEvmEnv + 'static,
+{
+    /// Handler for `flashbots_validateBuilderSubmissionV1`
+    async fn validate_builder_submission_v1(
+        &self,
+        request: BuilderBlockValidationRequest,
+    ) -> RpcResult<()> {
+        let BuilderBlockValidationRequest { request, registered_gas_limit } = request;
+        self.on_blocking_task(move |this| {
+            this.validate_builder_submission(request, registered_gas_limit, None)
+        })
+        .await
+    }
+
+    /// Handler for `flashbots_validateBuilderSubmissionV2`
+    async fn validate_builder_submission_v2(
+        &self,
+        request: BuilderBlockValidationRequestV2,
+    ) -> RpcResult<()> {
+        let BuilderBlockValidationRequestV2 { request, registered_gas_limit, withdrawals_root } =
+            request;
+        self.on_blocking_task(move |this| {
+            this.validate_builder_submission(request, registered_gas_limit, Some(withdrawals_root))
+        })
+        .await
+    }
+}
+
+impl<Provider, EvmConfig> std::fmt::Debug for ValidationApi<Provider, EvmConfig> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ValidationApi").finish_non_exhaustive()
+    }
+}
+
+impl<Provider, EvmConfig> Clone for ValidationApi<Provider, EvmConfig> {
+    fn clone(&self) -> Self {
+        Self { inner: Arc::clone(&self.inner) }
+    }
+}
+
+struct ValidationApiInner<Provider, EvmConfig> {
+    /// The provider that can interact with the chain.
+    provider: Provider,
+    /// Creates the executors the submitted blocks are executed with.
+    executor_factory: EvmProcessorFactory<EvmConfig>,
+    /// The type that can spawn tasks which would otherwise block.
+    task_spawner: Box<dyn TaskSpawner>,
+}
+
+/// Checks that the bid of a builder submission matches the submitted block.
+fn compare_message_and_block(
+    message: &BidTrace,
+    block: &SealedBlock,
+) -> Result<(), ValidateBuilderSubmissionEqualityError> {
+    if message.parent_hash != block.parent_hash {
+        return Err(ValidateBuilderSubmissionEqualityError::IncorrectParentHash {
+            expected: block.parent_hash,
+            actual: message.parent_hash,
+        })
+    }
+    if message.block_hash != block.hash() {
+        return Err(ValidateBuilderSubmissionEqualityError::IncorrectBlockHash {
+            expected: block.hash(),
+            actual: message.block_hash,
+        })
+    }
+    if message.gas_limit != block.gas_limit {
+        return Err(ValidateBuilderSubmissionEqualityError::IncorrectGasLimit {
+            expected: block.gas_limit,
+            actual: message.gas_limit,
+        })
+    }
+    if message.gas_used != block.gas_used {
+        return Err(ValidateBuilderSubmissionEqualityError::IncorrectGasUsed {
+            expected: block.gas_used,
+            actual: message.gas_used,
+        })
+    }
+    Ok(())
+}
+
+/// Checks that the proposer's fee recipient is paid at least the bid value, either through the
+/// balance change of the fee recipient or through the payment transaction at the end of the block.
+fn verify_proposer_payment(
+    block: &SealedBlock,
+    payment_receipt: Option<&Receipt>,
+    fee_recipient: Address,
+    value: U256,
+    balance_before: U256,
+    balance_after: U256,
+) -> Result<(), ValidationApiError> {
+    // the proposer is paid if its fee recipient's balance increased by at least the bid value
+    if balance_after >= balance_before.saturating_add(value) {
+        return Ok(())
+    }
+
+    // otherwise the last transaction of the block must be the payment to the proposer
+    let (Some(payment_tx), Some(payment_receipt)) = (block.body.last(), payment_receipt) else {
+        return Err(ValidationApiError::ProposerPaymentMissing)
+    };
+    if !payment_receipt.success {
+        return Err(ValidationApiError::ProposerPaymentFailed)
+    }
+    if payment_tx.to() != Some(fee_recipient) {
+        return Err(ValidationApiError::ProposerPaymentRecipient(payment_tx.to()))
+    }
+    let payment_value = U256::from(payment_tx.value());
+    if payment_value != value {
+        return Err(ValidationApiError::ProposerPaymentValue(GotExpected {
+            got: payment_value,
+            expected: value,
+        }))
+    }
+    if !payment_tx.input().is_empty() {
+        return Err(ValidationApiError::ProposerPaymentMalformed("contains calldata"))
+    }
+    let base_fee = block.base_fee_per_gas.unwrap_or_default() as u128;
+    if payment_tx.max_fee_per_gas() != base_fee {
+        return Err(ValidationApiError::ProposerPaymentMalformed(
+            "gas fee cap not equal to base fee",
+        ))
+    }
+    if payment_tx.max_priority_fee_per_gas().map_or(false, |tip| tip != 0 && tip != base_fee) {
+        return Err(ValidationApiError::ProposerPaymentMalformed("unexpected gas tip cap"))
+    }
+
+    Ok(())
+}
+
+/// Computes the gas limit of the block after the parent, moving the parent's gas limit towards the
+/// desired gas limit by at most the allowed change per block.
+fn calc_gas_limit(parent_gas_limit: u64, desired_gas_limit: u64) -> u64 {
+    let delta = (parent_gas_limit / GAS_LIMIT_BOUND_DIVISOR).saturating_sub(1);
+    let desired_gas_limit = desired_gas_limit.max(MINIMUM_GAS_LIMIT);
+    if parent_gas_limit < desired_gas_limit {
+        (parent_gas_limit + delta).min(desired_gas_limit)
+    } else {
+        parent_gas_limit.saturating_sub(delta).max(desired_gas_limit)
+    }
+}
+
+/// [ValidationApi] specific errors.
+#[derive(Debug, thiserror::Error)]
+pub enum ValidationApiError {
+    /// Thrown if the submitted payload can't be converted into a block.
+    #[error("invalid payload: {0}")]
+    Payload(#[from] PayloadError),
+    /// Thrown if the bid doesn't match the submitted block.
+    #[error(transparent)]
+    BidMismatch(#[from] ValidateBuilderSubmissionEqualityError),
+    /// Thrown if the withdrawals root of the block doesn't match the requested one.
+    #[error("incorrect withdrawals root {got:?}, expected {expected}")]
+    WithdrawalsRootMismatch {
+        /// The withdrawals root of the block.
+        got: Option<B256>,
+        /// The requested withdrawals root.
+        expected: B256,
+    },
+    /// Thrown if the parent of the block is not known.
+    #[error("missing parent block {0}")]
+    MissingParentBlock(B256),
+    /// Thrown if the gas limit of the block doesn't follow the registered gas limit.
+    #[error("incorrect gas limit set: {0}")]
+    GasLimitMismatch(GotExpected<u64>),
+    /// Thrown if the signer of a transaction of the block can't be recovered.
+    #[error("invalid transaction signature")]
+    InvalidTransactionSignature,
+    /// Thrown if the block is not valid according to the consensus rules.
+    #[error(transparent)]
+    Consensus(#[from] ConsensusError),
+    /// Thrown if the execution of the block failed.
+    #[error(transparent)]
+    Execution(#[from] BlockExecutionError),
+    /// Thrown if the state root of the block doesn't match the executed state.
+    #[error("mismatched state root: {0}")]
+    StateRootMismatch(GotExpected<B256>),
+    /// Thrown if the proposer is not paid and the block has no payment transaction.
+    #[error("proposer payment not enough and no proposer payment transaction")]
+    ProposerPaymentMissing,
+    /// Thrown if the proposer payment transaction failed.
+    #[error("proposer payment not successful")]
+    ProposerPaymentFailed,
+    /// Thrown if the proposer payment transaction is not sent to the proposer's fee recipient.
+    #[error("proposer payment not sent to the fee recipient: {0:?}")]
+    ProposerPaymentRecipient(Option<Address>),
+    /// Thrown if the value of the proposer payment transaction doesn't match the bid.
+    #[error("inaccurate proposer payment: {0}")]
+    ProposerPaymentValue(GotExpected<U256>),
+    /// Thrown if the proposer payment transaction is not a plain transfer at the base fee.
+    #[error("malformed proposer payment, {0}")]
+    ProposerPaymentMalformed(&'static str),
+    /// Thrown if the state required for the validation can't be read.
+    #[error(transparent)]
+    Provider(#[from] ProviderError),
+}
+
+impl From<ValidationApiError> for ErrorObject<'static> {
+    fn from(error: ValidationApiError) -> Self {
+        match error {
+            ValidationApiError::Provider(_) => internal_rpc_err(error.to_string()),
+            _ => invalid_params_rpc_err(error.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{
+        Bytes, Header, SealedHeader, Signature, Transaction, TransactionKind, TransactionSigned,
+        TxEip1559,
+    };
+
+    const BASE_FEE: u64 = 7;
+
+    fn payment_tx(to: Address, value: U256) -> TxEip1559 {
+        TxEip1559 {
+            chain_id: 1,
+            gas_limit: 21_000,
+            max_fee_per_gas: BASE_FEE as u128,
+            max_priority_fee_per_gas: 0,
+            to: TransactionKind::Call(to),
+            value: value.into(),
+            ..Default::default()
+        }
+    }
+
+    fn block_with_payment(tx: TxEip1559) -> SealedBlock {
+        let header = Header { base_fee_per_gas: Some(BASE_FEE), ..Default::default() };
+        SealedBlock {
+            header: SealedHeader::new(header, B256::ZERO),
+            body: vec![TransactionSigned::from_transaction_and_signature(
+                Transaction::Eip1559(tx),
+                Signature::default(),
+            )],
+            ..Default::default()
+        }
+    }
+
+    fn receipt(success: bool) -> Receipt {
+        Receipt { success, ..Default::default() }
+    }
+
+    #[test]
+    fn test_calc_gas_limit() {
+        // moves towards the desired gas limit by at most parent / 1024 - 1
+        assert_eq!(calc_gas_limit(30_000_000, 36_000_000), 30_029_295);
+        assert_eq!(calc_gas_limit(30_000_000, 20_000_000), 29_970_705);
+        // reaches the desired gas limit if it is within the bound
+        assert_eq!(calc_gas_limit(30_000_000, 30_010_000), 30_010_000);
+        assert_eq!(calc_gas_limit(30_000_000, 30_000_000), 30_000_000);
+        // never goes below the minimum gas limit
+        assert_eq!(calc_gas_limit(5_000, 0), MINIMUM_GAS_LIMIT);
+    }
+
+    #[test]
+    fn test_proposer_paid_by_balance_change() {
+        let fee_recipient = Address::random();
+        let value = U256::from(100);
+        let block = SealedBlock::default();
+
+        assert!(verify_proposer_payment(
+            &block,
+            None,
+            fee_recipient,
+            value,
+            U256::from(50),
+            U256::from(150)
+        )
+        .is_ok());
+        assert!(matches!(
+            verify_proposer_payment(
+                &block,
+                None,
+                fee_recipient,
+                value,
+                U256::from(50),
+                U256::from(149)
+            ),
+            Err(ValidationApiError::ProposerPaymentMissing)
+        ));
+    }
+
+    #[test]
+    fn test_proposer_paid_by_payment_tx() {
+        let fee_recipient = Address::random();
+        let value = U256::from(100);
+        let verify = |tx: TxEip1559, success: bool| {
+            let block = block_with_payment(tx);
+            verify_proposer_payment(
+                &block,
+                Some(&receipt(success)),
+                fee_recipient,
+                value,
+                U256::ZERO,
+                U256::ZERO,
+            )
+        };
+
+        assert!(verify(payment_tx(fee_recipient, value), true).is_ok());
+        // a tip equal to the base fee is allowed
+        let tx = TxEip1559 {
+            max_priority_fee_per_gas: BASE_FEE as u128,
+            ..payment_tx(fee_recipient, value)
+        };
+        assert!(verify(tx, true).is_ok());
+
+        assert!(matches!(
+            verify(payment_tx(fee_recipient, value), false),
+            Err(ValidationApiError::ProposerPaymentFailed)
+        ));
+        let other = Address::random();
+        assert!(matches!(
+            verify(payment_tx(other, value), true),
+            Err(ValidationApiError::ProposerPaymentRecipient(Some(to))) if to == other
+        ));
+        assert!(matches!(
+            verify(payment_tx(fee_recipient, U256::from(99)), true),
+            Err(ValidationApiError::ProposerPaymentValue(_))
+        ));
+        let tx = TxEip1559 { input: Bytes::from_static(&[1]), ..payment_tx(fee_recipient, value) };
+        assert!(matches!(
+            verify(tx, true),
+            Err(ValidationApiError::ProposerPaymentMalformed("contains calldata"))
+        ));
+        let tx =
+            TxEip1559 { max_fee_per_gas: BASE_FEE as u128 + 1, ..payment_tx(fee_recipient, value) };
+        assert!(matches!(
+            verify(tx, true),
+            Err(ValidationApiError::ProposerPaymentMalformed("gas fee cap not equal to base fee"))
+        ));
+        let tx = TxEip1559 { max_priority_fee_per_gas: 1, ..payment_tx(fee_recipient, value) };
+        assert!(matches!(
+            verify(tx, true),
+            Err(ValidationApiError::ProposerPaymentMalformed("unexpected gas tip cap"))
+        ));
+    }
+
+    #[test]
+    fn test_proposer_payment_missing_receipt() {
+        let fee_recipient = Address::random();
+        let value = U256::from(100);
+        let block = block_with_payment(payment_tx(fee_recipient, value));
+
+        assert!(matches!(
+            verify_proposer_payment(&block, None, fee_recipient, value, U256::ZERO, U256::ZERO),
+            Err(ValidationApiError::ProposerPaymentMissing)
+        ));
+    }
+}
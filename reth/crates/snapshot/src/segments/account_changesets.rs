@@ -0,0 +1,95 @@
+use crate::segments::{dataset_from_rows, keyed_segment_config, prepare_jar, Segment};
+use reth_db::{
+    cursor::DbCursorRO, database::Database, snapshot::account_changeset_key, table::Compress,
+    tables, transaction::DbTx,
+};
+use reth_interfaces::provider::ProviderResult;
+use reth_nippy_jar::ColumnResult;
+use reth_primitives::{
+    snapshot::{Compression, PerfectHashingFunction, SegmentConfig},
+    BlockNumber, SnapshotSegment,
+};
+use reth_provider::DatabaseProviderRO;
+use std::{ops::RangeInclusive, path::Path};
+
+/// Snapshot segment responsible for [SnapshotSegment::AccountChangesets] part of data.
+///
+/// Each row holds one changeset entry, with the columns `AccountBeforeTx` and `BlockNumber`. Rows
+/// are queried by [`account_changeset_key`].
+#[derive(Debug)]
+pub struct AccountChangesets {
+    config: SegmentConfig,
+}
+
+impl AccountChangesets {
+    /// Creates new instance of [AccountChangesets] snapshot segment.
+    pub fn new(compression: Compression, phf: PerfectHashingFunction) -> Self {
+        Self { config: keyed_segment_config(compression, phf) }
+    }
+}
+
+impl Default for AccountChangesets {
+    fn default() -> Self {
+        Self { config: SnapshotSegment::AccountChangesets.config() }
+    }
+}
+
+impl Segment for AccountChangesets {
+    fn segment(&self) -> SnapshotSegment {
+        SnapshotSegment::AccountChangesets
+    }
+
+    fn snapshot<DB: Database>(
+        &self,
+        provider: &DatabaseProviderRO<DB>,
+        directory: impl AsRef<Path>,
+        block_range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<()> {
+        let tx = provider.tx_ref();
+        let total_rows = tx
+            .cursor_read::<tables::AccountChangeSet>()?
+            .walk_range(block_range.clone())?
+            .try_fold(0, |rows, entry| entry.map(|_| rows + 1))?;
+
+        let mut jar = prepare_jar::<DB, 2>(
+            provider,
+            directory,
+            self.segment(),
+            self.config,
+            block_range.clone(),
+            total_rows,
+            || {
+                let mut cursor = tx.cursor_read::<tables::AccountChangeSet>()?;
+                dataset_from_rows(cursor.walk_range(block_range.clone())?.map(|entry| {
+                    let (block_number, account_before) = entry?;
+                    Ok([account_before.compress(), block_number.compress()])
+                }))
+            },
+        )?;
+
+        // Generate list of keys for filters & PHF
+        let mut keys_cursor = tx.cursor_read::<tables::AccountChangeSet>()?;
+        let keys = keys_cursor.walk_range(block_range.clone())?.map(|entry| {
+            entry
+                .map(|(block_number, account_before)| {
+                    account_changeset_key(block_number, account_before.address)
+                })
+                .map_err(|e| e.into())
+        });
+        jar.prepare_index(keys, total_rows)?;
+
+        let mut changesets_cursor = tx.cursor_read::<tables::AccountChangeSet>()?;
+        let mut blocks_cursor = tx.cursor_read::<tables::AccountChangeSet>()?;
+        let columns: Vec<Box<dyn Iterator<Item = ColumnResult<Vec<u8>>>>> = vec![
+            Box::new(changesets_cursor.walk_range(block_range.clone())?.map(|entry| {
+                entry.map(|(_, account_before)| account_before.compress()).map_err(|e| e.into())
+            })),
+            Box::new(blocks_cursor.walk_range(block_range)?.map(|entry| {
+                entry.map(|(block_number, _)| block_number.compress()).map_err(|e| e.into())
+            })),
+        ];
+        jar.freeze(columns, total_rows as u64)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,80 @@
+use crate::segments::{
+    block_number_list, dataset_from_rows, keyed_segment_config, prepare_jar, Segment,
+};
+use reth_db::{database::Database, snapshot::account_history_key, table::Compress};
+use reth_interfaces::provider::ProviderResult;
+use reth_nippy_jar::ColumnResult;
+use reth_primitives::{
+    snapshot::{Compression, PerfectHashingFunction, SegmentConfig},
+    BlockNumber, SnapshotSegment,
+};
+use reth_provider::{AccountExtReader, DatabaseProviderRO};
+use std::{ops::RangeInclusive, path::Path};
+
+/// Snapshot segment responsible for [SnapshotSegment::AccountHistory] part of data.
+///
+/// Each row holds the blocks of the snapshot range in which an account was changed, with the
+/// columns `BlockNumberList` and `Address`. Rows are queried by [`account_history_key`].
+///
+/// Unlike the `AccountHistory` table, the history is not sharded, so the lists of an account are
+/// spread over all snapshots of the segment.
+#[derive(Debug)]
+pub struct AccountHistory {
+    config: SegmentConfig,
+}
+
+impl AccountHistory {
+    /// Creates new instance of [AccountHistory] snapshot segment.
+    pub fn new(compression: Compression, phf: PerfectHashingFunction) -> Self {
+        Self { config: keyed_segment_config(compression, phf) }
+    }
+}
+
+impl Default for AccountHistory {
+    fn default() -> Self {
+        Self { config: SnapshotSegment::AccountHistory.config() }
+    }
+}
+
+impl Segment for AccountHistory {
+    fn segment(&self) -> SnapshotSegment {
+        SnapshotSegment::AccountHistory
+    }
+
+    fn snapshot<DB: Database>(
+        &self,
+        provider: &DatabaseProviderRO<DB>,
+        directory: impl AsRef<Path>,
+        block_range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<()> {
+        // The history is built from the changesets, same as in the `IndexAccountHistory` stage.
+        let history = provider.changed_accounts_and_blocks_with_range(block_range.clone())?;
+
+        let mut jar = prepare_jar::<DB, 2>(
+            provider,
+            directory,
+            self.segment(),
+            self.config,
+            block_range,
+            history.len(),
+            || {
+                dataset_from_rows(history.iter().map(|(address, blocks)| {
+                    Ok([block_number_list(blocks).compress(), address.compress()])
+                }))
+            },
+        )?;
+
+        jar.prepare_index(
+            history.keys().map(|address| Ok(account_history_key(*address))),
+            history.len(),
+        )?;
+
+        let columns: Vec<Box<dyn Iterator<Item = ColumnResult<Vec<u8>>>>> = vec![
+            Box::new(history.values().map(|blocks| Ok(block_number_list(blocks).compress()))),
+            Box::new(history.keys().map(|address| Ok(address.compress()))),
+        ];
+        jar.freeze(columns, history.len() as u64)?;
+
+        Ok(())
+    }
+}
@@ -9,11 +9,27 @@ pub use headers::Headers;
 mod receipts;
 pub use receipts::Receipts;
 
+mod transaction_senders;
+pub use transaction_senders::TransactionSenders;
+
+mod account_changesets;
+pub use account_changesets::AccountChangesets;
+
+mod storage_changesets;
+pub use storage_changesets::StorageChangesets;
+
+mod account_history;
+pub use account_history::AccountHistory;
+
+mod storage_history;
+pub use storage_history::StorageHistory;
+
 use reth_db::{
-    cursor::DbCursorRO, database::Database, table::Table, transaction::DbTx, RawKey, RawTable,
+    cursor::DbCursorRO, database::Database, table::Table, transaction::DbTx, BlockNumberList,
+    RawKey, RawTable,
 };
 use reth_interfaces::provider::ProviderResult;
-use reth_nippy_jar::NippyJar;
+use reth_nippy_jar::{checksum::DEFAULT_CHECKSUM_BLOCK_SIZE, NippyJar};
 use reth_primitives::{
     snapshot::{
         Compression, Filters, InclusionFilter, PerfectHashingFunction, SegmentConfig, SegmentHeader,
@@ -55,6 +71,34 @@ pub trait Segment: Default {
     }
 }
 
+/// Generates the dataset to train zstd dictionaries with the first rows (at most 1000) of a segment
+/// whose columns are not the values of a single table.
+pub(crate) fn dataset_from_rows<const COLUMNS: usize>(
+    rows: impl Iterator<Item = ProviderResult<[Vec<u8>; COLUMNS]>>,
+) -> ProviderResult<Rows<COLUMNS>> {
+    let mut dataset: Rows<COLUMNS> = std::array::from_fn(|_| Vec::new());
+    for row in rows.take(1000) {
+        for (column, value) in dataset.iter_mut().zip(row?) {
+            column.push(value);
+        }
+    }
+    Ok(dataset)
+}
+
+/// Returns the configuration of a segment whose rows are queried by key, which always requires
+/// an inclusion filter and a perfect hashing function.
+pub(crate) fn keyed_segment_config(
+    compression: Compression,
+    phf: PerfectHashingFunction,
+) -> SegmentConfig {
+    SegmentConfig { compression, filters: Filters::WithFilters(InclusionFilter::Cuckoo, phf) }
+}
+
+/// Returns the [`BlockNumberList`] of the blocks in which a key of a history segment was changed.
+pub(crate) fn block_number_list(blocks: &[u64]) -> BlockNumberList {
+    BlockNumberList::new_pre_sorted(blocks.iter().map(|block| *block as usize).collect::<Vec<_>>())
+}
+
 /// Returns a [`NippyJar`] according to the desired configuration. The `directory` parameter
 /// determines the snapshot file's save location.
 pub(crate) fn prepare_jar<DB: Database, const COLUMNS: usize>(
@@ -71,7 +115,8 @@ pub(crate) fn prepare_jar<DB: Database, const COLUMNS: usize>(
         COLUMNS,
         &directory.as_ref().join(segment.filename(&block_range, &tx_range).as_str()),
         SegmentHeader::new(block_range, tx_range, segment),
-    );
+    )
+    .with_checksums(DEFAULT_CHECKSUM_BLOCK_SIZE);
 
     nippy_jar = match segment_config.compression {
         Compression::Lz4 => nippy_jar.with_lz4(),
@@ -0,0 +1,101 @@
+use crate::segments::{dataset_from_rows, keyed_segment_config, prepare_jar, Segment};
+use reth_db::{
+    cursor::DbCursorRO, database::Database, models::BlockNumberAddress,
+    snapshot::storage_changeset_key, table::Compress, tables, transaction::DbTx,
+};
+use reth_interfaces::provider::ProviderResult;
+use reth_nippy_jar::ColumnResult;
+use reth_primitives::{
+    snapshot::{Compression, PerfectHashingFunction, SegmentConfig},
+    BlockNumber, SnapshotSegment,
+};
+use reth_provider::DatabaseProviderRO;
+use std::{ops::RangeInclusive, path::Path};
+
+/// Snapshot segment responsible for [SnapshotSegment::StorageChangesets] part of data.
+///
+/// Each row holds one changeset entry, with the columns `StorageEntry`, `BlockNumber` and
+/// `Address`. Rows are queried by [`storage_changeset_key`].
+#[derive(Debug)]
+pub struct StorageChangesets {
+    config: SegmentConfig,
+}
+
+impl StorageChangesets {
+    /// Creates new instance of [StorageChangesets] snapshot segment.
+    pub fn new(compression: Compression, phf: PerfectHashingFunction) -> Self {
+        Self { config: keyed_segment_config(compression, phf) }
+    }
+}
+
+impl Default for StorageChangesets {
+    fn default() -> Self {
+        Self { config: SnapshotSegment::StorageChangesets.config() }
+    }
+}
+
+impl Segment for StorageChangesets {
+    fn segment(&self) -> SnapshotSegment {
+        SnapshotSegment::StorageChangesets
+    }
+
+    fn snapshot<DB: Database>(
+        &self,
+        provider: &DatabaseProviderRO<DB>,
+        directory: impl AsRef<Path>,
+        block_range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<()> {
+        let tx = provider.tx_ref();
+        let range = BlockNumberAddress::range(block_range.clone());
+        let total_rows = tx
+            .cursor_read::<tables::StorageChangeSet>()?
+            .walk_range(range.clone())?
+            .try_fold(0, |rows, entry| entry.map(|_| rows + 1))?;
+
+        let mut jar = prepare_jar::<DB, 3>(
+            provider,
+            directory,
+            self.segment(),
+            self.config,
+            block_range,
+            total_rows,
+            || {
+                let mut cursor = tx.cursor_read::<tables::StorageChangeSet>()?;
+                dataset_from_rows(cursor.walk_range(range.clone())?.map(|entry| {
+                    let (BlockNumberAddress((block_number, address)), storage_entry) = entry?;
+                    Ok([storage_entry.compress(), block_number.compress(), address.compress()])
+                }))
+            },
+        )?;
+
+        // Generate list of keys for filters & PHF
+        let mut keys_cursor = tx.cursor_read::<tables::StorageChangeSet>()?;
+        let keys = keys_cursor.walk_range(range.clone())?.map(|entry| {
+            entry
+                .map(|(BlockNumberAddress((block_number, address)), storage_entry)| {
+                    storage_changeset_key(block_number, address, storage_entry.key)
+                })
+                .map_err(|e| e.into())
+        });
+        jar.prepare_index(keys, total_rows)?;
+
+        let mut changesets_cursor = tx.cursor_read::<tables::StorageChangeSet>()?;
+        let mut blocks_cursor = tx.cursor_read::<tables::StorageChangeSet>()?;
+        let mut addresses_cursor = tx.cursor_read::<tables::StorageChangeSet>()?;
+        let columns: Vec<Box<dyn Iterator<Item = ColumnResult<Vec<u8>>>>> =
+            vec![
+                Box::new(changesets_cursor.walk_range(range.clone())?.map(|entry| {
+                    entry.map(|(_, storage_entry)| storage_entry.compress()).map_err(|e| e.into())
+                })),
+                Box::new(blocks_cursor.walk_range(range.clone())?.map(|entry| {
+                    entry.map(|(key, _)| key.block_number().compress()).map_err(|e| e.into())
+                })),
+                Box::new(addresses_cursor.walk_range(range)?.map(|entry| {
+                    entry.map(|(key, _)| key.address().compress()).map_err(|e| e.into())
+                })),
+            ];
+        jar.freeze(columns, total_rows as u64)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,88 @@
+use crate::segments::{
+    block_number_list, dataset_from_rows, keyed_segment_config, prepare_jar, Segment,
+};
+use reth_db::{database::Database, snapshot::storage_history_key, table::Compress};
+use reth_interfaces::provider::ProviderResult;
+use reth_nippy_jar::ColumnResult;
+use reth_primitives::{
+    snapshot::{Compression, PerfectHashingFunction, SegmentConfig},
+    BlockNumber, SnapshotSegment,
+};
+use reth_provider::{DatabaseProviderRO, StorageReader};
+use std::{ops::RangeInclusive, path::Path};
+
+/// Snapshot segment responsible for [SnapshotSegment::StorageHistory] part of data.
+///
+/// Each row holds the blocks of the snapshot range in which a storage slot was changed, with the
+/// columns `BlockNumberList`, `Address` and the storage key. Rows are queried by
+/// [`storage_history_key`].
+///
+/// Unlike the `StorageHistory` table, the history is not sharded, so the lists of a storage slot
+/// are spread over all snapshots of the segment.
+#[derive(Debug)]
+pub struct StorageHistory {
+    config: SegmentConfig,
+}
+
+impl StorageHistory {
+    /// Creates new instance of [StorageHistory] snapshot segment.
+    pub fn new(compression: Compression, phf: PerfectHashingFunction) -> Self {
+        Self { config: keyed_segment_config(compression, phf) }
+    }
+}
+
+impl Default for StorageHistory {
+    fn default() -> Self {
+        Self { config: SnapshotSegment::StorageHistory.config() }
+    }
+}
+
+impl Segment for StorageHistory {
+    fn segment(&self) -> SnapshotSegment {
+        SnapshotSegment::StorageHistory
+    }
+
+    fn snapshot<DB: Database>(
+        &self,
+        provider: &DatabaseProviderRO<DB>,
+        directory: impl AsRef<Path>,
+        block_range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<()> {
+        // The history is built from the changesets, same as in the `IndexStorageHistory` stage.
+        let history = provider.changed_storages_and_blocks_with_range(block_range.clone())?;
+
+        let mut jar = prepare_jar::<DB, 3>(
+            provider,
+            directory,
+            self.segment(),
+            self.config,
+            block_range,
+            history.len(),
+            || {
+                dataset_from_rows(history.iter().map(|((address, storage_key), blocks)| {
+                    Ok([
+                        block_number_list(blocks).compress(),
+                        address.compress(),
+                        storage_key.compress(),
+                    ])
+                }))
+            },
+        )?;
+
+        jar.prepare_index(
+            history
+                .keys()
+                .map(|(address, storage_key)| Ok(storage_history_key(*address, *storage_key))),
+            history.len(),
+        )?;
+
+        let columns: Vec<Box<dyn Iterator<Item = ColumnResult<Vec<u8>>>>> = vec![
+            Box::new(history.values().map(|blocks| Ok(block_number_list(blocks).compress()))),
+            Box::new(history.keys().map(|(address, _)| Ok(address.compress()))),
+            Box::new(history.keys().map(|(_, storage_key)| Ok(storage_key.compress()))),
+        ];
+        jar.freeze(columns, history.len() as u64)?;
+
+        Ok(())
+    }
+}
@@ -49,12 +49,24 @@ pub struct SnapshotTargets {
     headers: Option<RangeInclusive<BlockNumber>>,
     receipts: Option<(RangeInclusive<BlockNumber>, RangeInclusive<TxNumber>)>,
     transactions: Option<(RangeInclusive<BlockNumber>, RangeInclusive<TxNumber>)>,
+    transaction_senders: Option<(RangeInclusive<BlockNumber>, RangeInclusive<TxNumber>)>,
+    account_changesets: Option<RangeInclusive<BlockNumber>>,
+    storage_changesets: Option<RangeInclusive<BlockNumber>>,
+    account_history: Option<RangeInclusive<BlockNumber>>,
+    storage_history: Option<RangeInclusive<BlockNumber>>,
 }
 
 impl SnapshotTargets {
     /// Returns `true` if any of the targets are [Some].
     pub fn any(&self) -> bool {
-        self.headers.is_some() || self.receipts.is_some() || self.transactions.is_some()
+        self.headers.is_some() ||
+            self.receipts.is_some() ||
+            self.transactions.is_some() ||
+            self.transaction_senders.is_some() ||
+            self.account_changesets.is_some() ||
+            self.storage_changesets.is_some() ||
+            self.account_history.is_some() ||
+            self.storage_history.is_some()
     }
 
     /// Returns `true` if all targets are either [None] or multiple of `block_interval`.
@@ -63,6 +75,11 @@ impl SnapshotTargets {
             self.headers.as_ref(),
             self.receipts.as_ref().map(|(blocks, _)| blocks),
             self.transactions.as_ref().map(|(blocks, _)| blocks),
+            self.transaction_senders.as_ref().map(|(blocks, _)| blocks),
+            self.account_changesets.as_ref(),
+            self.storage_changesets.as_ref(),
+            self.account_history.as_ref(),
+            self.storage_history.as_ref(),
         ]
         .iter()
         .all(|blocks| blocks.map_or(true, |blocks| (blocks.end() + 1) % block_interval == 0))
@@ -75,6 +92,14 @@ impl SnapshotTargets {
             (self.headers.as_ref(), snapshots.headers),
             (self.receipts.as_ref().map(|(blocks, _)| blocks), snapshots.receipts),
             (self.transactions.as_ref().map(|(blocks, _)| blocks), snapshots.transactions),
+            (
+                self.transaction_senders.as_ref().map(|(blocks, _)| blocks),
+                snapshots.transaction_senders,
+            ),
+            (self.account_changesets.as_ref(), snapshots.account_changesets),
+            (self.storage_changesets.as_ref(), snapshots.storage_changesets),
+            (self.account_history.as_ref(), snapshots.account_history),
+            (self.storage_history.as_ref(), snapshots.storage_history),
         ]
         .iter()
         .all(|(target, highest)| {
@@ -144,6 +169,21 @@ impl<DB: Database> Snapshotter<DB> {
         if let Some((block_number, _)) = &targets.transactions {
             self.highest_snapshots.transactions = Some(*block_number.end());
         }
+        if let Some((block_number, _)) = &targets.transaction_senders {
+            self.highest_snapshots.transaction_senders = Some(*block_number.end());
+        }
+        if let Some(block_number) = &targets.account_changesets {
+            self.highest_snapshots.account_changesets = Some(*block_number.end());
+        }
+        if let Some(block_number) = &targets.storage_changesets {
+            self.highest_snapshots.storage_changesets = Some(*block_number.end());
+        }
+        if let Some(block_number) = &targets.account_history {
+            self.highest_snapshots.account_history = Some(*block_number.end());
+        }
+        if let Some(block_number) = &targets.storage_history {
+            self.highest_snapshots.storage_history = Some(*block_number.end());
+        }
     }
 
     /// Looks into the snapshot directory to find the highest snapshotted block of each segment, and
@@ -186,8 +226,20 @@ impl<DB: Database> Snapshotter<DB> {
             targets.transactions.clone().map(|(range, _)| range),
         )?;
 
+        self.run_segment::<segments::TransactionSenders>(
+            targets.transaction_senders.clone().map(|(range, _)| range),
+        )?;
+
         self.run_segment::<segments::Headers>(targets.headers.clone())?;
 
+        self.run_segment::<segments::AccountChangesets>(targets.account_changesets.clone())?;
+
+        self.run_segment::<segments::StorageChangesets>(targets.storage_changesets.clone())?;
+
+        self.run_segment::<segments::AccountHistory>(targets.account_history.clone())?;
+
+        self.run_segment::<segments::StorageHistory>(targets.storage_history.clone())?;
+
         self.update_highest_snapshots_tracker()?;
 
         Ok(targets)
@@ -240,6 +292,26 @@ impl<DB: Database> Snapshotter<DB> {
             self.get_snapshot_target_block_range(to_block_number, self.highest_snapshots.receipts);
         let transactions_block_range = self
             .get_snapshot_target_block_range(to_block_number, self.highest_snapshots.transactions);
+        let transaction_senders_block_range = self.get_snapshot_target_block_range(
+            to_block_number,
+            self.highest_snapshots.transaction_senders,
+        );
+        let account_changesets_block_range = self.get_snapshot_target_block_range(
+            to_block_number,
+            self.highest_snapshots.account_changesets,
+        );
+        let storage_changesets_block_range = self.get_snapshot_target_block_range(
+            to_block_number,
+            self.highest_snapshots.storage_changesets,
+        );
+        let account_history_block_range = self.get_snapshot_target_block_range(
+            to_block_number,
+            self.highest_snapshots.account_history,
+        );
+        let storage_history_block_range = self.get_snapshot_target_block_range(
+            to_block_number,
+            self.highest_snapshots.storage_history,
+        );
 
         // Calculate transaction ranges to snapshot
         let mut block_to_tx_number_cache = HashMap::default();
@@ -255,6 +327,12 @@ impl<DB: Database> Snapshotter<DB> {
             self.highest_snapshots.transactions,
             &transactions_block_range,
         )?;
+        let transaction_senders_tx_range = self.get_snapshot_target_tx_range(
+            &provider,
+            &mut block_to_tx_number_cache,
+            self.highest_snapshots.transaction_senders,
+            &transaction_senders_block_range,
+        )?;
 
         Ok(SnapshotTargets {
             headers: headers_block_range
@@ -275,6 +353,36 @@ impl<DB: Database> Snapshotter<DB> {
                 .expect("finalized block should be >= last transactions snapshot")
                 .ge(&(self.block_interval as usize))
                 .then_some((transactions_block_range, transactions_tx_range)),
+            transaction_senders: transaction_senders_block_range
+                .size_hint()
+                .1
+                .expect("finalized block should be >= last transaction senders snapshot")
+                .ge(&(self.block_interval as usize))
+                .then_some((transaction_senders_block_range, transaction_senders_tx_range)),
+            account_changesets: account_changesets_block_range
+                .size_hint()
+                .1
+                .expect("finalized block should be >= last account changesets snapshot")
+                .ge(&(self.block_interval as usize))
+                .then_some(account_changesets_block_range),
+            storage_changesets: storage_changesets_block_range
+                .size_hint()
+                .1
+                .expect("finalized block should be >= last storage changesets snapshot")
+                .ge(&(self.block_interval as usize))
+                .then_some(storage_changesets_block_range),
+            account_history: account_history_block_range
+                .size_hint()
+                .1
+                .expect("finalized block should be >= last account history snapshot")
+                .ge(&(self.block_interval as usize))
+                .then_some(account_history_block_range),
+            storage_history: storage_history_block_range
+                .size_hint()
+                .1
+                .expect("finalized block should be >= last storage history snapshot")
+                .ge(&(self.block_interval as usize))
+                .then_some(storage_history_block_range),
         })
     }
 
@@ -361,7 +469,12 @@ mod tests {
             SnapshotTargets {
                 headers: Some(0..=1),
                 receipts: Some((0..=1, 0..=3)),
-                transactions: Some((0..=1, 0..=3))
+                transactions: Some((0..=1, 0..=3)),
+                transaction_senders: Some((0..=1, 0..=3)),
+                account_changesets: Some(0..=1),
+                storage_changesets: Some(0..=1),
+                account_history: Some(0..=1),
+                storage_history: Some(0..=1),
             }
         );
         assert!(targets.is_multiple_of_block_interval(snapshotter.block_interval));
@@ -372,7 +485,16 @@ mod tests {
         // Nothing to snapshot, last snapshots state of snapshotter doesn't pass the thresholds
         assert_eq!(
             snapshotter.get_snapshot_targets(2),
-            Ok(SnapshotTargets { headers: None, receipts: None, transactions: None })
+            Ok(SnapshotTargets {
+                headers: None,
+                receipts: None,
+                transactions: None,
+                transaction_senders: None,
+                account_changesets: None,
+                storage_changesets: None,
+                account_history: None,
+                storage_history: None,
+            })
         );
 
         // Snapshot targets has data per part up to the passed finalized block number,
@@ -383,7 +505,12 @@ mod tests {
             SnapshotTargets {
                 headers: Some(2..=3),
                 receipts: Some((2..=3, 4..=7)),
-                transactions: Some((2..=3, 4..=7))
+                transactions: Some((2..=3, 4..=7)),
+                transaction_senders: Some((2..=3, 4..=7)),
+                account_changesets: Some(2..=3),
+                storage_changesets: Some(2..=3),
+                account_history: Some(2..=3),
+                storage_history: Some(2..=3),
             }
         );
         assert!(targets.is_multiple_of_block_interval(snapshotter.block_interval));
@@ -0,0 +1,260 @@
+use crate::{ExecInput, ExecOutput, Stage, StageError, UnwindInput, UnwindOutput};
+use reth_db::database::Database;
+use reth_primitives::stage::{StageCheckpoint, StageId};
+use reth_provider::{DatabaseProviderRW, HistoryWriter};
+use std::fmt::Debug;
+
+/// Stage is indexing the addresses touched by the transactions of each block, i.e. their senders,
+/// recipients and created contracts, and the addresses reached by their calls according to the
+/// account and storage changesets. For more information on index sharding take a look at
+/// [`reth_db::tables::AddressTransactions`].
+///
+/// This stage is optional and not part of the
+/// [`DefaultStages`][crate::sets::DefaultStages]. Once it has a checkpoint, the index is also
+/// kept up to date when blocks are inserted or unwound outside of the pipeline.
+#[derive(Debug)]
+pub struct IndexAddressTransactionsStage {
+    /// Number of blocks after which the control
+    /// flow will be returned to the pipeline for commit.
+    pub commit_threshold: u64,
+}
+
+impl IndexAddressTransactionsStage {
+    /// Create new instance of [IndexAddressTransactionsStage].
+    pub fn new(commit_threshold: u64) -> Self {
+        Self { commit_threshold }
+    }
+}
+
+impl Default for IndexAddressTransactionsStage {
+    fn default() -> Self {
+        Self { commit_threshold: 100_000 }
+    }
+}
+
+impl<DB: Database> Stage<DB> for IndexAddressTransactionsStage {
+    /// Return the id of the stage
+    fn id(&self) -> StageId {
+        StageId::IndexAddressTransactions
+    }
+
+    /// Execute the stage.
+    fn execute(
+        &mut self,
+        provider: &DatabaseProviderRW<DB>,
+        input: ExecInput,
+    ) -> Result<ExecOutput, StageError> {
+        if input.target_reached() {
+            return Ok(ExecOutput::done(input.checkpoint()))
+        }
+
+        let (range, is_final_range) = input.next_block_range_with_threshold(self.commit_threshold);
+
+        let indices = provider.transaction_addresses_and_blocks_with_range(range.clone())?;
+        // Insert touched addresses to the index
+        provider.insert_address_transactions_index(indices)?;
+
+        Ok(ExecOutput { checkpoint: StageCheckpoint::new(*range.end()), done: is_final_range })
+    }
+
+    /// Unwind the stage.
+    fn unwind(
+        &mut self,
+        provider: &DatabaseProviderRW<DB>,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        let (range, unwind_progress, _) =
+            input.unwind_block_range_with_threshold(self.commit_threshold);
+
+        provider.unwind_address_transactions_index(range)?;
+
+        Ok(UnwindOutput { checkpoint: StageCheckpoint::new(unwind_progress) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{
+        stage_test_suite_ext, ExecuteStageTestRunner, StageTestRunner, TestRunnerError,
+        TestStageDB, UnwindStageTestRunner,
+    };
+    use reth_db::{
+        models::{AccountBeforeTx, BlockNumberAddress, ShardedKey},
+        tables,
+        transaction::DbTxMut,
+        BlockNumberList,
+    };
+    use reth_interfaces::test_utils::{generators, generators::random_block_range};
+    use reth_primitives::{Address, BlockNumber, SealedBlock, StorageEntry, TransactionKind, B256};
+    use reth_provider::AddressTransactionsReader;
+    use std::collections::{BTreeMap, BTreeSet};
+
+    fn cast(
+        table: Vec<(ShardedKey<Address>, BlockNumberList)>,
+    ) -> BTreeMap<Address, Vec<BlockNumber>> {
+        table
+            .into_iter()
+            .map(|(k, v)| {
+                assert_eq!(k.highest_block_number, u64::MAX);
+                (k.key, v.iter(0).map(|i| i as BlockNumber).collect())
+            })
+            .collect()
+    }
+
+    /// Computes the expected index for the given blocks and changed addresses up to and including
+    /// `to`.
+    fn expected_index(
+        blocks: &[SealedBlock],
+        changed: &[(Address, BlockNumber)],
+        to: BlockNumber,
+    ) -> BTreeMap<Address, Vec<u64>> {
+        let mut index: BTreeMap<Address, BTreeSet<u64>> = BTreeMap::new();
+        for block in blocks.iter().filter(|block| block.number <= to) {
+            for transaction in &block.body {
+                let sender = transaction.recover_signer().expect("failed to recover sender");
+                let touched = match transaction.transaction.kind() {
+                    TransactionKind::Call(to) => *to,
+                    TransactionKind::Create => sender.create(transaction.transaction.nonce()),
+                };
+                for address in [sender, touched] {
+                    index.entry(address).or_default().insert(block.number);
+                }
+            }
+        }
+        for (address, block) in changed.iter().filter(|(_, block)| *block <= to) {
+            index.entry(*address).or_default().insert(*block);
+        }
+        index.into_iter().map(|(address, blocks)| (address, blocks.into_iter().collect())).collect()
+    }
+
+    #[tokio::test]
+    async fn insert_and_unwind_index() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(&mut rng, 0..=10, B256::ZERO, 1..3);
+        db.insert_blocks(blocks.iter(), None).unwrap();
+
+        // addresses only reached by the calls of the transactions
+        let (called, written) = (Address::random(), Address::random());
+        let changed = [(called, 3), (written, 4), (called, 7)];
+        db.commit(|tx| {
+            tx.put::<tables::AccountChangeSet>(3, AccountBeforeTx { address: called, info: None })?;
+            tx.put::<tables::StorageChangeSet>(
+                BlockNumberAddress((4, written)),
+                StorageEntry::default(),
+            )?;
+            tx.put::<tables::StorageChangeSet>(
+                BlockNumberAddress((7, called)),
+                StorageEntry::default(),
+            )?;
+            Ok(())
+        })
+        .unwrap();
+
+        // run
+        let input = ExecInput { target: Some(10), ..Default::default() };
+        let mut stage = IndexAddressTransactionsStage::default();
+        let provider = db.factory.provider_rw().unwrap();
+        let out = stage.execute(&provider, input).unwrap();
+        assert_eq!(out, ExecOutput { checkpoint: StageCheckpoint::new(10), done: true });
+        provider.commit().unwrap();
+
+        // verify
+        let expected = expected_index(&blocks, &changed, 10);
+        assert_eq!(expected[&called], vec![3, 7]);
+        assert_eq!(cast(db.table::<tables::AddressTransactions>().unwrap()), expected);
+
+        // verify the reader
+        let provider = db.factory.provider().unwrap();
+        for (address, blocks) in &expected {
+            assert_eq!(&provider.address_blocks_after(*address, 0, usize::MAX).unwrap(), blocks);
+            assert_eq!(
+                provider.address_blocks_before(*address, 11, usize::MAX).unwrap(),
+                blocks.iter().rev().copied().collect::<Vec<_>>()
+            );
+            assert_eq!(provider.address_blocks_before(*address, 11, 1).unwrap().len(), 1);
+        }
+        drop(provider);
+
+        // unwind
+        let input = UnwindInput {
+            checkpoint: StageCheckpoint::new(10),
+            unwind_to: 5,
+            ..Default::default()
+        };
+        let provider = db.factory.provider_rw().unwrap();
+        let out = stage.unwind(&provider, input).unwrap();
+        assert_eq!(out, UnwindOutput { checkpoint: StageCheckpoint::new(5) });
+        provider.commit().unwrap();
+
+        // verify
+        assert_eq!(
+            cast(db.table::<tables::AddressTransactions>().unwrap()),
+            expected_index(&blocks, &changed, 5)
+        );
+    }
+
+    stage_test_suite_ext!(IndexAddressTransactionsTestRunner, index_address_transactions);
+
+    #[derive(Default)]
+    struct IndexAddressTransactionsTestRunner {
+        db: TestStageDB,
+    }
+
+    impl StageTestRunner for IndexAddressTransactionsTestRunner {
+        type S = IndexAddressTransactionsStage;
+
+        fn db(&self) -> &TestStageDB {
+            &self.db
+        }
+
+        fn stage(&self) -> Self::S {
+            IndexAddressTransactionsStage::default()
+        }
+    }
+
+    impl ExecuteStageTestRunner for IndexAddressTransactionsTestRunner {
+        type Seed = Vec<SealedBlock>;
+
+        fn seed_execution(&mut self, input: ExecInput) -> Result<Self::Seed, TestRunnerError> {
+            let stage_progress = input.checkpoint().block_number;
+            let end = input.target();
+            let mut rng = generators::rng();
+
+            let blocks = random_block_range(&mut rng, stage_progress + 1..=end, B256::ZERO, 0..2);
+            self.db.insert_blocks(blocks.iter(), None)?;
+            Ok(blocks)
+        }
+
+        fn validate_execution(
+            &self,
+            input: ExecInput,
+            output: Option<ExecOutput>,
+        ) -> Result<(), TestRunnerError> {
+            if let Some(output) = output {
+                let start_block = input.next_block();
+                let end_block = output.checkpoint.block_number;
+                if start_block > end_block {
+                    return Ok(())
+                }
+
+                let provider = self.db.factory.provider()?;
+                let expected =
+                    provider.transaction_addresses_and_blocks_with_range(0..=end_block)?;
+                let table = cast(self.db.table::<tables::AddressTransactions>()?);
+                assert_eq!(table, expected);
+            }
+            Ok(())
+        }
+    }
+
+    impl UnwindStageTestRunner for IndexAddressTransactionsTestRunner {
+        fn validate_unwind(&self, input: UnwindInput) -> Result<(), TestRunnerError> {
+            let table = cast(self.db.table::<tables::AddressTransactions>()?);
+            assert!(table.values().flatten().all(|block| *block <= input.unwind_to));
+            Ok(())
+        }
+    }
+}
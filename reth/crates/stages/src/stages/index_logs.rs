@@ -0,0 +1,299 @@
+use crate::{ExecInput, ExecOutput, Stage, StageError, UnwindInput, UnwindOutput};
+use reth_db::database::Database;
+use reth_primitives::{
+    stage::{StageCheckpoint, StageId},
+    PruneCheckpoint, PruneMode, PruneSegment,
+};
+use reth_provider::{
+    DatabaseProviderRW, HistoryWriter, PruneCheckpointReader, PruneCheckpointWriter,
+};
+use std::fmt::Debug;
+
+/// Stage is indexing the addresses and topics of the logs found in the receipts generated in
+/// [`ExecutionStage`][crate::stages::ExecutionStage]. For more information on index sharding take
+/// a look at [`reth_db::tables::LogAddressIndex`] and [`reth_db::tables::LogTopicIndex`].
+///
+/// This stage is optional and not part of the
+/// [`DefaultStages`][crate::sets::DefaultStages]. Once it has a checkpoint, the index is also
+/// kept up to date when blocks are inserted or unwound outside of the pipeline.
+#[derive(Debug)]
+pub struct IndexLogsStage {
+    /// Number of blocks after which the control
+    /// flow will be returned to the pipeline for commit.
+    pub commit_threshold: u64,
+    /// Pruning configuration.
+    pub prune_mode: Option<PruneMode>,
+}
+
+impl IndexLogsStage {
+    /// Create new instance of [IndexLogsStage].
+    pub fn new(commit_threshold: u64, prune_mode: Option<PruneMode>) -> Self {
+        Self { commit_threshold, prune_mode }
+    }
+}
+
+impl Default for IndexLogsStage {
+    fn default() -> Self {
+        Self { commit_threshold: 100_000, prune_mode: None }
+    }
+}
+
+impl<DB: Database> Stage<DB> for IndexLogsStage {
+    /// Return the id of the stage
+    fn id(&self) -> StageId {
+        StageId::IndexLogs
+    }
+
+    /// Execute the stage.
+    fn execute(
+        &mut self,
+        provider: &DatabaseProviderRW<DB>,
+        mut input: ExecInput,
+    ) -> Result<ExecOutput, StageError> {
+        if let Some((target_prunable_block, prune_mode)) = self
+            .prune_mode
+            .map(|mode| mode.prune_target_block(input.target(), PruneSegment::LogIndex))
+            .transpose()?
+            .flatten()
+        {
+            if target_prunable_block > input.checkpoint().block_number {
+                input.checkpoint = Some(StageCheckpoint::new(target_prunable_block));
+
+                // Save prune checkpoint only if we don't have one already.
+                // Otherwise, pruner may skip the unpruned range of blocks.
+                if provider.get_prune_checkpoint(PruneSegment::LogIndex)?.is_none() {
+                    provider.save_prune_checkpoint(
+                        PruneSegment::LogIndex,
+                        PruneCheckpoint {
+                            block_number: Some(target_prunable_block),
+                            tx_number: None,
+                            prune_mode,
+                        },
+                    )?;
+                }
+            }
+        }
+
+        if input.target_reached() {
+            return Ok(ExecOutput::done(input.checkpoint()))
+        }
+
+        let (range, is_final_range) = input.next_block_range_with_threshold(self.commit_threshold);
+
+        let (addresses, topics) = provider.log_addresses_and_topics_with_range(range.clone())?;
+        // Insert log addresses and topics to the index
+        provider.insert_log_index(addresses, topics)?;
+
+        Ok(ExecOutput { checkpoint: StageCheckpoint::new(*range.end()), done: is_final_range })
+    }
+
+    /// Unwind the stage.
+    fn unwind(
+        &mut self,
+        provider: &DatabaseProviderRW<DB>,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        let (range, unwind_progress, _) =
+            input.unwind_block_range_with_threshold(self.commit_threshold);
+
+        provider.unwind_log_index(range)?;
+
+        Ok(UnwindOutput { checkpoint: StageCheckpoint::new(unwind_progress) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{
+        stage_test_suite_ext, ExecuteStageTestRunner, StageTestRunner, TestRunnerError,
+        TestStageDB, UnwindStageTestRunner,
+    };
+    use reth_db::{models::ShardedKey, tables, BlockNumberList};
+    use reth_interfaces::test_utils::{
+        generators,
+        generators::{random_block_range, random_log, random_receipt},
+    };
+    use reth_primitives::{Address, BlockNumber, Receipt, SealedBlock, B256};
+    use reth_provider::LogIndexReader;
+    use std::collections::BTreeMap;
+
+    fn cast<K: Ord>(table: Vec<(ShardedKey<K>, BlockNumberList)>) -> BTreeMap<K, Vec<BlockNumber>> {
+        table
+            .into_iter()
+            .map(|(k, v)| {
+                assert_eq!(k.highest_block_number, u64::MAX);
+                (k.key, v.iter(0).map(|i| i as BlockNumber).collect())
+            })
+            .collect()
+    }
+
+    /// Inserts a receipt with up to two logs for every transaction of the given blocks and
+    /// returns the receipts of each block.
+    fn insert_receipts(
+        db: &TestStageDB,
+        blocks: &[SealedBlock],
+    ) -> Vec<(BlockNumber, Vec<Receipt>)> {
+        let mut rng = generators::rng();
+        let mut tx_num = 0;
+        let mut rows = Vec::new();
+        let mut receipts = Vec::new();
+        for block in blocks {
+            let mut block_receipts = Vec::new();
+            for transaction in &block.body {
+                let mut receipt = random_receipt(&mut rng, transaction, Some(0));
+                receipt.logs =
+                    (0..tx_num % 3).map(|_| random_log(&mut rng, None, Some(2))).collect();
+                rows.push((tx_num, receipt.clone()));
+                block_receipts.push(receipt);
+                tx_num += 1;
+            }
+            receipts.push((block.number, block_receipts));
+        }
+        db.insert_receipts(rows).unwrap();
+        receipts
+    }
+
+    /// Computes the expected address and topic indices for the given receipts up to and including
+    /// `to`.
+    fn expected_index(
+        receipts: &[(BlockNumber, Vec<Receipt>)],
+        to: BlockNumber,
+    ) -> (BTreeMap<Address, Vec<u64>>, BTreeMap<B256, Vec<u64>>) {
+        let mut addresses: BTreeMap<Address, Vec<u64>> = BTreeMap::new();
+        let mut topics: BTreeMap<B256, Vec<u64>> = BTreeMap::new();
+        for (block_number, block_receipts) in receipts.iter().filter(|(number, _)| *number <= to) {
+            for log in block_receipts.iter().flat_map(|receipt| &receipt.logs) {
+                let blocks = addresses.entry(log.address).or_default();
+                if blocks.last() != Some(block_number) {
+                    blocks.push(*block_number);
+                }
+                for topic in &log.topics {
+                    let blocks = topics.entry(*topic).or_default();
+                    if blocks.last() != Some(block_number) {
+                        blocks.push(*block_number);
+                    }
+                }
+            }
+        }
+        (addresses, topics)
+    }
+
+    #[tokio::test]
+    async fn insert_and_unwind_index() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(&mut rng, 0..=10, B256::ZERO, 1..3);
+        db.insert_blocks(blocks.iter(), None).unwrap();
+        let receipts = insert_receipts(&db, &blocks);
+
+        // run
+        let input = ExecInput { target: Some(10), ..Default::default() };
+        let mut stage = IndexLogsStage::default();
+        let provider = db.factory.provider_rw().unwrap();
+        let out = stage.execute(&provider, input).unwrap();
+        assert_eq!(out, ExecOutput { checkpoint: StageCheckpoint::new(10), done: true });
+        provider.commit().unwrap();
+
+        // verify
+        let (addresses, topics) = expected_index(&receipts, 10);
+        assert_eq!(cast(db.table::<tables::LogAddressIndex>().unwrap()), addresses);
+        assert_eq!(cast(db.table::<tables::LogTopicIndex>().unwrap()), topics);
+
+        // verify the reader
+        let provider = db.factory.provider().unwrap();
+        for (address, blocks) in &addresses {
+            assert_eq!(&provider.log_address_blocks(*address, 0..=10).unwrap(), blocks);
+            assert_eq!(
+                provider.log_address_blocks(*address, 3..=7).unwrap(),
+                blocks.iter().copied().filter(|block| (3..=7).contains(block)).collect::<Vec<_>>()
+            );
+        }
+        for (topic, blocks) in &topics {
+            assert_eq!(&provider.log_topic_blocks(*topic, 0..=10).unwrap(), blocks);
+        }
+        drop(provider);
+
+        // unwind
+        let input = UnwindInput {
+            checkpoint: StageCheckpoint::new(10),
+            unwind_to: 5,
+            ..Default::default()
+        };
+        let provider = db.factory.provider_rw().unwrap();
+        let out = stage.unwind(&provider, input).unwrap();
+        assert_eq!(out, UnwindOutput { checkpoint: StageCheckpoint::new(5) });
+        provider.commit().unwrap();
+
+        // verify
+        let (addresses, topics) = expected_index(&receipts, 5);
+        assert_eq!(cast(db.table::<tables::LogAddressIndex>().unwrap()), addresses);
+        assert_eq!(cast(db.table::<tables::LogTopicIndex>().unwrap()), topics);
+    }
+
+    stage_test_suite_ext!(IndexLogsTestRunner, index_logs);
+
+    #[derive(Default)]
+    struct IndexLogsTestRunner {
+        db: TestStageDB,
+    }
+
+    impl StageTestRunner for IndexLogsTestRunner {
+        type S = IndexLogsStage;
+
+        fn db(&self) -> &TestStageDB {
+            &self.db
+        }
+
+        fn stage(&self) -> Self::S {
+            IndexLogsStage::default()
+        }
+    }
+
+    impl ExecuteStageTestRunner for IndexLogsTestRunner {
+        type Seed = Vec<SealedBlock>;
+
+        fn seed_execution(&mut self, input: ExecInput) -> Result<Self::Seed, TestRunnerError> {
+            let stage_progress = input.checkpoint().block_number;
+            let end = input.target();
+            let mut rng = generators::rng();
+
+            let blocks = random_block_range(&mut rng, stage_progress + 1..=end, B256::ZERO, 0..2);
+            self.db.insert_blocks(blocks.iter(), None)?;
+            insert_receipts(&self.db, &blocks);
+            Ok(blocks)
+        }
+
+        fn validate_execution(
+            &self,
+            input: ExecInput,
+            output: Option<ExecOutput>,
+        ) -> Result<(), TestRunnerError> {
+            if let Some(output) = output {
+                let start_block = input.next_block();
+                let end_block = output.checkpoint.block_number;
+                if start_block > end_block {
+                    return Ok(())
+                }
+
+                let provider = self.db.factory.provider()?;
+                let (addresses, topics) =
+                    provider.log_addresses_and_topics_with_range(0..=end_block)?;
+                assert_eq!(cast(self.db.table::<tables::LogAddressIndex>()?), addresses);
+                assert_eq!(cast(self.db.table::<tables::LogTopicIndex>()?), topics);
+            }
+            Ok(())
+        }
+    }
+
+    impl UnwindStageTestRunner for IndexLogsTestRunner {
+        fn validate_unwind(&self, input: UnwindInput) -> Result<(), TestRunnerError> {
+            let addresses = cast(self.db.table::<tables::LogAddressIndex>()?);
+            assert!(addresses.values().flatten().all(|block| *block <= input.unwind_to));
+            let topics = cast(self.db.table::<tables::LogTopicIndex>()?);
+            assert!(topics.values().flatten().all(|block| *block <= input.unwind_to));
+            Ok(())
+        }
+    }
+}
@@ -12,6 +12,10 @@ mod hashing_storage;
 mod headers;
 /// Index history of account changes
 mod index_account_history;
+/// Index transactions touching each address
+mod index_address_transactions;
+/// Index addresses and topics of logs
+mod index_logs;
 /// Index history of storage changes
 mod index_storage_history;
 /// Stage for computing state root.
@@ -30,6 +34,8 @@ pub use hashing_account::*;
 pub use hashing_storage::*;
 pub use headers::*;
 pub use index_account_history::*;
+pub use index_address_transactions::*;
+pub use index_logs::*;
 pub use index_storage_history::*;
 pub use merkle::*;
 pub use sender_recovery::*;
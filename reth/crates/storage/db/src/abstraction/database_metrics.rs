@@ -1,4 +1,5 @@
 use metrics::{counter, gauge, histogram, Label};
+use serde::Serialize;
 use std::sync::Arc;
 
 /// Represents a type that can report metrics, used mainly with the database. The `report_metrics`
@@ -35,12 +36,108 @@ pub trait DatabaseMetrics {
     }
 }
 
+/// Returns the gauge metrics of the page statistics of the given tables.
+///
+/// The sampled entry sizes are not reported, as sampling is too expensive for every scrape. They
+/// are printed by `reth db stats` instead.
+pub fn table_stats_gauge_metrics(stats: &[TableStats]) -> Vec<(&'static str, f64, Vec<Label>)> {
+    let mut metrics = Vec::new();
+    for stats in stats {
+        let table = stats.table;
+        metrics.push(("db.table_size", stats.size() as f64, vec![Label::new("table", table)]));
+        metrics.push((
+            "db.table_pages",
+            stats.leaf_pages as f64,
+            vec![Label::new("table", table), Label::new("type", "leaf")],
+        ));
+        metrics.push((
+            "db.table_pages",
+            stats.branch_pages as f64,
+            vec![Label::new("table", table), Label::new("type", "branch")],
+        ));
+        metrics.push((
+            "db.table_pages",
+            stats.overflow_pages as f64,
+            vec![Label::new("table", table), Label::new("type", "overflow")],
+        ));
+        metrics.push(("db.table_entries", stats.entries as f64, vec![Label::new("table", table)]));
+    }
+    metrics
+}
+
 impl<DB: DatabaseMetrics> DatabaseMetrics for Arc<DB> {
     fn report_metrics(&self) {
         <DB as DatabaseMetrics>::report_metrics(self)
     }
 }
 
+/// Size statistics of a database table.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TableStats {
+    /// Name of the table.
+    pub table: &'static str,
+    /// Number of entries in the table.
+    pub entries: usize,
+    /// Size of a page in bytes.
+    pub page_size: usize,
+    /// Number of branch pages.
+    pub branch_pages: usize,
+    /// Number of leaf pages.
+    pub leaf_pages: usize,
+    /// Number of overflow pages, holding values that don't fit into a leaf page.
+    pub overflow_pages: usize,
+    /// Sizes of a sample of the entries of the table.
+    pub sample: TableSample,
+}
+
+impl TableStats {
+    /// Returns the total size of the pages of the table in bytes.
+    pub fn size(&self) -> usize {
+        self.page_size * (self.branch_pages + self.leaf_pages + self.overflow_pages)
+    }
+}
+
+/// Sizes of the entries sampled from the start of a table.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct TableSample {
+    /// Number of sampled entries.
+    pub entries: usize,
+    /// Total size of the sampled keys in bytes.
+    pub key_bytes: usize,
+    /// Total size of the sampled values in bytes.
+    pub value_bytes: usize,
+    /// Total size of the sampled values in bytes, if each of them was compressed with LZ4 as in
+    /// snapshots.
+    pub compressed_value_bytes: usize,
+}
+
+impl TableSample {
+    /// Returns the average size of a key in bytes.
+    pub fn average_key_size(&self) -> f64 {
+        if self.entries == 0 {
+            return 0.0
+        }
+        self.key_bytes as f64 / self.entries as f64
+    }
+
+    /// Returns the average size of a value in bytes.
+    pub fn average_value_size(&self) -> f64 {
+        if self.entries == 0 {
+            return 0.0
+        }
+        self.value_bytes as f64 / self.entries as f64
+    }
+
+    /// Returns the estimated share of the value bytes, between 0 and 1, that compressing them
+    /// would save.
+    pub fn compression_savings(&self) -> f64 {
+        if self.value_bytes == 0 {
+            return 0.0
+        }
+        1.0 - (self.compressed_value_bytes as f64 / self.value_bytes as f64).min(1.0)
+    }
+}
+
 /// The type used to store metadata about the database.
 #[derive(Debug, Default)]
 pub struct DatabaseMetadataValue {
@@ -72,3 +169,24 @@ impl<DB: DatabaseMetadata> DatabaseMetadata for Arc<DB> {
         <DB as DatabaseMetadata>::metadata(self)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn table_sample_ratios() {
+        assert_eq!(TableSample::default().average_key_size(), 0.0);
+        assert_eq!(TableSample::default().compression_savings(), 0.0);
+
+        let sample =
+            TableSample { entries: 4, key_bytes: 32, value_bytes: 100, compressed_value_bytes: 25 };
+        assert_eq!(sample.average_key_size(), 8.0);
+        assert_eq!(sample.average_value_size(), 25.0);
+        assert_eq!(sample.compression_savings(), 0.75);
+
+        // Incompressible values don't save anything, instead of a negative share.
+        let sample = TableSample { compressed_value_bytes: 120, ..sample };
+        assert_eq!(sample.compression_savings(), 0.0);
+    }
+}
@@ -0,0 +1,464 @@
+//! Cursor of the in-memory database.
+
+use super::{table_index, Error, TableEntries, TablesEntries, TransactionKind, RO, RW};
+use crate::{
+    common::{PairResult, ValueOnlyResult},
+    cursor::{
+        DbCursorRO, DbCursorRW, DbDupCursorRO, DbDupCursorRW, DupWalker, RangeWalker,
+        ReverseWalker, Walker,
+    },
+    table::{Compress, DupSort, Encode, Table},
+    tables::utils::*,
+    DatabaseError,
+};
+use parking_lot::RwLock;
+use reth_interfaces::db::{DatabaseWriteError, DatabaseWriteOperation};
+use std::{
+    borrow::Cow,
+    collections::{BTreeSet, Bound},
+    fmt,
+    marker::PhantomData,
+    ops::RangeBounds,
+    sync::Arc,
+};
+
+/// Read only Cursor.
+pub type CursorRO<T> = Cursor<RO, T>;
+/// Read write cursor.
+pub type CursorRW<T> = Cursor<RW, T>;
+
+/// Encoded key and compressed value of an entry.
+type Entry = (Vec<u8>, Vec<u8>);
+
+/// Cursor of a table of the in-memory database.
+///
+/// The cursor is positioned at an entry, which might have been deleted since. Moving the cursor
+/// looks up the entries around that position, like MDBX does.
+pub struct Cursor<K: TransactionKind, T: Table> {
+    /// Tables of the transaction.
+    tables: Arc<RwLock<TablesEntries>>,
+    /// Index of the table in the tables.
+    table: usize,
+    /// Whether the table is a dupsort table.
+    dupsort: bool,
+    /// Entry the cursor is positioned at, if any.
+    position: Option<Entry>,
+    /// Phantom data to enforce encoding/decoding.
+    _dbi: PhantomData<(K, T)>,
+}
+
+impl<K: TransactionKind, T: Table> fmt::Debug for Cursor<K, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Cursor")
+            .field("table", &T::NAME)
+            .field("position", &self.position)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<K: TransactionKind, T: Table> Cursor<K, T> {
+    pub(crate) fn new(tables: Arc<RwLock<TablesEntries>>) -> Self {
+        let (table, dupsort) = table_index(T::NAME);
+        Self { tables, table, dupsort, position: None, _dbi: PhantomData }
+    }
+
+    /// Calls the closure with the entries of the table.
+    fn read<R>(&self, f: impl FnOnce(&TableEntries) -> R) -> R {
+        f(&self.tables.read()[self.table])
+    }
+
+    /// Positions the cursor at the entry, if any, and decodes it.
+    fn move_to(&mut self, entry: Option<Entry>) -> PairResult<T> {
+        if entry.is_some() {
+            self.position.clone_from(&entry);
+        }
+        decode::<T>(entry)
+    }
+
+    /// Returns the entry the cursor is positioned at, or the next one if it was deleted.
+    fn current_entry(&self) -> Option<Entry> {
+        let position = self.position.as_ref()?;
+        self.read(|entries| at_or_after(entries, position))
+    }
+
+    /// Returns the next value of the current key.
+    fn next_dup_entry(&mut self) -> Option<Entry> {
+        let entry = match &self.position {
+            Some((key, value)) => self.read(|entries| {
+                entries
+                    .get(key)?
+                    .range::<[u8], _>((Bound::Excluded(value.as_slice()), Bound::Unbounded))
+                    .next()
+                    .map(|value| (key.clone(), value.clone()))
+            }),
+            None => self.read(first),
+        };
+        if entry.is_some() {
+            self.position.clone_from(&entry);
+        }
+        entry
+    }
+
+    /// Returns the first value of the key that is greater or equal to the subkey.
+    fn get_both_range(&mut self, key: Vec<u8>, subkey: &[u8]) -> Option<Entry> {
+        let value = self.read(|entries| {
+            entries
+                .get(&key)?
+                .range::<[u8], _>((Bound::Included(subkey), Bound::Unbounded))
+                .next()
+                .cloned()
+        })?;
+        let entry = (key, value);
+        self.position = Some(entry.clone());
+        Some(entry)
+    }
+}
+
+impl<T: Table> Cursor<RW, T> {
+    /// Calls the closure with the mutable entries of the table.
+    fn write<R>(&self, f: impl FnOnce(&mut TableEntries) -> R) -> R {
+        f(Arc::make_mut(&mut self.tables.write()[self.table]))
+    }
+
+    /// Returns a write error of the operation on the key.
+    fn write_error(error: Error, operation: DatabaseWriteOperation, key: Vec<u8>) -> DatabaseError {
+        DatabaseWriteError { info: error.into(), operation, table_name: T::NAME, key }.into()
+    }
+
+    /// Inserts the entry, replacing the value of the key if the table is not dupsort, and
+    /// positions the cursor at it.
+    fn put(&mut self, key: Vec<u8>, value: Vec<u8>) {
+        let dupsort = self.dupsort;
+        self.write(|entries| {
+            let values = entries.entry(key.clone()).or_default();
+            if !dupsort {
+                values.clear();
+            }
+            values.insert(value.clone());
+        });
+        self.position = Some((key, value));
+    }
+}
+
+/// Decodes a `(key, value)` pair of the table.
+fn decode<T: Table>(entry: Option<Entry>) -> PairResult<T> {
+    entry.map(|(key, value)| decoder::<T>((Cow::Owned(key), Cow::Owned(value)))).transpose()
+}
+
+/// Returns the entry of the key with its first value.
+fn with_first_value((key, values): (&Vec<u8>, &BTreeSet<Vec<u8>>)) -> Option<Entry> {
+    values.first().map(|value| (key.clone(), value.clone()))
+}
+
+/// Returns the entry of the key with its last value.
+fn with_last_value((key, values): (&Vec<u8>, &BTreeSet<Vec<u8>>)) -> Option<Entry> {
+    values.last().map(|value| (key.clone(), value.clone()))
+}
+
+/// Returns the first entry of the table.
+fn first(entries: &TableEntries) -> Option<Entry> {
+    entries.first_key_value().and_then(with_first_value)
+}
+
+/// Returns the last entry of the table.
+fn last(entries: &TableEntries) -> Option<Entry> {
+    entries.last_key_value().and_then(with_last_value)
+}
+
+/// Returns the first entry whose key is greater or equal to the key.
+fn seek(entries: &TableEntries, key: &[u8]) -> Option<Entry> {
+    entries
+        .range::<[u8], _>((Bound::Included(key), Bound::Unbounded))
+        .next()
+        .and_then(with_first_value)
+}
+
+/// Returns the first entry whose key is greater than the key.
+fn after_key(entries: &TableEntries, key: &[u8]) -> Option<Entry> {
+    entries
+        .range::<[u8], _>((Bound::Excluded(key), Bound::Unbounded))
+        .next()
+        .and_then(with_first_value)
+}
+
+/// Returns the first entry that is greater or equal to the entry.
+fn at_or_after(entries: &TableEntries, (key, value): &Entry) -> Option<Entry> {
+    entries
+        .get(key)
+        .and_then(|values| {
+            values.range::<[u8], _>((Bound::Included(value.as_slice()), Bound::Unbounded)).next()
+        })
+        .map(|value| (key.clone(), value.clone()))
+        .or_else(|| after_key(entries, key))
+}
+
+/// Returns the first entry that is greater than the entry.
+fn after(entries: &TableEntries, (key, value): &Entry) -> Option<Entry> {
+    entries
+        .get(key)
+        .and_then(|values| {
+            values.range::<[u8], _>((Bound::Excluded(value.as_slice()), Bound::Unbounded)).next()
+        })
+        .map(|value| (key.clone(), value.clone()))
+        .or_else(|| after_key(entries, key))
+}
+
+/// Returns the last entry that is less than the entry.
+fn before(entries: &TableEntries, (key, value): &Entry) -> Option<Entry> {
+    entries
+        .get(key)
+        .and_then(|values| {
+            values
+                .range::<[u8], _>((Bound::Unbounded, Bound::Excluded(value.as_slice())))
+                .next_back()
+        })
+        .map(|value| (key.clone(), value.clone()))
+        .or_else(|| {
+            entries
+                .range::<[u8], _>((Bound::Unbounded, Bound::Excluded(key.as_slice())))
+                .next_back()
+                .and_then(with_last_value)
+        })
+}
+
+impl<K: TransactionKind, T: Table> DbCursorRO<T> for Cursor<K, T> {
+    fn first(&mut self) -> PairResult<T> {
+        self.position = self.read(first);
+        decode::<T>(self.position.clone())
+    }
+
+    fn seek_exact(&mut self, key: <T as Table>::Key) -> PairResult<T> {
+        let key: Vec<u8> = key.encode().into();
+        let entry =
+            self.read(|entries| entries.get(&key).and_then(|values| values.first().cloned()));
+        match entry {
+            Some(value) => self.move_to(Some((key, value))),
+            None => {
+                // Like MDBX, the cursor is still positioned at the next key.
+                self.position = self.read(|entries| seek(entries, &key));
+                Ok(None)
+            }
+        }
+    }
+
+    fn seek(&mut self, key: <T as Table>::Key) -> PairResult<T> {
+        self.position = self.read(|entries| seek(entries, key.encode().as_ref()));
+        decode::<T>(self.position.clone())
+    }
+
+    fn next(&mut self) -> PairResult<T> {
+        let entry = match &self.position {
+            Some(position) => self.read(|entries| after(entries, position)),
+            None => self.read(first),
+        };
+        self.move_to(entry)
+    }
+
+    fn prev(&mut self) -> PairResult<T> {
+        let entry = match &self.position {
+            Some(position) => self.read(|entries| before(entries, position)),
+            None => self.read(last),
+        };
+        self.move_to(entry)
+    }
+
+    fn last(&mut self) -> PairResult<T> {
+        self.position = self.read(last);
+        decode::<T>(self.position.clone())
+    }
+
+    fn current(&mut self) -> PairResult<T> {
+        decode::<T>(self.current_entry())
+    }
+
+    fn walk(&mut self, start_key: Option<T::Key>) -> Result<Walker<'_, T, Self>, DatabaseError> {
+        let start = if let Some(start_key) = start_key {
+            self.seek(start_key).transpose()
+        } else {
+            self.first().transpose()
+        };
+
+        Ok(Walker::new(self, start))
+    }
+
+    fn walk_range(
+        &mut self,
+        range: impl RangeBounds<T::Key>,
+    ) -> Result<RangeWalker<'_, T, Self>, DatabaseError> {
+        let start = match range.start_bound().cloned() {
+            Bound::Included(key) => self.seek(key),
+            Bound::Excluded(_key) => {
+                unreachable!("Rust doesn't allow for Bound::Excluded in starting bounds");
+            }
+            Bound::Unbounded => self.first(),
+        }
+        .transpose();
+        Ok(RangeWalker::new(self, start, range.end_bound().cloned()))
+    }
+
+    fn walk_back(
+        &mut self,
+        start_key: Option<T::Key>,
+    ) -> Result<ReverseWalker<'_, T, Self>, DatabaseError> {
+        let start =
+            if let Some(start_key) = start_key { self.seek(start_key) } else { self.last() }
+                .transpose();
+
+        Ok(ReverseWalker::new(self, start))
+    }
+}
+
+impl<K: TransactionKind, T: DupSort> DbDupCursorRO<T> for Cursor<K, T> {
+    /// Returns the next `(key, value)` pair of a DUPSORT table.
+    fn next_dup(&mut self) -> PairResult<T> {
+        let entry = self.next_dup_entry();
+        decode::<T>(entry)
+    }
+
+    /// Returns the next `(key, value)` pair skipping the duplicates.
+    fn next_no_dup(&mut self) -> PairResult<T> {
+        let entry = match &self.position {
+            Some((key, _)) => self.read(|entries| after_key(entries, key)),
+            None => self.read(first),
+        };
+        self.move_to(entry)
+    }
+
+    /// Returns the next `value` of a duplicate `key`.
+    fn next_dup_val(&mut self) -> ValueOnlyResult<T> {
+        self.next_dup_entry().map(|(_, value)| decode_one::<T>(Cow::Owned(value))).transpose()
+    }
+
+    fn seek_by_key_subkey(
+        &mut self,
+        key: <T as Table>::Key,
+        subkey: <T as DupSort>::SubKey,
+    ) -> ValueOnlyResult<T> {
+        self.get_both_range(key.encode().into(), subkey.encode().as_ref())
+            .map(|(_, value)| decode_one::<T>(Cow::Owned(value)))
+            .transpose()
+    }
+
+    /// Depending on its arguments, returns an iterator starting at:
+    /// - Some(key), Some(subkey): a `key` item whose data is >= than `subkey`
+    /// - Some(key), None: first item of a specified `key`
+    /// - None, Some(subkey): like first case, but in the first key
+    /// - None, None: first item in the table
+    /// of a DUPSORT table.
+    fn walk_dup(
+        &mut self,
+        key: Option<T::Key>,
+        subkey: Option<T::SubKey>,
+    ) -> Result<DupWalker<'_, T, Self>, DatabaseError> {
+        let start = match (key, subkey) {
+            (Some(key), Some(subkey)) => {
+                decode::<T>(self.get_both_range(key.encode().into(), subkey.encode().as_ref()))
+                    .transpose()
+            }
+            (Some(key), None) => self.seek_exact(key).transpose(),
+            (None, Some(subkey)) => {
+                if let Some((key, _)) = self.first()? {
+                    decode::<T>(self.get_both_range(key.encode().into(), subkey.encode().as_ref()))
+                        .transpose()
+                } else {
+                    Some(Err(DatabaseError::Read(Error::NotFound.into())))
+                }
+            }
+            (None, None) => self.first().transpose(),
+        };
+
+        Ok(DupWalker::<'_, T, Self> { cursor: self, start })
+    }
+}
+
+impl<T: Table> DbCursorRW<T> for Cursor<RW, T> {
+    /// Database operation that will update an existing row if a specified value already
+    /// exists in a table, and insert a new row if the specified value doesn't already exist
+    ///
+    /// For a DUPSORT table, `upsert` will not actually update-or-insert. If the key already exists,
+    /// it will append the value to the subkey, even if the subkeys are the same. So if you want
+    /// to properly upsert, you'll need to `seek_exact` & `delete_current` if the key+subkey was
+    /// found, before calling `upsert`.
+    fn upsert(&mut self, key: T::Key, value: T::Value) -> Result<(), DatabaseError> {
+        self.put(key.encode().into(), value.compress().into());
+        Ok(())
+    }
+
+    fn insert(&mut self, key: T::Key, value: T::Value) -> Result<(), DatabaseError> {
+        let key: Vec<u8> = key.encode().into();
+        if let Some(existing) = self.read(|entries| seek(entries, &key)).filter(|e| e.0 == key) {
+            self.position = Some(existing);
+            return Err(Self::write_error(
+                Error::KeyExist,
+                DatabaseWriteOperation::CursorInsert,
+                key,
+            ))
+        }
+        self.put(key, value.compress().into());
+        Ok(())
+    }
+
+    /// Appends the data to the end of the table. Consequently, the append operation
+    /// will fail if the inserted key is less than the last table key
+    fn append(&mut self, key: T::Key, value: T::Value) -> Result<(), DatabaseError> {
+        let key: Vec<u8> = key.encode().into();
+        if let Some(last) = self.read(last) {
+            // Dupsort tables accept more values of the last key.
+            if key < last.0 || (key == last.0 && !self.dupsort) {
+                self.position = Some(last);
+                return Err(Self::write_error(
+                    Error::KeyMismatch,
+                    DatabaseWriteOperation::CursorAppend,
+                    key,
+                ))
+            }
+        }
+        self.put(key, value.compress().into());
+        Ok(())
+    }
+
+    fn delete_current(&mut self) -> Result<(), DatabaseError> {
+        let Some((key, value)) = self.current_entry() else {
+            return Err(DatabaseError::Delete(Error::NotFound.into()))
+        };
+        self.write(|entries| {
+            if let Some(values) = entries.get_mut(&key) {
+                values.remove(&value);
+                if values.is_empty() {
+                    entries.remove(&key);
+                }
+            }
+        });
+        // The cursor stays at the deleted entry, so that it moves to its neighbours.
+        self.position = Some((key, value));
+        Ok(())
+    }
+}
+
+impl<T: DupSort> DbDupCursorRW<T> for Cursor<RW, T> {
+    fn delete_current_duplicates(&mut self) -> Result<(), DatabaseError> {
+        let Some(entry) = self.current_entry() else {
+            return Err(DatabaseError::Delete(Error::NotFound.into()))
+        };
+        self.write(|entries| entries.remove(&entry.0));
+        self.position = Some(entry);
+        Ok(())
+    }
+
+    fn append_dup(&mut self, key: T::Key, value: T::Value) -> Result<(), DatabaseError> {
+        let key: Vec<u8> = key.encode().into();
+        let value: Vec<u8> = value.compress().into();
+        if let Some(last) = self.read(last) {
+            if (&key, &value) <= (&last.0, &last.1) {
+                self.position = Some(last);
+                return Err(Self::write_error(
+                    Error::KeyMismatch,
+                    DatabaseWriteOperation::CursorAppendDup,
+                    key,
+                ))
+            }
+        }
+        self.put(key, value);
+        Ok(())
+    }
+}
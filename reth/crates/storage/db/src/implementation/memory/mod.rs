@@ -0,0 +1,230 @@
+//! In-memory database, backed by ordered maps.
+
+use crate::{
+    database::Database,
+    database_metrics::{DatabaseMetadata, DatabaseMetadataValue, DatabaseMetrics},
+    tables::{TableType, Tables, NUM_TABLES},
+    DatabaseError,
+};
+use metrics::{gauge, Label};
+use parking_lot::{Condvar, Mutex, RwLock};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    str::FromStr,
+    sync::Arc,
+};
+use tx::Tx;
+
+pub mod cursor;
+pub mod tx;
+
+/// Entries of a table, as encoded keys and compressed values.
+///
+/// Tables that are not [`TableType::DupSort`] have exactly one value per key.
+pub(crate) type TableEntries = BTreeMap<Vec<u8>, BTreeSet<Vec<u8>>>;
+
+/// Entries of all tables, indexed by [`Tables`].
+///
+/// Tables are copied on their first write in a transaction, so transactions share the tables they
+/// didn't write to.
+pub(crate) type TablesEntries = Vec<Arc<TableEntries>>;
+
+/// Marker of the kind of a transaction.
+pub trait TransactionKind: private::Sealed + Send + Sync + std::fmt::Debug + 'static {}
+
+/// Marker of a read-only transaction.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct RO;
+
+/// Marker of a read-write transaction.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct RW;
+
+impl TransactionKind for RO {}
+impl TransactionKind for RW {}
+
+mod private {
+    pub trait Sealed {}
+
+    impl Sealed for super::RO {}
+    impl Sealed for super::RW {}
+}
+
+/// Errors of the in-memory database.
+///
+/// Codes and messages are the ones of the equivalent MDBX errors, so that both implementations
+/// return the same [`DatabaseError`]s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum Error {
+    /// The key/value pair already exists.
+    #[error("key/data pair already exists")]
+    KeyExist,
+    /// No matching key/value pair was found.
+    #[error("no matching key/data pair found")]
+    NotFound,
+    /// The key or value is out of order for an append.
+    #[error("the given key value is mismatched to the current cursor position")]
+    KeyMismatch,
+}
+
+impl From<Error> for i32 {
+    fn from(error: Error) -> i32 {
+        match error {
+            Error::KeyExist => -30799,
+            Error::NotFound => -30798,
+            Error::KeyMismatch => -30418,
+        }
+    }
+}
+
+/// Returns the index of the table in [`Tables`], and whether it's a [`TableType::DupSort`] table.
+pub(crate) fn table_index(name: &str) -> (usize, bool) {
+    let table = Tables::from_str(name).expect("Requested table should be part of `Tables`.");
+    (table as usize, table.table_type() == TableType::DupSort)
+}
+
+/// Database that keeps all tables in memory, with the transaction and cursor semantics of MDBX.
+///
+/// Read transactions are snapshots of the committed tables, and write transactions are
+/// serialized: [`Database::tx_mut`] blocks until the previous write transaction is closed. Nothing
+/// is persisted, so this is meant for tests and development nodes.
+#[derive(Debug, Clone)]
+pub struct MemoryDatabase {
+    /// Committed tables.
+    tables: Arc<RwLock<TablesEntries>>,
+    /// Lock of the write transaction.
+    write_lock: Arc<WriteLock>,
+}
+
+impl MemoryDatabase {
+    /// Creates a new empty database with all tables.
+    pub fn new() -> Self {
+        Self {
+            tables: Arc::new(RwLock::new(vec![Arc::default(); NUM_TABLES])),
+            write_lock: Arc::default(),
+        }
+    }
+}
+
+impl Default for MemoryDatabase {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Database for MemoryDatabase {
+    type TX = Tx<RO>;
+    type TXMut = Tx<RW>;
+
+    fn tx(&self) -> Result<Self::TX, DatabaseError> {
+        Ok(Tx::new(self.tables.read().clone(), None))
+    }
+
+    fn tx_mut(&self) -> Result<Self::TXMut, DatabaseError> {
+        // The lock is acquired first, so that no other transaction commits after the snapshot.
+        let guard = WriteGuard::new(self.write_lock.clone());
+        Ok(Tx::new(self.tables.read().clone(), Some((self.tables.clone(), guard))))
+    }
+}
+
+impl DatabaseMetrics for MemoryDatabase {
+    fn report_metrics(&self) {
+        for (name, value, labels) in self.gauge_metrics() {
+            gauge!(name, value, labels);
+        }
+    }
+
+    fn gauge_metrics(&self) -> Vec<(&'static str, f64, Vec<Label>)> {
+        let tables = self.tables.read().clone();
+        Tables::ALL
+            .iter()
+            .zip(tables.iter())
+            .map(|(table, entries)| {
+                let entries = entries.values().map(BTreeSet::len).sum::<usize>();
+                ("db.table_entries", entries as f64, vec![Label::new("table", table.name())])
+            })
+            .collect()
+    }
+}
+
+impl DatabaseMetadata for MemoryDatabase {
+    fn metadata(&self) -> DatabaseMetadataValue {
+        DatabaseMetadataValue::new(None)
+    }
+}
+
+/// Lock that allows a single write transaction at a time.
+#[derive(Debug, Default)]
+pub(crate) struct WriteLock {
+    /// Whether a write transaction is open.
+    locked: Mutex<bool>,
+    /// Notified when the write transaction is closed.
+    unlocked: Condvar,
+}
+
+/// Guard of the [`WriteLock`], which is released on drop.
+#[derive(Debug)]
+pub(crate) struct WriteGuard(Arc<WriteLock>);
+
+impl WriteGuard {
+    /// Waits until the lock is released by the previous write transaction, and acquires it.
+    fn new(lock: Arc<WriteLock>) -> Self {
+        let mut locked = lock.locked.lock();
+        while *locked {
+            lock.unlocked.wait(&mut locked);
+        }
+        *locked = true;
+        drop(locked);
+        Self(lock)
+    }
+}
+
+impl Drop for WriteGuard {
+    fn drop(&mut self) {
+        *self.0.locked.lock() = false;
+        self.0.unlocked.notify_one();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        tables,
+        test_utils::create_test_memory_db,
+        transaction::{DbTx, DbTxMut},
+    };
+    use reth_primitives::Header;
+    use std::{sync::mpsc, thread, time::Duration};
+
+    database_test_suite!(crate::test_utils::create_test_memory_db, crate::memory::Error);
+
+    #[test]
+    fn db_write_transactions_are_serialized() {
+        let db = create_test_memory_db();
+        let tx = db.tx_mut().expect(ERROR_INIT_TX);
+
+        let (sender, receiver) = mpsc::channel();
+        let handle = thread::spawn({
+            let db = db.clone();
+            move || {
+                let tx = db.tx_mut().expect(ERROR_INIT_TX);
+                sender.send(tx.get::<tables::Headers>(0).expect(ERROR_GET)).unwrap();
+            }
+        });
+        assert!(receiver.recv_timeout(Duration::from_millis(100)).is_err());
+
+        tx.put::<tables::Headers>(0, Header::default()).expect(ERROR_PUT);
+        tx.commit().expect(ERROR_COMMIT);
+
+        assert_eq!(receiver.recv().unwrap(), Some(Header::default()));
+        handle.join().unwrap();
+    }
+
+    const ERROR_INIT_TX: &str = "Failed to create a transaction.";
+    const ERROR_PUT: &str = "Not able to insert value into table.";
+    const ERROR_GET: &str = "Not able to get value from table.";
+    const ERROR_COMMIT: &str = "Not able to commit transaction.";
+}
@@ -0,0 +1,157 @@
+//! Transaction of the in-memory database.
+
+use super::{
+    cursor::Cursor, table_index, TableEntries, TablesEntries, TransactionKind, WriteGuard, RW,
+};
+use crate::{
+    table::{Compress, DupSort, Encode, Table, TableImporter},
+    tables::utils::decode_one,
+    transaction::{DbTx, DbTxMut},
+    DatabaseError,
+};
+use parking_lot::RwLock;
+use std::{borrow::Cow, collections::BTreeSet, fmt, marker::PhantomData, sync::Arc};
+
+/// Transaction of the in-memory database.
+///
+/// The transaction works on its own copy of the tables, which is shared with its cursors and
+/// replaces the committed tables of the database on [`DbTx::commit`].
+pub struct Tx<K: TransactionKind> {
+    /// Tables of the transaction.
+    tables: Arc<RwLock<TablesEntries>>,
+    /// Committed tables of the database and the write lock, for a write transaction.
+    writer: Option<(Arc<RwLock<TablesEntries>>, WriteGuard)>,
+    /// Kind of the transaction.
+    _kind: PhantomData<K>,
+}
+
+impl<K: TransactionKind> fmt::Debug for Tx<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Tx").field("read_only", &self.writer.is_none()).finish_non_exhaustive()
+    }
+}
+
+impl<K: TransactionKind> Tx<K> {
+    /// Creates a new transaction on a snapshot of the committed tables.
+    pub(crate) fn new(
+        tables: TablesEntries,
+        writer: Option<(Arc<RwLock<TablesEntries>>, WriteGuard)>,
+    ) -> Self {
+        Self { tables: Arc::new(RwLock::new(tables)), writer, _kind: PhantomData }
+    }
+
+    /// Creates a cursor of the table.
+    pub fn new_cursor<T: Table>(&self) -> Result<Cursor<K, T>, DatabaseError> {
+        Ok(Cursor::new(self.tables.clone()))
+    }
+
+    /// Calls the closure with the entries of the table.
+    fn with_entries<T: Table, R>(&self, f: impl FnOnce(&TableEntries) -> R) -> R {
+        let (table, _) = table_index(T::NAME);
+        f(&self.tables.read()[table])
+    }
+
+    /// Calls the closure with the mutable entries of the table, and whether it's a dupsort table.
+    fn with_entries_mut<T: Table, R>(&self, f: impl FnOnce(&mut TableEntries, bool) -> R) -> R {
+        let (table, dupsort) = table_index(T::NAME);
+        f(Arc::make_mut(&mut self.tables.write()[table]), dupsort)
+    }
+}
+
+impl TableImporter for Tx<RW> {}
+
+impl<K: TransactionKind> DbTx for Tx<K> {
+    type Cursor<T: Table> = Cursor<K, T>;
+    type DupCursor<T: DupSort> = Cursor<K, T>;
+
+    fn get<T: Table>(&self, key: T::Key) -> Result<Option<<T as Table>::Value>, DatabaseError> {
+        let value = self.with_entries::<T, _>(|entries| {
+            entries.get(key.encode().as_ref()).and_then(|values| values.first().cloned())
+        });
+        value.map(|value| decode_one::<T>(Cow::Owned(value))).transpose()
+    }
+
+    fn commit(self) -> Result<bool, DatabaseError> {
+        if let Some((committed, _guard)) = &self.writer {
+            *committed.write() = self.tables.read().clone();
+        }
+        Ok(false)
+    }
+
+    fn abort(self) {}
+
+    // Iterate over read only values in database.
+    fn cursor_read<T: Table>(&self) -> Result<Self::Cursor<T>, DatabaseError> {
+        self.new_cursor()
+    }
+
+    /// Iterate over read only values in database.
+    fn cursor_dup_read<T: DupSort>(&self) -> Result<Self::DupCursor<T>, DatabaseError> {
+        self.new_cursor()
+    }
+
+    /// Returns number of entries in the table.
+    fn entries<T: Table>(&self) -> Result<usize, DatabaseError> {
+        Ok(self.with_entries::<T, _>(|entries| entries.values().map(BTreeSet::len).sum()))
+    }
+
+    /// Read transactions are snapshots without a timeout, so there's nothing to disable.
+    fn disable_long_read_transaction_safety(&mut self) {}
+}
+
+impl DbTxMut for Tx<RW> {
+    type CursorMut<T: Table> = Cursor<RW, T>;
+    type DupCursorMut<T: DupSort> = Cursor<RW, T>;
+
+    fn put<T: Table>(&self, key: T::Key, value: T::Value) -> Result<(), DatabaseError> {
+        let key = key.encode().into();
+        let value = value.compress().into();
+        self.with_entries_mut::<T, _>(|entries, dupsort| {
+            let values = entries.entry(key).or_default();
+            if !dupsort {
+                values.clear();
+            }
+            values.insert(value);
+        });
+        Ok(())
+    }
+
+    fn delete<T: Table>(
+        &self,
+        key: T::Key,
+        value: Option<T::Value>,
+    ) -> Result<bool, DatabaseError> {
+        let key: Vec<u8> = key.encode().into();
+        let value: Option<Vec<u8>> = value.map(|value| value.compress().into());
+        Ok(self.with_entries_mut::<T, _>(|entries, _| {
+            let Some(values) = entries.get_mut(&key) else { return false };
+            let deleted = match value {
+                // Like MDBX, the value has to match also for tables that are not dupsort.
+                Some(value) => values.remove(&value),
+                None => {
+                    values.clear();
+                    true
+                }
+            };
+            if values.is_empty() {
+                entries.remove(&key);
+            }
+            deleted
+        }))
+    }
+
+    fn clear<T: Table>(&self) -> Result<(), DatabaseError> {
+        let (table, _) = table_index(T::NAME);
+        self.tables.write()[table] = Arc::default();
+
+        Ok(())
+    }
+
+    fn cursor_write<T: Table>(&self) -> Result<Self::CursorMut<T>, DatabaseError> {
+        self.new_cursor()
+    }
+
+    fn cursor_dup_write<T: DupSort>(&self) -> Result<Self::DupCursorMut<T>, DatabaseError> {
+        self.new_cursor()
+    }
+}
@@ -1,2 +1,7 @@
+#[cfg(test)]
+#[macro_use]
+mod test_suite;
+
 #[cfg(feature = "mdbx")]
 pub(crate) mod mdbx;
+pub(crate) mod memory;
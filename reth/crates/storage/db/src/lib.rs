@@ -67,6 +67,7 @@ pub mod abstraction;
 
 mod implementation;
 mod metrics;
+pub mod migration;
 pub mod snapshot;
 pub mod tables;
 mod utils;
@@ -79,6 +80,11 @@ pub mod mdbx {
     pub use reth_libmdbx::*;
 }
 
+/// In-memory database, with the same transaction and cursor semantics as MDBX.
+pub mod memory {
+    pub use crate::implementation::memory::*;
+}
+
 pub use abstraction::*;
 pub use reth_interfaces::db::{DatabaseError, DatabaseWriteOperation};
 pub use tables::*;
@@ -154,6 +160,7 @@ pub mod test_utils {
     use crate::{
         database::Database,
         database_metrics::{DatabaseMetadata, DatabaseMetadataValue, DatabaseMetrics},
+        memory::MemoryDatabase,
     };
     use reth_libmdbx::MaxReadTransactionDuration;
     use reth_primitives::fs;
@@ -246,6 +253,11 @@ pub mod test_utils {
         Arc::new(TempDatabase { db: Some(db), path })
     }
 
+    /// Create in-memory database for testing
+    pub fn create_test_memory_db() -> Arc<MemoryDatabase> {
+        Arc::new(MemoryDatabase::new())
+    }
+
     /// Create read/write database for testing
     pub fn create_test_rw_db_with_path<P: AsRef<Path>>(path: P) -> Arc<TempDatabase<DatabaseEnv>> {
         let path = path.as_ref().to_path_buf();
@@ -314,7 +326,7 @@ mod tests {
             )
         }
 
-        // Database is not empty, version file contains not matching version
+        // Database is not empty, version file contains an older version without migrations
         {
             fs::write(path.path().join(db_version_file_path(&path)), "0").unwrap();
             let db = init_db(&path, args);
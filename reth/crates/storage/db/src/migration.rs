@@ -0,0 +1,403 @@
+//! Database migrations, which upgrade the database from older versions to [`DB_VERSION`].
+//!
+//! Every [`Migration`] upgrades the database by exactly one version. It transforms the tables in
+//! batches, each in its own write transaction that also saves the [`MigrationCheckpoint`] of the
+//! migration, so that an interrupted migration resumes from its last committed batch. The database
+//! version file is only updated once all batches of a migration are committed.
+
+use crate::{
+    database::Database,
+    tables::{models::MigrationCheckpoint, MigrationCheckpoints},
+    transaction::{DbTx, DbTxMut},
+    version::{get_db_version, write_db_version_file, DatabaseVersionError, DB_VERSION},
+    DatabaseEnv, DatabaseError,
+};
+use reth_tracing::tracing::info;
+use std::{fmt, io, path::Path};
+
+/// The default number of entries that are migrated in a single write transaction.
+pub const DEFAULT_MIGRATION_BATCH_SIZE: usize = 10_000;
+
+/// Returns all migrations, ordered by the version they upgrade the database to.
+///
+/// The last migration has to upgrade the database to [`DB_VERSION`], so a new migration is added
+/// here whenever [`DB_VERSION`] is bumped.
+pub fn migrations<DB: Database>() -> Vec<Box<dyn Migration<DB>>> {
+    Vec::new()
+}
+
+/// Returns the versions that the [`migrations`] upgrade the database to, in order.
+///
+/// The versions don't depend on the database the migrations run on, so they're read from the
+/// migrations of a [`DatabaseEnv`].
+pub fn migration_versions() -> Vec<u64> {
+    migrations::<DatabaseEnv>().iter().map(|migration| migration.version()).collect()
+}
+
+/// Returns `true` if the [`migrations`] upgrade the database from the given version to
+/// [`DB_VERSION`].
+pub fn is_migratable(version: u64) -> bool {
+    is_migratable_with(version, &migration_versions())
+}
+
+/// Returns `true` if the given migration versions upgrade the database from the given version to
+/// [`DB_VERSION`].
+pub(crate) fn is_migratable_with(version: u64, migration_versions: &[u64]) -> bool {
+    version < DB_VERSION &&
+        (version + 1..=DB_VERSION).all(|version| migration_versions.contains(&version))
+}
+
+/// A migration that upgrades the database from the previous version to [`Migration::version`].
+pub trait Migration<DB: Database>: Send + Sync + fmt::Debug {
+    /// Returns the version the database is upgraded to.
+    fn version(&self) -> u64;
+
+    /// Returns a short description of the migration.
+    fn description(&self) -> &str;
+
+    /// Migrates at most `batch_size` entries, starting at the given position.
+    ///
+    /// The position is empty for the first batch, and the [`MigrationBatch::next`] position of
+    /// the previous batch otherwise. The transaction is only committed if the batch succeeds.
+    fn migrate_batch(
+        &self,
+        tx: &DB::TXMut,
+        position: &[u8],
+        batch_size: usize,
+    ) -> Result<MigrationBatch, DatabaseError>;
+}
+
+/// Result of a single batch of a [`Migration`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct MigrationBatch {
+    /// The number of migrated entries.
+    pub entries: u64,
+    /// The position of the next batch, or [`None`] if all entries were migrated.
+    pub next: Option<Vec<u8>>,
+}
+
+/// Errors of running the database migrations.
+#[derive(Debug, thiserror::Error)]
+pub enum MigrationError {
+    /// A migration failed to read or write the database.
+    #[error(transparent)]
+    Database(#[from] DatabaseError),
+    /// The database version couldn't be read, or the database is newer than [`DB_VERSION`].
+    #[error(transparent)]
+    Version(#[from] DatabaseVersionError),
+    /// The database version file couldn't be written.
+    #[error("failed to write the database version file: {0}")]
+    WriteVersion(#[from] io::Error),
+    /// There's no migration from the given version to the next one.
+    #[error("no migration upgrades the database from version {from}")]
+    MissingMigration {
+        /// The version without a migration.
+        from: u64,
+    },
+}
+
+/// Report of a [`Migration`] that was run by the [`Migrator`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationReport {
+    /// The version the database is upgraded to.
+    pub version: u64,
+    /// The description of the migration.
+    pub description: String,
+    /// The number of migrated entries.
+    pub entries: u64,
+    /// Whether all entries were migrated. This is `false` for the migrations that weren't run
+    /// in a dry run.
+    pub finished: bool,
+}
+
+/// Runs the pending migrations of a database.
+#[derive(Debug)]
+pub struct Migrator<'a, DB: Database> {
+    /// The migrated database.
+    db: &'a DB,
+    /// The path of the database, which contains the database version file.
+    db_path: &'a Path,
+    /// All migrations, ordered by version.
+    migrations: Vec<Box<dyn Migration<DB>>>,
+    /// The number of entries that are migrated in a single write transaction.
+    batch_size: usize,
+    /// Whether the changes are discarded instead of committed.
+    dry_run: bool,
+}
+
+impl<'a, DB: Database> Migrator<'a, DB> {
+    /// Creates a new migrator of the database at the given path.
+    pub fn new(db: &'a DB, db_path: &'a Path, migrations: Vec<Box<dyn Migration<DB>>>) -> Self {
+        Self { db, db_path, migrations, batch_size: DEFAULT_MIGRATION_BATCH_SIZE, dry_run: false }
+    }
+
+    /// Sets the number of entries that are migrated in a single write transaction.
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Sets whether the changes are discarded instead of committed.
+    ///
+    /// A dry run only runs the first pending migration, as the following ones depend on its
+    /// changes. The database and its version file are left untouched.
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Returns the migrations that upgrade the database from the given version to [`DB_VERSION`].
+    pub fn pending(&self, version: u64) -> Result<&[Box<dyn Migration<DB>>], MigrationError> {
+        if version > DB_VERSION {
+            return Err(DatabaseVersionError::VersionMismatch { version }.into())
+        }
+
+        let start = self.migrations.partition_point(|migration| migration.version() <= version);
+        let end = self.migrations.partition_point(|migration| migration.version() <= DB_VERSION);
+        let pending = &self.migrations[start..end];
+
+        let mut from = version;
+        for migration in pending {
+            if migration.version() != from + 1 {
+                break
+            }
+            from += 1;
+        }
+        if from != DB_VERSION {
+            return Err(MigrationError::MissingMigration { from })
+        }
+
+        Ok(pending)
+    }
+
+    /// Runs the pending migrations, and returns their reports.
+    pub fn run(&self) -> Result<Vec<MigrationReport>, MigrationError> {
+        let version = get_db_version(self.db_path)?;
+
+        let mut reports = Vec::new();
+        for migration in self.pending(version)? {
+            let report = if self.dry_run && !reports.is_empty() {
+                MigrationReport {
+                    version: migration.version(),
+                    description: migration.description().to_string(),
+                    entries: 0,
+                    finished: false,
+                }
+            } else {
+                self.migrate(migration.as_ref())?
+            };
+            reports.push(report);
+        }
+
+        Ok(reports)
+    }
+
+    /// Runs all batches of the migration, resuming from its checkpoint.
+    fn migrate(&self, migration: &dyn Migration<DB>) -> Result<MigrationReport, MigrationError> {
+        let version = migration.version();
+        let mut checkpoint =
+            self.db.view(|tx| tx.get::<MigrationCheckpoints>(version))??.unwrap_or_default();
+        if checkpoint.entries > 0 && !checkpoint.finished {
+            info!(target: "storage::db::migration", version, entries = checkpoint.entries, "Resuming migration");
+        }
+
+        while !checkpoint.finished {
+            let tx = self.db.tx_mut()?;
+            let batch = migration.migrate_batch(&tx, &checkpoint.position, self.batch_size)?;
+
+            checkpoint = MigrationCheckpoint {
+                entries: checkpoint.entries + batch.entries,
+                finished: batch.next.is_none(),
+                position: batch.next.unwrap_or_default().into(),
+            };
+            if self.dry_run {
+                // The next batch starts at the position of the discarded changes.
+                tx.abort();
+            } else {
+                tx.put::<MigrationCheckpoints>(version, checkpoint.clone())?;
+                tx.commit()?;
+            }
+
+            info!(target: "storage::db::migration", version, entries = checkpoint.entries, "Migrated batch");
+        }
+
+        if !self.dry_run {
+            write_db_version_file(self.db_path, version)?;
+            info!(target: "storage::db::migration", version, "Database migrated");
+        }
+
+        Ok(MigrationReport {
+            version,
+            description: migration.description().to_string(),
+            entries: checkpoint.entries,
+            finished: checkpoint.finished,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        cursor::DbCursorRO,
+        table::{Decode, Encode},
+        tables,
+        test_utils::create_test_rw_db,
+    };
+    use assert_matches::assert_matches;
+    use reth_primitives::B256;
+
+    /// Sets the canonical hash of every block to its number.
+    #[derive(Debug)]
+    struct TestMigration {
+        version: u64,
+        fail_at: Option<u64>,
+    }
+
+    impl<DB: Database> Migration<DB> for TestMigration {
+        fn version(&self) -> u64 {
+            self.version
+        }
+
+        fn description(&self) -> &str {
+            "Set canonical hashes to block numbers"
+        }
+
+        fn migrate_batch(
+            &self,
+            tx: &DB::TXMut,
+            position: &[u8],
+            batch_size: usize,
+        ) -> Result<MigrationBatch, DatabaseError> {
+            let start = if position.is_empty() { 0 } else { u64::decode(position)? };
+            let numbers = tx
+                .cursor_read::<tables::CanonicalHeaders>()?
+                .walk(Some(start))?
+                .take(batch_size + 1)
+                .map(|entry| entry.map(|(number, _)| number))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            let mut batch = MigrationBatch::default();
+            for &number in numbers.iter().take(batch_size) {
+                if self.fail_at == Some(number) {
+                    return Err(DatabaseError::Decode)
+                }
+                tx.put::<tables::CanonicalHeaders>(number, B256::with_last_byte(number as u8))?;
+                batch.entries += 1;
+            }
+            batch.next = numbers.get(batch_size).map(|number| number.encode().to_vec());
+
+            Ok(batch)
+        }
+    }
+
+    fn canonical_hashes<DB: Database>(db: &DB) -> Vec<B256> {
+        db.view(|tx| {
+            tx.cursor_read::<tables::CanonicalHeaders>()
+                .unwrap()
+                .walk(None)
+                .unwrap()
+                .map(|entry| entry.unwrap().1)
+                .collect()
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn migrations_upgrade_to_latest_version() {
+        let versions = migration_versions();
+        if let Some(first) = versions.first() {
+            assert_eq!(versions, (*first..=DB_VERSION).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn migratable_versions() {
+        assert!(is_migratable_with(DB_VERSION - 1, &[DB_VERSION]));
+        assert!(!is_migratable_with(DB_VERSION - 1, &[]));
+        assert!(!is_migratable_with(DB_VERSION, &[DB_VERSION]));
+        assert!(!is_migratable_with(DB_VERSION + 1, &[DB_VERSION]));
+    }
+
+    #[test]
+    fn migrate_resumes_from_checkpoint() {
+        let db = create_test_rw_db();
+        db.update(|tx| {
+            for number in 0..5 {
+                tx.put::<tables::CanonicalHeaders>(number, B256::ZERO).unwrap();
+            }
+        })
+        .unwrap();
+        write_db_version_file(db.path(), DB_VERSION - 1).unwrap();
+
+        let failing = TestMigration { version: DB_VERSION, fail_at: Some(3) };
+        let result =
+            Migrator::new(&db, db.path(), vec![Box::new(failing)]).with_batch_size(2).run();
+        assert_matches!(result, Err(MigrationError::Database(DatabaseError::Decode)));
+        assert_eq!(
+            db.view(|tx| tx.get::<MigrationCheckpoints>(DB_VERSION)).unwrap().unwrap(),
+            Some(MigrationCheckpoint {
+                entries: 2,
+                finished: false,
+                position: 2u64.encode().to_vec().into()
+            })
+        );
+        assert_eq!(get_db_version(db.path()).unwrap(), DB_VERSION - 1);
+
+        let migration = TestMigration { version: DB_VERSION, fail_at: None };
+        let reports =
+            Migrator::new(&db, db.path(), vec![Box::new(migration)]).with_batch_size(2).run();
+        assert_matches!(
+            reports.as_deref(),
+            Ok([MigrationReport { entries: 5, finished: true, .. }])
+        );
+        assert_eq!(get_db_version(db.path()).unwrap(), DB_VERSION);
+        assert_eq!(canonical_hashes(&db), (0..5).map(B256::with_last_byte).collect::<Vec<_>>());
+
+        // The database is up to date.
+        let reports = Migrator::new(&db, db.path(), Vec::new()).run();
+        assert_matches!(reports.as_deref(), Ok([]));
+    }
+
+    #[test]
+    fn migrate_dry_run() {
+        let db = create_test_rw_db();
+        db.update(|tx| {
+            for number in 0..5 {
+                tx.put::<tables::CanonicalHeaders>(number, B256::ZERO).unwrap();
+            }
+        })
+        .unwrap();
+        write_db_version_file(db.path(), DB_VERSION - 1).unwrap();
+
+        let migration = TestMigration { version: DB_VERSION, fail_at: None };
+        let reports = Migrator::new(&db, db.path(), vec![Box::new(migration)])
+            .with_batch_size(2)
+            .with_dry_run(true)
+            .run();
+        assert_matches!(
+            reports.as_deref(),
+            Ok([MigrationReport { entries: 5, finished: true, .. }])
+        );
+
+        assert_eq!(get_db_version(db.path()).unwrap(), DB_VERSION - 1);
+        assert_eq!(db.view(|tx| tx.entries::<MigrationCheckpoints>()).unwrap().unwrap(), 0);
+        assert_eq!(canonical_hashes(&db), vec![B256::ZERO; 5]);
+    }
+
+    #[test]
+    fn migrate_requires_all_migrations() {
+        let db = create_test_rw_db();
+        write_db_version_file(db.path(), DB_VERSION - 1).unwrap();
+
+        let result = Migrator::new(&db, db.path(), Vec::new()).run();
+        assert_matches!(result, Err(MigrationError::MissingMigration { from }) if from == DB_VERSION - 1);
+
+        write_db_version_file(db.path(), DB_VERSION + 1).unwrap();
+        let result = Migrator::new(&db, db.path(), Vec::new()).run();
+        assert_matches!(
+            result,
+            Err(MigrationError::Version(DatabaseVersionError::VersionMismatch { .. }))
+        );
+    }
+}
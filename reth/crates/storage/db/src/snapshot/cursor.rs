@@ -102,6 +102,12 @@ impl<'a> From<&'a B256> for KeyOrNumber<'a> {
     }
 }
 
+impl<'a> From<&'a [u8]> for KeyOrNumber<'a> {
+    fn from(value: &'a [u8]) -> Self {
+        KeyOrNumber::Key(value)
+    }
+}
+
 impl<'a> From<u64> for KeyOrNumber<'a> {
     fn from(value: u64) -> Self {
         KeyOrNumber::Number(value)
@@ -0,0 +1,38 @@
+//! Keys of the snapshot segments whose rows are queried by key instead of by block or transaction
+//! number.
+//!
+//! The perfect hashing function and inclusion filter of these segments are built over these keys,
+//! so they must be the same on creation and on lookup.
+
+use crate::{models::BlockNumberAddress, table::Encode};
+use reth_primitives::{Address, BlockNumber, B256};
+
+/// Returns the key of an account changeset row: the block number followed by the address.
+pub fn account_changeset_key(block_number: BlockNumber, address: Address) -> [u8; 28] {
+    BlockNumberAddress((block_number, address)).encode()
+}
+
+/// Returns the key of a storage changeset row: the block number, the address and the storage key.
+pub fn storage_changeset_key(
+    block_number: BlockNumber,
+    address: Address,
+    storage_key: B256,
+) -> [u8; 60] {
+    let mut key = [0u8; 60];
+    key[..28].copy_from_slice(&account_changeset_key(block_number, address));
+    key[28..].copy_from_slice(storage_key.as_slice());
+    key
+}
+
+/// Returns the key of an account history row: the address.
+pub fn account_history_key(address: Address) -> [u8; 20] {
+    address.0 .0
+}
+
+/// Returns the key of a storage history row: the address followed by the storage key.
+pub fn storage_history_key(address: Address, storage_key: B256) -> [u8; 52] {
+    let mut key = [0u8; 52];
+    key[..20].copy_from_slice(address.as_slice());
+    key[20..].copy_from_slice(storage_key.as_slice());
+    key
+}
@@ -1,11 +1,15 @@
-use super::{ReceiptMask, TransactionMask};
+use super::{
+    AccountChangesetMask, AccountHistoryMask, ReceiptMask, StorageChangesetMask,
+    StorageHistoryMask, TransactionMask, TransactionSenderMask,
+};
 use crate::{
     add_snapshot_mask,
-    snapshot::mask::{ColumnSelectorOne, ColumnSelectorTwo, HeaderMask},
+    snapshot::mask::{ColumnSelectorOne, ColumnSelectorThree, ColumnSelectorTwo, HeaderMask},
     table::Table,
-    CanonicalHeaders, HeaderTD, Receipts, Transactions,
+    AccountChangeSet, AccountHistory, CanonicalHeaders, HeaderTD, Receipts, StorageChangeSet,
+    StorageHistory, Transactions, TxSenders,
 };
-use reth_primitives::{BlockHash, Header};
+use reth_primitives::{Address, BlockHash, BlockNumber, Header, B256};
 
 // HEADER MASKS
 
@@ -26,3 +30,24 @@ add_snapshot_mask!(ReceiptMask, <Receipts as Table>::Value, 0b1);
 
 // TRANSACTION MASKS
 add_snapshot_mask!(TransactionMask, <Transactions as Table>::Value, 0b1);
+
+// TRANSACTION SENDER MASKS
+add_snapshot_mask!(TransactionSenderMask, <TxSenders as Table>::Value, 0b1);
+
+// ACCOUNT CHANGESET MASKS
+add_snapshot_mask!(AccountChangesetMask, <AccountChangeSet as Table>::Value, BlockNumber, 0b11);
+
+// STORAGE CHANGESET MASKS
+add_snapshot_mask!(
+    StorageChangesetMask,
+    <StorageChangeSet as Table>::Value,
+    BlockNumber,
+    Address,
+    0b111
+);
+
+// ACCOUNT HISTORY MASKS
+add_snapshot_mask!(AccountHistoryMask, <AccountHistory as Table>::Value, Address, 0b11);
+
+// STORAGE HISTORY MASKS
+add_snapshot_mask!(StorageHistoryMask, <StorageHistory as Table>::Value, Address, B256, 0b111);
@@ -10,7 +10,10 @@ use std::{
 pub use generation::*;
 
 mod cursor;
-pub use cursor::SnapshotCursor;
+pub use cursor::{KeyOrNumber, SnapshotCursor};
+
+mod keys;
+pub use keys::*;
 
 mod mask;
 pub use mask::*;
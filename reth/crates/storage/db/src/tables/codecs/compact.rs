@@ -47,7 +47,8 @@ impl_compression_for_compact!(
     TransactionSignedNoHash,
     CompactU256,
     StageCheckpoint,
-    PruneCheckpoint
+    PruneCheckpoint,
+    MigrationCheckpoint
 );
 
 macro_rules! impl_compression_fixed_compact {
@@ -30,7 +30,7 @@ use crate::{
             accounts::{AccountBeforeTx, BlockNumberAddress},
             blocks::{HeaderHash, StoredBlockOmmers},
             storage_sharded_key::StorageShardedKey,
-            ShardedKey, StoredBlockBodyIndices, StoredBlockWithdrawals,
+            MigrationCheckpoint, ShardedKey, StoredBlockBodyIndices, StoredBlockWithdrawals,
         },
     },
 };
@@ -38,7 +38,8 @@ use reth_primitives::{
     stage::StageCheckpoint,
     trie::{StorageTrieEntry, StoredBranchNode, StoredNibbles, StoredNibblesSubKey},
     Account, Address, BlockHash, BlockNumber, Bytecode, Header, IntegerList, PruneCheckpoint,
-    PruneSegment, Receipt, StorageEntry, TransactionSignedNoHash, TxHash, TxNumber, B256,
+    PruneSegment, Receipt, SnapshotSegment, StorageEntry, TransactionSignedNoHash, TxHash,
+    TxNumber, B256,
 };
 
 /// Enum for the types of tables present in libmdbx.
@@ -51,7 +52,7 @@ pub enum TableType {
 }
 
 /// Number of tables that should be present inside database.
-pub const NUM_TABLES: usize = 26;
+pub const NUM_TABLES: usize = 31;
 
 /// The general purpose of this is to use with a combination of Tables enum,
 /// by implementing a `TableViewer` trait you can operate on db tables in an abstract way.
@@ -210,12 +211,17 @@ tables!([
             Bytecodes,
             AccountHistory,
             StorageHistory,
+            AddressTransactions,
+            LogAddressIndex,
+            LogTopicIndex,
             HashedAccount,
             AccountsTrie,
             TxSenders,
             SyncStage,
             SyncStageProgress,
-            PruneCheckpoints
+            PruneCheckpoints,
+            SnapshotPruneCheckpoints,
+            MigrationCheckpoints
         ]
     ),
     (
@@ -389,6 +395,39 @@ table!(
     ( StorageHistory ) StorageShardedKey | BlockNumberList
 );
 
+table!(
+    /// Stores pointers to the blocks containing transactions that touched each address.
+    ///
+    /// An address is touched by a transaction if it is its sender, its recipient or the contract
+    /// created by it, or if the calls of the transaction changed its account or storage. The
+    /// table is only populated if the optional `IndexAddressTransactions` stage is enabled.
+    ///
+    /// Shards are laid out the same way as in [`AccountHistory`]: the last shard of an address is
+    /// keyed with `u64::MAX`.
+    ( AddressTransactions ) ShardedKey<Address> | BlockNumberList
+);
+
+table!(
+    /// Stores pointers to the blocks containing logs emitted by each address.
+    ///
+    /// The table is only populated if the optional `IndexLogs` stage is enabled.
+    ///
+    /// Shards are laid out the same way as in [`AccountHistory`]: the last shard of an address is
+    /// keyed with `u64::MAX`.
+    ( LogAddressIndex ) ShardedKey<Address> | BlockNumberList
+);
+
+table!(
+    /// Stores pointers to the blocks containing logs with each topic, regardless of the position of
+    /// the topic within the log.
+    ///
+    /// The table is only populated if the optional `IndexLogs` stage is enabled.
+    ///
+    /// Shards are laid out the same way as in [`AccountHistory`]: the last shard of a topic is
+    /// keyed with `u64::MAX`.
+    ( LogTopicIndex ) ShardedKey<B256> | BlockNumberList
+);
+
 dupsort!(
     /// Stores the state of an account before a certain transaction changed it.
     /// Change on state can be: account is created, selfdestructed, touched while empty
@@ -451,6 +490,17 @@ table!(
     ( PruneCheckpoints ) PruneSegment | PruneCheckpoint
 );
 
+table!(
+    /// Stores the highest block number of each snapshot segment whose data has been removed from
+    /// the database, since it's kept in the snapshots.
+    ( SnapshotPruneCheckpoints ) SnapshotSegment | PruneCheckpoint
+);
+
+table!(
+    /// Stores the progress of the database migration to each version.
+    ( MigrationCheckpoints ) u64 | MigrationCheckpoint
+);
+
 /// Alias Types
 
 /// List with transaction numbers.
@@ -479,12 +529,17 @@ mod tests {
         (TableType::Table, Bytecodes::NAME),
         (TableType::Table, AccountHistory::NAME),
         (TableType::Table, StorageHistory::NAME),
+        (TableType::Table, AddressTransactions::NAME),
+        (TableType::Table, LogAddressIndex::NAME),
+        (TableType::Table, LogTopicIndex::NAME),
         (TableType::Table, HashedAccount::NAME),
         (TableType::Table, AccountsTrie::NAME),
         (TableType::Table, TxSenders::NAME),
         (TableType::Table, SyncStage::NAME),
         (TableType::Table, SyncStageProgress::NAME),
         (TableType::Table, PruneCheckpoints::NAME),
+        (TableType::Table, SnapshotPruneCheckpoints::NAME),
+        (TableType::Table, MigrationCheckpoints::NAME),
         (TableType::DupSort, PlainStorageState::NAME),
         (TableType::DupSort, AccountChangeSet::NAME),
         (TableType::DupSort, StorageChangeSet::NAME),
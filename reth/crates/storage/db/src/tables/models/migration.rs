@@ -0,0 +1,21 @@
+//! Migration related models and types.
+
+use reth_codecs::{main_codec, Compact};
+use reth_primitives::Bytes;
+
+/// Progress of a database migration.
+///
+/// Saved after every batch, so that an interrupted migration resumes from the last committed
+/// batch.
+#[derive(Debug, Default, Eq, PartialEq, Clone)]
+#[main_codec]
+pub struct MigrationCheckpoint {
+    /// The number of entries migrated so far.
+    pub entries: u64,
+    /// Whether all entries were migrated.
+    pub finished: bool,
+    /// The migration-specific position to resume from, e.g. the next encoded key.
+    ///
+    /// NOTE: It has to be the last field, as it's decoded from the remaining bytes.
+    pub position: Bytes,
+}
@@ -7,17 +7,19 @@ use crate::{
 use reth_codecs::Compact;
 use reth_primitives::{
     trie::{StoredNibbles, StoredNibblesSubKey},
-    Address, PruneSegment, B256,
+    Address, PruneSegment, SnapshotSegment, B256,
 };
 
 pub mod accounts;
 pub mod blocks;
 pub mod integer_list;
+pub mod migration;
 pub mod sharded_key;
 pub mod storage_sharded_key;
 
 pub use accounts::*;
 pub use blocks::*;
+pub use migration::MigrationCheckpoint;
 pub use sharded_key::ShardedKey;
 
 /// Macro that implements [`Encode`] and [`Decode`] for uint types.
@@ -155,3 +157,21 @@ impl Decode for PruneSegment {
         Ok(Self::from_compact(buf, buf.len()).0)
     }
 }
+
+impl Encode for SnapshotSegment {
+    type Encoded = Vec<u8>;
+
+    // Encoded by name, same as in the snapshot file names
+    fn encode(self) -> Self::Encoded {
+        self.as_ref().as_bytes().to_vec()
+    }
+}
+
+impl Decode for SnapshotSegment {
+    fn decode<B: AsRef<[u8]>>(value: B) -> Result<Self, DatabaseError> {
+        std::str::from_utf8(value.as_ref())
+            .ok()
+            .and_then(|name| name.parse().ok())
+            .ok_or(DatabaseError::Decode)
+    }
+}
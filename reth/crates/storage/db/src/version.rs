@@ -1,5 +1,6 @@
 //! Database version utils.
 
+use crate::migration::{is_migratable_with, migration_versions};
 use std::{
     fs, io,
     path::{Path, PathBuf},
@@ -20,6 +21,15 @@ pub enum DatabaseVersionError {
     /// Unable to determine the version of the database; the file is malformed.
     #[error("unable to determine the version of the database, file is malformed")]
     MalformedFile,
+    /// The database has an older version, which [`crate::migration`] can upgrade.
+    #[error(
+        "your database version (v{version}) is older than the latest database version \
+         (v{DB_VERSION}), run `reth db migrate` to upgrade it"
+    )]
+    MigrationRequired {
+        /// The detected version in the database.
+        version: u64,
+    },
     /// Breaking database change detected.
     ///
     /// Your database version is incompatible with the latest database version.
@@ -46,12 +56,22 @@ pub enum DatabaseVersionError {
 /// Returns [Ok] if file is found and has one line which equals to [DB_VERSION].
 /// Otherwise, returns different [DatabaseVersionError] error variants.
 pub fn check_db_version_file<P: AsRef<Path>>(db_path: P) -> Result<(), DatabaseVersionError> {
-    let version = get_db_version(db_path)?;
-    if version != DB_VERSION {
-        return Err(DatabaseVersionError::VersionMismatch { version })
+    check_db_version(get_db_version(db_path)?, &migration_versions())
+}
+
+/// Checks the database version against [DB_VERSION].
+///
+/// An older version is only reported as [DatabaseVersionError::MigrationRequired] if the given
+/// migration versions upgrade it to [DB_VERSION].
+fn check_db_version(version: u64, migration_versions: &[u64]) -> Result<(), DatabaseVersionError> {
+    if version == DB_VERSION {
+        return Ok(())
+    }
+    if is_migratable_with(version, migration_versions) {
+        return Err(DatabaseVersionError::MigrationRequired { version })
     }
 
-    Ok(())
+    Err(DatabaseVersionError::VersionMismatch { version })
 }
 
 /// Returns the database version from file with [DB_VERSION_FILE_NAME] name.
@@ -74,7 +94,15 @@ pub fn get_db_version<P: AsRef<Path>>(db_path: P) -> Result<u64, DatabaseVersion
 /// This function will create a file if it does not exist,
 /// and will entirely replace its contents if it does.
 pub fn create_db_version_file<P: AsRef<Path>>(db_path: P) -> io::Result<()> {
-    fs::write(db_version_file_path(db_path), DB_VERSION.to_string())
+    write_db_version_file(db_path, DB_VERSION)
+}
+
+/// Writes the given version to the database version file with [DB_VERSION_FILE_NAME] name.
+///
+/// This function will create a file if it does not exist,
+/// and will entirely replace its contents if it does.
+pub fn write_db_version_file<P: AsRef<Path>>(db_path: P, version: u64) -> io::Result<()> {
+    fs::write(db_version_file_path(db_path), version.to_string())
 }
 
 /// Returns a database version file path.
@@ -84,7 +112,10 @@ pub fn db_version_file_path<P: AsRef<Path>>(db_path: P) -> PathBuf {
 
 #[cfg(test)]
 mod tests {
-    use super::{check_db_version_file, db_version_file_path, DatabaseVersionError};
+    use super::{
+        check_db_version, check_db_version_file, db_version_file_path, DatabaseVersionError,
+        DB_VERSION,
+    };
     use assert_matches::assert_matches;
     use std::fs;
     use tempfile::tempdir;
@@ -107,11 +138,30 @@ mod tests {
     }
 
     #[test]
-    fn version_mismatch() {
+    fn migration_required() {
+        let result = check_db_version(DB_VERSION - 1, &[DB_VERSION]);
+        assert_matches!(result, Err(DatabaseVersionError::MigrationRequired { version }) if version == DB_VERSION - 1);
+    }
+
+    #[test]
+    fn missing_migration() {
+        let result = check_db_version(DB_VERSION - 1, &[]);
+        assert_matches!(result, Err(DatabaseVersionError::VersionMismatch { version }) if version == DB_VERSION - 1);
+
         let dir = tempdir().unwrap();
         fs::write(db_version_file_path(&dir), "0").unwrap();
 
+        // No migration upgrades the database from version 0.
         let result = check_db_version_file(&dir);
         assert_matches!(result, Err(DatabaseVersionError::VersionMismatch { version: 0 }));
     }
+
+    #[test]
+    fn version_mismatch() {
+        let dir = tempdir().unwrap();
+        fs::write(db_version_file_path(&dir), (DB_VERSION + 1).to_string()).unwrap();
+
+        let result = check_db_version_file(&dir);
+        assert_matches!(result, Err(DatabaseVersionError::VersionMismatch { version }) if version == DB_VERSION + 1);
+    }
 }
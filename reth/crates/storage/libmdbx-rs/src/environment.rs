@@ -1,7 +1,7 @@
 use crate::{
     database::Database,
     error::{mdbx_result, Error, Result},
-    flags::EnvironmentFlags,
+    flags::{CopyFlags, EnvironmentFlags},
     transaction::{RO, RW},
     txn_manager::{TxnManager, TxnManagerMessage, TxnPtr},
     Transaction, TransactionKind,
@@ -170,6 +170,20 @@ impl Environment {
         }
     }
 
+    /// Copies the environment into a new data file at `path`, which must not exist yet.
+    ///
+    /// The copy is made from a read transaction, so it is consistent and the environment can be
+    /// used while it's running. Pages freed by write transactions can't be reused until the copy
+    /// is done, so a long copy can grow the database file.
+    ///
+    /// Pass [CopyFlags::COMPACT] to leave out the free pages and renumber the pages in the copy
+    /// sequentially, which shrinks a fragmented database to the size of its data.
+    pub fn copy<P: AsRef<Path>>(&self, path: P, flags: CopyFlags) -> Result<()> {
+        let path = path_to_cstring(path)?;
+        mdbx_result(unsafe { ffi::mdbx_env_copy(self.env_ptr(), path.as_ptr(), flags.bits()) })?;
+        Ok(())
+    }
+
     /// Retrieves the total number of pages on the freelist.
     ///
     /// Along with [Environment::info()], this can be used to calculate the exact number
@@ -215,6 +229,24 @@ impl Environment {
     }
 }
 
+#[cfg(unix)]
+fn path_to_bytes<P: AsRef<Path>>(path: P) -> Vec<u8> {
+    use std::os::unix::ffi::OsStrExt;
+    path.as_ref().as_os_str().as_bytes().to_vec()
+}
+
+#[cfg(windows)]
+fn path_to_bytes<P: AsRef<Path>>(path: P) -> Vec<u8> {
+    // On Windows, could use std::os::windows::ffi::OsStrExt to encode_wide(),
+    // but we end up with a Vec<u16> instead of a Vec<u8>, so that doesn't
+    // really help.
+    path.as_ref().to_string_lossy().to_string().into_bytes()
+}
+
+fn path_to_cstring<P: AsRef<Path>>(path: P) -> Result<CString> {
+    CString::new(path_to_bytes(path)).map_err(|_| Error::Invalid)
+}
+
 /// Container type for Environment internals.
 ///
 /// This holds the raw pointer to the MDBX environment and the transaction manager.
@@ -667,24 +699,7 @@ impl EnvironmentBuilder {
                     ))?;
                 }
 
-                #[cfg(unix)]
-                fn path_to_bytes<P: AsRef<Path>>(path: P) -> Vec<u8> {
-                    use std::os::unix::ffi::OsStrExt;
-                    path.as_ref().as_os_str().as_bytes().to_vec()
-                }
-
-                #[cfg(windows)]
-                fn path_to_bytes<P: AsRef<Path>>(path: P) -> Vec<u8> {
-                    // On Windows, could use std::os::windows::ffi::OsStrExt to encode_wide(),
-                    // but we end up with a Vec<u16> instead of a Vec<u8>, so that doesn't
-                    // really help.
-                    path.as_ref().to_string_lossy().to_string().into_bytes()
-                }
-
-                let path = match CString::new(path_to_bytes(path)) {
-                    Ok(path) => path,
-                    Err(_) => return Err(Error::Invalid),
-                };
+                let path = path_to_cstring(path)?;
                 mdbx_result(ffi::mdbx_env_open(
                     env,
                     path.as_ptr(),
@@ -154,3 +154,37 @@ fn test_freelist() {
     freelist = env.freelist().unwrap();
     assert!(freelist > 0);
 }
+
+#[test]
+fn test_copy_compact() {
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().open(dir.path()).unwrap();
+
+    // Write a few values and clear them, so that the environment has free pages.
+    for i in 0..64 {
+        let mut value = [0u8; 8];
+        LittleEndian::write_u64(&mut value, i);
+        let tx = env.begin_rw_txn().expect("begin_rw_txn");
+        tx.put(tx.open_db(None).unwrap().dbi(), value, value, WriteFlags::default())
+            .expect("tx.put");
+        tx.commit().expect("tx.commit");
+    }
+    let tx = env.begin_rw_txn().expect("begin_rw_txn");
+    let db = tx.open_db(None).unwrap();
+    tx.clear_db(db.dbi()).expect("clear");
+    tx.put(db.dbi(), b"key", b"value", WriteFlags::default()).expect("tx.put");
+    tx.commit().expect("tx.commit");
+    assert!(env.freelist().unwrap() > 0);
+
+    let copy_dir = tempdir().unwrap();
+    env.copy(copy_dir.path().join("mdbx.dat"), CopyFlags::COMPACT).unwrap();
+    // The destination file must not exist.
+    assert!(env.copy(copy_dir.path().join("mdbx.dat"), CopyFlags::COMPACT).is_err());
+
+    let copy = Environment::builder().open(copy_dir.path()).unwrap();
+    assert_eq!(copy.freelist().unwrap(), 0);
+
+    let tx = copy.begin_ro_txn().unwrap();
+    let db = tx.open_db(None).unwrap();
+    assert_eq!(tx.get::<[u8; 5]>(db.dbi(), b"key").unwrap(), Some(*b"value"));
+}
@@ -0,0 +1,274 @@
+use crate::NippyJarError;
+use crc::{Crc, CRC_32_ISCSI};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::File,
+    io::{Read, Seek, SeekFrom},
+    ops::Range,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+/// CRC-32C (Castagnoli) algorithm used for all checksums.
+const CRC32C: Crc<u32> = Crc::<u32>::new(&CRC_32_ISCSI);
+
+/// Default size in bytes of a checksummed block.
+pub const DEFAULT_CHECKSUM_BLOCK_SIZE: u64 = 64 * 1024;
+
+/// Checksums of the files of a [`NippyJar`](crate::NippyJar), recorded in its configuration.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Checksums {
+    /// Checksums of the blocks of the data file.
+    pub(crate) data: BlockChecksums,
+    /// Checksums of the blocks of the offsets file.
+    pub(crate) offsets: BlockChecksums,
+    /// Checksum of the index file, holding the offsets index, PHF and filter. Only set if the
+    /// index file has been written.
+    pub(crate) index: Option<u32>,
+}
+
+impl Checksums {
+    /// Creates empty [`Checksums`] with the given block size for the data and offsets files.
+    pub fn new(block_size: u64) -> Self {
+        Self {
+            data: BlockChecksums::new(block_size),
+            offsets: BlockChecksums::new(block_size),
+            index: None,
+        }
+    }
+
+    /// Returns the checksums of the data file.
+    pub fn data(&self) -> &BlockChecksums {
+        &self.data
+    }
+
+    /// Returns the checksums of the offsets file.
+    pub fn offsets(&self) -> &BlockChecksums {
+        &self.offsets
+    }
+
+    /// Returns the checksum of the index file.
+    pub fn index(&self) -> Option<u32> {
+        self.index
+    }
+}
+
+/// CRC-32C checksums of the consecutive fixed-size blocks of a file. The last block may be
+/// partial.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BlockChecksums {
+    /// Size of a block in bytes.
+    block_size: u64,
+    /// Checksum of each block.
+    checksums: Vec<u32>,
+}
+
+impl BlockChecksums {
+    /// Creates empty [`BlockChecksums`] with the given block size.
+    pub fn new(block_size: u64) -> Self {
+        assert!(block_size > 0, "block size must not be zero");
+        Self { block_size, checksums: Vec::new() }
+    }
+
+    /// Returns the size of a block in bytes.
+    pub fn block_size(&self) -> u64 {
+        self.block_size
+    }
+
+    /// Returns the number of checksummed blocks.
+    pub fn len(&self) -> usize {
+        self.checksums.len()
+    }
+
+    /// Returns `true` if there are no checksummed blocks.
+    pub fn is_empty(&self) -> bool {
+        self.checksums.is_empty()
+    }
+
+    /// Returns the indices of the blocks that cover the byte `range`.
+    pub fn blocks(&self, range: Range<usize>) -> Range<usize> {
+        if range.is_empty() {
+            return 0..0
+        }
+        let block_size = self.block_size as usize;
+        range.start / block_size..range.end.div_ceil(block_size)
+    }
+
+    /// Returns `true` if block `index` of the file with the given contents matches its checksum.
+    pub fn verify_block(&self, file: &[u8], index: usize) -> bool {
+        let block_size = self.block_size as usize;
+        let start = index.saturating_mul(block_size);
+        let end = start.saturating_add(block_size).min(file.len());
+
+        match (self.checksums.get(index), file.get(start..end)) {
+            (Some(expected), Some(block)) if !block.is_empty() => {
+                CRC32C.checksum(block) == *expected
+            }
+            _ => false,
+        }
+    }
+
+    /// Verifies the entire file against the checksums, including its size.
+    ///
+    /// Returns the index of the first corrupted block on failure.
+    pub fn verify(&self, file: &[u8]) -> Result<(), usize> {
+        let blocks = file.len().div_ceil(self.block_size as usize);
+        if let Some(index) = (0..blocks).find(|index| !self.verify_block(file, *index)) {
+            return Err(index)
+        }
+        if blocks != self.checksums.len() {
+            return Err(blocks.min(self.checksums.len()))
+        }
+        Ok(())
+    }
+
+    /// Updates the checksums to the current contents of the file, by recomputing them from the
+    /// last checksummed block onwards.
+    ///
+    /// Assumes that the file has only been appended to or truncated since the last update. The
+    /// file position is set to its end.
+    pub(crate) fn update(&mut self, file: &mut File) -> Result<(), NippyJarError> {
+        let len = file.metadata()?.len();
+
+        // Blocks before the last one are complete and only change if the file was truncated.
+        let unchanged =
+            self.checksums.len().saturating_sub(1).min((len / self.block_size) as usize);
+        self.checksums.truncate(unchanged);
+
+        file.seek(SeekFrom::Start(unchanged as u64 * self.block_size))?;
+        let mut block = Vec::with_capacity(self.block_size as usize);
+        loop {
+            block.clear();
+            (&mut *file).take(self.block_size).read_to_end(&mut block)?;
+            if block.is_empty() {
+                break
+            }
+            self.checksums.push(CRC32C.checksum(&block));
+        }
+        file.seek(SeekFrom::End(0))?;
+
+        Ok(())
+    }
+}
+
+/// Returns the CRC-32C checksum of the given bytes.
+pub(crate) fn checksum(bytes: &[u8]) -> u32 {
+    CRC32C.checksum(bytes)
+}
+
+/// Returns the CRC-32C checksum of the whole file, reading it from its current position in
+/// blocks of [`DEFAULT_CHECKSUM_BLOCK_SIZE`] bytes.
+pub fn file_checksum(file: &mut File) -> Result<u32, NippyJarError> {
+    let mut digest = CRC32C.digest();
+    let mut block = vec![0; DEFAULT_CHECKSUM_BLOCK_SIZE as usize];
+    loop {
+        let read = file.read(&mut block)?;
+        if read == 0 {
+            break
+        }
+        digest.update(&block[..read]);
+    }
+    Ok(digest.finalize())
+}
+
+/// Verifies the blocks of a file against their [`BlockChecksums`] on demand, each block at most
+/// once.
+#[derive(Debug)]
+pub(crate) struct LazyVerifier {
+    /// Checksums of the file.
+    checksums: BlockChecksums,
+    /// Whether a block has been verified already.
+    verified: Vec<AtomicBool>,
+}
+
+impl LazyVerifier {
+    /// Creates a new [`LazyVerifier`] without any verified block.
+    pub(crate) fn new(checksums: BlockChecksums) -> Self {
+        let verified = (0..checksums.len()).map(|_| AtomicBool::new(false)).collect();
+        Self { checksums, verified }
+    }
+
+    /// Returns the checksums of the file.
+    pub(crate) fn checksums(&self) -> &BlockChecksums {
+        &self.checksums
+    }
+
+    /// Verifies the blocks covering the byte `range` of the file, which have not been verified
+    /// yet.
+    ///
+    /// Returns the index of the first corrupted block on failure.
+    pub(crate) fn verify(&self, file: &[u8], range: Range<usize>) -> Result<(), usize> {
+        for index in self.checksums.blocks(range) {
+            let Some(verified) = self.verified.get(index) else { return Err(index) };
+            if verified.load(Ordering::Relaxed) {
+                continue
+            }
+            if !self.checksums.verify_block(file, index) {
+                return Err(index)
+            }
+            verified.store(true, Ordering::Relaxed);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn update_after_append_and_truncate() {
+        let mut file = tempfile::tempfile().unwrap();
+        let mut checksums = BlockChecksums::new(4);
+
+        file.write_all(b"0123456").unwrap();
+        checksums.update(&mut file).unwrap();
+        assert_eq!(checksums.len(), 2);
+        assert_eq!(checksums.verify(b"0123456"), Ok(()));
+
+        file.write_all(b"789ab").unwrap();
+        checksums.update(&mut file).unwrap();
+        assert_eq!(checksums.len(), 3);
+        assert_eq!(checksums.verify(b"0123456789ab"), Ok(()));
+
+        file.set_len(5).unwrap();
+        checksums.update(&mut file).unwrap();
+        assert_eq!(checksums.len(), 2);
+        assert_eq!(checksums.verify(b"01234"), Ok(()));
+
+        // Corruption, truncation and extension are detected
+        assert_eq!(checksums.verify(b"01x34"), Err(0));
+        assert_eq!(checksums.verify(b"0123"), Err(1));
+        assert_eq!(checksums.verify(b"012345"), Err(1));
+        assert_eq!(checksums.verify(b"012345678"), Err(1));
+    }
+
+    #[test]
+    fn lazy_verification() {
+        let data = b"0123456789";
+        let mut file = tempfile::tempfile().unwrap();
+        file.write_all(data).unwrap();
+        let mut checksums = BlockChecksums::new(4);
+        checksums.update(&mut file).unwrap();
+
+        let verifier = LazyVerifier::new(checksums);
+        assert_eq!(verifier.verify(data, 3..5), Ok(()));
+        assert_eq!(verifier.verify(b"0123x56789", 8..10), Ok(()));
+        assert_eq!(verifier.verify(b"0123x56789", 4..5), Ok(()), "verified blocks are skipped");
+
+        let verifier = LazyVerifier::new(verifier.checksums().clone());
+        assert_eq!(verifier.verify(b"0123x56789", 0..10), Err(1));
+        assert_eq!(verifier.verify(data, 8..13), Err(3));
+        assert_eq!(verifier.verify(data, 5..5), Ok(()));
+    }
+
+    #[test]
+    fn whole_file_checksum() {
+        let data = vec![7u8; DEFAULT_CHECKSUM_BLOCK_SIZE as usize + 3];
+        let mut file = tempfile::tempfile().unwrap();
+        file.write_all(&data).unwrap();
+        file.seek(SeekFrom::Start(0)).unwrap();
+
+        assert_eq!(file_checksum(&mut file).unwrap(), checksum(&data));
+    }
+}
@@ -213,6 +213,8 @@ impl<'a, H: NippyJarHeader> NippyJarCursor<'a, H> {
     ) -> Result<(), NippyJarError> {
         // Find out the offset of the column value
         let offset_pos = self.row as usize * self.jar.columns + column;
+        // Verifies the offset of the value and of the next one, which marks its end
+        self.reader.verify_offsets(offset_pos..offset_pos + 2)?;
         let value_offset = self.reader.offset(offset_pos) as usize;
 
         let column_offset_range = if self.jar.rows * self.jar.columns == offset_pos + 1 {
@@ -223,6 +225,8 @@ impl<'a, H: NippyJarHeader> NippyJarCursor<'a, H> {
             value_offset..next_value_offset
         };
 
+        self.reader.verify_data(column_offset_range.clone())?;
+
         if let Some(compression) = self.jar.compressor() {
             let from = self.internal_buffer.len();
             match compression {
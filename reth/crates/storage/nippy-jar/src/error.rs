@@ -45,4 +45,10 @@ pub enum NippyJarError {
     InvalidPruning(u64, u64),
     #[error("jar has been frozen and cannot be modified.")]
     FrozenJar,
+    #[error("checksum mismatch in block {0} of the data file")]
+    DataChecksumMismatch(usize),
+    #[error("checksum mismatch in block {0} of the offsets file")]
+    OffsetsChecksumMismatch(usize),
+    #[error("checksum mismatch of the index file")]
+    IndexChecksumMismatch,
 }
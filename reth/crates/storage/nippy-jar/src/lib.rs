@@ -13,6 +13,7 @@ use serde::{Deserialize, Serialize};
 use std::{
     error::Error as StdError,
     fs::File,
+    io::Read,
     marker::Sync,
     ops::Range,
     path::{Path, PathBuf},
@@ -30,6 +31,9 @@ pub mod phf;
 pub use phf::PHFKey;
 use phf::{Fmph, Functions, GoFmph, PerfectHashingFunction};
 
+pub mod checksum;
+use checksum::{Checksums, LazyVerifier};
+
 mod error;
 pub use error::NippyJarError;
 
@@ -39,7 +43,7 @@ pub use cursor::NippyJarCursor;
 mod writer;
 pub use writer::NippyJarWriter;
 
-const NIPPY_JAR_VERSION: usize = 1;
+const NIPPY_JAR_VERSION: usize = 2;
 
 const INDEX_FILE_EXTENSION: &str = "idx";
 const OFFSETS_FILE_EXTENSION: &str = "off";
@@ -114,11 +118,46 @@ pub struct NippyJar<H = ()> {
     /// Maximum uncompressed row size of the set. This will enable decompression without any
     /// resizing of the output buffer.
     max_row_size: usize,
+    /// Optional checksums of the data, offsets and index files.
+    checksums: Option<Checksums>,
     /// Data path for file. Supporting files will have a format `{path}.{extension}`.
     #[serde(skip)]
     path: PathBuf,
 }
 
+/// The configuration layout of version 1 of the NippyJar format, which has no checksums.
+#[derive(Serialize, Deserialize)]
+struct NippyJarV1<H> {
+    version: usize,
+    user_header: H,
+    columns: usize,
+    rows: usize,
+    compressor: Option<Compressors>,
+    max_row_size: usize,
+}
+
+impl<H> From<NippyJarV1<H>> for NippyJar<H> {
+    fn from(jar: NippyJarV1<H>) -> Self {
+        let NippyJarV1 { version, user_header, columns, rows, compressor, max_row_size } = jar;
+        debug_assert_eq!(version, 1);
+
+        // The configuration is written with the current layout from now on.
+        NippyJar {
+            version: NIPPY_JAR_VERSION,
+            user_header,
+            columns,
+            rows,
+            compressor,
+            filter: None,
+            phf: None,
+            offsets_index: PrefixSummedEliasFano::default(),
+            max_row_size,
+            checksums: None,
+            path: PathBuf::new(),
+        }
+    }
+}
+
 impl<H: NippyJarHeader> std::fmt::Debug for NippyJar<H> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("NippyJar")
@@ -133,6 +172,7 @@ impl<H: NippyJarHeader> std::fmt::Debug for NippyJar<H> {
             .field("offsets_index (size in bytes)", &self.offsets_index.size_in_bytes())
             .field("path", &self.path)
             .field("max_row_size", &self.max_row_size)
+            .field("checksums", &self.checksums.is_some())
             .finish_non_exhaustive()
     }
 }
@@ -167,6 +207,7 @@ impl<H: NippyJarHeader> NippyJar<H> {
             filter: None,
             phf: None,
             offsets_index: PrefixSummedEliasFano::default(),
+            checksums: None,
             path: path.to_path_buf(),
         }
     }
@@ -202,6 +243,16 @@ impl<H: NippyJarHeader> NippyJar<H> {
         self
     }
 
+    /// Adds checksums of the data and offsets files, for blocks of `block_size` bytes, as well as
+    /// of the index file.
+    ///
+    /// Checksums are verified lazily when reading through a [`NippyJarCursor`], or entirely with
+    /// [`NippyJar::verify_checksums`].
+    pub fn with_checksums(mut self, block_size: u64) -> Self {
+        self.checksums = Some(Checksums::new(block_size));
+        self
+    }
+
     /// Gets a reference to the user header.
     pub fn user_header(&self) -> &H {
         &self.user_header
@@ -217,6 +268,16 @@ impl<H: NippyJarHeader> NippyJar<H> {
         self.offsets_index.size_in_bytes()
     }
 
+    /// Returns the number of rows in the jar.
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Gets a reference to the checksums, if the jar has any.
+    pub fn checksums(&self) -> Option<&Checksums> {
+        self.checksums.as_ref()
+    }
+
     /// Gets a reference to the compressor.
     pub fn compressor(&self) -> Option<&Compressors> {
         self.compressor.as_ref()
@@ -233,9 +294,13 @@ impl<H: NippyJarHeader> NippyJar<H> {
     /// **The user must ensure the header type matches the one used during the jar's creation.**
     pub fn load(path: &Path) -> Result<Self, NippyJarError> {
         // Read [`Self`] located at the data file.
-        let config_file = File::open(path.with_extension(CONFIG_FILE_EXTENSION))?;
+        let config = std::fs::read(path.with_extension(CONFIG_FILE_EXTENSION))?;
 
-        let mut obj: Self = bincode::deserialize_from(&config_file)?;
+        // The version is the first serialized field of every layout.
+        let mut obj: Self = match bincode::deserialize::<usize>(&config)? {
+            1 => bincode::deserialize::<NippyJarV1<H>>(&config)?.into(),
+            _ => bincode::deserialize(&config)?,
+        };
         obj.path = path.to_path_buf();
         Ok(obj)
     }
@@ -243,14 +308,40 @@ impl<H: NippyJarHeader> NippyJar<H> {
     /// Loads filters into memory
     pub fn load_filters(mut self) -> Result<Self, NippyJarError> {
         // Read the offsets lists located at the index file.
-        let mut offsets_file = File::open(self.index_path())?;
+        let mut index = Vec::new();
+        File::open(self.index_path())?.read_to_end(&mut index)?;
+
+        if let Some(expected) = self.checksums.as_ref().and_then(|checksums| checksums.index) {
+            if checksum::checksum(&index) != expected {
+                return Err(NippyJarError::IndexChecksumMismatch)
+            }
+        }
 
-        self.offsets_index = PrefixSummedEliasFano::deserialize_from(&mut offsets_file)?;
-        self.phf = bincode::deserialize_from(&mut offsets_file)?;
-        self.filter = bincode::deserialize_from(&mut offsets_file)?;
+        let mut index = index.as_slice();
+        self.offsets_index = PrefixSummedEliasFano::deserialize_from(&mut index)?;
+        self.phf = bincode::deserialize_from(&mut index)?;
+        self.filter = bincode::deserialize_from(&mut index)?;
         Ok(self)
     }
 
+    /// Verifies the data, offsets and index files against their checksums entirely. Does nothing
+    /// if the jar was created without checksums.
+    pub fn verify_checksums(&self) -> Result<(), NippyJarError> {
+        let Some(checksums) = &self.checksums else { return Ok(()) };
+
+        self.open_data_reader()?.verify_checksums()?;
+
+        if let Some(expected) = checksums.index {
+            let mut index = Vec::new();
+            File::open(self.index_path())?.read_to_end(&mut index)?;
+            if checksum::checksum(&index) != expected {
+                return Err(NippyJarError::IndexChecksumMismatch)
+            }
+        }
+
+        Ok(())
+    }
+
     /// Returns the path for the data file
     pub fn data_path(&self) -> &Path {
         self.path.as_ref()
@@ -271,9 +362,14 @@ impl<H: NippyJarHeader> NippyJar<H> {
         self.path.with_extension(CONFIG_FILE_EXTENSION)
     }
 
-    /// Returns a [`DataReader`] of the data and offset file
+    /// Returns a [`DataReader`] of the data and offset file, which verifies the checksums of the
+    /// jar if it has any.
     pub fn open_data_reader(&self) -> Result<DataReader, NippyJarError> {
-        DataReader::new(self.data_path())
+        let reader = DataReader::new(self.data_path())?;
+        Ok(match &self.checksums {
+            Some(checksums) => reader.with_checksums(checksums),
+            None => reader,
+        })
     }
 
     /// If required, prepares any compression algorithm to an early pass of the data.
@@ -369,10 +465,18 @@ impl<H: NippyJarHeader> NippyJar<H> {
     fn freeze_filters(&mut self) -> Result<(), NippyJarError> {
         debug!(target: "nippy-jar", path=?self.index_path(), "Writing offsets and offsets index to file.");
 
-        let mut file = File::create(self.index_path())?;
-        self.offsets_index.serialize_into(&mut file)?;
-        bincode::serialize_into(&mut file, &self.phf)?;
-        bincode::serialize_into(&mut file, &self.filter)?;
+        let mut index = Vec::new();
+        self.offsets_index.serialize_into(&mut index)?;
+        bincode::serialize_into(&mut index, &self.phf)?;
+        bincode::serialize_into(&mut index, &self.filter)?;
+        std::fs::write(self.index_path(), &index)?;
+
+        // Record the checksum of the index file, after the configuration has been written by the
+        // writer.
+        if let Some(checksums) = &mut self.checksums {
+            checksums.index = Some(checksum::checksum(&index));
+            self.freeze_config()?;
+        }
 
         Ok(())
     }
@@ -447,6 +551,10 @@ pub struct DataReader {
     offset_mmap: Mmap,
     /// Number of bytes that represent one offset.
     offset_size: u64,
+    /// Lazy verification of the data file, if the jar has checksums.
+    data_verifier: Option<LazyVerifier>,
+    /// Lazy verification of the offsets file, if the jar has checksums.
+    offsets_verifier: Option<LazyVerifier>,
 }
 
 impl DataReader {
@@ -467,9 +575,69 @@ impl DataReader {
             // First byte is the size of one offset in bytes
             offset_size: offset_mmap[0] as u64,
             offset_mmap,
+            data_verifier: None,
+            offsets_verifier: None,
         })
     }
 
+    /// Verifies the data and offsets files against the given checksums when reading through a
+    /// [`NippyJarCursor`].
+    pub fn with_checksums(mut self, checksums: &Checksums) -> Self {
+        self.data_verifier = Some(LazyVerifier::new(checksums.data.clone()));
+        self.offsets_verifier = Some(LazyVerifier::new(checksums.offsets.clone()));
+        self
+    }
+
+    /// Verifies the data blocks covering the byte `range` of the data file, unless they have been
+    /// verified before. Does nothing without checksums.
+    pub fn verify_data(&self, range: Range<usize>) -> Result<(), NippyJarError> {
+        match &self.data_verifier {
+            Some(verifier) => {
+                verifier.verify(&self.data_mmap, range).map_err(NippyJarError::DataChecksumMismatch)
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Verifies the blocks of the offsets file covering the offsets with the given `indices`,
+    /// unless they have been verified before. Does nothing without checksums.
+    pub fn verify_offsets(&self, indices: Range<usize>) -> Result<(), NippyJarError> {
+        match &self.offsets_verifier {
+            Some(verifier) => {
+                // The first byte is the size of one offset
+                let offset_size = self.offset_size as usize;
+                verifier
+                    .verify(&self.offset_mmap, 0..1)
+                    .and_then(|_| {
+                        verifier.verify(
+                            &self.offset_mmap,
+                            indices.start * offset_size + 1..indices.end * offset_size + 1,
+                        )
+                    })
+                    .map_err(NippyJarError::OffsetsChecksumMismatch)
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Verifies the data and offsets files entirely against their checksums, including their
+    /// sizes. Does nothing without checksums.
+    pub fn verify_checksums(&self) -> Result<(), NippyJarError> {
+        if let Some(verifier) = &self.data_verifier {
+            verifier
+                .checksums()
+                .verify(&self.data_mmap)
+                .map_err(NippyJarError::DataChecksumMismatch)?;
+        }
+        if let Some(verifier) = &self.offsets_verifier {
+            verifier
+                .checksums()
+                .verify(&self.offset_mmap)
+                .map_err(NippyJarError::OffsetsChecksumMismatch)?;
+        }
+        Ok(())
+    }
+
     /// Returns the offset for the requested data index
     pub fn offset(&self, index: usize) -> u64 {
         // + 1 represents the offset_len u8 which is in the beginning of the file
@@ -1044,6 +1212,132 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_checksums() {
+        let (col1, col2) = test_data(Some(1));
+        let num_columns = 2;
+        let num_rows = col1.len() as u64;
+        let file_path = tempfile::NamedTempFile::new().unwrap();
+
+        {
+            let mut nippy = NippyJar::new_without_header(num_columns, file_path.path())
+                .with_lz4()
+                .with_cuckoo_filter(col1.len())
+                .with_fmph()
+                .with_checksums(256);
+            nippy.prepare_index(clone_with_result(&col1), col1.len()).unwrap();
+            nippy
+                .freeze(vec![clone_with_result(&col1), clone_with_result(&col2)], num_rows)
+                .unwrap();
+        }
+
+        let nippy =
+            NippyJar::load_without_header(file_path.path()).unwrap().load_filters().unwrap();
+        let checksums = nippy.checksums().unwrap();
+        assert!(checksums.data().len() > 1);
+        assert!(checksums.offsets().len() > 1);
+        assert!(checksums.index().is_some());
+        nippy.verify_checksums().unwrap();
+
+        // Flips a bit of the last value of the data file
+        let mut data = std::fs::read(nippy.data_path()).unwrap();
+        let last = data.len() - 1;
+        data[last] ^= 1;
+        std::fs::write(nippy.data_path(), &data).unwrap();
+
+        let last_block = checksums.data().len() - 1;
+        assert!(matches!(
+            nippy.verify_checksums(),
+            Err(NippyJarError::DataChecksumMismatch(block)) if block == last_block
+        ));
+
+        // Only rows of the corrupted block fail to be read
+        let mut cursor = NippyJarCursor::new(&nippy).unwrap();
+        let row = cursor.row_by_number(0).unwrap().unwrap();
+        assert_eq!((row[0], row[1]), (col1[0].as_slice(), col2[0].as_slice()));
+        assert!(matches!(
+            cursor.row_by_number(col1.len() - 1),
+            Err(NippyJarError::DataChecksumMismatch(block)) if block == last_block
+        ));
+
+        // Truncating the offsets file is detected as well
+        data[last] ^= 1;
+        std::fs::write(nippy.data_path(), &data).unwrap();
+        let offsets = OpenOptions::new().write(true).open(nippy.offsets_path()).unwrap();
+        offsets.set_len(offsets.metadata().unwrap().len() - 8).unwrap();
+        assert!(matches!(nippy.verify_checksums(), Err(NippyJarError::OffsetsChecksumMismatch(_))));
+
+        // A corrupted index file can't be loaded
+        let mut index = std::fs::read(nippy.index_path()).unwrap();
+        index[0] ^= 1;
+        std::fs::write(nippy.index_path(), &index).unwrap();
+        assert!(matches!(
+            NippyJar::load_without_header(file_path.path()).unwrap().load_filters(),
+            Err(NippyJarError::IndexChecksumMismatch)
+        ));
+    }
+
+    #[test]
+    fn test_load_v1() {
+        let (col1, col2) = test_data(None);
+        let num_columns = 2;
+        let num_rows = col1.len() as u64;
+        let file_path = tempfile::NamedTempFile::new().unwrap();
+
+        let mut nippy = NippyJar::new_without_header(num_columns, file_path.path()).with_lz4();
+        nippy.freeze(vec![clone_with_result(&col1), clone_with_result(&col2)], num_rows).unwrap();
+
+        // Rewrites the configuration with the layout of version 1
+        let v1 = NippyJarV1 {
+            version: 1,
+            user_header: (),
+            columns: nippy.columns,
+            rows: nippy.rows,
+            compressor: nippy.compressor.take(),
+            max_row_size: nippy.max_row_size,
+        };
+        std::fs::write(nippy.config_path(), bincode::serialize(&v1).unwrap()).unwrap();
+
+        let loaded = NippyJar::load_without_header(file_path.path()).unwrap();
+        assert_eq!(loaded.version, NIPPY_JAR_VERSION);
+        assert_eq!(loaded.rows, col1.len());
+        assert!(loaded.checksums().is_none());
+
+        let mut cursor = NippyJarCursor::new(&loaded).unwrap();
+        for (row_num, (a, b)) in col1.iter().zip(col2.iter()).enumerate() {
+            let row = cursor.row_by_number(row_num).unwrap().unwrap();
+            assert_eq!((row[0], row[1]), (a.as_slice(), b.as_slice()));
+        }
+    }
+
+    #[test]
+    fn test_writer_checksums() {
+        let (col1, col2) = test_data(None);
+        let num_columns = 2;
+        let file_path = tempfile::NamedTempFile::new().unwrap();
+
+        let mut nippy =
+            NippyJar::new_without_header(num_columns, file_path.path()).with_checksums(64);
+        nippy.freeze_config().unwrap();
+
+        let mut writer = NippyJarWriter::new(&mut nippy).unwrap();
+        writer.append_rows(vec![clone_with_result(&col1), clone_with_result(&col2)], 10).unwrap();
+        writer.commit().unwrap();
+        writer.append_rows(vec![clone_with_result(&col1), clone_with_result(&col2)], 5).unwrap();
+        writer.commit().unwrap();
+        drop(writer);
+        nippy.verify_checksums().unwrap();
+
+        let mut writer = NippyJarWriter::new(&mut nippy).unwrap();
+        writer.prune_rows(7).unwrap();
+        drop(writer);
+        nippy.verify_checksums().unwrap();
+
+        let nippy = NippyJar::load_without_header(file_path.path()).unwrap();
+        assert_eq!(nippy.rows, 8);
+        nippy.verify_checksums().unwrap();
+    }
+
     fn test_append_consistency_partial_commit(
         file_path: &Path,
         col1: &[Vec<u8>],
@@ -77,15 +77,17 @@ impl<'a, H: NippyJarHeader> NippyJarWriter<'a, H> {
     ) -> Result<(File, File, bool), NippyJarError> {
         let is_created = !data.exists() || !offsets.exists();
 
+        // Files are opened for reading as well, to compute checksums from them.
         let mut data_file = if !data.exists() {
-            File::create(data)?
+            OpenOptions::new().read(true).write(true).create_new(true).open(data)?
         } else {
             OpenOptions::new().read(true).write(true).open(data)?
         };
         data_file.seek(SeekFrom::End(0))?;
 
         let mut offsets_file = if !offsets.exists() {
-            let mut offsets = File::create(offsets)?;
+            let mut offsets =
+                OpenOptions::new().read(true).write(true).create_new(true).open(offsets)?;
 
             // First byte of the offset file is the size of one offset in bytes
             offsets.write_all(&[OFFSET_SIZE_BYTES as u8])?;
@@ -138,6 +140,7 @@ impl<'a, H: NippyJarHeader> NippyJarWriter<'a, H> {
                     OFFSET_SIZE_BYTES) as usize;
 
                 // Freeze row count changed
+                self.update_checksums()?;
                 self.jar.freeze_config()?;
             }
             Ordering::Equal => {}
@@ -340,6 +343,7 @@ impl<'a, H: NippyJarHeader> NippyJarWriter<'a, H> {
         if self.jar.rows == 0 {
             self.jar.max_row_size = 0;
         }
+        self.update_checksums()?;
         self.jar.freeze_config()?;
 
         Ok(())
@@ -362,12 +366,22 @@ impl<'a, H: NippyJarHeader> NippyJarWriter<'a, H> {
 
         self.commit_offsets()?;
 
-        // Flushes `max_row_size` and total `rows` to disk.
+        // Flushes `max_row_size`, total `rows` and checksums to disk.
+        self.update_checksums()?;
         self.jar.freeze_config()?;
 
         Ok(())
     }
 
+    /// Updates the checksums of the data and offsets files, if the jar has any.
+    fn update_checksums(&mut self) -> Result<(), NippyJarError> {
+        if let Some(checksums) = &mut self.jar.checksums {
+            checksums.data.update(&mut self.data_file)?;
+            checksums.offsets.update(&mut self.offsets_file)?;
+        }
+        Ok(())
+    }
+
     /// Flushes offsets to disk.
     pub(crate) fn commit_offsets(&mut self) -> Result<(), NippyJarError> {
         // The last offset on disk can be the first offset of `self.offsets` given how
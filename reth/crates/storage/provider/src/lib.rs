@@ -18,8 +18,9 @@ pub use traits::*;
 /// Provider trait implementations.
 pub mod providers;
 pub use providers::{
-    DatabaseProvider, DatabaseProviderRO, DatabaseProviderRW, HistoricalStateProvider,
-    HistoricalStateProviderRef, LatestStateProvider, LatestStateProviderRef, ProviderFactory,
+    DatabaseFollower, DatabaseProvider, DatabaseProviderRO, DatabaseProviderRW,
+    HistoricalStateProvider, HistoricalStateProviderRef, LatestStateProvider,
+    LatestStateProviderRef, ProviderFactory,
 };
 
 #[cfg(any(test, feature = "test-utils"))]
@@ -0,0 +1,311 @@
+use crate::{
+    chain::{ChainSplit, ChainSplitTarget},
+    BlockHashReader, CanonStateNotification, CanonStateNotificationSender, CanonStateNotifications,
+    CanonStateSubscriptions, Chain, ProviderFactory, StageCheckpointReader,
+};
+use reth_db::database::Database;
+use reth_interfaces::provider::ProviderResult;
+use reth_primitives::{snapshot::HighestSnapshots, stage::StageId, BlockNumber, SnapshotSegment};
+use std::{sync::Arc, time::Duration};
+use strum::IntoEnumIterator;
+use tokio::sync::{broadcast, watch};
+use tracing::{debug, warn};
+
+/// Default number of canonical blocks that are kept by [`DatabaseFollower`] to detect reorgs.
+const DEFAULT_FOLLOWER_WINDOW: u64 = 64;
+
+/// Size of the [`CanonStateNotification`] channel of [`DatabaseFollower`].
+const CANON_STATE_NOTIFICATION_CHANNEL_SIZE: usize = 256;
+
+/// Follows a database that is written by another process, e.g. a running node, and reconstructs
+/// its canonical state changes.
+///
+/// Every [`DatabaseFollower::poll`] opens a new read transaction, so it sees the transactions that
+/// were committed since the previous one. The canonical tip is the block of the
+/// [`StageId::Finish`] checkpoint, and the database is skipped while the pipeline is syncing, as
+/// the changes are only applied by the [`StageId::Execution`] stage up to then.
+///
+/// Reorgs are detected by comparing the hashes of the last followed blocks with the canonical
+/// hashes in the database. The state of reverted blocks is gone once the node unwound them, so the
+/// followed blocks are kept together with their state, and the old chain of a
+/// [`CanonStateNotification::Reorg`] is split off from them. Blocks that are unwound without new
+/// blocks replacing them are only notified once the new canonical blocks are committed.
+#[derive(Debug)]
+pub struct DatabaseFollower<DB> {
+    /// Factory of the followed database.
+    factory: ProviderFactory<DB>,
+    /// Sender of the reconstructed canonical state changes.
+    canon_state_notification_sender: CanonStateNotificationSender,
+    /// Sender of the highest snapshots that are available on disk.
+    highest_snapshots: watch::Sender<Option<HighestSnapshots>>,
+    /// The last followed canonical blocks with their state, if the follower was initialized.
+    chain: Option<Chain>,
+    /// Maximum number of followed blocks, which is also the maximum length of a notified chain.
+    window: u64,
+}
+
+impl<DB> DatabaseFollower<DB> {
+    /// Creates a new follower of the database of the factory.
+    pub fn new(factory: ProviderFactory<DB>) -> Self {
+        let (canon_state_notification_sender, _) =
+            broadcast::channel(CANON_STATE_NOTIFICATION_CHANNEL_SIZE);
+        let (highest_snapshots, _) = watch::channel(None);
+        Self {
+            factory,
+            canon_state_notification_sender,
+            highest_snapshots,
+            chain: None,
+            window: DEFAULT_FOLLOWER_WINDOW,
+        }
+    }
+
+    /// Sets the number of canonical blocks that are kept to detect reorgs.
+    ///
+    /// Reorgs deeper than the window revert all followed blocks. If more blocks than the window
+    /// were committed between two polls, only the last ones are notified.
+    pub fn with_window(mut self, window: u64) -> Self {
+        self.window = window.max(1);
+        self
+    }
+
+    /// Returns a receiver of the highest snapshots, which is updated when new snapshots are
+    /// found on disk.
+    ///
+    /// The snapshots are only watched if the factory was created with
+    /// [`ProviderFactory::with_snapshots`].
+    pub fn subscribe_to_highest_snapshots(&self) -> watch::Receiver<Option<HighestSnapshots>> {
+        self.highest_snapshots.subscribe()
+    }
+}
+
+impl<DB: Database> DatabaseFollower<DB> {
+    /// Polls the database and the snapshot directory for changes, and notifies the subscribers of
+    /// the canonical state change, if any.
+    ///
+    /// The first poll starts following the database at its canonical tip, without notification.
+    pub fn poll(&mut self) -> ProviderResult<Option<CanonStateNotification>> {
+        let provider = self.factory.provider()?;
+
+        // The snapshots are updated after opening the transaction, so that the data moved from
+        // the database to snapshots meanwhile is still found in one of them.
+        self.update_snapshots()?;
+
+        let tip = provider.get_stage_checkpoint(StageId::Finish)?.unwrap_or_default().block_number;
+        let executed =
+            provider.get_stage_checkpoint(StageId::Execution)?.unwrap_or_default().block_number;
+        if executed != tip {
+            debug!(target: "providers::db", tip, executed, "Pipeline is syncing, skipping database follower poll");
+            return Ok(None)
+        }
+
+        let Some(followed) = &self.chain else {
+            self.chain = Some(provider.get_block_and_execution_range_ro(tip..=tip)?);
+            return Ok(None)
+        };
+
+        // The fork block is the highest followed block that is still canonical.
+        let mut fork_block = None;
+        for (number, block) in followed.blocks().iter().rev() {
+            if *number <= tip && provider.block_hash(*number)? == Some(block.hash()) {
+                fork_block = Some(*number);
+                break
+            }
+        }
+        let followed_start = followed.blocks().keys().next().copied();
+        let reorg = fork_block != followed.blocks().keys().next_back().copied();
+        let first_block = fork_block
+            .map(|fork_block| fork_block + 1)
+            .or(followed_start)
+            .unwrap_or(tip + 1)
+            .max((tip + 1).saturating_sub(self.window));
+
+        // Without new blocks there is nothing to notify. Reverted blocks stay followed, so that
+        // they are notified together with the blocks that replace them.
+        if first_block > tip {
+            return Ok(None)
+        }
+
+        let new = provider.get_block_and_execution_range_ro(first_block..=tip)?;
+        // The state of the canonical part of a split chain is no longer usable, so the followed
+        // blocks below the new ones are read again.
+        let canonical = match followed_start.zip(fork_block) {
+            Some((start, _)) if reorg => {
+                let start = start.max((tip + 1).saturating_sub(self.window));
+                (start < first_block)
+                    .then(|| provider.get_block_and_execution_range_ro(start..=tip))
+                    .transpose()?
+            }
+            _ => None,
+        };
+
+        let followed = self.chain.take().expect("follower is initialized");
+        let notification = if reorg {
+            let old = match fork_block {
+                Some(fork_block) => match followed.split(ChainSplitTarget::Number(fork_block)) {
+                    ChainSplit::Split { pending, .. } => pending,
+                    ChainSplit::NoSplitPending(chain) | ChainSplit::NoSplitCanonical(chain) => {
+                        chain
+                    }
+                },
+                None => followed,
+            };
+            self.chain = Some(canonical.unwrap_or_else(|| new.clone()));
+            CanonStateNotification::Reorg { old: Arc::new(old), new: Arc::new(new) }
+        } else {
+            let mut followed = followed;
+            if followed.is_empty() || followed.append_chain(new.clone()).is_err() {
+                // More blocks than the window were committed since the last poll.
+                followed = new.clone();
+            }
+            self.chain = Some(self.truncate_to_window(followed, tip));
+            CanonStateNotification::Commit { new: Arc::new(new) }
+        };
+
+        // Sending fails only if there are no subscribers.
+        let _ = self.canon_state_notification_sender.send(notification.clone());
+
+        Ok(Some(notification))
+    }
+
+    /// Polls the database at the given interval, until the returned future is dropped.
+    ///
+    /// Subscriptions have to be created before, as this consumes the follower. The database is
+    /// read on the calling task, so this is best spawned as a blocking or dedicated task.
+    pub async fn run(mut self, interval: Duration) {
+        let mut interval = tokio::time::interval(interval);
+        loop {
+            interval.tick().await;
+            if let Err(err) = self.poll() {
+                warn!(target: "providers::db", %err, "Failed to poll followed database");
+            }
+        }
+    }
+
+    /// Removes the followed blocks that are more than the window below the tip.
+    fn truncate_to_window(&self, chain: Chain, tip: BlockNumber) -> Chain {
+        if chain.len() as u64 <= self.window {
+            return chain
+        }
+
+        match chain.split(ChainSplitTarget::Number(tip - self.window)) {
+            ChainSplit::Split { pending, .. } => pending,
+            ChainSplit::NoSplitPending(chain) | ChainSplit::NoSplitCanonical(chain) => chain,
+        }
+    }
+
+    /// Indexes the new snapshots on disk and notifies the subscribers if the highest ones changed.
+    fn update_snapshots(&self) -> ProviderResult<()> {
+        let Some(snapshot_provider) = &self.factory.snapshot_provider else { return Ok(()) };
+        snapshot_provider.update_index()?;
+
+        let mut highest = HighestSnapshots::default();
+        for segment in SnapshotSegment::iter() {
+            *highest.as_mut(segment) = snapshot_provider.get_highest_snapshot_block(segment);
+        }
+        self.highest_snapshots.send_if_modified(|current| {
+            let modified = *current != Some(highest);
+            if modified {
+                *current = Some(highest);
+            }
+            modified
+        });
+
+        Ok(())
+    }
+}
+
+impl<DB: Send + Sync> CanonStateSubscriptions for DatabaseFollower<DB> {
+    fn subscribe_to_canonical_state(&self) -> CanonStateNotifications {
+        self.canon_state_notification_sender.subscribe()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        test_utils::{blocks::BlockChainTestData, create_test_provider_factory},
+        BlockExecutionWriter, BlockWriter, StageCheckpointWriter,
+    };
+    use assert_matches::assert_matches;
+    use reth_db::{tables, transaction::DbTxMut};
+    use reth_primitives::{stage::StageCheckpoint, Address, Bytes, MAINNET, U256};
+    use reth_trie::{updates::TrieUpdates, HashedPostState};
+
+    #[test]
+    fn follow_commits_and_reverts() {
+        let factory = create_test_provider_factory();
+        let data = BlockChainTestData::default();
+        let (block1, state1) = data.blocks[0].clone();
+        let (block2, state2) = data.blocks[1].clone();
+
+        let provider = factory.provider_rw().unwrap();
+        provider.insert_block(data.genesis.try_seal_with_senders().unwrap(), None).unwrap();
+        provider.commit().unwrap();
+
+        let mut follower = factory.follower();
+        let mut notifications = follower.subscribe_to_canonical_state();
+        assert_matches!(follower.poll(), Ok(None));
+
+        let mut state = state1;
+        state.extend(state2);
+        let provider = factory.provider_rw().unwrap();
+        provider
+            .append_blocks_with_state(
+                vec![block1.clone(), block2.clone()],
+                state,
+                HashedPostState::default(),
+                TrieUpdates::default(),
+                None,
+            )
+            .unwrap();
+        let expected = provider.get_block_and_execution_range(&MAINNET, 1..=2).unwrap();
+        provider.commit().unwrap();
+
+        let notification = follower.poll().unwrap().unwrap();
+        assert_matches!(&notification, CanonStateNotification::Commit { new } if **new == expected);
+        assert_matches!(notifications.try_recv(), Ok(CanonStateNotification::Commit { .. }));
+        assert_matches!(follower.poll(), Ok(None));
+
+        // The pipeline is syncing.
+        let provider = factory.provider_rw().unwrap();
+        provider.save_stage_checkpoint(StageId::Execution, StageCheckpoint::new(3)).unwrap();
+        provider.commit().unwrap();
+        assert_matches!(follower.poll(), Ok(None));
+
+        // Block 2 is reverted.
+        let provider = factory.provider_rw().unwrap();
+        provider.tx_ref().delete::<tables::CanonicalHeaders>(2, None).unwrap();
+        for stage in [StageId::Execution, StageId::Finish] {
+            provider.save_stage_checkpoint(stage, StageCheckpoint::new(1)).unwrap();
+        }
+        provider.commit().unwrap();
+
+        // The reverted block is only notified together with the block that replaces it.
+        assert_matches!(follower.poll(), Ok(None));
+
+        let mut header = block2.header.clone().unseal();
+        header.extra_data = Bytes::from_static(b"reorg");
+        let header = header.seal_slow();
+        let provider = factory.provider_rw().unwrap();
+        provider.tx_ref().put::<tables::Headers>(2, header.clone().unseal()).unwrap();
+        provider.tx_ref().put::<tables::CanonicalHeaders>(2, header.hash()).unwrap();
+        for stage in [StageId::Execution, StageId::Finish] {
+            provider.save_stage_checkpoint(stage, StageCheckpoint::new(2)).unwrap();
+        }
+        provider.commit().unwrap();
+
+        let notification = follower.poll().unwrap().unwrap();
+        assert_matches!(notification, CanonStateNotification::Reorg { old, new } => {
+            assert_eq!(old.blocks().values().collect::<Vec<_>>(), vec![&block2]);
+            // The state of the reverted block is the one from before the revert.
+            assert_eq!(old.state().receipts_by_block(2), expected.state().receipts_by_block(2));
+            assert_eq!(
+                old.state().storage(&Address::new([0x60; 20]), U256::from(5)),
+                Some(U256::from(15))
+            );
+            assert_eq!(new.blocks().keys().collect::<Vec<_>>(), vec![&2]);
+            assert_eq!(new.tip().hash(), header.hash());
+        });
+    }
+}
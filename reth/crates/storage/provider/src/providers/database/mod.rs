@@ -5,9 +5,10 @@ use crate::{
     },
     traits::{BlockSource, ReceiptProvider},
     BlockHashReader, BlockNumReader, BlockReader, ChainSpecProvider, EvmEnvProvider,
-    HeaderProvider, HeaderSyncGap, HeaderSyncGapProvider, HeaderSyncMode, ProviderError,
-    PruneCheckpointReader, StageCheckpointReader, StateProviderBox, TransactionVariant,
-    TransactionsProvider, WithdrawalsProvider,
+    HashedAccountRange, HashedStateRangeReader, HashedStorageRanges, HeaderProvider, HeaderSyncGap,
+    HeaderSyncGapProvider, HeaderSyncMode, ProviderError, PruneCheckpointReader,
+    StageCheckpointReader, StateProviderBox, StateRangeProviderBox, StateRangeReader,
+    TransactionVariant, TransactionsProvider, WithdrawalsProvider,
 };
 use reth_db::{database::Database, init_db, models::StoredBlockBodyIndices, DatabaseEnv};
 use reth_interfaces::{provider::ProviderResult, RethError, RethResult};
@@ -15,13 +16,15 @@ use reth_node_api::ConfigureEvmEnv;
 use reth_primitives::{
     snapshot::HighestSnapshots,
     stage::{StageCheckpoint, StageId},
-    Address, Block, BlockHash, BlockHashOrNumber, BlockNumber, BlockWithSenders, ChainInfo,
+    trie::Nibbles,
+    Address, Block, BlockHash, BlockHashOrNumber, BlockNumber, BlockWithSenders, Bytes, ChainInfo,
     ChainSpec, Header, PruneCheckpoint, PruneSegment, Receipt, SealedBlock, SealedBlockWithSenders,
     SealedHeader, TransactionMeta, TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber,
     Withdrawal, Withdrawals, B256, U256,
 };
 use revm::primitives::{BlockEnv, CfgEnvWithHandlerCfg};
 use std::{
+    collections::BTreeSet,
     ops::{RangeBounds, RangeInclusive},
     path::{Path, PathBuf},
     sync::Arc,
@@ -29,9 +32,11 @@ use std::{
 use tokio::sync::watch;
 use tracing::trace;
 
+mod follower;
 mod metrics;
 mod provider;
 
+pub use follower::DatabaseFollower;
 pub use provider::{DatabaseProvider, DatabaseProviderRO, DatabaseProviderRW};
 use reth_db::mdbx::DatabaseArguments;
 
@@ -97,6 +102,15 @@ impl<DB> ProviderFactory<DB> {
     }
 }
 
+impl<DB: Clone> ProviderFactory<DB> {
+    /// Returns a [`DatabaseFollower`] of the database, which reconstructs the canonical state
+    /// changes committed by another process, e.g. when the database is opened read-only next to a
+    /// running node.
+    pub fn follower(&self) -> DatabaseFollower<DB> {
+        DatabaseFollower::new(self.clone())
+    }
+}
+
 impl<DB: Database> ProviderFactory<DB> {
     /// Returns a provider with a created `DbTx` inside, which allows fetching data from the
     /// database using different types of providers. Example: [`HeaderProvider`]
@@ -173,6 +187,9 @@ impl<DB: Database> ProviderFactory<DB> {
                 prune_checkpoint_block_number + 1,
             );
         }
+        if let Some(snapshot_provider) = &self.snapshot_provider {
+            state_provider = state_provider.with_snapshot_provider(snapshot_provider.clone());
+        }
 
         Ok(Box::new(state_provider))
     }
@@ -523,17 +540,89 @@ impl<DB: Database> PruneCheckpointReader for ProviderFactory<DB> {
     }
 }
 
+impl<DB: Database> StateRangeReader for ProviderFactory<DB> {
+    fn lowest_available_state_block(&self) -> ProviderResult<BlockNumber> {
+        self.provider()?.lowest_available_state_block()
+    }
+
+    fn state_range_at(&self, block: BlockNumber) -> ProviderResult<StateRangeProviderBox> {
+        Ok(Box::new(self.provider()?.into_state_range_at(block)?))
+    }
+
+    fn changed_addresses_with_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<Address>> {
+        self.provider()?.changed_addresses_with_range(range)
+    }
+}
+
+impl<DB: Database> HashedStateRangeReader for ProviderFactory<DB> {
+    fn hashed_state_root(&self) -> ProviderResult<Option<B256>> {
+        self.provider()?.hashed_state_root()
+    }
+
+    fn hashed_account_range(
+        &self,
+        state_root: B256,
+        start: B256,
+        limit: B256,
+        max_bytes: usize,
+    ) -> ProviderResult<Option<HashedAccountRange>> {
+        self.provider()?.hashed_account_range(state_root, start, limit, max_bytes)
+    }
+
+    fn hashed_storage_ranges(
+        &self,
+        state_root: B256,
+        accounts: &[B256],
+        start: B256,
+        limit: B256,
+        max_bytes: usize,
+    ) -> ProviderResult<Option<HashedStorageRanges>> {
+        self.provider()?.hashed_storage_ranges(state_root, accounts, start, limit, max_bytes)
+    }
+
+    fn account_trie_nodes(
+        &self,
+        state_root: B256,
+        paths: &[Nibbles],
+    ) -> ProviderResult<Option<Vec<Bytes>>> {
+        self.provider()?.account_trie_nodes(state_root, paths)
+    }
+
+    fn storage_trie_nodes(
+        &self,
+        state_root: B256,
+        hashed_address: B256,
+        paths: &[Nibbles],
+    ) -> ProviderResult<Option<Vec<Bytes>>> {
+        self.provider()?.storage_trie_nodes(state_root, hashed_address, paths)
+    }
+
+    fn bytecodes(&self, hashes: &[B256], max_bytes: usize) -> ProviderResult<Vec<Bytes>> {
+        self.provider()?.bytecodes(hashes, max_bytes)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::ProviderFactory;
     use crate::{
         test_utils::create_test_provider_factory, BlockHashReader, BlockNumReader, BlockWriter,
-        HeaderSyncGapProvider, HeaderSyncMode, TransactionsProvider,
+        HashedStateRangeReader, HeaderSyncGapProvider, HeaderSyncMode, StageCheckpointWriter,
+        StateRangeProvider, StateRangeReader, TransactionsProvider,
     };
     use alloy_rlp::Decodable;
     use assert_matches::assert_matches;
     use rand::Rng;
-    use reth_db::{tables, test_utils::ERROR_TEMPDIR, transaction::DbTxMut, DatabaseEnv};
+    use reth_db::{
+        models::{AccountBeforeTx, BlockNumberAddress},
+        tables,
+        test_utils::ERROR_TEMPDIR,
+        transaction::DbTxMut,
+        DatabaseEnv,
+    };
     use reth_interfaces::{
         provider::ProviderError,
         test_utils::{
@@ -543,8 +632,13 @@ mod tests {
         RethError,
     };
     use reth_primitives::{
-        hex_literal::hex, ChainSpecBuilder, PruneMode, PruneModes, SealedBlock, TxNumber, B256,
+        hex_literal::hex,
+        keccak256,
+        stage::{StageCheckpoint, StageId},
+        Account, Address, Bytecode, Bytes, ChainSpecBuilder, Header, PruneMode, PruneModes,
+        SealedBlock, StorageEntry, TxNumber, B256, KECCAK_EMPTY, U256,
     };
+    use reth_trie::StateRoot;
     use std::{ops::RangeInclusive, sync::Arc};
     use tokio::sync::watch;
 
@@ -591,6 +685,246 @@ mod tests {
         provider.block_hash(0).unwrap();
     }
 
+    #[test]
+    fn state_range_at_reverts_plain_state() {
+        let factory = create_test_provider_factory();
+        let (a1, a2, a3) =
+            (Address::with_last_byte(1), Address::with_last_byte(2), Address::with_last_byte(3));
+        let account = |nonce| Account { nonce, ..Default::default() };
+        let slot = |key: u8, value: u64| StorageEntry {
+            key: B256::with_last_byte(key),
+            value: U256::from(value),
+        };
+        let hashed_slot = |key: u8, value: u64| StorageEntry {
+            key: keccak256(B256::with_last_byte(key)),
+            value: U256::from(value),
+        };
+        let sorted = |mut entries: Vec<(B256, Account)>| {
+            entries.sort_by_key(|(hashed_address, _)| *hashed_address);
+            entries
+        };
+
+        // Block 2 updated `a1`, destroyed `a2` and created `a3`. It also set slot 1 of `a1` and
+        // cleared slot 2.
+        let provider = factory.provider_rw().unwrap();
+        let tx = provider.tx_ref();
+        tx.put::<tables::PlainAccountState>(a1, account(2)).unwrap();
+        tx.put::<tables::PlainAccountState>(a3, account(1)).unwrap();
+        tx.put::<tables::PlainStorageState>(a1, slot(1, 5)).unwrap();
+        tx.put::<tables::HashedAccount>(keccak256(a1), account(2)).unwrap();
+        tx.put::<tables::HashedAccount>(keccak256(a3), account(1)).unwrap();
+        tx.put::<tables::HashedStorage>(keccak256(a1), hashed_slot(1, 5)).unwrap();
+        for (address, info) in [(a1, Some(account(1))), (a2, Some(account(5))), (a3, None)] {
+            tx.put::<tables::AccountChangeSet>(2, AccountBeforeTx { address, info }).unwrap();
+        }
+        for entry in [slot(1, 0), slot(2, 7)] {
+            tx.put::<tables::StorageChangeSet>(BlockNumberAddress((2, a1)), entry).unwrap();
+        }
+        provider.save_stage_checkpoint(StageId::Execution, StageCheckpoint::new(2)).unwrap();
+        provider.commit().unwrap();
+
+        let (at_2, at_1) = (factory.state_range_at(2).unwrap(), factory.state_range_at(1).unwrap());
+        assert_eq!(
+            at_2.account_range(Address::ZERO, 10).unwrap(),
+            vec![(a1, account(2)), (a3, account(1))]
+        );
+        assert_eq!(
+            at_1.account_range(Address::ZERO, 10).unwrap(),
+            vec![(a1, account(1)), (a2, account(5))]
+        );
+        assert_eq!(at_1.account_range(a2, 10).unwrap(), vec![(a2, account(5))]);
+        assert_eq!(at_1.account_range(Address::ZERO, 1).unwrap(), vec![(a1, account(1))]);
+
+        assert_eq!(at_2.storage_range(a1, B256::ZERO, 10).unwrap(), vec![slot(1, 5)]);
+        assert_eq!(at_1.storage_range(a1, B256::ZERO, 10).unwrap(), vec![slot(2, 7)]);
+
+        // the hashed state is reverted in the same way
+        assert_eq!(
+            at_2.hashed_account_range(B256::ZERO, 10).unwrap(),
+            sorted(vec![(keccak256(a1), account(2)), (keccak256(a3), account(1))])
+        );
+        let accounts = sorted(vec![(keccak256(a1), account(1)), (keccak256(a2), account(5))]);
+        assert_eq!(at_1.hashed_account_range(B256::ZERO, 10).unwrap(), accounts);
+        assert_eq!(at_1.hashed_account_range(accounts[1].0, 10).unwrap(), accounts[1..]);
+        assert_eq!(at_1.hashed_account_range(B256::ZERO, 1).unwrap(), accounts[..1]);
+        assert_eq!(
+            at_2.hashed_storage_range(keccak256(a1), B256::ZERO, 10).unwrap(),
+            vec![hashed_slot(1, 5)]
+        );
+        assert_eq!(
+            at_1.hashed_storage_range(keccak256(a1), B256::ZERO, 10).unwrap(),
+            vec![hashed_slot(2, 7)]
+        );
+
+        // the provider holds its own transaction, so later writes don't change the state it
+        // serves
+        let provider = factory.provider_rw().unwrap();
+        provider.tx_ref().put::<tables::PlainAccountState>(a2, account(1)).unwrap();
+        provider.commit().unwrap();
+        assert_eq!(
+            at_1.account_range(Address::ZERO, 10).unwrap(),
+            vec![(a1, account(1)), (a2, account(5))]
+        );
+
+        assert_eq!(
+            factory.changed_addresses_with_range(2..=2).unwrap().into_iter().collect::<Vec<_>>(),
+            vec![a1, a2, a3]
+        );
+        assert_matches!(factory.state_range_at(3).err(), Some(ProviderError::HeaderNotFound(_)));
+    }
+
+    #[test]
+    fn hashed_state_ranges() {
+        let factory = create_test_provider_factory();
+        let hashed_addresses = [1, 2, 3].map(B256::repeat_byte);
+        let account = |nonce| Account { nonce, ..Default::default() };
+        let slots = [1, 2, 3]
+            .map(|key| StorageEntry { key: B256::repeat_byte(key), value: U256::from(key) });
+        let code = Bytes::from_static(&[0x60, 0x00]);
+        let code_hash = B256::repeat_byte(0xcc);
+
+        // Write the hashed state with the storage of the second account, its trie and the header
+        // of the block it belongs to.
+        let mut provider = factory.provider_rw().unwrap();
+        let tx = provider.tx_ref();
+        for (nonce, hashed_address) in hashed_addresses.into_iter().enumerate() {
+            tx.put::<tables::HashedAccount>(hashed_address, account(nonce as u64)).unwrap();
+        }
+        for slot in slots {
+            tx.put::<tables::HashedStorage>(hashed_addresses[1], slot).unwrap();
+        }
+        tx.put::<tables::Bytecodes>(code_hash, Bytecode::new_raw(code.clone())).unwrap();
+        let (state_root, updates) = StateRoot::from_tx(tx).root_with_updates().unwrap();
+        updates.flush(provider.tx_mut()).unwrap();
+        let header = Header { state_root, ..Default::default() };
+        provider.tx_ref().put::<tables::Headers>(0, header).unwrap();
+        for stage in [
+            StageId::AccountHashing,
+            StageId::StorageHashing,
+            StageId::MerkleExecute,
+            StageId::Finish,
+        ] {
+            provider.save_stage_checkpoint(stage, StageCheckpoint::new(0)).unwrap();
+        }
+        provider.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        let max = B256::repeat_byte(0xff);
+        assert_eq!(provider.hashed_state_root().unwrap(), Some(state_root));
+        assert_eq!(provider.hashed_account_range(B256::ZERO, B256::ZERO, max, 1024).unwrap(), None);
+
+        // The range includes the first account at or after the limit.
+        let range =
+            provider.hashed_account_range(state_root, B256::ZERO, hashed_addresses[1], 1024);
+        let range = range.unwrap().unwrap();
+        assert_eq!(
+            range.accounts.iter().map(|(hashed_address, _, _)| *hashed_address).collect::<Vec<_>>(),
+            hashed_addresses[..2]
+        );
+        assert!(!range.proof.is_empty());
+        let range = provider.hashed_account_range(state_root, B256::ZERO, max, 1).unwrap().unwrap();
+        assert_eq!(range.accounts.len(), 1);
+
+        // The entire storage is not proven.
+        let ranges = provider
+            .hashed_storage_ranges(state_root, &hashed_addresses, B256::ZERO, max, 1024)
+            .unwrap()
+            .unwrap();
+        assert_eq!(ranges.slots, vec![vec![], slots.to_vec(), vec![]]);
+        assert!(ranges.proof.is_empty());
+
+        // A partial range of the first account is proven and ends the response.
+        let ranges = provider
+            .hashed_storage_ranges(state_root, &hashed_addresses[1..], slots[1].key, max, 1024)
+            .unwrap()
+            .unwrap();
+        assert_eq!(ranges.slots, vec![slots[1..].to_vec()]);
+        assert!(!ranges.proof.is_empty());
+        let ranges = provider
+            .hashed_storage_ranges(state_root, &hashed_addresses[1..], B256::ZERO, max, 1)
+            .unwrap()
+            .unwrap();
+        assert_eq!(ranges.slots, vec![slots[..1].to_vec()]);
+        assert!(!ranges.proof.is_empty());
+        let ranges = provider
+            .hashed_storage_ranges(
+                state_root,
+                &hashed_addresses[1..],
+                B256::ZERO,
+                slots[0].key,
+                1024,
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(ranges.slots, vec![slots[..1].to_vec()]);
+        assert!(!ranges.proof.is_empty());
+
+        // The root node is at the empty path.
+        let nodes =
+            provider.account_trie_nodes(state_root, &[Default::default()]).unwrap().unwrap();
+        assert_eq!(keccak256(&nodes[0]), state_root);
+
+        assert_eq!(
+            provider.bytecodes(&[KECCAK_EMPTY, B256::ZERO, code_hash], 1024).unwrap(),
+            vec![Bytes::new(), code]
+        );
+    }
+
+    #[test]
+    fn hashed_state_ranges_of_recent_blocks() {
+        let factory = create_test_provider_factory();
+        let addresses = [1, 2].map(Address::with_last_byte);
+        let hashed_addresses = addresses.map(keccak256);
+        let account = |nonce| Account { nonce, ..Default::default() };
+
+        // The first account is changed by block 1.
+        let mut provider = factory.provider_rw().unwrap();
+        let tx = provider.tx_ref();
+        for hashed_address in hashed_addresses {
+            tx.put::<tables::HashedAccount>(hashed_address, account(1)).unwrap();
+        }
+        let old_state_root = StateRoot::from_tx(tx).root().unwrap();
+        tx.put::<tables::HashedAccount>(hashed_addresses[0], account(2)).unwrap();
+        tx.put::<tables::AccountChangeSet>(
+            1,
+            AccountBeforeTx { address: addresses[0], info: Some(account(1)) },
+        )
+        .unwrap();
+        let (state_root, updates) = StateRoot::from_tx(tx).root_with_updates().unwrap();
+        updates.flush(provider.tx_mut()).unwrap();
+        for (number, state_root) in [(0, old_state_root), (1, state_root)] {
+            let header = Header { number, state_root, ..Default::default() };
+            provider.tx_ref().put::<tables::Headers>(number, header).unwrap();
+        }
+        for stage in [
+            StageId::AccountHashing,
+            StageId::StorageHashing,
+            StageId::MerkleExecute,
+            StageId::Finish,
+        ] {
+            provider.save_stage_checkpoint(stage, StageCheckpoint::new(1)).unwrap();
+        }
+        provider.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        let max = B256::repeat_byte(0xff);
+        assert_eq!(provider.hashed_state_root().unwrap(), Some(state_root));
+
+        let nonces = |state_root| {
+            let range = provider.hashed_account_range(state_root, B256::ZERO, max, 1024);
+            let mut accounts = range.unwrap().unwrap().accounts;
+            accounts.sort_by_key(|(hashed_address, _, _)| *hashed_address != hashed_addresses[0]);
+            accounts.into_iter().map(|(_, account, _)| account.nonce).collect::<Vec<_>>()
+        };
+        assert_eq!(nonces(state_root), vec![2, 1]);
+        // The state of the previous block is served with the changes of block 1 reverted.
+        assert_eq!(nonces(old_state_root), vec![1, 1]);
+
+        let nodes =
+            provider.account_trie_nodes(old_state_root, &[Default::default()]).unwrap().unwrap();
+        assert_eq!(keccak256(&nodes[0]), old_state_root);
+    }
+
     #[test]
     fn insert_block_with_prune_modes() {
         let factory = create_test_provider_factory();
@@ -1,15 +1,17 @@
 use crate::{
     bundle_state::{BundleStateInit, BundleStateWithReceipts, HashedStateChanges, RevertsInit},
-    providers::{database::metrics, SnapshotProvider},
+    providers::{database::metrics, snapshot, SnapshotProvider},
     to_range,
     traits::{
         AccountExtReader, BlockSource, ChangeSetReader, ReceiptProvider, StageCheckpointWriter,
     },
-    AccountReader, BlockExecutionWriter, BlockHashReader, BlockNumReader, BlockReader, BlockWriter,
-    Chain, EvmEnvProvider, HashingWriter, HeaderProvider, HeaderSyncGap, HeaderSyncGapProvider,
-    HeaderSyncMode, HistoryWriter, OriginalValuesKnown, ProviderError, PruneCheckpointReader,
-    PruneCheckpointWriter, StageCheckpointReader, StorageReader, TransactionVariant,
-    TransactionsProvider, TransactionsProviderExt, WithdrawalsProvider,
+    AccountReader, AddressTransactionsReader, BlockExecutionWriter, BlockHashReader,
+    BlockNumReader, BlockReader, BlockWriter, Chain, EvmEnvProvider, HashedAccountRange,
+    HashedStateRangeReader, HashedStorageRanges, HashingWriter, HeaderProvider, HeaderSyncGap,
+    HeaderSyncGapProvider, HeaderSyncMode, HistoryWriter, LogIndexReader, OriginalValuesKnown,
+    ProviderError, PruneCheckpointReader, PruneCheckpointWriter, StageCheckpointReader,
+    StateRangeProvider, StorageReader, TransactionVariant, TransactionsProvider,
+    TransactionsProviderExt, WithdrawalsProvider,
 };
 use itertools::{izip, Itertools};
 use reth_db::{
@@ -36,13 +38,17 @@ use reth_primitives::{
     revm::{config::revm_spec, env::fill_block_env},
     stage::{StageCheckpoint, StageId},
     trie::Nibbles,
-    Account, Address, Block, BlockHash, BlockHashOrNumber, BlockNumber, BlockWithSenders,
+    Account, Address, Block, BlockHash, BlockHashOrNumber, BlockNumber, BlockWithSenders, Bytes,
     ChainInfo, ChainSpec, GotExpected, Hardfork, Head, Header, PruneCheckpoint, PruneModes,
     PruneSegment, Receipt, SealedBlock, SealedBlockWithSenders, SealedHeader, SnapshotSegment,
-    StorageEntry, TransactionMeta, TransactionSigned, TransactionSignedEcRecovered,
-    TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, Withdrawals, B256, U256,
+    StorageEntry, TransactionKind, TransactionMeta, TransactionSigned,
+    TransactionSignedEcRecovered, TransactionSignedNoHash, TxHash, TxNumber, Withdrawal,
+    Withdrawals, B256, KECCAK_EMPTY, U256,
+};
+use reth_trie::{
+    hashed_cursor::HashedPostStateCursorFactory, prefix_set::PrefixSetMut, proof::Proof,
+    updates::TrieUpdates, HashedPostState, HashedPostStateSorted, StateRoot, StorageRootError,
 };
-use reth_trie::{prefix_set::PrefixSetMut, updates::TrieUpdates, HashedPostState, StateRoot};
 use revm::primitives::{BlockEnv, CfgEnvWithHandlerCfg, SpecId};
 use std::{
     collections::{hash_map, BTreeMap, BTreeSet, HashMap, HashSet},
@@ -205,6 +211,46 @@ where
     Ok(Vec::new())
 }
 
+/// Merges the ordered entries of a plain state table with the values reverted from the changesets,
+/// returning up to `limit` entries that exist in the reverted state.
+///
+/// The reverts must be ordered by key. A reverted value of `None` means that the entry did not
+/// exist at the reverted block and overrides the plain state entry with the same key.
+fn merge_plain_state_with_reverts<K: Ord + Copy, V>(
+    mut plain_entry: Option<(K, V)>,
+    mut next_plain_entry: impl FnMut() -> ProviderResult<Option<(K, V)>>,
+    reverts: impl IntoIterator<Item = (K, Option<V>)>,
+    limit: usize,
+) -> ProviderResult<Vec<(K, V)>> {
+    let mut entries = Vec::new();
+    let mut reverts = reverts.into_iter().peekable();
+    while entries.len() < limit {
+        let revert_key = reverts.peek().map(|(key, _)| *key);
+        let entry = match (plain_entry.take(), revert_key) {
+            (None, None) => break,
+            (Some((key, value)), Some(revert_key)) if key < revert_key => {
+                plain_entry = next_plain_entry()?;
+                Some((key, value))
+            }
+            (Some(entry), None) => {
+                plain_entry = next_plain_entry()?;
+                Some(entry)
+            }
+            (current, Some(_)) => {
+                let (revert_key, value) = reverts.next().expect("is present");
+                // The reverted value takes precedence over the plain state entry.
+                plain_entry = match current {
+                    Some((key, _)) if key == revert_key => next_plain_entry()?,
+                    current => current,
+                };
+                value.map(|value| (revert_key, value))
+            }
+        };
+        entries.extend(entry);
+    }
+    Ok(entries)
+}
+
 impl<TX: DbTx> DatabaseProvider<TX> {
     /// Creates a provider with an inner read-only transaction.
     pub fn new(tx: TX, chain_spec: Arc<ChainSpec>) -> Self {
@@ -243,6 +289,140 @@ impl<TX: DbTx> DatabaseProvider<TX> {
             .collect::<Result<Vec<_>, DatabaseError>>()
     }
 
+    /// Recreates the state of the given changesets, taking the values after the changes from the
+    /// plain state.
+    ///
+    /// This only works for changesets of the blocks at the tip, as the plain state is the state
+    /// after the last block.
+    fn populate_bundle_state(
+        &self,
+        account_changeset: Vec<(BlockNumber, AccountBeforeTx)>,
+        storage_changeset: Vec<(BlockNumberAddress, StorageEntry)>,
+    ) -> ProviderResult<(BundleStateInit, RevertsInit)> {
+        // iterate previous value and get plain state value to create changeset
+        // Double option around Account represent if Account state is know (first option) and
+        // account is removed (Second Option)
+
+        let mut state: BundleStateInit = HashMap::new();
+
+        // This is not working for blocks that are not at tip. as plain state is not the last
+        // state of end range. We should rename the functions or add support to access
+        // History state. Accessing history state can be tricky but we are not gaining
+        // anything.
+        let mut plain_accounts_cursor = self.tx.cursor_read::<tables::PlainAccountState>()?;
+        let mut plain_storage_cursor = self.tx.cursor_dup_read::<tables::PlainStorageState>()?;
+
+        let mut reverts: RevertsInit = HashMap::new();
+
+        // add account changeset changes
+        for (block_number, account_before) in account_changeset.into_iter().rev() {
+            let AccountBeforeTx { info: old_info, address } = account_before;
+            match state.entry(address) {
+                hash_map::Entry::Vacant(entry) => {
+                    let new_info = plain_accounts_cursor.seek_exact(address)?.map(|kv| kv.1);
+                    entry.insert((old_info, new_info, HashMap::new()));
+                }
+                hash_map::Entry::Occupied(mut entry) => {
+                    // overwrite old account state.
+                    entry.get_mut().0 = old_info;
+                }
+            }
+            // insert old info into reverts.
+            reverts.entry(block_number).or_default().entry(address).or_default().0 = Some(old_info);
+        }
+
+        // add storage changeset changes
+        for (block_and_address, old_storage) in storage_changeset.into_iter().rev() {
+            let BlockNumberAddress((block_number, address)) = block_and_address;
+            // get account state or insert from plain state.
+            let account_state = match state.entry(address) {
+                hash_map::Entry::Vacant(entry) => {
+                    let present_info = plain_accounts_cursor.seek_exact(address)?.map(|kv| kv.1);
+                    entry.insert((present_info, present_info, HashMap::new()))
+                }
+                hash_map::Entry::Occupied(entry) => entry.into_mut(),
+            };
+
+            // match storage.
+            match account_state.2.entry(old_storage.key) {
+                hash_map::Entry::Vacant(entry) => {
+                    let new_storage = plain_storage_cursor
+                        .seek_by_key_subkey(address, old_storage.key)?
+                        .filter(|storage| storage.key == old_storage.key)
+                        .unwrap_or_default();
+                    entry.insert((old_storage.value, new_storage.value));
+                }
+                hash_map::Entry::Occupied(mut entry) => {
+                    entry.get_mut().0 = old_storage.value;
+                }
+            };
+
+            reverts
+                .entry(block_number)
+                .or_default()
+                .entry(address)
+                .or_default()
+                .1
+                .push(old_storage);
+        }
+
+        Ok((state, reverts))
+    }
+
+    /// Returns the blocks of the range with their senders and execution results, like
+    /// [`BlockExecutionWriter::get_block_and_execution_range`], but without write access.
+    ///
+    /// The state after the blocks is read from the plain state, so the range has to end at the
+    /// block that the state of the database is at.
+    pub fn get_block_and_execution_range_ro(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Chain> {
+        let mut blocks = Vec::new();
+        for number in range.clone() {
+            let block = self
+                .block_with_senders(number.into(), TransactionVariant::WithHash)?
+                .ok_or(ProviderError::BlockNotFound(number.into()))?;
+            let hash =
+                self.block_hash(number)?.ok_or(ProviderError::HeaderNotFound(number.into()))?;
+            blocks.push(block.seal(hash));
+        }
+
+        let Some(start_block_number) = blocks.first().map(|block| block.number) else {
+            return Ok(Chain::new(blocks, BundleStateWithReceipts::default(), None))
+        };
+
+        let block_bodies = self
+            .tx
+            .cursor_read::<tables::BlockBodyIndices>()?
+            .walk_range(range.clone())?
+            .collect::<Result<Vec<_>, _>>()?;
+        let first_transaction_num =
+            block_bodies.first().expect("blocks have body indices").1.first_tx_num();
+        let last_transaction_num =
+            block_bodies.last().expect("blocks have body indices").1.last_tx_num();
+
+        let storage_changeset = self.storage_changesets(range.clone())?;
+        let account_changeset = self.account_changesets(range)?;
+        let (state, reverts) = self.populate_bundle_state(account_changeset, storage_changeset)?;
+
+        let receipts = self
+            .tx
+            .cursor_read::<tables::Receipts>()?
+            .walk_range(first_transaction_num..=last_transaction_num)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let state = BundleStateWithReceipts::new_init(
+            state,
+            reverts,
+            Vec::new(),
+            receipts_by_block(block_bodies, receipts),
+            start_block_number,
+        );
+
+        Ok(Chain::new(blocks, state, None))
+    }
+
     /// Disables long-lived read transaction safety guarantees for leaks prevention and
     /// observability improvements.
     ///
@@ -282,8 +462,16 @@ impl<TX: DbTx> DatabaseProvider<TX> {
         if let Some(snapshot_provider) = &self.snapshot_provider {
             // If there is, check the maximum block or transaction number of the segment.
             if let Some(snapshot_upper_bound) = match segment {
-                SnapshotSegment::Headers => snapshot_provider.get_highest_snapshot_block(segment),
-                SnapshotSegment::Transactions | SnapshotSegment::Receipts => {
+                SnapshotSegment::Headers |
+                SnapshotSegment::AccountChangesets |
+                SnapshotSegment::StorageChangesets |
+                SnapshotSegment::AccountHistory |
+                SnapshotSegment::StorageHistory => {
+                    snapshot_provider.get_highest_snapshot_block(segment)
+                }
+                SnapshotSegment::Transactions |
+                SnapshotSegment::Receipts |
+                SnapshotSegment::TransactionSenders => {
                     snapshot_provider.get_highest_snapshot_tx(segment)
                 }
             } {
@@ -329,10 +517,14 @@ impl<TX: DbTx> DatabaseProvider<TX> {
         if let Some(provider) = &self.snapshot_provider {
             // If there is, check the maximum block or transaction number of the segment.
             let snapshot_upper_bound = match segment {
-                SnapshotSegment::Headers => provider.get_highest_snapshot_block(segment),
-                SnapshotSegment::Transactions | SnapshotSegment::Receipts => {
-                    provider.get_highest_snapshot_tx(segment)
-                }
+                SnapshotSegment::Headers |
+                SnapshotSegment::AccountChangesets |
+                SnapshotSegment::StorageChangesets |
+                SnapshotSegment::AccountHistory |
+                SnapshotSegment::StorageHistory => provider.get_highest_snapshot_block(segment),
+                SnapshotSegment::Transactions |
+                SnapshotSegment::Receipts |
+                SnapshotSegment::TransactionSenders => provider.get_highest_snapshot_tx(segment),
             };
 
             if snapshot_upper_bound
@@ -360,6 +552,199 @@ impl<TX: DbTx> DatabaseProvider<TX> {
             |_| true,
         )
     }
+
+    /// Iterate over the transactions and changesets of the given block range and collect the
+    /// addresses touched by the transactions, together with the blocks they were touched in.
+    ///
+    /// An address is touched by a transaction if it is its sender, its recipient or the contract
+    /// created by it. Addresses reached through the call traces of the transactions are covered
+    /// by the account and storage changesets if the calls changed their state, e.g. internal
+    /// transfers, contract creations, self-destructs and storage writes.
+    pub fn transaction_addresses_and_blocks_with_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeMap<Address, Vec<u64>>> {
+        let mut addresses: BTreeMap<Address, BTreeSet<u64>> = BTreeMap::new();
+        let mut body_cursor = self.tx.cursor_read::<tables::BlockBodyIndices>()?;
+        let mut tx_cursor = self.tx.cursor_read::<tables::Transactions>()?;
+        let mut senders_cursor = self.tx.cursor_read::<tables::TxSenders>()?;
+
+        for entry in body_cursor.walk_range(range.clone())? {
+            let (block_number, body) = entry?;
+            if body.tx_count == 0 {
+                continue
+            }
+
+            let transactions =
+                self.transactions_by_tx_range_with_cursor(body.tx_num_range(), &mut tx_cursor)?;
+            let senders = senders_cursor
+                .walk_range(body.tx_num_range())?
+                .collect::<Result<HashMap<_, _>, _>>()?;
+
+            for (tx_number, transaction) in body.tx_num_range().zip(transactions) {
+                // Senders might have been pruned, in which case they need to be recovered.
+                let sender = match senders.get(&tx_number) {
+                    Some(sender) => *sender,
+                    None => {
+                        transaction.recover_signer().ok_or(ProviderError::SenderRecoveryError)?
+                    }
+                };
+                let touched = match transaction.transaction.kind() {
+                    TransactionKind::Call(to) => *to,
+                    TransactionKind::Create => sender.create(transaction.transaction.nonce()),
+                };
+
+                addresses.entry(sender).or_default().insert(block_number);
+                addresses.entry(touched).or_default().insert(block_number);
+            }
+        }
+
+        for (block_number, account) in self.account_changesets(range.clone())? {
+            addresses.entry(account.address).or_default().insert(block_number);
+        }
+        for (key, _) in self.storage_changesets(range)? {
+            addresses.entry(key.address()).or_default().insert(key.block_number());
+        }
+
+        Ok(addresses
+            .into_iter()
+            .map(|(address, blocks)| (address, blocks.into_iter().collect()))
+            .collect())
+    }
+
+    /// Returns an error if the changesets of the block have been snapshotted. Snapshots are
+    /// immutable, so such blocks can't be unwound anymore.
+    fn ensure_not_snapshotted(&self, block_number: BlockNumber) -> ProviderResult<()> {
+        if let Some(snapshot_provider) = &self.snapshot_provider {
+            for segment in [SnapshotSegment::AccountChangesets, SnapshotSegment::StorageChangesets]
+            {
+                if snapshot_provider
+                    .get_highest_snapshot_block(segment)
+                    .map_or(false, |highest| highest >= block_number)
+                {
+                    return Err(ProviderError::UnwindSnapshottedBlock(segment, block_number))
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the account changesets of the blocks in the range, reading the blocks covered by
+    /// the account changesets snapshots from them.
+    fn account_changesets(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, AccountBeforeTx)>> {
+        snapshot::account_changesets(&self.tx, self.snapshot_provider.as_deref(), range)
+    }
+
+    /// Returns the storage changesets of the blocks in the range, reading the blocks covered by
+    /// the storage changesets snapshots from them.
+    fn storage_changesets(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>> {
+        snapshot::storage_changesets(&self.tx, self.snapshot_provider.as_deref(), range)
+    }
+
+    /// Returns the range of blocks whose changesets need to be reverted from the plain state to
+    /// get the state after the given block, or `None` if the plain state is at that block.
+    ///
+    /// Returns an error if the block is ahead of the plain state or if the required changesets
+    /// have been pruned.
+    fn plain_state_revert_range(
+        &self,
+        block: BlockNumber,
+    ) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        let tip = self
+            .get_stage_checkpoint(StageId::Execution)?
+            .map(|checkpoint| checkpoint.block_number)
+            .unwrap_or_default();
+        if block > tip {
+            return Err(ProviderError::HeaderNotFound(block.into()))
+        }
+        if block == tip {
+            return Ok(None)
+        }
+
+        if block < self.lowest_available_state_block()? {
+            return Err(ProviderError::StateAtBlockPruned(block))
+        }
+
+        Ok(Some(block + 1..=tip))
+    }
+
+    /// Iterate over the receipts of the given block range and collect the addresses that emitted
+    /// logs and the topics of those logs, together with the blocks they were emitted in.
+    ///
+    /// Pruned receipts are skipped.
+    pub fn log_addresses_and_topics_with_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<(BTreeMap<Address, Vec<u64>>, BTreeMap<B256, Vec<u64>>)> {
+        fn push_block<K: Ord>(index: &mut BTreeMap<K, Vec<u64>>, key: K, block_number: u64) {
+            let blocks = index.entry(key).or_default();
+            if blocks.last() != Some(&block_number) {
+                blocks.push(block_number);
+            }
+        }
+
+        let mut addresses: BTreeMap<Address, Vec<u64>> = BTreeMap::new();
+        let mut topics: BTreeMap<B256, Vec<u64>> = BTreeMap::new();
+
+        for entry in self.tx.cursor_read::<tables::BlockBodyIndices>()?.walk_range(range)? {
+            let (block_number, body) = entry?;
+            if body.tx_count == 0 {
+                continue
+            }
+
+            for receipt in self.receipts_by_tx_range(body.tx_num_range())? {
+                for log in receipt.logs {
+                    push_block(&mut addresses, log.address, block_number);
+                    for topic in log.topics {
+                        push_block(&mut topics, topic, block_number);
+                    }
+                }
+            }
+        }
+
+        Ok((addresses, topics))
+    }
+
+    /// Returns the blocks within the given range from the shards of a log index table.
+    fn log_index_blocks<K, T>(
+        &self,
+        key: K,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>>
+    where
+        K: PartialEq + Clone,
+        T: Table<Key = ShardedKey<K>, Value = BlockNumberList>,
+    {
+        let mut blocks = Vec::new();
+
+        // The first shard with a highest block number greater or equal to the start of the range
+        // is the oldest shard that can contain blocks within the range.
+        let mut cursor = self.tx.cursor_read::<T>()?;
+        let mut item = cursor.seek(ShardedKey::new(key.clone(), *range.start()))?;
+        while let Some((sharded_key, list)) = item {
+            if sharded_key.key != key {
+                break
+            }
+            blocks.extend(
+                list.iter(0)
+                    .map(|i| i as BlockNumber)
+                    .skip_while(|i| i < range.start())
+                    .take_while(|i| i <= range.end()),
+            );
+            if sharded_key.highest_block_number >= *range.end() {
+                break
+            }
+            item = cursor.next()?;
+        }
+
+        Ok(blocks)
+    }
 }
 
 impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
@@ -412,80 +797,27 @@ impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
         let to_transaction_num =
             block_bodies.last().expect("already checked if there are blocks").1.last_tx_num();
 
-        let storage_range = BlockNumberAddress::range(range.clone());
-
-        let storage_changeset =
-            self.get_or_take::<tables::StorageChangeSet, UNWIND>(storage_range)?;
-        let account_changeset = self.get_or_take::<tables::AccountChangeSet, UNWIND>(range)?;
+        // Snapshotted changesets may have been removed from the database, so they can only be
+        // peeked at.
+        let (storage_changeset, account_changeset) = if UNWIND {
+            self.ensure_not_snapshotted(start_block_number)?;
 
-        // iterate previous value and get plain state value to create changeset
-        // Double option around Account represent if Account state is know (first option) and
-        // account is removed (Second Option)
-
-        let mut state: BundleStateInit = HashMap::new();
-
-        // This is not working for blocks that are not at tip. as plain state is not the last
-        // state of end range. We should rename the functions or add support to access
-        // History state. Accessing history state can be tricky but we are not gaining
-        // anything.
-        let mut plain_accounts_cursor = self.tx.cursor_write::<tables::PlainAccountState>()?;
-        let mut plain_storage_cursor = self.tx.cursor_dup_write::<tables::PlainStorageState>()?;
-
-        let mut reverts: RevertsInit = HashMap::new();
-
-        // add account changeset changes
-        for (block_number, account_before) in account_changeset.into_iter().rev() {
-            let AccountBeforeTx { info: old_info, address } = account_before;
-            match state.entry(address) {
-                hash_map::Entry::Vacant(entry) => {
-                    let new_info = plain_accounts_cursor.seek_exact(address)?.map(|kv| kv.1);
-                    entry.insert((old_info, new_info, HashMap::new()));
-                }
-                hash_map::Entry::Occupied(mut entry) => {
-                    // overwrite old account state.
-                    entry.get_mut().0 = old_info;
-                }
-            }
-            // insert old info into reverts.
-            reverts.entry(block_number).or_default().entry(address).or_default().0 = Some(old_info);
-        }
-
-        // add storage changeset changes
-        for (block_and_address, old_storage) in storage_changeset.into_iter().rev() {
-            let BlockNumberAddress((block_number, address)) = block_and_address;
-            // get account state or insert from plain state.
-            let account_state = match state.entry(address) {
-                hash_map::Entry::Vacant(entry) => {
-                    let present_info = plain_accounts_cursor.seek_exact(address)?.map(|kv| kv.1);
-                    entry.insert((present_info, present_info, HashMap::new()))
-                }
-                hash_map::Entry::Occupied(entry) => entry.into_mut(),
-            };
-
-            // match storage.
-            match account_state.2.entry(old_storage.key) {
-                hash_map::Entry::Vacant(entry) => {
-                    let new_storage = plain_storage_cursor
-                        .seek_by_key_subkey(address, old_storage.key)?
-                        .filter(|storage| storage.key == old_storage.key)
-                        .unwrap_or_default();
-                    entry.insert((old_storage.value, new_storage.value));
-                }
-                hash_map::Entry::Occupied(mut entry) => {
-                    entry.get_mut().0 = old_storage.value;
-                }
-            };
+            let storage_range = BlockNumberAddress::range(range.clone());
+            (
+                self.get_or_take::<tables::StorageChangeSet, UNWIND>(storage_range)?,
+                self.get_or_take::<tables::AccountChangeSet, UNWIND>(range)?,
+            )
+        } else {
+            (self.storage_changesets(range.clone())?, self.account_changesets(range)?)
+        };
 
-            reverts
-                .entry(block_number)
-                .or_default()
-                .entry(address)
-                .or_default()
-                .1
-                .push(old_storage);
-        }
+        let (state, reverts) = self.populate_bundle_state(account_changeset, storage_changeset)?;
 
         if UNWIND {
+            let mut plain_accounts_cursor = self.tx.cursor_write::<tables::PlainAccountState>()?;
+            let mut plain_storage_cursor =
+                self.tx.cursor_dup_write::<tables::PlainStorageState>()?;
+
             // iterate over local plain state remove all account and all storages.
             for (address, (old_account, new_account, storage)) in state.iter() {
                 // revert account if needed.
@@ -520,28 +852,14 @@ impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
             }
         }
 
-        // iterate over block body and create ExecutionResult
-        let mut receipt_iter = self
-            .get_or_take::<tables::Receipts, UNWIND>(from_transaction_num..=to_transaction_num)?
-            .into_iter();
-
-        let mut receipts = Vec::new();
-        // loop break if we are at the end of the blocks.
-        for (_, block_body) in block_bodies.into_iter() {
-            let mut block_receipts = Vec::with_capacity(block_body.tx_count as usize);
-            for _ in block_body.tx_num_range() {
-                if let Some((_, receipt)) = receipt_iter.next() {
-                    block_receipts.push(Some(receipt));
-                }
-            }
-            receipts.push(block_receipts);
-        }
+        let receipts = self
+            .get_or_take::<tables::Receipts, UNWIND>(from_transaction_num..=to_transaction_num)?;
 
         Ok(BundleStateWithReceipts::new_init(
             state,
             reverts,
             Vec::new(),
-            reth_primitives::Receipts::from_vec(receipts),
+            receipts_by_block(block_bodies, receipts),
             start_block_number,
         ))
     }
@@ -907,6 +1225,70 @@ impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
         Ok((deleted, walker.next().transpose()?.is_none()))
     }
 
+    /// Prune the history shards of the given keys up to the provided block, inclusive.
+    ///
+    /// Each key is the sharded key of the first shard to look at, e.g. with a highest block number
+    /// of zero, and `key_matches` tells whether a shard still belongs to it. Shards holding only
+    /// blocks up to the target one are deleted, and the first shard with higher blocks is
+    /// filtered.
+    ///
+    /// Returns the number of keys processed, the number of deleted and updated shards, and
+    /// whether all keys have been processed within the limit of shards.
+    pub fn prune_history_shards<T, SK>(
+        &self,
+        keys: impl IntoIterator<Item = T::Key>,
+        to_block: BlockNumber,
+        limit: usize,
+        key_matches: impl Fn(&T::Key, &T::Key) -> bool,
+    ) -> Result<(usize, usize, bool), DatabaseError>
+    where
+        T: Table<Value = BlockNumberList>,
+        T::Key: AsRef<ShardedKey<SK>>,
+    {
+        let mut cursor = self.tx.cursor_write::<T>()?;
+        let mut processed = 0;
+        let mut pruned = 0;
+
+        let mut keys = keys.into_iter().peekable();
+        while pruned < limit {
+            let Some(key) = keys.next() else { break };
+
+            let mut item = cursor.seek(key.clone())?;
+            while let Some((shard_key, blocks)) = item {
+                if !key_matches(&shard_key, &key) {
+                    break
+                }
+
+                if shard_key.as_ref().highest_block_number <= to_block {
+                    cursor.delete_current()?;
+                    pruned += 1;
+                    item = cursor.next()?;
+                    continue
+                }
+
+                // Shards are ordered by their highest block, so this is the last shard of the key
+                // that can hold blocks up to the target one.
+                let new_blocks = blocks
+                    .iter(0)
+                    .skip_while(|block| *block as u64 <= to_block)
+                    .collect::<Vec<_>>();
+                if new_blocks.len() != blocks.len() {
+                    if new_blocks.is_empty() {
+                        cursor.delete_current()?;
+                    } else {
+                        cursor.upsert(shard_key, BlockNumberList::new_pre_sorted(new_blocks))?;
+                    }
+                    pruned += 1;
+                }
+                break
+            }
+
+            processed += 1;
+        }
+
+        Ok((processed, pruned, keys.peek().is_none()))
+    }
+
     /// Load shard and remove it. If list is empty, last shard was full or
     /// there are no shards at all.
     fn take_shard<T>(&self, key: T::Key) -> ProviderResult<Vec<u64>>
@@ -966,6 +1348,43 @@ impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
         }
         Ok(())
     }
+
+    /// Unwind the shards of a log index table, removing all blocks starting with the first block
+    /// of each key.
+    ///
+    /// Returns number of keys unwound.
+    fn unwind_log_index_shards<K, T>(
+        &self,
+        index_updates: BTreeMap<K, Vec<u64>>,
+    ) -> ProviderResult<usize>
+    where
+        K: PartialEq + Clone,
+        T: Table<Key = ShardedKey<K>, Value = BlockNumberList>,
+    {
+        let mut unwound = 0;
+        let mut cursor = self.tx.cursor_write::<T>()?;
+        for (key, blocks) in index_updates {
+            let Some(&rem_index) = blocks.first() else { continue };
+            let partial_shard = unwind_history_shards::<_, T, _>(
+                &mut cursor,
+                ShardedKey::last(key.clone()),
+                rem_index,
+                |sharded_key| sharded_key.key == key,
+            )?;
+
+            // Check the last returned partial shard.
+            // If it's not empty, the shard needs to be reinserted.
+            if !partial_shard.is_empty() {
+                cursor.insert(
+                    ShardedKey::last(key),
+                    BlockNumberList::new_pre_sorted(partial_shard),
+                )?;
+            }
+            unwound += 1;
+        }
+
+        Ok(unwound)
+    }
 }
 
 impl<TX: DbTx> AccountReader for DatabaseProvider<TX> {
@@ -979,13 +1398,11 @@ impl<TX: DbTx> AccountExtReader for DatabaseProvider<TX> {
         &self,
         range: impl RangeBounds<BlockNumber>,
     ) -> ProviderResult<BTreeSet<Address>> {
-        self.tx
-            .cursor_read::<tables::AccountChangeSet>()?
-            .walk_range(range)?
-            .map(|entry| {
-                entry.map(|(_, account_before)| account_before.address).map_err(Into::into)
-            })
-            .collect()
+        Ok(self
+            .account_changesets(range)?
+            .into_iter()
+            .map(|(_, account_before)| account_before.address)
+            .collect())
     }
 
     fn basic_accounts(
@@ -1003,16 +1420,13 @@ impl<TX: DbTx> AccountExtReader for DatabaseProvider<TX> {
         &self,
         range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<BTreeMap<Address, Vec<u64>>> {
-        let mut changeset_cursor = self.tx.cursor_read::<tables::AccountChangeSet>()?;
-
-        let account_transitions = changeset_cursor.walk_range(range)?.try_fold(
+        let account_transitions = self.account_changesets(range)?.into_iter().fold(
             BTreeMap::new(),
-            |mut accounts: BTreeMap<Address, Vec<u64>>, entry| -> ProviderResult<_> {
-                let (index, account) = entry?;
+            |mut accounts: BTreeMap<Address, Vec<u64>>, (index, account)| {
                 accounts.entry(account.address).or_default().push(index);
-                Ok(accounts)
+                accounts
             },
-        )?;
+        );
 
         Ok(account_transitions)
     }
@@ -1024,17 +1438,527 @@ impl<TX: DbTx> ChangeSetReader for DatabaseProvider<TX> {
         block_number: BlockNumber,
     ) -> ProviderResult<Vec<AccountBeforeTx>> {
         let range = block_number..=block_number;
-        self.tx
-            .cursor_read::<tables::AccountChangeSet>()?
-            .walk_range(range)?
-            .map(|result| -> ProviderResult<_> {
-                let (_, account_before) = result?;
-                Ok(account_before)
+        Ok(self
+            .account_changesets(range)?
+            .into_iter()
+            .map(|(_, account_before)| account_before)
+            .collect())
+    }
+}
+
+impl<TX: DbTx> AddressTransactionsReader for DatabaseProvider<TX> {
+    fn address_transactions_index_tip(&self) -> ProviderResult<Option<BlockNumber>> {
+        Ok(self
+            .get_stage_checkpoint(StageId::IndexAddressTransactions)?
+            .map(|checkpoint| checkpoint.block_number))
+    }
+
+    fn address_blocks_before(
+        &self,
+        address: Address,
+        block: BlockNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        let mut blocks = Vec::new();
+        if block == 0 {
+            return Ok(blocks)
+        }
+
+        // The first shard with a highest block number greater or equal to `block - 1` is the
+        // newest shard that can contain lower blocks, older shards are walked backwards.
+        let mut cursor = self.tx.cursor_read::<tables::AddressTransactions>()?;
+        let mut item = cursor.seek(ShardedKey::new(address, block - 1))?;
+        while let Some((sharded_key, list)) = item {
+            if sharded_key.key != address || blocks.len() >= limit {
+                break
+            }
+            let shard = list
+                .iter(0)
+                .map(|i| i as BlockNumber)
+                .take_while(|i| *i < block)
+                .collect::<Vec<_>>();
+            blocks.extend(shard.into_iter().rev().take(limit - blocks.len()));
+            item = cursor.prev()?;
+        }
+
+        Ok(blocks)
+    }
+
+    fn address_blocks_after(
+        &self,
+        address: Address,
+        block: BlockNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        let mut blocks = Vec::new();
+
+        let mut cursor = self.tx.cursor_read::<tables::AddressTransactions>()?;
+        let mut item = cursor.seek(ShardedKey::new(address, block.saturating_add(1)))?;
+        while let Some((sharded_key, list)) = item {
+            if sharded_key.key != address || blocks.len() >= limit {
+                break
+            }
+            blocks.extend(
+                list.iter(0)
+                    .map(|i| i as BlockNumber)
+                    .filter(|i| *i > block)
+                    .take(limit - blocks.len()),
+            );
+            item = cursor.next()?;
+        }
+
+        Ok(blocks)
+    }
+}
+
+impl<TX: DbTx> LogIndexReader for DatabaseProvider<TX> {
+    fn log_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        let Some(tip) = self.get_stage_checkpoint(StageId::IndexLogs)? else { return Ok(None) };
+        let lowest = match self
+            .get_prune_checkpoint(PruneSegment::LogIndex)?
+            .and_then(|checkpoint| checkpoint.block_number)
+        {
+            Some(pruned) => pruned + 1,
+            None => 0,
+        };
+        Ok((lowest <= tip.block_number).then_some(lowest..=tip.block_number))
+    }
+
+    fn log_address_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.log_index_blocks::<_, tables::LogAddressIndex>(address, range)
+    }
+
+    fn log_topic_blocks(
+        &self,
+        topic: B256,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.log_index_blocks::<_, tables::LogTopicIndex>(topic, range)
+    }
+}
+
+impl<TX: DbTx> DatabaseProvider<TX> {
+    /// Returns the lowest block whose state is available, i.e. whose subsequent changesets have
+    /// not been pruned.
+    pub fn lowest_available_state_block(&self) -> ProviderResult<BlockNumber> {
+        // The state after the highest pruned block can still be reverted to, since only the
+        // changesets up to and including that block have been pruned.
+        let mut lowest = 0;
+        for segment in [PruneSegment::AccountHistory, PruneSegment::StorageHistory] {
+            if let Some(pruned) =
+                self.get_prune_checkpoint(segment)?.and_then(|checkpoint| checkpoint.block_number)
+            {
+                lowest = lowest.max(pruned);
+            }
+        }
+        Ok(lowest)
+    }
+
+    /// Returns all addresses whose account or storage changed in the given range of blocks.
+    pub fn changed_addresses_with_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<Address>> {
+        let mut addresses = self.changed_accounts_with_range(range.clone())?;
+        for (block_address, _) in self.storage_changesets(range)? {
+            addresses.insert(block_address.address());
+        }
+        Ok(addresses)
+    }
+
+    /// Converts the provider into a provider of the state after the given block, see
+    /// [`StateRangeReader::state_range_at`](crate::StateRangeReader::state_range_at).
+    pub(crate) fn into_state_range_at(
+        self,
+        block: BlockNumber,
+    ) -> ProviderResult<StateRangeAtBlock<TX>> {
+        let mut accounts = BTreeMap::new();
+        let mut storages = HashMap::<_, BTreeMap<_, _>>::new();
+        let mut hashed = HashedPostState::default();
+        if let Some(range) = self.plain_state_revert_range(block)? {
+            let account_changesets = self.account_changesets(range.clone())?;
+            let storage_changesets = self.storage_changesets(range)?;
+
+            // Record the value before the first change of each account and slot after the block.
+            for (_, AccountBeforeTx { address, info }) in &account_changesets {
+                accounts.entry(*address).or_insert(*info);
+            }
+            for (block_address, StorageEntry { key, value }) in &storage_changesets {
+                storages
+                    .entry(block_address.address())
+                    .or_default()
+                    .entry(*key)
+                    .or_insert((*value != U256::ZERO).then_some(*value));
+            }
+            hashed = HashedPostState::from_reverts(account_changesets, storage_changesets);
+        }
+
+        Ok(StateRangeAtBlock { provider: self, accounts, storages, hashed: hashed.into_sorted() })
+    }
+}
+
+/// The state after a block, as the reverts from the latest plain and hashed state to it.
+#[derive(Debug)]
+pub(crate) struct StateRangeAtBlock<TX> {
+    /// The provider of the latest state.
+    provider: DatabaseProvider<TX>,
+    /// The value of each account before its first change after the block.
+    accounts: BTreeMap<Address, Option<Account>>,
+    /// The value of each storage slot before its first change after the block, keyed by address.
+    storages: HashMap<Address, BTreeMap<B256, Option<U256>>>,
+    /// The sorted reverts that are overlaid on the hashed state.
+    hashed: HashedPostStateSorted,
+}
+
+impl<TX: DbTx> StateRangeProvider for StateRangeAtBlock<TX> {
+    fn account_range(
+        &self,
+        start: Address,
+        limit: usize,
+    ) -> ProviderResult<Vec<(Address, Account)>> {
+        let reverts = self.accounts.range(start..).map(|(address, info)| (*address, *info));
+        let mut cursor = self.provider.tx.cursor_read::<tables::PlainAccountState>()?;
+        let first = cursor.seek(start)?;
+        merge_plain_state_with_reverts(first, || Ok(cursor.next()?), reverts, limit)
+    }
+
+    fn storage_range(
+        &self,
+        address: Address,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<Vec<StorageEntry>> {
+        let reverts = self
+            .storages
+            .get(&address)
+            .into_iter()
+            .flat_map(|slots| slots.range(start..))
+            .map(|(key, value)| (*key, *value));
+        let mut cursor = self.provider.tx.cursor_dup_read::<tables::PlainStorageState>()?;
+        let first =
+            cursor.seek_by_key_subkey(address, start)?.map(|entry| (entry.key, entry.value));
+        let entries = merge_plain_state_with_reverts(
+            first,
+            || Ok(cursor.next_dup_val()?.map(|entry| (entry.key, entry.value))),
+            reverts,
+            limit,
+        )?;
+        Ok(entries.into_iter().map(|(key, value)| StorageEntry { key, value }).collect())
+    }
+
+    fn hashed_account_range(
+        &self,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<Vec<(B256, Account)>> {
+        use reth_trie::hashed_cursor::{HashedAccountCursor, HashedCursorFactory};
+
+        let mut cursor = HashedPostStateCursorFactory::new(&self.provider.tx, &self.hashed)
+            .hashed_account_cursor()?;
+        let mut accounts = Vec::new();
+        let mut entry = cursor.seek(start)?;
+        while let Some(account) = entry {
+            if accounts.len() >= limit {
+                break
+            }
+            accounts.push(account);
+            entry = cursor.next()?;
+        }
+        Ok(accounts)
+    }
+
+    fn hashed_storage_range(
+        &self,
+        hashed_address: B256,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<Vec<StorageEntry>> {
+        use reth_trie::hashed_cursor::{HashedCursorFactory, HashedStorageCursor};
+
+        let mut cursor = HashedPostStateCursorFactory::new(&self.provider.tx, &self.hashed)
+            .hashed_storage_cursor()?;
+        let mut slots = Vec::new();
+        let mut entry = if cursor.is_storage_empty(hashed_address)? {
+            None
+        } else {
+            cursor.seek(hashed_address, start)?
+        };
+        while let Some(slot) = entry {
+            if slots.len() >= limit {
+                break
+            }
+            slots.push(slot);
+            entry = cursor.next()?;
+        }
+        Ok(slots)
+    }
+
+    fn storage_roots(&self, hashed_addresses: &[B256]) -> ProviderResult<Vec<B256>> {
+        hashed_addresses
+            .iter()
+            .map(|hashed_address| {
+                self.hashed
+                    .storage_root(&self.provider.tx, *hashed_address)
+                    .map_err(|StorageRootError::DB(err)| ProviderError::Database(err))
             })
             .collect()
     }
 }
 
+/// The estimated size of an account of a hashed account range: the hashed address and the account
+/// with its storage root and code hash.
+const ESTIMATED_HASHED_ACCOUNT_SIZE: usize = 32 + 110;
+
+/// The estimated size of a storage slot of a hashed storage range: the hashed slot and its value.
+const ESTIMATED_HASHED_SLOT_SIZE: usize = 32 + 33;
+
+/// The number of recent blocks whose state is served by [`HashedStateRangeReader`].
+///
+/// Snap peers sync towards a pivot block some distance behind the tip, and keep requesting its
+/// state while the chain advances.
+const SNAP_STATE_WINDOW: u64 = 128;
+
+/// The state of a recent block, as the reverts from the latest hashed state to it.
+#[derive(Debug)]
+struct SnapState {
+    /// The sorted reverts that are overlaid on the hashed state.
+    reverts: HashedPostStateSorted,
+    /// The account prefixes changed by the reverts.
+    account_prefixes: PrefixSetMut,
+    /// The storage prefixes changed by the reverts, keyed by hashed address.
+    storage_prefixes: HashMap<B256, PrefixSetMut>,
+}
+
+impl SnapState {
+    /// Returns a factory of cursors over the hashed state with the reverts overlaid.
+    fn cursor_factory<'a, TX>(&'a self, tx: &'a TX) -> HashedPostStateCursorFactory<'a, &'a TX> {
+        HashedPostStateCursorFactory::new(tx, &self.reverts)
+    }
+
+    /// Returns a proof generator for the state, which recomputes the trie nodes that are changed
+    /// by the reverts.
+    fn proof<'a, TX>(
+        &'a self,
+        tx: &'a TX,
+    ) -> Proof<'a, TX, HashedPostStateCursorFactory<'a, &'a TX>> {
+        Proof::new(tx)
+            .with_hashed_cursor_factory(self.cursor_factory(tx))
+            .with_changed_account_prefixes(self.account_prefixes.clone())
+            .with_changed_storage_prefixes(self.storage_prefixes.clone())
+    }
+}
+
+impl<TX: DbTx> DatabaseProvider<TX> {
+    /// Returns the block whose state the hashed state and the trie are at, or `None` if they are
+    /// not consistent with the last synced block, e.g. while the pipeline is running.
+    fn hashed_state_block(&self) -> ProviderResult<Option<BlockNumber>> {
+        // The hashed state and the trie are only consistent with the last synced block once all
+        // stages writing them have reached it.
+        let best_block = self.best_block_number()?;
+        for stage in [StageId::AccountHashing, StageId::StorageHashing, StageId::MerkleExecute] {
+            let checkpoint =
+                self.get_stage_checkpoint(stage)?.map(|checkpoint| checkpoint.block_number);
+            if checkpoint != Some(best_block) {
+                return Ok(None)
+            }
+        }
+        Ok(Some(best_block))
+    }
+
+    /// Returns the state with the given root, if it is the state of one of the last
+    /// [`SNAP_STATE_WINDOW`] blocks.
+    fn snap_state(&self, state_root: B256) -> ProviderResult<Option<SnapState>> {
+        let Some(best_block) = self.hashed_state_block()? else { return Ok(None) };
+
+        let first_block = (best_block + 1).saturating_sub(SNAP_STATE_WINDOW);
+        for block in (first_block..=best_block).rev() {
+            let Some(header) = self.header_by_number(block)? else { break };
+            if header.state_root != state_root {
+                continue
+            }
+
+            let reverts = HashedPostState::from_reverts(
+                self.account_changesets(block + 1..=best_block)?,
+                self.storage_changesets(block + 1..=best_block)?,
+            );
+            let (account_prefixes, storage_prefixes) = reverts.construct_prefix_sets_mut();
+            return Ok(Some(SnapState {
+                reverts: reverts.into_sorted(),
+                account_prefixes,
+                storage_prefixes,
+            }));
+        }
+
+        Ok(None)
+    }
+}
+
+impl<TX: DbTx> HashedStateRangeReader for DatabaseProvider<TX> {
+    fn hashed_state_root(&self) -> ProviderResult<Option<B256>> {
+        let Some(block) = self.hashed_state_block()? else { return Ok(None) };
+        Ok(self.header_by_number(block)?.map(|header| header.state_root))
+    }
+
+    fn hashed_account_range(
+        &self,
+        state_root: B256,
+        start: B256,
+        limit: B256,
+        max_bytes: usize,
+    ) -> ProviderResult<Option<HashedAccountRange>> {
+        use reth_trie::hashed_cursor::{HashedAccountCursor, HashedCursorFactory};
+
+        let Some(state) = self.snap_state(state_root)? else { return Ok(None) };
+
+        let proof = state.proof(&self.tx);
+        let mut cursor = state.cursor_factory(&self.tx).hashed_account_cursor()?;
+        let mut range = HashedAccountRange::default();
+        let mut size = 0;
+        let mut entry = cursor.seek(start)?;
+        while let Some((hashed_address, account)) = entry {
+            let storage_root = proof
+                .storage_root(hashed_address)
+                .map_err(|StorageRootError::DB(err)| ProviderError::Database(err))?;
+            range.accounts.push((hashed_address, account, storage_root));
+
+            size += ESTIMATED_HASHED_ACCOUNT_SIZE;
+            if hashed_address >= limit || size >= max_bytes {
+                break
+            }
+            entry = cursor.next()?;
+        }
+
+        let mut targets = Vec::from([Nibbles::unpack(start)]);
+        targets.extend(
+            range.accounts.last().map(|(hashed_address, _, _)| Nibbles::unpack(hashed_address)),
+        );
+        range.proof = proof
+            .account_multiproof(targets)
+            .map_err(|err| ProviderError::Database(err.into()))?
+            .into_values()
+            .collect();
+
+        Ok(Some(range))
+    }
+
+    fn hashed_storage_ranges(
+        &self,
+        state_root: B256,
+        accounts: &[B256],
+        start: B256,
+        limit: B256,
+        max_bytes: usize,
+    ) -> ProviderResult<Option<HashedStorageRanges>> {
+        use reth_trie::hashed_cursor::{HashedCursorFactory, HashedStorageCursor};
+
+        let Some(state) = self.snap_state(state_root)? else { return Ok(None) };
+
+        let proof = state.proof(&self.tx);
+        let mut cursor = state.cursor_factory(&self.tx).hashed_storage_cursor()?;
+        let mut ranges = HashedStorageRanges::default();
+        let mut size = 0;
+        for (idx, hashed_address) in accounts.iter().enumerate() {
+            if size >= max_bytes {
+                break
+            }
+
+            // The requested range only applies to the first account.
+            let (start, limit) =
+                if idx == 0 { (start, limit) } else { (B256::ZERO, B256::repeat_byte(0xff)) };
+
+            let mut slots = Vec::new();
+            let mut has_more = false;
+            let mut entry = if cursor.is_storage_empty(*hashed_address)? {
+                None
+            } else {
+                cursor.seek(*hashed_address, start)?
+            };
+            while let Some(slot) = entry {
+                let key = slot.key;
+                slots.push(slot);
+
+                size += ESTIMATED_HASHED_SLOT_SIZE;
+                if size >= max_bytes || key >= limit {
+                    has_more = cursor.next()?.is_some();
+                    break
+                }
+                entry = cursor.next()?;
+            }
+
+            // The range needs to be proven if it does not cover the entire storage of the account,
+            // in which case it has to be the last one.
+            let last = slots.last().map(|slot| slot.key);
+            ranges.slots.push(slots);
+            if start != B256::ZERO || has_more {
+                let mut targets = Vec::from([Nibbles::unpack(start)]);
+                targets.extend(last.map(Nibbles::unpack));
+                ranges.proof = proof
+                    .storage_multiproof(*hashed_address, targets)
+                    .map_err(|StorageRootError::DB(err)| ProviderError::Database(err))?
+                    .into_values()
+                    .collect();
+                break
+            }
+        }
+
+        Ok(Some(ranges))
+    }
+
+    fn account_trie_nodes(
+        &self,
+        state_root: B256,
+        paths: &[Nibbles],
+    ) -> ProviderResult<Option<Vec<Bytes>>> {
+        let Some(state) = self.snap_state(state_root)? else { return Ok(None) };
+
+        let nodes = state
+            .proof(&self.tx)
+            .account_multiproof(paths.to_vec())
+            .map_err(|err| ProviderError::Database(err.into()))?;
+        Ok(Some(paths.iter().map(|path| nodes.get(path).cloned().unwrap_or_default()).collect()))
+    }
+
+    fn storage_trie_nodes(
+        &self,
+        state_root: B256,
+        hashed_address: B256,
+        paths: &[Nibbles],
+    ) -> ProviderResult<Option<Vec<Bytes>>> {
+        let Some(state) = self.snap_state(state_root)? else { return Ok(None) };
+
+        let nodes = state
+            .proof(&self.tx)
+            .storage_multiproof(hashed_address, paths.to_vec())
+            .map_err(|StorageRootError::DB(err)| ProviderError::Database(err))?;
+        Ok(Some(paths.iter().map(|path| nodes.get(path).cloned().unwrap_or_default()).collect()))
+    }
+
+    fn bytecodes(&self, hashes: &[B256], max_bytes: usize) -> ProviderResult<Vec<Bytes>> {
+        let mut codes = Vec::new();
+        let mut size = 0;
+        for hash in hashes {
+            if size >= max_bytes {
+                break
+            }
+
+            let code = if *hash == KECCAK_EMPTY {
+                Some(Bytes::new())
+            } else {
+                self.tx.get::<tables::Bytecodes>(*hash)?.map(|code| code.original_bytes())
+            };
+            if let Some(code) = code {
+                size += code.len();
+                codes.push(code);
+            }
+        }
+        Ok(codes)
+    }
+}
+
 impl<TX: DbTx> HeaderSyncGapProvider for DatabaseProvider<TX> {
     fn sync_gap(
         &self,
@@ -1641,11 +2565,24 @@ impl<TX: DbTx> TransactionsProvider for DatabaseProvider<TX> {
         &self,
         range: impl RangeBounds<TxNumber>,
     ) -> ProviderResult<Vec<Address>> {
-        self.cursor_read_collect::<tables::TxSenders, _>(range, Ok).map_err(Into::into)
+        self.get_range_with_snapshot(
+            SnapshotSegment::TransactionSenders,
+            to_range(range),
+            |snapshot, range, _| snapshot.senders_by_tx_range(range),
+            |range, _| {
+                self.cursor_read_collect::<tables::TxSenders, _>(range, Ok).map_err(Into::into)
+            },
+            |_| true,
+        )
     }
 
     fn transaction_sender(&self, id: TxNumber) -> ProviderResult<Option<Address>> {
-        Ok(self.tx.get::<tables::TxSenders>(id)?)
+        self.get_with_snapshot(
+            SnapshotSegment::TransactionSenders,
+            id,
+            |snapshot| snapshot.transaction_sender(id),
+            || Ok(self.tx.get::<tables::TxSenders>(id)?),
+        )
     }
 }
 
@@ -1878,6 +2815,19 @@ impl<TX: DbTxMut> StageCheckpointWriter for DatabaseProvider<TX> {
             )?;
         }
 
+        // optional stages are only updated if they are enabled, i.e. have a checkpoint.
+        for stage_id in StageId::OPTIONAL {
+            if let Some((_, checkpoint)) = cursor.seek_exact(stage_id.to_string())? {
+                cursor.upsert(
+                    stage_id.to_string(),
+                    StageCheckpoint {
+                        block_number,
+                        ..if drop_stage_checkpoint { Default::default() } else { checkpoint }
+                    },
+                )?;
+            }
+        }
+
         Ok(())
     }
 }
@@ -1910,36 +2860,32 @@ impl<TX: DbTx> StorageReader for DatabaseProvider<TX> {
         &self,
         range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<BTreeMap<Address, BTreeSet<B256>>> {
-        self.tx
-            .cursor_read::<tables::StorageChangeSet>()?
-            .walk_range(BlockNumberAddress::range(range))?
+        Ok(self
+            .storage_changesets(range)?
+            .into_iter()
             // fold all storages and save its old state so we can remove it from HashedStorage
             // it is needed as it is dup table.
-            .try_fold(BTreeMap::new(), |mut accounts: BTreeMap<Address, BTreeSet<B256>>, entry| {
-                let (BlockNumberAddress((_, address)), storage_entry) = entry?;
+            .fold(BTreeMap::new(), |mut accounts: BTreeMap<Address, BTreeSet<B256>>, entry| {
+                let (BlockNumberAddress((_, address)), storage_entry) = entry;
                 accounts.entry(address).or_default().insert(storage_entry.key);
-                Ok(accounts)
-            })
+                accounts
+            }))
     }
 
     fn changed_storages_and_blocks_with_range(
         &self,
         range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<BTreeMap<(Address, B256), Vec<u64>>> {
-        let mut changeset_cursor = self.tx.cursor_read::<tables::StorageChangeSet>()?;
-
-        let storage_changeset_lists =
-            changeset_cursor.walk_range(BlockNumberAddress::range(range))?.try_fold(
-                BTreeMap::new(),
-                |mut storages: BTreeMap<(Address, B256), Vec<u64>>, entry| -> ProviderResult<_> {
-                    let (index, storage) = entry?;
-                    storages
-                        .entry((index.address(), storage.key))
-                        .or_default()
-                        .push(index.block_number());
-                    Ok(storages)
-                },
-            )?;
+        let storage_changeset_lists = self.storage_changesets(range)?.into_iter().fold(
+            BTreeMap::new(),
+            |mut storages: BTreeMap<(Address, B256), Vec<u64>>, (index, storage)| {
+                storages
+                    .entry((index.address(), storage.key))
+                    .or_default()
+                    .push(index.block_number());
+                storages
+            },
+        );
 
         Ok(storage_changeset_lists)
     }
@@ -1950,6 +2896,8 @@ impl<TX: DbTxMut + DbTx> HashingWriter for DatabaseProvider<TX> {
         &self,
         range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<BTreeMap<B256, Option<Account>>> {
+        self.ensure_not_snapshotted(*range.start())?;
+
         // Aggregate all block changesets and make a list of accounts that have been changed.
         // Note that collecting and then reversing the order is necessary to ensure that the
         // changes are applied in the correct order.
@@ -1997,6 +2945,8 @@ impl<TX: DbTxMut + DbTx> HashingWriter for DatabaseProvider<TX> {
         &self,
         range: Range<BlockNumberAddress>,
     ) -> ProviderResult<HashMap<B256, BTreeSet<B256>>> {
+        self.ensure_not_snapshotted(range.start.block_number())?;
+
         // Aggregate all block changesets and make list of accounts that have been changed.
         let mut changesets = self.tx.cursor_read::<tables::StorageChangeSet>()?;
         let mut hashed_storages = changesets
@@ -2156,13 +3106,88 @@ impl<TX: DbTxMut + DbTx> HistoryWriter for DatabaseProvider<TX> {
 
         // storage history stage
         {
-            let indices = self.changed_storages_and_blocks_with_range(range)?;
+            let indices = self.changed_storages_and_blocks_with_range(range.clone())?;
             self.insert_storage_history_index(indices)?;
         }
 
+        // address transactions stage, only if it's enabled
+        if self.get_stage_checkpoint(StageId::IndexAddressTransactions)?.is_some() {
+            let indices = self.transaction_addresses_and_blocks_with_range(range.clone())?;
+            self.insert_address_transactions_index(indices)?;
+        }
+
+        // logs stage, only if it's enabled
+        if self.get_stage_checkpoint(StageId::IndexLogs)?.is_some() {
+            let (addresses, topics) = self.log_addresses_and_topics_with_range(range)?;
+            self.insert_log_index(addresses, topics)?;
+        }
+
         Ok(())
     }
 
+    fn insert_address_transactions_index(
+        &self,
+        address_transactions: BTreeMap<Address, Vec<u64>>,
+    ) -> ProviderResult<()> {
+        self.append_history_index::<_, tables::AddressTransactions>(
+            address_transactions,
+            ShardedKey::new,
+        )
+    }
+
+    fn unwind_address_transactions_index(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<usize> {
+        let mut last_indices = self
+            .transaction_addresses_and_blocks_with_range(range)?
+            .into_iter()
+            .filter_map(|(address, blocks)| blocks.first().map(|block| (address, *block)))
+            .collect::<Vec<_>>();
+        last_indices.sort_by_key(|(a, _)| *a);
+
+        // Unwind the address transactions index.
+        let mut cursor = self.tx.cursor_write::<tables::AddressTransactions>()?;
+        for &(address, rem_index) in &last_indices {
+            let partial_shard = unwind_history_shards::<_, tables::AddressTransactions, _>(
+                &mut cursor,
+                ShardedKey::last(address),
+                rem_index,
+                |sharded_key| sharded_key.key == address,
+            )?;
+
+            // Check the last returned partial shard.
+            // If it's not empty, the shard needs to be reinserted.
+            if !partial_shard.is_empty() {
+                cursor.insert(
+                    ShardedKey::last(address),
+                    BlockNumberList::new_pre_sorted(partial_shard),
+                )?;
+            }
+        }
+
+        Ok(last_indices.len())
+    }
+
+    fn insert_log_index(
+        &self,
+        log_addresses: BTreeMap<Address, Vec<u64>>,
+        log_topics: BTreeMap<B256, Vec<u64>>,
+    ) -> ProviderResult<()> {
+        self.append_history_index::<_, tables::LogAddressIndex>(log_addresses, ShardedKey::new)?;
+        self.append_history_index::<_, tables::LogTopicIndex>(log_topics, ShardedKey::new)
+    }
+
+    fn unwind_log_index(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<usize> {
+        let (addresses, topics) = self.log_addresses_and_topics_with_range(range)?;
+
+        let unwound_addresses =
+            self.unwind_log_index_shards::<_, tables::LogAddressIndex>(addresses)?;
+        let unwound_topics = self.unwind_log_index_shards::<_, tables::LogTopicIndex>(topics)?;
+
+        Ok(unwound_addresses + unwound_topics)
+    }
+
     fn insert_storage_history_index(
         &self,
         storage_transitions: BTreeMap<(Address, B256), Vec<u64>>,
@@ -2186,6 +3211,8 @@ impl<TX: DbTxMut + DbTx> HistoryWriter for DatabaseProvider<TX> {
         &self,
         range: Range<BlockNumberAddress>,
     ) -> ProviderResult<usize> {
+        self.ensure_not_snapshotted(range.start.block_number())?;
+
         let mut storage_changesets = self
             .tx
             .cursor_read::<tables::StorageChangeSet>()?
@@ -2226,6 +3253,8 @@ impl<TX: DbTxMut + DbTx> HistoryWriter for DatabaseProvider<TX> {
         &self,
         range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<usize> {
+        self.ensure_not_snapshotted(*range.start())?;
+
         let mut last_indices = self
             .tx
             .cursor_read::<tables::AccountChangeSet>()?
@@ -2302,6 +3331,16 @@ impl<TX: DbTxMut + DbTx> BlockExecutionWriter for DatabaseProvider<TX> {
             // Unwind storage history indices.
             self.unwind_storage_history_indices(storage_range)?;
 
+            // Unwind the address transactions index, if it's enabled.
+            if self.get_stage_checkpoint(StageId::IndexAddressTransactions)?.is_some() {
+                self.unwind_address_transactions_index(range.clone())?;
+            }
+
+            // Unwind the log index, if it's enabled.
+            if self.get_stage_checkpoint(StageId::IndexLogs)?.is_some() {
+                self.unwind_log_index(range.clone())?;
+            }
+
             // Calculate the reverted merkle root.
             // This is the same as `StateRoot::incremental_root_with_updates`, only the prefix sets
             // are pre-loaded.
@@ -2559,6 +3598,28 @@ impl<TX: DbTxMut> PruneCheckpointWriter for DatabaseProvider<TX> {
     }
 }
 
+/// Groups the receipts by the blocks of the given body indices.
+fn receipts_by_block(
+    block_bodies: Vec<(BlockNumber, StoredBlockBodyIndices)>,
+    receipts: Vec<(TxNumber, Receipt)>,
+) -> reth_primitives::Receipts {
+    let mut receipt_iter = receipts.into_iter();
+
+    let mut receipts = Vec::new();
+    // loop break if we are at the end of the blocks.
+    for (_, block_body) in block_bodies.into_iter() {
+        let mut block_receipts = Vec::with_capacity(block_body.tx_count as usize);
+        for _ in block_body.tx_num_range() {
+            if let Some((_, receipt)) = receipt_iter.next() {
+                block_receipts.push(Some(receipt));
+            }
+        }
+        receipts.push(block_receipts);
+    }
+
+    reth_primitives::Receipts::from_vec(receipts)
+}
+
 fn range_size_hint(range: &impl RangeBounds<TxNumber>) -> Option<usize> {
     let start = match range.start_bound().cloned() {
         Bound::Included(start) => start,
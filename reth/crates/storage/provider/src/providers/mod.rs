@@ -1,10 +1,12 @@
 use crate::{
-    AccountReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
-    BlockchainTreePendingStateProvider, BundleStateDataProvider, CanonChainTracker,
-    CanonStateNotifications, CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader,
-    DatabaseProviderFactory, EvmEnvProvider, HeaderProvider, ProviderError, PruneCheckpointReader,
-    ReceiptProvider, ReceiptProviderIdExt, StageCheckpointReader, StateProviderBox,
-    StateProviderFactory, TransactionVariant, TransactionsProvider, WithdrawalsProvider,
+    AccountReader, AddressTransactionsReader, BlockHashReader, BlockIdReader, BlockNumReader,
+    BlockReader, BlockReaderIdExt, BlockchainTreePendingStateProvider, BundleStateDataProvider,
+    CanonChainTracker, CanonStateNotifications, CanonStateSubscriptions, ChainSpecProvider,
+    ChangeSetReader, DatabaseProviderFactory, EvmEnvProvider, HashedAccountRange,
+    HashedStateRangeReader, HashedStorageRanges, HeaderProvider, LogIndexReader, ProviderError,
+    PruneCheckpointReader, ReceiptProvider, ReceiptProviderIdExt, StageCheckpointReader,
+    StateProviderBox, StateProviderFactory, StateRangeProviderBox, StateRangeReader,
+    TransactionVariant, TransactionsProvider, WithdrawalsProvider,
 };
 use reth_db::{database::Database, models::StoredBlockBodyIndices};
 use reth_interfaces::{
@@ -16,15 +18,16 @@ use reth_interfaces::{
 use reth_node_api::ConfigureEvmEnv;
 use reth_primitives::{
     stage::{StageCheckpoint, StageId},
+    trie::Nibbles,
     Account, Address, Block, BlockHash, BlockHashOrNumber, BlockId, BlockNumHash, BlockNumber,
-    BlockNumberOrTag, BlockWithSenders, ChainInfo, ChainSpec, Header, PruneCheckpoint,
+    BlockNumberOrTag, BlockWithSenders, Bytes, ChainInfo, ChainSpec, Header, PruneCheckpoint,
     PruneSegment, Receipt, SealedBlock, SealedBlockWithSenders, SealedHeader, TransactionMeta,
     TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, Withdrawals, B256,
     U256,
 };
 use revm::primitives::{BlockEnv, CfgEnvWithHandlerCfg};
 use std::{
-    collections::{BTreeMap, HashSet},
+    collections::{BTreeMap, BTreeSet, HashSet},
     ops::{RangeBounds, RangeInclusive},
     sync::Arc,
     time::Instant,
@@ -884,6 +887,135 @@ where
     }
 }
 
+impl<DB, Tree> AddressTransactionsReader for BlockchainProvider<DB, Tree>
+where
+    DB: Database,
+    Tree: Sync + Send,
+{
+    fn address_transactions_index_tip(&self) -> ProviderResult<Option<BlockNumber>> {
+        self.database.provider()?.address_transactions_index_tip()
+    }
+
+    fn address_blocks_before(
+        &self,
+        address: Address,
+        block: BlockNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.database.provider()?.address_blocks_before(address, block, limit)
+    }
+
+    fn address_blocks_after(
+        &self,
+        address: Address,
+        block: BlockNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.database.provider()?.address_blocks_after(address, block, limit)
+    }
+}
+
+impl<DB, Tree> LogIndexReader for BlockchainProvider<DB, Tree>
+where
+    DB: Database,
+    Tree: Sync + Send,
+{
+    fn log_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        self.database.provider()?.log_index_range()
+    }
+
+    fn log_address_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.database.provider()?.log_address_blocks(address, range)
+    }
+
+    fn log_topic_blocks(
+        &self,
+        topic: B256,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.database.provider()?.log_topic_blocks(topic, range)
+    }
+}
+
+impl<DB, Tree> StateRangeReader for BlockchainProvider<DB, Tree>
+where
+    DB: Database,
+    Tree: Sync + Send,
+{
+    fn lowest_available_state_block(&self) -> ProviderResult<BlockNumber> {
+        self.database.provider()?.lowest_available_state_block()
+    }
+
+    fn state_range_at(&self, block: BlockNumber) -> ProviderResult<StateRangeProviderBox> {
+        self.database.state_range_at(block)
+    }
+
+    fn changed_addresses_with_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<Address>> {
+        self.database.provider()?.changed_addresses_with_range(range)
+    }
+}
+
+impl<DB, Tree> HashedStateRangeReader for BlockchainProvider<DB, Tree>
+where
+    DB: Database,
+    Tree: Sync + Send,
+{
+    fn hashed_state_root(&self) -> ProviderResult<Option<B256>> {
+        self.database.provider()?.hashed_state_root()
+    }
+
+    fn hashed_account_range(
+        &self,
+        state_root: B256,
+        start: B256,
+        limit: B256,
+        max_bytes: usize,
+    ) -> ProviderResult<Option<HashedAccountRange>> {
+        self.database.provider()?.hashed_account_range(state_root, start, limit, max_bytes)
+    }
+
+    fn hashed_storage_ranges(
+        &self,
+        state_root: B256,
+        accounts: &[B256],
+        start: B256,
+        limit: B256,
+        max_bytes: usize,
+    ) -> ProviderResult<Option<HashedStorageRanges>> {
+        self.database
+            .provider()?
+            .hashed_storage_ranges(state_root, accounts, start, limit, max_bytes)
+    }
+
+    fn account_trie_nodes(
+        &self,
+        state_root: B256,
+        paths: &[Nibbles],
+    ) -> ProviderResult<Option<Vec<Bytes>>> {
+        self.database.provider()?.account_trie_nodes(state_root, paths)
+    }
+
+    fn storage_trie_nodes(
+        &self,
+        state_root: B256,
+        hashed_address: B256,
+        paths: &[Nibbles],
+    ) -> ProviderResult<Option<Vec<Bytes>>> {
+        self.database.provider()?.storage_trie_nodes(state_root, hashed_address, paths)
+    }
+
+    fn bytecodes(&self, hashes: &[B256], max_bytes: usize) -> ProviderResult<Vec<Bytes>> {
+        self.database.provider()?.bytecodes(hashes, max_bytes)
+    }
+}
+
 impl<DB, Tree> AccountReader for BlockchainProvider<DB, Tree>
 where
     DB: Database + Sync + Send,
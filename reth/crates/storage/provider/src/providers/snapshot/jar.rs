@@ -5,14 +5,22 @@ use crate::{
 };
 use reth_db::{
     codecs::CompactU256,
-    snapshot::{HeaderMask, ReceiptMask, SnapshotCursor, TransactionMask},
+    models::{AccountBeforeTx, BlockNumberAddress},
+    snapshot::{
+        account_changeset_key, account_history_key, storage_changeset_key, storage_history_key,
+        AccountChangesetMask, AccountHistoryMask, HeaderMask, KeyOrNumber, ReceiptMask,
+        SnapshotCursor, StorageChangesetMask, StorageHistoryMask, TransactionMask,
+        TransactionSenderMask,
+    },
+    BlockNumberList,
 };
 use reth_interfaces::provider::{ProviderError, ProviderResult};
 use reth_primitives::{
     Address, BlockHash, BlockHashOrNumber, BlockNumber, ChainInfo, Header, Receipt, SealedHeader,
-    TransactionMeta, TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber, B256, U256,
+    StorageEntry, TransactionMeta, TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber,
+    B256, U256,
 };
-use std::ops::{Deref, RangeBounds};
+use std::ops::{Deref, Range, RangeBounds};
 
 /// Provider over a specific `NippyJar` and range.
 #[derive(Debug)]
@@ -50,6 +58,152 @@ impl<'a> SnapshotJarProvider<'a> {
         self.auxiliar_jar = Some(Box::new(auxiliar_jar));
         self
     }
+
+    /// Returns the sender of the transaction from a transaction senders snapshot.
+    pub fn sender(&self, num: TxNumber) -> ProviderResult<Option<Address>> {
+        self.cursor()?.get_one::<TransactionSenderMask<Address>>(num.into())
+    }
+
+    /// Returns the changeset entry of the account in the given block from an account changesets
+    /// snapshot.
+    pub fn account_changeset(
+        &self,
+        block_number: BlockNumber,
+        address: Address,
+    ) -> ProviderResult<Option<AccountBeforeTx>> {
+        let key = account_changeset_key(block_number, address);
+        Ok(self
+            .cursor()?
+            .get_two::<AccountChangesetMask<AccountBeforeTx, BlockNumber>>(key.as_slice().into())?
+            .filter(|(account_before, number)| {
+                account_before.address == address && *number == block_number
+            })
+            .map(|(account_before, _)| account_before))
+    }
+
+    /// Returns the changeset entry of the storage slot in the given block from a storage
+    /// changesets snapshot.
+    pub fn storage_changeset(
+        &self,
+        block_number: BlockNumber,
+        address: Address,
+        storage_key: B256,
+    ) -> ProviderResult<Option<StorageEntry>> {
+        let key = storage_changeset_key(block_number, address, storage_key);
+        Ok(self
+            .cursor()?
+            .get_three::<StorageChangesetMask<StorageEntry, BlockNumber, Address>>(
+                key.as_slice().into(),
+            )?
+            .filter(|(entry, number, entry_address)| {
+                entry.key == storage_key && *number == block_number && *entry_address == address
+            })
+            .map(|(entry, _, _)| entry))
+    }
+
+    /// Returns the changeset entries of the blocks in the range from an account changesets
+    /// snapshot.
+    pub fn account_changesets(
+        &self,
+        range: Range<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, AccountBeforeTx)>> {
+        Ok(self
+            .changesets(
+                range,
+                |cursor, row| {
+                    cursor.get_two::<AccountChangesetMask<AccountBeforeTx, BlockNumber>>(row)
+                },
+                |(_, number)| *number,
+            )?
+            .into_iter()
+            .map(|(account_before, number)| (number, account_before))
+            .collect())
+    }
+
+    /// Returns the changeset entries of the blocks in the range from a storage changesets
+    /// snapshot.
+    pub fn storage_changesets(
+        &self,
+        range: Range<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>> {
+        Ok(self
+            .changesets(
+                range,
+                |cursor, row| {
+                    cursor
+                        .get_three::<StorageChangesetMask<StorageEntry, BlockNumber, Address>>(row)
+                },
+                |(_, number, _)| *number,
+            )?
+            .into_iter()
+            .map(|(entry, number, address)| ((number, address).into(), entry))
+            .collect())
+    }
+
+    /// Returns the rows of a changesets snapshot whose block is in the range.
+    ///
+    /// Rows are stored in block order, so the first one of the range is found by binary search.
+    fn changesets<T>(
+        &self,
+        range: Range<BlockNumber>,
+        mut read: impl FnMut(&mut SnapshotCursor<'_>, KeyOrNumber<'_>) -> ProviderResult<Option<T>>,
+        block_number: impl Fn(&T) -> BlockNumber,
+    ) -> ProviderResult<Vec<T>> {
+        let mut cursor = self.cursor()?;
+        let offset = self.user_header().start();
+        let rows = self.rows() as u64;
+        let mut read_row = |cursor: &mut SnapshotCursor<'_>, row: u64| {
+            read(cursor, (offset + row).into())?
+                .ok_or_else(|| ProviderError::NippyJar(format!("row {row} is missing")))
+        };
+
+        let (mut low, mut high) = (0, rows);
+        while low < high {
+            let middle = low + (high - low) / 2;
+            if block_number(&read_row(&mut cursor, middle)?) < range.start {
+                low = middle + 1;
+            } else {
+                high = middle;
+            }
+        }
+
+        let mut entries = Vec::new();
+        for number in low..rows {
+            let entry = read_row(&mut cursor, number)?;
+            if block_number(&entry) >= range.end {
+                break
+            }
+            entries.push(entry);
+        }
+
+        Ok(entries)
+    }
+
+    /// Returns the blocks of an account history snapshot in which the account was changed.
+    pub fn account_history(&self, address: Address) -> ProviderResult<Option<BlockNumberList>> {
+        let key = account_history_key(address);
+        Ok(self
+            .cursor()?
+            .get_two::<AccountHistoryMask<BlockNumberList, Address>>(key.as_slice().into())?
+            .filter(|(_, entry_address)| *entry_address == address)
+            .map(|(blocks, _)| blocks))
+    }
+
+    /// Returns the blocks of a storage history snapshot in which the storage slot was changed.
+    pub fn storage_history(
+        &self,
+        address: Address,
+        storage_key: B256,
+    ) -> ProviderResult<Option<BlockNumberList>> {
+        let key = storage_history_key(address, storage_key);
+        Ok(self
+            .cursor()?
+            .get_three::<StorageHistoryMask<BlockNumberList, Address, B256>>(key.as_slice().into())?
+            .filter(|(_, entry_address, entry_key)| {
+                *entry_address == address && *entry_key == storage_key
+            })
+            .map(|(blocks, _, _)| blocks))
+    }
 }
 
 impl<'a> HeaderProvider for SnapshotJarProvider<'a> {
@@ -8,16 +8,20 @@ use dashmap::DashMap;
 use parking_lot::RwLock;
 use reth_db::{
     codecs::CompactU256,
-    models::StoredBlockBodyIndices,
-    snapshot::{iter_snapshots, HeaderMask, ReceiptMask, SnapshotCursor, TransactionMask},
+    models::{AccountBeforeTx, BlockNumberAddress, StoredBlockBodyIndices},
+    snapshot::{
+        iter_snapshots, HeaderMask, ReceiptMask, SnapshotCursor, TransactionMask,
+        TransactionSenderMask,
+    },
+    BlockNumberList,
 };
 use reth_interfaces::provider::{ProviderError, ProviderResult};
 use reth_nippy_jar::NippyJar;
 use reth_primitives::{
     snapshot::HighestSnapshots, Address, Block, BlockHash, BlockHashOrNumber, BlockNumber,
     BlockWithSenders, ChainInfo, Header, Receipt, SealedBlock, SealedBlockWithSenders,
-    SealedHeader, SnapshotSegment, TransactionMeta, TransactionSigned, TransactionSignedNoHash,
-    TxHash, TxNumber, Withdrawal, Withdrawals, B256, U256,
+    SealedHeader, SnapshotSegment, StorageEntry, TransactionMeta, TransactionSigned,
+    TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, Withdrawals, B256, U256,
 };
 use std::{
     collections::{hash_map::Entry, BTreeMap, HashMap},
@@ -25,6 +29,7 @@ use std::{
     path::{Path, PathBuf},
 };
 use tokio::sync::watch;
+use tracing::warn;
 
 /// Alias type for a map that can be queried for transaction/block ranges from a block/transaction
 /// segment respectively. It uses `BlockNumber` to represent the block end of a snapshot range or
@@ -47,6 +52,8 @@ pub struct SnapshotProvider {
     snapshots_tx_index: RwLock<SegmentRanges>,
     /// Tracks the highest snapshot of every segment.
     highest_tracker: Option<watch::Receiver<Option<HighestSnapshots>>>,
+    /// Highest snapshots reported by the tracker when the index was last updated.
+    indexed_snapshots: RwLock<Option<HighestSnapshots>>,
     /// Directory where snapshots are located
     path: PathBuf,
     /// Whether [`SnapshotJarProvider`] loads filters into memory. If not, `by_hash` queries won't
@@ -62,6 +69,7 @@ impl SnapshotProvider {
             snapshots_block_index: Default::default(),
             snapshots_tx_index: Default::default(),
             highest_tracker: None,
+            indexed_snapshots: Default::default(),
             path: path.as_ref().to_path_buf(),
             load_filters: false,
         };
@@ -161,7 +169,8 @@ impl SnapshotProvider {
         } else {
             let jar = NippyJar::load(&self.path.join(segment.filename(block_range, tx_range)))
                 .map(|jar| {
-                if self.load_filters {
+                // Keyed segments can't be queried at all without their filters.
+                if self.load_filters || segment.is_keyed() {
                     return jar.load_filters()
                 }
                 Ok(jar)
@@ -179,6 +188,7 @@ impl SnapshotProvider {
         segment: SnapshotSegment,
         block: u64,
     ) -> Option<(RangeInclusive<BlockNumber>, RangeInclusive<TxNumber>)> {
+        self.update_index_from_tracker();
         let snapshots = self.snapshots_block_index.read();
         let segment_snapshots = snapshots.get(&segment)?;
 
@@ -209,6 +219,7 @@ impl SnapshotProvider {
         segment: SnapshotSegment,
         tx: u64,
     ) -> Option<(RangeInclusive<BlockNumber>, RangeInclusive<TxNumber>)> {
+        self.update_index_from_tracker();
         let snapshots = self.snapshots_tx_index.read();
         let segment_snapshots = snapshots.get(&segment)?;
 
@@ -229,6 +240,24 @@ impl SnapshotProvider {
         None
     }
 
+    /// Updates the index if the tracker reports snapshots that haven't been indexed yet, e.g.
+    /// because the snapshotter created them after this provider.
+    fn update_index_from_tracker(&self) {
+        let Some(highest_tracker) = &self.highest_tracker else { return };
+
+        let highest_snapshots = *highest_tracker.borrow();
+        if *self.indexed_snapshots.read() == highest_snapshots {
+            return
+        }
+
+        match self.update_index() {
+            Ok(()) => *self.indexed_snapshots.write() = highest_snapshots,
+            Err(err) => {
+                warn!(target: "provider::snapshot", %err, "Failed to update the snapshot index")
+            }
+        }
+    }
+
     /// Updates the inner transaction and block index
     pub fn update_index(&self) -> ProviderResult<()> {
         let mut block_index = self.snapshots_block_index.write();
@@ -264,6 +293,7 @@ impl SnapshotProvider {
 
     /// Gets the highest snapshot block if it exists for a snapshot segment.
     pub fn get_highest_snapshot_block(&self, segment: SnapshotSegment) -> Option<BlockNumber> {
+        self.update_index_from_tracker();
         self.snapshots_block_index
             .read()
             .get(&segment)
@@ -272,6 +302,7 @@ impl SnapshotProvider {
 
     /// Gets the highest snapshotted transaction.
     pub fn get_highest_snapshot_tx(&self, segment: SnapshotSegment) -> Option<TxNumber> {
+        self.update_index_from_tracker();
         self.snapshots_tx_index
             .read()
             .get(&segment)
@@ -285,6 +316,7 @@ impl SnapshotProvider {
         segment: SnapshotSegment,
         func: impl Fn(SnapshotJarProvider<'_>) -> ProviderResult<Option<T>>,
     ) -> ProviderResult<Option<T>> {
+        self.update_index_from_tracker();
         let snapshots = self.snapshots_block_index.read();
         if let Some(segment_snapshots) = snapshots.get(&segment) {
             // It's more probable that the request comes from a newer block height, so we iterate
@@ -327,8 +359,16 @@ impl SnapshotProvider {
         P: FnMut(&T) -> bool,
     {
         let get_provider = |start: u64| match segment {
-            SnapshotSegment::Headers => self.get_segment_provider_from_block(segment, start, None),
-            SnapshotSegment::Transactions | SnapshotSegment::Receipts => {
+            SnapshotSegment::Headers |
+            SnapshotSegment::AccountChangesets |
+            SnapshotSegment::StorageChangesets |
+            SnapshotSegment::AccountHistory |
+            SnapshotSegment::StorageHistory => {
+                self.get_segment_provider_from_block(segment, start, None)
+            }
+            SnapshotSegment::Transactions |
+            SnapshotSegment::Receipts |
+            SnapshotSegment::TransactionSenders => {
                 self.get_segment_provider_from_transaction(segment, start, None)
             }
         };
@@ -359,6 +399,189 @@ impl SnapshotProvider {
 
         Ok(result)
     }
+
+    /// Returns the changeset entry of the account in the given block.
+    pub fn account_changeset(
+        &self,
+        block_number: BlockNumber,
+        address: Address,
+    ) -> ProviderResult<Option<AccountBeforeTx>> {
+        self.get_segment_provider_from_block(
+            SnapshotSegment::AccountChangesets,
+            block_number,
+            None,
+        )?
+        .account_changeset(block_number, address)
+    }
+
+    /// Returns the changeset entry of the storage slot in the given block.
+    pub fn storage_changeset(
+        &self,
+        block_number: BlockNumber,
+        address: Address,
+        storage_key: B256,
+    ) -> ProviderResult<Option<StorageEntry>> {
+        self.get_segment_provider_from_block(
+            SnapshotSegment::StorageChangesets,
+            block_number,
+            None,
+        )?
+        .storage_changeset(block_number, address, storage_key)
+    }
+
+    /// Returns the account changeset entries of the blocks in the range that are covered by the
+    /// account changesets snapshots.
+    pub fn account_changesets(
+        &self,
+        range: Range<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, AccountBeforeTx)>> {
+        self.changesets(SnapshotSegment::AccountChangesets, range, |jar_provider, range| {
+            jar_provider.account_changesets(range)
+        })
+    }
+
+    /// Returns the storage changeset entries of the blocks in the range that are covered by the
+    /// storage changesets snapshots.
+    pub fn storage_changesets(
+        &self,
+        range: Range<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>> {
+        self.changesets(SnapshotSegment::StorageChangesets, range, |jar_provider, range| {
+            jar_provider.storage_changesets(range)
+        })
+    }
+
+    /// Returns the first block at or after `block_number` in which the account was changed, if
+    /// it is covered by the account history snapshots.
+    pub fn account_history_lookup(
+        &self,
+        address: Address,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Option<BlockNumber>> {
+        self.history_lookup(SnapshotSegment::AccountHistory, block_number, |jar_provider| {
+            jar_provider.account_history(address)
+        })
+    }
+
+    /// Returns the first block at or after `block_number` in which the storage slot was changed,
+    /// if it is covered by the storage history snapshots.
+    pub fn storage_history_lookup(
+        &self,
+        address: Address,
+        storage_key: B256,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Option<BlockNumber>> {
+        self.history_lookup(SnapshotSegment::StorageHistory, block_number, |jar_provider| {
+            jar_provider.storage_history(address, storage_key)
+        })
+    }
+
+    /// Returns `true` if the account was changed before `block_number` in the account history
+    /// snapshots.
+    pub fn account_written_before(
+        &self,
+        address: Address,
+        block_number: BlockNumber,
+    ) -> ProviderResult<bool> {
+        self.history_written_before(SnapshotSegment::AccountHistory, block_number, |jar_provider| {
+            jar_provider.account_history(address)
+        })
+    }
+
+    /// Returns `true` if the storage slot was changed before `block_number` in the storage
+    /// history snapshots.
+    pub fn storage_written_before(
+        &self,
+        address: Address,
+        storage_key: B256,
+        block_number: BlockNumber,
+    ) -> ProviderResult<bool> {
+        self.history_written_before(SnapshotSegment::StorageHistory, block_number, |jar_provider| {
+            jar_provider.storage_history(address, storage_key)
+        })
+    }
+
+    /// Walks the snapshots of a history segment from the one containing `block_number` downwards,
+    /// and returns `true` if a list returned by `history` has a block before `block_number`.
+    fn history_written_before(
+        &self,
+        segment: SnapshotSegment,
+        block_number: BlockNumber,
+        history: impl Fn(&SnapshotJarProvider<'_>) -> ProviderResult<Option<BlockNumberList>>,
+    ) -> ProviderResult<bool> {
+        let Some(highest_block) = self.get_highest_snapshot_block(segment) else { return Ok(false) };
+
+        let mut block = block_number.min(highest_block);
+        loop {
+            let jar_provider = self.get_segment_provider_from_block(segment, block, None)?;
+
+            // Lists are sorted, so the first block is the earliest change of the snapshot.
+            if let Some(blocks) = history(&jar_provider)? {
+                if blocks.iter(0).next().map_or(false, |first| (first as u64) < block_number) {
+                    return Ok(true)
+                }
+            }
+
+            match jar_provider.user_header().block_start().checked_sub(1) {
+                Some(previous) => block = previous,
+                None => return Ok(false),
+            }
+        }
+    }
+
+    /// Walks the snapshots of a changesets segment over the part of the range they cover, and
+    /// collects the entries returned by `changesets` for each of them.
+    fn changesets<T>(
+        &self,
+        segment: SnapshotSegment,
+        range: Range<BlockNumber>,
+        changesets: impl Fn(&SnapshotJarProvider<'_>, Range<BlockNumber>) -> ProviderResult<Vec<T>>,
+    ) -> ProviderResult<Vec<T>> {
+        let Some(highest_block) = self.get_highest_snapshot_block(segment) else {
+            return Ok(Vec::new())
+        };
+
+        let end = range.end.min(highest_block + 1);
+        let mut entries = Vec::new();
+        let mut block = range.start;
+        while block < end {
+            let jar_provider = self.get_segment_provider_from_block(segment, block, None)?;
+            entries.extend(changesets(&jar_provider, block..end)?);
+            block = jar_provider.user_header().block_end() + 1;
+        }
+
+        Ok(entries)
+    }
+
+    /// Walks the snapshots of a history segment from the one containing `block_number` upwards,
+    /// and returns the first block at or after `block_number` of the list returned by
+    /// `history`.
+    fn history_lookup(
+        &self,
+        segment: SnapshotSegment,
+        block_number: BlockNumber,
+        history: impl Fn(&SnapshotJarProvider<'_>) -> ProviderResult<Option<BlockNumberList>>,
+    ) -> ProviderResult<Option<BlockNumber>> {
+        let Some(highest_block) = self.get_highest_snapshot_block(segment) else { return Ok(None) };
+
+        let mut block = block_number;
+        while block <= highest_block {
+            let jar_provider = self.get_segment_provider_from_block(segment, block, None)?;
+
+            if let Some(blocks) = history(&jar_provider)? {
+                let blocks = blocks.0.enable_rank();
+                // Get the rank of the first entry at or after our block.
+                let rank = blocks.rank(block_number as usize);
+                if rank < blocks.len() {
+                    return Ok(Some(blocks.select(rank) as u64))
+                }
+            }
+
+            block = jar_provider.user_header().block_end() + 1;
+        }
+
+        Ok(None)
+    }
 }
 
 impl HeaderProvider for SnapshotProvider {
@@ -566,9 +789,12 @@ impl TransactionsProvider for SnapshotProvider {
         &self,
         range: impl RangeBounds<TxNumber>,
     ) -> ProviderResult<Vec<Address>> {
-        let txes = self.transactions_by_tx_range(range)?;
-        TransactionSignedNoHash::recover_signers(&txes, txes.len())
-            .ok_or(ProviderError::SenderRecoveryError)
+        self.fetch_range(
+            SnapshotSegment::TransactionSenders,
+            to_range(range),
+            |cursor, number| cursor.get_one::<TransactionSenderMask<Address>>(number.into()),
+            |_| true,
+        )
     }
 
     fn transactions_by_tx_range(
@@ -586,7 +812,8 @@ impl TransactionsProvider for SnapshotProvider {
     }
 
     fn transaction_sender(&self, id: TxNumber) -> ProviderResult<Option<Address>> {
-        Ok(self.transaction_by_id_no_hash(id)?.and_then(|tx| tx.recover_signer()))
+        self.get_segment_provider_from_transaction(SnapshotSegment::TransactionSenders, id, None)?
+            .sender(id)
     }
 }
 
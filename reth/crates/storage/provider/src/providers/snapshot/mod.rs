@@ -4,10 +4,22 @@ pub use manager::SnapshotProvider;
 mod jar;
 pub use jar::SnapshotJarProvider;
 
+use crate::to_range;
+use reth_db::{
+    cursor::DbCursorRO,
+    models::{AccountBeforeTx, BlockNumberAddress},
+    tables,
+    transaction::DbTx,
+};
 use reth_interfaces::provider::ProviderResult;
 use reth_nippy_jar::NippyJar;
-use reth_primitives::{snapshot::SegmentHeader, SnapshotSegment};
-use std::{ops::Deref, sync::Arc};
+use reth_primitives::{
+    snapshot::SegmentHeader, Address, BlockNumber, SnapshotSegment, StorageEntry,
+};
+use std::{
+    ops::{Deref, Range, RangeBounds},
+    sync::Arc,
+};
 
 /// Alias type for each specific `NippyJar`.
 type LoadedJarRef<'a> = dashmap::mapref::one::Ref<'a, (u64, SnapshotSegment), LoadedJar>;
@@ -38,6 +50,66 @@ impl Deref for LoadedJar {
     }
 }
 
+/// Returns the account changesets of the blocks in the range, in block order. Blocks covered by
+/// the account changesets snapshots are read from them, and the rest from the database.
+pub(crate) fn account_changesets<TX: DbTx>(
+    tx: &TX,
+    snapshot_provider: Option<&SnapshotProvider>,
+    range: impl RangeBounds<BlockNumber>,
+) -> ProviderResult<Vec<(BlockNumber, AccountBeforeTx)>> {
+    let range = to_range(range);
+    let (mut changesets, range) = match snapshot_provider {
+        Some(snapshot_provider) => (
+            snapshot_provider.account_changesets(range.clone())?,
+            database_range(snapshot_provider, SnapshotSegment::AccountChangesets, range),
+        ),
+        None => (Vec::new(), range),
+    };
+
+    for entry in tx.cursor_read::<tables::AccountChangeSet>()?.walk_range(range)? {
+        changesets.push(entry?);
+    }
+
+    Ok(changesets)
+}
+
+/// Returns the storage changesets of the blocks in the range, in block order. Blocks covered by
+/// the storage changesets snapshots are read from them, and the rest from the database.
+pub(crate) fn storage_changesets<TX: DbTx>(
+    tx: &TX,
+    snapshot_provider: Option<&SnapshotProvider>,
+    range: impl RangeBounds<BlockNumber>,
+) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>> {
+    let range = to_range(range);
+    let (mut changesets, range) = match snapshot_provider {
+        Some(snapshot_provider) => (
+            snapshot_provider.storage_changesets(range.clone())?,
+            database_range(snapshot_provider, SnapshotSegment::StorageChangesets, range),
+        ),
+        None => (Vec::new(), range),
+    };
+
+    let range = BlockNumberAddress::from((range.start, Address::ZERO))
+        ..BlockNumberAddress::from((range.end, Address::ZERO));
+    for entry in tx.cursor_read::<tables::StorageChangeSet>()?.walk_range(range)? {
+        changesets.push(entry?);
+    }
+
+    Ok(changesets)
+}
+
+/// Returns the part of the range above the highest snapshotted block of the segment.
+fn database_range(
+    snapshot_provider: &SnapshotProvider,
+    segment: SnapshotSegment,
+    range: Range<BlockNumber>,
+) -> Range<BlockNumber> {
+    match snapshot_provider.get_highest_snapshot_block(segment) {
+        Some(highest) => range.start.max(highest + 1)..range.end,
+        None => range,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -45,13 +117,15 @@ mod tests {
     use rand::{self, seq::SliceRandom};
     use reth_db::{
         cursor::DbCursorRO,
-        snapshot::create_snapshot_T1_T2_T3,
+        snapshot::{account_history_key, create_snapshot_T1_T2_T3},
+        table::Compress,
         transaction::{DbTx, DbTxMut},
-        CanonicalHeaders, HeaderNumbers, HeaderTD, Headers, RawTable,
+        BlockNumberList, CanonicalHeaders, HeaderNumbers, HeaderTD, Headers, RawTable,
     };
     use reth_interfaces::test_utils::generators::{self, random_header_range};
-    use reth_nippy_jar::NippyJar;
-    use reth_primitives::{BlockNumber, B256, U256};
+    use reth_nippy_jar::{ColumnResult, NippyJar};
+    use reth_primitives::{Address, BlockNumber, B256, U256};
+    use std::collections::BTreeMap;
 
     #[test]
     fn test_snap() {
@@ -157,4 +231,65 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_account_history_snap() {
+        let snap_path = tempfile::tempdir().unwrap();
+        let first = Address::random();
+        let second = Address::random();
+
+        // Blocks in which the accounts were changed, split over two snapshots.
+        let snapshots = [
+            (0..=99, BTreeMap::from([(first, vec![5, 40]), (second, vec![90])])),
+            (100..=199, BTreeMap::from([(first, vec![150])])),
+        ];
+
+        for (range, history) in snapshots {
+            let segment_header =
+                SegmentHeader::new(range.clone(), range.clone(), SnapshotSegment::AccountHistory);
+            let snap_file =
+                snap_path.path().join(SnapshotSegment::AccountHistory.filename(&range, &range));
+
+            let mut nippy_jar = NippyJar::new(2, snap_file.as_path(), segment_header)
+                .with_cuckoo_filter(history.len())
+                .with_fmph();
+            nippy_jar
+                .prepare_index(
+                    history.keys().map(|address| Ok(account_history_key(*address))),
+                    history.len(),
+                )
+                .unwrap();
+
+            let lists: Vec<ColumnResult<Vec<u8>>> = history
+                .values()
+                .map(|blocks| {
+                    Ok(BlockNumberList::new_pre_sorted(
+                        blocks.iter().map(|block| *block as usize).collect::<Vec<_>>(),
+                    )
+                    .compress())
+                })
+                .collect();
+            let addresses = history.keys().map(|address| Ok(address.compress())).collect();
+            nippy_jar.freeze(vec![lists, addresses], history.len() as u64).unwrap();
+        }
+
+        let manager = SnapshotProvider::new(snap_path.path()).unwrap();
+
+        assert_eq!(manager.account_history_lookup(first, 0).unwrap(), Some(5));
+        assert_eq!(manager.account_history_lookup(first, 40).unwrap(), Some(40));
+        // The next change is in the second snapshot.
+        assert_eq!(manager.account_history_lookup(first, 41).unwrap(), Some(150));
+        assert_eq!(manager.account_history_lookup(first, 151).unwrap(), None);
+        assert_eq!(manager.account_history_lookup(second, 91).unwrap(), None);
+        assert_eq!(manager.account_history_lookup(Address::random(), 0).unwrap(), None);
+
+        // Only changes strictly before the block count, also across snapshots.
+        assert!(!manager.account_written_before(first, 5).unwrap());
+        assert!(manager.account_written_before(first, 6).unwrap());
+        assert!(manager.account_written_before(first, 150).unwrap());
+        assert!(manager.account_written_before(first, 1000).unwrap());
+        assert!(!manager.account_written_before(second, 90).unwrap());
+        assert!(manager.account_written_before(second, 120).unwrap());
+        assert!(!manager.account_written_before(Address::random(), 120).unwrap());
+    }
 }
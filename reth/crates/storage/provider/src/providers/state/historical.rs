@@ -1,10 +1,11 @@
 use crate::{
-    providers::state::macros::delegate_provider_impls, AccountReader, BlockHashReader,
-    BundleStateWithReceipts, ProviderError, StateProvider, StateRootProvider,
+    providers::{snapshot, state::macros::delegate_provider_impls, SnapshotProvider},
+    AccountReader, BlockHashReader, BundleStateWithReceipts, ProviderError, StateProvider,
+    StateRootProvider,
 };
 use reth_db::{
     cursor::{DbCursorRO, DbDupCursorRO},
-    models::{storage_sharded_key::StorageShardedKey, ShardedKey},
+    models::{storage_sharded_key::StorageShardedKey, AccountBeforeTx, ShardedKey},
     table::Table,
     tables,
     transaction::DbTx,
@@ -13,9 +14,10 @@ use reth_db::{
 use reth_interfaces::provider::ProviderResult;
 use reth_primitives::{
     constants::EPOCH_SLOTS, trie::AccountProof, Account, Address, BlockNumber, Bytecode,
-    StorageKey, StorageValue, B256,
+    SnapshotSegment, StorageEntry, StorageKey, StorageValue, B256,
 };
 use reth_trie::{updates::TrieUpdates, HashedPostState};
+use std::sync::Arc;
 
 /// State provider for a given block number which takes a tx reference.
 ///
@@ -28,6 +30,9 @@ use reth_trie::{updates::TrieUpdates, HashedPostState};
 /// - [tables::StorageHistory]
 /// - [tables::AccountChangeSet]
 /// - [tables::StorageChangeSet]
+///
+/// If a [SnapshotProvider] is set, the history and changesets are read from the snapshots of the
+/// blocks that have been snapshotted.
 #[derive(Debug)]
 pub struct HistoricalStateProviderRef<'b, TX: DbTx> {
     /// Transaction
@@ -36,6 +41,8 @@ pub struct HistoricalStateProviderRef<'b, TX: DbTx> {
     block_number: BlockNumber,
     /// Lowest blocks at which different parts of the state are available.
     lowest_available_blocks: LowestAvailableBlocks,
+    /// Snapshot provider
+    snapshot_provider: Option<Arc<SnapshotProvider>>,
 }
 
 #[derive(Debug, Eq, PartialEq)]
@@ -49,7 +56,12 @@ pub enum HistoryInfo {
 impl<'b, TX: DbTx> HistoricalStateProviderRef<'b, TX> {
     /// Create new StateProvider for historical block number
     pub fn new(tx: &'b TX, block_number: BlockNumber) -> Self {
-        Self { tx, block_number, lowest_available_blocks: Default::default() }
+        Self {
+            tx,
+            block_number,
+            lowest_available_blocks: Default::default(),
+            snapshot_provider: None,
+        }
     }
 
     /// Create new StateProvider for historical block number and lowest block numbers at which
@@ -59,7 +71,13 @@ impl<'b, TX: DbTx> HistoricalStateProviderRef<'b, TX> {
         block_number: BlockNumber,
         lowest_available_blocks: LowestAvailableBlocks,
     ) -> Self {
-        Self { tx, block_number, lowest_available_blocks }
+        Self { tx, block_number, lowest_available_blocks, snapshot_provider: None }
+    }
+
+    /// Reads the history and changesets of snapshotted blocks from the given snapshot provider.
+    pub fn with_snapshot_provider(mut self, snapshot_provider: Arc<SnapshotProvider>) -> Self {
+        self.snapshot_provider = Some(snapshot_provider);
+        self
     }
 
     /// Lookup an account in the AccountHistory table
@@ -68,11 +86,31 @@ impl<'b, TX: DbTx> HistoricalStateProviderRef<'b, TX> {
             return Err(ProviderError::StateAtBlockPruned(self.block_number))
         }
 
+        let written_before = || match &self.snapshot_provider {
+            Some(snapshot_provider) => {
+                snapshot_provider.account_written_before(address, self.block_number)
+            }
+            None => Ok(false),
+        };
+
+        if let Some(snapshot_provider) = &self.snapshot_provider {
+            if let Some(changeset_block_number) =
+                snapshot_provider.account_history_lookup(address, self.block_number)?
+            {
+                return self.snapshot_history_info(
+                    changeset_block_number,
+                    written_before,
+                    self.lowest_available_blocks.account_history_block_number,
+                )
+            }
+        }
+
         // history key to search IntegerList of block number changesets.
         let history_key = ShardedKey::new(address, self.block_number);
         self.history_info::<tables::AccountHistory, _>(
             history_key,
             |key| key.key == address,
+            written_before,
             self.lowest_available_blocks.account_history_block_number,
         )
     }
@@ -87,15 +125,87 @@ impl<'b, TX: DbTx> HistoricalStateProviderRef<'b, TX> {
             return Err(ProviderError::StateAtBlockPruned(self.block_number))
         }
 
+        let written_before = || match &self.snapshot_provider {
+            Some(snapshot_provider) => {
+                snapshot_provider.storage_written_before(address, storage_key, self.block_number)
+            }
+            None => Ok(false),
+        };
+
+        if let Some(snapshot_provider) = &self.snapshot_provider {
+            if let Some(changeset_block_number) =
+                snapshot_provider.storage_history_lookup(address, storage_key, self.block_number)?
+            {
+                return self.snapshot_history_info(
+                    changeset_block_number,
+                    written_before,
+                    self.lowest_available_blocks.storage_history_block_number,
+                )
+            }
+        }
+
         // history key to search IntegerList of block number changesets.
         let history_key = StorageShardedKey::new(address, storage_key, self.block_number);
         self.history_info::<tables::StorageHistory, _>(
             history_key,
             |key| key.address == address && key.sharded_key.key == storage_key,
+            written_before,
             self.lowest_available_blocks.storage_history_block_number,
         )
     }
 
+    /// Returns the snapshot provider if the segment has been snapshotted up to the given block.
+    fn snapshot_provider_at(
+        &self,
+        segment: SnapshotSegment,
+        block_number: BlockNumber,
+    ) -> Option<&SnapshotProvider> {
+        self.snapshot_provider.as_deref().filter(|provider| {
+            provider
+                .get_highest_snapshot_block(segment)
+                .map_or(false, |highest| highest >= block_number)
+        })
+    }
+
+    /// Returns the changeset entry of the account in the given block.
+    fn account_changeset(
+        &self,
+        block_number: BlockNumber,
+        address: Address,
+    ) -> ProviderResult<Option<AccountBeforeTx>> {
+        if let Some(snapshot_provider) =
+            self.snapshot_provider_at(SnapshotSegment::AccountChangesets, block_number)
+        {
+            return snapshot_provider.account_changeset(block_number, address)
+        }
+
+        Ok(self
+            .tx
+            .cursor_dup_read::<tables::AccountChangeSet>()?
+            .seek_by_key_subkey(block_number, address)?
+            .filter(|acc| acc.address == address))
+    }
+
+    /// Returns the changeset entry of the storage slot in the given block.
+    fn storage_changeset(
+        &self,
+        block_number: BlockNumber,
+        address: Address,
+        storage_key: StorageKey,
+    ) -> ProviderResult<Option<StorageEntry>> {
+        if let Some(snapshot_provider) =
+            self.snapshot_provider_at(SnapshotSegment::StorageChangesets, block_number)
+        {
+            return snapshot_provider.storage_changeset(block_number, address, storage_key)
+        }
+
+        Ok(self
+            .tx
+            .cursor_dup_read::<tables::StorageChangeSet>()?
+            .seek_by_key_subkey((block_number, address).into(), storage_key)?
+            .filter(|entry| entry.key == storage_key))
+    }
+
     /// Retrieve revert hashed state for this history provider.
     fn revert_state(&self) -> ProviderResult<HashedPostState> {
         if !self.lowest_available_blocks.is_account_history_available(self.block_number) ||
@@ -118,13 +228,44 @@ impl<'b, TX: DbTx> HistoricalStateProviderRef<'b, TX> {
             );
         }
 
-        Ok(HashedPostState::from_revert_range(self.tx, self.block_number..=tip)?)
+        let range = self.block_number..=tip;
+        let snapshot_provider = self.snapshot_provider.as_deref();
+        Ok(HashedPostState::from_reverts(
+            snapshot::account_changesets(self.tx, snapshot_provider, range.clone())?,
+            snapshot::storage_changesets(self.tx, snapshot_provider, range)?,
+        ))
     }
 
+    /// Returns the [HistoryInfo] of a key whose first change at or after our block was found in
+    /// the history snapshots.
+    ///
+    /// Same as for the database history, the key has not been written to yet unless it has been
+    /// changed before our block, or its earlier history may have been pruned.
+    fn snapshot_history_info(
+        &self,
+        changeset_block_number: BlockNumber,
+        written_before: impl FnOnce() -> ProviderResult<bool>,
+        lowest_available_block_number: Option<BlockNumber>,
+    ) -> ProviderResult<HistoryInfo> {
+        if changeset_block_number == self.block_number ||
+            lowest_available_block_number.is_some() ||
+            written_before()?
+        {
+            Ok(HistoryInfo::InChangeset(changeset_block_number))
+        } else {
+            Ok(HistoryInfo::NotYetWritten)
+        }
+    }
+
+    /// Looks up the history of a key in the database history table.
+    ///
+    /// `written_before` returns whether the key has been changed before our block in the history
+    /// snapshots, whose blocks may have been removed from the database history.
     fn history_info<T, K>(
         &self,
         key: K,
         key_filter: impl Fn(&K) -> bool,
+        written_before: impl FnOnce() -> ProviderResult<bool>,
         lowest_available_block_number: Option<BlockNumber>,
     ) -> ProviderResult<HistoryInfo>
     where
@@ -149,7 +290,8 @@ impl<'b, TX: DbTx> HistoricalStateProviderRef<'b, TX> {
             // table.
             if rank == 0 &&
                 chunk.select(rank) as u64 != self.block_number &&
-                !cursor.prev()?.is_some_and(|(key, _)| key_filter(&key))
+                !cursor.prev()?.is_some_and(|(key, _)| key_filter(&key)) &&
+                !written_before()?
             {
                 if lowest_available_block_number.is_some() {
                     // The key may have been written, but due to pruning we may not have changesets
@@ -171,6 +313,9 @@ impl<'b, TX: DbTx> HistoricalStateProviderRef<'b, TX> {
             // The key may have been written, but due to pruning we may not have changesets and
             // history, so we need to make a plain state lookup.
             Ok(HistoryInfo::MaybeInPlainState)
+        } else if written_before()? {
+            // The key has only been written to in snapshotted blocks, all before our block.
+            Ok(HistoryInfo::InPlainState)
         } else {
             // The key has not been written to at all.
             Ok(HistoryInfo::NotYetWritten)
@@ -184,10 +329,7 @@ impl<'b, TX: DbTx> AccountReader for HistoricalStateProviderRef<'b, TX> {
         match self.account_history_lookup(address)? {
             HistoryInfo::NotYetWritten => Ok(None),
             HistoryInfo::InChangeset(changeset_block_number) => Ok(self
-                .tx
-                .cursor_dup_read::<tables::AccountChangeSet>()?
-                .seek_by_key_subkey(changeset_block_number, address)?
-                .filter(|acc| acc.address == address)
+                .account_changeset(changeset_block_number, address)?
                 .ok_or(ProviderError::AccountChangesetNotFound {
                     block_number: changeset_block_number,
                     address,
@@ -253,10 +395,7 @@ impl<'b, TX: DbTx> StateProvider for HistoricalStateProviderRef<'b, TX> {
         match self.storage_history_lookup(address, storage_key)? {
             HistoryInfo::NotYetWritten => Ok(None),
             HistoryInfo::InChangeset(changeset_block_number) => Ok(Some(
-                self.tx
-                    .cursor_dup_read::<tables::StorageChangeSet>()?
-                    .seek_by_key_subkey((changeset_block_number, address).into(), storage_key)?
-                    .filter(|entry| entry.key == storage_key)
+                self.storage_changeset(changeset_block_number, address, storage_key)?
                     .ok_or_else(|| ProviderError::StorageChangesetNotFound {
                         block_number: changeset_block_number,
                         address,
@@ -280,8 +419,11 @@ impl<'b, TX: DbTx> StateProvider for HistoricalStateProviderRef<'b, TX> {
     }
 
     /// Get account and storage proofs.
-    fn proof(&self, _address: Address, _keys: &[B256]) -> ProviderResult<AccountProof> {
-        Err(ProviderError::StateRootNotAvailableForHistoricalBlock)
+    fn proof(&self, address: Address, keys: &[B256]) -> ProviderResult<AccountProof> {
+        let revert_state = self.revert_state()?;
+        revert_state
+            .account_proof(self.tx, address, keys)
+            .map_err(|err| ProviderError::Database(err.into()))
     }
 }
 
@@ -295,12 +437,25 @@ pub struct HistoricalStateProvider<TX: DbTx> {
     block_number: BlockNumber,
     /// Lowest blocks at which different parts of the state are available.
     lowest_available_blocks: LowestAvailableBlocks,
+    /// Snapshot provider
+    snapshot_provider: Option<Arc<SnapshotProvider>>,
 }
 
 impl<TX: DbTx> HistoricalStateProvider<TX> {
     /// Create new StateProvider for historical block number
     pub fn new(tx: TX, block_number: BlockNumber) -> Self {
-        Self { tx, block_number, lowest_available_blocks: Default::default() }
+        Self {
+            tx,
+            block_number,
+            lowest_available_blocks: Default::default(),
+            snapshot_provider: None,
+        }
+    }
+
+    /// Reads the history and changesets of snapshotted blocks from the given snapshot provider.
+    pub fn with_snapshot_provider(mut self, snapshot_provider: Arc<SnapshotProvider>) -> Self {
+        self.snapshot_provider = Some(snapshot_provider);
+        self
     }
 
     /// Set the lowest block number at which the account history is available.
@@ -324,11 +479,12 @@ impl<TX: DbTx> HistoricalStateProvider<TX> {
     /// Returns a new provider that takes the `TX` as reference
     #[inline(always)]
     fn as_ref(&self) -> HistoricalStateProviderRef<'_, TX> {
-        HistoricalStateProviderRef::new_with_lowest_available_blocks(
-            &self.tx,
-            self.block_number,
-            self.lowest_available_blocks,
-        )
+        HistoricalStateProviderRef {
+            tx: &self.tx,
+            block_number: self.block_number,
+            lowest_available_blocks: self.lowest_available_blocks,
+            snapshot_provider: self.snapshot_provider.clone(),
+        }
     }
 }
 
@@ -1,9 +1,12 @@
 use crate::{
     bundle_state::BundleStateWithReceipts,
+    test_utils::NoopProvider,
     traits::{BlockSource, ReceiptProvider},
-    AccountReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
-    BundleStateDataProvider, ChainSpecProvider, ChangeSetReader, EvmEnvProvider, HeaderProvider,
-    ReceiptProviderIdExt, StateProvider, StateProviderBox, StateProviderFactory, StateRootProvider,
+    AccountReader, AddressTransactionsReader, BlockHashReader, BlockIdReader, BlockNumReader,
+    BlockReader, BlockReaderIdExt, BundleStateDataProvider, ChainSpecProvider, ChangeSetReader,
+    EvmEnvProvider, HashedAccountRange, HashedStateRangeReader, HashedStorageRanges,
+    HeaderProvider, LogIndexReader, ReceiptProviderIdExt, StateProvider, StateProviderBox,
+    StateProviderFactory, StateRangeProviderBox, StateRangeReader, StateRootProvider,
     TransactionVariant, TransactionsProvider, WithdrawalsProvider,
 };
 use parking_lot::Mutex;
@@ -11,16 +14,17 @@ use reth_db::models::{AccountBeforeTx, StoredBlockBodyIndices};
 use reth_interfaces::provider::{ProviderError, ProviderResult};
 use reth_node_api::ConfigureEvmEnv;
 use reth_primitives::{
-    keccak256, trie::AccountProof, Account, Address, Block, BlockHash, BlockHashOrNumber, BlockId,
-    BlockNumber, BlockWithSenders, Bytecode, Bytes, ChainInfo, ChainSpec, Header, Receipt,
-    SealedBlock, SealedBlockWithSenders, SealedHeader, StorageKey, StorageValue, TransactionMeta,
-    TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, Withdrawals, B256,
-    U256,
+    keccak256,
+    trie::{AccountProof, Nibbles},
+    Account, Address, Block, BlockHash, BlockHashOrNumber, BlockId, BlockNumber, BlockWithSenders,
+    Bytecode, Bytes, ChainInfo, ChainSpec, Header, Receipt, SealedBlock, SealedBlockWithSenders,
+    SealedHeader, StorageKey, StorageValue, TransactionMeta, TransactionSigned,
+    TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, Withdrawals, B256, U256,
 };
 use reth_trie::updates::TrieUpdates;
 use revm::primitives::{BlockEnv, CfgEnvWithHandlerCfg};
 use std::{
-    collections::{BTreeMap, HashMap},
+    collections::{BTreeMap, BTreeSet, HashMap},
     ops::{RangeBounds, RangeInclusive},
     sync::Arc,
 };
@@ -710,3 +714,118 @@ impl ChangeSetReader for MockEthProvider {
         Ok(Vec::default())
     }
 }
+
+impl AddressTransactionsReader for MockEthProvider {
+    fn address_transactions_index_tip(&self) -> ProviderResult<Option<BlockNumber>> {
+        Ok(None)
+    }
+
+    fn address_blocks_before(
+        &self,
+        _address: Address,
+        _block: BlockNumber,
+        _limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        Ok(Vec::default())
+    }
+
+    fn address_blocks_after(
+        &self,
+        _address: Address,
+        _block: BlockNumber,
+        _limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        Ok(Vec::default())
+    }
+}
+
+impl LogIndexReader for MockEthProvider {
+    fn log_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        Ok(None)
+    }
+
+    fn log_address_blocks(
+        &self,
+        _address: Address,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        Ok(Vec::default())
+    }
+
+    fn log_topic_blocks(
+        &self,
+        _topic: B256,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        Ok(Vec::default())
+    }
+}
+
+impl StateRangeReader for MockEthProvider {
+    fn lowest_available_state_block(&self) -> ProviderResult<BlockNumber> {
+        Ok(0)
+    }
+
+    fn state_range_at(&self, _block: BlockNumber) -> ProviderResult<StateRangeProviderBox> {
+        Ok(Box::new(NoopProvider::default()))
+    }
+
+    fn changed_addresses_with_range(
+        &self,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<Address>> {
+        Ok(BTreeSet::default())
+    }
+}
+
+impl HashedStateRangeReader for MockEthProvider {
+    fn hashed_state_root(&self) -> ProviderResult<Option<B256>> {
+        Ok(None)
+    }
+
+    fn hashed_account_range(
+        &self,
+        _state_root: B256,
+        _start: B256,
+        _limit: B256,
+        _max_bytes: usize,
+    ) -> ProviderResult<Option<HashedAccountRange>> {
+        Ok(None)
+    }
+
+    fn hashed_storage_ranges(
+        &self,
+        _state_root: B256,
+        _accounts: &[B256],
+        _start: B256,
+        _limit: B256,
+        _max_bytes: usize,
+    ) -> ProviderResult<Option<HashedStorageRanges>> {
+        Ok(None)
+    }
+
+    fn account_trie_nodes(
+        &self,
+        _state_root: B256,
+        _paths: &[Nibbles],
+    ) -> ProviderResult<Option<Vec<Bytes>>> {
+        Ok(None)
+    }
+
+    fn storage_trie_nodes(
+        &self,
+        _state_root: B256,
+        _hashed_address: B256,
+        _paths: &[Nibbles],
+    ) -> ProviderResult<Option<Vec<Bytes>>> {
+        Ok(None)
+    }
+
+    fn bytecodes(&self, hashes: &[B256], _max_bytes: usize) -> ProviderResult<Vec<Bytes>> {
+        let mut codes = Vec::new();
+        for hash in hashes {
+            codes.extend(self.bytecode_by_hash(*hash)?.map(|code| code.original_bytes()));
+        }
+        Ok(codes)
+    }
+}
@@ -1,27 +1,31 @@
 use crate::{
     bundle_state::BundleStateWithReceipts,
     traits::{BlockSource, ReceiptProvider},
-    AccountReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
-    ChainSpecProvider, ChangeSetReader, EvmEnvProvider, HeaderProvider, PruneCheckpointReader,
-    ReceiptProviderIdExt, StageCheckpointReader, StateProvider, StateProviderBox,
-    StateProviderFactory, StateRootProvider, TransactionVariant, TransactionsProvider,
-    WithdrawalsProvider,
+    AccountReader, AddressTransactionsReader, BlockHashReader, BlockIdReader, BlockNumReader,
+    BlockReader, BlockReaderIdExt, ChainSpecProvider, ChangeSetReader, EvmEnvProvider,
+    HashedAccountRange, HashedStateRangeReader, HashedStorageRanges, HeaderProvider,
+    LogIndexReader, PruneCheckpointReader, ReceiptProviderIdExt, StageCheckpointReader,
+    StateProvider, StateProviderBox, StateProviderFactory, StateRangeProvider,
+    StateRangeProviderBox, StateRangeReader, StateRootProvider, TransactionVariant,
+    TransactionsProvider, WithdrawalsProvider,
 };
 use reth_db::models::{AccountBeforeTx, StoredBlockBodyIndices};
 use reth_interfaces::provider::ProviderResult;
 use reth_node_api::ConfigureEvmEnv;
 use reth_primitives::{
+    constants::EMPTY_ROOT_HASH,
     stage::{StageCheckpoint, StageId},
-    trie::AccountProof,
-    Account, Address, Block, BlockHash, BlockHashOrNumber, BlockId, BlockNumber, Bytecode,
+    trie::{AccountProof, Nibbles},
+    Account, Address, Block, BlockHash, BlockHashOrNumber, BlockId, BlockNumber, Bytecode, Bytes,
     ChainInfo, ChainSpec, Header, PruneCheckpoint, PruneSegment, Receipt, SealedBlock,
-    SealedBlockWithSenders, SealedHeader, StorageKey, StorageValue, TransactionMeta,
+    SealedBlockWithSenders, SealedHeader, StorageEntry, StorageKey, StorageValue, TransactionMeta,
     TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, Withdrawals, B256,
     MAINNET, U256,
 };
 use reth_trie::updates::TrieUpdates;
 use revm::primitives::{BlockEnv, CfgEnvWithHandlerCfg};
 use std::{
+    collections::BTreeSet,
     ops::{RangeBounds, RangeInclusive},
     sync::Arc,
 };
@@ -284,6 +288,157 @@ impl ChangeSetReader for NoopProvider {
     }
 }
 
+impl AddressTransactionsReader for NoopProvider {
+    fn address_transactions_index_tip(&self) -> ProviderResult<Option<BlockNumber>> {
+        Ok(None)
+    }
+
+    fn address_blocks_before(
+        &self,
+        _address: Address,
+        _block: BlockNumber,
+        _limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        Ok(Vec::default())
+    }
+
+    fn address_blocks_after(
+        &self,
+        _address: Address,
+        _block: BlockNumber,
+        _limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        Ok(Vec::default())
+    }
+}
+
+impl LogIndexReader for NoopProvider {
+    fn log_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        Ok(None)
+    }
+
+    fn log_address_blocks(
+        &self,
+        _address: Address,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        Ok(Vec::default())
+    }
+
+    fn log_topic_blocks(
+        &self,
+        _topic: B256,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        Ok(Vec::default())
+    }
+}
+
+impl StateRangeReader for NoopProvider {
+    fn lowest_available_state_block(&self) -> ProviderResult<BlockNumber> {
+        Ok(0)
+    }
+
+    fn state_range_at(&self, _block: BlockNumber) -> ProviderResult<StateRangeProviderBox> {
+        Ok(Box::new(*self))
+    }
+
+    fn changed_addresses_with_range(
+        &self,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<Address>> {
+        Ok(BTreeSet::default())
+    }
+}
+
+impl StateRangeProvider for NoopProvider {
+    fn account_range(
+        &self,
+        _start: Address,
+        _limit: usize,
+    ) -> ProviderResult<Vec<(Address, Account)>> {
+        Ok(Vec::default())
+    }
+
+    fn storage_range(
+        &self,
+        _address: Address,
+        _start: B256,
+        _limit: usize,
+    ) -> ProviderResult<Vec<StorageEntry>> {
+        Ok(Vec::default())
+    }
+
+    fn hashed_account_range(
+        &self,
+        _start: B256,
+        _limit: usize,
+    ) -> ProviderResult<Vec<(B256, Account)>> {
+        Ok(Vec::default())
+    }
+
+    fn hashed_storage_range(
+        &self,
+        _hashed_address: B256,
+        _start: B256,
+        _limit: usize,
+    ) -> ProviderResult<Vec<StorageEntry>> {
+        Ok(Vec::default())
+    }
+
+    fn storage_roots(&self, hashed_addresses: &[B256]) -> ProviderResult<Vec<B256>> {
+        Ok(vec![EMPTY_ROOT_HASH; hashed_addresses.len()])
+    }
+}
+
+impl HashedStateRangeReader for NoopProvider {
+    fn hashed_state_root(&self) -> ProviderResult<Option<B256>> {
+        Ok(None)
+    }
+
+    fn hashed_account_range(
+        &self,
+        _state_root: B256,
+        _start: B256,
+        _limit: B256,
+        _max_bytes: usize,
+    ) -> ProviderResult<Option<HashedAccountRange>> {
+        Ok(None)
+    }
+
+    fn hashed_storage_ranges(
+        &self,
+        _state_root: B256,
+        _accounts: &[B256],
+        _start: B256,
+        _limit: B256,
+        _max_bytes: usize,
+    ) -> ProviderResult<Option<HashedStorageRanges>> {
+        Ok(None)
+    }
+
+    fn account_trie_nodes(
+        &self,
+        _state_root: B256,
+        _paths: &[Nibbles],
+    ) -> ProviderResult<Option<Vec<Bytes>>> {
+        Ok(None)
+    }
+
+    fn storage_trie_nodes(
+        &self,
+        _state_root: B256,
+        _hashed_address: B256,
+        _paths: &[Nibbles],
+    ) -> ProviderResult<Option<Vec<Bytes>>> {
+        Ok(None)
+    }
+
+    fn bytecodes(&self, _hashes: &[B256], _max_bytes: usize) -> ProviderResult<Vec<Bytes>> {
+        Ok(Vec::default())
+    }
+}
+
 impl StateRootProvider for NoopProvider {
     fn state_root(&self, _state: &BundleStateWithReceipts) -> ProviderResult<B256> {
         Ok(B256::default())
@@ -89,6 +89,10 @@ pub struct BlockExecutorStats {
     pub merge_transitions_duration: Duration,
     /// Time needed to calculate receipt roots.
     pub receipt_root_duration: Duration,
+    /// Number of account and storage reads served by the state prefetcher.
+    pub prefetch_hits: u64,
+    /// Number of account and storage reads that missed the state prefetcher.
+    pub prefetch_misses: u64,
 }
 
 impl BlockExecutorStats {
@@ -101,7 +105,15 @@ impl BlockExecutorStats {
             apply_post_state = ?self.apply_post_execution_state_changes_duration,
             merge_transitions = ?self.merge_transitions_duration,
             receipt_root = ?self.receipt_root_duration,
+            prefetch_hit_rate = ?self.prefetch_hit_rate(),
             "Execution time"
         );
     }
+
+    /// Returns the share of the account and storage reads served by the state prefetcher, or
+    /// `None` if no reads went through the prefetcher.
+    pub fn prefetch_hit_rate(&self) -> Option<f64> {
+        let reads = self.prefetch_hits + self.prefetch_misses;
+        (reads > 0).then(|| self.prefetch_hits as f64 / reads as f64)
+    }
 }
@@ -0,0 +1,79 @@
+use auto_impl::auto_impl;
+use reth_interfaces::provider::ProviderResult;
+use reth_primitives::{trie::Nibbles, Account, Bytes, StorageEntry, B256};
+
+/// A range of hashed accounts together with the trie nodes proving its boundaries.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct HashedAccountRange {
+    /// The hashed addresses of the accounts, the accounts and their storage roots, ordered by
+    /// hashed address.
+    pub accounts: Vec<(B256, Account, B256)>,
+    /// The account trie nodes proving the start of the range and the last account.
+    pub proof: Vec<Bytes>,
+}
+
+/// Consecutive hashed storage slots of multiple accounts, together with the trie nodes proving
+/// the boundaries of the last one if it is incomplete.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct HashedStorageRanges {
+    /// The slots of each account, keyed by the hashed slot and ordered by it.
+    pub slots: Vec<Vec<StorageEntry>>,
+    /// The storage trie nodes proving the start of the last range and its last slot.
+    pub proof: Vec<Bytes>,
+}
+
+/// Serves ranges of the hashed state and its trie nodes, as required by the `snap` protocol.
+///
+/// The states of the recent blocks are served, since peers sync towards a pivot block behind the
+/// tip. All methods taking a state root return `None` if it is not the root of one of them.
+#[auto_impl(&, Arc, Box)]
+pub trait HashedStateRangeReader: Send + Sync {
+    /// Returns the state root of the latest hashed state, or `None` if the hashed state and the
+    /// trie are not consistent with the last synced block, e.g. while the pipeline is running.
+    fn hashed_state_root(&self) -> ProviderResult<Option<B256>>;
+
+    /// Returns consecutive accounts starting at the hashed address `start`, up to and including
+    /// the first account at or after `limit`, or until their estimated size exceeds `max_bytes`.
+    fn hashed_account_range(
+        &self,
+        state_root: B256,
+        start: B256,
+        limit: B256,
+        max_bytes: usize,
+    ) -> ProviderResult<Option<HashedAccountRange>>;
+
+    /// Returns the storage slots of the given accounts, until their estimated size exceeds
+    /// `max_bytes`.
+    ///
+    /// The `start` and `limit` hashed slots only apply to the first account, the storage of the
+    /// following accounts is returned in full unless the size limit is reached. A proof is only
+    /// returned for the last account, and only if its range does not cover its entire storage.
+    fn hashed_storage_ranges(
+        &self,
+        state_root: B256,
+        accounts: &[B256],
+        start: B256,
+        limit: B256,
+        max_bytes: usize,
+    ) -> ProviderResult<Option<HashedStorageRanges>>;
+
+    /// Returns the nodes of the account trie at the given paths. Missing nodes are empty.
+    fn account_trie_nodes(
+        &self,
+        state_root: B256,
+        paths: &[Nibbles],
+    ) -> ProviderResult<Option<Vec<Bytes>>>;
+
+    /// Returns the nodes of the storage trie of the given account at the given paths. Missing
+    /// nodes are empty.
+    fn storage_trie_nodes(
+        &self,
+        state_root: B256,
+        hashed_address: B256,
+        paths: &[Nibbles],
+    ) -> ProviderResult<Option<Vec<Bytes>>>;
+
+    /// Returns the bytecodes with the given code hashes in the order of the request, until their
+    /// size exceeds `max_bytes`. Unknown bytecodes are skipped.
+    fn bytecodes(&self, hashes: &[B256], max_bytes: usize) -> ProviderResult<Vec<Bytes>>;
+}
@@ -38,6 +38,86 @@ pub trait HistoryWriter: Send + Sync {
         storage_transitions: BTreeMap<(Address, B256), Vec<u64>>,
     ) -> ProviderResult<()>;
 
+    /// Unwind and clear the address transactions index.
+    ///
+    /// Returns number of addresses unwound.
+    fn unwind_address_transactions_index(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<usize>;
+
+    /// Insert address transactions index to database. Used inside IndexAddressTransactions stage
+    fn insert_address_transactions_index(
+        &self,
+        address_transactions: BTreeMap<Address, Vec<u64>>,
+    ) -> ProviderResult<()>;
+
+    /// Unwind and clear the log address and topic indices.
+    ///
+    /// Returns number of addresses and topics unwound.
+    fn unwind_log_index(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<usize>;
+
+    /// Insert log address and topic indices to database. Used inside IndexLogs stage
+    fn insert_log_index(
+        &self,
+        log_addresses: BTreeMap<Address, Vec<u64>>,
+        log_topics: BTreeMap<B256, Vec<u64>>,
+    ) -> ProviderResult<()>;
+
     /// Read account/storage changesets and update account/storage history indices.
+    ///
+    /// The address transactions and log indices are updated as well if they're enabled.
     fn update_history_indices(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<()>;
 }
+
+/// Address transactions index reader
+#[auto_impl(&, Arc, Box)]
+pub trait AddressTransactionsReader: Send + Sync {
+    /// Returns the highest block covered by the address transactions index.
+    ///
+    /// Returns `None` if the index is not enabled.
+    fn address_transactions_index_tip(&self) -> ProviderResult<Option<BlockNumber>>;
+
+    /// Returns up to `limit` blocks lower than `block` that contain transactions touching the
+    /// given address, in descending order.
+    fn address_blocks_before(
+        &self,
+        address: Address,
+        block: BlockNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>>;
+
+    /// Returns up to `limit` blocks higher than `block` that contain transactions touching the
+    /// given address, in ascending order.
+    fn address_blocks_after(
+        &self,
+        address: Address,
+        block: BlockNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>>;
+}
+
+/// Log index reader
+#[auto_impl(&, Arc, Box)]
+pub trait LogIndexReader: Send + Sync {
+    /// Returns the range of blocks covered by the log index.
+    ///
+    /// Returns `None` if the index is not enabled, or if all of its blocks have been pruned.
+    fn log_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>>;
+
+    /// Returns the blocks within the given range that contain logs emitted by the given address,
+    /// in ascending order.
+    fn log_address_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>>;
+
+    /// Returns the blocks within the given range that contain logs with the given topic at any
+    /// position, in ascending order.
+    fn log_topic_blocks(
+        &self,
+        topic: B256,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>>;
+}
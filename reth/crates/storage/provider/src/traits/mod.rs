@@ -6,6 +6,12 @@ pub use account::{AccountExtReader, AccountReader, ChangeSetReader};
 mod storage;
 pub use storage::StorageReader;
 
+mod state_range;
+pub use state_range::{StateRangeProvider, StateRangeProviderBox, StateRangeReader};
+
+mod hashed_state_range;
+pub use hashed_state_range::{HashedAccountRange, HashedStateRangeReader, HashedStorageRanges};
+
 mod block;
 pub use block::{
     BlockExecutionWriter, BlockReader, BlockReaderIdExt, BlockSource, BlockWriter,
@@ -64,7 +70,7 @@ mod hashing;
 pub use hashing::HashingWriter;
 
 mod history;
-pub use history::HistoryWriter;
+pub use history::{AddressTransactionsReader, HistoryWriter, LogIndexReader};
 
 mod prune_checkpoint;
 pub use prune_checkpoint::{PruneCheckpointReader, PruneCheckpointWriter};
@@ -0,0 +1,74 @@
+use auto_impl::auto_impl;
+use reth_interfaces::provider::ProviderResult;
+use reth_primitives::{Account, Address, BlockNumber, StorageEntry, B256};
+use std::{collections::BTreeSet, ops::RangeInclusive};
+
+/// A [StateRangeProvider] boxed trait object.
+pub type StateRangeProviderBox = Box<dyn StateRangeProvider>;
+
+/// Provides paginated access to the state of past blocks.
+#[auto_impl(&, Arc, Box)]
+pub trait StateRangeReader: Send + Sync {
+    /// Returns the lowest block whose state is available, i.e. whose subsequent changesets have
+    /// not been pruned.
+    fn lowest_available_state_block(&self) -> ProviderResult<BlockNumber>;
+
+    /// Returns a provider of the state after the given block.
+    ///
+    /// The plain and hashed state are reverted to the block using the account and storage
+    /// changesets, which are read once when the provider is created and kept in memory. The cost
+    /// of this grows with the distance between the block and the tip.
+    fn state_range_at(&self, block: BlockNumber) -> ProviderResult<StateRangeProviderBox>;
+
+    /// Returns all addresses whose account or storage changed in the given range of blocks.
+    fn changed_addresses_with_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<Address>>;
+}
+
+/// Provides paginated access to the state after a single block, see
+/// [StateRangeReader::state_range_at].
+#[auto_impl(&, Arc, Box)]
+pub trait StateRangeProvider: Send + Sync {
+    /// Returns up to `limit` existing accounts, ordered by address and starting at `start`.
+    fn account_range(
+        &self,
+        start: Address,
+        limit: usize,
+    ) -> ProviderResult<Vec<(Address, Account)>>;
+
+    /// Returns up to `limit` non-zero storage slots of the account, ordered by slot and starting
+    /// at `start`.
+    fn storage_range(
+        &self,
+        address: Address,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<Vec<StorageEntry>>;
+
+    /// Returns up to `limit` existing accounts, ordered by hashed address and starting at the
+    /// hashed address `start`.
+    ///
+    /// This reverts the hashed state, which must be at the same block as the plain state.
+    fn hashed_account_range(
+        &self,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<Vec<(B256, Account)>>;
+
+    /// Returns up to `limit` non-zero storage slots of the account with the given hashed address,
+    /// ordered by hashed slot and starting at the hashed slot `start`. The keys of the returned
+    /// entries are hashed slots.
+    ///
+    /// This reverts the hashed state, which must be at the same block as the plain state.
+    fn hashed_storage_range(
+        &self,
+        hashed_address: B256,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<Vec<StorageEntry>>;
+
+    /// Returns the storage roots of the accounts with the given hashed addresses.
+    fn storage_roots(&self, hashed_addresses: &[B256]) -> ProviderResult<Vec<B256>>;
+}
@@ -5,12 +5,33 @@ use alloy_rlp::{Decodable, Encodable};
 use parking_lot::{Mutex, RwLock};
 use reth_primitives::{BlobTransactionSidecar, TxHash, B256};
 use schnellru::{ByLength, LruMap};
-use std::{fmt, fs, io, path::PathBuf, sync::Arc};
+use std::{
+    collections::HashMap,
+    fmt,
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::PathBuf,
+    str::FromStr,
+    sync::Arc,
+};
 use tracing::{debug, trace};
 
 /// How many [BlobTransactionSidecar] to cache in memory.
 pub const DEFAULT_MAX_CACHED_BLOBS: u32 = 100;
 
+/// Name of the index file in the blob store directory.
+const INDEX_FILE_NAME: &str = "index";
+
+/// Size of a record of the index file: the kind of record, the transaction hash and the size of
+/// the blob file.
+const INDEX_RECORD_SIZE: usize = 1 + 32 + 8;
+
+/// Kind of an index record that adds a blob file.
+const INDEX_INSERT: u8 = 0;
+
+/// Kind of an index record that removes a blob file.
+const INDEX_DELETE: u8 = 1;
+
 /// A blob store that stores blob data on disk.
 #[derive(Clone, Debug)]
 pub struct DiskFileBlobStore {
@@ -19,17 +40,23 @@ pub struct DiskFileBlobStore {
 
 impl DiskFileBlobStore {
     /// Opens and initializes a new disk file blob store according to the given options.
+    ///
+    /// With [OpenDiskFileBlobStore::ReIndex] the blobs of the previous run are kept, see
+    /// [OpenDiskFileBlobStore::ReIndex] for how the index is checked against the blob files.
     pub fn open(
         blob_dir: impl Into<PathBuf>,
         opts: DiskFileBlobStoreConfig,
     ) -> Result<Self, DiskFileBlobStoreError> {
         let blob_dir = blob_dir.into();
-        let DiskFileBlobStoreConfig { max_cached_entries, .. } = opts;
+        let DiskFileBlobStoreConfig { max_cached_entries, open } = opts;
         let inner = DiskFileBlobStoreInner::new(blob_dir, max_cached_entries);
 
         // initialize the blob store
-        inner.delete_all()?;
+        if open == OpenDiskFileBlobStore::Clear {
+            inner.delete_all()?;
+        }
         inner.create_blob_dir()?;
+        inner.reindex()?;
 
         Ok(Self { inner: Arc::new(inner) })
     }
@@ -103,6 +130,10 @@ impl BlobStore for DiskFileBlobStore {
     fn blobs_len(&self) -> usize {
         self.inner.size_tracker.blobs_len()
     }
+
+    fn all_hashes(&self) -> Result<Vec<B256>, BlobStoreError> {
+        Ok(self.inner.index.lock().entries.keys().copied().collect())
+    }
 }
 
 struct DiskFileBlobStoreInner {
@@ -110,6 +141,7 @@ struct DiskFileBlobStoreInner {
     blob_cache: Mutex<LruMap<TxHash, BlobTransactionSidecar, ByLength>>,
     size_tracker: BlobStoreSize,
     file_lock: RwLock<()>,
+    index: Mutex<BlobIndex>,
 }
 
 impl DiskFileBlobStoreInner {
@@ -120,9 +152,115 @@ impl DiskFileBlobStoreInner {
             blob_cache: Mutex::new(LruMap::new(ByLength::new(max_length))),
             size_tracker: Default::default(),
             file_lock: Default::default(),
+            index: Default::default(),
         }
     }
 
+    /// Returns the path to the index file.
+    fn index_path(&self) -> PathBuf {
+        self.blob_dir.join(INDEX_FILE_NAME)
+    }
+
+    /// Loads the index from disk and checks it against the blob files.
+    ///
+    /// Indexed blobs whose file is missing or doesn't have the indexed size are dropped, and blob
+    /// files that are not indexed are deleted. The index file is then rewritten with the remaining
+    /// entries only.
+    fn reindex(&self) -> Result<(), DiskFileBlobStoreError> {
+        let index_path = self.index_path();
+        let index_err = |err| DiskFileBlobStoreError::Index(index_path.clone(), err);
+        let open_err = |err| DiskFileBlobStoreError::Open(self.blob_dir.clone(), err);
+
+        let indexed = match fs::read(&index_path) {
+            Ok(data) => BlobIndex::replay(&data),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => HashMap::new(),
+            Err(err) => return Err(index_err(err)),
+        };
+
+        let mut entries = HashMap::with_capacity(indexed.len());
+        for file in fs::read_dir(&self.blob_dir).map_err(open_err)? {
+            let file = file.map_err(open_err)?;
+            let Some(tx) = file.file_name().to_str().and_then(|name| TxHash::from_str(name).ok())
+            else {
+                // not a blob file
+                continue
+            };
+            match (indexed.get(&tx), file.metadata()) {
+                (Some(&size), Ok(metadata)) if metadata.len() == size as u64 => {
+                    entries.insert(tx, size);
+                }
+                _ => {
+                    // the blob file was not completely written or its deletion was not completed
+                    debug!(target:"txpool::blob", ?tx, "Deleting blob file that is not indexed");
+                    let path = file.path();
+                    fs::remove_file(&path)
+                        .map_err(|e| DiskFileBlobStoreError::DeleteFile(tx, path, e))?;
+                }
+            }
+        }
+
+        let mut records = Vec::with_capacity(entries.len() * INDEX_RECORD_SIZE);
+        for (tx, size) in &entries {
+            encode_index_record(&mut records, INDEX_INSERT, *tx, *size);
+        }
+        let tmp_path = index_path.with_extension("tmp");
+        fs::write(&tmp_path, records)
+            .and_then(|_| fs::rename(&tmp_path, &index_path))
+            .map_err(index_err)?;
+        let file = OpenOptions::new().append(true).open(&index_path).map_err(index_err)?;
+
+        debug!(
+            target:"txpool::blob",
+            blobs = entries.len(),
+            dropped = indexed.len() - entries.len(),
+            "Indexed blob store"
+        );
+        self.size_tracker.add_size(entries.values().sum());
+        self.size_tracker.update_len(entries.len());
+        *self.index.lock() = BlobIndex { entries, file: Some(file) };
+
+        Ok(())
+    }
+
+    /// Adds the written blob files to the index and updates the size of the store.
+    ///
+    /// This must be called while holding the write lock of the blob files.
+    fn index_insert(
+        &self,
+        blobs: impl IntoIterator<Item = (TxHash, usize)>,
+    ) -> Result<(), DiskFileBlobStoreError> {
+        let mut index = self.index.lock();
+        let mut records = Vec::new();
+        for (tx, size) in blobs {
+            encode_index_record(&mut records, INDEX_INSERT, tx, size);
+            if let Some(replaced) = index.entries.insert(tx, size) {
+                self.size_tracker.sub_size(replaced);
+            }
+            self.size_tracker.add_size(size);
+        }
+        self.size_tracker.update_len(index.entries.len());
+        index.append(&records).map_err(|e| DiskFileBlobStoreError::Index(self.index_path(), e))
+    }
+
+    /// Removes the deleted blob files from the index and updates the size of the store.
+    ///
+    /// This must be called while holding the write lock of the blob files.
+    fn index_delete(
+        &self,
+        txs: impl IntoIterator<Item = TxHash>,
+    ) -> Result<(), DiskFileBlobStoreError> {
+        let mut index = self.index.lock();
+        let mut records = Vec::new();
+        for tx in txs {
+            if let Some(size) = index.entries.remove(&tx) {
+                encode_index_record(&mut records, INDEX_DELETE, tx, size);
+                self.size_tracker.sub_size(size);
+            }
+        }
+        self.size_tracker.update_len(index.entries.len());
+        index.append(&records).map_err(|e| DiskFileBlobStoreError::Index(self.index_path(), e))
+    }
+
     fn create_blob_dir(&self) -> Result<(), DiskFileBlobStoreError> {
         debug!(target:"txpool::blob", blob_dir = ?self.blob_dir, "Creating blob store");
         fs::create_dir_all(&self.blob_dir)
@@ -145,10 +283,8 @@ impl DiskFileBlobStoreInner {
         let mut buf = Vec::with_capacity(data.fields_len());
         data.encode(&mut buf);
         self.blob_cache.lock().insert(tx, data);
-        let size = self.write_one_encoded(tx, &buf)?;
+        self.write_one_encoded(tx, &buf)?;
 
-        self.size_tracker.add_size(size);
-        self.size_tracker.inc_len(1);
         Ok(())
     }
 
@@ -158,7 +294,7 @@ impl DiskFileBlobStoreInner {
             .map(|(tx, data)| {
                 let mut buf = Vec::with_capacity(data.fields_len());
                 data.encode(&mut buf);
-                (self.blob_disk_file(*tx), buf)
+                (*tx, self.blob_disk_file(*tx), buf)
             })
             .collect::<Vec<_>>();
 
@@ -168,32 +304,23 @@ impl DiskFileBlobStoreInner {
                 cache.insert(tx, data);
             }
         }
-        let mut add = 0;
-        let mut num = 0;
-        {
-            let _lock = self.file_lock.write();
-            for (path, data) in raw {
-                if let Err(err) = fs::write(&path, &data) {
-                    debug!( target:"txpool::blob", ?err, ?path, "Failed to write blob file");
-                } else {
-                    add += data.len();
-                    num += 1;
-                }
+        let _lock = self.file_lock.write();
+        let mut written = Vec::with_capacity(raw.len());
+        for (tx, path, data) in raw {
+            if let Err(err) = fs::write(&path, &data) {
+                debug!( target:"txpool::blob", ?err, ?path, "Failed to write blob file");
+            } else {
+                written.push((tx, data.len()));
             }
         }
-        self.size_tracker.add_size(add);
-        self.size_tracker.inc_len(num);
+        self.index_insert(written)?;
 
         Ok(())
     }
 
-    /// Returns true if the blob for the given transaction hash is in the blob cache or on disk.
+    /// Returns true if the blob for the given transaction hash is in the index.
     fn contains(&self, tx: B256) -> Result<bool, BlobStoreError> {
-        if self.blob_cache.lock().get(&tx).is_some() {
-            return Ok(true)
-        }
-        // we only check if the file exists and assume it's valid
-        Ok(self.blob_disk_file(tx).is_file())
+        Ok(self.index.lock().entries.contains_key(&tx))
     }
 
     /// Retrieves the blob for the given transaction hash from the blob cache or disk.
@@ -276,6 +403,7 @@ impl DiskFileBlobStoreInner {
         let _lock = self.file_lock.write();
 
         fs::write(&path, data).map_err(|e| DiskFileBlobStoreError::WriteFile(tx, path, e))?;
+        self.index_insert([(tx, data.len())])?;
         Ok(data.len())
     }
 
@@ -286,7 +414,10 @@ impl DiskFileBlobStoreInner {
         let path = self.blob_disk_file(tx);
 
         let _lock = self.file_lock.write();
-        fs::remove_file(&path).map_err(|e| DiskFileBlobStoreError::WriteFile(tx, path, e))?;
+        let res = fs::remove_file(&path);
+        // a file that failed to be deleted is no longer indexed and removed on the next reindex
+        self.index_delete([tx])?;
+        res.map_err(|e| DiskFileBlobStoreError::DeleteFile(tx, path, e))?;
 
         Ok(())
     }
@@ -298,15 +429,18 @@ impl DiskFileBlobStoreInner {
         txs: impl IntoIterator<Item = TxHash>,
     ) -> Result<(), DiskFileBlobStoreError> {
         let _lock = self.file_lock.write();
+        let mut deleted = Vec::new();
         for tx in txs.into_iter() {
             trace!( target:"txpool::blob", "[{:?}] deleting blob file", tx);
             let path = self.blob_disk_file(tx);
 
             let _ = fs::remove_file(&path).map_err(|e| {
-                let err = DiskFileBlobStoreError::WriteFile(tx, path, e);
+                let err = DiskFileBlobStoreError::DeleteFile(tx, path, e);
                 debug!( target:"txpool::blob", ?err);
             });
+            deleted.push(tx);
         }
+        self.index_delete(deleted)?;
 
         Ok(())
     }
@@ -361,10 +495,64 @@ impl fmt::Debug for DiskFileBlobStoreInner {
         f.debug_struct("DiskFileBlobStoreInner")
             .field("blob_dir", &self.blob_dir)
             .field("cached_blobs", &self.blob_cache.try_lock().map(|lock| lock.len()))
+            .field("indexed_blobs", &self.index.try_lock().map(|lock| lock.entries.len()))
             .finish()
     }
 }
 
+/// Index of the blob files, mapping transaction hashes to the size of their blob file.
+///
+/// The index is persisted as an append-only log of insert and delete records in the blob store
+/// directory, which is compacted when the blob store is opened.
+#[derive(Debug, Default)]
+struct BlobIndex {
+    /// Size of the blob file of each stored transaction.
+    entries: HashMap<TxHash, usize>,
+    /// The index file records are appended to, opened when the blob store is opened.
+    file: Option<File>,
+}
+
+impl BlobIndex {
+    /// Replays the records of an index file.
+    ///
+    /// A truncated last record, for example of a crash while it was appended, is ignored.
+    fn replay(data: &[u8]) -> HashMap<TxHash, usize> {
+        let mut entries = HashMap::new();
+        for record in data.chunks_exact(INDEX_RECORD_SIZE) {
+            let tx = TxHash::from_slice(&record[1..33]);
+            match record[0] {
+                INDEX_INSERT => {
+                    let size = u64::from_be_bytes(record[33..].try_into().expect("8 bytes"));
+                    entries.insert(tx, size as usize);
+                }
+                INDEX_DELETE => {
+                    entries.remove(&tx);
+                }
+                kind => {
+                    debug!(target:"txpool::blob", kind, "Unknown blob store index record");
+                    break
+                }
+            }
+        }
+        entries
+    }
+
+    /// Appends the encoded records to the index file.
+    fn append(&mut self, records: &[u8]) -> io::Result<()> {
+        match &mut self.file {
+            Some(file) if !records.is_empty() => file.write_all(records),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Encodes a record of the index file.
+fn encode_index_record(buf: &mut Vec<u8>, kind: u8, tx: TxHash, size: usize) {
+    buf.push(kind);
+    buf.extend_from_slice(tx.as_slice());
+    buf.extend_from_slice(&(size as u64).to_be_bytes());
+}
+
 /// Errors that can occur when interacting with a disk file blob store.
 #[derive(Debug, thiserror::Error)]
 pub enum DiskFileBlobStoreError {
@@ -384,6 +572,10 @@ pub enum DiskFileBlobStoreError {
     #[error("[{0}] failed to delete blob file at {1}: {2}")]
     /// Indicates a failure while deleting a blob file.
     DeleteFile(TxHash, PathBuf, io::Error),
+    /// Failure while reading or writing the index file.
+    #[error("failed to access blob store index at {0}: {1}")]
+    /// Indicates a failure while reading or writing the index file.
+    Index(PathBuf, io::Error),
 }
 
 impl From<DiskFileBlobStoreError> for BlobStoreError {
@@ -411,9 +603,12 @@ impl Default for DiskFileBlobStoreConfig {
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum OpenDiskFileBlobStore {
     /// Clear everything in the blob store.
-    #[default]
     Clear,
-    /// Keep the existing blob store and index
+    /// Keep the existing blob store and index.
+    ///
+    /// Indexed blobs whose file is missing or incomplete are dropped from the index, and blob
+    /// files that are not indexed are deleted.
+    #[default]
     ReIndex,
 }
 
@@ -469,4 +664,80 @@ mod tests {
         assert!(!store.contains(all_hashes[0]).unwrap());
         assert!(store.get_exact(all_hashes).is_err());
     }
+
+    #[test]
+    fn disk_reopen_keeps_blobs() {
+        let (store, dir) = tmp_store();
+
+        let blobs = rng_blobs(10);
+        let (deleted, _) = blobs[0];
+        store.insert_all(blobs.clone()).unwrap();
+        store.delete(deleted).unwrap();
+        let data_size = store.data_size_hint();
+        drop(store);
+
+        let store = DiskFileBlobStore::open(dir.path(), Default::default()).unwrap();
+        assert_eq!(store.blobs_len(), blobs.len() - 1);
+        assert_eq!(store.data_size_hint(), data_size);
+        assert!(!store.contains(deleted).unwrap());
+        for (tx, blob) in &blobs[1..] {
+            assert!(!store.is_cached(tx));
+            assert_eq!(store.get(*tx).unwrap().unwrap(), *blob);
+        }
+
+        let mut hashes = store.all_hashes().unwrap();
+        hashes.sort();
+        let mut expected = blobs[1..].iter().map(|(tx, _)| *tx).collect::<Vec<_>>();
+        expected.sort();
+        assert_eq!(hashes, expected);
+    }
+
+    #[test]
+    fn disk_reopen_clear() {
+        let (store, dir) = tmp_store();
+        store.insert_all(rng_blobs(10)).unwrap();
+        drop(store);
+
+        let config =
+            DiskFileBlobStoreConfig { open: OpenDiskFileBlobStore::Clear, ..Default::default() };
+        let store = DiskFileBlobStore::open(dir.path(), config).unwrap();
+        assert_eq!(store.blobs_len(), 0);
+        assert!(store.all_hashes().unwrap().is_empty());
+    }
+
+    #[test]
+    fn disk_reindex_removes_inconsistent_blobs() {
+        let (store, dir) = tmp_store();
+
+        let blobs = rng_blobs(3);
+        store.insert_all(blobs.clone()).unwrap();
+        let (missing, _) = blobs[0];
+        let (truncated, _) = blobs[1];
+        let orphan = TxHash::random();
+        drop(store);
+
+        fs::remove_file(dir.path().join(format!("{missing:x}"))).unwrap();
+        fs::write(dir.path().join(format!("{truncated:x}")), [1, 2, 3]).unwrap();
+        let mut buf = Vec::new();
+        blobs[2].1.encode(&mut buf);
+        fs::write(dir.path().join(format!("{orphan:x}")), buf).unwrap();
+
+        let store = DiskFileBlobStore::open(dir.path(), Default::default()).unwrap();
+        assert_eq!(store.all_hashes().unwrap(), vec![blobs[2].0]);
+        assert_eq!(store.blobs_len(), 1);
+        assert!(!dir.path().join(format!("{truncated:x}")).exists());
+        assert!(!dir.path().join(format!("{orphan:x}")).exists());
+        assert_eq!(store.get(blobs[2].0).unwrap().unwrap(), blobs[2].1);
+    }
+
+    #[test]
+    fn replay_ignores_truncated_record() {
+        let tx = TxHash::random();
+        let mut records = Vec::new();
+        encode_index_record(&mut records, INDEX_INSERT, tx, 10);
+        encode_index_record(&mut records, INDEX_DELETE, tx, 10);
+        records.truncate(records.len() - 1);
+
+        assert_eq!(BlobIndex::replay(&records), HashMap::from([(tx, 10)]));
+    }
 }
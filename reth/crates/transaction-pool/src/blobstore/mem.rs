@@ -108,6 +108,10 @@ impl BlobStore for InMemoryBlobStore {
     fn blobs_len(&self) -> usize {
         self.inner.size_tracker.blobs_len()
     }
+
+    fn all_hashes(&self) -> Result<Vec<B256>, BlobStoreError> {
+        Ok(self.inner.store.read().keys().copied().collect())
+    }
 }
 
 /// Removes the given blob from the store and returns the size of the blob that was removed.
@@ -59,6 +59,13 @@ pub trait BlobStore: fmt::Debug + Send + Sync + 'static {
 
     /// How many blobs are in the blob store.
     fn blobs_len(&self) -> usize;
+
+    /// Returns the transaction hashes of all blobs in the blob store.
+    ///
+    /// By default, this returns no hashes, for stores that can't list their blobs.
+    fn all_hashes(&self) -> Result<Vec<B256>, BlobStoreError> {
+        Ok(Vec::new())
+    }
 }
 
 /// Error variants that can occur when interacting with a blob store.
@@ -98,11 +105,6 @@ impl BlobStoreSize {
         self.num_blobs.store(len, std::sync::atomic::Ordering::Relaxed);
     }
 
-    #[inline]
-    pub(crate) fn inc_len(&self, add: usize) {
-        self.num_blobs.fetch_add(add, std::sync::atomic::Ordering::Relaxed);
-    }
-
     #[inline]
     pub(crate) fn data_size(&self) -> usize {
         self.data_size.load(std::sync::atomic::Ordering::Relaxed)
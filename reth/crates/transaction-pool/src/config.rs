@@ -1,6 +1,6 @@
 use crate::{PoolSize, TransactionOrigin};
 use reth_primitives::{Address, EIP4844_TX_TYPE_ID};
-use std::collections::HashSet;
+use std::{collections::HashSet, time::Duration};
 /// Guarantees max transactions for one sender, compatible with geth/erigon
 pub const TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER: usize = 16;
 
@@ -18,6 +18,10 @@ pub const DEFAULT_PRICE_BUMP: u128 = 10;
 /// This enforces that a blob transaction requires a 100% price bump to be replaced
 pub const REPLACE_BLOB_PRICE_BUMP: u128 = 100;
 
+/// Default maximum time transactions are kept in the queued and basefee sub-pools, compatible
+/// with geth's `txpool.lifetime`.
+pub const DEFAULT_MAX_QUEUED_LIFETIME: Duration = Duration::from_secs(3 * 60 * 60);
+
 /// Configuration options for the Transaction pool.
 #[derive(Debug, Clone)]
 pub struct PoolConfig {
@@ -33,6 +37,10 @@ pub struct PoolConfig {
     pub max_account_slots: usize,
     /// Price bump (in %) for the transaction pool underpriced check.
     pub price_bumps: PriceBumpConfig,
+    /// Max time a transaction is kept in the queued or basefee sub-pool before it's evicted.
+    ///
+    /// Local transactions are exempt, unless local exemptions are disabled.
+    pub max_queued_lifetime: Duration,
     /// How to handle locally received transactions:
     /// [TransactionOrigin::Local](crate::TransactionOrigin).
     pub local_transactions_config: LocalTransactionConfig,
@@ -58,6 +66,7 @@ impl Default for PoolConfig {
             blob_limit: Default::default(),
             max_account_slots: TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
             price_bumps: Default::default(),
+            max_queued_lifetime: DEFAULT_MAX_QUEUED_LIFETIME,
             local_transactions_config: Default::default(),
         }
     }
@@ -167,6 +167,39 @@ pub enum Eip4844PoolTransactionError {
     Eip4844NonceGap,
 }
 
+/// Represents all reasons for which an [AdmissionPolicy](crate::validate::AdmissionPolicy) can
+/// reject a transaction
+#[derive(Debug, thiserror::Error)]
+pub enum AdmissionPolicyError {
+    /// Thrown if the recipient of the transaction is denied
+    #[error("recipient {0} is denied")]
+    DeniedRecipient(Address),
+    /// Thrown if the input data of the transaction exceeds the configured limit
+    #[error("input data size {size} exceeds limit {limit}")]
+    OversizedInput {
+        /// Size of the input data in bytes
+        size: usize,
+        /// Configured limit in bytes
+        limit: usize,
+    },
+    /// Thrown if the sender isn't allowed to submit transactions during a private window
+    #[error("sender {0} not allowed during private window")]
+    SenderNotAllowed(Address),
+    /// Thrown if the priority fee of the transaction is below the minimum for its type
+    #[error("priority fee {tip} below minimum {minimum} for transaction type {tx_type}")]
+    TipBelowMinimum {
+        /// Type of the transaction
+        tx_type: u8,
+        /// Priority fee of the transaction, or the gas price for legacy transactions
+        tip: u128,
+        /// Configured minimum priority fee for the type
+        minimum: u128,
+    },
+    /// Any other rejection of a custom policy
+    #[error(transparent)]
+    Other(Box<dyn PoolTransactionError>),
+}
+
 /// Represents errors that can happen when validating transactions for the pool
 ///
 /// See [TransactionValidator](crate::TransactionValidator).
@@ -197,6 +230,9 @@ pub enum InvalidPoolTransactionError {
     /// Eip-4844 related errors
     #[error(transparent)]
     Eip4844(#[from] Eip4844PoolTransactionError),
+    /// Thrown if the transaction was rejected by an admission policy of the pool
+    #[error(transparent)]
+    AdmissionPolicy(#[from] AdmissionPolicyError),
     /// Any other error that occurred while inserting/validating that is transaction specific
     #[error(transparent)]
     Other(Box<dyn PoolTransactionError>),
@@ -259,6 +295,13 @@ impl InvalidPoolTransactionError {
             }
             InvalidPoolTransactionError::IntrinsicGasTooLow => true,
             InvalidPoolTransactionError::Overdraft => false,
+            InvalidPoolTransactionError::AdmissionPolicy(err) => match err {
+                AdmissionPolicyError::Other(err) => err.is_bad_transaction(),
+                _ => {
+                    // local setting
+                    false
+                }
+            },
             InvalidPoolTransactionError::Other(err) => err.is_bad_transaction(),
             InvalidPoolTransactionError::Eip4844(eip4844_err) => {
                 match eip4844_err {
@@ -139,6 +139,7 @@
 //! ## Feature Flags
 //!
 //! - `serde` (default): Enable serde support
+//! - `clap`: Derive `clap::ValueEnum` for [TransactionOrderingKind]
 //! - `test-utils`: Export utilities for testing
 
 #![doc(
@@ -161,12 +162,16 @@ use tracing::{instrument, trace};
 pub use crate::{
     blobstore::{BlobStore, BlobStoreError},
     config::{
-        LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, DEFAULT_PRICE_BUMP,
-        REPLACE_BLOB_PRICE_BUMP, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
-        TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT, TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
+        LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit,
+        DEFAULT_MAX_QUEUED_LIFETIME, DEFAULT_PRICE_BUMP, REPLACE_BLOB_PRICE_BUMP,
+        TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER, TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT,
+        TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
     },
     error::PoolResult,
-    ordering::{CoinbaseTipOrdering, Priority, TransactionOrdering},
+    ordering::{
+        BlobFeeAwareOrdering, CoinbaseTipOrdering, ConfiguredOrdering,
+        FirstComeFirstServedOrdering, Priority, TransactionOrdering, TransactionOrderingKind,
+    },
     pool::{
         blob_tx_priority, fee_delta, state::SubPool, AllTransactionsEvents, FullTransactionEvent,
         TransactionEvent, TransactionEvents,
@@ -202,6 +207,13 @@ pub type EthTransactionPool<Client, S> = Pool<
     S,
 >;
 
+/// Type alias for an ethereum transaction pool that uses a built-in ordering selected at runtime
+pub type ConfiguredEthTransactionPool<Client, S> = Pool<
+    TransactionValidationTaskExecutor<EthTransactionValidator<Client, EthPooledTransaction>>,
+    ConfiguredOrdering<EthPooledTransaction>,
+    S,
+>;
+
 /// A shareable, generic, customizable `TransactionPool` implementation.
 #[derive(Debug)]
 pub struct Pool<V, T: TransactionOrdering, S> {
@@ -283,6 +295,8 @@ where
     /// Returns a new [Pool] that uses the default [TransactionValidationTaskExecutor] when
     /// validating [EthPooledTransaction]s and ords via [CoinbaseTipOrdering]
     ///
+    /// See [ConfiguredEthTransactionPool] to use another built-in ordering.
+    ///
     /// # Example
     ///
     /// ```
@@ -317,6 +331,25 @@ where
     }
 }
 
+impl<Client, S> ConfiguredEthTransactionPool<Client, S>
+where
+    Client: StateProviderFactory + reth_provider::BlockReaderIdExt + Clone + 'static,
+    S: BlobStore,
+{
+    /// Returns a new [Pool] that uses the default [TransactionValidationTaskExecutor] when
+    /// validating [EthPooledTransaction]s and orders via the given built-in ordering.
+    pub fn eth_pool_with_ordering(
+        validator: TransactionValidationTaskExecutor<
+            EthTransactionValidator<Client, EthPooledTransaction>,
+        >,
+        ordering: TransactionOrderingKind,
+        blob_store: S,
+        config: PoolConfig,
+    ) -> Self {
+        Self::new(validator, ConfiguredOrdering::new(ordering), blob_store, config)
+    }
+}
+
 /// implements the `TransactionPool` interface for various transaction pool API consumers.
 #[async_trait::async_trait]
 impl<V, T, S> TransactionPool for Pool<V, T, S>
@@ -548,6 +581,14 @@ where
     fn delete_blobs(&self, txs: Vec<TxHash>) {
         self.pool.delete_blobs(txs)
     }
+
+    fn blob_store_hashes(&self) -> Result<Vec<TxHash>, BlobStoreError> {
+        self.pool.blob_store().all_hashes()
+    }
+
+    fn remove_expired_transactions(&self) -> Vec<TxHash> {
+        self.pool.remove_expired_transactions()
+    }
 }
 
 impl<V, T: TransactionOrdering, S> Clone for Pool<V, T, S> {
@@ -5,8 +5,9 @@ use crate::{
     error::PoolError,
     metrics::MaintainPoolMetrics,
     traits::{CanonicalStateUpdate, ChangedAccount, TransactionPool, TransactionPoolExt},
-    BlockInfo,
+    BlockInfo, TransactionOrigin,
 };
+use alloy_rlp::{RlpDecodable, RlpEncodable};
 use futures_util::{
     future::{BoxFuture, Fuse, FusedFuture},
     FutureExt, Stream, StreamExt,
@@ -14,7 +15,7 @@ use futures_util::{
 use reth_primitives::{
     fs::FsPathError, Address, BlockHash, BlockNumber, BlockNumberOrTag,
     FromRecoveredPooledTransaction, FromRecoveredTransaction, IntoRecoveredTransaction,
-    PooledTransactionsElementEcRecovered, TransactionSigned,
+    PooledTransactionsElementEcRecovered, TransactionSigned, TransactionSignedEcRecovered, B256,
 };
 use reth_provider::{
     BlockReaderIdExt, BundleStateWithReceipts, CanonStateNotification, ChainSpecProvider,
@@ -23,15 +24,17 @@ use reth_provider::{
 use reth_tasks::TaskSpawner;
 use std::{
     borrow::Borrow,
-    collections::HashSet,
+    collections::{BTreeMap, HashSet},
     hash::{Hash, Hasher},
     path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
-use tokio::sync::oneshot;
+use tokio::sync::{oneshot, Notify};
 use tracing::{debug, error, info, trace, warn};
 
 /// Additional settings for maintaining the transaction pool
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone)]
 pub struct MaintainPoolConfig {
     /// Maximum (reorg) depth we handle when updating the transaction pool: `new.number -
     /// last_seen.number`
@@ -42,11 +45,43 @@ pub struct MaintainPoolConfig {
     ///
     /// Default: 100
     pub max_reload_accounts: usize,
+    /// How often transactions that exceeded their lifetime are evicted from the pool.
+    ///
+    /// Default: 60s
+    pub expired_transactions_interval: Duration,
+    /// Signaled once the transactions persisted by the previous run were reinserted into the
+    /// pool. The blobs of the blob store that aren't referenced by a pool transaction are only
+    /// deleted after that.
+    ///
+    /// Default: None, the unreferenced blobs are deleted right away
+    pub transactions_reloaded: Option<TransactionsReloaded>,
 }
 
 impl Default for MaintainPoolConfig {
     fn default() -> Self {
-        Self { max_update_depth: 64, max_reload_accounts: 100 }
+        Self {
+            max_update_depth: 64,
+            max_reload_accounts: 100,
+            expired_transactions_interval: Duration::from_secs(60),
+            transactions_reloaded: None,
+        }
+    }
+}
+
+/// Signals that the transactions persisted by the previous run of the node were reinserted into
+/// the pool, see [MaintainPoolConfig::transactions_reloaded].
+#[derive(Debug, Clone, Default)]
+pub struct TransactionsReloaded(Arc<Notify>);
+
+impl TransactionsReloaded {
+    /// Signals that the transactions were reinserted, or that there was nothing to reinsert.
+    pub fn notify(&self) {
+        self.0.notify_one();
+    }
+
+    /// Waits until the transactions were reinserted.
+    pub async fn wait(&self) {
+        self.0.notified().await;
     }
 }
 
@@ -55,12 +90,80 @@ impl Default for MaintainPoolConfig {
 pub struct LocalTransactionBackupConfig {
     /// Path to transactions backup file
     pub transactions_path: Option<PathBuf>,
+    /// Signaled once the transactions of the backup were reinserted into the pool
+    pub transactions_reloaded: Option<TransactionsReloaded>,
 }
 
 impl LocalTransactionBackupConfig {
     /// Receive path to transactions backup and return initialized config
     pub const fn with_local_txs_backup(transactions_path: PathBuf) -> Self {
-        Self { transactions_path: Some(transactions_path) }
+        Self { transactions_path: Some(transactions_path), transactions_reloaded: None }
+    }
+
+    /// Sets the signal to notify once the transactions of the backup were reinserted
+    pub fn with_transactions_reloaded(
+        mut self,
+        transactions_reloaded: TransactionsReloaded,
+    ) -> Self {
+        self.transactions_reloaded = Some(transactions_reloaded);
+        self
+    }
+}
+
+/// Version of the format of the transaction pool journal.
+pub const POOL_JOURNAL_VERSION: u8 = 1;
+
+/// Default interval at which the transaction pool is journaled.
+pub const DEFAULT_POOL_JOURNAL_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Settings for the transaction pool journal task
+#[derive(Debug, Clone)]
+pub struct PoolJournalConfig {
+    /// Path to the journal file
+    pub journal_path: PathBuf,
+    /// Interval at which the pool is journaled, in addition to on shutdown
+    ///
+    /// Default: 60s
+    pub interval: Duration,
+    /// Path to a backup of local transactions, see [LocalTransactionBackupConfig].
+    ///
+    /// The transactions of the backup are reinserted once on startup and the backup is removed,
+    /// so local transactions aren't lost when switching from the backup to the journal.
+    pub local_transactions_path: Option<PathBuf>,
+    /// Signaled once the transactions of the journal were reinserted into the pool
+    pub transactions_reloaded: Option<TransactionsReloaded>,
+}
+
+impl PoolJournalConfig {
+    /// Returns a config that journals the pool to the given file at the default interval
+    pub const fn new(journal_path: PathBuf) -> Self {
+        Self {
+            journal_path,
+            interval: DEFAULT_POOL_JOURNAL_INTERVAL,
+            local_transactions_path: None,
+            transactions_reloaded: None,
+        }
+    }
+
+    /// Sets the interval at which the pool is journaled
+    pub const fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Sets the backup of local transactions to reinsert on startup
+    pub fn with_local_txs_backup(mut self, local_transactions_path: PathBuf) -> Self {
+        self.local_transactions_path = Some(local_transactions_path);
+        self
+    }
+
+    /// Sets the signal to notify once the transactions of the journal were reinserted
+    pub fn with_transactions_reloaded(
+        mut self,
+        transactions_reloaded: TransactionsReloaded,
+    ) -> Self {
+        self.transactions_reloaded = Some(transactions_reloaded);
+        self
     }
 }
 
@@ -100,7 +203,12 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
     Tasks: TaskSpawner + 'static,
 {
     let metrics = MaintainPoolMetrics::default();
-    let MaintainPoolConfig { max_update_depth, max_reload_accounts, .. } = config;
+    let MaintainPoolConfig {
+        max_update_depth,
+        max_reload_accounts,
+        expired_transactions_interval,
+        transactions_reloaded,
+    } = config;
     // ensure the pool points to latest state
     if let Ok(Some(latest)) = client.header_by_number_or_tag(BlockNumberOrTag::Latest) {
         let latest = latest.seal_slow();
@@ -123,6 +231,21 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
     let mut last_finalized_block =
         FinalizedBlockTracker::new(client.finalized_block_number().ok().flatten());
 
+    // the blob store can outlive the node, so it may contain blobs of transactions that were mined
+    // in the meantime or that are not in the pool anymore, the transactions are looked up on a
+    // blocking task
+    let mut reconcile_blob_store_fut = {
+        let (tx, rx) = oneshot::channel();
+        let c = client.clone();
+        let p = pool.clone();
+        let finalized_block = last_finalized_block.last_finalized_block;
+        let fut = async move {
+            let _ = tx.send(reconcile_blob_store(&c, &p, finalized_block));
+        };
+        task_spawner.spawn_blocking(fut.boxed());
+        rx.fuse()
+    };
+
     // keeps track of any dirty accounts that we know of are out of sync with the pool
     let mut dirty_addresses = HashSet::new();
 
@@ -132,6 +255,12 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
     // the future that reloads accounts from state
     let mut reload_accounts_fut = Fuse::terminated();
 
+    // periodically evicts transactions that were parked for too long
+    let mut evict_expired_interval = tokio::time::interval_at(
+        tokio::time::Instant::now() + expired_transactions_interval,
+        expired_transactions_interval,
+    );
+
     // The update loop that waits for new blocks and reorgs and performs pool updated
     // Listen for new chain events and derive the update action for the pool
     loop {
@@ -209,10 +338,31 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
             ev = events.next() =>  {
                  if ev.is_none() {
                     // the stream ended, we are done
-                    break;
+                    break
                 }
                 event = ev;
             }
+            res = &mut reconcile_blob_store_fut => {
+                if let Ok(BlobStoreReconciliation { finalized, mined, unmined }) = res {
+                    metrics.inc_deleted_tracked_blobs(finalized);
+                    blob_store_tracker.add_blocks(mined);
+                    task_spawner.spawn(
+                        delete_unreferenced_blobs(
+                            pool.clone(),
+                            unmined,
+                            transactions_reloaded.clone(),
+                        )
+                        .boxed(),
+                    );
+                }
+            }
+            _ = evict_expired_interval.tick() => {
+                let evicted = pool.remove_expired_transactions();
+                if !evicted.is_empty() {
+                    debug!(target: "txpool", count=evicted.len(), "evicted expired transactions");
+                    metrics.inc_evicted_expired_transactions(evicted.len());
+                }
+            }
         }
 
         // handle the result of the account reload
@@ -552,6 +702,95 @@ fn changed_accounts_iter(
         .map(|(address, acc)| ChangedAccount { address, nonce: acc.nonce, balance: acc.balance })
 }
 
+/// Outcome of [reconcile_blob_store].
+#[derive(Debug, Default)]
+struct BlobStoreReconciliation {
+    /// The number of deleted blobs of transactions mined in finalized blocks.
+    finalized: usize,
+    /// Blobs of transactions mined in blocks that are not finalized yet, by block.
+    mined: BTreeMap<BlockNumber, Vec<B256>>,
+    /// Blobs of transactions that were not mined.
+    unmined: Vec<B256>,
+}
+
+/// Checks the blobs in the blob store against the canonical chain.
+///
+/// Blobs of transactions that were mined in finalized blocks are deleted. Transactions that were
+/// mined in blocks that are not finalized yet are returned to be tracked until they are, and the
+/// rest to be checked against the pool, see [delete_unreferenced_blobs].
+///
+/// This looks up every blob transaction in the database, so it should run on a blocking task.
+fn reconcile_blob_store<Client, P>(
+    client: &Client,
+    pool: &P,
+    finalized_block: Option<BlockNumber>,
+) -> BlobStoreReconciliation
+where
+    Client: BlockReaderIdExt,
+    P: TransactionPoolExt,
+{
+    let blobs = match pool.blob_store_hashes() {
+        Ok(blobs) => blobs,
+        Err(err) => {
+            debug!(target: "txpool", %err, "failed to load blob store hashes");
+            return BlobStoreReconciliation::default()
+        }
+    };
+
+    let mut finalized = Vec::new();
+    let mut reconciliation = BlobStoreReconciliation::default();
+    for tx in blobs {
+        match client.transaction_by_hash_with_meta(tx) {
+            Ok(Some((_, meta))) => {
+                if finalized_block.is_some_and(|finalized| meta.block_number <= finalized) {
+                    finalized.push(tx);
+                } else {
+                    reconciliation.mined.entry(meta.block_number).or_default().push(tx);
+                }
+            }
+            Ok(None) => reconciliation.unmined.push(tx),
+            // keep the blob if the transaction can't be looked up
+            Err(_) => {}
+        }
+    }
+
+    debug!(
+        target: "txpool",
+        finalized = finalized.len(),
+        mined = reconciliation.mined.values().map(Vec::len).sum::<usize>(),
+        unmined = reconciliation.unmined.len(),
+        "reconciled blob store with the canonical chain"
+    );
+
+    reconciliation.finalized = finalized.len();
+    if !finalized.is_empty() {
+        pool.delete_blobs(finalized);
+    }
+    reconciliation
+}
+
+/// Deletes the blobs of transactions that were not mined and are not in the pool.
+///
+/// This waits until the transactions persisted by the previous run were reinserted into the pool,
+/// since those reuse the blobs of the blob store.
+async fn delete_unreferenced_blobs<P>(
+    pool: P,
+    unmined: Vec<B256>,
+    transactions_reloaded: Option<TransactionsReloaded>,
+) where
+    P: TransactionPool,
+{
+    if let Some(transactions_reloaded) = transactions_reloaded {
+        transactions_reloaded.wait().await;
+    }
+
+    let unreferenced = unmined.into_iter().filter(|tx| !pool.contains(tx)).collect::<Vec<_>>();
+    debug!(target: "txpool", count = unreferenced.len(), "deleting unreferenced blobs");
+    if !unreferenced.is_empty() {
+        pool.delete_blobs(unreferenced);
+    }
+}
+
 /// Loads transactions from a file, decodes them from the RLP format, and inserts them
 /// into the transaction pool on node boot up.
 /// The file is removed after the transactions have been successfully processed.
@@ -577,7 +816,8 @@ where
 
     let pool_transactions = txs_signed
         .into_iter()
-        .filter_map(|tx| tx.try_ecrecovered().map(<P::Transaction>::from_recovered_transaction))
+        .filter_map(|tx| tx.try_ecrecovered())
+        .filter_map(|tx| restore_pool_transaction(&pool, tx))
         .collect::<Vec<_>>();
     let outcome = pool.add_transactions(crate::TransactionOrigin::Local, pool_transactions).await;
 
@@ -586,6 +826,27 @@ where
     Ok(())
 }
 
+/// Converts a transaction of a backup or journal into a pool transaction.
+///
+/// Blobs are not backed up, so a blob transaction is only restored if its blob is still in the blob
+/// store, which is the case if the blob store was persisted.
+fn restore_pool_transaction<P>(pool: &P, tx: TransactionSignedEcRecovered) -> Option<P::Transaction>
+where
+    P: TransactionPool,
+{
+    if tx.is_eip4844() {
+        pool.get_blob(tx.hash)
+            .ok()
+            .flatten()
+            .and_then(|sidecar| {
+                PooledTransactionsElementEcRecovered::try_from_blob_transaction(tx, sidecar).ok()
+            })
+            .map(<P::Transaction>::from_recovered_pooled_transaction)
+    } else {
+        Some(<P::Transaction>::from_recovered_transaction(tx))
+    }
+}
+
 fn save_local_txs_backup<P>(pool: P, file_path: &Path)
 where
     P: TransactionPool,
@@ -629,6 +890,9 @@ pub enum TransactionsBackupError {
     /// Error adding transactions to the transaction pool
     #[error("failed to insert transactions to the transactions pool. Encountered pool error: {0}")]
     Pool(#[from] PoolError),
+    /// The journal was written with a format version that is not supported
+    #[error("failed to apply transactions journal. Unsupported journal version: {0}")]
+    UnsupportedJournalVersion(u8),
 }
 
 /// Task which manages saving local transactions to the persistent file in case of shutdown.
@@ -640,14 +904,21 @@ pub async fn backup_local_transactions_task<P>(
 ) where
     P: TransactionPool + Clone,
 {
-    let Some(transactions_path) = config.transactions_path else {
+    let LocalTransactionBackupConfig { transactions_path, transactions_reloaded } = config;
+    let Some(transactions_path) = transactions_path else {
         // nothing to do
+        if let Some(transactions_reloaded) = transactions_reloaded {
+            transactions_reloaded.notify();
+        }
         return
     };
 
     if let Err(err) = load_and_reinsert_transactions(pool.clone(), &transactions_path).await {
         error!(target: "txpool", "{}", err)
     }
+    if let Some(transactions_reloaded) = transactions_reloaded {
+        transactions_reloaded.notify();
+    }
 
     let graceful_guard = shutdown.await;
 
@@ -657,19 +928,291 @@ pub async fn backup_local_transactions_task<P>(
     drop(graceful_guard)
 }
 
+/// A transaction of the pool journal.
+#[derive(Debug, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+struct PoolJournalEntry {
+    /// Origin of the transaction, see [journal_origin].
+    origin: u8,
+    /// When the transaction was added to the pool, in milliseconds since the unix epoch.
+    ///
+    /// This is only used to reinsert the transactions in the order they were added, the pool
+    /// treats reinserted transactions as new.
+    timestamp: u64,
+    /// The transaction, without the blob of a blob transaction.
+    transaction: TransactionSigned,
+}
+
+/// Returns the journal encoding of the origin.
+const fn journal_origin(origin: TransactionOrigin) -> u8 {
+    match origin {
+        TransactionOrigin::Local => 0,
+        TransactionOrigin::External => 1,
+        TransactionOrigin::Private => 2,
+    }
+}
+
+/// Decodes the journal encoding of an origin, see [journal_origin].
+const fn origin_from_journal(origin: u8) -> Option<TransactionOrigin> {
+    match origin {
+        0 => Some(TransactionOrigin::Local),
+        1 => Some(TransactionOrigin::External),
+        2 => Some(TransactionOrigin::Private),
+        _ => None,
+    }
+}
+
+/// Loads the transactions of the journal file and inserts them into the transaction pool.
+///
+/// The transactions are validated again on insertion, so transactions that became invalid while
+/// the node was offline are dropped. Transactions of the same origin are inserted in the order they
+/// were added to the pool before. The time they were added is not restored, reinserted
+/// transactions count as just added, e.g. for the lifetime of queued transactions.
+async fn load_and_reinsert_journal<P>(
+    pool: P,
+    journal_path: &Path,
+) -> Result<(), TransactionsBackupError>
+where
+    P: TransactionPool,
+{
+    if !journal_path.exists() {
+        return Ok(())
+    }
+
+    debug!(target: "txpool", journal =?journal_path, "Loading transactions from journal");
+    let data = reth_primitives::fs::read(journal_path)?;
+
+    // the journal is the format version followed by the RLP list of entries
+    let Some((&version, mut journal)) = data.split_first() else { return Ok(()) };
+    if version != POOL_JOURNAL_VERSION {
+        return Err(TransactionsBackupError::UnsupportedJournalVersion(version))
+    }
+    let mut entries: Vec<PoolJournalEntry> = alloy_rlp::Decodable::decode(&mut journal)?;
+    entries.sort_by_key(|entry| entry.timestamp);
+
+    let mut num_txs = 0;
+    for origin in
+        [TransactionOrigin::Local, TransactionOrigin::Private, TransactionOrigin::External]
+    {
+        let pool_transactions = entries
+            .iter()
+            .filter(|entry| origin_from_journal(entry.origin) == Some(origin))
+            .filter_map(|entry| entry.transaction.clone().try_ecrecovered())
+            .filter_map(|tx| restore_pool_transaction(&pool, tx))
+            .collect::<Vec<_>>();
+        let outcome = pool.add_transactions(origin, pool_transactions).await;
+        num_txs += outcome.iter().filter(|res| res.is_ok()).count();
+    }
+
+    info!(target: "txpool", journal =?journal_path, num_txs=%num_txs, num_journaled=%entries.len(), "Reinserted transactions from journal");
+    Ok(())
+}
+
+/// Returns the journal entries of all transactions of the pool.
+fn pool_journal_entries<P>(pool: &P) -> Vec<PoolJournalEntry>
+where
+    P: TransactionPool,
+{
+    let now = SystemTime::now();
+    [TransactionOrigin::Local, TransactionOrigin::External, TransactionOrigin::Private]
+        .into_iter()
+        .flat_map(|origin| pool.get_transactions_by_origin(origin))
+        .map(|tx| PoolJournalEntry {
+            origin: journal_origin(tx.origin),
+            timestamp: now
+                .checked_sub(tx.timestamp.elapsed())
+                .and_then(|timestamp| timestamp.duration_since(UNIX_EPOCH).ok())
+                .map(|timestamp| timestamp.as_millis() as u64)
+                .unwrap_or_default(),
+            transaction: tx.to_recovered_transaction().into_signed(),
+        })
+        .collect()
+}
+
+/// Writes the entries to the journal file.
+///
+/// The journal is written to a temporary file first, so that the previous journal is kept if
+/// writing fails.
+fn write_pool_journal(entries: &[PoolJournalEntry], journal_path: &Path) {
+    let mut buf = vec![POOL_JOURNAL_VERSION];
+    alloy_rlp::encode_list(entries, &mut buf);
+
+    let tmp_path = journal_path.with_extension("tmp");
+    let res = journal_path
+        .parent()
+        .map(reth_primitives::fs::create_dir_all)
+        .transpose()
+        .and_then(|_| reth_primitives::fs::write(&tmp_path, buf))
+        .and_then(|_| reth_primitives::fs::rename(&tmp_path, journal_path));
+    match res {
+        Ok(_) => {
+            trace!(target: "txpool", journal=?journal_path, num_txs=%entries.len(), "Wrote transactions journal");
+        }
+        Err(err) => {
+            warn!(target: "txpool", %err, journal=?journal_path, "Failed to write transactions journal");
+        }
+    }
+}
+
+/// Writes all transactions of the pool to the journal file.
+///
+/// The journal is encoded and written on a blocking task.
+async fn save_pool_journal<P, Tasks>(pool: &P, task_spawner: &Tasks, journal_path: &Path)
+where
+    P: TransactionPool,
+    Tasks: TaskSpawner,
+{
+    let entries = pool_journal_entries(pool);
+    let journal_path = journal_path.to_path_buf();
+    let (tx, rx) = oneshot::channel();
+    let fut = async move {
+        write_pool_journal(&entries, &journal_path);
+        let _ = tx.send(());
+    };
+    task_spawner.spawn_blocking(fut.boxed());
+    let _ = rx.await;
+}
+
+/// Task which journals all transactions of the pool, periodically and on shutdown.
+///
+/// Unlike [backup_local_transactions_task], this persists the transactions of all origins and sub
+/// pools. On startup, the transactions of the journal are validated again and reinserted into the
+/// pool.
+pub async fn journal_pool_transactions_task<P, Tasks>(
+    mut shutdown: reth_tasks::shutdown::GracefulShutdown,
+    pool: P,
+    task_spawner: Tasks,
+    config: PoolJournalConfig,
+) where
+    P: TransactionPool + Clone,
+    Tasks: TaskSpawner,
+{
+    let PoolJournalConfig {
+        journal_path,
+        interval,
+        local_transactions_path,
+        transactions_reloaded,
+    } = config;
+
+    if let Some(local_transactions_path) = local_transactions_path {
+        if let Err(err) =
+            load_and_reinsert_transactions(pool.clone(), &local_transactions_path).await
+        {
+            error!(target: "txpool", "{}", err)
+        }
+    }
+    if let Err(err) = load_and_reinsert_journal(pool.clone(), &journal_path).await {
+        error!(target: "txpool", "{}", err)
+    }
+    if let Some(transactions_reloaded) = transactions_reloaded {
+        transactions_reloaded.notify();
+    }
+
+    let mut interval = tokio::time::interval_at(tokio::time::Instant::now() + interval, interval);
+    let graceful_guard = loop {
+        tokio::select! {
+            guard = &mut shutdown => break guard,
+            _ = interval.tick() => save_pool_journal(&pool, &task_spawner, &journal_path).await,
+        }
+    };
+
+    // write the final journal before shutting down, in place since tasks spawned now would be
+    // cancelled by the shutdown
+    write_pool_journal(&pool_journal_entries(&pool), &journal_path);
+
+    drop(graceful_guard)
+}
+
 #[cfg(not(feature = "optimism"))]
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{
-        blobstore::InMemoryBlobStore, validate::EthTransactionValidatorBuilder,
+        blobstore::{BlobStore, InMemoryBlobStore},
+        validate::{EthTransactionValidator, EthTransactionValidatorBuilder},
         CoinbaseTipOrdering, EthPooledTransaction, Pool, PoolTransaction, TransactionOrigin,
     };
     use reth_primitives::{
-        fs, hex, FromRecoveredPooledTransaction, PooledTransactionsElement, MAINNET, U256,
+        fs, hex, Block, FromRecoveredPooledTransaction, Header, PooledTransactionsElement,
+        Transaction, TxLegacy, MAINNET, U256,
     };
     use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
-    use reth_tasks::TaskManager;
+    use reth_tasks::{TaskManager, TokioTaskExecutor};
+
+    #[test]
+    fn reconcile_blob_store_with_chain() {
+        let provider = MockEthProvider::default();
+        let blob_store = InMemoryBlobStore::default();
+        let validator = EthTransactionValidatorBuilder::new(MAINNET.clone())
+            .build(provider.clone(), blob_store.clone());
+        let txpool = Pool::new(
+            validator,
+            CoinbaseTipOrdering::default(),
+            blob_store.clone(),
+            Default::default(),
+        );
+
+        // transactions mined in blocks 1 and 2, and one that is not mined
+        let mut txs = (0..3).map(|nonce| {
+            TransactionSigned::from_transaction_and_signature(
+                Transaction::Legacy(TxLegacy { nonce, ..Default::default() }),
+                Default::default(),
+            )
+        });
+        let (finalized, mined, pending) =
+            (txs.next().unwrap(), txs.next().unwrap(), txs.next().unwrap());
+        for (number, tx) in [(1, &finalized), (2, &mined)] {
+            let block = Block {
+                header: Header { number, ..Default::default() },
+                body: vec![tx.clone()],
+                ..Default::default()
+            };
+            provider.add_block(B256::with_last_byte(number as u8), block);
+        }
+        for tx in [&finalized, &mined, &pending] {
+            blob_store.insert(tx.hash, Default::default()).unwrap();
+        }
+
+        let reconciliation = reconcile_blob_store(&provider, &txpool, Some(1));
+        assert_eq!(reconciliation.finalized, 1);
+        assert_eq!(reconciliation.mined, BTreeMap::from([(2, vec![mined.hash])]));
+        assert_eq!(reconciliation.unmined, vec![pending.hash]);
+
+        assert!(!blob_store.contains(finalized.hash).unwrap());
+        assert!(blob_store.contains(mined.hash).unwrap());
+        assert!(blob_store.contains(pending.hash).unwrap());
+    }
+
+    #[tokio::test]
+    async fn delete_unreferenced_blobs_after_reload() {
+        let blob_store = InMemoryBlobStore::default();
+        let validator = EthTransactionValidatorBuilder::new(MAINNET.clone())
+            .build(MockEthProvider::default(), blob_store.clone());
+        let txpool = Pool::new(
+            validator,
+            CoinbaseTipOrdering::default(),
+            blob_store.clone(),
+            Default::default(),
+        );
+
+        let unmined = vec![B256::with_last_byte(1), B256::with_last_byte(2)];
+        for tx in &unmined {
+            blob_store.insert(*tx, Default::default()).unwrap();
+        }
+
+        // the blobs are kept until the persisted transactions were reinserted
+        let transactions_reloaded = TransactionsReloaded::default();
+        let delete = tokio::spawn(delete_unreferenced_blobs(
+            txpool.clone(),
+            unmined.clone(),
+            Some(transactions_reloaded.clone()),
+        ));
+        tokio::task::yield_now().await;
+        assert_eq!(blob_store.blobs_len(), 2);
+
+        transactions_reloaded.notify();
+        delete.await.unwrap();
+        assert_eq!(blob_store.blobs_len(), 0);
+    }
 
     #[test]
     fn changed_acc_entry() {
@@ -682,29 +1225,36 @@ mod tests {
     const EXTENSION: &str = "rlp";
     const FILENAME: &str = "test_transactions_backup";
 
-    #[tokio::test(flavor = "multi_thread")]
-    async fn test_save_local_txs_backup() {
-        let temp_dir = tempfile::tempdir().unwrap();
-        let transactions_path = temp_dir.path().join(FILENAME).with_extension(EXTENSION);
+    /// Returns a signed mainnet transaction, and a validator and blob store for pools that accept
+    /// it.
+    fn transaction_with_validator() -> (
+        EthPooledTransaction,
+        EthTransactionValidator<MockEthProvider, EthPooledTransaction>,
+        InMemoryBlobStore,
+    ) {
         let tx_bytes = hex!("02f87201830655c2808505ef61f08482565f94388c818ca8b9251b393131c08a736a67ccb192978801049e39c4b5b1f580c001a01764ace353514e8abdfb92446de356b260e3c1225b73fc4c8876a6258d12a129a04f02294aa61ca7676061cd99f29275491218b4754b46a0248e5e42bc5091f507");
         let tx = PooledTransactionsElement::decode_enveloped(tx_bytes.into()).unwrap();
-        let provider = MockEthProvider::default();
         let transaction = EthPooledTransaction::from_recovered_pooled_transaction(
             tx.try_into_ecrecovered().unwrap(),
         );
-        let tx_to_cmp = transaction.clone();
+        let provider = MockEthProvider::default();
         let sender = hex!("1f9090aaE28b8a3dCeaDf281B0F12828e676c326").into();
         provider.add_account(sender, ExtendedAccount::new(42, U256::MAX));
         let blob_store = InMemoryBlobStore::default();
         let validator = EthTransactionValidatorBuilder::new(MAINNET.clone())
             .build(provider, blob_store.clone());
+        (transaction, validator, blob_store)
+    }
 
-        let txpool = Pool::new(
-            validator.clone(),
-            CoinbaseTipOrdering::default(),
-            blob_store.clone(),
-            Default::default(),
-        );
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_save_local_txs_backup() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let transactions_path = temp_dir.path().join(FILENAME).with_extension(EXTENSION);
+        let (transaction, validator, blob_store) = transaction_with_validator();
+        let tx_to_cmp = transaction.clone();
+
+        let txpool =
+            Pool::new(validator, CoinbaseTipOrdering::default(), blob_store, Default::default());
 
         txpool.add_transaction(TransactionOrigin::Local, transaction.clone()).await.unwrap();
 
@@ -731,4 +1281,88 @@ mod tests {
 
         temp_dir.close().unwrap();
     }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_pool_journal_roundtrip() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let journal_path = temp_dir.path().join("txpool-journal");
+        let (transaction, validator, blob_store) = transaction_with_validator();
+        let new_pool = || {
+            Pool::new(
+                validator.clone(),
+                CoinbaseTipOrdering::default(),
+                blob_store.clone(),
+                Default::default(),
+            )
+        };
+
+        let txpool = new_pool();
+        txpool.add_transaction(TransactionOrigin::External, transaction.clone()).await.unwrap();
+        save_pool_journal(&txpool, &TokioTaskExecutor::default(), &journal_path).await;
+
+        let data = fs::read(&journal_path).unwrap();
+        assert_eq!(data[0], POOL_JOURNAL_VERSION);
+
+        let txpool = new_pool();
+        load_and_reinsert_journal(txpool.clone(), &journal_path).await.unwrap();
+        let restored = txpool.get(transaction.hash()).expect("transaction is restored");
+        assert_eq!(restored.origin, TransactionOrigin::External);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_pool_journal_loads_local_txs_backup() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let transactions_path = temp_dir.path().join(FILENAME).with_extension(EXTENSION);
+        let journal_path = temp_dir.path().join("txpool-journal");
+        let (transaction, validator, blob_store) = transaction_with_validator();
+        let txpool =
+            Pool::new(validator, CoinbaseTipOrdering::default(), blob_store, Default::default());
+
+        // a backup of the previous run, without the journal
+        let mut buf = alloy_rlp::BytesMut::new();
+        alloy_rlp::encode_list(&[transaction.to_recovered_transaction().into_signed()], &mut buf);
+        fs::write(&transactions_path, buf).unwrap();
+
+        let transactions_reloaded = TransactionsReloaded::default();
+        let config = PoolJournalConfig::new(journal_path.clone())
+            .with_local_txs_backup(transactions_path.clone())
+            .with_transactions_reloaded(transactions_reloaded.clone());
+        let manager = TaskManager::new(tokio::runtime::Handle::current());
+        manager.executor().spawn_critical_with_graceful_shutdown_signal("test task", |shutdown| {
+            journal_pool_transactions_task(
+                shutdown,
+                txpool.clone(),
+                TokioTaskExecutor::default(),
+                config,
+            )
+        });
+        transactions_reloaded.wait().await;
+
+        let restored = txpool.get(transaction.hash()).expect("transaction is restored");
+        assert_eq!(restored.origin, TransactionOrigin::Local);
+        // the backup is only loaded once
+        assert!(!transactions_path.exists());
+
+        // the transaction is journaled on shutdown
+        manager.graceful_shutdown();
+        let data = fs::read(&journal_path).unwrap();
+        let entries: Vec<PoolJournalEntry> = alloy_rlp::Decodable::decode(&mut &data[1..]).unwrap();
+        assert_eq!(entries.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_pool_journal_unsupported_version() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let journal_path = temp_dir.path().join("txpool-journal");
+        let mut data = vec![POOL_JOURNAL_VERSION + 1];
+        alloy_rlp::encode_list::<PoolJournalEntry, PoolJournalEntry>(&[], &mut data);
+        fs::write(&journal_path, data).unwrap();
+
+        let txpool = crate::test_utils::testing_pool();
+        assert!(matches!(
+            load_and_reinsert_journal(txpool, &journal_path).await,
+            Err(TransactionsBackupError::UnsupportedJournalVersion(version))
+                if version == POOL_JOURNAL_VERSION + 1
+        ));
+    }
 }
@@ -70,6 +70,8 @@ pub struct MaintainPoolMetrics {
     pub(crate) reinserted_transactions: Counter,
     /// Number of transactions finalized blob transactions we were tracking.
     pub(crate) deleted_tracked_finalized_blobs: Counter,
+    /// Number of transactions evicted because they exceeded their lifetime.
+    pub(crate) evicted_expired_transactions: Counter,
 }
 
 impl MaintainPoolMetrics {
@@ -88,6 +90,11 @@ impl MaintainPoolMetrics {
         self.deleted_tracked_finalized_blobs.increment(count as u64);
     }
 
+    #[inline]
+    pub(crate) fn inc_evicted_expired_transactions(&self, count: usize) {
+        self.evicted_expired_transactions.increment(count as u64);
+    }
+
     #[inline]
     pub(crate) fn inc_drift(&self) {
         self.drift_count.increment(1);
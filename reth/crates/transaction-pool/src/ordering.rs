@@ -39,6 +39,28 @@ pub trait TransactionOrdering: Send + Sync + 'static {
         transaction: &Self::Transaction,
         base_fee: u64,
     ) -> Priority<Self::PriorityValue>;
+
+    /// Returns the priority score for the given transaction, if the blob fee of the block the
+    /// transaction is included in is known.
+    ///
+    /// By default, the blob fee is ignored and this returns the [priority](Self::priority).
+    fn priority_with_blob_fee(
+        &self,
+        transaction: &Self::Transaction,
+        base_fee: u64,
+        _blob_fee: u128,
+    ) -> Priority<Self::PriorityValue> {
+        self.priority(transaction, base_fee)
+    }
+
+    /// Returns whether the [priority](Self::priority_with_blob_fee) of a transaction depends on the
+    /// blob fee.
+    ///
+    /// If this returns `false`, the pool doesn't recalculate the priority of blob transactions for
+    /// a given blob fee.
+    fn uses_blob_fee(&self) -> bool {
+        false
+    }
 }
 
 /// Default ordering for the pool.
@@ -79,3 +101,233 @@ impl<T> Clone for CoinbaseTipOrdering<T> {
         Self::default()
     }
 }
+
+/// First-come-first-served ordering.
+///
+/// All transactions have the same priority, so transactions are ordered by the time they were
+/// added to the pending sub-pool.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct FirstComeFirstServedOrdering<T>(PhantomData<T>);
+
+impl<T> TransactionOrdering for FirstComeFirstServedOrdering<T>
+where
+    T: PoolTransaction + 'static,
+{
+    type PriorityValue = U256;
+    type Transaction = T;
+
+    fn priority(
+        &self,
+        _transaction: &Self::Transaction,
+        _base_fee: u64,
+    ) -> Priority<Self::PriorityValue> {
+        Priority::Value(U256::ZERO)
+    }
+}
+
+impl<T> Default for FirstComeFirstServedOrdering<T> {
+    fn default() -> Self {
+        Self(Default::default())
+    }
+}
+
+impl<T> Clone for FirstComeFirstServedOrdering<T> {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+/// Ordering that accounts for the blob fee when ranking blob transactions.
+///
+/// Like [CoinbaseTipOrdering], transactions are ordered by their coinbase tip. If the blob fee is
+/// known, blob transactions whose max fee per blob gas doesn't cover it can't be included and have
+/// no priority.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct BlobFeeAwareOrdering<T>(PhantomData<T>);
+
+impl<T> TransactionOrdering for BlobFeeAwareOrdering<T>
+where
+    T: PoolTransaction + 'static,
+{
+    type PriorityValue = U256;
+    type Transaction = T;
+
+    fn priority(
+        &self,
+        transaction: &Self::Transaction,
+        base_fee: u64,
+    ) -> Priority<Self::PriorityValue> {
+        CoinbaseTipOrdering::default().priority(transaction, base_fee)
+    }
+
+    fn priority_with_blob_fee(
+        &self,
+        transaction: &Self::Transaction,
+        base_fee: u64,
+        blob_fee: u128,
+    ) -> Priority<Self::PriorityValue> {
+        if transaction.is_eip4844() &&
+            transaction.max_fee_per_blob_gas().is_some_and(|max_fee| max_fee < blob_fee)
+        {
+            return Priority::None
+        }
+        self.priority(transaction, base_fee)
+    }
+
+    fn uses_blob_fee(&self) -> bool {
+        true
+    }
+}
+
+impl<T> Default for BlobFeeAwareOrdering<T> {
+    fn default() -> Self {
+        Self(Default::default())
+    }
+}
+
+impl<T> Clone for BlobFeeAwareOrdering<T> {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+/// The built-in [TransactionOrdering]s, which can be selected at runtime.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum TransactionOrderingKind {
+    /// Orders transactions by their coinbase tip
+    #[default]
+    CoinbaseTip,
+    /// Orders transactions by the time they became pending
+    #[cfg_attr(feature = "clap", value(name = "fcfs"))]
+    FirstComeFirstServed,
+    /// Orders transactions by their coinbase tip, and excludes blob transactions that don't cover
+    /// the blob fee
+    BlobFeeAware,
+}
+
+/// A [TransactionOrdering] that uses the built-in ordering of its [TransactionOrderingKind].
+///
+/// This has the same [PriorityValue](TransactionOrdering::PriorityValue) for all orderings, so the
+/// ordering of a pool can be configured without changing its type.
+#[derive(Debug)]
+pub struct ConfiguredOrdering<T> {
+    /// The built-in ordering to use.
+    kind: TransactionOrderingKind,
+    _marker: PhantomData<T>,
+}
+
+impl<T> ConfiguredOrdering<T> {
+    /// Creates a new ordering that uses the given built-in ordering.
+    pub const fn new(kind: TransactionOrderingKind) -> Self {
+        Self { kind, _marker: PhantomData }
+    }
+
+    /// Returns the built-in ordering that's used.
+    pub const fn kind(&self) -> TransactionOrderingKind {
+        self.kind
+    }
+}
+
+impl<T> TransactionOrdering for ConfiguredOrdering<T>
+where
+    T: PoolTransaction + 'static,
+{
+    type PriorityValue = U256;
+    type Transaction = T;
+
+    fn priority(
+        &self,
+        transaction: &Self::Transaction,
+        base_fee: u64,
+    ) -> Priority<Self::PriorityValue> {
+        match self.kind {
+            TransactionOrderingKind::CoinbaseTip => {
+                CoinbaseTipOrdering::default().priority(transaction, base_fee)
+            }
+            TransactionOrderingKind::FirstComeFirstServed => {
+                FirstComeFirstServedOrdering::default().priority(transaction, base_fee)
+            }
+            TransactionOrderingKind::BlobFeeAware => {
+                BlobFeeAwareOrdering::default().priority(transaction, base_fee)
+            }
+        }
+    }
+
+    fn priority_with_blob_fee(
+        &self,
+        transaction: &Self::Transaction,
+        base_fee: u64,
+        blob_fee: u128,
+    ) -> Priority<Self::PriorityValue> {
+        match self.kind {
+            TransactionOrderingKind::CoinbaseTip => CoinbaseTipOrdering::default()
+                .priority_with_blob_fee(transaction, base_fee, blob_fee),
+            TransactionOrderingKind::FirstComeFirstServed => FirstComeFirstServedOrdering::default(
+            )
+            .priority_with_blob_fee(transaction, base_fee, blob_fee),
+            TransactionOrderingKind::BlobFeeAware => BlobFeeAwareOrdering::default()
+                .priority_with_blob_fee(transaction, base_fee, blob_fee),
+        }
+    }
+
+    fn uses_blob_fee(&self) -> bool {
+        self.kind == TransactionOrderingKind::BlobFeeAware
+    }
+}
+
+impl<T> Default for ConfiguredOrdering<T> {
+    fn default() -> Self {
+        Self::new(TransactionOrderingKind::default())
+    }
+}
+
+impl<T> Clone for ConfiguredOrdering<T> {
+    fn clone(&self) -> Self {
+        Self::new(self.kind)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MockTransaction;
+
+    #[test]
+    fn blob_fee_aware_priority() {
+        let ordering = BlobFeeAwareOrdering::default();
+        let tx = MockTransaction::eip4844()
+            .with_priority_fee(100)
+            .with_max_fee(1_000)
+            .with_blob_fee(50)
+            .with_gas_limit(21_000);
+
+        // the priority is the coinbase tip per gas
+        assert_eq!(ordering.priority(&tx, 0), Priority::Value(U256::from(100)));
+        assert_eq!(ordering.priority(&tx, 950), Priority::Value(U256::from(50)));
+        assert_eq!(ordering.priority_with_blob_fee(&tx, 0, 50), Priority::Value(U256::from(100)));
+        // the blob fee isn't covered
+        assert_eq!(ordering.priority_with_blob_fee(&tx, 0, 51), Priority::None);
+
+        // the blob fee doesn't affect other transactions
+        let tx = MockTransaction::eip1559()
+            .with_priority_fee(100)
+            .with_max_fee(1_000)
+            .with_gas_limit(50_000);
+        assert_eq!(
+            ordering.priority_with_blob_fee(&tx, 0, 10_000),
+            Priority::Value(U256::from(100))
+        );
+    }
+
+    #[test]
+    fn configured_ordering_uses_blob_fee() {
+        let ordering = ConfiguredOrdering::<MockTransaction>::default();
+        assert!(!ordering.uses_blob_fee());
+        let ordering =
+            ConfiguredOrdering::<MockTransaction>::new(TransactionOrderingKind::BlobFeeAware);
+        assert!(ordering.uses_blob_fee());
+    }
+}
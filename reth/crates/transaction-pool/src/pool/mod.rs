@@ -669,6 +669,24 @@ where
         removed
     }
 
+    /// Removes all transactions that exceeded their lifetime in the queued and basefee sub-pools.
+    pub(crate) fn remove_expired_transactions(&self) -> Vec<TxHash> {
+        let removed = self.pool.write().remove_expired(Instant::now());
+        if removed.is_empty() {
+            return Vec::new()
+        }
+
+        {
+            let mut listener = self.event_listener.write();
+            removed.iter().for_each(|tx| listener.discarded(tx.hash()));
+        }
+
+        // delete any blobs associated with removed blob transactions
+        self.delete_discarded_blobs(removed.iter());
+
+        removed.into_iter().map(|tx| *tx.hash()).collect()
+    }
+
     /// Removes all transactions that are present in the pool.
     pub(crate) fn retain_unknown<A: HandleAnnouncement>(&self, announcement: &mut A)
     where
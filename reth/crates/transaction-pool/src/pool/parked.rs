@@ -8,6 +8,7 @@ use std::{
     collections::{BTreeMap, BTreeSet, BinaryHeap},
     ops::{Bound::Unbounded, Deref},
     sync::Arc,
+    time::Instant,
 };
 
 /// A pool of transactions that are currently parked and are waiting for external changes (e.g.
@@ -58,7 +59,11 @@ impl<T: ParkedOrd> ParkedPool<T> {
         self.size_of += tx.size();
 
         // update or create sender entry
-        let transaction = ParkedPoolTransaction { submission_id, transaction: tx.into() };
+        let transaction = ParkedPoolTransaction {
+            submission_id,
+            parked_at: Instant::now(),
+            transaction: tx.into(),
+        };
 
         self.by_id.insert(id, transaction.clone());
         self.best.insert(transaction);
@@ -71,6 +76,14 @@ impl<T: ParkedOrd> ParkedPool<T> {
         self.by_id.values().map(|tx| tx.transaction.clone().into())
     }
 
+    /// Returns an iterator over all transactions in the pool together with the time they were
+    /// parked in this pool.
+    pub(crate) fn all_with_parked_at(
+        &self,
+    ) -> impl Iterator<Item = (Arc<ValidPoolTransaction<T::Transaction>>, Instant)> + '_ {
+        self.by_id.values().map(|tx| (tx.transaction.clone().into(), tx.parked_at))
+    }
+
     /// Removes the transaction from the pool
     pub(crate) fn remove_transaction(
         &mut self,
@@ -289,13 +302,19 @@ impl<T: ParkedOrd> Default for ParkedPool<T> {
 struct ParkedPoolTransaction<T: ParkedOrd> {
     /// Identifier that tags when transaction was submitted in the pool.
     submission_id: u64,
+    /// When the transaction was moved into this pool.
+    parked_at: Instant,
     /// Actual transaction.
     transaction: T,
 }
 
 impl<T: ParkedOrd> Clone for ParkedPoolTransaction<T> {
     fn clone(&self) -> Self {
-        Self { submission_id: self.submission_id, transaction: self.transaction.clone() }
+        Self {
+            submission_id: self.submission_id,
+            parked_at: self.parked_at,
+            transaction: self.transaction.clone(),
+        }
     }
 }
 
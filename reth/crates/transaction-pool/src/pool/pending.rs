@@ -115,9 +115,33 @@ impl<T: TransactionOrdering> PendingPool<T> {
         }
     }
 
-    /// Same as `best` but only returns transactions that satisfy the given basefee.
-    pub(crate) fn best_with_basefee(&self, base_fee: u64) -> BestTransactionsWithBasefee<T> {
-        BestTransactionsWithBasefee { best: self.best(), base_fee }
+    /// Same as `best` but ranks blob transactions with the given blob fee, if any.
+    ///
+    /// See also [TransactionOrdering::priority_with_blob_fee].
+    pub(crate) fn best_with_blob_fee(
+        &self,
+        base_fee: u64,
+        blob_fee: Option<u128>,
+    ) -> BestTransactions<T> {
+        let mut best = self.best();
+        self.reprioritize_blob_transactions(&mut best, base_fee, blob_fee);
+        best
+    }
+
+    /// Same as `best_with_blob_fee` but only returns transactions that satisfy the given basefee.
+    ///
+    /// The priorities of the transactions were computed with the `pool_base_fee` the pool was
+    /// last updated with, so blob transactions are ranked with it as well.
+    pub(crate) fn best_with_basefee(
+        &self,
+        base_fee: u64,
+        pool_base_fee: u64,
+        blob_fee: Option<u128>,
+    ) -> BestTransactionsWithBasefee<T> {
+        BestTransactionsWithBasefee {
+            best: self.best_with_blob_fee(pool_base_fee, blob_fee),
+            base_fee,
+        }
     }
 
     /// Same as `best` but also includes the given unlocked transactions.
@@ -134,6 +158,7 @@ impl<T: TransactionOrdering> PendingPool<T> {
         &self,
         unlocked: Vec<Arc<ValidPoolTransaction<T::Transaction>>>,
         base_fee: u64,
+        blob_fee: Option<u128>,
     ) -> BestTransactions<T> {
         let mut best = self.best();
         let mut submission_id = self.submission_id;
@@ -149,9 +174,35 @@ impl<T: TransactionOrdering> PendingPool<T> {
             best.all.insert(tx_id, transaction);
         }
 
+        self.reprioritize_blob_transactions(&mut best, base_fee, blob_fee);
+
         best
     }
 
+    /// Recalculates the priority of all blob transactions of the iterator with the given blob fee.
+    ///
+    /// This is a no-op if there's no blob fee or the ordering doesn't use it.
+    fn reprioritize_blob_transactions(
+        &self,
+        best: &mut BestTransactions<T>,
+        base_fee: u64,
+        blob_fee: Option<u128>,
+    ) {
+        let Some(blob_fee) = blob_fee.filter(|_| self.ordering.uses_blob_fee()) else { return };
+        for tx in best.all.values_mut().filter(|tx| tx.transaction.is_eip4844()) {
+            // the priority is part of the key of the independent set
+            let is_independent = best.independent.remove(&*tx);
+            tx.priority = self.ordering.priority_with_blob_fee(
+                &tx.transaction.transaction,
+                base_fee,
+                blob_fee,
+            );
+            if is_independent {
+                best.independent.insert(tx.clone());
+            }
+        }
+    }
+
     /// Returns an iterator over all transactions in the pool
     pub(crate) fn all(
         &self,
@@ -587,6 +638,7 @@ impl<T: TransactionOrdering> Ord for PendingTransaction<T> {
 mod tests {
     use super::*;
     use crate::{
+        ordering::{BlobFeeAwareOrdering, FirstComeFirstServedOrdering},
         test_utils::{MockOrdering, MockTransaction, MockTransactionFactory, MockTransactionSet},
         PoolTransaction,
     };
@@ -811,4 +863,68 @@ mod tests {
             pending.into_iter().map(|tx| (tx.sender(), tx.nonce())).collect::<HashSet<_>>();
         assert_eq!(pending, expected_pending);
     }
+
+    #[test]
+    fn best_with_blob_fee_respects_ordering() {
+        let mut f = MockTransactionFactory::default();
+        let mut pool = PendingPool::new(BlobFeeAwareOrdering::default());
+
+        let tx = f.validated_arc(
+            MockTransaction::eip1559()
+                .with_priority_fee(50)
+                .with_max_fee(1_000)
+                .with_gas_limit(21_000),
+        );
+        let blob_tx = f.validated_arc(
+            MockTransaction::eip4844()
+                .with_priority_fee(100)
+                .with_max_fee(1_000)
+                .with_blob_fee(20)
+                .with_gas_limit(21_000),
+        );
+        pool.add_transaction(tx.clone(), 0);
+        pool.add_transaction(blob_tx.clone(), 0);
+
+        // without a blob fee, blob transactions are ranked by their tip
+        let best = pool.best_with_blob_fee(0, None).map(|tx| *tx.hash()).collect::<Vec<_>>();
+        assert_eq!(best, vec![*blob_tx.hash(), *tx.hash()]);
+
+        // the blob fee is covered
+        let best = pool.best_with_blob_fee(0, Some(20)).map(|tx| *tx.hash()).collect::<Vec<_>>();
+        assert_eq!(best, vec![*blob_tx.hash(), *tx.hash()]);
+
+        // the blob fee isn't covered
+        let best = pool.best_with_blob_fee(0, Some(25)).map(|tx| *tx.hash()).collect::<Vec<_>>();
+        assert_eq!(best, vec![*tx.hash(), *blob_tx.hash()]);
+
+        let best = pool.best_with_basefee(0, 0, Some(25)).map(|tx| *tx.hash()).collect::<Vec<_>>();
+        assert_eq!(best, vec![*tx.hash(), *blob_tx.hash()]);
+
+        // with a higher base fee, blob transactions are ranked with the base fee of the pool like
+        // all other transactions, otherwise the blob transaction's tip would drop to 40
+        let best =
+            pool.best_with_basefee(960, 0, Some(20)).map(|tx| *tx.hash()).collect::<Vec<_>>();
+        assert_eq!(best, vec![*blob_tx.hash(), *tx.hash()]);
+    }
+
+    #[test]
+    fn first_come_first_served_across_senders() {
+        let mut f = MockTransactionFactory::default();
+        let mut pool = PendingPool::new(FirstComeFirstServedOrdering::default());
+
+        let a = MockTransaction::eip1559().with_priority_fee(1).with_max_fee(1_000);
+        let txs = [
+            a.clone(),
+            MockTransaction::eip1559().with_priority_fee(100).with_max_fee(1_000),
+            a.next(),
+            MockTransaction::eip1559().with_priority_fee(50).with_max_fee(1_000),
+        ];
+        for tx in &txs {
+            pool.add_transaction(f.validated_arc(tx.clone()), 0);
+        }
+
+        // transactions are returned in submission order, regardless of their tip and sender
+        let best = pool.best().map(|tx| *tx.hash()).collect::<Vec<_>>();
+        assert_eq!(best, txs.iter().map(|tx| *tx.hash()).collect::<Vec<_>>());
+    }
 }
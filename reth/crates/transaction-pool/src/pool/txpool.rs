@@ -31,6 +31,7 @@ use std::{
     fmt,
     ops::Bound::{Excluded, Unbounded},
     sync::Arc,
+    time::Instant,
 };
 use tracing::trace;
 
@@ -299,15 +300,24 @@ impl<T: TransactionOrdering> TxPool<T> {
         best_transactions_attributes: BestTransactionsAttributes,
     ) -> Box<dyn crate::traits::BestTransactions<Item = Arc<ValidPoolTransaction<T::Transaction>>>>
     {
+        // blob transactions are ranked with the given blob fee, if any
+        let blob_fee = best_transactions_attributes.blob_fee.map(u128::from);
         match best_transactions_attributes.basefee.cmp(&self.all_transactions.pending_fees.base_fee)
         {
             Ordering::Equal => {
                 // fee unchanged, nothing to shift
-                Box::new(self.best_transactions())
+                Box::new(
+                    self.pending_pool
+                        .best_with_blob_fee(best_transactions_attributes.basefee, blob_fee),
+                )
             }
             Ordering::Greater => {
                 // base fee increased, we only need to enforce this on the pending pool
-                Box::new(self.pending_pool.best_with_basefee(best_transactions_attributes.basefee))
+                Box::new(self.pending_pool.best_with_basefee(
+                    best_transactions_attributes.basefee,
+                    self.all_transactions.pending_fees.base_fee,
+                    blob_fee,
+                ))
             }
             Ordering::Less => {
                 // base fee decreased, we need to move transactions from the basefee pool to the
@@ -318,6 +328,7 @@ impl<T: TransactionOrdering> TxPool<T> {
                 Box::new(self.pending_pool.best_with_unlocked(
                     unlocked_with_blob,
                     self.all_transactions.pending_fees.base_fee,
+                    blob_fee,
                 ))
             }
         }
@@ -816,6 +827,42 @@ impl<T: TransactionOrdering> TxPool<T> {
         removed
     }
 
+    /// Removes all transactions that have been in the queued or basefee sub-pool for longer than
+    /// the configured lifetime, together with their descendants.
+    ///
+    /// Local transactions are exempt, unless local exemptions are disabled.
+    ///
+    /// This returns all transactions that were removed from the entire pool.
+    pub(crate) fn remove_expired(
+        &mut self,
+        now: Instant,
+    ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        let lifetime = self.config.max_queued_lifetime;
+        // transactions expire by the time they were parked, so that a transaction that was
+        // demoted from the pending pool isn't evicted right away
+        let expired = self
+            .queued_pool
+            .all_with_parked_at()
+            .chain(self.basefee_pool.all_with_parked_at())
+            .filter(|(tx, parked_at)| {
+                !self.config.local_transactions_config.is_local(tx.origin, tx.sender()) &&
+                    now.saturating_duration_since(*parked_at) > lifetime
+            })
+            .map(|(tx, _)| *tx.id())
+            .collect::<Vec<_>>();
+
+        let mut removed = Vec::new();
+        for id in expired {
+            // the transaction was already removed if it's a descendant of an expired transaction
+            if let Some(tx) = self.remove_transaction(&id) {
+                removed.push(tx);
+                self.remove_descendants(&id, &mut removed);
+            }
+        }
+
+        removed
+    }
+
     /// Number of transactions in the entire pool
     pub(crate) fn len(&self) -> usize {
         self.all_transactions.len()
@@ -1827,6 +1874,7 @@ mod tests {
         traits::TransactionOrigin,
         SubPoolLimit,
     };
+    use std::time::Duration;
 
     #[test]
     fn test_insert_blob() {
@@ -2818,4 +2866,71 @@ mod tests {
             assert!(pool.size().blob <= blob_limit.max_txs);
         }
     }
+
+    #[test]
+    fn remove_expired_queued_transactions() {
+        let mut f = MockTransactionFactory::default();
+        let mut pool = TxPool::new(MockOrdering::default(), Default::default());
+
+        let pending = MockTransaction::eip1559();
+        pool.add_transaction(f.validated(pending.clone()), U256::MAX, 0).unwrap();
+
+        // nonce gap, so the transaction and its descendant are queued
+        let queued = MockTransaction::eip1559().with_nonce(1);
+        let descendant = queued.next();
+        pool.add_transaction(f.validated(queued.clone()), U256::MAX, 0).unwrap();
+        pool.add_transaction(f.validated(descendant.clone()), U256::MAX, 0).unwrap();
+
+        let local = MockTransaction::eip1559().with_nonce(1);
+        let validated = f.validated_with_origin(TransactionOrigin::Local, local.clone());
+        pool.add_transaction(validated, U256::MAX, 0).unwrap();
+        assert_eq!(pool.size().pending, 1);
+        assert_eq!(pool.size().queued, 3);
+
+        assert!(pool.remove_expired(Instant::now()).is_empty());
+
+        let expired_at = Instant::now() + pool.config.max_queued_lifetime + Duration::from_secs(1);
+        let removed = pool.remove_expired(expired_at);
+        let mut removed = removed.iter().map(|tx| *tx.hash()).collect::<Vec<_>>();
+        removed.sort();
+        let mut expected = vec![queued.get_hash(), descendant.get_hash()];
+        expected.sort();
+        assert_eq!(removed, expected);
+
+        assert!(pool.contains(&pending.get_hash()));
+        assert!(pool.contains(&local.get_hash()));
+        assert_eq!(pool.size().queued, 1);
+        pool.assert_invariants();
+    }
+
+    #[test]
+    fn remove_expired_by_time_parked() {
+        let mut f = MockTransactionFactory::default();
+        let mut pool = TxPool::new(MockOrdering::default(), Default::default());
+        let lifetime = pool.config.max_queued_lifetime;
+
+        // a transaction that was validated long before it's demoted from the pending pool
+        let tx = MockTransaction::eip1559().inc_price_by(10);
+        let mut validated = f.validated(tx.clone());
+        if let Some(validated_at) = Instant::now().checked_sub(lifetime * 2) {
+            validated.timestamp = validated_at;
+        }
+        pool.add_transaction(validated, U256::MAX, 0).unwrap();
+        assert_eq!(pool.size().pending, 1);
+        assert!(pool.remove_expired(Instant::now() + lifetime * 2).is_empty());
+
+        pool.update_basefee((tx.max_fee_per_gas() + 1) as u64);
+        assert_eq!(pool.size().basefee, 1);
+
+        // the transaction was just parked, so it's not expired yet
+        assert!(pool.remove_expired(Instant::now()).is_empty());
+        assert!(pool.contains(&tx.get_hash()));
+
+        let expired_at = Instant::now() + lifetime + Duration::from_secs(1);
+        let removed = pool.remove_expired(expired_at);
+        assert_eq!(removed.len(), 1);
+        assert_eq!(*removed[0].hash(), tx.get_hash());
+        assert_eq!(pool.size().basefee, 0);
+        pool.assert_invariants();
+    }
 }
@@ -396,6 +396,24 @@ pub trait TransactionPoolExt: TransactionPool {
 
     /// Deletes multiple blob sidecars from the blob store
     fn delete_blobs(&self, txs: Vec<B256>);
+
+    /// Returns the transaction hashes of all blob sidecars in the blob store
+    ///
+    /// By default, this returns no hashes.
+    fn blob_store_hashes(&self) -> Result<Vec<B256>, BlobStoreError> {
+        Ok(Vec::new())
+    }
+
+    /// Removes all transactions that have been in the queued or basefee sub-pool for longer than
+    /// the configured [max_queued_lifetime](crate::PoolConfig::max_queued_lifetime).
+    ///
+    /// Returns the hashes of all removed transactions, including descendants of expired
+    /// transactions.
+    ///
+    /// By default, this is a no-op that doesn't remove any transactions.
+    fn remove_expired_transactions(&self) -> Vec<TxHash> {
+        Vec::new()
+    }
 }
 
 /// Determines what kind of new transactions should be emitted by a stream of transactions.
@@ -4,7 +4,10 @@ use crate::{
     blobstore::BlobStore,
     error::{Eip4844PoolTransactionError, InvalidPoolTransactionError},
     traits::TransactionOrigin,
-    validate::{ValidTransaction, ValidationTask, MAX_INIT_CODE_BYTE_SIZE, MAX_TX_INPUT_BYTES},
+    validate::{
+        AdmissionCandidate, AdmissionPolicies, AdmissionPolicy, AdmissionPolicyConfig,
+        ValidTransaction, ValidationTask, MAX_INIT_CODE_BYTE_SIZE, MAX_TX_INPUT_BYTES,
+    },
     EthBlobTransactionSidecar, EthPoolTransaction, LocalTransactionConfig, PoolTransaction,
     TransactionValidationOutcome, TransactionValidationTaskExecutor, TransactionValidator,
 };
@@ -118,6 +121,8 @@ pub(crate) struct EthTransactionValidatorInner<Client, T> {
     kzg_settings: Arc<KzgSettings>,
     /// How to handle [TransactionOrigin::Local](TransactionOrigin) transactions.
     local_transactions_config: LocalTransactionConfig,
+    /// Policies that are evaluated after the stateful validation.
+    admission_policies: AdmissionPolicies,
     /// Marker for the transaction type
     _marker: PhantomData<T>,
 }
@@ -425,6 +430,13 @@ where
             }
         }
 
+        // Apply the admission policies to the otherwise valid transaction
+        if let Err(err) =
+            self.admission_policies.check(&AdmissionCandidate::new(origin, &transaction))
+        {
+            return TransactionValidationOutcome::Invalid(transaction, err.into())
+        }
+
         // Return the valid transaction
         TransactionValidationOutcome::Valid {
             balance: account.balance,
@@ -480,6 +492,8 @@ pub struct EthTransactionValidatorBuilder {
     kzg_settings: Arc<KzgSettings>,
     /// How to handle [TransactionOrigin::Local](TransactionOrigin) transactions.
     local_transactions_config: LocalTransactionConfig,
+    /// Policies that are evaluated after the stateful validation.
+    admission_policies: AdmissionPolicies,
 }
 
 impl EthTransactionValidatorBuilder {
@@ -495,6 +509,7 @@ impl EthTransactionValidatorBuilder {
             additional_tasks: 1,
             kzg_settings: Arc::clone(&MAINNET_KZG_TRUSTED_SETUP),
             local_transactions_config: Default::default(),
+            admission_policies: Default::default(),
 
             // by default all transaction types are allowed
             eip2718: true,
@@ -523,6 +538,19 @@ impl EthTransactionValidatorBuilder {
         self
     }
 
+    /// Appends the admission policy to the policies that are evaluated after the stateful
+    /// validation.
+    pub fn with_admission_policy(mut self, policy: impl AdmissionPolicy + 'static) -> Self {
+        self.admission_policies.push(policy);
+        self
+    }
+
+    /// Appends all policies of the [AdmissionPolicyConfig].
+    pub fn with_admission_policy_config(mut self, config: &AdmissionPolicyConfig) -> Self {
+        self.admission_policies.extend(config.policies());
+        self
+    }
+
     /// Set the Cancun fork.
     pub const fn set_cancun(mut self, cancun: bool) -> Self {
         self.cancun = cancun;
@@ -609,6 +637,7 @@ impl EthTransactionValidatorBuilder {
             minimum_priority_fee,
             kzg_settings,
             local_transactions_config,
+            admission_policies,
             ..
         } = self;
 
@@ -627,6 +656,7 @@ impl EthTransactionValidatorBuilder {
             blob_store: Box::new(blob_store),
             kzg_settings,
             local_transactions_config,
+            admission_policies,
             _marker: Default::default(),
         };
 
@@ -778,6 +808,22 @@ mod tests {
 
         assert!(outcome.is_valid());
 
+        // the transaction is otherwise valid, but rejected by the admission policy
+        let policy_validator = EthTransactionValidatorBuilder::new(MAINNET.clone())
+            .with_admission_policy(crate::validate::MaxInputSize(1024))
+            .build(provider.clone(), blob_store.clone());
+        let outcome =
+            policy_validator.validate_one(TransactionOrigin::External, transaction.clone());
+        assert!(matches!(
+            outcome,
+            TransactionValidationOutcome::Invalid(
+                _,
+                InvalidPoolTransactionError::AdmissionPolicy(
+                    crate::error::AdmissionPolicyError::OversizedInput { limit: 1024, .. }
+                )
+            )
+        ));
+
         let pool =
             Pool::new(validator, CoinbaseTipOrdering::default(), blob_store, Default::default());
 
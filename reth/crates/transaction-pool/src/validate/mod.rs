@@ -13,11 +13,18 @@ use std::{fmt, time::Instant};
 
 mod constants;
 mod eth;
+mod policy;
 mod task;
 
 /// A `TransactionValidator` implementation that validates ethereum transaction.
 pub use eth::*;
 
+/// Admission policies that filter otherwise valid transactions.
+pub use policy::{
+    AdmissionCandidate, AdmissionPolicies, AdmissionPolicy, AdmissionPolicyConfig,
+    DeniedRecipients, MaxInputSize, MinPriorityFeeByType, PrivateWindow,
+};
+
 /// A spawnable task that performs transaction validation.
 pub use task::{TransactionValidationTaskExecutor, ValidationTask};
 
@@ -0,0 +1,288 @@
+//! Admission policies that filter transactions before they're admitted into the pool.
+
+use crate::{error::AdmissionPolicyError, traits::TransactionOrigin, PoolTransaction};
+use reth_primitives::{Address, TxHash};
+use std::{
+    collections::{BTreeMap, HashSet},
+    fmt,
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A transaction that's checked against the [AdmissionPolicy]s of the pool.
+///
+/// This is a view of the fields of a [PoolTransaction] that are relevant for admission.
+#[derive(Debug, Clone, Copy)]
+pub struct AdmissionCandidate<'a> {
+    /// Where the transaction originated from.
+    pub origin: TransactionOrigin,
+    /// Hash of the transaction.
+    pub hash: &'a TxHash,
+    /// Sender of the transaction.
+    pub sender: Address,
+    /// Recipient of the transaction, or `None` for contract creations.
+    pub to: Option<Address>,
+    /// Type of the transaction.
+    pub tx_type: u8,
+    /// Input data of the transaction.
+    pub input: &'a [u8],
+    /// The max priority fee per gas for EIP-1559 transactions, and the gas price otherwise.
+    pub priority_fee_or_price: u128,
+}
+
+impl<'a> AdmissionCandidate<'a> {
+    /// Creates the candidate for the given transaction.
+    pub fn new<T: PoolTransaction>(origin: TransactionOrigin, transaction: &'a T) -> Self {
+        Self {
+            origin,
+            hash: transaction.hash(),
+            sender: transaction.sender(),
+            to: transaction.to(),
+            tx_type: transaction.tx_type(),
+            input: transaction.input(),
+            priority_fee_or_price: transaction.priority_fee_or_price(),
+        }
+    }
+}
+
+/// A policy that decides whether a transaction is admitted into the pool.
+///
+/// Policies are evaluated after the stateful validation of the transaction, so they only see
+/// transactions that are otherwise valid.
+pub trait AdmissionPolicy: fmt::Debug + Send + Sync {
+    /// Returns an error if the transaction is rejected by this policy.
+    fn check(&self, candidate: &AdmissionCandidate<'_>) -> Result<(), AdmissionPolicyError>;
+}
+
+/// A chain of [AdmissionPolicy]s, that rejects a transaction if any of its policies rejects it.
+///
+/// Policies are evaluated in order, and the first rejection is returned.
+#[derive(Debug, Clone, Default)]
+pub struct AdmissionPolicies {
+    policies: Vec<Arc<dyn AdmissionPolicy>>,
+}
+
+impl AdmissionPolicies {
+    /// Appends the policy to the chain.
+    pub fn with_policy(mut self, policy: impl AdmissionPolicy + 'static) -> Self {
+        self.push(policy);
+        self
+    }
+
+    /// Appends the policy to the chain.
+    pub fn push(&mut self, policy: impl AdmissionPolicy + 'static) {
+        self.policies.push(Arc::new(policy));
+    }
+
+    /// Appends all policies of the other chain to this chain.
+    pub fn extend(&mut self, other: AdmissionPolicies) {
+        self.policies.extend(other.policies);
+    }
+
+    /// Returns the number of policies in the chain.
+    pub fn len(&self) -> usize {
+        self.policies.len()
+    }
+
+    /// Returns `true` if the chain contains no policies.
+    pub fn is_empty(&self) -> bool {
+        self.policies.is_empty()
+    }
+}
+
+impl AdmissionPolicy for AdmissionPolicies {
+    fn check(&self, candidate: &AdmissionCandidate<'_>) -> Result<(), AdmissionPolicyError> {
+        self.policies.iter().try_for_each(|policy| policy.check(candidate))
+    }
+}
+
+/// Rejects transactions to any of the given recipients.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeniedRecipients(pub HashSet<Address>);
+
+impl AdmissionPolicy for DeniedRecipients {
+    fn check(&self, candidate: &AdmissionCandidate<'_>) -> Result<(), AdmissionPolicyError> {
+        match candidate.to {
+            Some(to) if self.0.contains(&to) => Err(AdmissionPolicyError::DeniedRecipient(to)),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Rejects transactions with input data larger than the given number of bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MaxInputSize(pub usize);
+
+impl AdmissionPolicy for MaxInputSize {
+    fn check(&self, candidate: &AdmissionCandidate<'_>) -> Result<(), AdmissionPolicyError> {
+        let size = candidate.input.len();
+        if size > self.0 {
+            return Err(AdmissionPolicyError::OversizedInput { size, limit: self.0 })
+        }
+        Ok(())
+    }
+}
+
+/// Only admits transactions of the given senders while the window is open.
+///
+/// Outside of the window, transactions of all senders are admitted.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PrivateWindow {
+    /// Senders that are admitted during the window.
+    pub senders: HashSet<Address>,
+    /// Unix timestamp in seconds at which the window opens.
+    pub start: u64,
+    /// Unix timestamp in seconds at which the window closes, exclusive.
+    pub end: u64,
+}
+
+impl PrivateWindow {
+    /// Returns `true` if the window is open at the given unix timestamp in seconds.
+    pub const fn is_open_at(&self, timestamp: u64) -> bool {
+        self.start <= timestamp && timestamp < self.end
+    }
+
+    fn check_at(
+        &self,
+        candidate: &AdmissionCandidate<'_>,
+        timestamp: u64,
+    ) -> Result<(), AdmissionPolicyError> {
+        if self.is_open_at(timestamp) && !self.senders.contains(&candidate.sender) {
+            return Err(AdmissionPolicyError::SenderNotAllowed(candidate.sender))
+        }
+        Ok(())
+    }
+}
+
+impl AdmissionPolicy for PrivateWindow {
+    fn check(&self, candidate: &AdmissionCandidate<'_>) -> Result<(), AdmissionPolicyError> {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        self.check_at(candidate, now)
+    }
+}
+
+/// Rejects transactions with a priority fee below the minimum configured for their type.
+///
+/// For transactions without a priority fee, the gas price is compared instead.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MinPriorityFeeByType(pub BTreeMap<u8, u128>);
+
+impl AdmissionPolicy for MinPriorityFeeByType {
+    fn check(&self, candidate: &AdmissionCandidate<'_>) -> Result<(), AdmissionPolicyError> {
+        let Some(&minimum) = self.0.get(&candidate.tx_type) else { return Ok(()) };
+        let tip = candidate.priority_fee_or_price;
+        if tip < minimum {
+            return Err(AdmissionPolicyError::TipBelowMinimum {
+                tx_type: candidate.tx_type,
+                tip,
+                minimum,
+            })
+        }
+        Ok(())
+    }
+}
+
+/// Configuration of the built-in [AdmissionPolicy]s, as loaded from a config file.
+///
+/// Policies that aren't configured are not applied.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, deny_unknown_fields))]
+pub struct AdmissionPolicyConfig {
+    /// Recipients that transactions are rejected for.
+    pub denied_recipients: HashSet<Address>,
+    /// Max size of the input data of a transaction in bytes.
+    pub max_input_size: Option<usize>,
+    /// Window during which only the given senders are admitted.
+    pub private_window: Option<PrivateWindow>,
+    /// Minimum priority fee per transaction type.
+    pub min_priority_fee: BTreeMap<u8, u128>,
+}
+
+impl AdmissionPolicyConfig {
+    /// Returns the chain of all configured policies.
+    pub fn policies(&self) -> AdmissionPolicies {
+        let mut policies = AdmissionPolicies::default();
+        if !self.denied_recipients.is_empty() {
+            policies.push(DeniedRecipients(self.denied_recipients.clone()));
+        }
+        if let Some(max_input_size) = self.max_input_size {
+            policies.push(MaxInputSize(max_input_size));
+        }
+        if let Some(private_window) = &self.private_window {
+            policies.push(private_window.clone());
+        }
+        if !self.min_priority_fee.is_empty() {
+            policies.push(MinPriorityFeeByType(self.min_priority_fee.clone()));
+        }
+        policies
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MockTransaction;
+    use reth_primitives::{Bytes, EIP1559_TX_TYPE_ID};
+
+    #[test]
+    fn policy_chain_rejects_first() {
+        let tx = MockTransaction::eip1559().with_input(Bytes::from(vec![0; 100]));
+        let candidate = AdmissionCandidate::new(TransactionOrigin::External, &tx);
+
+        let config = AdmissionPolicyConfig {
+            max_input_size: Some(50),
+            min_priority_fee: BTreeMap::from([(EIP1559_TX_TYPE_ID, u128::MAX)]),
+            ..Default::default()
+        };
+        let policies = config.policies();
+        assert_eq!(policies.len(), 2);
+        assert!(matches!(
+            policies.check(&candidate),
+            Err(AdmissionPolicyError::OversizedInput { size: 100, limit: 50 })
+        ));
+
+        let policies = AdmissionPolicyConfig { max_input_size: Some(100), ..config }.policies();
+        assert!(matches!(
+            policies.check(&candidate),
+            Err(AdmissionPolicyError::TipBelowMinimum { tx_type: EIP1559_TX_TYPE_ID, .. })
+        ));
+
+        assert!(AdmissionPolicies::default().check(&candidate).is_ok());
+    }
+
+    #[test]
+    fn denied_recipients() {
+        let tx = MockTransaction::eip1559();
+        let candidate = AdmissionCandidate::new(TransactionOrigin::Local, &tx);
+        let to = candidate.to.unwrap();
+
+        let policy = DeniedRecipients(HashSet::from([to]));
+        assert!(matches!(
+            policy.check(&candidate),
+            Err(AdmissionPolicyError::DeniedRecipient(denied)) if denied == to
+        ));
+        assert!(DeniedRecipients(HashSet::from([Address::random()])).check(&candidate).is_ok());
+    }
+
+    #[test]
+    fn private_window() {
+        let tx = MockTransaction::eip1559();
+        let candidate = AdmissionCandidate::new(TransactionOrigin::External, &tx);
+
+        let window = PrivateWindow { senders: HashSet::new(), start: 10, end: 20 };
+        assert!(window.check_at(&candidate, 9).is_ok());
+        assert!(matches!(
+            window.check_at(&candidate, 10),
+            Err(AdmissionPolicyError::SenderNotAllowed(_))
+        ));
+        assert!(window.check_at(&candidate, 20).is_ok());
+
+        let window = PrivateWindow { senders: HashSet::from([tx.get_sender()]), ..window };
+        assert!(window.check_at(&candidate, 10).is_ok());
+    }
+}
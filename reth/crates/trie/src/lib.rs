@@ -43,6 +43,10 @@ pub mod proof;
 mod trie;
 pub use trie::{StateRoot, StorageRoot};
 
+/// The parallel state root computation.
+mod parallel;
+pub use parallel::ParallelStateRoot;
+
 /// Buffer for trie updates.
 pub mod updates;
 
@@ -0,0 +1,275 @@
+use crate::{
+    hashed_cursor::{HashedCursorFactory, HashedPostStateCursorFactory},
+    node_iter::{AccountNode, AccountNodeIter},
+    prefix_set::PrefixSetMut,
+    trie_cursor::TrieCursorFactory,
+    updates::{TrieKey, TrieUpdates},
+    walker::TrieWalker,
+    HashedPostState, HashedPostStateSorted, StateRootError, StorageRoot, StorageRootError,
+};
+use alloy_rlp::{BufMut, Encodable};
+use rayon::prelude::*;
+use reth_db::{database::Database, transaction::DbTx, DatabaseError};
+use reth_primitives::{
+    trie::{HashBuilder, Nibbles, TrieAccount},
+    B256,
+};
+use std::collections::{HashMap, HashSet};
+use tracing::{debug, trace};
+
+/// ParallelStateRoot is used to compute the root node of a state trie on top of the database
+/// state overlaid with the [HashedPostState].
+///
+/// The roots of the changed storage tries are computed concurrently on the global [rayon] thread
+/// pool, each over its own read-only transaction, before the account trie is walked. The storage
+/// roots of the accounts without storage changes are computed during the walk, same as in
+/// [StateRoot](crate::StateRoot). The resulting root and trie updates are identical to the ones
+/// returned by [HashedPostState::state_root_with_updates].
+///
+/// Since every computation opens a new transaction, only the committed database state is visible
+/// to the calculator. It must not be used for the changes pending in a write transaction, and
+/// since the transactions are not subject to the long read transaction safety, it should not be
+/// used while a write transaction is open in parallel.
+#[derive(Debug)]
+pub struct ParallelStateRoot<DB> {
+    /// The database to open the read-only transactions on.
+    db: DB,
+    /// The sorted hashed post state overlaying the database state.
+    hashed_state: HashedPostStateSorted,
+    /// A set of account prefixes that have changed.
+    changed_account_prefixes: PrefixSetMut,
+    /// A map containing storage changes with the hashed address as key and a set of storage key
+    /// prefixes as the value. The sets are frozen on the threads computing the storage roots.
+    changed_storage_prefixes: HashMap<B256, PrefixSetMut>,
+    /// A set containing keys of accounts that were destroyed.
+    destroyed_accounts: HashSet<B256>,
+}
+
+impl<DB> ParallelStateRoot<DB> {
+    /// Create a new [ParallelStateRoot] instance for the database and the hashed post state.
+    pub fn new(db: DB, hashed_state: HashedPostState) -> Self {
+        let (changed_account_prefixes, changed_storage_prefixes) =
+            hashed_state.construct_prefix_sets_mut();
+        let hashed_state = hashed_state.into_sorted();
+        let destroyed_accounts = hashed_state.destroyed_accounts();
+        Self {
+            db,
+            hashed_state,
+            changed_account_prefixes,
+            changed_storage_prefixes,
+            destroyed_accounts,
+        }
+    }
+}
+
+impl<DB: Database> ParallelStateRoot<DB> {
+    /// Calculates the state root.
+    ///
+    /// # Returns
+    ///
+    /// The state root hash.
+    pub fn root(self) -> Result<B256, StateRootError> {
+        let (root, _) = self.calculate(false)?;
+        Ok(root)
+    }
+
+    /// Calculates the state root and collects the trie updates in the process.
+    ///
+    /// # Returns
+    ///
+    /// The state root hash and the trie updates.
+    pub fn root_with_updates(self) -> Result<(B256, TrieUpdates), StateRootError> {
+        self.calculate(true)
+    }
+
+    fn calculate(self, retain_updates: bool) -> Result<(B256, TrieUpdates), StateRootError> {
+        let db = &self.db;
+        let hashed_state = &self.hashed_state;
+
+        debug!(target: "trie::parallel_state_root", storage_tries = self.changed_storage_prefixes.len(), "calculating storage roots");
+        let mut storage_roots = self
+            .changed_storage_prefixes
+            .into_par_iter()
+            .map(|(hashed_address, prefix_set)| {
+                let tx = read_tx(db)?;
+                let calculator = StorageRoot::from_tx_hashed(&tx, hashed_address)
+                    .with_hashed_cursor_factory(HashedPostStateCursorFactory::new(
+                        &tx,
+                        hashed_state,
+                    ))
+                    .with_changed_prefixes(prefix_set.freeze());
+                Ok((hashed_address, storage_root(calculator, retain_updates)?))
+            })
+            .collect::<Result<HashMap<_, _>, StateRootError>>()?;
+
+        trace!(target: "trie::parallel_state_root", "calculating state root");
+        let tx = read_tx(db)?;
+        let trie_cursor_factory = &tx;
+        let hashed_cursor_factory = HashedPostStateCursorFactory::new(&tx, hashed_state);
+
+        let walker = TrieWalker::new(
+            trie_cursor_factory.account_trie_cursor()?,
+            self.changed_account_prefixes.freeze(),
+        )
+        .with_updates(retain_updates);
+        let mut account_node_iter =
+            AccountNodeIter::new(walker, hashed_cursor_factory.hashed_account_cursor()?);
+        let mut hash_builder = HashBuilder::default().with_updates(retain_updates);
+
+        let mut trie_updates = TrieUpdates::default();
+        let mut account_rlp = Vec::with_capacity(128);
+        while let Some(node) = account_node_iter.try_next()? {
+            match node {
+                AccountNode::Branch(node) => {
+                    hash_builder.add_branch(node.key, node.value, node.children_are_in_trie);
+                }
+                AccountNode::Leaf(hashed_address, account) => {
+                    let (storage_root, updates) = match storage_roots.remove(&hashed_address) {
+                        Some(result) => result,
+                        // The storage of the account has not changed, so the root is computed
+                        // from the existing trie nodes.
+                        None => storage_root(
+                            StorageRoot::new_hashed(
+                                trie_cursor_factory,
+                                hashed_cursor_factory.clone(),
+                                hashed_address,
+                            ),
+                            retain_updates,
+                        )?,
+                    };
+                    trie_updates.extend(updates.into_iter());
+
+                    let account = TrieAccount::from((account, storage_root));
+
+                    account_rlp.clear();
+                    account.encode(&mut account_rlp as &mut dyn BufMut);
+
+                    hash_builder.add_leaf(Nibbles::unpack(hashed_address), &account_rlp);
+                }
+            }
+        }
+
+        let root = hash_builder.root();
+
+        let (_, walker_updates) = account_node_iter.walker.split();
+        let (_, hash_builder_updates) = hash_builder.split();
+
+        trie_updates.extend(walker_updates.into_iter());
+        trie_updates.extend_with_account_updates(hash_builder_updates);
+        trie_updates
+            .extend_with_deletes(self.destroyed_accounts.into_iter().map(TrieKey::StorageTrie));
+
+        trace!(target: "trie::parallel_state_root", ?root, "calculated state root");
+        Ok((root, trie_updates))
+    }
+}
+
+/// Opens a read-only transaction for the state root calculation.
+///
+/// State root calculation can take a while, so the long read transaction safety is disabled. See
+/// <https://github.com/paradigmxyz/reth/issues/6168>.
+fn read_tx<DB: Database>(db: &DB) -> Result<DB::TX, DatabaseError> {
+    let mut tx = db.tx()?;
+    tx.disable_long_read_transaction_safety();
+    Ok(tx)
+}
+
+/// Calculates the storage root with the given calculator, collecting the trie updates only if
+/// `retain_updates` is set.
+fn storage_root<T, H>(
+    calculator: StorageRoot<T, H>,
+    retain_updates: bool,
+) -> Result<(B256, TrieUpdates), StorageRootError>
+where
+    T: TrieCursorFactory,
+    H: HashedCursorFactory,
+{
+    if retain_updates {
+        let (root, _, updates) = calculator.root_with_updates()?;
+        Ok((root, updates))
+    } else {
+        Ok((calculator.root()?, TrieUpdates::default()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{HashedStorage, StateRoot};
+    use proptest::{prelude::ProptestConfig, proptest};
+    use reth_db::{tables, transaction::DbTxMut};
+    use reth_primitives::{keccak256, Account, Address, StorageEntry, U256};
+    use reth_provider::test_utils::create_test_provider_factory;
+    use std::collections::BTreeMap;
+
+    type State = BTreeMap<Address, (Account, BTreeMap<B256, U256>)>;
+
+    #[test]
+    fn parallel_root_matches_sequential() {
+        proptest!(ProptestConfig::with_cases(10), |(state: State)| {
+            let factory = create_test_provider_factory();
+            let provider = factory.provider_rw().unwrap();
+            for (address, (account, storage)) in &state {
+                let hashed_address = keccak256(address);
+                provider.tx_ref().put::<tables::HashedAccount>(hashed_address, *account).unwrap();
+                for (slot, value) in storage {
+                    provider
+                        .tx_ref()
+                        .put::<tables::HashedStorage>(
+                            hashed_address,
+                            StorageEntry { key: keccak256(slot), value: *value },
+                        )
+                        .unwrap();
+                }
+            }
+            let (_, updates) = StateRoot::from_tx(provider.tx_ref()).root_with_updates().unwrap();
+            updates.flush(provider.tx_ref()).unwrap();
+            provider.commit().unwrap();
+
+            // Modify every other account, wipe the storage of every third one and destroy the
+            // last one.
+            let mut hashed_state = HashedPostState::default();
+            for (idx, (address, (account, storage))) in state.iter().enumerate() {
+                let hashed_address = keccak256(address);
+                if idx == state.len() - 1 {
+                    hashed_state.accounts.insert(hashed_address, None);
+                    hashed_state.storages.insert(hashed_address, HashedStorage::new(true));
+                    continue
+                }
+                if idx % 2 == 0 {
+                    continue
+                }
+
+                let account = Account { nonce: account.nonce.wrapping_add(1), ..*account };
+                hashed_state.accounts.insert(hashed_address, Some(account));
+                hashed_state.storages.insert(
+                    hashed_address,
+                    HashedStorage::from_iter(
+                        idx % 3 == 0,
+                        storage.iter().enumerate().map(|(slot_idx, (slot, value))| {
+                            let value = if slot_idx % 2 == 0 {
+                                U256::ZERO
+                            } else {
+                                value.wrapping_add(U256::from(1))
+                            };
+                            (keccak256(slot), value)
+                        }),
+                    ),
+                );
+            }
+
+            let provider = factory.provider().unwrap();
+            let expected = hashed_state.state_root_with_updates(provider.tx_ref()).unwrap();
+            assert_eq!(
+                ParallelStateRoot::new(factory.db_ref(), hashed_state.clone())
+                    .root_with_updates()
+                    .unwrap(),
+                expected
+            );
+            assert_eq!(
+                ParallelStateRoot::new(factory.db_ref(), hashed_state).root().unwrap(),
+                expected.0
+            );
+        });
+    }
+}
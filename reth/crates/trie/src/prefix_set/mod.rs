@@ -87,6 +87,12 @@ impl PrefixSetMut {
         self.keys.push(nibbles);
     }
 
+    /// Extends the set with the given `nibbles`.
+    pub fn extend(&mut self, nibbles: impl IntoIterator<Item = Nibbles>) {
+        self.sorted = false;
+        self.keys.extend(nibbles);
+    }
+
     /// Returns the number of elements in the set.
     pub fn len(&self) -> usize {
         self.keys.len()
@@ -12,8 +12,9 @@ use reth_primitives::{
     constants::EMPTY_ROOT_HASH,
     keccak256,
     trie::{AccountProof, HashBuilder, Nibbles, StorageProof, TrieAccount},
-    Address, B256,
+    Address, Bytes, B256,
 };
+use std::collections::{BTreeMap, HashMap};
 
 /// A struct for generating merkle proofs.
 ///
@@ -26,12 +27,49 @@ pub struct Proof<'a, TX, H> {
     tx: &'a TX,
     /// The factory for hashed cursors.
     hashed_cursor_factory: H,
+    /// A set of account prefixes that have changed relative to the stored trie nodes.
+    changed_account_prefixes: PrefixSetMut,
+    /// A map containing storage changes with the hashed address as key and a set of storage key
+    /// prefixes as the value.
+    changed_storage_prefixes: HashMap<B256, PrefixSetMut>,
 }
 
 impl<'a, TX> Proof<'a, TX, &'a TX> {
     /// Create a new [Proof] instance.
     pub fn new(tx: &'a TX) -> Self {
-        Self { tx, hashed_cursor_factory: tx }
+        Self {
+            tx,
+            hashed_cursor_factory: tx,
+            changed_account_prefixes: PrefixSetMut::default(),
+            changed_storage_prefixes: HashMap::default(),
+        }
+    }
+}
+
+impl<'a, TX, H> Proof<'a, TX, H> {
+    /// Set the hashed cursor factory.
+    pub fn with_hashed_cursor_factory<HF>(self, hashed_cursor_factory: HF) -> Proof<'a, TX, HF> {
+        Proof {
+            tx: self.tx,
+            hashed_cursor_factory,
+            changed_account_prefixes: self.changed_account_prefixes,
+            changed_storage_prefixes: self.changed_storage_prefixes,
+        }
+    }
+
+    /// Set the changed account prefixes.
+    ///
+    /// Must be set whenever the hashed cursor factory overlays state that differs from the one
+    /// the stored trie nodes were computed for, so that the stale branch nodes are not reused.
+    pub fn with_changed_account_prefixes(mut self, prefixes: PrefixSetMut) -> Self {
+        self.changed_account_prefixes = prefixes;
+        self
+    }
+
+    /// Set the changed storage prefixes.
+    pub fn with_changed_storage_prefixes(mut self, prefixes: HashMap<B256, PrefixSetMut>) -> Self {
+        self.changed_storage_prefixes = prefixes;
+        self
     }
 }
 
@@ -55,7 +93,7 @@ where
             DatabaseAccountTrieCursor::new(self.tx.cursor_read::<tables::AccountsTrie>()?);
 
         // Create the walker.
-        let mut prefix_set = PrefixSetMut::default();
+        let mut prefix_set = self.changed_account_prefixes.clone();
         prefix_set.insert(target_nibbles.clone());
         let walker = TrieWalker::new(trie_cursor, prefix_set.freeze());
 
@@ -97,6 +135,95 @@ where
         Ok(account_proof)
     }
 
+    /// Generate the account trie nodes on the paths to the given targets.
+    ///
+    /// Returns the retained nodes keyed by their path. Every node whose path is a prefix of any of
+    /// the targets is included, so the result is a merkle proof of all targets at once, and the
+    /// node at a target path, if any, is the entry keyed by that path.
+    pub fn account_multiproof(
+        &self,
+        targets: Vec<Nibbles>,
+    ) -> Result<BTreeMap<Nibbles, Bytes>, StateRootError> {
+        let hashed_account_cursor = self.hashed_cursor_factory.hashed_account_cursor()?;
+        let trie_cursor =
+            DatabaseAccountTrieCursor::new(self.tx.cursor_read::<tables::AccountsTrie>()?);
+
+        let mut prefix_set = self.changed_account_prefixes.clone();
+        prefix_set.extend(targets.clone());
+        let walker = TrieWalker::new(trie_cursor, prefix_set.freeze());
+
+        let mut hash_builder = HashBuilder::default().with_proof_retainer(targets);
+
+        let mut account_rlp = Vec::with_capacity(128);
+        let mut account_node_iter = AccountNodeIter::new(walker, hashed_account_cursor);
+        while let Some(account_node) = account_node_iter.try_next()? {
+            match account_node {
+                AccountNode::Branch(node) => {
+                    hash_builder.add_branch(node.key, node.value, node.children_are_in_trie);
+                }
+                AccountNode::Leaf(hashed_address, account) => {
+                    let storage_root = self.storage_root(hashed_address)?;
+
+                    account_rlp.clear();
+                    let account = TrieAccount::from((account, storage_root));
+                    account.encode(&mut account_rlp as &mut dyn BufMut);
+
+                    hash_builder.add_leaf(Nibbles::unpack(hashed_address), &account_rlp);
+                }
+            }
+        }
+
+        let _ = hash_builder.root();
+
+        Ok(hash_builder.take_proofs())
+    }
+
+    /// Generate the storage trie nodes of the given account on the paths to the given targets.
+    ///
+    /// See [Proof::account_multiproof] for the layout of the result.
+    pub fn storage_multiproof(
+        &self,
+        hashed_address: B256,
+        targets: Vec<Nibbles>,
+    ) -> Result<BTreeMap<Nibbles, Bytes>, StorageRootError> {
+        let mut hashed_storage_cursor = self.hashed_cursor_factory.hashed_storage_cursor()?;
+
+        // short circuit on empty storage
+        if hashed_storage_cursor.is_storage_empty(hashed_address)? {
+            return Ok(BTreeMap::default())
+        }
+
+        let mut prefix_set =
+            self.changed_storage_prefixes.get(&hashed_address).cloned().unwrap_or_default();
+        prefix_set.extend(targets.clone());
+        let trie_cursor = DatabaseStorageTrieCursor::new(
+            self.tx.cursor_dup_read::<tables::StoragesTrie>()?,
+            hashed_address,
+        );
+        let walker = TrieWalker::new(trie_cursor, prefix_set.freeze());
+
+        let mut hash_builder = HashBuilder::default().with_proof_retainer(targets);
+        let mut storage_node_iter =
+            StorageNodeIter::new(walker, hashed_storage_cursor, hashed_address);
+        while let Some(node) = storage_node_iter.try_next()? {
+            match node {
+                StorageNode::Branch(node) => {
+                    hash_builder.add_branch(node.key, node.value, node.children_are_in_trie);
+                }
+                StorageNode::Leaf(hashed_slot, value) => {
+                    hash_builder.add_leaf(
+                        Nibbles::unpack(hashed_slot),
+                        alloy_rlp::encode_fixed_size(&value).as_ref(),
+                    );
+                }
+            }
+        }
+
+        let _ = hash_builder.root();
+
+        Ok(hash_builder.take_proofs())
+    }
+
     /// Compute storage root.
     pub fn storage_root(&self, hashed_address: B256) -> Result<B256, StorageRootError> {
         let (storage_root, _) = self.storage_root_with_proofs(hashed_address, &[])?;
@@ -119,7 +246,10 @@ where
         }
 
         let target_nibbles = proofs.iter().map(|p| p.nibbles.clone()).collect::<Vec<_>>();
-        let prefix_set = PrefixSetMut::from(target_nibbles.clone()).freeze();
+        let mut prefix_set =
+            self.changed_storage_prefixes.get(&hashed_address).cloned().unwrap_or_default();
+        prefix_set.extend(target_nibbles.clone());
+        let prefix_set = prefix_set.freeze();
         let trie_cursor = DatabaseStorageTrieCursor::new(
             self.tx.cursor_dup_read::<tables::StoragesTrie>()?,
             hashed_address,
@@ -164,12 +294,12 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::StateRoot;
+    use crate::{HashedPostState, StateRoot};
     use alloy_chains::Chain;
     use once_cell::sync::Lazy;
     use reth_db::database::Database;
     use reth_interfaces::RethResult;
-    use reth_primitives::{Account, Bytes, ChainSpec, StorageEntry, HOLESKY, MAINNET, U256};
+    use reth_primitives::{Account, ChainSpec, StorageEntry, HOLESKY, MAINNET, U256};
     use reth_provider::{test_utils::create_test_provider_factory, HashingWriter, ProviderFactory};
     use std::{str::FromStr, sync::Arc};
 
@@ -293,6 +423,74 @@ mod tests {
         }
     }
 
+    #[test]
+    fn testspec_account_multiproof() {
+        // Create test database and insert genesis accounts.
+        let factory = create_test_provider_factory();
+        insert_genesis(&factory, TEST_SPEC.clone()).unwrap();
+
+        let targets = [
+            Address::from_str("0x2031f89b3ea8014eb51a78c316e42af3e0d7695f").unwrap(),
+            Address::from_str("0x1ed9b1dd266b607ee278726d324b855a093394a6").unwrap(),
+        ];
+
+        let provider = factory.provider().unwrap();
+        let proof = Proof::new(provider.tx_ref());
+        let multiproof = proof
+            .account_multiproof(
+                targets.iter().map(|target| Nibbles::unpack(keccak256(target))).collect(),
+            )
+            .unwrap();
+
+        // The multiproof is the union of the individual proofs.
+        let mut expected = Vec::new();
+        for target in targets {
+            for node in proof.account_proof(target, &[]).unwrap().proof {
+                if !expected.contains(&node) {
+                    expected.push(node);
+                }
+            }
+        }
+        assert_eq!(multiproof.len(), expected.len());
+        assert!(expected.iter().all(|node| multiproof.values().any(|value| value == node)));
+
+        // The root node is keyed by the empty path.
+        assert_eq!(multiproof.get(&Nibbles::default()), expected.first());
+    }
+
+    #[test]
+    fn holesky_deposit_contract_storage_multiproof() {
+        // Create test database and insert genesis accounts.
+        let factory = create_test_provider_factory();
+        insert_genesis(&factory, HOLESKY.clone()).unwrap();
+
+        let target = Address::from_str("0x4242424242424242424242424242424242424242").unwrap();
+        let slots = Vec::from([B256::with_last_byte(0x22), B256::with_last_byte(0x24)]);
+
+        let provider = factory.provider().unwrap();
+        let proof = Proof::new(provider.tx_ref());
+        let account_proof = proof.account_proof(target, &slots).unwrap();
+        let multiproof = proof
+            .storage_multiproof(
+                keccak256(target),
+                slots.iter().map(|slot| Nibbles::unpack(keccak256(slot))).collect(),
+            )
+            .unwrap();
+
+        for storage_proof in account_proof.storage_proofs {
+            assert!(storage_proof
+                .proof
+                .iter()
+                .all(|node| multiproof.values().any(|value| value == node)));
+        }
+
+        // Accounts without storage have no storage trie nodes.
+        let empty = proof
+            .storage_multiproof(keccak256(Address::ZERO), Vec::from([Nibbles::default()]))
+            .unwrap();
+        assert!(empty.is_empty());
+    }
+
     #[test]
     fn testspec_empty_storage_proof() {
         // Create test database and insert genesis accounts.
@@ -312,6 +510,35 @@ mod tests {
         }
     }
 
+    #[test]
+    fn testspec_overlay_account_proof() {
+        let target = Address::from_str("0x1ed9b1dd266b607ee278726d324b855a093394a6").unwrap();
+
+        // Create a chain spec with the balance of the target account modified.
+        let mut modified_spec = (**TEST_SPEC).clone();
+        let genesis_account = modified_spec.genesis.alloc.get_mut(&target).unwrap();
+        genesis_account.balance = U256::from(1);
+        let modified_account = Account::from_genesis_account(genesis_account.clone());
+
+        // Create test databases with the original and modified genesis accounts.
+        let factory = create_test_provider_factory();
+        insert_genesis(&factory, TEST_SPEC.clone()).unwrap();
+        let modified_factory = create_test_provider_factory();
+        insert_genesis(&modified_factory, Arc::new(modified_spec)).unwrap();
+
+        // Overlay the modification on top of the original database.
+        let mut overlay = HashedPostState::default();
+        overlay.accounts.insert(keccak256(target), Some(modified_account));
+
+        let provider = factory.provider().unwrap();
+        let overlay_proof = overlay.account_proof(provider.tx_ref(), target, &[]).unwrap();
+
+        let modified_provider = modified_factory.provider().unwrap();
+        let expected_proof =
+            Proof::new(modified_provider.tx_ref()).account_proof(target, &[]).unwrap();
+        similar_asserts::assert_eq!(overlay_proof, expected_proof);
+    }
+
     #[test]
     fn mainnet_genesis_account_proof() {
         // Create test database and insert genesis accounts.
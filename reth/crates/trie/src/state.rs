@@ -1,8 +1,9 @@
 use crate::{
     hashed_cursor::HashedPostStateCursorFactory,
     prefix_set::{PrefixSet, PrefixSetMut},
+    proof::Proof,
     updates::TrieUpdates,
-    StateRoot, StateRootError,
+    StateRoot, StateRootError, StorageRoot, StorageRootError,
 };
 use reth_db::{
     cursor::DbCursorRO,
@@ -12,8 +13,10 @@ use reth_db::{
     DatabaseError,
 };
 use reth_primitives::{
-    keccak256, revm::compat::into_reth_acc, trie::Nibbles, Account, Address, BlockNumber, B256,
-    U256,
+    keccak256,
+    revm::compat::into_reth_acc,
+    trie::{AccountProof, Nibbles},
+    Account, Address, BlockNumber, StorageEntry, B256, U256,
 };
 use revm::db::BundleAccount;
 use std::{
@@ -63,12 +66,30 @@ impl HashedPostState {
         tx: &TX,
         range: RangeInclusive<BlockNumber>,
     ) -> Result<Self, DatabaseError> {
+        let account_changesets = tx
+            .cursor_read::<tables::AccountChangeSet>()?
+            .walk_range(range.clone())?
+            .collect::<Result<Vec<_>, _>>()?;
+        let storage_changesets = tx
+            .cursor_read::<tables::StorageChangeSet>()?
+            .walk_range(BlockNumberAddress::range(range))?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self::from_reverts(account_changesets, storage_changesets))
+    }
+
+    /// Apply account and storage changeset entries, ordered by block, to hashed state in reverse.
+    ///
+    /// Like [HashedPostState::from_revert_range], but for changesets that are not read from the
+    /// database, e.g. because they have been moved to snapshots.
+    pub fn from_reverts(
+        account_changesets: impl IntoIterator<Item = (BlockNumber, AccountBeforeTx)>,
+        storage_changesets: impl IntoIterator<Item = (BlockNumberAddress, StorageEntry)>,
+    ) -> Self {
         let mut this = Self::default();
 
         // Iterate over account changesets and record value before first occurring account change.
-        let mut account_changesets_cursor = tx.cursor_read::<tables::AccountChangeSet>()?;
-        for entry in account_changesets_cursor.walk_range(range.clone())? {
-            let (_, AccountBeforeTx { address, info }) = entry?;
+        for (_, AccountBeforeTx { address, info }) in account_changesets {
             let hashed_address = keccak256(address); // TODO: cache hashes?
             if let hash_map::Entry::Vacant(entry) = this.accounts.entry(hashed_address) {
                 entry.insert(info);
@@ -77,9 +98,7 @@ impl HashedPostState {
 
         // Iterate over storage changesets and record value before first occurring storage change.
         let mut storages = HashMap::<Address, HashMap<B256, U256>>::default();
-        let mut storage_changesets_cursor = tx.cursor_read::<tables::StorageChangeSet>()?;
-        for entry in storage_changesets_cursor.walk_range(BlockNumberAddress::range(range))? {
-            let (BlockNumberAddress((_, address)), storage) = entry?;
+        for (BlockNumberAddress((_, address)), storage) in storage_changesets {
             let account_storage = storages.entry(address).or_default();
             if let hash_map::Entry::Vacant(entry) = account_storage.entry(storage.key) {
                 entry.insert(storage.value);
@@ -97,7 +116,7 @@ impl HashedPostState {
             this.storages.insert(keccak256(address), hashed_storage);
         }
 
-        Ok(this)
+        this
     }
 
     /// Extend this hashed post state with contents of another.
@@ -145,6 +164,19 @@ impl HashedPostState {
     /// The prefix sets contain the hashed account and storage keys that have been changed in the
     /// post state.
     pub fn construct_prefix_sets(&self) -> (PrefixSet, HashMap<B256, PrefixSet>) {
+        let (account_prefix_set, storage_prefix_sets) = self.construct_prefix_sets_mut();
+        (
+            account_prefix_set.freeze(),
+            storage_prefix_sets
+                .into_iter()
+                .map(|(hashed_address, prefix_set)| (hashed_address, prefix_set.freeze()))
+                .collect(),
+        )
+    }
+
+    /// Construct mutable [PrefixSetMut]s from hashed post state.
+    /// See [Self::construct_prefix_sets] for more info.
+    pub fn construct_prefix_sets_mut(&self) -> (PrefixSetMut, HashMap<B256, PrefixSetMut>) {
         // Populate account prefix set.
         let mut account_prefix_set = PrefixSetMut::with_capacity(self.accounts.len());
         for hashed_address in self.accounts.keys() {
@@ -160,10 +192,10 @@ impl HashedPostState {
             for hashed_slot in hashed_storage.storage.keys() {
                 prefix_set.insert(Nibbles::unpack(hashed_slot));
             }
-            storage_prefix_sets.insert(*hashed_address, prefix_set.freeze());
+            storage_prefix_sets.insert(*hashed_address, prefix_set);
         }
 
-        (account_prefix_set.freeze(), storage_prefix_sets)
+        (account_prefix_set, storage_prefix_sets)
     }
 
     /// Calculate the state root for this [HashedPostState].
@@ -219,6 +251,26 @@ impl HashedPostState {
             .with_changed_storage_prefixes(storage_prefix_set)
             .root_with_updates()
     }
+
+    /// Generates the merkle proof for the target address and storage slots on top of the database
+    /// state overlaid with this [HashedPostState].
+    ///
+    /// The changed keys are added to the prefix sets of the proof generator so that the stale
+    /// intermediate nodes stored in the database are recomputed from the overlaid leaves.
+    pub fn account_proof<TX: DbTx>(
+        &self,
+        tx: &TX,
+        address: Address,
+        slots: &[B256],
+    ) -> Result<AccountProof, StateRootError> {
+        let sorted = self.clone().into_sorted();
+        let (account_prefix_set, storage_prefix_sets) = self.construct_prefix_sets_mut();
+        Proof::new(tx)
+            .with_hashed_cursor_factory(HashedPostStateCursorFactory::new(tx, &sorted))
+            .with_changed_account_prefixes(account_prefix_set)
+            .with_changed_storage_prefixes(storage_prefix_sets)
+            .account_proof(address, slots)
+    }
 }
 
 /// Representation of in-memory hashed storage.
@@ -294,6 +346,30 @@ impl HashedPostStateSorted {
             .with_hashed_cursor_factory(hashed_cursor_factory)
             .with_destroyed_accounts(self.destroyed_accounts())
     }
+
+    /// Calculates the storage root of the account with the given hashed address on top of the
+    /// database state overlaid with this sorted state.
+    pub fn storage_root<TX: DbTx>(
+        &self,
+        tx: &TX,
+        hashed_address: B256,
+    ) -> Result<B256, StorageRootError> {
+        let mut prefix_set = PrefixSetMut::default();
+        if let Some(storage) = self.storages.get(&hashed_address) {
+            prefix_set.extend(
+                storage
+                    .non_zero_valued_slots
+                    .iter()
+                    .map(|(hashed_slot, _)| hashed_slot)
+                    .chain(storage.zero_valued_slots.iter())
+                    .map(Nibbles::unpack),
+            );
+        }
+        StorageRoot::from_tx_hashed(tx, hashed_address)
+            .with_hashed_cursor_factory(HashedPostStateCursorFactory::new(tx, self))
+            .with_changed_prefixes(prefix_set.freeze())
+            .root()
+    }
 }
 
 /// Sorted hashed storage optimized for iterating during state trie calculation.